@@ -0,0 +1,89 @@
+//! Benchmarks on synthetic boards. Run with `cargo bench -p kuk`.
+
+use axum::body::Body;
+use axum::http::Request;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use kuk::cli::render_list;
+use kuk::dev::generate_board;
+use kuk::model::CardSort;
+use kuk::storage::Store;
+
+const SIZES: &[usize] = &[100, 1_000, 10_000];
+
+fn store_with_board(cards: usize) -> (TempDir, Store) {
+    let dir = TempDir::new().unwrap();
+    let store = Store::new(dir.path());
+    store.init().unwrap();
+    store
+        .save_board(&generate_board("bench", cards, 1))
+        .unwrap();
+    (dir, store)
+}
+
+fn store_load_save(c: &mut Criterion) {
+    let mut group = c.benchmark_group("store");
+    for &cards in SIZES {
+        let (_dir, store) = store_with_board(cards);
+        let board = store.load_board("bench").unwrap();
+        group.bench_with_input(BenchmarkId::new("load", cards), &cards, |b, _| {
+            b.iter(|| store.load_board("bench").unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("load_summary", cards), &cards, |b, _| {
+            b.iter(|| store.load_board_summary("bench").unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("save", cards), &cards, |b, _| {
+            b.iter(|| store.save_board(&board).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn list_rendering(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list");
+    for &cards in SIZES {
+        let board = generate_board("bench", cards, 1);
+        group.bench_with_input(BenchmarkId::new("render", cards), &board, |b, board| {
+            b.iter(|| render_list(board, CardSort::default(), false))
+        });
+    }
+    group.finish();
+}
+
+fn server_throughput(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("server");
+    for &cards in &[100, 1_000] {
+        let (_dir, store) = store_with_board(cards);
+        let app = kuk::server::router(store, false);
+        group.bench_with_input(BenchmarkId::new("get_board", cards), &cards, |b, _| {
+            b.iter(|| {
+                rt.block_on(async {
+                    let req = Request::get("/v1/boards/bench")
+                        .body(Body::empty())
+                        .unwrap();
+                    let resp = app.clone().oneshot(req).await.unwrap();
+                    axum::body::to_bytes(resp.into_body(), usize::MAX)
+                        .await
+                        .unwrap()
+                })
+            })
+        });
+    }
+    group.bench_function("health", |b| {
+        let (_dir, store) = store_with_board(0);
+        let app = kuk::server::router(store, false);
+        b.iter(|| {
+            rt.block_on(async {
+                let req = Request::get("/health").body(Body::empty()).unwrap();
+                app.clone().oneshot(req).await.unwrap()
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, store_load_save, list_rendering, server_throughput);
+criterion_main!(benches);
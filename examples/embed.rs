@@ -0,0 +1,41 @@
+//! Embedding kuk through `kuk::api`.
+//!
+//! ```text
+//! cargo run --example embed -- <repo> [card title]
+//! ```
+//!
+//! Prints every active card on the default board and, given a title, adds a
+//! card labelled `from-example` first.
+
+use kuk::api::{CardBuilder, CardQuery, Project};
+
+fn main() -> kuk::api::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let root = args.next().unwrap_or_else(|| ".".into());
+    let project = Project::open(&root)?;
+
+    let mut board = project.default_board()?;
+    if let Some(title) = args.next() {
+        let card = board.add(CardBuilder::new(title).label("from-example"))?;
+        println!("Added {} to {}", card.title, card.column);
+        board.save()?;
+    }
+
+    for column in board.columns() {
+        println!("{}", column.name);
+        for card in board.column_cards(&column.name) {
+            println!("  {}", card.title);
+        }
+    }
+
+    // Cards on every board, one board in memory at a time
+    let labelled = CardQuery {
+        label: Some("from-example".into()),
+        ..CardQuery::default()
+    };
+    for item in project.cards(labelled)? {
+        let (board, card) = item?;
+        println!("{board}: {}", card.title);
+    }
+    Ok(())
+}
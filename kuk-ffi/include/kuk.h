@@ -0,0 +1,51 @@
+/*
+ * C bindings for kuk boards (kuk-ffi).
+ *
+ * Every function except kuk_version returns a NUL-terminated JSON string
+ * that the caller owns and must release with kuk_string_free:
+ *
+ *   {"ok": <result>}     on success
+ *   {"error": "<text>"}  on failure
+ *
+ * Boards use the same JSON as .kuk/boards/<name>.json. Changes passed to
+ * kuk_board_apply are objects tagged by "op"; cards are named by id or
+ * 1-based display number:
+ *
+ *   {"op": "add", "title": "...", "column": "todo", "labels": [], "assignee": null, "description": null}
+ *   {"op": "move", "card": "1", "to": "done"}
+ *   {"op": "hoist", "card": "1"}
+ *   {"op": "demote", "card": "1"}
+ *   {"op": "archive", "card": "1"}
+ *   {"op": "delete", "card": "1"}
+ *   {"op": "assign", "card": "1", "user": "sam"}
+ *   {"op": "label", "card": "1", "add": ["bug"], "remove": []}
+ */
+
+#ifndef KUK_H
+#define KUK_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Load a board from the project at repo; board may be NULL for the default
+ * board. ok: the board. */
+char *kuk_board_load(const char *repo, const char *board);
+
+/* Write board_json to the project at repo. ok: the board name. */
+char *kuk_board_save(const char *repo, const char *board_json);
+
+/* Apply a change in memory. ok: {"board": <new board>, "card": <card>}. */
+char *kuk_board_apply(const char *board_json, const char *op_json);
+
+/* Release a string returned by this library. NULL is ignored. */
+void kuk_string_free(char *s);
+
+/* Library version. Static; do not free. */
+const char *kuk_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KUK_H */
@@ -0,0 +1,166 @@
+//! C ABI. Every function returns a JSON string owned by the caller, to be
+//! released with `kuk_string_free`: `{"ok": ...}` on success or
+//! `{"error": "..."}` on failure. See `include/kuk.h`.
+
+use std::ffi::{CStr, CString, c_char};
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use kuk::model::Board;
+use kuk::storage::Store;
+
+use crate::ops;
+
+/// Load a board from the kuk project at `repo`. A null `board` loads the
+/// project's default board.
+///
+/// # Safety
+/// `repo` must be a valid NUL-terminated string; `board` must be one or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kuk_board_load(repo: *const c_char, board: *const c_char) -> *mut c_char {
+    respond((|| {
+        let store = Store::new(unsafe { path_arg(repo, "repo") }?);
+        let name = match unsafe { opt_str_arg(board, "board") }? {
+            Some(name) => name.to_string(),
+            None => {
+                store
+                    .load_config()
+                    .map_err(|e| e.to_string())?
+                    .default_board
+            }
+        };
+        let board = store.load_board(&name).map_err(|e| e.to_string())?;
+        serde_json::to_value(board).map_err(|e| e.to_string())
+    })())
+}
+
+/// Write `board_json` to the kuk project at `repo`, replacing the board of
+/// the same name.
+///
+/// # Safety
+/// Both arguments must be valid NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kuk_board_save(
+    repo: *const c_char,
+    board_json: *const c_char,
+) -> *mut c_char {
+    respond((|| {
+        let store = Store::new(unsafe { path_arg(repo, "repo") }?);
+        let board: Board = serde_json::from_str(unsafe { str_arg(board_json, "board_json") }?)
+            .map_err(|e| format!("Invalid board: {e}"))?;
+        store.save_board(&board).map_err(|e| e.to_string())?;
+        Ok(json!(board.name))
+    })())
+}
+
+/// Apply a change (see `ops::Op`) to `board_json` in memory. Returns the new
+/// board and the card the change touched: `{"ok": {"board": ..., "card": ...}}`.
+///
+/// # Safety
+/// Both arguments must be valid NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kuk_board_apply(
+    board_json: *const c_char,
+    op_json: *const c_char,
+) -> *mut c_char {
+    respond((|| {
+        let board = unsafe { str_arg(board_json, "board_json") }?;
+        let op = unsafe { str_arg(op_json, "op_json") }?;
+        let out = ops::apply_json(board, op)?;
+        serde_json::from_str(&out).map_err(|e| e.to_string())
+    })())
+}
+
+/// Release a string returned by any `kuk_*` function. Null is ignored.
+///
+/// # Safety
+/// `s` must come from this library and not have been freed already.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kuk_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// The library version, as a static string the caller must not free.
+#[unsafe(no_mangle)]
+pub extern "C" fn kuk_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+fn respond(result: Result<Value, String>) -> *mut c_char {
+    let body = match result {
+        Ok(value) => json!({ "ok": value }),
+        Err(error) => json!({ "error": error }),
+    };
+    // JSON escapes control characters, so there is never an interior NUL
+    CString::new(body.to_string())
+        .expect("JSON has no NUL bytes")
+        .into_raw()
+}
+
+unsafe fn opt_str_arg<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>, String> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map(Some)
+        .map_err(|_| format!("{name} is not valid UTF-8"))
+}
+
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, String> {
+    unsafe { opt_str_arg(ptr, name) }?.ok_or_else(|| format!("{name} is null"))
+}
+
+unsafe fn path_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a Path, String> {
+    unsafe { str_arg(ptr, name) }.map(Path::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Call through the C ABI and parse the response.
+    fn call(f: impl FnOnce() -> *mut c_char) -> Value {
+        let ptr = f();
+        let text = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+        unsafe { kuk_string_free(ptr) };
+        serde_json::from_str(&text).unwrap()
+    }
+
+    #[test]
+    fn load_apply_save_round_trip() {
+        let dir = TempDir::new().unwrap();
+        Store::new(dir.path()).init().unwrap();
+        let repo = CString::new(dir.path().to_str().unwrap()).unwrap();
+
+        let loaded = call(|| unsafe { kuk_board_load(repo.as_ptr(), std::ptr::null()) });
+        let board = CString::new(loaded["ok"].to_string()).unwrap();
+        let op = CString::new(r#"{"op": "add", "title": "From C"}"#).unwrap();
+        let applied = call(|| unsafe { kuk_board_apply(board.as_ptr(), op.as_ptr()) });
+        assert_eq!(applied["ok"]["card"]["title"], "From C");
+
+        let changed = CString::new(applied["ok"]["board"].to_string()).unwrap();
+        let saved = call(|| unsafe { kuk_board_save(repo.as_ptr(), changed.as_ptr()) });
+        assert_eq!(saved["ok"], "default");
+        let board = Store::new(dir.path()).load_board("default").unwrap();
+        assert_eq!(board.cards[0].title, "From C");
+    }
+
+    #[test]
+    fn errors_come_back_as_json() {
+        let dir = TempDir::new().unwrap();
+        let repo = CString::new(dir.path().to_str().unwrap()).unwrap();
+        let missing = call(|| unsafe { kuk_board_load(repo.as_ptr(), std::ptr::null()) });
+        assert!(missing["error"].as_str().unwrap().contains("kuk init"));
+        let null = call(|| unsafe { kuk_board_apply(std::ptr::null(), std::ptr::null()) });
+        assert_eq!(null["error"], "board_json is null");
+        assert_eq!(
+            unsafe { CStr::from_ptr(kuk_version()) }.to_str().unwrap(),
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! Bindings that let editor plugins and browser builds reuse kuk's board
+//! logic. Boards and changes cross the boundary as JSON.
+//!
+//! - `c` (default): a C ABI for loading, saving and changing boards in a kuk
+//!   project, declared in `include/kuk.h`. Build the shared library with
+//!   `cargo build -p kuk-ffi --release`.
+//! - `wasm`: wasm-bindgen exports for changing boards in memory. Build with
+//!   `cargo build -p kuk-ffi --target wasm32-unknown-unknown
+//!   --no-default-features --features wasm` and
+//!   `RUSTFLAGS='--cfg getrandom_backend="wasm_js"'`.
+
+#[cfg(feature = "c")]
+pub mod c;
+pub mod ops;
+#[cfg(feature = "wasm")]
+pub mod wasm;
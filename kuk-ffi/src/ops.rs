@@ -0,0 +1,224 @@
+//! Board changes shared by the C and wasm bindings. Both take the board and
+//! the change as JSON, so callers only need a JSON library.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use kuk::model::{Board, Card};
+
+/// A change to a board. Cards are named by id or 1-based display number, as
+/// on the command line.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Op {
+    Add {
+        title: String,
+        /// Defaults to the board's first column.
+        column: Option<String>,
+        #[serde(default)]
+        labels: Vec<String>,
+        assignee: Option<String>,
+        description: Option<String>,
+    },
+    Move {
+        card: String,
+        to: String,
+    },
+    Hoist {
+        card: String,
+    },
+    Demote {
+        card: String,
+    },
+    Archive {
+        card: String,
+    },
+    Delete {
+        card: String,
+    },
+    Assign {
+        card: String,
+        user: Option<String>,
+    },
+    Label {
+        card: String,
+        #[serde(default)]
+        add: Vec<String>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+}
+
+/// The board after a change, and the card it touched.
+#[derive(Debug, Serialize)]
+pub struct Applied {
+    pub board: Board,
+    pub card: Card,
+}
+
+/// Apply `op` to `board`. Errors are messages for the caller to show.
+pub fn apply(board: &mut Board, op: Op) -> Result<Card, String> {
+    let resolve = |board: &Board, card: &str| {
+        board
+            .resolve_card_id(card)
+            .ok_or_else(|| format!("Card not found: {card}"))
+    };
+    let check_column = |board: &Board, column: &str| {
+        if board.has_column(column) {
+            Ok(())
+        } else {
+            Err(format!("Column not found: {column}"))
+        }
+    };
+
+    let id = match op {
+        Op::Add {
+            title,
+            column,
+            labels,
+            assignee,
+            description,
+        } => {
+            let column = match column.or_else(|| board.columns.first().map(|c| c.name.clone())) {
+                Some(column) => column,
+                None => return Err(format!("Board {} has no columns", board.name)),
+            };
+            check_column(board, &column)?;
+            board
+                .check_labels(&labels)
+                .map_err(|l| format!("Unknown label: {l}"))?;
+            let mut card = Card::new(title, &column);
+            card.order = board.next_order(&column);
+            card.labels = labels;
+            card.assignee = assignee;
+            card.description = description;
+            board.add_card(card).id.clone()
+        }
+        Op::Move { card, to } => {
+            check_column(board, &to)?;
+            let id = resolve(board, &card)?;
+            board.move_card(&id, &to);
+            id
+        }
+        Op::Hoist { card } => {
+            let id = resolve(board, &card)?;
+            board.hoist_card(&id);
+            id
+        }
+        Op::Demote { card } => {
+            let id = resolve(board, &card)?;
+            board.demote_card(&id);
+            id
+        }
+        Op::Archive { card } => {
+            let id = resolve(board, &card)?;
+            update(board, &id, |c| {
+                c.archive();
+            });
+            id
+        }
+        Op::Delete { card } => {
+            let id = resolve(board, &card)?;
+            let pos = board.cards.iter().position(|c| c.id == id);
+            return Ok(board.cards.remove(pos.expect("resolved ids exist")));
+        }
+        Op::Assign { card, user } => {
+            let id = resolve(board, &card)?;
+            update(board, &id, |c| {
+                c.set_assignee(user);
+            });
+            id
+        }
+        Op::Label { card, add, remove } => {
+            board
+                .check_labels(&add)
+                .map_err(|l| format!("Unknown label: {l}"))?;
+            let id = resolve(board, &card)?;
+            update(board, &id, |c| {
+                for label in &remove {
+                    c.remove_label(label);
+                }
+                for label in &add {
+                    c.add_label(label);
+                }
+            });
+            id
+        }
+    };
+    Ok(board.find_card(&id).expect("card exists").clone())
+}
+
+/// Parse `board_json` and `op_json`, apply the change and return the result
+/// as JSON.
+pub fn apply_json(board_json: &str, op_json: &str) -> Result<String, String> {
+    let mut board: Board =
+        serde_json::from_str(board_json).map_err(|e| format!("Invalid board: {e}"))?;
+    let op: Op = serde_json::from_str(op_json).map_err(|e| format!("Invalid op: {e}"))?;
+    let card = apply(&mut board, op)?;
+    serde_json::to_string(&Applied { board, card }).map_err(|e| e.to_string())
+}
+
+fn update(board: &mut Board, id: &str, change: impl FnOnce(&mut Card)) {
+    let card = board.find_card_mut(id).expect("resolved ids exist");
+    change(card);
+    card.updated_at = Utc::now();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn run(board: &Board, op: Value) -> Result<Value, String> {
+        let board = serde_json::to_string(board).unwrap();
+        apply_json(&board, &op.to_string()).map(|out| serde_json::from_str(&out).unwrap())
+    }
+
+    #[test]
+    fn ops_round_trip_through_json() {
+        let board = Board::default_board();
+        let out = run(
+            &board,
+            json!({"op": "add", "title": "Ship", "labels": ["release"]}),
+        )
+        .unwrap();
+        assert_eq!(out["card"]["column"], "todo");
+        let board: Board = serde_json::from_value(out["board"].clone()).unwrap();
+
+        let out = run(&board, json!({"op": "move", "card": "1", "to": "done"})).unwrap();
+        assert_eq!(out["card"]["column"], "done");
+        let board: Board = serde_json::from_value(out["board"].clone()).unwrap();
+
+        let out = run(
+            &board,
+            json!({"op": "label", "card": "1", "add": ["shipped"], "remove": ["release"]}),
+        )
+        .unwrap();
+        assert_eq!(out["card"]["labels"], json!(["shipped"]));
+        let board: Board = serde_json::from_value(out["board"].clone()).unwrap();
+
+        let out = run(&board, json!({"op": "delete", "card": "1"})).unwrap();
+        assert_eq!(out["card"]["title"], "Ship");
+        assert_eq!(out["board"]["cards"], json!([]));
+    }
+
+    #[test]
+    fn bad_input_is_reported() {
+        let board = Board::default_board();
+        let err = |op| run(&board, op).unwrap_err();
+        assert_eq!(
+            err(json!({"op": "move", "card": "9", "to": "done"})),
+            "Card not found: 9"
+        );
+        assert_eq!(
+            err(json!({"op": "add", "title": "X", "column": "nope"})),
+            "Column not found: nope"
+        );
+        assert!(err(json!({"op": "explode"})).starts_with("Invalid op"));
+        assert!(
+            apply_json("{", "{}")
+                .unwrap_err()
+                .starts_with("Invalid board")
+        );
+    }
+}
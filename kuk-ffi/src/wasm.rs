@@ -0,0 +1,21 @@
+//! wasm-bindgen exports for running board logic in the browser. There is no
+//! file access here: the host loads and stores board JSON itself.
+
+use wasm_bindgen::prelude::*;
+
+use kuk::model::Board;
+
+use crate::ops;
+
+/// A new board with kuk's default columns, as JSON.
+#[wasm_bindgen(js_name = defaultBoard)]
+pub fn default_board() -> String {
+    serde_json::to_string(&Board::default_board()).expect("boards serialize")
+}
+
+/// Apply a change to a board. Returns `{"board": ..., "card": ...}` as JSON
+/// and throws the error message on failure.
+#[wasm_bindgen(js_name = applyOp)]
+pub fn apply_op(board_json: &str, op_json: &str) -> Result<String, JsError> {
+    ops::apply_json(board_json, op_json).map_err(|e| JsError::new(&e))
+}
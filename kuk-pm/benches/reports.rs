@@ -0,0 +1,67 @@
+//! Report calculations on synthetic boards. Run with `cargo bench -p kuk-pm`.
+
+use chrono::{Days, Utc};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use kuk::dev::generate_board;
+use kuk::model::{Board, Card};
+use kuk_pm::model::{Calendar, OutlierPolicy, Sprint, SprintStatus};
+use kuk_pm::reports::{self, ReportWindow};
+
+const SIZES: &[usize] = &[100, 1_000, 10_000];
+
+/// `cards` split over five boards, like `kuk dev generate --boards 5`.
+fn boards(cards: usize) -> Vec<Board> {
+    (0..5)
+        .map(|i| generate_board(&format!("gen-{}", i + 1), cards / 5, i as u64 + 1))
+        .collect()
+}
+
+/// Every card across `boards`, as reports receive them.
+fn all_cards(boards: &[Board]) -> Vec<Card> {
+    boards.iter().flat_map(|b| b.cards.clone()).collect()
+}
+
+fn sprint() -> Sprint {
+    let today = Utc::now().date_naive();
+    Sprint {
+        name: "bench".into(),
+        start: today - Days::new(28),
+        end: today + Days::new(14),
+        goal: None,
+        boards: Vec::new(),
+        status: SprintStatus::Active,
+        milestone: None,
+    }
+}
+
+fn report_calculations(c: &mut Criterion) {
+    let cal = Calendar::default();
+    let window = ReportWindow::default();
+    let policy = OutlierPolicy::default();
+    let sprint = sprint();
+
+    let mut group = c.benchmark_group("reports");
+    for &size in SIZES {
+        let boards = boards(size);
+        let cards = all_cards(&boards);
+        group.bench_with_input(BenchmarkId::new("velocity", size), &cards, |b, cards| {
+            b.iter(|| reports::calculate_velocity(cards, 8, &window, &cal))
+        });
+        group.bench_with_input(BenchmarkId::new("stats", size), &boards, |b, boards| {
+            b.iter(|| reports::calculate_stats(&boards[0], policy, &window, &cal))
+        });
+        group.bench_with_input(BenchmarkId::new("burndown", size), &cards, |b, cards| {
+            b.iter(|| reports::calculate_burndown(cards, &sprint, &cal))
+        });
+        group.bench_with_input(BenchmarkId::new("roadmap", size), &cards, |b, cards| {
+            b.iter(|| {
+                reports::calculate_roadmap(cards, std::slice::from_ref(&sprint), 8, 5.0, &cal)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, report_calculations);
+criterion_main!(benches);
@@ -1,14 +1,21 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use chrono::NaiveDate;
 use clap::{Parser, Subcommand};
+use serde::Serialize;
 
 use crate::error::{PmError, Result};
 use crate::git;
-use crate::model::{PmConfig, Sprint, SprintStatus};
+use crate::model::{
+    ForgeUrl, Holiday, KeyResult, LinkKind, LinkRole, Locale, Objective, PmConfig, PostedComment,
+    Sprint, SprintStatus, TeamCalendar, Vacation,
+};
 use crate::reports;
+use crate::reports::{Compared, ReportWindow};
 use crate::sync;
-use kuk::storage::Store;
+use crate::sync::{load_sprints, load_team_calendar, save_sprints, save_team_calendar};
+use kuk::storage::{CardQuery, Store};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -31,6 +38,10 @@ pub struct Cli {
     /// Path to repo root (defaults to current directory)
     #[arg(long, global = true)]
     pub repo: Option<PathBuf>,
+
+    /// Log diagnostics to stderr (-v requests and API calls, -vv storage and git)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
 }
 
 #[derive(Subcommand, Debug)]
@@ -42,30 +53,90 @@ pub enum Commands {
     Projects,
 
     /// Bidirectional sync with GitHub/GitLab
+    #[command(args_conflicts_with_subcommands = true)]
     Sync {
+        #[command(subcommand)]
+        command: Option<SyncCmd>,
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
+        /// Board to sync (defaults to the default board)
+        #[arg(long)]
+        board: Option<String>,
+        /// Only sync cards with this label (repeatable)
+        #[arg(long)]
+        label: Vec<String>,
+        /// Only sync cards in these columns (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        columns: Vec<String>,
+        /// Create GitHub issues for cards that have none linked
+        #[arg(long)]
+        create_missing: bool,
     },
 
     /// Link a card to an issue or PR URL
     Link {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
         card_id: String,
         /// Issue or PR URL
         url: String,
+        /// How the link relates to the card: implements, fixes, or reverts
+        #[arg(long, default_value = "implements")]
+        role: String,
+        /// Don't copy the issue's assignee and labels onto the card
+        #[arg(long)]
+        no_fetch: bool,
+    },
+
+    /// Remove issue/PR links from a card
+    Unlink {
+        /// Card ID, short ID (KUK-42) or number
+        card_id: String,
+        /// URL of the link to remove
+        #[arg(long, conflicts_with = "all", required_unless_present = "all")]
+        url: Option<String>,
+        /// Remove every link on the card
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Comment on a card's linked issue or PR
+    Comment {
+        /// Card ID, short ID (KUK-42) or number
+        card_id: String,
+        /// Comment text (Markdown)
+        message: String,
+        /// Linked issue or PR to comment on (default: the first linked issue,
+        /// else the first PR)
+        #[arg(long)]
+        url: Option<String>,
+    },
+
+    /// Show the kuk-pm metadata stored on a card
+    Meta {
+        /// Card ID, short ID (KUK-42) or number
+        card_id: String,
     },
 
     /// Create a git branch from a card
     Branch {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
         card_id: String,
     },
 
     /// Create a PR from the current branch
     Pr {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
         card_id: String,
+        /// How the PR relates to the card: implements, fixes, or reverts
+        #[arg(long, default_value = "implements")]
+        role: String,
+    },
+
+    /// Write a card out as a forge issue, or create it there
+    Export {
+        #[command(subcommand)]
+        command: ExportCmd,
     },
 
     /// Show velocity metrics
@@ -73,9 +144,25 @@ pub enum Commands {
         /// Number of weeks to analyze
         #[arg(long, default_value = "4")]
         weeks: u32,
-        /// Target repo path (or "all")
+        /// Repo path or project name to report on, or "all"
         #[arg(long)]
         target: Option<String>,
+        /// Start of the reporting window (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// End of the reporting window (YYYY-MM-DD, default today)
+        #[arg(long)]
+        until: Option<String>,
+        /// Also report the preceding equal-length window and the change
+        #[arg(long)]
+        compare_previous: bool,
+        /// Print a single summary line, for status bars and prompts
+        #[arg(long, conflicts_with = "compare_previous")]
+        oneline: bool,
+        /// Also count each week's commit authors and report cards per
+        /// contributor-week, so the trend allows for absences and team growth
+        #[arg(long)]
+        normalize: bool,
     },
 
     /// Show burndown chart
@@ -83,6 +170,12 @@ pub enum Commands {
         /// Sprint name
         #[arg(long)]
         sprint: Option<String>,
+        /// Repo path or project name to report on, or "all"
+        #[arg(long)]
+        target: Option<String>,
+        /// Print a single summary line, for status bars and prompts
+        #[arg(long)]
+        oneline: bool,
     },
 
     /// Show roadmap
@@ -90,13 +183,46 @@ pub enum Commands {
         /// Number of weeks to project
         #[arg(long, default_value = "12")]
         weeks: u32,
+        /// Repo path or project name to report on, or "all"
+        #[arg(long)]
+        target: Option<String>,
     },
 
     /// Generate release notes
     ReleaseNotes {
         /// Starting point (tag or ref)
-        #[arg(long, default_value = "last-tag")]
+        #[arg(long, visible_alias = "from", default_value = "last-tag")]
+        since: Option<String>,
+        /// End point (tag, branch or ref), e.g. a release branch
+        #[arg(long, default_value = "HEAD")]
+        to: String,
+        /// Leave out commits already released on this branch or tag,
+        /// including ones cherry-picked onto it (repeatable)
+        #[arg(long)]
+        exclude: Vec<String>,
+        /// Let a pre-release tag like v2.0.0-rc.1 be the last tag
+        #[arg(long)]
+        pre_release: bool,
+        /// Add commit totals per author, marking first-time contributors
+        #[arg(long)]
+        contributors: bool,
+    },
+
+    /// List tags in version order and show which one counts as the latest
+    Tags {
+        /// Let a pre-release tag like v2.0.0-rc.1 be the latest
+        #[arg(long)]
+        pre_release: bool,
+    },
+
+    /// Link past commits to cards from their `Kuk-Card:` and `Closes:` trailers
+    BackfillLinks {
+        /// Only look at commits after this ref (default: all of HEAD's history)
+        #[arg(long)]
         since: Option<String>,
+        /// Show what would be linked without saving
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Sprint management
@@ -105,11 +231,80 @@ pub enum Commands {
         command: SprintCmd,
     },
 
+    /// Objectives and key results linked to cards
+    Okr {
+        #[command(subcommand)]
+        command: Option<OkrCmd>,
+    },
+
+    /// Team working days, holidays, and vacations
+    Calendar {
+        #[command(subcommand)]
+        command: Option<CalendarCmd>,
+    },
+
     /// Show project statistics
-    Stats,
+    Stats {
+        /// Only count cards completed on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Measure as of this date (YYYY-MM-DD, default today)
+        #[arg(long)]
+        until: Option<String>,
+        /// Also report the preceding equal-length window (30 days without
+        /// --since) and the change
+        #[arg(long)]
+        compare_previous: bool,
+        /// Add weekly sparklines of WIP, throughput and cycle time
+        #[arg(long)]
+        history: bool,
+        /// Number of weeks the --history trends cover
+        #[arg(long, default_value = "12", requires = "history")]
+        weeks: u32,
+        /// Repo path or project name to report on, or "all"
+        #[arg(long)]
+        target: Option<String>,
+    },
+
+    /// Show how long cards wait at each handoff between columns
+    Handoffs {
+        /// Number of weeks to analyze
+        #[arg(long, default_value = "4")]
+        weeks: u32,
+        /// Start of the reporting window (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// End of the reporting window (YYYY-MM-DD, default today)
+        #[arg(long)]
+        until: Option<String>,
+        /// Repo path or project name to report on, or "all"
+        #[arg(long)]
+        target: Option<String>,
+    },
+
+    /// List when each column went over its WIP limit, for how long, and
+    /// which cards were in it
+    WipHistory {
+        /// Start of the reporting window (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// End of the reporting window (YYYY-MM-DD, default today)
+        #[arg(long)]
+        until: Option<String>,
+        /// Repo path or project name to report on, or "all"
+        #[arg(long)]
+        target: Option<String>,
+    },
+
+    /// Open a dashboard of reports, sprints and sync in the terminal
+    Tui,
 
     /// Run as MCP server (stdio transport for Claude Code / AI agents)
-    Mcp,
+    Mcp {
+        /// Also serve kuk's board tools, so one server covers both
+        #[arg(long)]
+        with_board: bool,
+    },
 
     /// Health check
     Doctor,
@@ -118,6 +313,38 @@ pub enum Commands {
     Version,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum SyncCmd {
+    /// Show past sync runs
+    Log {
+        /// Only show the N most recent runs
+        #[arg(long)]
+        last: Option<usize>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ExportCmd {
+    /// Print a card as a ready-to-paste issue body, or create the issue
+    Card {
+        /// Card ID, short ID (KUK-42) or number
+        card_id: String,
+        #[arg(long, value_enum, default_value = "gh-issue")]
+        format: ExportFormat,
+        /// Create the issue on the repo's forge and link it to the card
+        #[arg(long)]
+        create: bool,
+    },
+}
+
+/// Formats `kuk-pm export card` writes.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A GitHub issue: description with the checklist as a task list, and
+    /// a labels footer
+    GhIssue,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum SprintCmd {
     /// Create a new sprint
@@ -130,6 +357,24 @@ pub enum SprintCmd {
         /// End date (YYYY-MM-DD)
         #[arg(long)]
         end: String,
+        /// Also create a GitHub milestone due on the end date
+        #[arg(long)]
+        github_milestone: bool,
+    },
+    /// Start a planned sprint
+    Start {
+        /// Sprint name
+        name: String,
+        /// Create a board for the sprint, with the default board's columns;
+        /// burndown and stats then cover that board
+        #[arg(long)]
+        create_board: bool,
+        /// Move a card from the default board onto the sprint board (repeatable)
+        #[arg(long = "move", value_name = "CARD", requires = "create_board")]
+        move_cards: Vec<String>,
+        /// Copy a card from the default board onto the sprint board (repeatable)
+        #[arg(long = "copy", value_name = "CARD", requires = "create_board")]
+        copy_cards: Vec<String>,
     },
     /// Close an active sprint
     Close {
@@ -140,6 +385,73 @@ pub enum SprintCmd {
     List,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum OkrCmd {
+    /// Show each objective's progress (default)
+    Progress,
+    /// Create an objective
+    Create {
+        /// Short name used to refer to it, e.g. adoption
+        name: String,
+        /// Full title (defaults to the name)
+        #[arg(long)]
+        title: Option<String>,
+        /// Quarter, e.g. 2026-Q2
+        #[arg(long)]
+        quarter: Option<String>,
+        /// Key result title (repeatable, numbered in order)
+        #[arg(long = "kr", value_name = "TITLE")]
+        key_results: Vec<String>,
+    },
+    /// Link a card, or every card in an epic, to a key result
+    Link {
+        /// Key result as <objective>/<number>, e.g. adoption/1
+        key_result: String,
+        /// Card ID, short ID (KUK-42) or number
+        #[arg(required_unless_present = "epic")]
+        card: Option<String>,
+        /// Epic label; every card carrying it counts
+        #[arg(long, conflicts_with = "card")]
+        epic: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CalendarCmd {
+    /// Show the team calendar (default)
+    Show,
+    /// Set the weekdays the team works
+    Workdays {
+        /// Comma-separated weekdays, e.g. mon,tue,wed,thu,fri
+        #[arg(value_delimiter = ',', required = true)]
+        days: Vec<String>,
+    },
+    /// Add a team-wide holiday
+    Holiday {
+        /// Date (YYYY-MM-DD)
+        date: String,
+        /// Holiday name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Add time off for one person
+    Vacation {
+        /// Person (matches card assignees)
+        person: String,
+        /// First day off (YYYY-MM-DD)
+        #[arg(long)]
+        from: String,
+        /// Last day off (YYYY-MM-DD)
+        #[arg(long)]
+        to: String,
+    },
+    /// Remove a holiday by date, or all of a person's vacations
+    Remove {
+        /// Holiday date (YYYY-MM-DD) or person
+        target: String,
+    },
+}
+
 // --- Command implementations ---
 
 pub fn init(repo: &Path) -> Result<()> {
@@ -314,7 +626,7 @@ pub fn doctor(repo: &Path) -> Result<()> {
         Ok(boards) => {
             println!("  [OK] {} board(s): {}", boards.len(), boards.join(", "));
             for b in &boards {
-                match store.load_board(b) {
+                match store.load_board_summary(b) {
                     Ok(board) => {
                         let active = board.cards.iter().filter(|c| !c.archived).count();
                         let archived = board.cards.iter().filter(|c| c.archived).count();
@@ -390,8 +702,31 @@ pub fn sprint(repo: &Path, command: SprintCmd, json_output: bool) -> Result<()>
     }
 
     match command {
-        SprintCmd::Create { name, start, end } => {
-            sprint_create(&store, &name, &start, &end, json_output)
+        SprintCmd::Create {
+            name,
+            start,
+            end,
+            github_milestone,
+        } => sprint_create(
+            repo,
+            &store,
+            &name,
+            &start,
+            &end,
+            github_milestone,
+            json_output,
+        ),
+        SprintCmd::Start {
+            name,
+            create_board,
+            move_cards,
+            copy_cards,
+        } => {
+            let cards = create_board.then_some(sync::SprintBoardCards {
+                moved: move_cards,
+                copied: copy_cards,
+            });
+            sprint_start(&store, &name, cards.as_ref(), json_output)
         }
         SprintCmd::Close { name } => sprint_close(&store, &name, json_output),
         SprintCmd::List => sprint_list(&store, json_output),
@@ -399,10 +734,12 @@ pub fn sprint(repo: &Path, command: SprintCmd, json_output: bool) -> Result<()>
 }
 
 fn sprint_create(
+    repo: &Path,
     store: &Store,
     name: &str,
     start_str: &str,
     end_str: &str,
+    github_milestone: bool,
     json_output: bool,
 ) -> Result<()> {
     let start = NaiveDate::parse_from_str(start_str, "%Y-%m-%d")
@@ -422,6 +759,13 @@ fn sprint_create(
         return Err(PmError::SprintAlreadyExists(name.into()));
     }
 
+    // Create the milestone first so a failure doesn't leave an unmapped sprint
+    let milestone = if github_milestone {
+        Some(sync::provider()?.create_milestone(repo, name, end)?)
+    } else {
+        None
+    };
+
     let sprint = Sprint {
         name: name.into(),
         start,
@@ -429,6 +773,7 @@ fn sprint_create(
         goal: None,
         boards: Vec::new(),
         status: SprintStatus::Planned,
+        milestone,
     };
 
     sprints.push(sprint.clone());
@@ -438,6 +783,37 @@ fn sprint_create(
         println!("{}", serde_json::to_string_pretty(&sprint)?);
     } else {
         println!("Created sprint: {name} ({start} → {end})");
+        if let Some(m) = &sprint.milestone {
+            println!("  GitHub milestone #{}: {}", m.number, m.url);
+        }
+    }
+    Ok(())
+}
+
+fn sprint_start(
+    store: &Store,
+    name: &str,
+    cards: Option<&sync::SprintBoardCards>,
+    json_output: bool,
+) -> Result<()> {
+    let started = sync::start_sprint(store, name, cards)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&started)?);
+        return Ok(());
+    }
+    println!("Started sprint: {name}");
+    if let Some(board) = &started.board {
+        println!("  Created board: {board}");
+        if !started.moved.is_empty() {
+            println!("  Moved: {}", started.moved.join(", "));
+        }
+        if !started.copied.is_empty() {
+            println!("  Copied: {}", started.copied.join(", "));
+        }
+        println!(
+            "  Burndown and stats now cover {board}. Work on it with `kuk board switch {board}`."
+        );
     }
     Ok(())
 }
@@ -493,14 +869,279 @@ fn sprint_list(store: &Store, json_output: bool) -> Result<()> {
     Ok(())
 }
 
+// ─── OKRs ────────────────────────────────────────────────────
+
+pub fn okr(repo: &Path, command: Option<OkrCmd>, json_output: bool) -> Result<()> {
+    let store = Store::new(repo);
+    if !store.is_initialized() {
+        return Err(PmError::KukNotInitialized);
+    }
+
+    match command.unwrap_or(OkrCmd::Progress) {
+        OkrCmd::Progress => okr_progress(&store, json_output),
+        OkrCmd::Create {
+            name,
+            title,
+            quarter,
+            key_results,
+        } => okr_create(&store, name, title, quarter, key_results, json_output),
+        OkrCmd::Link {
+            key_result,
+            card,
+            epic,
+        } => okr_link(&store, &key_result, card.as_deref(), epic, json_output),
+    }
+}
+
+fn okr_progress(store: &Store, json_output: bool) -> Result<()> {
+    let objectives = sync::load_okrs(store)?;
+    let cards = store.active_cards()?;
+    let progress = reports::calculate_okr_progress(&objectives, &cards);
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&progress)?);
+    } else {
+        print!("{}", reports::render_okr_text(&progress));
+    }
+    Ok(())
+}
+
+fn okr_create(
+    store: &Store,
+    name: String,
+    title: Option<String>,
+    quarter: Option<String>,
+    key_results: Vec<String>,
+    json_output: bool,
+) -> Result<()> {
+    let mut objectives = sync::load_okrs(store)?;
+    if objectives.iter().any(|o| o.name == name) {
+        return Err(PmError::ObjectiveAlreadyExists(name));
+    }
+
+    let objective = Objective {
+        title: title.unwrap_or_else(|| name.clone()),
+        name,
+        quarter,
+        key_results: key_results.into_iter().map(KeyResult::new).collect(),
+    };
+    objectives.push(objective.clone());
+    sync::save_okrs(store, &objectives)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&objective)?);
+    } else {
+        println!(
+            "Created objective: {} ({} key results)",
+            objective.name,
+            objective.key_results.len()
+        );
+    }
+    Ok(())
+}
+
+fn okr_link(
+    store: &Store,
+    key_result: &str,
+    card: Option<&str>,
+    epic: Option<String>,
+    json_output: bool,
+) -> Result<()> {
+    let invalid = || {
+        PmError::Other(format!(
+            "Invalid key result: {key_result} (expected <objective>/<number>)"
+        ))
+    };
+    let (name, number) = key_result.rsplit_once('/').ok_or_else(invalid)?;
+    let number: usize = number.parse().map_err(|_| invalid())?;
+
+    let mut objectives = sync::load_okrs(store)?;
+    let objective = objectives
+        .iter_mut()
+        .find(|o| o.name == name)
+        .ok_or_else(|| PmError::ObjectiveNotFound(name.into()))?;
+    let count = objective.key_results.len();
+    let kr = number
+        .checked_sub(1)
+        .and_then(|i| objective.key_results.get_mut(i))
+        .ok_or_else(|| {
+            PmError::Other(format!("No key result {number} in {name} (it has {count})"))
+        })?;
+
+    let linked = match (card, epic) {
+        (Some(card), _) => {
+            let config = store.load_config()?;
+            let board = store.load_board_summary(&config.default_board)?;
+            let id = board
+                .resolve_card_id(card)
+                .ok_or_else(|| PmError::CardNotFound(card.into()))?;
+            let title = board.find_card(&id).map(|c| c.title.clone());
+            if !kr.cards.contains(&id) {
+                kr.cards.push(id);
+            }
+            format!("card {}", title.unwrap_or_default())
+        }
+        (None, Some(epic)) => {
+            let linked = format!("epic {epic}");
+            if !kr.epics.contains(&epic) {
+                kr.epics.push(epic);
+            }
+            linked
+        }
+        (None, None) => unreachable!("clap requires a card or --epic"),
+    };
+    let result = kr.clone();
+    sync::save_okrs(store, &objectives)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        println!("Linked {linked} to {name}/{number}: {}", result.title);
+    }
+    Ok(())
+}
+
+// ─── Calendar ────────────────────────────────────────────────
+
+fn parse_date(s: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| PmError::InvalidDate(s.into()))
+}
+
+pub fn calendar(repo: &Path, command: Option<CalendarCmd>, json_output: bool) -> Result<()> {
+    let store = Store::new(repo);
+    if !store.is_initialized() {
+        return Err(PmError::KukNotInitialized);
+    }
+
+    let mut team = load_team_calendar(&store)?;
+    let message = match command.unwrap_or(CalendarCmd::Show) {
+        CalendarCmd::Show => return calendar_show(&team, json_output),
+        CalendarCmd::Workdays { days } => {
+            let mut working_days = Vec::new();
+            for d in &days {
+                let day: chrono::Weekday = d
+                    .trim()
+                    .parse()
+                    .map_err(|_| PmError::Other(format!("Invalid weekday: {d}")))?;
+                if !working_days.contains(&day) {
+                    working_days.push(day);
+                }
+            }
+            working_days.sort_by_key(|d| d.num_days_from_monday());
+            team.working_days = working_days;
+            format!("Working days: {}", format_weekdays(&team))
+        }
+        CalendarCmd::Holiday { date, name } => {
+            let date = parse_date(&date)?;
+            team.holidays.retain(|h| h.date != date);
+            team.holidays.push(Holiday {
+                date,
+                name: name.clone(),
+            });
+            team.holidays.sort_by_key(|h| h.date);
+            match name {
+                Some(name) => format!("Added holiday: {date} ({name})"),
+                None => format!("Added holiday: {date}"),
+            }
+        }
+        CalendarCmd::Vacation { person, from, to } => {
+            let start = parse_date(&from)?;
+            let end = parse_date(&to)?;
+            if end < start {
+                return Err(PmError::InvalidDate(
+                    "vacation must end on or after its start".into(),
+                ));
+            }
+            team.vacations.push(Vacation {
+                person: person.clone(),
+                start,
+                end,
+            });
+            team.vacations
+                .sort_by(|a, b| (a.start, &a.person).cmp(&(b.start, &b.person)));
+            format!("Added vacation: {person} ({start} → {end})")
+        }
+        CalendarCmd::Remove { target } => {
+            let before = team.holidays.len() + team.vacations.len();
+            match parse_date(&target) {
+                Ok(date) => team.holidays.retain(|h| h.date != date),
+                Err(_) => team.vacations.retain(|v| v.person != target),
+            }
+            let removed = before - team.holidays.len() - team.vacations.len();
+            if removed == 0 {
+                return Err(PmError::Other(format!(
+                    "No holiday or vacation matches: {target}"
+                )));
+            }
+            format!("Removed {removed} calendar entries for {target}")
+        }
+    };
+
+    save_team_calendar(&store, &team)?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&team)?);
+    } else {
+        println!("{message}");
+    }
+    Ok(())
+}
+
+fn format_weekdays(team: &TeamCalendar) -> String {
+    if team.working_days.is_empty() {
+        return "none".into();
+    }
+    team.working_days
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn calendar_show(team: &TeamCalendar, json_output: bool) -> Result<()> {
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(team)?);
+        return Ok(());
+    }
+
+    println!("Team Calendar");
+    println!("─────────────");
+    println!("Working days: {}", format_weekdays(team));
+
+    if !team.holidays.is_empty() {
+        println!("\nHolidays");
+        for h in &team.holidays {
+            match &h.name {
+                Some(name) => println!("  {}  {name}", h.date),
+                None => println!("  {}", h.date),
+            }
+        }
+    }
+
+    if !team.vacations.is_empty() {
+        println!("\nVacations");
+        for v in &team.vacations {
+            println!("  {}  {} → {}", v.person, v.start, v.end);
+        }
+    }
+    Ok(())
+}
+
 // ─── Link ────────────────────────────────────────────────────
 
-pub fn link(repo: &Path, card_id: &str, url: &str, json_output: bool) -> Result<()> {
+pub fn link(
+    repo: &Path,
+    card_id: &str,
+    url: &str,
+    role: &str,
+    no_fetch: bool,
+    json_output: bool,
+) -> Result<()> {
+    let role: LinkRole = role.parse().map_err(PmError::Other)?;
     let store = Store::new(repo);
     if !store.is_initialized() {
         return Err(PmError::KukNotInitialized);
     }
 
+    let pm_config = sync::load_pm_config(&store)?;
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
 
@@ -515,14 +1156,33 @@ pub fn link(repo: &Path, card_id: &str, url: &str, json_output: bool) -> Result<
     let mut meta = sync::get_pm_metadata(card);
 
     // Detect issue vs PR from URL
-    if url.contains("/pull/") || url.contains("/pulls/") || url.contains("/merge_requests/") {
-        meta.pr_url = Some(url.into());
-    } else {
-        meta.issue_url = Some(url.into());
+    let hosts = &pm_config.forge_hosts;
+    if ForgeUrl::parse(url, hosts).is_none() {
+        eprintln!(
+            "Warning: {url} is not on a recognized forge host; add the host to forge_hosts in .kuk/pm.json so sync can track it"
+        );
+    }
+    let kind = sync::link_kind(url, hosts);
+    let is_pr = kind == LinkKind::Pr;
+    meta.add_link(url, kind, role);
+
+    // Pull assignee and labels from the issue so the card starts out in sync
+    let mut label_colors = BTreeMap::new();
+    if !is_pr && !no_fetch {
+        match sync::fetch_issue_details(&*sync::provider()?, url, hosts) {
+            Ok(details) => {
+                sync::apply_issue_details(card, &details);
+                label_colors = details.label_colors;
+            }
+            Err(e) => eprintln!("Warning: could not fetch issue details: {e}"),
+        }
     }
 
     sync::set_pm_metadata(card, &meta);
     card.updated_at = chrono::Utc::now();
+    let assignee = card.assignee.clone();
+    let labels = card.labels.clone();
+    sync::apply_label_colors(&mut board, &label_colors);
 
     store.save_board(&board)?;
 
@@ -532,35 +1192,32 @@ pub fn link(repo: &Path, card_id: &str, url: &str, json_output: bool) -> Result<
             serde_json::json!({
                 "card_id": card_uuid,
                 "url": url,
-                "type": if meta.pr_url.as_deref() == Some(url) { "pr" } else { "issue" }
+                "type": if is_pr { "pr" } else { "issue" },
+                "role": role,
+                "assignee": assignee,
+                "labels": labels
             })
         );
     } else {
-        let link_type = if url.contains("/pull/")
-            || url.contains("/pulls/")
-            || url.contains("/merge_requests/")
-        {
-            "PR"
-        } else {
-            "issue"
-        };
-        println!("Linked card {} to {link_type}: {url}", card_uuid);
+        println!("Linked card {} to {kind} ({role}): {url}", card_uuid);
     }
     Ok(())
 }
 
-// ─── PR ──────────────────────────────────────────────────────
+// ─── Unlink ──────────────────────────────────────────────────
 
-pub fn pr(repo: &Path, card_id: &str, json_output: bool) -> Result<()> {
+pub fn unlink(
+    repo: &Path,
+    card_id: &str,
+    url: Option<&str>,
+    all: bool,
+    json_output: bool,
+) -> Result<()> {
     let store = Store::new(repo);
     if !store.is_initialized() {
         return Err(PmError::KukNotInitialized);
     }
 
-    if !git::is_git_repo(repo) {
-        return Err(PmError::NotGitRepo);
-    }
-
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
 
@@ -568,28 +1225,105 @@ pub fn pr(repo: &Path, card_id: &str, json_output: bool) -> Result<()> {
         .resolve_card_id(card_id)
         .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
 
-    let card = board
-        .find_card(&card_uuid)
-        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
-
-    let title = card.title.clone();
-    let body = card
-        .description
-        .clone()
-        .unwrap_or_else(|| format!("Card: {}", card.title));
-
-    let pr_url = sync::create_pr(repo, &title, &body)?;
-
-    // Update card metadata with PR URL
     let card = board
         .find_card_mut(&card_uuid)
         .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
 
     let mut meta = sync::get_pm_metadata(card);
-    meta.pr_url = Some(pr_url.clone());
+
+    let removed: Vec<String> = if all {
+        meta.links.drain(..).map(|l| l.url).collect()
+    } else {
+        let url = url.ok_or_else(|| PmError::Other("Pass --url <URL> or --all".into()))?;
+        if !meta.remove_link(url) {
+            return Err(PmError::Other(format!(
+                "Card {card_uuid} is not linked to {url}"
+            )));
+        }
+        vec![url.to_string()]
+    };
+
     sync::set_pm_metadata(card, &meta);
     card.updated_at = chrono::Utc::now();
+    store.save_board(&board)?;
+
+    if json_output {
+        println!(
+            "{}",
+            serde_json::json!({
+                "card_id": card_uuid,
+                "removed": removed,
+                "remaining": meta.links.len()
+            })
+        );
+    } else if removed.is_empty() {
+        println!("Card {card_uuid} has no links.");
+    } else {
+        for url in &removed {
+            println!("Unlinked card {card_uuid} from {url}");
+        }
+    }
+    Ok(())
+}
+
+// ─── Comment ─────────────────────────────────────────────────
+
+pub fn comment(
+    repo: &Path,
+    card_id: &str,
+    message: &str,
+    url: Option<&str>,
+    json_output: bool,
+) -> Result<()> {
+    let store = Store::new(repo);
+    if !store.is_initialized() {
+        return Err(PmError::KukNotInitialized);
+    }
+    if message.trim().is_empty() {
+        return Err(PmError::Other("Comment must not be empty".into()));
+    }
+
+    let pm_config = sync::load_pm_config(&store)?;
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_uuid = board
+        .resolve_card_id(card_id)
+        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
+
+    let card = board
+        .find_card_mut(&card_uuid)
+        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
 
+    let mut meta = sync::get_pm_metadata(card);
+    let target =
+        match url {
+            Some(url) => meta.links.iter().find(|l| l.url == url).ok_or_else(|| {
+                PmError::Other(format!("Card {card_uuid} is not linked to {url}"))
+            })?,
+            None => meta.comment_target().ok_or_else(|| {
+                PmError::Other(format!(
+                    "Card {card_uuid} has no linked issue or PR. Link one with `kuk-pm link`."
+                ))
+            })?,
+        };
+    let (target_url, kind) = (target.url.clone(), target.kind);
+
+    let comment_url = sync::post_comment(
+        &*sync::provider()?,
+        &target_url,
+        message,
+        &pm_config.forge_hosts,
+    )?;
+    let posted = PostedComment {
+        at: chrono::Utc::now(),
+        url: target_url,
+        body: message.into(),
+        comment_url,
+    };
+    meta.comments.push(posted.clone());
+    sync::set_pm_metadata(card, &meta);
+    card.updated_at = posted.at;
     store.save_board(&board)?;
 
     if json_output {
@@ -597,121 +1331,379 @@ pub fn pr(repo: &Path, card_id: &str, json_output: bool) -> Result<()> {
             "{}",
             serde_json::json!({
                 "card_id": card_uuid,
-                "pr_url": pr_url,
-                "title": title
+                "comment": posted,
             })
         );
     } else {
-        println!("Created PR: {pr_url}");
-        println!("  Card: {title}");
+        println!("Commented on {kind} {}", posted.url);
+        if let Some(comment_url) = &posted.comment_url {
+            println!("  {comment_url}");
+        }
     }
     Ok(())
 }
 
-// ─── Velocity ────────────────────────────────────────────────
+// ─── Meta ────────────────────────────────────────────────────
 
-pub fn velocity(repo: &Path, weeks: u32, _target: Option<&str>, json_output: bool) -> Result<()> {
+pub fn meta(repo: &Path, card_id: &str, json_output: bool) -> Result<()> {
     let store = Store::new(repo);
     if !store.is_initialized() {
         return Err(PmError::KukNotInitialized);
     }
 
-    let boards = load_all_boards(&store)?;
-    let report = reports::calculate_velocity(&boards, weeks);
+    let config = store.load_config()?;
+    let board = store.load_board(&config.default_board)?;
+
+    let card_uuid = board
+        .resolve_card_id(card_id)
+        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
+
+    let card = board
+        .find_card(&card_uuid)
+        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
+
+    let meta = sync::get_pm_metadata(card);
 
     if json_output {
-        println!("{}", serde_json::to_string_pretty(&report)?);
+        println!(
+            "{}",
+            serde_json::json!({
+                "card_id": card_uuid,
+                "title": card.title,
+                "pm": meta
+            })
+        );
+        return Ok(());
+    }
+
+    println!("{} — {}", card_uuid, card.title);
+    println!(
+        "  Branch:      {}",
+        meta.branch.as_deref().unwrap_or("(none)")
+    );
+    if meta.links.is_empty() {
+        println!("  Links:       (none)");
     } else {
-        print!("{}", reports::render_velocity_text(&report));
+        println!("  Links:");
+        for l in &meta.links {
+            println!("    {} ({}) {}", l.kind, l.role, l.url);
+        }
+    }
+    println!("  Commits:     {}", meta.commits.len());
+    if !meta.comments.is_empty() {
+        println!("  Comments:    {}", meta.comments.len());
     }
+    println!(
+        "  Last synced: {}",
+        meta.last_synced
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "never".into())
+    );
     Ok(())
 }
 
-// ─── Burndown ────────────────────────────────────────────────
+// ─── PR ──────────────────────────────────────────────────────
 
-pub fn burndown(repo: &Path, sprint_name: Option<&str>, json_output: bool) -> Result<()> {
+pub fn pr(repo: &Path, card_id: &str, role: &str, json_output: bool) -> Result<()> {
+    let role: LinkRole = role.parse().map_err(PmError::Other)?;
     let store = Store::new(repo);
     if !store.is_initialized() {
         return Err(PmError::KukNotInitialized);
     }
 
-    let sprints = load_sprints(&store)?;
+    if !git::is_git_repo(repo) {
+        return Err(PmError::NotGitRepo);
+    }
 
-    let sprint = match sprint_name {
-        Some(name) => sprints
-            .iter()
-            .find(|s| s.name == name)
-            .ok_or_else(|| PmError::SprintNotFound(name.into()))?,
-        None => sprints
-            .iter()
-            .find(|s| s.status == SprintStatus::Active)
-            .ok_or(PmError::NoActiveSprint)?,
-    };
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_uuid = board
+        .resolve_card_id(card_id)
+        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
+
+    let card = board
+        .find_card(&card_uuid)
+        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
+
+    let title = card.title.clone();
+    let body = card
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("Card: {}", card.title));
+
+    let pr_url = sync::provider()?.create_pr(repo, &title, &body)?;
+
+    // Update card metadata with PR URL
+    let card = board
+        .find_card_mut(&card_uuid)
+        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
 
-    let boards = load_all_boards(&store)?;
-    let report = reports::calculate_burndown(&boards, sprint);
+    let mut meta = sync::get_pm_metadata(card);
+    meta.add_link(&pr_url, LinkKind::Pr, role);
+    sync::set_pm_metadata(card, &meta);
+    card.updated_at = chrono::Utc::now();
+
+    store.save_board(&board)?;
 
     if json_output {
-        println!("{}", serde_json::to_string_pretty(&report)?);
+        println!(
+            "{}",
+            serde_json::json!({
+                "card_id": card_uuid,
+                "pr_url": pr_url,
+                "title": title
+            })
+        );
     } else {
-        print!("{}", reports::render_burndown_text(&report));
+        println!("Created PR: {pr_url}");
+        println!("  Card: {title}");
     }
     Ok(())
 }
 
-// ─── Roadmap ─────────────────────────────────────────────────
+// ─── Export ──────────────────────────────────────────────────
 
-pub fn roadmap(repo: &Path, weeks: u32, json_output: bool) -> Result<()> {
+/// Print a card as an issue body or, with `create`, open the issue through
+/// the sync provider and link it to the card.
+pub fn export_card(
+    repo: &Path,
+    card_id: &str,
+    format: ExportFormat,
+    create: bool,
+    json_output: bool,
+) -> Result<()> {
     let store = Store::new(repo);
     if !store.is_initialized() {
         return Err(PmError::KukNotInitialized);
     }
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+    let card = board
+        .resolve_card_id(card_id)
+        .and_then(|id| board.find_card_mut(&id))
+        .ok_or_else(|| PmError::CardNotFound(card_id.into()))?;
+
+    let ExportFormat::GhIssue = format;
+    let issue = card.to_issue();
+    if !create {
+        if json_output {
+            println!("{}", serde_json::to_string_pretty(&issue)?);
+        } else {
+            print!("{}", issue.markdown());
+        }
+        return Ok(());
+    }
+
+    if !git::is_git_repo(repo) {
+        return Err(PmError::NotGitRepo);
+    }
+    let mut meta = sync::get_pm_metadata(card);
+    if let Some(link) = meta.issues().next() {
+        return Err(PmError::Other(format!(
+            "{} is already tracked by {}",
+            card.title, link.url
+        )));
+    }
+    let url = sync::provider()?.create_issue(repo, &issue.title, &issue.body)?;
+    meta.add_link(&url, LinkKind::Issue, LinkRole::Implements);
+    sync::set_pm_metadata(card, &meta);
+    card.updated_at = chrono::Utc::now();
+    let card_uuid = card.id.clone();
+    store.save_board(&board)?;
+
+    if json_output {
+        println!(
+            "{}",
+            serde_json::json!({
+                "card_id": card_uuid,
+                "issue_url": url,
+                "title": issue.title
+            })
+        );
+    } else {
+        println!("Created issue: {url}");
+        println!("  Card: {}", issue.title);
+    }
+    Ok(())
+}
+
+// ─── Velocity ────────────────────────────────────────────────
+
+#[allow(clippy::too_many_arguments)]
+pub fn velocity(
+    repo: &Path,
+    weeks: u32,
+    target: Option<&str>,
+    window: &ReportWindow,
+    compare_previous: bool,
+    normalize: bool,
+    oneline: bool,
+    json_output: bool,
+) -> Result<()> {
+    run_report(
+        repo,
+        target,
+        json_output,
+        |store| {
+            let root = store.repo_root();
+            // The cache only watches the board, so a new commit must change
+            // the key of a normalized report
+            let head = match normalize {
+                true if !git::is_git_repo(root) => return Err(PmError::NotGitRepo),
+                true => Some(git::head_sha(root)?),
+                false => None,
+            };
+            let params = (weeks, window, compare_previous, head);
+            reports::cached(store, "velocity", &params, || {
+                let cards = store.active_cards()?;
+                let cal = sync::load_calendar(store)?;
+                let commits = match normalize {
+                    true => git::commits_in_range(root, &[], "HEAD")?,
+                    false => Vec::new(),
+                };
+                Ok(if compare_previous {
+                    let mut cmp = reports::compare_velocity(&cards, weeks, window, &cal);
+                    if normalize {
+                        reports::normalize_velocity_comparison(&mut cmp, &commits, &cal);
+                    }
+                    Compared::Yes(cmp)
+                } else {
+                    let mut report = reports::calculate_velocity(&cards, weeks, window, &cal);
+                    if normalize {
+                        reports::normalize_velocity(&mut report, &commits, &cal);
+                    }
+                    Compared::No(report)
+                })
+            })
+        },
+        |report, locale| match report {
+            Compared::No(report) if oneline => reports::render_velocity_oneline(report, locale),
+            Compared::No(report) => reports::render_velocity_text(report, locale),
+            Compared::Yes(cmp) => format!(
+                "{}{}",
+                reports::render_velocity_text(&cmp.current, locale),
+                reports::render_changes_text(&cmp.previous.window, &cmp.changes, locale)
+            ),
+        },
+    )
+}
+
+// ─── Burndown ────────────────────────────────────────────────
+
+pub fn burndown(
+    repo: &Path,
+    sprint_name: Option<&str>,
+    target: Option<&str>,
+    oneline: bool,
+    json_output: bool,
+) -> Result<()> {
+    run_report(
+        repo,
+        target,
+        json_output,
+        |store| {
+            let sprints = load_sprints(store)?;
+            let sprint = match sprint_name {
+                Some(name) => sprints
+                    .iter()
+                    .find(|s| s.name == name)
+                    .ok_or_else(|| PmError::SprintNotFound(name.into()))?,
+                None => sprints
+                    .iter()
+                    .find(|s| s.status == SprintStatus::Active)
+                    .ok_or(PmError::NoActiveSprint)?,
+            };
+
+            reports::cached(store, "burndown", &sprint.name, || {
+                let cards = sync::sprint_cards(store, sprint)?;
+                let cal = sync::load_calendar(store)?;
+                Ok(reports::calculate_burndown(&cards, sprint, &cal))
+            })
+        },
+        |report, locale| {
+            if oneline {
+                reports::render_burndown_oneline(report)
+            } else {
+                reports::render_burndown_text(report, locale)
+            }
+        },
+    )
+}
+
+// ─── Roadmap ─────────────────────────────────────────────────
+
+pub fn roadmap(repo: &Path, weeks: u32, target: Option<&str>, json_output: bool) -> Result<()> {
+    run_report(
+        repo,
+        target,
+        json_output,
+        |store| reports::cached(store, "roadmap", &weeks, || roadmap_report(store, weeks)),
+        reports::render_roadmap_text,
+    )
+}
 
-    let boards = load_all_boards(&store)?;
-    let sprints = load_sprints(&store)?;
+/// Roadmap projected at the velocity of the last four weeks.
+fn roadmap_report(store: &Store, weeks: u32) -> Result<reports::RoadmapReport> {
+    let cards = store.active_cards()?;
+    let sprints = load_sprints(store)?;
+    let cal = sync::load_calendar(store)?;
 
     // Use recent velocity for projection
-    let vel_report = reports::calculate_velocity(&boards, 4);
+    let vel_report = reports::calculate_velocity(&cards, 4, &ReportWindow::default(), &cal);
     let velocity = if vel_report.average > 0.0 {
         vel_report.average
     } else {
         1.0 // default assumption
     };
 
-    let report = reports::calculate_roadmap(&boards, &sprints, weeks, velocity);
-
-    if json_output {
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        print!("{}", reports::render_roadmap_text(&report));
-    }
-    Ok(())
+    Ok(reports::calculate_roadmap(
+        &store.all_cards()?,
+        &sprints,
+        weeks,
+        velocity,
+        &cal,
+    ))
 }
 
 // ─── Release Notes ───────────────────────────────────────────
 
-pub fn release_notes(repo: &Path, since: Option<&str>, json_output: bool) -> Result<()> {
+pub fn release_notes(
+    repo: &Path,
+    since: Option<&str>,
+    to: &str,
+    exclude: &[String],
+    pre_release: bool,
+    contributors: bool,
+    json_output: bool,
+) -> Result<()> {
     if !git::is_git_repo(repo) {
         return Err(PmError::NotGitRepo);
     }
 
     let since_ref = since.unwrap_or("last-tag");
 
-    let commits = if since_ref == "last-tag" {
+    let from = if since_ref == "last-tag" {
         // Find most recent tag, fall back to all recent commits
-        match git::list_tags(repo) {
-            Ok(tags) if !tags.is_empty() => {
-                let tag = tags.last().unwrap();
-                git::commits_since_ref(repo, tag)?
-            }
-            _ => git::recent_commits(repo, 50)?,
-        }
+        git::latest_tag(repo, pre_release).ok().flatten()
     } else {
-        git::commits_since_ref(repo, since_ref)?
+        Some(since_ref.to_string())
     };
+    let range = git::release_range(repo, from.as_deref(), to, exclude)?;
 
-    let mut report = reports::categorize_commits(&commits);
+    let mut report = reports::categorize_commits(&range.commits);
     report.since = since_ref.to_string();
+    report.to = (to != "HEAD").then(|| to.to_string());
+    report.already_released = range
+        .released
+        .iter()
+        .map(|c| c.message.lines().next().unwrap_or_default().to_string())
+        .collect();
+    report.cards = sync::release_cards(repo, &range.commits)?;
+    if contributors {
+        let earlier = git::earlier_authors(repo, from.as_deref(), to)?;
+        report.contributors = Some(reports::count_contributors(&range.commits, &earlier));
+    }
 
     if json_output {
         println!("{}", serde_json::to_string_pretty(&report)?);
@@ -721,57 +1713,355 @@ pub fn release_notes(repo: &Path, since: Option<&str>, json_output: bool) -> Res
     Ok(())
 }
 
-// ─── Sync ────────────────────────────────────────────────────
+pub fn backfill_links(
+    repo: &Path,
+    since: Option<&str>,
+    dry_run: bool,
+    json_output: bool,
+) -> Result<()> {
+    let store = Store::new(repo);
+    if !store.is_initialized() {
+        return Err(PmError::KukNotInitialized);
+    }
+    if !git::is_git_repo(repo) {
+        return Err(PmError::NotGitRepo);
+    }
+
+    let pm_config = sync::load_pm_config(&store)?;
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+    let commits = git::commits_in_range(repo, since.as_slice(), "HEAD")?;
 
-pub fn sync(repo: &Path, dry_run: bool, json_output: bool) -> Result<()> {
-    sync::run_sync(repo, dry_run, json_output)?;
+    let linked = sync::backfill_links(&mut board, &commits, &pm_config.forge_hosts);
+    if !dry_run && !linked.is_empty() {
+        store.save_board(&board)?;
+    }
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&linked)?);
+        return Ok(());
+    }
+    for link in &linked {
+        let short = &link.sha[..link.sha.len().min(7)];
+        println!("  {short} {} → {}", link.subject, link.card_title);
+    }
+    let verb = if dry_run { "Would link" } else { "Linked" };
+    println!(
+        "{verb} {} commit(s) to cards ({} scanned)",
+        linked.len(),
+        commits.len()
+    );
     Ok(())
 }
 
-// ─── Stats ───────────────────────────────────────────────────
+pub fn tags(repo: &Path, pre_release: bool, json_output: bool) -> Result<()> {
+    if !git::is_git_repo(repo) {
+        return Err(PmError::NotGitRepo);
+    }
+
+    let tags = git::tags(repo)?;
+    let latest = tags
+        .iter()
+        .rev()
+        .find(|t| pre_release || !t.is_pre_release())
+        .map(|t| t.name.clone());
+
+    if json_output {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "latest": latest,
+                "tags": tags,
+            }))?
+        );
+        return Ok(());
+    }
 
-pub fn stats(repo: &Path, json_output: bool) -> Result<()> {
+    if tags.is_empty() {
+        println!("No tags.");
+        return Ok(());
+    }
+
+    let width = tags.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    for tag in tags.iter().rev() {
+        let date = chrono::DateTime::from_timestamp(tag.time, 0)
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let mut line = format!("  {:<width$}  {date}", tag.name);
+        if tag.is_pre_release() {
+            line.push_str("  pre-release");
+        }
+        if latest.as_ref() == Some(&tag.name) {
+            line.push_str("  ← latest");
+        }
+        println!("{line}");
+    }
+    Ok(())
+}
+
+// ─── Sync ────────────────────────────────────────────────────
+
+pub fn sync(
+    repo: &Path,
+    scope: &sync::SyncScope,
+    create_missing: bool,
+    dry_run: bool,
+    json_output: bool,
+) -> Result<()> {
+    sync::run_sync(
+        &*sync::provider()?,
+        repo,
+        scope,
+        create_missing,
+        dry_run,
+        json_output,
+    )?;
+    Ok(())
+}
+
+pub fn sync_log(repo: &Path, last: Option<usize>, json_output: bool) -> Result<()> {
     let store = Store::new(repo);
     if !store.is_initialized() {
         return Err(PmError::KukNotInitialized);
     }
 
-    let config = store.load_config()?;
-    let board = store.load_board(&config.default_board)?;
-    let report = reports::calculate_stats(&board);
+    let entries = sync::load_sync_log(&store, last)?;
 
     if json_output {
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        print!("{}", reports::render_stats_text(&report));
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No sync runs recorded.");
+        return Ok(());
+    }
+
+    for entry in &entries {
+        println!(
+            "{}  {} action(s){}",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.actions.len(),
+            if entry.dry_run { " (dry run)" } else { "" }
+        );
+        for action in &entry.actions {
+            println!("{}", sync::render_action(action));
+        }
     }
     Ok(())
 }
 
-// ─── Sprint/board helpers ────────────────────────────────────
+// ─── Stats ───────────────────────────────────────────────────
 
-fn load_sprints(store: &Store) -> Result<Vec<Sprint>> {
-    let path = store.kuk_dir().join("sprints.json");
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
-    let data = std::fs::read_to_string(&path)?;
-    Ok(serde_json::from_str(&data)?)
+pub fn stats(
+    repo: &Path,
+    window: &ReportWindow,
+    compare_previous: bool,
+    history_weeks: Option<u32>,
+    target: Option<&str>,
+    json_output: bool,
+) -> Result<()> {
+    run_report(
+        repo,
+        target,
+        json_output,
+        |store| {
+            let params = (window, compare_previous, history_weeks);
+            reports::cached(store, "stats", &params, || {
+                let board = sync::stats_board(store)?;
+                let pm_config = sync::load_pm_config(store)?;
+                let cal = sync::load_calendar(store)?;
+                let policy = pm_config.cycle_outliers;
+                let history = history_weeks
+                    .map(|weeks| reports::calculate_stats_history(&board, weeks, window, &cal));
+                Ok(if compare_previous {
+                    let mut cmp = reports::compare_stats(&board, policy, window, &cal);
+                    cmp.current.history = history;
+                    Compared::Yes(cmp)
+                } else {
+                    let mut report = reports::calculate_stats(&board, policy, window, &cal);
+                    report.history = history;
+                    Compared::No(report)
+                })
+            })
+        },
+        |report, locale| match report {
+            Compared::No(report) => reports::render_stats_text(report, locale),
+            Compared::Yes(cmp) => format!(
+                "{}{}",
+                reports::render_stats_text(&cmp.current, locale),
+                reports::render_changes_text(&cmp.previous.window, &cmp.changes, locale)
+            ),
+        },
+    )
 }
 
-fn save_sprints(store: &Store, sprints: &[Sprint]) -> Result<()> {
-    let json = serde_json::to_string_pretty(sprints)?;
-    std::fs::write(store.kuk_dir().join("sprints.json"), json)?;
-    Ok(())
+// ─── Handoffs ────────────────────────────────────────────────
+
+pub fn handoffs(
+    repo: &Path,
+    weeks: u32,
+    window: &ReportWindow,
+    target: Option<&str>,
+    json_output: bool,
+) -> Result<()> {
+    run_report(
+        repo,
+        target,
+        json_output,
+        |store| {
+            reports::cached(store, "handoffs", &(weeks, window), || {
+                // Transitions live in card metadata, so load full cards
+                let query = CardQuery {
+                    full: true,
+                    ..CardQuery::default()
+                };
+                let cards = store
+                    .iter_cards(query)?
+                    .map(|item| item.map(|(_, card)| card))
+                    .collect::<kuk::error::Result<Vec<_>>>()?;
+                let cal = sync::load_calendar(store)?;
+                Ok(reports::calculate_handoffs(&cards, weeks, window, &cal))
+            })
+        },
+        reports::render_handoffs_text,
+    )
+}
+
+// ─── WIP history ─────────────────────────────────────────────
+
+pub fn wip_history(
+    repo: &Path,
+    window: &ReportWindow,
+    target: Option<&str>,
+    json_output: bool,
+) -> Result<()> {
+    run_report(
+        repo,
+        target,
+        json_output,
+        |store| {
+            reports::cached(store, "wip-history", window, || {
+                // Transitions live in card metadata, so load full boards
+                let boards = store
+                    .list_boards()?
+                    .iter()
+                    .map(|name| store.load_board(name))
+                    .collect::<kuk::error::Result<Vec<_>>>()?;
+                let cal = sync::load_calendar(store)?;
+                Ok(reports::calculate_wip_history(
+                    &boards,
+                    window,
+                    &cal,
+                    chrono::Utc::now(),
+                ))
+            })
+        },
+        reports::render_wip_history_text,
+    )
+}
+
+// ─── Report targets ──────────────────────────────────────────
+
+/// Repos a report runs against, from `--target`.
+#[derive(Debug, PartialEq)]
+enum Target {
+    Repo(PathBuf),
+    /// Every project in the global index.
+    All,
+}
+
+/// `--target` takes `all`, a repo path, or the name of an indexed project.
+/// Without it the report covers `repo`.
+fn resolve_target(repo: &Path, target: Option<&str>) -> Result<Target> {
+    let Some(target) = target else {
+        return Ok(Target::Repo(repo.to_path_buf()));
+    };
+    if target == "all" {
+        return Ok(Target::All);
+    }
+    let path = Path::new(target);
+    if path.exists() {
+        return Ok(Target::Repo(path.to_path_buf()));
+    }
+    Store::load_global_index()
+        .unwrap_or_default()
+        .projects
+        .into_iter()
+        .find(|p| p.name == target)
+        .map(|p| Target::Repo(p.path.into()))
+        .ok_or_else(|| {
+            PmError::Other(format!(
+                "No repo or kuk project named {target} (see `kuk-pm projects`)"
+            ))
+        })
 }
 
-fn load_all_boards(store: &Store) -> Result<Vec<kuk::model::Board>> {
-    let board_names = store.list_boards()?;
-    let mut boards = Vec::new();
-    for name in &board_names {
-        boards.push(store.load_board(name)?);
+/// Build and print a report for each target repo. With `all`, projects are
+/// reported in index order, and a project whose report fails shows its
+/// error instead of stopping the rest.
+fn run_report<T: Serialize>(
+    repo: &Path,
+    target: Option<&str>,
+    json_output: bool,
+    build: impl Fn(&Store) -> Result<T>,
+    render: impl Fn(&T, &Locale) -> String,
+) -> Result<()> {
+    let build_for = |path: &Path| {
+        let store = Store::new(path);
+        if !store.is_initialized() {
+            return Err(PmError::KukNotInitialized);
+        }
+        Ok((build(&store)?, store))
+    };
+    // Only text is localized, so JSON output never reads pm.json for it
+    let render_text = |report: &T, store: &Store| {
+        let locale = sync::load_pm_config(store)?.locale;
+        Ok::<_, PmError>(render(report, &locale))
+    };
+
+    let path = match resolve_target(repo, target)? {
+        Target::Repo(path) => path,
+        Target::All => {
+            let index = Store::load_global_index().unwrap_or_default();
+            let mut results = Vec::new();
+            for project in &index.projects {
+                let report = build_for(Path::new(&project.path));
+                if json_output {
+                    let mut entry = serde_json::json!({
+                        "project": project.name,
+                        "path": project.path,
+                    });
+                    match report {
+                        Ok((report, _)) => entry["report"] = serde_json::to_value(&report)?,
+                        Err(e) => entry["error"] = e.to_string().into(),
+                    }
+                    results.push(entry);
+                } else {
+                    println!("═══ {} ({}) ═══", project.name, project.path);
+                    match report.and_then(|(report, store)| render_text(&report, &store)) {
+                        Ok(text) => print!("{text}"),
+                        Err(e) => println!("  Error: {e}"),
+                    }
+                    println!();
+                }
+            }
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&results)?);
+            } else if index.projects.is_empty() {
+                println!("No kuk projects found. Run `kuk init` in a repo.");
+            }
+            return Ok(());
+        }
+    };
+
+    let (report, store) = build_for(&path)?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", render_text(&report, &store)?);
     }
-    Ok(boards)
+    Ok(())
 }
 
 #[cfg(test)]
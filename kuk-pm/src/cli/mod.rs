@@ -2,8 +2,11 @@ mod commands;
 
 pub use commands::Cli;
 pub use commands::Commands;
+use commands::SyncCmd;
 
-use crate::error::Result;
+use crate::error::{PmError, Result};
+use crate::reports::ReportWindow;
+use crate::sync::SyncScope;
 
 pub fn run(cli: Cli) -> Result<()> {
     let repo = cli.repo.unwrap_or_else(|| std::env::current_dir().unwrap());
@@ -12,28 +15,152 @@ pub fn run(cli: Cli) -> Result<()> {
     match cli.command {
         Some(Commands::Init) => commands::init(&repo),
         Some(Commands::Projects) => commands::projects(json_output),
-        Some(Commands::Sync { dry_run }) => commands::sync(&repo, dry_run, json_output),
-        Some(Commands::Link { card_id, url }) => commands::link(&repo, &card_id, &url, json_output),
+        Some(Commands::Sync {
+            command,
+            dry_run,
+            board,
+            label,
+            columns,
+            create_missing,
+        }) => match command {
+            Some(SyncCmd::Log { last }) => commands::sync_log(&repo, last, json_output),
+            None => {
+                let scope = SyncScope {
+                    board,
+                    labels: label,
+                    columns,
+                };
+                commands::sync(&repo, &scope, create_missing, dry_run, json_output)
+            }
+        },
+        Some(Commands::Link {
+            card_id,
+            url,
+            role,
+            no_fetch,
+        }) => commands::link(&repo, &card_id, &url, &role, no_fetch, json_output),
+        Some(Commands::Unlink { card_id, url, all }) => {
+            commands::unlink(&repo, &card_id, url.as_deref(), all, json_output)
+        }
+        Some(Commands::Comment {
+            card_id,
+            message,
+            url,
+        }) => commands::comment(&repo, &card_id, &message, url.as_deref(), json_output),
+        Some(Commands::Meta { card_id }) => commands::meta(&repo, &card_id, json_output),
         Some(Commands::Branch { card_id }) => commands::branch(&repo, &card_id, json_output),
-        Some(Commands::Pr { card_id }) => commands::pr(&repo, &card_id, json_output),
-        Some(Commands::Velocity { weeks, target }) => {
-            commands::velocity(&repo, weeks, target.as_deref(), json_output)
+        Some(Commands::Pr { card_id, role }) => commands::pr(&repo, &card_id, &role, json_output),
+        Some(Commands::Export {
+            command:
+                commands::ExportCmd::Card {
+                    card_id,
+                    format,
+                    create,
+                },
+        }) => commands::export_card(&repo, &card_id, format, create, json_output),
+        Some(Commands::Velocity {
+            weeks,
+            target,
+            since,
+            until,
+            compare_previous,
+            oneline,
+            normalize,
+        }) => {
+            let window = report_window(since, until)?;
+            commands::velocity(
+                &repo,
+                weeks,
+                target.as_deref(),
+                &window,
+                compare_previous,
+                normalize,
+                oneline,
+                json_output,
+            )
         }
-        Some(Commands::Burndown { sprint }) => {
-            commands::burndown(&repo, sprint.as_deref(), json_output)
+        Some(Commands::Burndown {
+            sprint,
+            target,
+            oneline,
+        }) => commands::burndown(
+            &repo,
+            sprint.as_deref(),
+            target.as_deref(),
+            oneline,
+            json_output,
+        ),
+        Some(Commands::Roadmap { weeks, target }) => {
+            commands::roadmap(&repo, weeks, target.as_deref(), json_output)
         }
-        Some(Commands::Roadmap { weeks }) => commands::roadmap(&repo, weeks, json_output),
-        Some(Commands::ReleaseNotes { since }) => {
-            commands::release_notes(&repo, since.as_deref(), json_output)
+        Some(Commands::ReleaseNotes {
+            since,
+            to,
+            exclude,
+            pre_release,
+            contributors,
+        }) => commands::release_notes(
+            &repo,
+            since.as_deref(),
+            &to,
+            &exclude,
+            pre_release,
+            contributors,
+            json_output,
+        ),
+        Some(Commands::Tags { pre_release }) => commands::tags(&repo, pre_release, json_output),
+        Some(Commands::BackfillLinks { since, dry_run }) => {
+            commands::backfill_links(&repo, since.as_deref(), dry_run, json_output)
         }
         Some(Commands::Sprint { command }) => commands::sprint(&repo, command, json_output),
-        Some(Commands::Stats) => commands::stats(&repo, json_output),
-        Some(Commands::Mcp) => {
+        Some(Commands::Okr { command }) => commands::okr(&repo, command, json_output),
+        Some(Commands::Calendar { command }) => commands::calendar(&repo, command, json_output),
+        Some(Commands::Stats {
+            since,
+            until,
+            compare_previous,
+            history,
+            weeks,
+            target,
+        }) => {
+            let window = report_window(since, until)?;
+            commands::stats(
+                &repo,
+                &window,
+                compare_previous,
+                history.then_some(weeks),
+                target.as_deref(),
+                json_output,
+            )
+        }
+        Some(Commands::Handoffs {
+            weeks,
+            since,
+            until,
+            target,
+        }) => {
+            let window = report_window(since, until)?;
+            commands::handoffs(&repo, weeks, &window, target.as_deref(), json_output)
+        }
+        Some(Commands::WipHistory {
+            since,
+            until,
+            target,
+        }) => {
+            let window = report_window(since, until)?;
+            commands::wip_history(&repo, &window, target.as_deref(), json_output)
+        }
+        Some(Commands::Tui) => crate::tui::run_tui(&repo),
+        Some(Commands::Mcp { with_board }) => {
             let store = kuk::storage::Store::new(&repo);
-            crate::mcp_stdio::run(&store, &repo)
+            crate::mcp_stdio::run(&store, &repo, with_board)
         }
         Some(Commands::Doctor) => commands::doctor(&repo),
         Some(Commands::Version) => commands::version(),
         None => commands::default_action(),
     }
 }
+
+fn report_window(since: Option<String>, until: Option<String>) -> Result<ReportWindow> {
+    ReportWindow::parse(since.as_deref(), until.as_deref()).map_err(PmError::InvalidDate)
+}
@@ -17,6 +17,13 @@ pub enum PmError {
     #[error("Git error: {0}")]
     Git(String),
 
+    #[error("Git: failed to {op}")]
+    GitOp {
+        op: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
     #[error("Card not found: {0}")]
     CardNotFound(String),
 
@@ -29,6 +36,12 @@ pub enum PmError {
     #[error("Sprint already closed: {0}")]
     SprintAlreadyClosed(String),
 
+    #[error("Objective not found: {0}")]
+    ObjectiveNotFound(String),
+
+    #[error("Objective already exists: {0}")]
+    ObjectiveAlreadyExists(String),
+
     #[error("No active sprint found")]
     NoActiveSprint,
 
@@ -38,6 +51,17 @@ pub enum PmError {
     #[error("Not yet implemented: {0}")]
     NotImplemented(String),
 
+    /// A forge API call (`gh`, `glab`) that failed.
+    #[error("{message}")]
+    Forge {
+        message: String,
+        /// The HTTP status, when the call got a response.
+        status: Option<u16>,
+        /// How long the forge asked callers to wait, from `Retry-After` or
+        /// an exhausted rate limit.
+        retry_after: Option<std::time::Duration>,
+    },
+
     #[error(transparent)]
     Kuk(#[from] kuk::error::KukError),
 
@@ -51,4 +75,59 @@ pub enum PmError {
     Other(String),
 }
 
+impl PmError {
+    /// A git failure with the operation that was attempted.
+    pub fn git<E>(op: impl Into<String>) -> impl FnOnce(E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let op = op.into();
+        move |source| PmError::GitOp {
+            op,
+            source: source.into(),
+        }
+    }
+
+    /// Whether the same forge call might succeed later: server errors,
+    /// rate limits, and connections that failed or timed out.
+    pub fn is_transient(&self) -> bool {
+        let PmError::Forge {
+            message,
+            status,
+            retry_after,
+        } = self
+        else {
+            return false;
+        };
+        let message = message.to_lowercase();
+        match status {
+            Some(status) => {
+                *status >= 500
+                    || *status == 429
+                    || retry_after.is_some()
+                    || message.contains("rate limit")
+            }
+            None => ["rate limit", "timed out", "timeout", "error connecting"]
+                .iter()
+                .any(|m| message.contains(m)),
+        }
+    }
+
+    /// What the user can do about the error, where there is something.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            PmError::Kuk(e) => e.hint(),
+            PmError::NotGitRepo => {
+                Some("Run kuk-pm inside a git repository, or pass --repo.".into())
+            }
+            PmError::GitOp { op, .. } if op.contains("HEAD") => {
+                Some("Make sure the repository has at least one commit.".into())
+            }
+            PmError::CardNotFound(_) => Some("Run `kuk list` to see card numbers.".into()),
+            PmError::SprintNotFound(_) => Some("Run `kuk-pm sprint list` to see sprints.".into()),
+            _ => None,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, PmError>;
@@ -1,36 +1,81 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde::Serialize;
 
 use crate::error::{PmError, Result};
 
+mod trailers;
+
+pub use trailers::{CARD_TRAILERS, CLOSING_TRAILERS, CardRef, card_refs, parse_trailers};
+
 /// Information about a git commit.
 #[derive(Debug, Clone)]
 pub struct CommitInfo {
     pub sha: String,
     pub message: String,
     pub author: String,
+    pub email: String,
     pub time: i64,
 }
 
+impl CommitInfo {
+    /// Who wrote the commit, for telling authors apart: the lowercased
+    /// email, or the name when there is no email.
+    pub fn author_key(&self) -> String {
+        if self.email.is_empty() {
+            self.author.clone()
+        } else {
+            self.email.to_lowercase()
+        }
+    }
+}
+
+fn commit_info(info: gix::revision::walk::Info<'_>) -> Result<CommitInfo> {
+    let commit = info.object().map_err(PmError::git("read commit"))?;
+    let author = commit.author().ok();
+    Ok(CommitInfo {
+        sha: info.id.to_string(),
+        message: commit.message_raw_sloppy().to_string(),
+        author: author.map(|a| a.name.to_string()).unwrap_or_default(),
+        email: author.map(|a| a.email.to_string()).unwrap_or_default(),
+        time: commit.time().map(|t| t.seconds).unwrap_or(0),
+    })
+}
+
+/// Discover the repository containing `path`.
+fn open(path: &Path) -> Result<gix::Repository> {
+    gix::discover(path).map_err(PmError::git(format!(
+        "open repository at {}",
+        path.display()
+    )))
+}
+
 /// Check if a path is inside a git repository.
 pub fn is_git_repo(path: &Path) -> bool {
     gix::discover(path).is_ok()
 }
 
 /// Get the current branch name, or None if HEAD is detached.
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
 pub fn current_branch(path: &Path) -> Result<Option<String>> {
-    let repo = gix::discover(path).map_err(|e| PmError::Git(e.to_string()))?;
-    match repo.head_ref().map_err(|e| PmError::Git(e.to_string()))? {
+    let repo = open(path)?;
+    match repo.head_ref().map_err(PmError::git("read HEAD"))? {
         Some(reference) => Ok(Some(reference.name().shorten().to_string())),
         None => Ok(None),
     }
 }
 
 /// Create a new branch pointing at HEAD.
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
 pub fn create_branch(path: &Path, name: &str) -> Result<()> {
-    let repo = gix::discover(path).map_err(|e| PmError::Git(e.to_string()))?;
+    let repo = open(path)?;
     let head = repo
         .head_commit()
-        .map_err(|e| PmError::Git(e.to_string()))?;
+        .map_err(PmError::git("resolve HEAD commit"))?;
     let ref_name = format!("refs/heads/{name}");
 
     // Check if branch already exists
@@ -44,100 +89,307 @@ pub fn create_branch(path: &Path, name: &str) -> Result<()> {
         gix::refs::transaction::PreviousValue::MustNotExist,
         format!("kuk-pm: create branch {name}"),
     )
-    .map_err(|e| PmError::Git(e.to_string()))?;
+    .map_err(PmError::git(format!("create branch {name}")))?;
     Ok(())
 }
 
 /// Get the N most recent commits from HEAD.
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
 pub fn recent_commits(path: &Path, count: usize) -> Result<Vec<CommitInfo>> {
-    let repo = gix::discover(path).map_err(|e| PmError::Git(e.to_string()))?;
+    let repo = open(path)?;
     let head = repo
         .head_commit()
-        .map_err(|e| PmError::Git(e.to_string()))?;
+        .map_err(PmError::git("resolve HEAD commit"))?;
 
     let mut commits = Vec::new();
 
     for ancestor in head
         .ancestors()
         .all()
-        .map_err(|e| PmError::Git(e.to_string()))?
+        .map_err(PmError::git("walk history"))?
         .take(count)
     {
-        let info = ancestor.map_err(|e| PmError::Git(e.to_string()))?;
-        let commit = info.object().map_err(|e| PmError::Git(e.to_string()))?;
-
-        commits.push(CommitInfo {
-            sha: info.id.to_string(),
-            message: commit.message_raw_sloppy().to_string(),
-            author: commit
-                .author()
-                .map(|a| a.name.to_string())
-                .unwrap_or_default(),
-            time: commit.time().map(|t| t.seconds).unwrap_or(0),
-        });
+        let info = ancestor.map_err(PmError::git("walk history"))?;
+        commits.push(commit_info(info)?);
     }
 
     Ok(commits)
 }
 
-/// List all tag names in the repository.
-pub fn list_tags(path: &Path) -> Result<Vec<String>> {
-    let repo = gix::discover(path).map_err(|e| PmError::Git(e.to_string()))?;
-    let references = repo.references().map_err(|e| PmError::Git(e.to_string()))?;
+/// A tag and what it is ordered by.
+#[derive(Debug, Clone, Serialize)]
+pub struct TagInfo {
+    pub name: String,
+    /// The version the name spells, with or without a leading `v`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<semver::Version>,
+    /// When the tag was made: the tagger time of an annotated tag, else the
+    /// time of the commit it points at.
+    pub time: i64,
+}
+
+impl TagInfo {
+    pub fn is_pre_release(&self) -> bool {
+        self.version.as_ref().is_some_and(|v| !v.pre.is_empty())
+    }
+}
+
+/// Parse a tag name like `v1.2.3` or `1.3.0-rc.1` as a version.
+fn parse_version(name: &str) -> Option<semver::Version> {
+    let bare = name.strip_prefix(['v', 'V']).unwrap_or(name);
+    semver::Version::parse(bare).ok()
+}
+
+/// Every tag in the repository, oldest first. Version tags are ordered by
+/// version, so `v1.10.0` follows `v1.9.0` and `v2.0.0-rc.1` precedes
+/// `v2.0.0`; other tags are ordered by when they were made and come before
+/// all version tags.
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
+pub fn tags(path: &Path) -> Result<Vec<TagInfo>> {
+    let repo = open(path)?;
+    let references = repo.references().map_err(PmError::git("list references"))?;
     let tag_refs = references
         .prefixed("refs/tags/")
-        .map_err(|e| PmError::Git(e.to_string()))?;
+        .map_err(PmError::git("list tags"))?;
 
     let mut tags = Vec::new();
     for reference in tag_refs {
-        let reference = reference.map_err(|e| PmError::Git(e.to_string()))?;
-        tags.push(reference.name().shorten().to_string());
+        let mut reference = reference.map_err(PmError::git("read tag"))?;
+        let name = reference.name().shorten().to_string();
+        let tagged = reference
+            .peel_to_tag()
+            .ok()
+            .and_then(|tag| Some(tag.tagger().ok()??.time.seconds));
+        let time = match tagged {
+            Some(time) => time,
+            None => reference
+                .peel_to_commit()
+                .ok()
+                .and_then(|commit| commit.time().ok())
+                .map_or(0, |t| t.seconds),
+        };
+        tags.push(TagInfo {
+            version: parse_version(&name),
+            name,
+            time,
+        });
     }
 
+    tags.sort_by(|a, b| match (&a.version, &b.version) {
+        (Some(x), Some(y)) => x.cmp(y).then(a.time.cmp(&b.time)),
+        (None, None) => a.time.cmp(&b.time).then_with(|| a.name.cmp(&b.name)),
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+    });
     Ok(tags)
 }
 
-/// Get commits between HEAD and a named ref (tag or branch).
-/// Walks ancestors of HEAD and stops when reaching the target ref's commit.
+/// List all tag names in the repository, oldest first (see [`tags`]).
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
+pub fn list_tags(path: &Path) -> Result<Vec<String>> {
+    Ok(tags(path)?.into_iter().map(|t| t.name).collect())
+}
+
+/// The most recent tag, which release notes start from by default.
+/// Pre-releases like `v2.0.0-rc.1` are passed over unless `pre_release`.
+pub fn latest_tag(path: &Path, pre_release: bool) -> Result<Option<String>> {
+    Ok(tags(path)?
+        .into_iter()
+        .rev()
+        .find(|t| pre_release || !t.is_pre_release())
+        .map(|t| t.name))
+}
+
+/// Get commits between HEAD and a named ref (tag or branch): those on
+/// HEAD that the ref doesn't contain.
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
 pub fn commits_since_ref(path: &Path, ref_name: &str) -> Result<Vec<CommitInfo>> {
-    let repo = gix::discover(path).map_err(|e| PmError::Git(e.to_string()))?;
+    commits_in_range(path, &[ref_name], "HEAD")
+}
 
-    // Try multiple ref formats
-    let target_id = repo
-        .find_reference(&format!("refs/tags/{ref_name}"))
-        .or_else(|_| repo.find_reference(&format!("refs/heads/{ref_name}")))
-        .or_else(|_| repo.find_reference(ref_name))
-        .map_err(|e| PmError::Git(format!("ref not found '{ref_name}': {e}")))?
-        .id()
-        .detach();
+/// Resolve a tag, branch, sha or other revision to the commit it points at.
+fn resolve_commit(repo: &gix::Repository, rev: &str) -> Result<gix::ObjectId> {
+    let commit = repo
+        .rev_parse_single(rev)
+        .map_err(PmError::git(format!("find ref '{rev}'")))?
+        .object()
+        .map_err(PmError::git(format!("read '{rev}'")))?
+        .peel_to_commit()
+        .map_err(PmError::git(format!("resolve '{rev}' to a commit")))?;
+    Ok(commit.id)
+}
 
-    let head = repo
-        .head_commit()
-        .map_err(|e| PmError::Git(e.to_string()))?;
+/// The sha HEAD points at.
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
+pub fn head_sha(path: &Path) -> Result<String> {
+    Ok(resolve_commit(&open(path)?, "HEAD")?.to_string())
+}
+
+/// Commits reachable from `to` but from none of `hidden`, newest first, as
+/// in `git log ^hidden to`. Merges and their side branches are followed.
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
+pub fn commits_in_range(path: &Path, hidden: &[&str], to: &str) -> Result<Vec<CommitInfo>> {
+    let repo = open(path)?;
+    let tip = resolve_commit(&repo, to)?;
+
+    let mut seen = HashSet::new();
+    for rev in hidden {
+        let id = resolve_commit(&repo, rev)?;
+        let reachable = repo
+            .rev_walk([id])
+            .selected(|id| !seen.contains(id))
+            .map_err(PmError::git("walk history"))?
+            .map(|info| info.map(|info| info.id))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(PmError::git("walk history"))?;
+        seen.extend(reachable);
+    }
 
     let mut commits = Vec::new();
-    for ancestor in head
-        .ancestors()
-        .all()
-        .map_err(|e| PmError::Git(e.to_string()))?
+    for ancestor in repo
+        .rev_walk([tip])
+        .selected(|id| !seen.contains(id))
+        .map_err(PmError::git("walk history"))?
     {
-        let info = ancestor.map_err(|e| PmError::Git(e.to_string()))?;
-        if info.id == target_id {
-            break;
+        let info = ancestor.map_err(PmError::git("walk history"))?;
+        commits.push(commit_info(info)?);
+    }
+
+    Ok(commits)
+}
+
+/// Everyone who authored a commit on `to` before the release range that
+/// [`release_range`] picks for `from`, by [`CommitInfo::author_key`].
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
+pub fn earlier_authors(path: &Path, from: Option<&str>, to: &str) -> Result<HashSet<String>> {
+    let earlier = match from {
+        Some(from) => commits_in_range(path, &[], from)?,
+        None => commits_in_range(path, &[], to)?
+            .into_iter()
+            .skip(UNTAGGED_RELEASE_COMMITS)
+            .collect(),
+    };
+    Ok(earlier.iter().map(CommitInfo::author_key).collect())
+}
+
+/// The stable patch ID of every non-merge commit `git log` lists for
+/// `revs`, keyed by sha.
+/// Two commits with the same patch ID make the same change, which is how a
+/// cherry-pick is recognized. gix has no patch IDs, so this runs
+/// `git log -p | git patch-id --stable`.
+fn patch_ids(path: &Path, revs: &[&str]) -> Result<HashMap<String, String>> {
+    let run_err = |e: std::io::Error| PmError::Git(format!("failed to run git: {e}"));
+    let log = Command::new("git")
+        .args(["log", "-p", "--no-merges", "--format=commit %H"])
+        .args(revs)
+        .current_dir(path)
+        .output()
+        .map_err(run_err)?;
+    if !log.status.success() {
+        return Err(PmError::Git(format!(
+            "git log {}: {}",
+            revs.join(" "),
+            String::from_utf8_lossy(&log.stderr).trim()
+        )));
+    }
+
+    let mut child = Command::new("git")
+        .args(["patch-id", "--stable"])
+        .current_dir(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(run_err)?;
+    // Write from another thread so a full stdout pipe can't block us
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = std::thread::spawn(move || stdin.write_all(&log.stdout));
+    let output = child.wait_with_output().map_err(run_err)?;
+    writer
+        .join()
+        .expect("patch-id writer panicked")
+        .map_err(run_err)?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (patch_id, sha) = line.split_once(' ')?;
+            Some((sha.to_string(), patch_id.to_string()))
+        })
+        .collect())
+}
+
+/// How many of the latest commits release notes cover when there is no
+/// starting point.
+const UNTAGGED_RELEASE_COMMITS: usize = 50;
+
+/// The commits release notes cover: those in `from..to`, without what was
+/// already released on the `exclude` refs.
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseRange {
+    pub commits: Vec<CommitInfo>,
+    /// Commits in the range that are on an `exclude` ref, or make the same
+    /// change as one there (a cherry-pick).
+    pub released: Vec<CommitInfo>,
+}
+
+/// Commits on `to` since `from`, newest first, split into new ones and
+/// ones already released on one of `exclude`: either reachable from it, or
+/// cherry-picked onto it with the same patch ID. Without `from`, the 50 most
+/// recent commits on `to`.
+#[tracing::instrument(level = "debug", fields(path = %path.display()), skip(path))]
+pub fn release_range(
+    path: &Path,
+    from: Option<&str>,
+    to: &str,
+    exclude: &[String],
+) -> Result<ReleaseRange> {
+    let range = match from {
+        Some(from) => commits_in_range(path, &[from], to)?,
+        None => {
+            let mut all = commits_in_range(path, &[], to)?;
+            all.truncate(UNTAGGED_RELEASE_COMMITS);
+            all
         }
-        let commit = info.object().map_err(|e| PmError::Git(e.to_string()))?;
-        commits.push(CommitInfo {
-            sha: info.id.to_string(),
-            message: commit.message_raw_sloppy().to_string(),
-            author: commit
-                .author()
-                .map(|a| a.name.to_string())
-                .unwrap_or_default(),
-            time: commit.time().map(|t| t.seconds).unwrap_or(0),
+    };
+    if exclude.is_empty() {
+        return Ok(ReleaseRange {
+            commits: range,
+            released: Vec::new(),
         });
     }
 
-    Ok(commits)
+    let mut hidden: Vec<&str> = exclude.iter().map(String::as_str).collect();
+    hidden.extend(from);
+    let fresh: HashSet<String> = commits_in_range(path, &hidden, to)?
+        .into_iter()
+        .map(|c| c.sha)
+        .collect();
+
+    let mut released_ids = HashSet::new();
+    for other in exclude {
+        let not_on_to = format!("^{to}");
+        released_ids.extend(patch_ids(path, &[&not_on_to, other])?.into_values());
+    }
+    let candidates: Vec<&str> = range
+        .iter()
+        .filter(|c| fresh.contains(&c.sha))
+        .map(|c| c.sha.as_str())
+        .collect();
+    let ours = if candidates.is_empty() || released_ids.is_empty() {
+        HashMap::new()
+    } else {
+        let mut revs = vec!["--no-walk"];
+        revs.extend(candidates);
+        patch_ids(path, &revs)?
+    };
+
+    let (commits, released) = range.into_iter().partition(|c| {
+        fresh.contains(&c.sha)
+            && ours
+                .get(&c.sha)
+                .is_none_or(|patch_id| !released_ids.contains(patch_id))
+    });
+    Ok(ReleaseRange { commits, released })
 }
 
 #[cfg(test)]
@@ -273,4 +525,77 @@ mod tests {
         assert_eq!(commits.len(), 1);
         assert_eq!(commits[0].author, "Test");
     }
+
+    #[test]
+    fn release_range_leaves_out_cherry_picks() {
+        let dir = init_git_repo();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?} failed");
+        };
+        let commit = |file: &str, msg: &str| {
+            std::fs::write(dir.path().join(file), msg).unwrap();
+            git(&["add", file]);
+            git(&["commit", "-m", msg]);
+        };
+
+        git(&["tag", "v1.0.0"]);
+        git(&["branch", "release/1.0"]);
+        commit("search.txt", "feat: search");
+        commit("crash.txt", "fix: crash");
+        git(&["checkout", "-q", "release/1.0"]);
+        git(&["cherry-pick", "HEAD@{1}"]);
+        git(&["checkout", "-q", "-"]);
+
+        let maintenance = release_range(dir.path(), Some("v1.0.0"), "release/1.0", &[]).unwrap();
+        assert_eq!(maintenance.commits.len(), 1);
+        assert!(maintenance.commits[0].message.starts_with("fix: crash"));
+
+        let all = release_range(dir.path(), Some("v1.0.0"), "HEAD", &[]).unwrap();
+        assert_eq!(all.commits.len(), 2);
+
+        let fresh = release_range(
+            dir.path(),
+            Some("v1.0.0"),
+            "HEAD",
+            &["release/1.0".to_string()],
+        )
+        .unwrap();
+        let messages: Vec<&str> = fresh.commits.iter().map(|c| c.message.trim()).collect();
+        assert_eq!(messages, ["feat: search"]);
+        assert!(fresh.released[0].message.starts_with("fix: crash"));
+    }
+
+    #[test]
+    fn tags_sort_by_version() {
+        let dir = init_git_repo();
+        for args in [
+            &["tag", "v1.10.0"][..],
+            &["tag", "-a", "v1.9.0", "-m", "1.9"],
+            &["tag", "v2.0.0-rc.1"],
+            &["tag", "nightly"],
+        ] {
+            Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap();
+        }
+
+        let names = list_tags(dir.path()).unwrap();
+        assert_eq!(names, ["nightly", "v1.9.0", "v1.10.0", "v2.0.0-rc.1"]);
+        assert!(tags(dir.path()).unwrap().iter().all(|t| t.time > 0));
+        assert_eq!(
+            latest_tag(dir.path(), false).unwrap().as_deref(),
+            Some("v1.10.0")
+        );
+        assert_eq!(
+            latest_tag(dir.path(), true).unwrap().as_deref(),
+            Some("v2.0.0-rc.1")
+        );
+    }
 }
@@ -0,0 +1,101 @@
+//! Commit message trailers that tie a commit to a card, like `Kuk-Card: #42`
+//! or `Closes: #17`, so commits link up with cards even on branches kuk-pm
+//! didn't create.
+
+/// Trailer keys that name a card.
+pub const CARD_TRAILERS: &[&str] = &["Kuk-Card"];
+
+/// Trailer keys that name an issue the commit closes.
+pub const CLOSING_TRAILERS: &[&str] = &["Closes", "Fixes", "Resolves"];
+
+/// Something a trailer points at that may be a card.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CardRef {
+    /// A card ID, short ID or number, as `kuk` resolves it.
+    Card(String),
+    /// An issue number, matched against the issues linked to cards.
+    Issue(String),
+    /// An issue or PR URL, matched against the links on cards.
+    Url(String),
+}
+
+/// The `Key: value` trailers of a commit message: the lines of its last
+/// paragraph, when that isn't the subject and every line is a trailer.
+pub fn parse_trailers(message: &str) -> Vec<(String, String)> {
+    let paragraphs: Vec<&str> = message
+        .trim()
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    let [_, .., last] = paragraphs.as_slice() else {
+        return Vec::new();
+    };
+    let trailers: Option<Vec<(String, String)>> = last
+        .lines()
+        .map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let key = key.trim();
+            let valid =
+                !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            valid.then(|| (key.to_string(), value.trim().to_string()))
+        })
+        .collect();
+    trailers.unwrap_or_default()
+}
+
+/// Every card reference in a commit message's trailers, in order.
+pub fn card_refs(message: &str) -> Vec<CardRef> {
+    let is = |keys: &[&str], key: &str| keys.iter().any(|k| k.eq_ignore_ascii_case(key));
+    let mut refs = Vec::new();
+    for (key, value) in parse_trailers(message) {
+        let values = value
+            .split([',', ' '])
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        if is(CARD_TRAILERS, &key) {
+            refs.extend(values.map(|v| CardRef::Card(v.trim_start_matches('#').into())));
+        } else if is(CLOSING_TRAILERS, &key) {
+            refs.extend(values.filter_map(|v| {
+                if v.starts_with("http://") || v.starts_with("https://") {
+                    Some(CardRef::Url(v.into()))
+                } else {
+                    let number = v.strip_prefix('#')?;
+                    number
+                        .chars()
+                        .all(|c| c.is_ascii_digit())
+                        .then(|| CardRef::Issue(number.into()))
+                }
+            }));
+        }
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_trailers_from_the_last_paragraph() {
+        let message = "Fix login redirect\n\nThe proxy strips the path: we keep it.\n\nKuk-Card: #42\nCloses: #17, https://github.com/o/r/issues/18\nSigned-off-by: Ana <ana@example.com>\n";
+        assert_eq!(
+            card_refs(message),
+            [
+                CardRef::Card("42".into()),
+                CardRef::Issue("17".into()),
+                CardRef::Url("https://github.com/o/r/issues/18".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_subjects_and_prose() {
+        assert!(parse_trailers("Closes: #17").is_empty());
+        assert!(parse_trailers("Subject\n\nNote: this is prose\nthat wraps").is_empty());
+        assert_eq!(
+            card_refs("Subject\n\nkuk-card: 01JMXK3V"),
+            [CardRef::Card("01JMXK3V".into())]
+        );
+    }
+}
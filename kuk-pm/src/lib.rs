@@ -5,3 +5,4 @@ pub mod mcp_stdio;
 pub mod model;
 pub mod reports;
 pub mod sync;
+pub mod tui;
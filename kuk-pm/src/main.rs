@@ -2,8 +2,9 @@ use clap::Parser;
 
 fn main() {
     let cli = kuk_pm::cli::Cli::parse();
+    kuk::logging::init(cli.verbose);
     if let Err(e) = kuk_pm::cli::run(cli) {
-        eprintln!("Error: {e}");
+        eprintln!("{}", kuk::error::render_report(&e, e.hint().as_deref()));
         std::process::exit(1);
     }
 }
@@ -2,8 +2,10 @@
 //!
 //! Reads JSON-RPC 2.0 messages from stdin (one per line),
 //! processes them, and writes responses to stdout.
-//! Exposes project management tools: stats, velocity, burndown,
-//! roadmap, sprints, release notes, sync, and linking.
+//! Exposes project management tools: an overview, stats, velocity, burndown,
+//! roadmap, sprints, release notes, sync, and linking. With `--with-board`
+//! it also serves kuk's `kuk_*` board tools, so an agent needs one server
+//! per repo instead of two.
 
 use std::io::{self, BufRead, Write};
 use std::path::Path;
@@ -14,10 +16,11 @@ use serde_json::Value;
 
 use crate::error::PmError;
 use crate::git;
-use crate::model::{Sprint, SprintStatus};
+use crate::model::{LinkKind, LinkRole, Sprint, SprintStatus};
 use crate::reports;
+use crate::reports::{Compared, ReportWindow};
 use crate::sync;
-use kuk::model::Board;
+use kuk::mcp_stdio::BoardTools;
 use kuk::storage::Store;
 
 #[derive(Debug, Deserialize)]
@@ -69,12 +72,14 @@ impl JsonRpcResponse {
     }
 }
 
-/// Run the stdio MCP server loop. Blocks until stdin is closed.
-pub fn run(store: &Store, repo: &Path) -> crate::error::Result<()> {
+/// Run the stdio MCP server loop, with kuk's board tools too when
+/// `with_board` is set. Blocks until stdin is closed.
+pub fn run(store: &Store, repo: &Path, with_board: bool) -> crate::error::Result<()> {
     let stdin = io::stdin();
     let stdout = io::stdout();
     let reader = stdin.lock();
     let mut writer = stdout.lock();
+    let mut board = with_board.then(|| BoardTools::new(store));
 
     for line in reader.lines() {
         let line = match line {
@@ -90,8 +95,7 @@ pub fn run(store: &Store, repo: &Path) -> crate::error::Result<()> {
         let req: JsonRpcRequest = match serde_json::from_str(trimmed) {
             Ok(r) => r,
             Err(e) => {
-                let resp =
-                    JsonRpcResponse::error(Value::Null, -32700, format!("Parse error: {e}"));
+                let resp = JsonRpcResponse::error(Value::Null, -32700, format!("Parse error: {e}"));
                 let _ = writeln!(writer, "{}", serde_json::to_string(&resp).unwrap());
                 let _ = writer.flush();
                 continue;
@@ -102,10 +106,23 @@ pub fn run(store: &Store, repo: &Path) -> crate::error::Result<()> {
         let id = req.id.clone().unwrap_or(Value::Null);
 
         let response = match req.method.as_str() {
-            "initialize" => Some(handle_initialize(id)),
+            "initialize" => {
+                if let Some(board) = &mut board {
+                    board.set_client(req.params["clientInfo"]["name"].as_str());
+                }
+                Some(handle_initialize(id))
+            }
             "notifications/initialized" | "initialized" => None,
-            "tools/list" => Some(handle_tools_list(id)),
-            "tools/call" => Some(handle_tools_call(id, &req.params, store, repo)),
+            "tools/list" => Some(handle_tools_list(id, board.is_some())),
+            "tools/call" => match &mut board {
+                Some(board) if BoardTools::has(req.params["name"].as_str().unwrap_or("")) => {
+                    Some(match board.call(&req.params) {
+                        Ok(result) => JsonRpcResponse::success(id, result),
+                        Err((code, message)) => JsonRpcResponse::error(id, code, message),
+                    })
+                }
+                _ => Some(handle_tools_call(id, &req.params, store, repo)),
+            },
             "ping" => Some(JsonRpcResponse::success(id, serde_json::json!({}))),
             _ => {
                 if is_notification {
@@ -145,15 +162,30 @@ fn handle_initialize(id: Value) -> JsonRpcResponse {
     )
 }
 
-fn handle_tools_list(id: Value) -> JsonRpcResponse {
-    let tools = serde_json::json!({
+fn handle_tools_list(id: Value, with_board: bool) -> JsonRpcResponse {
+    let mut tools = serde_json::json!({
         "tools": [
+            {
+                "name": "pm_overview",
+                "description": "Orient yourself in a workspace: headline stats for every board, the active sprint's status, and the top risks (sprint behind, columns over their WIP limit, growing queues, overdue and stale cards). A good first call.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "all_projects": {"type": "boolean", "description": "Cover every project in the global kuk index instead of this repo"},
+                        "max_risks": {"type": "number", "description": "Risks to list per project (default: 5)"}
+                    }
+                }
+            },
             {
                 "name": "pm_stats",
                 "description": "Show project statistics (cards per column, label distribution, aging)",
                 "inputSchema": {
                     "type": "object",
-                    "properties": {}
+                    "properties": {
+                        "since": {"type": "string", "description": "Only count cards completed on or after this date (YYYY-MM-DD)"},
+                        "until": {"type": "string", "description": "Measure as of this date (YYYY-MM-DD, default today)"},
+                        "compare_previous": {"type": "boolean", "description": "Also report the preceding equal-length window and the change"}
+                    }
                 }
             },
             {
@@ -162,7 +194,11 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "weeks": {"type": "number", "description": "Number of weeks to analyze (default: 4)"}
+                        "weeks": {"type": "number", "description": "Number of weeks to analyze (default: 4)"},
+                        "since": {"type": "string", "description": "Start of the reporting window (YYYY-MM-DD); overrides weeks"},
+                        "until": {"type": "string", "description": "End of the reporting window (YYYY-MM-DD, default today)"},
+                        "compare_previous": {"type": "boolean", "description": "Also report the preceding equal-length window and the change"},
+                        "normalize": {"type": "boolean", "description": "Also count each week's commit authors and report cards per contributor-week"}
                     }
                 }
             },
@@ -202,7 +238,8 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                     "properties": {
                         "name": {"type": "string", "description": "Sprint name"},
                         "start": {"type": "string", "description": "Start date (YYYY-MM-DD)"},
-                        "end": {"type": "string", "description": "End date (YYYY-MM-DD)"}
+                        "end": {"type": "string", "description": "End date (YYYY-MM-DD)"},
+                        "github_milestone": {"type": "boolean", "description": "Also create a GitHub milestone due on the end date (default: false)"}
                     },
                     "required": ["name", "start", "end"]
                 }
@@ -213,7 +250,10 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "name": {"type": "string", "description": "Sprint name"}
+                        "name": {"type": "string", "description": "Sprint name"},
+                        "create_board": {"type": "boolean", "description": "Create a board for the sprint with the default board's columns; burndown and stats then cover it (default: false)"},
+                        "move_cards": {"type": "array", "items": {"type": "string"}, "description": "Cards to move from the default board onto the sprint board (with create_board)"},
+                        "copy_cards": {"type": "array", "items": {"type": "string"}, "description": "Cards to copy from the default board onto the sprint board (with create_board)"}
                     },
                     "required": ["name"]
                 }
@@ -236,7 +276,9 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                     "type": "object",
                     "properties": {
                         "card_id": {"type": "string", "description": "Card ID or short number (e.g. #1)"},
-                        "url": {"type": "string", "description": "GitHub issue or PR URL"}
+                        "url": {"type": "string", "description": "GitHub issue or PR URL"},
+                        "role": {"type": "string", "enum": ["implements", "fixes", "reverts"], "description": "How the link relates to the card (default: implements)"},
+                        "fetch": {"type": "boolean", "description": "Copy the issue's assignee and labels onto the card (default: true)"}
                     },
                     "required": ["card_id", "url"]
                 }
@@ -247,7 +289,11 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "since": {"type": "string", "description": "Starting point - tag or ref (default: last tag)"}
+                        "since": {"type": "string", "description": "Starting point - tag or ref (default: last tag)"},
+                        "to": {"type": "string", "description": "End point - tag, branch or ref (default: HEAD)"},
+                        "exclude": {"type": "array", "items": {"type": "string"}, "description": "Branches or tags whose commits, and cherry-picks of them, were already released"},
+                        "pre_release": {"type": "boolean", "description": "Let a pre-release tag be the last tag (default: false)"},
+                        "contributors": {"type": "boolean", "description": "Add commit totals per author and mark first-time contributors (default: false)"}
                     }
                 }
             },
@@ -257,31 +303,34 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "dry_run": {"type": "boolean", "description": "Preview changes without applying (default: false)"}
+                        "dry_run": {"type": "boolean", "description": "Preview changes without applying (default: false)"},
+                        "board": {"type": "string", "description": "Board to sync (default: the default board)"},
+                        "labels": {"type": "array", "items": {"type": "string"}, "description": "Only sync cards with one of these labels"},
+                        "columns": {"type": "array", "items": {"type": "string"}, "description": "Only sync cards in these columns"},
+                        "create_missing": {"type": "boolean", "description": "Create GitHub issues for cards with no linked issue (default: false)"}
                     }
                 }
             }
         ]
     });
+    if with_board && let Some(list) = tools["tools"].as_array_mut() {
+        list.extend(BoardTools::definitions());
+    }
     JsonRpcResponse::success(id, tools)
 }
 
-fn handle_tools_call(
-    id: Value,
-    params: &Value,
-    store: &Store,
-    repo: &Path,
-) -> JsonRpcResponse {
+fn handle_tools_call(id: Value, params: &Value, store: &Store, repo: &Path) -> JsonRpcResponse {
     let tool_name = params["name"].as_str().unwrap_or("");
     let args = &params["arguments"];
 
     match tool_name {
-        "pm_stats" => tool_stats(id, store),
+        "pm_overview" => tool_overview(id, args, store),
+        "pm_stats" => tool_stats(id, args, store),
         "pm_velocity" => tool_velocity(id, args, store),
         "pm_burndown" => tool_burndown(id, args, store),
         "pm_roadmap" => tool_roadmap(id, args, store),
         "pm_sprint_list" => tool_sprint_list(id, store),
-        "pm_sprint_create" => tool_sprint_create(id, args, store),
+        "pm_sprint_create" => tool_sprint_create(id, args, store, repo),
         "pm_sprint_start" => tool_sprint_start(id, args, store),
         "pm_sprint_end" => tool_sprint_end(id, args, store),
         "pm_link" => tool_link(id, args, store),
@@ -314,34 +363,101 @@ fn save_sprints(store: &Store, sprints: &[Sprint]) -> Result<(), PmError> {
     Ok(())
 }
 
-fn load_all_boards(store: &Store) -> Result<Vec<Board>, PmError> {
-    let board_names = store.list_boards()?;
-    let mut boards = Vec::new();
-    for name in &board_names {
-        boards.push(store.load_board(name)?);
+// ─── Tool implementations ────────────────────────────────────
+
+fn tool_overview(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
+    let max_risks = args["max_risks"].as_u64().unwrap_or(5) as usize;
+    if !args["all_projects"].as_bool().unwrap_or(false) {
+        if !store.is_initialized() {
+            return JsonRpcResponse::error(id, -32603, "kuk not initialized");
+        }
+        return report_response(id, build_overview(store, max_risks));
     }
-    Ok(boards)
+
+    // A project that fails to load shows its error instead of stopping the rest
+    let index = Store::load_global_index().unwrap_or_default();
+    let projects: Vec<Value> = index
+        .projects
+        .iter()
+        .map(|project| {
+            let mut entry = serde_json::json!({
+                "project": project.name,
+                "path": project.path,
+            });
+            let store = Store::new(&project.path);
+            let overview = match store.is_initialized() {
+                true => build_overview(&store, max_risks),
+                false => Err(PmError::KukNotInitialized),
+            };
+            match overview.and_then(|o| Ok(serde_json::to_value(o)?)) {
+                Ok(overview) => entry["overview"] = overview,
+                Err(e) => entry["error"] = e.to_string().into(),
+            }
+            entry
+        })
+        .collect();
+    report_response(id, Ok(projects))
 }
 
-// ─── Tool implementations ────────────────────────────────────
+/// Every board of `store` plus the active sprint's burndown, summed up.
+fn build_overview(store: &Store, max_risks: usize) -> Result<reports::Overview, PmError> {
+    reports::cached(store, "overview", &max_risks, || {
+        let mut boards = Vec::new();
+        for name in store.list_boards()? {
+            let board = store.load_board_summary(&name)?;
+            // Queue trends need the moves kept in card metadata
+            if board.columns.iter().any(|c| c.queue) {
+                boards.push(store.load_board(&name)?);
+            } else {
+                boards.push(board);
+            }
+        }
+        let policy = sync::load_pm_config(store)?.cycle_outliers;
+        let cal = sync::load_calendar(store)?;
+        let burndown = match load_sprints(store)?
+            .iter()
+            .find(|s| s.status == SprintStatus::Active)
+        {
+            Some(sprint) => Some(reports::calculate_burndown(
+                &sync::sprint_cards(store, sprint)?,
+                sprint,
+                &cal,
+            )),
+            None => None,
+        };
+        Ok(reports::calculate_overview(
+            &boards,
+            burndown.as_ref(),
+            policy,
+            &cal,
+            chrono::Utc::now(),
+            max_risks,
+        ))
+    })
+}
 
-fn tool_stats(id: Value, store: &Store) -> JsonRpcResponse {
+fn tool_stats(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
     if !store.is_initialized() {
         return JsonRpcResponse::error(id, -32603, "kuk not initialized");
     }
 
-    let config = match store.load_config() {
-        Ok(c) => c,
-        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+    let window = match ReportWindow::parse(args["since"].as_str(), args["until"].as_str()) {
+        Ok(w) => w,
+        Err(e) => return JsonRpcResponse::error(id, -32602, e),
     };
-    let board = match store.load_board(&config.default_board) {
-        Ok(b) => b,
-        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
-    };
-
-    let report = reports::calculate_stats(&board);
-    let json = serde_json::to_string_pretty(&report).unwrap_or_default();
-    JsonRpcResponse::success(id, text_content(&json))
+    let compare = args["compare_previous"].as_bool().unwrap_or(false);
+
+    let report = reports::cached(store, "stats", &(window, compare), || {
+        let board = sync::stats_board(store)?;
+        let policy = sync::load_pm_config(store)?.cycle_outliers;
+        let cal = sync::load_calendar(store)?;
+        Ok(if compare {
+            Compared::Yes(reports::compare_stats(&board, policy, &window, &cal))
+        } else {
+            Compared::No(reports::calculate_stats(&board, policy, &window, &cal))
+        })
+    });
+    report_response(id, report)
 }
 
 fn tool_velocity(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
@@ -350,15 +466,47 @@ fn tool_velocity(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
     }
 
     let weeks = args["weeks"].as_u64().unwrap_or(4) as u32;
-
-    let boards = match load_all_boards(store) {
-        Ok(b) => b,
-        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+    let window = match ReportWindow::parse(args["since"].as_str(), args["until"].as_str()) {
+        Ok(w) => w,
+        Err(e) => return JsonRpcResponse::error(id, -32602, e),
     };
+    let compare = args["compare_previous"].as_bool().unwrap_or(false);
+    let normalize = args["normalize"].as_bool().unwrap_or(false);
 
-    let report = reports::calculate_velocity(&boards, weeks);
-    let json = serde_json::to_string_pretty(&report).unwrap_or_default();
-    JsonRpcResponse::success(id, text_content(&json))
+    let root = store.repo_root();
+    let head = match normalize {
+        true if !git::is_git_repo(root) => {
+            return JsonRpcResponse::error(id, -32603, "not a git repository");
+        }
+        true => match git::head_sha(root) {
+            Ok(sha) => Some(sha),
+            Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+        },
+        false => None,
+    };
+    let params = (weeks, window, compare, head);
+    let report = reports::cached(store, "velocity", &params, || {
+        let cards = store.active_cards()?;
+        let cal = sync::load_calendar(store)?;
+        let commits = match normalize {
+            true => git::commits_in_range(root, &[], "HEAD")?,
+            false => Vec::new(),
+        };
+        Ok(if compare {
+            let mut cmp = reports::compare_velocity(&cards, weeks, &window, &cal);
+            if normalize {
+                reports::normalize_velocity_comparison(&mut cmp, &commits, &cal);
+            }
+            Compared::Yes(cmp)
+        } else {
+            let mut report = reports::calculate_velocity(&cards, weeks, &window, &cal);
+            if normalize {
+                reports::normalize_velocity(&mut report, &commits, &cal);
+            }
+            Compared::No(report)
+        })
+    });
+    report_response(id, report)
 }
 
 fn tool_burndown(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
@@ -383,14 +531,12 @@ fn tool_burndown(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
         },
     };
 
-    let boards = match load_all_boards(store) {
-        Ok(b) => b,
-        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
-    };
-
-    let report = reports::calculate_burndown(&boards, sprint);
-    let json = serde_json::to_string_pretty(&report).unwrap_or_default();
-    JsonRpcResponse::success(id, text_content(&json))
+    let report = reports::cached(store, "burndown", &sprint.name, || {
+        let cards = sync::sprint_cards(store, sprint)?;
+        let cal = sync::load_calendar(store)?;
+        Ok(reports::calculate_burndown(&cards, sprint, &cal))
+    });
+    report_response(id, report)
 }
 
 fn tool_roadmap(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
@@ -400,25 +546,37 @@ fn tool_roadmap(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
 
     let weeks = args["weeks"].as_u64().unwrap_or(12) as u32;
 
-    let boards = match load_all_boards(store) {
-        Ok(b) => b,
-        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
-    };
-    let sprints = match load_sprints(store) {
-        Ok(s) => s,
-        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
-    };
+    let report = reports::cached(store, "roadmap", &weeks, || {
+        let cards = store.active_cards()?;
+        let sprints = load_sprints(store)?;
+        let cal = sync::load_calendar(store)?;
 
-    let vel_report = reports::calculate_velocity(&boards, 4);
-    let velocity = if vel_report.average > 0.0 {
-        vel_report.average
-    } else {
-        1.0
-    };
+        let vel_report = reports::calculate_velocity(&cards, 4, &ReportWindow::default(), &cal);
+        let velocity = if vel_report.average > 0.0 {
+            vel_report.average
+        } else {
+            1.0
+        };
+        Ok(reports::calculate_roadmap(
+            &store.all_cards()?,
+            &sprints,
+            weeks,
+            velocity,
+            &cal,
+        ))
+    });
+    report_response(id, report)
+}
 
-    let report = reports::calculate_roadmap(&boards, &sprints, weeks, velocity);
-    let json = serde_json::to_string_pretty(&report).unwrap_or_default();
-    JsonRpcResponse::success(id, text_content(&json))
+/// Reply with a report as pretty JSON text.
+fn report_response<T: Serialize>(id: Value, report: Result<T, PmError>) -> JsonRpcResponse {
+    match report {
+        Ok(report) => {
+            let json = serde_json::to_string_pretty(&report).unwrap_or_default();
+            JsonRpcResponse::success(id, text_content(&json))
+        }
+        Err(e) => JsonRpcResponse::error(id, -32603, e.to_string()),
+    }
 }
 
 fn tool_sprint_list(id: Value, store: &Store) -> JsonRpcResponse {
@@ -438,7 +596,7 @@ fn tool_sprint_list(id: Value, store: &Store) -> JsonRpcResponse {
     JsonRpcResponse::success(id, text_content(&json))
 }
 
-fn tool_sprint_create(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
+fn tool_sprint_create(id: Value, args: &Value, store: &Store, repo: &Path) -> JsonRpcResponse {
     let name = match args["name"].as_str() {
         Some(n) => n,
         None => return JsonRpcResponse::error(id, -32602, "name is required"),
@@ -459,7 +617,7 @@ fn tool_sprint_create(id: Value, args: &Value, store: &Store) -> JsonRpcResponse
                 id,
                 -32602,
                 format!("Invalid start date: {start_str} (expected YYYY-MM-DD)"),
-            )
+            );
         }
     };
     let end = match NaiveDate::parse_from_str(end_str, "%Y-%m-%d") {
@@ -469,7 +627,7 @@ fn tool_sprint_create(id: Value, args: &Value, store: &Store) -> JsonRpcResponse
                 id,
                 -32602,
                 format!("Invalid end date: {end_str} (expected YYYY-MM-DD)"),
-            )
+            );
         }
     };
 
@@ -486,6 +644,19 @@ fn tool_sprint_create(id: Value, args: &Value, store: &Store) -> JsonRpcResponse
         return JsonRpcResponse::error(id, -32602, format!("Sprint already exists: {name}"));
     }
 
+    let milestone = if args["github_milestone"].as_bool().unwrap_or(false) {
+        match sync::provider().and_then(|p| p.create_milestone(repo, name, end)) {
+            Ok(m) => Some(m),
+            Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+        }
+    } else {
+        None
+    };
+    let mut text = format!("Created sprint: {name} ({start} -> {end})");
+    if let Some(m) = &milestone {
+        text.push_str(&format!("\nGitHub milestone #{}: {}", m.number, m.url));
+    }
+
     let sprint = Sprint {
         name: name.into(),
         start,
@@ -493,6 +664,7 @@ fn tool_sprint_create(id: Value, args: &Value, store: &Store) -> JsonRpcResponse
         goal: None,
         boards: Vec::new(),
         status: SprintStatus::Planned,
+        milestone,
     };
 
     sprints.push(sprint);
@@ -500,10 +672,7 @@ fn tool_sprint_create(id: Value, args: &Value, store: &Store) -> JsonRpcResponse
         return JsonRpcResponse::error(id, -32603, e.to_string());
     }
 
-    JsonRpcResponse::success(
-        id,
-        text_content(&format!("Created sprint: {name} ({start} -> {end})")),
-    )
+    JsonRpcResponse::success(id, text_content(&text))
 }
 
 fn tool_sprint_start(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
@@ -511,34 +680,46 @@ fn tool_sprint_start(id: Value, args: &Value, store: &Store) -> JsonRpcResponse
         Some(n) => n,
         None => return JsonRpcResponse::error(id, -32602, "name is required"),
     };
-
-    let mut sprints = match load_sprints(store) {
-        Ok(s) => s,
-        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+    let card_list = |key: &str| -> Vec<String> {
+        args[key]
+            .as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
     };
-
-    let sprint = match sprints.iter_mut().find(|s| s.name == name) {
-        Some(s) => s,
-        None => return JsonRpcResponse::error(id, -32602, format!("Sprint not found: {name}")),
-    };
-
-    match sprint.status {
-        SprintStatus::Active => {
-            return JsonRpcResponse::error(id, -32602, format!("Sprint already active: {name}"))
-        }
-        SprintStatus::Closed => {
-            return JsonRpcResponse::error(id, -32602, format!("Sprint already closed: {name}"))
+    let cards = args["create_board"]
+        .as_bool()
+        .unwrap_or(false)
+        .then(|| sync::SprintBoardCards {
+            moved: card_list("move_cards"),
+            copied: card_list("copy_cards"),
+        });
+
+    match sync::start_sprint(store, name, cards.as_ref()) {
+        Ok(started) => {
+            let mut text = format!("Started sprint: {name}");
+            if let Some(board) = &started.board {
+                text.push_str(&format!("\nCreated board: {board}"));
+                if !started.moved.is_empty() {
+                    text.push_str(&format!("\nMoved: {}", started.moved.join(", ")));
+                }
+                if !started.copied.is_empty() {
+                    text.push_str(&format!("\nCopied: {}", started.copied.join(", ")));
+                }
+            }
+            JsonRpcResponse::success(id, text_content(&text))
         }
-        SprintStatus::Planned => {}
-    }
-
-    sprint.status = SprintStatus::Active;
-
-    if let Err(e) = save_sprints(store, &sprints) {
-        return JsonRpcResponse::error(id, -32603, e.to_string());
+        Err(
+            e @ (PmError::SprintNotFound(_)
+            | PmError::SprintAlreadyClosed(_)
+            | PmError::CardNotFound(_)
+            | PmError::Other(_)),
+        ) => JsonRpcResponse::error(id, -32602, e.to_string()),
+        Err(e) => JsonRpcResponse::error(id, -32603, e.to_string()),
     }
-
-    JsonRpcResponse::success(id, text_content(&format!("Started sprint: {name}")))
 }
 
 fn tool_sprint_end(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
@@ -583,6 +764,10 @@ fn tool_link(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
         Some(u) => u,
         None => return JsonRpcResponse::error(id, -32602, "url is required"),
     };
+    let role: LinkRole = match args["role"].as_str().unwrap_or("implements").parse() {
+        Ok(r) => r,
+        Err(e) => return JsonRpcResponse::error(id, -32602, e),
+    };
 
     let config = match store.load_config() {
         Ok(c) => c,
@@ -605,17 +790,27 @@ fn tool_link(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
 
     let mut meta = sync::get_pm_metadata(card);
 
-    let link_type =
-        if url.contains("/pull/") || url.contains("/pulls/") || url.contains("/merge_requests/") {
-            meta.pr_url = Some(url.into());
-            "PR"
-        } else {
-            meta.issue_url = Some(url.into());
-            "issue"
-        };
+    let hosts = match sync::load_pm_config(store) {
+        Ok(c) => c.forge_hosts,
+        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+    };
+    let kind = sync::link_kind(url, &hosts);
+    meta.add_link(url, kind, role);
+
+    // Fetch failures are not fatal — the link itself is still recorded
+    let mut label_colors = std::collections::BTreeMap::new();
+    if kind == LinkKind::Issue
+        && args["fetch"].as_bool().unwrap_or(true)
+        && let Ok(provider) = sync::provider()
+        && let Ok(details) = sync::fetch_issue_details(&*provider, url, &hosts)
+    {
+        sync::apply_issue_details(card, &details);
+        label_colors = details.label_colors;
+    }
 
     sync::set_pm_metadata(card, &meta);
     card.updated_at = chrono::Utc::now();
+    sync::apply_label_colors(&mut board, &label_colors);
 
     if let Err(e) = store.save_board(&board) {
         return JsonRpcResponse::error(id, -32603, e.to_string());
@@ -623,7 +818,7 @@ fn tool_link(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
 
     JsonRpcResponse::success(
         id,
-        text_content(&format!("Linked card {card_id} to {link_type}: {url}")),
+        text_content(&format!("Linked card {card_id} to {kind} ({role}): {url}")),
     )
 }
 
@@ -633,30 +828,47 @@ fn tool_release_notes(id: Value, args: &Value, repo: &Path) -> JsonRpcResponse {
     }
 
     let since_ref = args["since"].as_str().unwrap_or("last-tag");
-
-    let commits = if since_ref == "last-tag" {
-        match git::list_tags(repo) {
-            Ok(tags) if !tags.is_empty() => {
-                let tag = tags.last().unwrap();
-                match git::commits_since_ref(repo, tag) {
-                    Ok(c) => c,
-                    Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
-                }
-            }
-            _ => match git::recent_commits(repo, 50) {
-                Ok(c) => c,
-                Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
-            },
-        }
+    let to = args["to"].as_str().unwrap_or("HEAD");
+    let pre_release = args["pre_release"].as_bool().unwrap_or(false);
+    let exclude: Vec<String> = args["exclude"]
+        .as_array()
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let from = if since_ref == "last-tag" {
+        git::latest_tag(repo, pre_release).ok().flatten()
     } else {
-        match git::commits_since_ref(repo, since_ref) {
-            Ok(c) => c,
-            Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
-        }
+        Some(since_ref.to_string())
+    };
+    let range = match git::release_range(repo, from.as_deref(), to, &exclude) {
+        Ok(r) => r,
+        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
     };
 
-    let mut report = reports::categorize_commits(&commits);
+    let mut report = reports::categorize_commits(&range.commits);
     report.since = since_ref.to_string();
+    report.to = (to != "HEAD").then(|| to.to_string());
+    report.already_released = range
+        .released
+        .iter()
+        .map(|c| c.message.lines().next().unwrap_or_default().to_string())
+        .collect();
+    report.cards = match sync::release_cards(repo, &range.commits) {
+        Ok(cards) => cards,
+        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+    };
+    if args["contributors"].as_bool().unwrap_or(false) {
+        match git::earlier_authors(repo, from.as_deref(), to) {
+            Ok(earlier) => {
+                report.contributors = Some(reports::count_contributors(&range.commits, &earlier));
+            }
+            Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+        }
+    }
 
     let json = serde_json::to_string_pretty(&report).unwrap_or_default();
     JsonRpcResponse::success(id, text_content(&json))
@@ -664,9 +876,30 @@ fn tool_release_notes(id: Value, args: &Value, repo: &Path) -> JsonRpcResponse {
 
 fn tool_sync(id: Value, args: &Value, repo: &Path) -> JsonRpcResponse {
     let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+    let strings = |key: &str| -> Vec<String> {
+        args[key]
+            .as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let scope = sync::SyncScope {
+        board: args["board"].as_str().map(String::from),
+        labels: strings("labels"),
+        columns: strings("columns"),
+    };
+
+    let create_missing = args["create_missing"].as_bool().unwrap_or(false);
 
-    match sync::run_sync(repo, dry_run, true) {
-        Ok(actions) => {
+    let retry = sync::RetryPolicy::default();
+    let synced = sync::provider()
+        .and_then(|p| sync::sync_board(&*p, repo, &scope, create_missing, dry_run, &retry));
+    match synced {
+        Ok(run) => {
+            let actions = run.map(|run| run.actions).unwrap_or_default();
             let json = serde_json::to_string_pretty(&actions).unwrap_or_default();
             JsonRpcResponse::success(id, text_content(&json))
         }
@@ -0,0 +1,323 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Days, FixedOffset, Local, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Timezone used to turn card timestamps into calendar days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ReportTz {
+    #[default]
+    Utc,
+    /// The system timezone of the machine running the report.
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl ReportTz {
+    pub fn is_utc(&self) -> bool {
+        *self == ReportTz::Utc
+    }
+}
+
+impl FromStr for ReportTz {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "utc" | "z" => Ok(ReportTz::Utc),
+            "local" => Ok(ReportTz::Local),
+            _ => s.parse::<FixedOffset>().map(ReportTz::Fixed).map_err(|_| {
+                format!("Invalid timezone: {s}. Use 'utc', 'local', or an offset like '+05:30'.")
+            }),
+        }
+    }
+}
+
+impl TryFrom<String> for ReportTz {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ReportTz> for String {
+    fn from(tz: ReportTz) -> Self {
+        tz.to_string()
+    }
+}
+
+impl fmt::Display for ReportTz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportTz::Utc => write!(f, "utc"),
+            ReportTz::Local => write!(f, "local"),
+            ReportTz::Fixed(offset) => write!(f, "{offset}"),
+        }
+    }
+}
+
+/// First day of the reporting week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub fn is_monday(&self) -> bool {
+        *self == WeekStart::Monday
+    }
+
+    fn weekday(self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+        }
+    }
+}
+
+/// A team-wide day off.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// One person's time off, inclusive of both ends.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Vacation {
+    pub person: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// Working days, holidays, and vacations, stored in `.kuk/calendar.json`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamCalendar {
+    #[serde(default = "default_working_days")]
+    pub working_days: Vec<Weekday>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub holidays: Vec<Holiday>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vacations: Vec<Vacation>,
+}
+
+fn default_working_days() -> Vec<Weekday> {
+    vec![
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+    ]
+}
+
+impl Default for TeamCalendar {
+    fn default() -> Self {
+        Self {
+            working_days: default_working_days(),
+            holidays: Vec::new(),
+            vacations: Vec::new(),
+        }
+    }
+}
+
+impl TeamCalendar {
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.working_days.contains(&date.weekday()) && !self.holidays.iter().any(|h| h.date == date)
+    }
+
+    /// Working days in `[start, end)`.
+    pub fn working_days_between(&self, start: NaiveDate, end: NaiveDate) -> u32 {
+        start
+            .iter_days()
+            .take_while(|d| *d < end)
+            .filter(|d| self.is_working_day(*d))
+            .count() as u32
+    }
+
+    /// Share of a team of `team_size` available on `date`: 0 on days off,
+    /// reduced by anyone on vacation.
+    pub fn availability(&self, date: NaiveDate, team_size: usize) -> f64 {
+        if !self.is_working_day(date) {
+            return 0.0;
+        }
+        let away = self
+            .vacations
+            .iter()
+            .filter(|v| v.start <= date && date <= v.end)
+            .map(|v| v.person.as_str())
+            .collect::<std::collections::BTreeSet<_>>()
+            .len();
+        let team = team_size.max(1);
+        (team.saturating_sub(away)) as f64 / team as f64
+    }
+
+    /// Fraction of a normal week's capacity available in the week from `week_start`.
+    pub fn week_capacity(&self, week_start: NaiveDate, team_size: usize) -> f64 {
+        if self.working_days.is_empty() {
+            return 1.0;
+        }
+        let available: f64 = week_start
+            .iter_days()
+            .take(7)
+            .map(|d| self.availability(d, team_size))
+            .sum();
+        available / self.working_days.len() as f64
+    }
+}
+
+/// Day and week conventions shared by the date-bucketed reports.
+#[derive(Debug, Clone, Default)]
+pub struct Calendar {
+    pub tz: ReportTz,
+    pub week_start: WeekStart,
+    pub team: TeamCalendar,
+}
+
+impl Calendar {
+    /// The calendar day `ts` falls on in the configured timezone.
+    pub fn date_of(&self, ts: DateTime<Utc>) -> NaiveDate {
+        match self.tz {
+            ReportTz::Utc => ts.date_naive(),
+            ReportTz::Local => ts.with_timezone(&Local).date_naive(),
+            ReportTz::Fixed(offset) => ts.with_timezone(&offset).date_naive(),
+        }
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.date_of(Utc::now())
+    }
+
+    /// First day of the week containing `date`.
+    pub fn week_start(&self, date: NaiveDate) -> NaiveDate {
+        let days_back = date.weekday().days_since(self.week_start.weekday());
+        date.checked_sub_days(Days::new(days_back as u64))
+            .unwrap_or(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parses_timezones() {
+        assert_eq!("UTC".parse::<ReportTz>().unwrap(), ReportTz::Utc);
+        assert_eq!("local".parse::<ReportTz>().unwrap(), ReportTz::Local);
+        let tz: ReportTz = "+05:30".parse().unwrap();
+        assert_eq!(tz, ReportTz::Fixed(FixedOffset::east_opt(19800).unwrap()));
+        assert!("Mars/Olympus".parse::<ReportTz>().is_err());
+    }
+
+    #[test]
+    fn timezone_roundtrips_as_string() {
+        let tz = ReportTz::Fixed(FixedOffset::west_opt(8 * 3600).unwrap());
+        let json = serde_json::to_string(&tz).unwrap();
+        assert_eq!(json, "\"-08:00\"");
+        assert_eq!(serde_json::from_str::<ReportTz>(&json).unwrap(), tz);
+        assert!(serde_json::from_str::<ReportTz>("\"nope\"").is_err());
+    }
+
+    #[test]
+    fn date_of_applies_offset() {
+        // 2026-03-02 03:00 UTC is still Sunday evening in UTC-8
+        let ts = Utc.with_ymd_and_hms(2026, 3, 2, 3, 0, 0).unwrap();
+        let cal = Calendar {
+            tz: "-08:00".parse().unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(
+            cal.date_of(ts),
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+        );
+        assert_eq!(
+            Calendar::default().date_of(ts),
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn week_start_monday_and_sunday() {
+        let wed = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+        let monday = Calendar::default();
+        assert_eq!(
+            monday.week_start(wed),
+            NaiveDate::from_ymd_opt(2026, 2, 16).unwrap()
+        );
+
+        let sunday = Calendar {
+            week_start: WeekStart::Sunday,
+            ..Default::default()
+        };
+        assert_eq!(
+            sunday.week_start(wed),
+            NaiveDate::from_ymd_opt(2026, 2, 15).unwrap()
+        );
+        let sun = NaiveDate::from_ymd_opt(2026, 2, 15).unwrap();
+        assert_eq!(sunday.week_start(sun), sun);
+        assert_eq!(
+            monday.week_start(sun),
+            NaiveDate::from_ymd_opt(2026, 2, 9).unwrap()
+        );
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn working_days_skip_weekends_and_holidays() {
+        let mut team = TeamCalendar::default();
+        // Mon 2026-02-16 .. Mon 2026-02-23: one full week
+        assert_eq!(
+            team.working_days_between(date("2026-02-16"), date("2026-02-23")),
+            5
+        );
+
+        team.holidays.push(Holiday {
+            date: date("2026-02-17"),
+            name: Some("Carnival".into()),
+        });
+        assert!(!team.is_working_day(date("2026-02-17")));
+        assert!(!team.is_working_day(date("2026-02-21")));
+        assert_eq!(
+            team.working_days_between(date("2026-02-16"), date("2026-02-23")),
+            4
+        );
+        assert_eq!(team.week_capacity(date("2026-02-16"), 1), 0.8);
+    }
+
+    #[test]
+    fn vacations_reduce_availability() {
+        let mut team = TeamCalendar::default();
+        team.vacations.push(Vacation {
+            person: "alice".into(),
+            start: date("2026-02-16"),
+            end: date("2026-02-20"),
+        });
+        assert_eq!(team.availability(date("2026-02-18"), 2), 0.5);
+        assert_eq!(team.availability(date("2026-02-23"), 2), 1.0);
+        assert_eq!(team.week_capacity(date("2026-02-16"), 2), 0.5);
+    }
+
+    #[test]
+    fn team_calendar_json_defaults() {
+        let team: TeamCalendar = serde_json::from_str("{}").unwrap();
+        assert_eq!(team, TeamCalendar::default());
+
+        let team: TeamCalendar =
+            serde_json::from_str(r#"{"working_days": ["sun", "Monday", "Tue"]}"#).unwrap();
+        assert_eq!(
+            team.working_days,
+            vec![Weekday::Sun, Weekday::Mon, Weekday::Tue]
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::LinkKind;
+
+/// Code hosting provider an issue/PR URL belongs to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    GitHub,
+    GitLab,
+    Bitbucket,
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Provider::GitHub => write!(f, "GitHub"),
+            Provider::GitLab => write!(f, "GitLab"),
+            Provider::Bitbucket => write!(f, "Bitbucket"),
+        }
+    }
+}
+
+/// Self-hosted forge hosts (GitHub Enterprise, GitLab, ...) mapped to the
+/// provider they run. Public hosts are always recognized.
+pub type ForgeHosts = BTreeMap<String, Provider>;
+
+/// An issue or PR URL broken into its provider-specific parts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForgeUrl {
+    pub provider: Provider,
+    pub host: String,
+    /// Project path: `owner/repo` on GitHub/Bitbucket, `group/sub/project` on GitLab.
+    pub project: String,
+    pub kind: LinkKind,
+    pub number: String,
+}
+
+impl ForgeUrl {
+    /// Parse an issue/PR URL. The host must be a public forge or listed in
+    /// `hosts`; anything else returns None.
+    pub fn parse(url: &str, hosts: &ForgeHosts) -> Option<Self> {
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))?;
+        let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+        let mut segments = rest.split('/').filter(|s| !s.is_empty());
+        let host = segments.next()?.to_lowercase();
+        let path: Vec<&str> = segments.collect();
+
+        let provider = provider_for_host(&host, hosts)?;
+        let (project, kind, number) = match provider {
+            Provider::GitHub => parse_owner_repo(
+                &path,
+                &[
+                    ("issues", LinkKind::Issue),
+                    ("pull", LinkKind::Pr),
+                    ("pulls", LinkKind::Pr),
+                ],
+            )?,
+            Provider::Bitbucket => parse_owner_repo(
+                &path,
+                &[("issues", LinkKind::Issue), ("pull-requests", LinkKind::Pr)],
+            )?,
+            Provider::GitLab => parse_gitlab(&path)?,
+        };
+
+        Some(ForgeUrl {
+            provider,
+            host,
+            project,
+            kind,
+            number,
+        })
+    }
+
+    /// The project's web address, e.g. `https://github.com/owner/repo`.
+    pub fn project_url(&self) -> String {
+        format!("https://{}/{}", self.host, self.project)
+    }
+}
+
+/// The canonical web address, which parses back to the same `ForgeUrl`.
+impl fmt::Display for ForgeUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match (self.provider, self.kind) {
+            (Provider::GitHub, LinkKind::Issue) => "issues",
+            (Provider::GitHub, LinkKind::Pr) => "pull",
+            (Provider::GitLab, LinkKind::Issue) => "-/issues",
+            (Provider::GitLab, LinkKind::Pr) => "-/merge_requests",
+            (Provider::Bitbucket, LinkKind::Issue) => "issues",
+            (Provider::Bitbucket, LinkKind::Pr) => "pull-requests",
+        };
+        write!(f, "{}/{kind}/{}", self.project_url(), self.number)
+    }
+}
+
+fn provider_for_host(host: &str, hosts: &ForgeHosts) -> Option<Provider> {
+    match host {
+        "github.com" | "www.github.com" => Some(Provider::GitHub),
+        "gitlab.com" | "www.gitlab.com" => Some(Provider::GitLab),
+        "bitbucket.org" | "www.bitbucket.org" => Some(Provider::Bitbucket),
+        _ => hosts
+            .iter()
+            .find(|(h, _)| h.eq_ignore_ascii_case(host))
+            .map(|(_, p)| *p),
+    }
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// `owner/repo/<kind>/<number>[/...]`
+fn parse_owner_repo(
+    path: &[&str],
+    kinds: &[(&str, LinkKind)],
+) -> Option<(String, LinkKind, String)> {
+    let [owner, repo, kind, number, ..] = path else {
+        return None;
+    };
+    let kind = kinds.iter().find(|(k, _)| k == kind)?.1;
+    if !is_number(number) {
+        return None;
+    }
+    Some((format!("{owner}/{repo}"), kind, number.to_string()))
+}
+
+/// `group/[sub/...]project/-/<kind>/<number>[/...]`, or the older layout
+/// without the `-` separator.
+fn parse_gitlab(path: &[&str]) -> Option<(String, LinkKind, String)> {
+    let kind_of = |s: &str| match s {
+        "issues" => Some(LinkKind::Issue),
+        "merge_requests" => Some(LinkKind::Pr),
+        _ => None,
+    };
+
+    let (project_end, kind_idx) = match path.iter().position(|s| *s == "-") {
+        Some(dash) => (dash, dash + 1),
+        None => {
+            let idx = path
+                .windows(2)
+                .position(|w| kind_of(w[0]).is_some() && is_number(w[1]))?;
+            (idx, idx)
+        }
+    };
+
+    // Projects always live under at least one namespace
+    if project_end < 2 {
+        return None;
+    }
+    let kind = kind_of(path.get(kind_idx)?)?;
+    let number = path.get(kind_idx + 1)?;
+    if !is_number(number) {
+        return None;
+    }
+    Some((path[..project_end].join("/"), kind, number.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> Option<ForgeUrl> {
+        ForgeUrl::parse(url, &ForgeHosts::new())
+    }
+
+    #[test]
+    fn github_issue_and_pr() {
+        let issue = parse("https://github.com/user/myrepo/issues/42").unwrap();
+        assert_eq!(issue.provider, Provider::GitHub);
+        assert_eq!(issue.host, "github.com");
+        assert_eq!(issue.project, "user/myrepo");
+        assert_eq!(issue.kind, LinkKind::Issue);
+        assert_eq!(issue.number, "42");
+
+        let pr = parse("https://github.com/org/project/pull/7/files?w=1").unwrap();
+        assert_eq!(pr.kind, LinkKind::Pr);
+        assert_eq!(pr.number, "7");
+    }
+
+    #[test]
+    fn trailing_slash_and_fragment() {
+        let u = parse("https://github.com/user/repo/issues/99/#issuecomment-1").unwrap();
+        assert_eq!(u.number, "99");
+    }
+
+    #[test]
+    fn gitlab_subgroups() {
+        let u = parse("https://gitlab.com/group/sub/deeper/project/-/merge_requests/12").unwrap();
+        assert_eq!(u.provider, Provider::GitLab);
+        assert_eq!(u.project, "group/sub/deeper/project");
+        assert_eq!(u.kind, LinkKind::Pr);
+        assert_eq!(u.number, "12");
+
+        let legacy = parse("https://gitlab.com/group/project/issues/3").unwrap();
+        assert_eq!(legacy.project, "group/project");
+        assert_eq!(legacy.kind, LinkKind::Issue);
+    }
+
+    #[test]
+    fn bitbucket_pull_request() {
+        let u = parse("https://bitbucket.org/team/repo/pull-requests/5").unwrap();
+        assert_eq!(u.provider, Provider::Bitbucket);
+        assert_eq!(u.project, "team/repo");
+        assert_eq!(u.kind, LinkKind::Pr);
+    }
+
+    #[test]
+    fn self_hosted_requires_allowlist() {
+        let url = "https://github.corp.example/team/app/pull/9";
+        assert!(parse(url).is_none());
+
+        let mut hosts = ForgeHosts::new();
+        hosts.insert("github.corp.example".into(), Provider::GitHub);
+        let u = ForgeUrl::parse(url, &hosts).unwrap();
+        assert_eq!(u.provider, Provider::GitHub);
+        assert_eq!(u.host, "github.corp.example");
+        assert_eq!(u.project, "team/app");
+
+        hosts.insert("git.corp.example".into(), Provider::GitLab);
+        let u = ForgeUrl::parse("https://git.corp.example/a/b/-/issues/1", &hosts).unwrap();
+        assert_eq!(u.provider, Provider::GitLab);
+    }
+
+    #[test]
+    fn rejects_malformed_urls() {
+        assert!(parse("not-a-url").is_none());
+        assert!(parse("https://github.com/user").is_none());
+        assert!(parse("https://github.com/user/repo/issues/abc").is_none());
+        assert!(parse("https://github.com/user/repo/wiki/1").is_none());
+        assert!(parse("https://gitlab.com/project/-/issues/1").is_none());
+    }
+
+    #[test]
+    fn displays_canonical_url() {
+        for (url, canonical) in [
+            (
+                "https://github.com/o/r/pulls/7/files",
+                "https://github.com/o/r/pull/7",
+            ),
+            (
+                "https://gitlab.com/g/p/issues/3",
+                "https://gitlab.com/g/p/-/issues/3",
+            ),
+            (
+                "https://bitbucket.org/t/r/pull-requests/5",
+                "https://bitbucket.org/t/r/pull-requests/5",
+            ),
+        ] {
+            let u = parse(url).unwrap();
+            assert_eq!(u.to_string(), canonical);
+            assert_eq!(parse(canonical), Some(u));
+        }
+    }
+}
@@ -1,20 +1,191 @@
+use std::fmt;
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Issue,
+    Pr,
+}
+
+impl LinkKind {
+    /// Detect issue vs PR from the URL layout. Used when the URL's host isn't
+    /// a recognized forge; see `ForgeUrl::parse`.
+    pub fn from_url(url: &str) -> Self {
+        if url.contains("/pull/")
+            || url.contains("/pulls/")
+            || url.contains("/merge_requests/")
+            || url.contains("/pull-requests/")
+        {
+            LinkKind::Pr
+        } else {
+            LinkKind::Issue
+        }
+    }
+}
+
+impl fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkKind::Issue => write!(f, "issue"),
+            LinkKind::Pr => write!(f, "PR"),
+        }
+    }
+}
+
+/// How a linked issue/PR relates to the card.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkRole {
+    #[default]
+    Implements,
+    Fixes,
+    Reverts,
+}
+
+impl FromStr for LinkRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "implements" => Ok(LinkRole::Implements),
+            "fixes" => Ok(LinkRole::Fixes),
+            "reverts" => Ok(LinkRole::Reverts),
+            _ => Err(format!(
+                "Invalid link role: {s}. Use 'implements', 'fixes', or 'reverts'."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LinkRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkRole::Implements => write!(f, "implements"),
+            LinkRole::Fixes => write!(f, "fixes"),
+            LinkRole::Reverts => write!(f, "reverts"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Link {
+    pub url: String,
+    pub kind: LinkKind,
+    #[serde(default)]
+    pub role: LinkRole,
+}
+
+/// A comment kuk-pm posted to a linked issue or PR, kept on the card.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostedComment {
+    pub at: DateTime<Utc>,
+    /// The issue or PR commented on.
+    pub url: String,
+    pub body: String,
+    /// Web address of the comment, when the forge returns one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment_url: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(from = "RawGitMetadata")]
 pub struct GitMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branch: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub issue_url: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub pr_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<Link>,
     #[serde(default)]
     pub commits: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<PostedComment>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_synced: Option<DateTime<Utc>>,
 }
 
+/// On-disk shape accepted when reading, including the single-URL fields
+/// written by earlier versions.
+#[derive(Deserialize)]
+struct RawGitMetadata {
+    #[serde(default)]
+    branch: Option<String>,
+    #[serde(default)]
+    issue_url: Option<String>,
+    #[serde(default)]
+    pr_url: Option<String>,
+    #[serde(default)]
+    links: Vec<Link>,
+    #[serde(default)]
+    commits: Vec<String>,
+    #[serde(default)]
+    comments: Vec<PostedComment>,
+    #[serde(default)]
+    last_synced: Option<DateTime<Utc>>,
+}
+
+impl From<RawGitMetadata> for GitMetadata {
+    fn from(raw: RawGitMetadata) -> Self {
+        let mut meta = GitMetadata {
+            branch: raw.branch,
+            links: raw.links,
+            commits: raw.commits,
+            comments: raw.comments,
+            last_synced: raw.last_synced,
+        };
+        if let Some(url) = raw.issue_url {
+            meta.add_link(&url, LinkKind::Issue, LinkRole::Implements);
+        }
+        if let Some(url) = raw.pr_url {
+            meta.add_link(&url, LinkKind::Pr, LinkRole::Implements);
+        }
+        meta
+    }
+}
+
+impl GitMetadata {
+    /// Add a link, or update the role of an existing link with the same URL.
+    /// Returns true if a new link was added.
+    pub fn add_link(&mut self, url: &str, kind: LinkKind, role: LinkRole) -> bool {
+        if let Some(existing) = self.links.iter_mut().find(|l| l.url == url) {
+            existing.role = role;
+            return false;
+        }
+        self.links.push(Link {
+            url: url.into(),
+            kind,
+            role,
+        });
+        true
+    }
+
+    /// Remove the link with the given URL. Returns true if one was removed.
+    pub fn remove_link(&mut self, url: &str) -> bool {
+        let before = self.links.len();
+        self.links.retain(|l| l.url != url);
+        self.links.len() != before
+    }
+
+    pub fn issues(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(|l| l.kind == LinkKind::Issue)
+    }
+
+    pub fn prs(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(|l| l.kind == LinkKind::Pr)
+    }
+
+    /// Where comments about the card go: the first linked issue, or the
+    /// first PR when it has none. Revert links are passed over.
+    pub fn comment_target(&self) -> Option<&Link> {
+        let tracked = || self.links.iter().filter(|l| l.role != LinkRole::Reverts);
+        tracked()
+            .find(|l| l.kind == LinkKind::Issue)
+            .or_else(|| tracked().next())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,25 +194,34 @@ mod tests {
     fn default_is_empty() {
         let meta = GitMetadata::default();
         assert!(meta.branch.is_none());
-        assert!(meta.issue_url.is_none());
-        assert!(meta.pr_url.is_none());
+        assert!(meta.links.is_empty());
         assert!(meta.commits.is_empty());
         assert!(meta.last_synced.is_none());
     }
 
     #[test]
     fn roundtrip_json() {
-        let meta = GitMetadata {
+        let mut meta = GitMetadata {
             branch: Some("feature/login".into()),
-            issue_url: Some("https://github.com/user/repo/issues/42".into()),
-            pr_url: None,
             commits: vec!["abc123".into(), "def456".into()],
             last_synced: Some(Utc::now()),
+            ..Default::default()
         };
+        meta.add_link(
+            "https://github.com/user/repo/issues/42",
+            LinkKind::Issue,
+            LinkRole::Implements,
+        );
+        meta.add_link(
+            "https://github.com/user/repo/pull/43",
+            LinkKind::Pr,
+            LinkRole::Fixes,
+        );
         let json = serde_json::to_string(&meta).unwrap();
         let parsed: GitMetadata = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.branch.unwrap(), "feature/login");
         assert_eq!(parsed.commits.len(), 2);
+        assert_eq!(parsed.links, meta.links);
     }
 
     #[test]
@@ -49,6 +229,7 @@ mod tests {
         let meta: GitMetadata = serde_json::from_str("{}").unwrap();
         assert!(meta.branch.is_none());
         assert!(meta.commits.is_empty());
+        assert!(meta.links.is_empty());
     }
 
     #[test]
@@ -56,8 +237,81 @@ mod tests {
         let meta = GitMetadata::default();
         let json = serde_json::to_string(&meta).unwrap();
         assert!(!json.contains("branch"));
-        assert!(!json.contains("issue_url"));
-        assert!(!json.contains("pr_url"));
+        assert!(!json.contains("links"));
         assert!(!json.contains("last_synced"));
     }
+
+    #[test]
+    fn legacy_single_urls_migrate_to_links() {
+        let json = r#"{
+            "issue_url": "https://github.com/u/r/issues/1",
+            "pr_url": "https://github.com/u/r/pull/2",
+            "commits": []
+        }"#;
+        let meta: GitMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(meta.links.len(), 2);
+        assert_eq!(meta.issues().count(), 1);
+        assert_eq!(meta.prs().count(), 1);
+        assert!(meta.links.iter().all(|l| l.role == LinkRole::Implements));
+
+        // Legacy fields are not written back
+        let out = serde_json::to_string(&meta).unwrap();
+        assert!(!out.contains("issue_url"));
+        assert!(!out.contains("pr_url"));
+    }
+
+    #[test]
+    fn add_link_dedupes_by_url() {
+        let mut meta = GitMetadata::default();
+        assert!(meta.add_link("https://x/pull/1", LinkKind::Pr, LinkRole::Implements));
+        assert!(!meta.add_link("https://x/pull/1", LinkKind::Pr, LinkRole::Reverts));
+        assert_eq!(meta.links.len(), 1);
+        assert_eq!(meta.links[0].role, LinkRole::Reverts);
+    }
+
+    #[test]
+    fn remove_link_by_url() {
+        let mut meta = GitMetadata::default();
+        meta.add_link("https://x/issues/1", LinkKind::Issue, LinkRole::Implements);
+        meta.add_link("https://x/pull/2", LinkKind::Pr, LinkRole::Fixes);
+        assert!(meta.remove_link("https://x/issues/1"));
+        assert!(!meta.remove_link("https://x/issues/1"));
+        assert_eq!(meta.links.len(), 1);
+        assert_eq!(meta.links[0].url, "https://x/pull/2");
+    }
+
+    #[test]
+    fn comment_target_prefers_issues() {
+        let mut meta = GitMetadata::default();
+        assert!(meta.comment_target().is_none());
+        meta.add_link("https://x/pull/2", LinkKind::Pr, LinkRole::Implements);
+        assert_eq!(meta.comment_target().unwrap().url, "https://x/pull/2");
+        meta.add_link("https://x/issues/1", LinkKind::Issue, LinkRole::Reverts);
+        assert_eq!(meta.comment_target().unwrap().url, "https://x/pull/2");
+        meta.add_link("https://x/issues/3", LinkKind::Issue, LinkRole::Fixes);
+        assert_eq!(meta.comment_target().unwrap().url, "https://x/issues/3");
+    }
+
+    #[test]
+    fn link_kind_from_url() {
+        assert_eq!(
+            LinkKind::from_url("https://github.com/u/r/pull/7"),
+            LinkKind::Pr
+        );
+        assert_eq!(
+            LinkKind::from_url("https://gitlab.com/g/p/-/merge_requests/3"),
+            LinkKind::Pr
+        );
+        assert_eq!(
+            LinkKind::from_url("https://github.com/u/r/issues/7"),
+            LinkKind::Issue
+        );
+    }
+
+    #[test]
+    fn link_role_parses() {
+        assert_eq!("fixes".parse::<LinkRole>().unwrap(), LinkRole::Fixes);
+        assert_eq!("Reverts".parse::<LinkRole>().unwrap(), LinkRole::Reverts);
+        assert!("breaks".parse::<LinkRole>().is_err());
+    }
 }
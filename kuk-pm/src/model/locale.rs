@@ -0,0 +1,189 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// How report text writes dates and numbers. The default, `iso`, writes
+/// `2026-03-02` and `1234.5`; a language tag such as `de-DE` writes
+/// `02.03.2026` and `1.234,5`. JSON output is never localized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Locale {
+    /// The tag as configured, or `None` for ISO.
+    tag: Option<String>,
+    dates: DateOrder,
+    decimal: char,
+    group: Option<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateOrder {
+    Iso,
+    Mdy(char),
+    Dmy(char),
+    Ymd(char),
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale {
+            tag: None,
+            dates: DateOrder::Iso,
+            decimal: '.',
+            group: None,
+        }
+    }
+}
+
+impl Locale {
+    pub fn is_iso(&self) -> bool {
+        self.tag.is_none()
+    }
+
+    pub fn date(&self, date: NaiveDate) -> String {
+        match self.dates {
+            DateOrder::Iso => date.to_string(),
+            DateOrder::Mdy(sep) => date.format(&format!("%m{sep}%d{sep}%Y")).to_string(),
+            DateOrder::Dmy(sep) => date.format(&format!("%d{sep}%m{sep}%Y")).to_string(),
+            DateOrder::Ymd(sep) => date.format(&format!("%Y{sep}%m{sep}%d")).to_string(),
+        }
+    }
+
+    /// `value` with `decimals` places, the locale's decimal mark, and its
+    /// thousands separator from four digits up.
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let plain = format!("{value:.decimals$}");
+        let (sign, plain) = match plain.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", plain.as_str()),
+        };
+        let (int, frac) = plain.split_once('.').unwrap_or((plain, ""));
+
+        let mut out = sign.to_string();
+        for (i, digit) in int.chars().enumerate() {
+            if let Some(group) = self.group
+                && i > 0
+                && (int.len() - i) % 3 == 0
+                && int.len() > 3
+            {
+                out.push(group);
+            }
+            out.push(digit);
+        }
+        if !frac.is_empty() {
+            out.push(self.decimal);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    /// Like [`number`](Self::number) with an explicit `+` on positive
+    /// values, for changes.
+    pub fn signed(&self, value: f64, decimals: usize) -> String {
+        let number = self.number(value, decimals);
+        if number.starts_with('-') {
+            number
+        } else {
+            format!("+{number}")
+        }
+    }
+}
+
+/// Date order, decimal mark and grouping for a lowercased language tag.
+/// A tag with a region not listed here falls back to its language.
+fn conventions(tag: &str) -> Option<(DateOrder, char, Option<char>)> {
+    use DateOrder::*;
+    const NNBSP: char = '\u{202f}';
+    const NBSP: char = '\u{a0}';
+
+    let exact = match tag {
+        "en-gb" | "en-au" | "en-nz" | "en-ie" | "en-in" | "en-za" => {
+            Some((Dmy('/'), '.', Some(',')))
+        }
+        "en-ca" => Some((Ymd('-'), '.', Some(','))),
+        "fr-ca" => Some((Ymd('-'), ',', Some(NBSP))),
+        "de-ch" => Some((Dmy('.'), '.', Some('’'))),
+        "pt-br" => Some((Dmy('/'), ',', Some('.'))),
+        _ => None,
+    };
+    let language = tag.split(['-', '_']).next().unwrap_or(tag);
+    exact.or(match language {
+        "en" => Some((Mdy('/'), '.', Some(','))),
+        "de" | "da" | "nb" | "no" | "fi" => Some((Dmy('.'), ',', Some('.'))),
+        "fr" => Some((Dmy('/'), ',', Some(NNBSP))),
+        "es" | "it" | "pt" | "el" => Some((Dmy('/'), ',', Some('.'))),
+        "nl" => Some((Dmy('-'), ',', Some('.'))),
+        "pl" | "ru" | "uk" | "cs" => Some((Dmy('.'), ',', Some(NBSP))),
+        "sv" => Some((Ymd('-'), ',', Some(NBSP))),
+        "ja" | "zh" => Some((Ymd('/'), '.', Some(','))),
+        "ko" => Some((Ymd('.'), '.', Some(','))),
+        _ => None,
+    })
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tag = s.trim().to_lowercase().replace('_', "-");
+        if tag == "iso" {
+            return Ok(Locale::default());
+        }
+        let (dates, decimal, group) = conventions(&tag).ok_or_else(|| {
+            format!("Unsupported locale: {s}. Use 'iso' or a language tag like 'en-US' or 'de-DE'.")
+        })?;
+        Ok(Locale {
+            tag: Some(s.trim().to_string()),
+            dates,
+            decimal,
+            group,
+        })
+    }
+}
+
+impl TryFrom<String> for Locale {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Locale> for String {
+    fn from(locale: Locale) -> Self {
+        locale.to_string()
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.tag.as_deref().unwrap_or("iso"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_dates_and_numbers_per_locale() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let format = |tag: &str| {
+            let locale: Locale = tag.parse().unwrap();
+            (locale.date(day), locale.number(-12345.678, 1))
+        };
+        assert_eq!(format("iso"), ("2026-03-02".into(), "-12345.7".into()));
+        assert_eq!(format("en-US"), ("03/02/2026".into(), "-12,345.7".into()));
+        assert_eq!(format("en_GB"), ("02/03/2026".into(), "-12,345.7".into()));
+        assert_eq!(format("de-AT"), ("02.03.2026".into(), "-12.345,7".into()));
+        assert_eq!(format("ja"), ("2026/03/02".into(), "-12,345.7".into()));
+
+        let de: Locale = "de-DE".parse().unwrap();
+        assert_eq!(de.number(999.0, 0), "999");
+        assert_eq!(de.signed(0.75, 1), "+0,8");
+        assert_eq!(de.to_string(), "de-DE");
+        assert!(Locale::default().is_iso());
+        assert!("tlh-QO".parse::<Locale>().is_err());
+    }
+}
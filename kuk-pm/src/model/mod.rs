@@ -1,10 +1,20 @@
+mod calendar;
+mod forge;
 mod git_meta;
+mod locale;
+mod okr;
 mod project;
 mod sprint;
 
-pub use git_meta::GitMetadata;
+pub use calendar::{Calendar, Holiday, ReportTz, TeamCalendar, Vacation, WeekStart};
+pub use forge::{ForgeHosts, ForgeUrl, Provider};
+pub use git_meta::{GitMetadata, Link, LinkKind, LinkRole, PostedComment};
+pub use locale::Locale;
+pub use okr::{KeyResult, Objective};
 pub use project::PmProject;
-pub use sprint::{Sprint, SprintStatus};
+pub use sprint::{Milestone, Sprint, SprintStatus};
+
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
@@ -14,6 +24,84 @@ pub struct PmConfig {
     pub auto_branch: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sync_provider: Option<String>,
+    /// Self-hosted forge hosts allowed for linking and sync.
+    #[serde(default, skip_serializing_if = "ForgeHosts::is_empty")]
+    pub forge_hosts: ForgeHosts,
+    /// Per-board sync switch; boards not listed are synced.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sync_boards: BTreeMap<String, bool>,
+    /// How cycle-time outliers (above p95) affect the stats average.
+    #[serde(default, skip_serializing_if = "OutlierPolicy::is_include")]
+    pub cycle_outliers: OutlierPolicy,
+    /// Timezone for bucketing card timestamps into days and weeks.
+    #[serde(default, skip_serializing_if = "ReportTz::is_utc")]
+    pub timezone: ReportTz,
+    #[serde(default, skip_serializing_if = "WeekStart::is_monday")]
+    pub week_start: WeekStart,
+    /// How report text writes dates and numbers.
+    #[serde(default, skip_serializing_if = "Locale::is_iso")]
+    pub locale: Locale,
+    /// Which way sync copies task list checkmarks between PRs and cards.
+    #[serde(default, skip_serializing_if = "ChecklistSync::is_pull")]
+    pub checklist_sync: ChecklistSync,
+    /// Have sync post a comment on the linked issue when a card changes
+    /// column.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub comment_on_move: bool,
+}
+
+/// Treatment of cycle-time outliers when averaging.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OutlierPolicy {
+    /// Flag outliers but keep them in the average.
+    #[default]
+    Include,
+    /// Drop outliers from the average.
+    Exclude,
+    /// Clamp outliers to the p95 value before averaging.
+    Winsorize,
+}
+
+impl OutlierPolicy {
+    fn is_include(&self) -> bool {
+        *self == OutlierPolicy::Include
+    }
+}
+
+/// Direction of checklist sync between a card's description and the task
+/// lists of its linked PRs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecklistSync {
+    /// Copy PR checkmarks onto the card.
+    #[default]
+    Pull,
+    /// Also check off PR items done on the card; an item checked on either
+    /// side counts as done.
+    Both,
+    /// Leave checklists alone.
+    Off,
+}
+
+impl ChecklistSync {
+    fn is_pull(&self) -> bool {
+        *self == ChecklistSync::Pull
+    }
+}
+
+impl PmConfig {
+    pub fn board_sync_enabled(&self, board: &str) -> bool {
+        self.sync_boards.get(board).copied().unwrap_or(true)
+    }
+
+    pub fn calendar(&self, team: TeamCalendar) -> Calendar {
+        Calendar {
+            tz: self.timezone,
+            week_start: self.week_start,
+            team,
+        }
+    }
 }
 
 impl Default for PmConfig {
@@ -22,6 +110,14 @@ impl Default for PmConfig {
             version: "0.1.0".into(),
             auto_branch: false,
             sync_provider: None,
+            forge_hosts: ForgeHosts::new(),
+            sync_boards: BTreeMap::new(),
+            cycle_outliers: OutlierPolicy::Include,
+            timezone: ReportTz::Utc,
+            week_start: WeekStart::Monday,
+            locale: Locale::default(),
+            checklist_sync: ChecklistSync::Pull,
+            comment_on_move: false,
         }
     }
 }
@@ -44,12 +140,29 @@ mod tests {
             version: "0.1.0".into(),
             auto_branch: true,
             sync_provider: Some("github".into()),
+            forge_hosts: ForgeHosts::from([("git.corp.example".into(), Provider::GitLab)]),
+            sync_boards: BTreeMap::from([("local".into(), false)]),
+            cycle_outliers: OutlierPolicy::Winsorize,
+            timezone: ReportTz::Local,
+            week_start: WeekStart::Sunday,
+            locale: "en-GB".parse().unwrap(),
+            checklist_sync: ChecklistSync::Both,
+            comment_on_move: true,
         };
         let json = serde_json::to_string(&config).unwrap();
         let parsed: PmConfig = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.version, "0.1.0");
         assert!(parsed.auto_branch);
-        assert_eq!(parsed.sync_provider.unwrap(), "github");
+        assert_eq!(parsed.sync_provider.as_deref(), Some("github"));
+        assert_eq!(parsed.forge_hosts["git.corp.example"], Provider::GitLab);
+        assert!(!parsed.board_sync_enabled("local"));
+        assert!(parsed.board_sync_enabled("default"));
+        assert_eq!(parsed.cycle_outliers, OutlierPolicy::Winsorize);
+        assert_eq!(parsed.timezone, ReportTz::Local);
+        assert_eq!(parsed.week_start, WeekStart::Sunday);
+        assert_eq!(parsed.locale.to_string(), "en-GB");
+        assert_eq!(parsed.checklist_sync, ChecklistSync::Both);
+        assert!(parsed.comment_on_move);
     }
 
     #[test]
@@ -57,5 +170,10 @@ mod tests {
         let config = PmConfig::default();
         let json = serde_json::to_string(&config).unwrap();
         assert!(!json.contains("sync_provider"));
+        assert!(!json.contains("forge_hosts"));
+        assert!(!json.contains("cycle_outliers"));
+        assert!(!json.contains("timezone"));
+        assert!(!json.contains("week_start"));
+        assert!(!json.contains("checklist_sync"));
     }
 }
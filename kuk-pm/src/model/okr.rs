@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+/// A quarterly goal, stored in `.kuk/okrs.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Objective {
+    /// Short name used on the command line, e.g. `adoption`.
+    pub name: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quarter: Option<String>,
+    #[serde(default)]
+    pub key_results: Vec<KeyResult>,
+}
+
+/// A measurable result, tracked by the cards linked to it. An epic is a
+/// label: every card carrying it counts toward the key result.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyResult {
+    pub title: String,
+    /// Linked card ids.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<String>,
+    /// Linked epic labels.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub epics: Vec<String>,
+}
+
+impl KeyResult {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Whether a card counts toward this key result.
+    pub fn covers(&self, card: &kuk::model::Card) -> bool {
+        self.cards.contains(&card.id) || card.labels.iter().any(|l| self.epics.contains(l))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_result_covers_linked_cards_and_epics() {
+        let mut kr = KeyResult::new("Ship onboarding");
+        let linked = kuk::model::Card::new("Wizard", "todo");
+        let mut tagged = kuk::model::Card::new("Docs", "todo");
+        tagged.labels = vec!["onboarding".into()];
+        let other = kuk::model::Card::new("Unrelated", "todo");
+
+        kr.cards.push(linked.id.clone());
+        kr.epics.push("onboarding".into());
+        assert!(kr.covers(&linked) && kr.covers(&tagged) && !kr.covers(&other));
+
+        let json = serde_json::to_value(KeyResult::new("Empty")).unwrap();
+        assert_eq!(json, serde_json::json!({"title": "Empty"}));
+    }
+}
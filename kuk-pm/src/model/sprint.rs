@@ -1,4 +1,4 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -19,6 +19,36 @@ pub struct Sprint {
     #[serde(default)]
     pub boards: Vec<String>,
     pub status: SprintStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<Milestone>,
+}
+
+/// GitHub milestone mirrored by a sprint. Counts and due date are refreshed
+/// by `kuk-pm sync`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Milestone {
+    pub number: u64,
+    pub url: String,
+    #[serde(default)]
+    pub open_issues: u32,
+    #[serde(default)]
+    pub closed_issues: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_on: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_synced: Option<DateTime<Utc>>,
+}
+
+impl Milestone {
+    /// Percentage of the milestone's issues that are closed.
+    pub fn progress(&self) -> f64 {
+        let total = self.open_issues + self.closed_issues;
+        if total == 0 {
+            0.0
+        } else {
+            self.closed_issues as f64 / total as f64 * 100.0
+        }
+    }
 }
 
 #[cfg(test)]
@@ -34,6 +64,7 @@ mod tests {
             goal: Some("Ship MVP".into()),
             boards: vec!["default".into(), "sprint-1".into()],
             status: SprintStatus::Active,
+            milestone: None,
         };
         let json = serde_json::to_string_pretty(&sprint).unwrap();
         let parsed: Sprint = serde_json::from_str(&json).unwrap();
@@ -60,6 +91,23 @@ mod tests {
         assert_eq!(sprint.name, "test");
         assert!(sprint.goal.is_none());
         assert!(sprint.boards.is_empty());
+        assert!(sprint.milestone.is_none());
+    }
+
+    #[test]
+    fn milestone_progress() {
+        let mut milestone = Milestone {
+            number: 3,
+            url: "https://github.com/u/r/milestone/3".into(),
+            open_issues: 0,
+            closed_issues: 0,
+            due_on: None,
+            last_synced: None,
+        };
+        assert_eq!(milestone.progress(), 0.0);
+        milestone.open_issues = 3;
+        milestone.closed_issues = 1;
+        assert_eq!(milestone.progress(), 25.0);
     }
 
     #[test]
@@ -71,6 +119,7 @@ mod tests {
             goal: None,
             boards: Vec::new(),
             status: SprintStatus::Planned,
+            milestone: None,
         };
         let duration = sprint.end - sprint.start;
         assert_eq!(duration.num_days(), 6);
@@ -0,0 +1,216 @@
+//! Computed reports cached under `.kuk/cache/`, so repeated calls (MCP agent
+//! loops in particular) skip re-parsing every board.
+//!
+//! Entries are stored per report and parameters, and stay valid while the
+//! fingerprint matches: the size and modification time of every board and of
+//! the kuk-pm files reports read, plus the calendar's current day, which
+//! anchors report windows. An entry written within a couple of seconds of an
+//! input's last change is not trusted, since coarse file timestamps could hide
+//! a second write. Cache problems never fail a report; it is simply
+//! recomputed.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use kuk::storage::Store;
+
+use crate::error::Result;
+use crate::sync;
+
+/// Files besides the boards that feed into reports.
+const INPUTS: &[&str] = &[
+    "config.json",
+    "pm.json",
+    "sprints.json",
+    "calendar.json",
+    // Boards in the sqlite layout
+    "kuk.db",
+];
+
+/// How much newer than its inputs an entry must be to be trusted.
+const MTIME_SLACK: Duration = Duration::from_secs(2);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Fingerprint {
+    today: NaiveDate,
+    /// (file name, size, mtime in nanoseconds) for each input that exists.
+    files: Vec<(String, u64, u128)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    fingerprint: Fingerprint,
+    /// When the entry was written, in nanoseconds since the epoch.
+    written_at: u128,
+    report: T,
+}
+
+impl<T> Entry<T> {
+    fn is_valid_for(&self, fingerprint: &Fingerprint) -> bool {
+        let newest = fingerprint.files.iter().map(|f| f.2).max().unwrap_or(0);
+        self.fingerprint == *fingerprint && newest + MTIME_SLACK.as_nanos() <= self.written_at
+    }
+}
+
+/// Return the cached `report` for `params` if its inputs are unchanged,
+/// otherwise run `build` and cache the result.
+pub fn cached<T, P>(
+    store: &Store,
+    report: &str,
+    params: &P,
+    build: impl FnOnce() -> Result<T>,
+) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    P: Serialize,
+{
+    let fingerprint = Fingerprint {
+        today: sync::load_calendar(store)?.today(),
+        files: input_files(&store.kuk_dir()),
+    };
+    let path = entry_path(store, report, params);
+
+    if let Some(entry) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|data| serde_json::from_str::<Entry<T>>(&data).ok())
+        .filter(|entry| entry.is_valid_for(&fingerprint))
+    {
+        tracing::debug!(report, "report cache hit");
+        return Ok(entry.report);
+    }
+
+    tracing::debug!(report, "report cache miss");
+    let value = build()?;
+    let entry = Entry {
+        fingerprint,
+        written_at: nanos(SystemTime::now()).unwrap_or(0),
+        report: value,
+    };
+    if let Err(e) = write_entry(&path, &entry) {
+        tracing::warn!(path = %path.display(), "could not write report cache: {e}");
+    }
+    Ok(entry.report)
+}
+
+fn cache_dir(store: &Store) -> PathBuf {
+    store.kuk_dir().join("cache")
+}
+
+fn entry_path<P: Serialize>(store: &Store, report: &str, params: &P) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(params)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    cache_dir(store).join(format!("{report}-{:016x}.json", hasher.finish()))
+}
+
+fn input_files(kuk_dir: &Path) -> Vec<(String, u64, u128)> {
+    let boards = files_under(&kuk_dir.join("boards")).into_iter();
+    let others = INPUTS.iter().map(|name| kuk_dir.join(name));
+    let mut files: Vec<_> = boards
+        .chain(others)
+        .filter_map(|path| {
+            let meta = fs::metadata(&path).ok()?;
+            let mtime = nanos(meta.modified().ok()?)?;
+            let name = path.strip_prefix(kuk_dir).ok()?.to_string_lossy().into();
+            Some((name, meta.len(), mtime))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Every file below `dir`, which takes in the card files of boards stored
+/// in the cards layout.
+fn files_under(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .flat_map(|e| {
+            let path = e.path();
+            match e.file_type() {
+                Ok(t) if t.is_dir() => files_under(&path),
+                _ => vec![path],
+            }
+        })
+        .collect()
+}
+
+fn nanos(time: SystemTime) -> Option<u128> {
+    Some(time.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+fn write_entry<T: Serialize>(path: &Path, entry: &Entry<T>) -> std::io::Result<()> {
+    let dir = path.parent().expect("cache entries live in the cache dir");
+    if !dir.exists() {
+        fs::create_dir_all(dir)?;
+        // Keep the cache out of commits when .kuk/ is tracked
+        fs::write(dir.join(".gitignore"), "*\n")?;
+    }
+    fs::write(path, serde_json::to_string(entry)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+
+    /// Make every input look untouched for a while.
+    fn age_inputs(store: &Store) {
+        let past = SystemTime::now() - Duration::from_secs(60);
+        let boards = fs::read_dir(store.kuk_dir().join("boards")).unwrap();
+        for path in boards.map(|e| e.unwrap().path()) {
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(past)
+                .unwrap();
+        }
+        fs::File::options()
+            .write(true)
+            .open(store.kuk_dir().join("config.json"))
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+    }
+
+    #[test]
+    fn reuses_entry_until_an_input_changes() {
+        let dir = TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+        age_inputs(&store);
+        let builds = Cell::new(0);
+        let build = || {
+            builds.set(builds.get() + 1);
+            Ok(builds.get())
+        };
+
+        assert_eq!(cached(&store, "test", &4, build).unwrap(), 1);
+        assert_eq!(cached(&store, "test", &4, build).unwrap(), 1);
+        // Different parameters get their own entry
+        assert_eq!(cached(&store, "test", &8, build).unwrap(), 2);
+        assert!(store.kuk_dir().join("cache/.gitignore").exists());
+
+        let mut board = store.load_board("default").unwrap();
+        board.cards.push(kuk::model::Card::new("New", "todo"));
+        store.save_board(&board).unwrap();
+        assert_eq!(cached(&store, "test", &4, build).unwrap(), 3);
+        // Too fresh to trust until the board has been quiet for a moment
+        assert_eq!(cached(&store, "test", &4, build).unwrap(), 4);
+
+        age_inputs(&store);
+        assert_eq!(cached(&store, "test", &4, build).unwrap(), 5);
+        assert_eq!(cached(&store, "test", &4, build).unwrap(), 5);
+    }
+}
@@ -0,0 +1,278 @@
+//! How long cards sit before being handed to the next column, from the
+//! transitions kuk records on every move. A handoff `doing → review` waits
+//! for as long as the card was in `doing`, counted from when it arrived there
+//! (or was created, for its first column).
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use kuk::model::{Card, Transition};
+
+use super::ReportWindow;
+use crate::model::{Calendar, Locale};
+
+/// Handoffs across one boundary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoundaryStats {
+    pub from: String,
+    pub to: String,
+    pub handoffs: usize,
+    pub avg_wait_days: f64,
+    pub max_wait_days: f64,
+}
+
+/// The boundary with the longest average wait among a week's handoffs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffWeek {
+    pub week_start: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slowest: Option<BoundaryStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffReport {
+    /// Every boundary crossed in the window, slowest first.
+    pub boundaries: Vec<BoundaryStats>,
+    pub weeks: Vec<HandoffWeek>,
+    /// Cards moved at least once; older moves left no history.
+    pub cards_with_history: usize,
+    #[serde(default, skip_serializing_if = "ReportWindow::is_open")]
+    pub window: ReportWindow,
+}
+
+struct Handoff<'a> {
+    from: &'a str,
+    to: &'a str,
+    on: NaiveDate,
+    wait_days: f64,
+}
+
+/// Handoffs per boundary and the slowest boundary each week, over the
+/// window or, without a `since` date, the last `num_weeks` weeks.
+pub fn calculate_handoffs(
+    cards: &[Card],
+    num_weeks: u32,
+    window: &ReportWindow,
+    cal: &Calendar,
+) -> HandoffReport {
+    let current_week = cal.week_start(window.end(cal));
+    let first_week = match window.since {
+        Some(since) => cal.week_start(since),
+        None => current_week
+            .checked_sub_days(Days::new(num_weeks.saturating_sub(1) as u64 * 7))
+            .unwrap_or(current_week),
+    };
+    let end = window.end(cal);
+    let since = window.since.unwrap_or(first_week);
+
+    let histories: Vec<(&Card, Vec<Transition>)> = cards
+        .iter()
+        .map(|c| (c, Transition::history(c)))
+        .filter(|(_, h)| !h.is_empty())
+        .collect();
+
+    let mut handoffs = Vec::new();
+    for (card, history) in &histories {
+        let mut arrived: DateTime<Utc> = card.created_at;
+        for t in history {
+            let on = cal.date_of(t.at);
+            if on >= since && on <= end {
+                handoffs.push(Handoff {
+                    from: &t.from,
+                    to: &t.to,
+                    on,
+                    wait_days: (t.at - arrived).num_minutes().max(0) as f64 / 1440.0,
+                });
+            }
+            arrived = t.at;
+        }
+    }
+
+    let boundaries = summarize(handoffs.iter());
+
+    let mut weeks = Vec::new();
+    let mut week_start = first_week;
+    while week_start <= current_week {
+        let next = week_start
+            .checked_add_days(Days::new(7))
+            .unwrap_or(week_start);
+        let in_week = handoffs
+            .iter()
+            .filter(|h| h.on >= week_start && h.on < next);
+        weeks.push(HandoffWeek {
+            week_start,
+            slowest: summarize(in_week).into_iter().next(),
+        });
+        if next == week_start {
+            break;
+        }
+        week_start = next;
+    }
+
+    HandoffReport {
+        boundaries,
+        weeks,
+        cards_with_history: histories.len(),
+        window: *window,
+    }
+}
+
+/// Group handoffs by boundary, slowest average first.
+fn summarize<'a>(handoffs: impl Iterator<Item = &'a Handoff<'a>>) -> Vec<BoundaryStats> {
+    let mut by_boundary: BTreeMap<(&str, &str), Vec<f64>> = BTreeMap::new();
+    for h in handoffs {
+        by_boundary
+            .entry((h.from, h.to))
+            .or_default()
+            .push(h.wait_days);
+    }
+    let mut stats: Vec<BoundaryStats> = by_boundary
+        .into_iter()
+        .map(|((from, to), waits)| BoundaryStats {
+            from: from.into(),
+            to: to.into(),
+            handoffs: waits.len(),
+            avg_wait_days: waits.iter().sum::<f64>() / waits.len() as f64,
+            max_wait_days: waits.iter().copied().fold(0.0, f64::max),
+        })
+        .collect();
+    stats.sort_by(|a, b| b.avg_wait_days.total_cmp(&a.avg_wait_days));
+    stats
+}
+
+pub fn render_handoffs_text(report: &HandoffReport, locale: &Locale) -> String {
+    let mut out = format!("Handoffs ({} weeks)\n", report.weeks.len());
+    out.push_str("────────────────────────────────\n");
+
+    if report.boundaries.is_empty() {
+        out.push_str("  No handoffs recorded in this period.\n");
+        if report.cards_with_history == 0 {
+            out.push_str(
+                "  kuk records column moves from now on; earlier moves left no history.\n",
+            );
+        }
+        return out;
+    }
+
+    let width = report
+        .boundaries
+        .iter()
+        .map(|b| b.from.chars().count() + b.to.chars().count() + 3)
+        .max()
+        .unwrap_or(0);
+    for b in &report.boundaries {
+        let name = format!("{} → {}", b.from, b.to);
+        out.push_str(&format!(
+            "  {name:<width$}  {:>3} cards  avg {}d  max {}d\n",
+            b.handoffs,
+            locale.number(b.avg_wait_days, 1),
+            locale.number(b.max_wait_days, 1)
+        ));
+    }
+
+    out.push_str("\nSlowest boundary by week:\n");
+    for week in &report.weeks {
+        match &week.slowest {
+            Some(b) => out.push_str(&format!(
+                "  {}  {} → {} (avg {}d, {} cards)\n",
+                locale.date(week.week_start),
+                b.from,
+                b.to,
+                locale.number(b.avg_wait_days, 1),
+                b.handoffs
+            )),
+            None => out.push_str(&format!("  {}  -\n", locale.date(week.week_start))),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kuk::model::{Board, TRANSITIONS_KEY};
+
+    fn at(day: &str) -> DateTime<Utc> {
+        NaiveDate::parse_from_str(day, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn card(created: &str, moves: &[(&str, &str, &str)]) -> Card {
+        let mut card = Card::new("Card", "todo");
+        card.created_at = at(created);
+        let history: Vec<Transition> = moves
+            .iter()
+            .map(|(day, from, to)| Transition {
+                at: at(day),
+                from: (*from).into(),
+                to: (*to).into(),
+                origin: None,
+            })
+            .collect();
+        card.metadata.insert(
+            TRANSITIONS_KEY.into(),
+            serde_json::to_value(history).unwrap(),
+        );
+        card
+    }
+
+    #[test]
+    fn waits_per_boundary_and_slowest_per_week() {
+        let cards = vec![
+            card(
+                "2026-03-02",
+                &[
+                    ("2026-03-03", "todo", "doing"),
+                    ("2026-03-05", "doing", "review"),
+                    ("2026-03-11", "review", "done"),
+                ],
+            ),
+            card("2026-03-09", &[("2026-03-10", "todo", "doing")]),
+            Card::new("Never moved", "todo"),
+        ];
+        let window = ReportWindow::parse(None, Some("2026-03-13")).unwrap();
+        let report = calculate_handoffs(&cards, 2, &window, &Calendar::default());
+
+        assert_eq!(report.cards_with_history, 2);
+        let first = &report.boundaries[0];
+        assert_eq!((first.from.as_str(), first.to.as_str()), ("review", "done"));
+        assert_eq!(first.avg_wait_days, 6.0);
+        let todo = report.boundaries.iter().find(|b| b.from == "todo").unwrap();
+        assert_eq!(todo.handoffs, 2);
+        assert_eq!(todo.avg_wait_days, 1.0);
+
+        let slowest: Vec<&str> = report
+            .weeks
+            .iter()
+            .map(|w| w.slowest.as_ref().unwrap().from.as_str())
+            .collect();
+        assert_eq!(slowest, ["doing", "review"]);
+
+        let text = render_handoffs_text(&report, &Locale::default());
+        assert!(text.contains("review → done     1 cards  avg 6.0d  max 6.0d"));
+        assert!(text.contains("2026-03-02  doing → review (avg 2.0d, 1 cards)"));
+    }
+
+    #[test]
+    fn moves_on_a_board_feed_the_report() {
+        let mut board = Board::default_board();
+        let card = Card::new("Fresh", "todo");
+        let id = card.id.clone();
+        board.cards.push(card);
+        board.move_card(&id, "doing");
+
+        let report = calculate_handoffs(
+            &board.cards,
+            1,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        assert_eq!(report.boundaries.len(), 1);
+        assert_eq!(report.boundaries[0].to, "doing");
+    }
+}
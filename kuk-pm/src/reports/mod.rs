@@ -1,9 +1,29 @@
-use chrono::{Datelike, Days, NaiveDate, Utc};
-use serde::Serialize;
+mod cache;
+mod handoffs;
+mod okr;
+mod overview;
+mod wip_history;
 
-use kuk::model::Board;
+pub use cache::cached;
+pub use handoffs::{
+    BoundaryStats, HandoffReport, HandoffWeek, calculate_handoffs, render_handoffs_text,
+};
+pub use okr::{KeyResultProgress, ObjectiveProgress, calculate_okr_progress, render_okr_text};
+pub use overview::{
+    BoardOverview, Overview, Risk, RiskKind, STALE_DAYS, SprintOverview, calculate_overview,
+};
+pub use wip_history::{
+    ViolationCard, WipHistoryReport, WipViolation, calculate_wip_history, render_wip_history_text,
+};
 
-use crate::model::Sprint;
+use std::collections::HashSet;
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use kuk::model::{Board, Card};
+
+use crate::model::{Calendar, Locale, Milestone, OutlierPolicy, Sprint};
 
 // --- Column classification helpers ---
 
@@ -21,30 +41,98 @@ pub fn is_wip_column(name: &str) -> bool {
     !is_done_column(name) && !is_todo_column(name)
 }
 
-fn week_start_monday(date: NaiveDate) -> NaiveDate {
-    let days_from_monday = date.weekday().num_days_from_monday() as u64;
-    date.checked_sub_days(Days::new(days_from_monday))
-        .unwrap_or(date)
+/// Whether cards in `column` of `board` are being worked on: a WIP column
+/// that isn't a queue.
+pub fn is_in_progress(board: &Board, column: &str) -> bool {
+    is_wip_column(column) && !board.is_queue(column)
+}
+
+/// When a card reached its current column, from the moves kuk records;
+/// `updated_at` stands in for cards last moved before kuk kept history.
+/// For a done card this is when it was finished.
+pub fn completed_at(card: &Card) -> DateTime<Utc> {
+    card.entered_column_at().unwrap_or(card.updated_at)
+}
+
+/// Date range a report covers, inclusive. An open start means all history;
+/// an open end means today.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ReportWindow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<NaiveDate>,
+}
+
+impl ReportWindow {
+    /// Build a window from optional `YYYY-MM-DD` bounds.
+    pub fn parse(since: Option<&str>, until: Option<&str>) -> Result<Self, String> {
+        let parse = |s: &str| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| format!("invalid date: {s}"))
+        };
+        let window = ReportWindow {
+            since: since.map(parse).transpose()?,
+            until: until.map(parse).transpose()?,
+        };
+        if let (Some(s), Some(u)) = (window.since, window.until)
+            && s > u
+        {
+            return Err("--since must not be after --until".into());
+        }
+        Ok(window)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    /// The day the report is anchored at.
+    pub fn end(&self, cal: &Calendar) -> NaiveDate {
+        self.until.unwrap_or_else(|| cal.today())
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.since.is_none_or(|s| date >= s) && self.until.is_none_or(|u| date <= u)
+    }
 }
 
 // ─── Velocity ────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeekBucket {
     pub week_start: NaiveDate,
     pub count: usize,
+    /// Distinct commit authors that week, set by [`normalize_velocity`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contributors: Option<usize>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VelocityReport {
     pub weeks: Vec<WeekBucket>,
     pub average: f64,
     pub trend: String,
+    #[serde(default, skip_serializing_if = "ReportWindow::is_open")]
+    pub window: ReportWindow,
+    /// Cards per contributor-week, set by [`normalize_velocity`] when anyone
+    /// committed in the window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalized: Option<f64>,
 }
 
-pub fn calculate_velocity(boards: &[Board], num_weeks: u32) -> VelocityReport {
-    let now = Utc::now().date_naive();
-    let current_week = week_start_monday(now);
+/// Weekly throughput ending at the window's end. With a `since` date the
+/// weeks span the window; otherwise `num_weeks` are counted back.
+pub fn calculate_velocity(
+    cards: &[Card],
+    num_weeks: u32,
+    window: &ReportWindow,
+    cal: &Calendar,
+) -> VelocityReport {
+    let current_week = cal.week_start(window.end(cal));
+    let num_weeks = match window.since {
+        Some(since) => ((current_week - cal.week_start(since)).num_days() / 7 + 1).max(1) as u32,
+        None => num_weeks,
+    };
 
     // Build week boundaries going back num_weeks
     let week_starts: Vec<NaiveDate> = (0..num_weeks)
@@ -57,11 +145,11 @@ pub fn calculate_velocity(boards: &[Board], num_weeks: u32) -> VelocityReport {
         .collect();
 
     // Collect done cards' completion dates
-    let done_dates: Vec<NaiveDate> = boards
+    let done_dates: Vec<NaiveDate> = cards
         .iter()
-        .flat_map(|b| b.cards.iter())
         .filter(|c| !c.archived && is_done_column(&c.column))
-        .map(|c| c.updated_at.date_naive())
+        .map(|c| cal.date_of(completed_at(c)))
+        .filter(|d| window.contains(*d))
         .collect();
 
     // Bucket done cards into weeks
@@ -73,6 +161,7 @@ pub fn calculate_velocity(boards: &[Board], num_weeks: u32) -> VelocityReport {
             WeekBucket {
                 week_start: ws,
                 count,
+                contributors: None,
             }
         })
         .collect();
@@ -84,28 +173,84 @@ pub fn calculate_velocity(boards: &[Board], num_weeks: u32) -> VelocityReport {
         0.0
     };
 
-    // Trend: compare first half vs second half
+    let trend = velocity_trend(&weeks);
+    VelocityReport {
+        weeks,
+        average,
+        trend,
+        window: *window,
+        normalized: None,
+    }
+}
+
+/// Compare the first half of the weeks with the second. With contributor
+/// counts, the second half is held against what the first half's rate per
+/// contributor-week predicts for it, so a week with half the team away
+/// doesn't read as a decline.
+fn velocity_trend(weeks: &[WeekBucket]) -> String {
     let half = weeks.len() / 2;
-    let first_half: usize = weeks[..half].iter().map(|b| b.count).sum();
-    let second_half: usize = weeks[half..].iter().map(|b| b.count).sum();
-    let trend = if second_half > first_half + 1 {
+    let cards = |ws: &[WeekBucket]| ws.iter().map(|b| b.count).sum::<usize>() as f64;
+    let people = |ws: &[WeekBucket]| ws.iter().filter_map(|b| b.contributors).sum::<usize>();
+    let (first, second) = weeks.split_at(half);
+
+    let mut first_half = cards(first);
+    let second_half = cards(second);
+    if people(first) > 0 && people(second) > 0 {
+        first_half = first_half * people(second) as f64 / people(first) as f64;
+    }
+    if second_half > first_half + 1.0 {
         "improving".into()
-    } else if first_half > second_half + 1 {
+    } else if first_half > second_half + 1.0 {
         "declining".into()
     } else {
         "stable".into()
-    };
+    }
+}
 
-    VelocityReport {
-        weeks,
-        average,
-        trend,
+/// Count the distinct authors of `commits` in each week of the report, set
+/// the cards per contributor-week, and recompute the trend with them.
+/// Commits outside the report's weeks are ignored.
+pub fn normalize_velocity(
+    report: &mut VelocityReport,
+    commits: &[crate::git::CommitInfo],
+    cal: &Calendar,
+) {
+    for week in &mut report.weeks {
+        let week_end = week
+            .week_start
+            .checked_add_days(Days::new(7))
+            .unwrap_or(week.week_start);
+        let authors: HashSet<String> = commits
+            .iter()
+            .filter(|c| {
+                DateTime::from_timestamp(c.time, 0)
+                    .map(|t| cal.date_of(t))
+                    .is_some_and(|d| d >= week.week_start && d < week_end)
+            })
+            .map(crate::git::CommitInfo::author_key)
+            .collect();
+        week.contributors = Some(authors.len());
     }
+
+    let cards: usize = report.weeks.iter().map(|w| w.count).sum();
+    let contributor_weeks: usize = report.weeks.iter().filter_map(|w| w.contributors).sum();
+    report.normalized = (contributor_weeks > 0).then(|| cards as f64 / contributor_weeks as f64);
+    report.trend = velocity_trend(&report.weeks);
 }
 
-pub fn render_velocity_text(report: &VelocityReport) -> String {
+pub fn render_velocity_text(report: &VelocityReport, locale: &Locale) -> String {
     let mut out = String::new();
-    out.push_str(&format!("Velocity (last {} weeks)\n", report.weeks.len()));
+    match (report.window.since, report.window.until) {
+        (None, None) => {
+            out.push_str(&format!("Velocity (last {} weeks)\n", report.weeks.len()));
+        }
+        (since, until) => out.push_str(&format!(
+            "Velocity ({} → {}, {} weeks)\n",
+            since.map_or("start".to_string(), |d| locale.date(d)),
+            until.map_or("today".to_string(), |d| locale.date(d)),
+            report.weeks.len()
+        )),
+    }
     out.push_str("────────────────────────────────\n");
 
     let max_count = report
@@ -119,13 +264,33 @@ pub fn render_velocity_text(report: &VelocityReport) -> String {
     for week in &report.weeks {
         let bar_len = week.count * 20 / max_count;
         let bar: String = "█".repeat(bar_len);
+        match week.contributors {
+            Some(n) => out.push_str(&format!(
+                "  {}  {:>3}  {:<20}  {n} contributor{}\n",
+                locale.date(week.week_start),
+                week.count,
+                bar,
+                if n == 1 { "" } else { "s" }
+            )),
+            None => out.push_str(&format!(
+                "  {}  {:>3}  {}\n",
+                locale.date(week.week_start),
+                week.count,
+                bar
+            )),
+        }
+    }
+
+    out.push_str(&format!(
+        "\nAverage: {} cards/week\n",
+        locale.number(report.average, 1)
+    ));
+    if let Some(normalized) = report.normalized {
         out.push_str(&format!(
-            "  {}  {:>3}  {}\n",
-            week.week_start, week.count, bar
+            "Per contributor: {} cards/contributor-week\n",
+            locale.number(normalized, 2)
         ));
     }
-
-    out.push_str(&format!("\nAverage: {:.1} cards/week\n", report.average));
     let trend_arrow = match report.trend.as_str() {
         "improving" => "↑ improving",
         "declining" => "↓ declining",
@@ -135,34 +300,58 @@ pub fn render_velocity_text(report: &VelocityReport) -> String {
     out
 }
 
+/// Velocity on one line for status bars, e.g.
+/// `velocity: 4.5/wk ▃▆▅█ ↑ improving`.
+pub fn render_velocity_oneline(report: &VelocityReport, locale: &Locale) -> String {
+    let counts: Vec<Option<f64>> = report.weeks.iter().map(|w| Some(w.count as f64)).collect();
+    let arrow = match report.trend.as_str() {
+        "improving" => "↑",
+        "declining" => "↓",
+        _ => "→",
+    };
+    let per_contributor = report.normalized.map_or(String::new(), |n| {
+        format!(" ({}/contributor)", locale.number(n, 1))
+    });
+    format!(
+        "velocity: {}/wk{per_contributor} {} {arrow} {}\n",
+        locale.number(report.average, 1),
+        sparkline(&counts),
+        report.trend
+    )
+}
+
 // ─── Burndown ────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BurndownPoint {
     pub date: NaiveDate,
     pub ideal: f64,
     pub actual: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BurndownReport {
     pub sprint_name: String,
     pub start: NaiveDate,
     pub end: NaiveDate,
     pub total_cards: usize,
     pub points: Vec<BurndownPoint>,
+    /// Where the sprint stands today, or on its last day once it is over.
+    pub current: BurndownPoint,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<Milestone>,
 }
 
-pub fn calculate_burndown(boards: &[Board], sprint: &Sprint) -> BurndownReport {
-    let all_cards: Vec<_> = boards
-        .iter()
-        .flat_map(|b| b.cards.iter())
-        .filter(|c| !c.archived)
-        .collect();
+pub fn calculate_burndown(cards: &[Card], sprint: &Sprint, cal: &Calendar) -> BurndownReport {
+    let all_cards: Vec<_> = cards.iter().filter(|c| !c.archived).collect();
 
     let total_cards = all_cards.len();
-    let sprint_days = (sprint.end - sprint.start).num_days().max(1) as f64;
-    let today = Utc::now().date_naive();
+    // The ideal line only burns on working days
+    let sprint_days = cal
+        .team
+        .working_days_between(sprint.start, sprint.end)
+        .max(1) as f64;
+    let today = cal.today();
 
     // Generate points at weekly intervals
     let mut points = Vec::new();
@@ -172,13 +361,13 @@ pub fn calculate_burndown(boards: &[Board], sprint: &Sprint) -> BurndownReport {
             break;
         }
 
-        let day_offset = (date - sprint.start).num_days() as f64;
+        let day_offset = cal.team.working_days_between(sprint.start, date) as f64;
         let ideal = total_cards as f64 * (1.0 - day_offset / sprint_days);
 
-        // Count cards done by this date (using updated_at as proxy)
+        // Count cards done by this date
         let done_by_date = all_cards
             .iter()
-            .filter(|c| is_done_column(&c.column) && c.updated_at.date_naive() <= date)
+            .filter(|c| is_done_column(&c.column) && cal.date_of(completed_at(c)) <= date)
             .count();
         let actual = total_cards.saturating_sub(done_by_date);
 
@@ -207,30 +396,63 @@ pub fn calculate_burndown(boards: &[Board], sprint: &Sprint) -> BurndownReport {
         });
     }
 
+    let as_of = today.min(sprint.end);
+    let elapsed = if as_of > sprint.start {
+        cal.team.working_days_between(sprint.start, as_of) as f64
+    } else {
+        0.0
+    };
+    let done = all_cards
+        .iter()
+        .filter(|c| is_done_column(&c.column) && cal.date_of(completed_at(c)) <= as_of)
+        .count();
+    let current = BurndownPoint {
+        date: as_of,
+        ideal: total_cards as f64 * (1.0 - elapsed / sprint_days).max(0.0),
+        actual: total_cards.saturating_sub(done),
+    };
+
     BurndownReport {
         sprint_name: sprint.name.clone(),
         start: sprint.start,
         end: sprint.end,
         total_cards,
         points,
+        current,
+        milestone: sprint.milestone.clone(),
     }
 }
 
-pub fn render_burndown_text(report: &BurndownReport) -> String {
+pub fn render_burndown_text(report: &BurndownReport, locale: &Locale) -> String {
     let mut out = String::new();
     out.push_str(&format!(
         "Burndown: {} ({} → {})\n",
-        report.sprint_name, report.start, report.end
+        report.sprint_name,
+        locale.date(report.start),
+        locale.date(report.end)
     ));
     out.push_str("──────────────────────────────────────────────\n");
-    out.push_str(&format!("Total scope: {} cards\n\n", report.total_cards));
+    out.push_str(&format!("Total scope: {} cards\n", report.total_cards));
+    if let Some(m) = &report.milestone {
+        out.push_str(&format!(
+            "Milestone:   #{} — {}/{} issues closed ({:.0}%)\n",
+            m.number,
+            m.closed_issues,
+            m.open_issues + m.closed_issues,
+            m.progress()
+        ));
+    }
+    out.push('\n');
     out.push_str("Date         Ideal  Actual  Remaining\n");
 
     for point in &report.points {
         let bar: String = "█".repeat(point.actual.min(30));
         out.push_str(&format!(
-            "{}  {:>5.1}  {:>6}  {}\n",
-            point.date, point.ideal, point.actual, bar
+            "{}  {:>5}  {:>6}  {}\n",
+            locale.date(point.date),
+            locale.number(point.ideal, 1),
+            point.actual,
+            bar
         ));
     }
 
@@ -251,37 +473,131 @@ pub fn render_burndown_text(report: &BurndownReport) -> String {
     out
 }
 
+/// The sprint on one line for status bars, e.g.
+/// `s1: 12/30 done, day 6/14, ▼ behind`.
+pub fn render_burndown_oneline(report: &BurndownReport) -> String {
+    let current = &report.current;
+    let days = (report.end - report.start).num_days() + 1;
+    let day = ((current.date - report.start).num_days() + 1).clamp(0, days);
+    let status = if current.actual == 0 {
+        "✓ complete"
+    } else if current.actual as f64 <= current.ideal {
+        "▲ on track"
+    } else {
+        "▼ behind"
+    };
+    format!(
+        "{}: {}/{} done, day {day}/{days}, {status}\n",
+        report.sprint_name,
+        report.total_cards - current.actual,
+        report.total_cards
+    )
+}
+
 // ─── Roadmap ─────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoadmapWeek {
     pub week_start: NaiveDate,
     pub todo: usize,
     pub wip: usize,
     pub done: usize,
     pub milestones: Vec<String>,
+    /// Share of a normal week's capacity after holidays and vacations.
+    pub capacity: f64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoadmapReport {
     pub weeks: Vec<RoadmapWeek>,
     pub velocity: f64,
+    /// Projected date the remaining todo/WIP cards are done.
+    pub eta: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub epics: Vec<EpicStatus>,
+}
+
+/// How far along an epic's cards are.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpicStatus {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_id: Option<String>,
+    pub title: String,
+    /// Cards in a done column or archived.
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Progress of every active epic among `cards`, which should include
+/// archived cards so finished work still counts.
+fn epic_statuses(cards: &[Card]) -> Vec<EpicStatus> {
+    cards
+        .iter()
+        .filter(|c| c.epic && !c.archived)
+        .map(|epic| {
+            let children: Vec<&Card> = cards
+                .iter()
+                .filter(|c| c.parent.as_deref() == Some(epic.id.as_str()))
+                .collect();
+            EpicStatus {
+                id: epic.id.clone(),
+                short_id: epic.short_id(),
+                title: epic.title.clone(),
+                done: children
+                    .iter()
+                    .filter(|c| c.archived || is_done_column(&c.column))
+                    .count(),
+                total: children.len(),
+            }
+        })
+        .collect()
+}
+
+/// People whose time off matters: card assignees plus anyone with a vacation.
+fn team_size(cards: &[&Card], cal: &Calendar) -> usize {
+    cards
+        .iter()
+        .filter_map(|c| c.assignee.as_deref())
+        .chain(cal.team.vacations.iter().map(|v| v.person.as_str()))
+        .collect::<std::collections::BTreeSet<_>>()
+        .len()
+}
+
+/// Walk forward from `from` spending `velocity` per normal week, prorated per
+/// working day, until `remaining` cards are done. Gives up after five years.
+fn forecast_eta(
+    from: NaiveDate,
+    remaining: usize,
+    velocity: f64,
+    team_size: usize,
+    cal: &Calendar,
+) -> Option<NaiveDate> {
+    if remaining == 0 {
+        return Some(from);
+    }
+    let per_day = velocity / cal.team.working_days.len().max(1) as f64;
+    let mut done = 0.0;
+    for date in from.iter_days().take(5 * 366) {
+        done += per_day * cal.team.availability(date, team_size);
+        if done >= remaining as f64 {
+            return Some(date);
+        }
+    }
+    None
 }
 
 pub fn calculate_roadmap(
-    boards: &[Board],
+    cards: &[Card],
     sprints: &[Sprint],
     num_weeks: u32,
     velocity: f64,
+    cal: &Calendar,
 ) -> RoadmapReport {
-    let now = Utc::now().date_naive();
-    let current_week = week_start_monday(now);
+    let current_week = cal.week_start(cal.today());
 
-    let all_cards: Vec<_> = boards
-        .iter()
-        .flat_map(|b| b.cards.iter())
-        .filter(|c| !c.archived)
-        .collect();
+    // Epics are containers; their cards carry the work
+    let all_cards: Vec<_> = cards.iter().filter(|c| !c.archived && !c.epic).collect();
 
     let total_todo = all_cards
         .iter()
@@ -297,6 +613,7 @@ pub fn calculate_roadmap(
         .count();
 
     let cards_per_week = velocity.max(0.1);
+    let team_size = team_size(&all_cards, cal);
 
     let mut weeks = Vec::new();
     let mut remaining_todo = total_todo as f64;
@@ -315,6 +632,7 @@ pub fn calculate_roadmap(
             .filter(|s| s.end >= ws && s.end < we)
             .map(|s| format!("{} ends", s.name))
             .collect();
+        let capacity = cal.team.week_capacity(ws, team_size);
 
         weeks.push(RoadmapWeek {
             week_start: ws,
@@ -322,50 +640,88 @@ pub fn calculate_roadmap(
             wip: remaining_wip.round().max(0.0) as usize,
             done: projected_done.round() as usize,
             milestones,
+            capacity,
         });
 
         // Project cards flowing through pipeline
         if i > 0 {
-            let completed = cards_per_week.min(remaining_wip);
+            let throughput = cards_per_week * capacity;
+            let completed = throughput.min(remaining_wip);
             remaining_wip -= completed;
             projected_done += completed;
 
-            let started = cards_per_week.min(remaining_todo);
+            let started = throughput.min(remaining_todo);
             remaining_todo -= started;
             remaining_wip += started;
         }
     }
 
-    RoadmapReport { weeks, velocity }
+    let eta = forecast_eta(
+        cal.today(),
+        total_todo + total_wip,
+        cards_per_week,
+        team_size,
+        cal,
+    );
+
+    RoadmapReport {
+        weeks,
+        velocity,
+        eta,
+        epics: epic_statuses(cards),
+    }
 }
 
-pub fn render_roadmap_text(report: &RoadmapReport) -> String {
+pub fn render_roadmap_text(report: &RoadmapReport, locale: &Locale) -> String {
     let mut out = String::new();
     out.push_str(&format!(
-        "Roadmap (next {} weeks, velocity: {:.1}/wk)\n",
+        "Roadmap (next {} weeks, velocity: {}/wk)\n",
         report.weeks.len(),
-        report.velocity
+        locale.number(report.velocity, 1)
     ));
     out.push_str("──────────────────────────────────────────────────\n");
     out.push_str("Week          Todo  Doing  Done  Milestones\n");
 
     for week in &report.weeks {
-        let milestones = if week.milestones.is_empty() {
-            String::new()
-        } else {
-            week.milestones.join(", ")
-        };
+        let mut notes = week.milestones.clone();
+        if week.capacity < 1.0 {
+            notes.push(format!("{:.0}% capacity", week.capacity * 100.0));
+        }
+        let milestones = notes.join(", ");
         out.push_str(&format!(
             "{}  {:>4}  {:>5}  {:>4}  {}\n",
-            week.week_start, week.todo, week.wip, week.done, milestones
+            locale.date(week.week_start),
+            week.todo,
+            week.wip,
+            week.done,
+            milestones
         ));
     }
 
+    if !report.epics.is_empty() {
+        out.push_str("\nEpics\n");
+        for epic in &report.epics {
+            let percent = (epic.done * 100).checked_div(epic.total).unwrap_or(0);
+            let name = match &epic.short_id {
+                Some(short_id) => format!("{short_id} {}", epic.title),
+                None => epic.title.clone(),
+            };
+            out.push_str(&format!(
+                "  {name:<32} {:>3}/{:<3} done ({percent}%)\n",
+                epic.done, epic.total
+            ));
+        }
+    }
+
     let remaining = report.weeks.first().map(|w| w.todo + w.wip).unwrap_or(0);
     if remaining > 0 && report.velocity > 0.0 {
         let weeks_to_complete = (remaining as f64 / report.velocity).ceil() as u32;
+        let eta = report
+            .eta
+            .map(|d| format!(", around {}", locale.date(d)))
+            .unwrap_or_default();
         out.push_str(&format!(
-            "\nEstimated completion: ~{weeks_to_complete} weeks ({remaining} cards remaining)\n"
+            "\nEstimated completion: ~{weeks_to_complete} weeks{eta} ({remaining} cards remaining)\n"
         ));
     } else if remaining == 0 {
         out.push_str("\nAll work complete\n");
@@ -376,7 +732,7 @@ pub fn render_roadmap_text(report: &RoadmapReport) -> String {
 
 // ─── Stats ───────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatsReport {
     pub board_name: String,
     pub total_cards: usize,
@@ -385,36 +741,132 @@ pub struct StatsReport {
     pub wip_count: usize,
     pub wip_limit: Option<u32>,
     pub wip_violation: bool,
+    /// Cards waiting in queue columns, left out of `wip_count`.
+    #[serde(default)]
+    pub queued: usize,
     pub done_7d: usize,
     pub done_30d: usize,
+    /// Done cards completed inside the window.
+    pub completed: usize,
     pub avg_cycle_days: Option<f64>,
+    /// 95th percentile cycle time; only computed with enough done cards.
+    pub cycle_p95_days: Option<f64>,
+    pub cycle_outliers: Vec<CycleOutlier>,
+    pub outlier_policy: OutlierPolicy,
     pub oldest_wip: Option<(String, i64)>,
+    /// Unfinished cards whose due date passed before the window's end.
+    #[serde(default)]
+    pub overdue: usize,
+    /// Average time completed cards spent in each column before done.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub column_times: Vec<ColumnTime>,
+    /// Weekly trends, with `stats --history`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<StatsHistory>,
+    #[serde(default, skip_serializing_if = "ReportWindow::is_open")]
+    pub window: ReportWindow,
+}
+
+/// A done card whose cycle time exceeds the p95.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleOutlier {
+    pub card_id: String,
+    pub title: String,
+    pub cycle_days: f64,
+}
+
+/// Average time the cards completed in a report's window spent in one
+/// column, over the cards that passed through it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnTime {
+    pub column: String,
+    pub avg_days: f64,
+    pub cards: usize,
+}
+
+/// Per-column averages of [`Card::time_in_columns`] for finished cards, up
+/// to when each was finished, in board column order.
+fn column_times(board: &Board, done_cards: &[&&Card]) -> Vec<ColumnTime> {
+    let mut totals: Vec<(String, f64, usize)> = Vec::new();
+    for card in done_cards {
+        for (column, spent) in card.time_in_columns(completed_at(card)) {
+            if is_done_column(&column) {
+                continue;
+            }
+            let days = spent.num_hours() as f64 / 24.0;
+            match totals.iter_mut().find(|(c, _, _)| *c == column) {
+                Some((_, total, cards)) => {
+                    *total += days;
+                    *cards += 1;
+                }
+                None => totals.push((column, days, 1)),
+            }
+        }
+    }
+    // Columns since renamed or removed keep their first-seen order at the end
+    let position = |name: &str| {
+        board
+            .columns
+            .iter()
+            .position(|c| c.name == name)
+            .unwrap_or(usize::MAX)
+    };
+    totals.sort_by_key(|(column, _, _)| position(column));
+    totals
+        .into_iter()
+        .map(|(column, total, cards)| ColumnTime {
+            column,
+            avg_days: total / cards as f64,
+            cards,
+        })
+        .collect()
 }
 
-pub fn calculate_stats(board: &Board) -> StatsReport {
-    let now = Utc::now();
-    let cutoff_7d = now
-        .date_naive()
-        .checked_sub_days(Days::new(7))
-        .unwrap_or(now.date_naive());
-    let cutoff_30d = now
-        .date_naive()
-        .checked_sub_days(Days::new(30))
-        .unwrap_or(now.date_naive());
+/// Below this many done cards the p95 is too noisy to call anything an outlier.
+const MIN_OUTLIER_SAMPLE: usize = 4;
+
+/// Linear-interpolated percentile of an ascending slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = pct / 100.0 * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+/// Snapshot counts plus throughput and cycle time for cards completed in
+/// `window`. Throughput and WIP age are measured back from the window's end.
+pub fn calculate_stats(
+    board: &Board,
+    policy: OutlierPolicy,
+    window: &ReportWindow,
+    cal: &Calendar,
+) -> StatsReport {
+    let end = window.end(cal);
+    let cutoff_7d = end.checked_sub_days(Days::new(7)).unwrap_or(end);
+    let cutoff_30d = end.checked_sub_days(Days::new(30)).unwrap_or(end);
+    // Completion day of done cards inside the window
+    let completed_on = |c: &kuk::model::Card| {
+        let d = cal.date_of(completed_at(c));
+        (is_done_column(&c.column) && window.contains(d) && d <= end).then_some(d)
+    };
 
     let active_cards: Vec<_> = board.cards.iter().filter(|c| !c.archived).collect();
     let archived_cards = board.cards.iter().filter(|c| c.archived).count();
 
     let wip_count = active_cards
         .iter()
-        .filter(|c| is_wip_column(&c.column))
+        .filter(|c| is_in_progress(board, &c.column))
+        .count();
+    let queued = active_cards
+        .iter()
+        .filter(|c| board.is_queue(&c.column))
         .count();
 
     // Find WIP limit for middle columns
     let wip_limit = board
         .columns
         .iter()
-        .filter(|col| is_wip_column(&col.name))
+        .filter(|col| is_in_progress(board, &col.name))
         .filter_map(|col| col.wip_limit)
         .next();
 
@@ -423,39 +875,74 @@ pub fn calculate_stats(board: &Board) -> StatsReport {
     // Throughput: done cards completed within window
     let done_7d = active_cards
         .iter()
-        .filter(|c| is_done_column(&c.column) && c.updated_at.date_naive() >= cutoff_7d)
+        .filter(|c| completed_on(c).is_some_and(|d| d >= cutoff_7d))
         .count();
     let done_30d = active_cards
         .iter()
-        .filter(|c| is_done_column(&c.column) && c.updated_at.date_naive() >= cutoff_30d)
+        .filter(|c| completed_on(c).is_some_and(|d| d >= cutoff_30d))
         .count();
 
-    // Cycle time: avg(updated_at - created_at) for done cards
+    // Cycle time: avg(completion - created_at) for done cards
     let done_cards: Vec<_> = active_cards
         .iter()
-        .filter(|c| is_done_column(&c.column))
+        .filter(|c| completed_on(c).is_some())
         .collect();
 
-    let avg_cycle_days = if done_cards.is_empty() {
+    let cycle_days: Vec<f64> = done_cards
+        .iter()
+        .map(|c| (completed_at(c) - c.created_at).num_hours() as f64 / 24.0)
+        .collect();
+
+    let cycle_p95_days = (cycle_days.len() >= MIN_OUTLIER_SAMPLE).then(|| {
+        let mut sorted = cycle_days.clone();
+        sorted.sort_by(f64::total_cmp);
+        percentile(&sorted, 95.0)
+    });
+
+    let mut cycle_outliers: Vec<CycleOutlier> = match cycle_p95_days {
+        Some(p95) => done_cards
+            .iter()
+            .zip(&cycle_days)
+            .filter(|(_, days)| **days > p95)
+            .map(|(c, days)| CycleOutlier {
+                card_id: c.id.clone(),
+                title: c.title.clone(),
+                cycle_days: *days,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    cycle_outliers.sort_by(|a, b| b.cycle_days.total_cmp(&a.cycle_days));
+
+    let averaged: Vec<f64> = match (policy, cycle_p95_days) {
+        (OutlierPolicy::Exclude, Some(p95)) => {
+            cycle_days.iter().copied().filter(|d| *d <= p95).collect()
+        }
+        (OutlierPolicy::Winsorize, Some(p95)) => cycle_days.iter().map(|d| d.min(p95)).collect(),
+        _ => cycle_days,
+    };
+    let avg_cycle_days = if averaged.is_empty() {
         None
     } else {
-        let total_hours: f64 = done_cards
-            .iter()
-            .map(|c| (c.updated_at - c.created_at).num_hours() as f64)
-            .sum();
-        Some(total_hours / done_cards.len() as f64 / 24.0)
+        Some(averaged.iter().sum::<f64>() / averaged.len() as f64)
     };
 
     // Oldest WIP card
     let oldest_wip = active_cards
         .iter()
-        .filter(|c| is_wip_column(&c.column))
+        .filter(|c| is_in_progress(board, &c.column) && cal.date_of(c.created_at) <= end)
         .min_by_key(|c| c.created_at)
         .map(|c| {
-            let days = (now - c.created_at).num_days();
+            let days = (end - cal.date_of(c.created_at)).num_days();
             (c.title.clone(), days)
         });
 
+    let overdue = active_cards
+        .iter()
+        .filter(|c| !is_done_column(&c.column))
+        .filter(|c| c.due.is_some_and(|due| cal.date_of(due) < end))
+        .count();
+
     StatsReport {
         board_name: board.name.clone(),
         total_cards: board.cards.len(),
@@ -464,14 +951,145 @@ pub fn calculate_stats(board: &Board) -> StatsReport {
         wip_count,
         wip_limit,
         wip_violation,
+        queued,
         done_7d,
         done_30d,
+        completed: done_cards.len(),
         avg_cycle_days,
+        cycle_p95_days,
+        cycle_outliers,
+        outlier_policy: policy,
         oldest_wip,
+        overdue,
+        column_times: column_times(board, &done_cards),
+        history: None,
+        window: *window,
     }
 }
 
-pub fn render_stats_text(report: &StatsReport) -> String {
+/// One week of [`StatsHistory`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsWeek {
+    pub week_start: NaiveDate,
+    /// Cards in a WIP column at the end of the week.
+    pub wip: usize,
+    pub throughput: usize,
+    pub avg_cycle_days: Option<f64>,
+}
+
+/// Weekly WIP, throughput and cycle time leading up to the window's end.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsHistory {
+    pub weeks: Vec<StatsWeek>,
+}
+
+/// Rebuild the last `num_weeks` of trends from card timestamps, since boards
+/// keep no snapshots. A card counts as WIP from its creation until it was
+/// finished (see [`completed_at`]) if it sits in a WIP column that isn't a
+/// queue or is done now; cards still in a todo column never counted.
+pub fn calculate_stats_history(
+    board: &Board,
+    num_weeks: u32,
+    window: &ReportWindow,
+    cal: &Calendar,
+) -> StatsHistory {
+    let current_week = cal.week_start(window.end(cal));
+    let cards: Vec<_> = board.cards.iter().filter(|c| !c.archived).collect();
+
+    let weeks = (0..num_weeks)
+        .rev()
+        .map(|i| {
+            let ws = current_week
+                .checked_sub_days(Days::new(i as u64 * 7))
+                .unwrap_or(current_week);
+            let we = ws.checked_add_days(Days::new(7)).unwrap_or(ws);
+
+            let wip = cards
+                .iter()
+                .filter(|c| cal.date_of(c.created_at) < we)
+                .filter(|c| {
+                    if is_done_column(&c.column) {
+                        cal.date_of(completed_at(c)) >= we
+                    } else {
+                        is_in_progress(board, &c.column)
+                    }
+                })
+                .count();
+
+            let cycle_days: Vec<f64> = cards
+                .iter()
+                .filter(|c| is_done_column(&c.column))
+                .filter(|c| (ws..we).contains(&cal.date_of(completed_at(c))))
+                .map(|c| (completed_at(c) - c.created_at).num_hours() as f64 / 24.0)
+                .collect();
+
+            StatsWeek {
+                week_start: ws,
+                wip,
+                throughput: cycle_days.len(),
+                avg_cycle_days: (!cycle_days.is_empty())
+                    .then(|| cycle_days.iter().sum::<f64>() / cycle_days.len() as f64),
+            }
+        })
+        .collect();
+
+    StatsHistory { weeks }
+}
+
+/// Unicode block sparkline scaled to the largest value. `None` is a gap.
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().flatten().copied().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|v| match v {
+            None => ' ',
+            Some(_) if max <= 0.0 => BARS[0],
+            Some(v) => BARS[((v / max) * 7.0).round().clamp(0.0, 7.0) as usize],
+        })
+        .collect()
+}
+
+fn render_history_text(history: &StatsHistory, locale: &Locale) -> String {
+    let Some(first) = history.weeks.first() else {
+        return String::new();
+    };
+    let last = history.weeks.last().expect("weeks is not empty");
+    let series = |f: &dyn Fn(&StatsWeek) -> Option<f64>| {
+        sparkline(&history.weeks.iter().map(f).collect::<Vec<_>>())
+    };
+
+    let mut out = format!(
+        "\nTrends ({} weeks from {})\n",
+        history.weeks.len(),
+        locale.date(first.week_start)
+    );
+    out.push_str(&format!(
+        "  WIP          {}  now {}\n",
+        series(&|w| Some(w.wip as f64)),
+        last.wip
+    ));
+    out.push_str(&format!(
+        "  Throughput   {}  {} this week\n",
+        series(&|w| Some(w.throughput as f64)),
+        last.throughput
+    ));
+    let latest_cycle = history
+        .weeks
+        .iter()
+        .rev()
+        .find_map(|w| w.avg_cycle_days)
+        .map_or("no data".to_string(), |d| {
+            format!("latest {} days", locale.number(d, 1))
+        });
+    out.push_str(&format!(
+        "  Cycle time   {}  {latest_cycle}\n",
+        series(&|w| w.avg_cycle_days)
+    ));
+    out
+}
+
+pub fn render_stats_text(report: &StatsReport, locale: &Locale) -> String {
     let mut out = String::new();
     out.push_str("Project Statistics\n");
     out.push_str("──────────────────\n");
@@ -479,6 +1097,19 @@ pub fn render_stats_text(report: &StatsReport) -> String {
         "Board: {} ({} active, {} archived)\n\n",
         report.board_name, report.active_cards, report.archived_cards
     ));
+    if !report.window.is_open() {
+        out.push_str(&format!(
+            "Window: {} → {}\n\n",
+            report
+                .window
+                .since
+                .map_or("start".to_string(), |d| locale.date(d)),
+            report
+                .window
+                .until
+                .map_or("today".to_string(), |d| locale.date(d))
+        ));
+    }
 
     let wip_status = if report.wip_violation {
         format!("{} cards (OVER LIMIT)", report.wip_count)
@@ -491,14 +1122,38 @@ pub fn render_stats_text(report: &StatsReport) -> String {
         Some(limit) => out.push_str(&format!("WIP Limit:          {limit}\n")),
         None => out.push_str("WIP Limit:          none set\n"),
     }
+    if report.queued > 0 {
+        out.push_str(&format!("Queued:             {} cards\n", report.queued));
+    }
 
     out.push_str(&format!("Throughput (7d):    {} cards\n", report.done_7d));
     out.push_str(&format!("Throughput (30d):   {} cards\n", report.done_30d));
+    if !report.window.is_open() {
+        out.push_str(&format!("Completed:          {} cards\n", report.completed));
+    }
 
+    let adjustment = match report.outlier_policy {
+        OutlierPolicy::Exclude if !report.cycle_outliers.is_empty() => {
+            format!(" ({} outliers excluded)", report.cycle_outliers.len())
+        }
+        OutlierPolicy::Winsorize if !report.cycle_outliers.is_empty() => {
+            " (winsorized at p95)".to_string()
+        }
+        _ => String::new(),
+    };
     match report.avg_cycle_days {
-        Some(days) => out.push_str(&format!("Avg Cycle Time:     {days:.1} days\n")),
+        Some(days) => out.push_str(&format!(
+            "Avg Cycle Time:     {} days{adjustment}\n",
+            locale.number(days, 1)
+        )),
         None => out.push_str("Avg Cycle Time:     no data\n"),
     }
+    if let Some(p95) = report.cycle_p95_days {
+        out.push_str(&format!(
+            "Cycle Time p95:     {} days\n",
+            locale.number(p95, 1)
+        ));
+    }
 
     if let Some((ref title, days)) = report.oldest_wip {
         let display_title = if title.len() > 30 {
@@ -511,6 +1166,204 @@ pub fn render_stats_text(report: &StatsReport) -> String {
         ));
     }
 
+    if report.overdue > 0 {
+        out.push_str(&format!("Overdue:            {} cards\n", report.overdue));
+    }
+
+    if !report.column_times.is_empty() {
+        out.push_str("\nTime in column (avg):\n");
+        for t in &report.column_times {
+            out.push_str(&format!(
+                "  {:<16} {} days ({} cards)\n",
+                t.column,
+                locale.number(t.avg_days, 1),
+                t.cards
+            ));
+        }
+    }
+
+    if !report.cycle_outliers.is_empty() {
+        out.push_str("\nCycle-time outliers (> p95):\n");
+        for o in &report.cycle_outliers {
+            out.push_str(&format!(
+                "  {}d  {}\n",
+                locale.number(o.cycle_days, 1),
+                o.title
+            ));
+        }
+    }
+
+    if let Some(history) = &report.history {
+        out.push_str(&render_history_text(history, locale));
+    }
+
+    out
+}
+
+// ─── Comparison ──────────────────────────────────────────────
+
+/// One metric measured over the current window and the one before it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub metric: String,
+    pub current: f64,
+    pub previous: f64,
+    pub delta: f64,
+    /// Change relative to the previous value; absent when that was zero.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent: Option<f64>,
+}
+
+impl MetricDelta {
+    fn new(metric: &str, current: f64, previous: f64) -> Self {
+        let delta = current - previous;
+        MetricDelta {
+            metric: metric.into(),
+            current,
+            previous,
+            delta,
+            percent: (previous != 0.0).then(|| delta / previous * 100.0),
+        }
+    }
+}
+
+/// A report, or the report with its `compare_previous` comparison.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Compared<T> {
+    No(T),
+    Yes(Comparison<T>),
+}
+
+/// A report alongside the same report for the preceding equal-length window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comparison<T> {
+    pub current: T,
+    pub previous: T,
+    pub changes: Vec<MetricDelta>,
+}
+
+/// Velocity for the requested weeks and for the same number of weeks before.
+pub fn compare_velocity(
+    cards: &[Card],
+    num_weeks: u32,
+    window: &ReportWindow,
+    cal: &Calendar,
+) -> Comparison<VelocityReport> {
+    let current = calculate_velocity(cards, num_weeks, window, cal);
+    let first_week = current
+        .weeks
+        .first()
+        .map(|w| w.week_start)
+        .unwrap_or_else(|| cal.week_start(window.end(cal)));
+    let span = Days::new(current.weeks.len().max(1) as u64 * 7);
+    let previous_window = ReportWindow {
+        since: first_week.checked_sub_days(span),
+        until: first_week.pred_opt(),
+    };
+    let previous = calculate_velocity(cards, num_weeks, &previous_window, cal);
+
+    let total = |r: &VelocityReport| r.weeks.iter().map(|w| w.count).sum::<usize>() as f64;
+    let changes = vec![
+        MetricDelta::new("Completed", total(&current), total(&previous)),
+        MetricDelta::new("Average/week", current.average, previous.average),
+    ];
+
+    Comparison {
+        current,
+        previous,
+        changes,
+    }
+}
+
+/// [`normalize_velocity`] for both windows of a comparison, adding the
+/// change in cards per contributor-week.
+pub fn normalize_velocity_comparison(
+    cmp: &mut Comparison<VelocityReport>,
+    commits: &[crate::git::CommitInfo],
+    cal: &Calendar,
+) {
+    normalize_velocity(&mut cmp.current, commits, cal);
+    normalize_velocity(&mut cmp.previous, commits, cal);
+    cmp.changes.push(MetricDelta::new(
+        "Per contributor-week",
+        cmp.current.normalized.unwrap_or(0.0),
+        cmp.previous.normalized.unwrap_or(0.0),
+    ));
+}
+
+/// Stats for the window and the equal-length window before it. Without a
+/// `since` date the window is the 30 days up to its end.
+pub fn compare_stats(
+    board: &Board,
+    policy: OutlierPolicy,
+    window: &ReportWindow,
+    cal: &Calendar,
+) -> Comparison<StatsReport> {
+    let end = window.end(cal);
+    let since = window
+        .since
+        .unwrap_or_else(|| end.checked_sub_days(Days::new(29)).unwrap_or(end));
+    let span = Days::new((end - since).num_days() as u64 + 1);
+    let current_window = ReportWindow {
+        since: Some(since),
+        until: Some(end),
+    };
+    let previous_window = ReportWindow {
+        since: since.checked_sub_days(span),
+        until: since.pred_opt(),
+    };
+
+    let current = calculate_stats(board, policy, &current_window, cal);
+    let previous = calculate_stats(board, policy, &previous_window, cal);
+
+    let mut changes = vec![MetricDelta::new(
+        "Completed",
+        current.completed as f64,
+        previous.completed as f64,
+    )];
+    if let (Some(cur), Some(prev)) = (current.avg_cycle_days, previous.avg_cycle_days) {
+        changes.push(MetricDelta::new("Avg cycle time (days)", cur, prev));
+    }
+    if let (Some(cur), Some(prev)) = (current.cycle_p95_days, previous.cycle_p95_days) {
+        changes.push(MetricDelta::new("Cycle time p95 (days)", cur, prev));
+    }
+
+    Comparison {
+        current,
+        previous,
+        changes,
+    }
+}
+
+pub fn render_changes_text(
+    previous: &ReportWindow,
+    changes: &[MetricDelta],
+    locale: &Locale,
+) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "\nCompared to previous period ({} → {})\n",
+        previous
+            .since
+            .map_or("start".to_string(), |d| locale.date(d)),
+        previous
+            .until
+            .map_or("today".to_string(), |d| locale.date(d))
+    ));
+    for c in changes {
+        let percent = c
+            .percent
+            .map(|p| format!(" ({}%)", locale.signed(p, 0)))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "  {:<22} {:>6} → {:>6}  {}{percent}\n",
+            c.metric,
+            locale.number(c.previous, 1),
+            locale.number(c.current, 1),
+            locale.signed(c.delta, 1)
+        ));
+    }
     out
 }
 
@@ -519,9 +1372,40 @@ pub fn render_stats_text(report: &StatsReport) -> String {
 #[derive(Debug, Clone, Serialize)]
 pub struct ReleaseNotesReport {
     pub since: String,
+    /// The ref the notes run up to, when not HEAD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// First lines of commits left out because they were already released
+    /// on another branch.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub already_released: Vec<String>,
     pub features: Vec<String>,
     pub fixes: Vec<String>,
     pub other: Vec<String>,
+    /// Cards the released commits belong to, by trailer or recorded link.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<ReleaseCard>,
+    /// Who wrote the released commits; only filled in on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contributors: Option<Vec<Contributor>>,
+}
+
+/// Someone who authored commits in a release.
+#[derive(Debug, Clone, Serialize)]
+pub struct Contributor {
+    pub name: String,
+    pub email: String,
+    pub commits: usize,
+    /// No commit of theirs was released before.
+    pub first_time: bool,
+}
+
+/// A card shipped in a release.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseCard {
+    pub id: String,
+    pub title: String,
+    pub column: String,
 }
 
 pub fn categorize_commits(commits: &[crate::git::CommitInfo]) -> ReleaseNotesReport {
@@ -544,15 +1428,55 @@ pub fn categorize_commits(commits: &[crate::git::CommitInfo]) -> ReleaseNotesRep
 
     ReleaseNotesReport {
         since: String::new(),
+        to: None,
+        already_released: Vec::new(),
         features,
         fixes,
         other,
+        cards: Vec::new(),
+        contributors: None,
     }
 }
 
+/// Commit totals per author, most commits first. Authors are told apart by
+/// [`CommitInfo::author_key`](crate::git::CommitInfo::author_key) and shown
+/// under their most recent name; those missing from `earlier_authors` are
+/// first-time contributors.
+pub fn count_contributors(
+    commits: &[crate::git::CommitInfo],
+    earlier_authors: &HashSet<String>,
+) -> Vec<Contributor> {
+    let mut by_author: Vec<(String, Contributor)> = Vec::new();
+    for commit in commits {
+        let key = commit.author_key();
+        match by_author.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, c)) => c.commits += 1,
+            None => {
+                let first_time = !earlier_authors.contains(&key);
+                by_author.push((
+                    key,
+                    Contributor {
+                        name: commit.author.clone(),
+                        email: commit.email.clone(),
+                        commits: 1,
+                        first_time,
+                    },
+                ));
+            }
+        }
+    }
+    let mut contributors: Vec<Contributor> = by_author.into_iter().map(|(_, c)| c).collect();
+    // Stable, so ties keep the most recently active author first
+    contributors.sort_by_key(|c| std::cmp::Reverse(c.commits));
+    contributors
+}
+
 pub fn render_release_notes_text(report: &ReleaseNotesReport) -> String {
     let mut out = String::new();
-    out.push_str(&format!("Release Notes (since {})\n", report.since));
+    match &report.to {
+        Some(to) => out.push_str(&format!("Release Notes ({}..{to})\n", report.since)),
+        None => out.push_str(&format!("Release Notes (since {})\n", report.since)),
+    }
     out.push_str("════════════════════════════════════════\n\n");
 
     if !report.features.is_empty() {
@@ -582,8 +1506,45 @@ pub fn render_release_notes_text(report: &ReleaseNotesReport) -> String {
         out.push('\n');
     }
 
+    if !report.cards.is_empty() {
+        out.push_str("Cards\n");
+        out.push_str("─────\n");
+        for c in &report.cards {
+            out.push_str(&format!("  - {} [{}]\n", c.title, c.column));
+        }
+        out.push('\n');
+    }
+
+    if let Some(contributors) = &report.contributors {
+        let first_time = contributors.iter().filter(|c| c.first_time).count();
+        out.push_str(&format!(
+            "Contributors ({}, {first_time} first-time)\n",
+            contributors.len()
+        ));
+        out.push_str("────────────\n");
+        for c in contributors {
+            let plural = if c.commits == 1 { "" } else { "s" };
+            let first = if c.first_time {
+                ", first contribution"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "  - {} ({} commit{plural}{first})\n",
+                c.name, c.commits
+            ));
+        }
+        out.push('\n');
+    }
+
     let total = report.features.len() + report.fixes.len() + report.other.len();
     out.push_str(&format!("{total} commits total\n"));
+    if !report.already_released.is_empty() {
+        out.push_str(&format!(
+            "{} already released on other branches, left out\n",
+            report.already_released.len()
+        ));
+    }
     out
 }
 
@@ -592,27 +1553,28 @@ pub fn render_release_notes_text(report: &ReleaseNotesReport) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use kuk::model::{Board, Card, Column};
+    use chrono::Utc;
+    use kuk::model::{Board, Card, Column, SCHEMA_VERSION};
 
     fn make_board_with_cards() -> Board {
         let now = Utc::now();
         let mut board = Board {
+            schema_version: SCHEMA_VERSION,
             name: "test".into(),
             columns: vec![
+                Column::new("todo"),
                 Column {
-                    name: "todo".into(),
-                    wip_limit: None,
-                },
-                Column {
-                    name: "doing".into(),
                     wip_limit: Some(3),
+                    ..Column::new("doing")
                 },
-                Column {
-                    name: "done".into(),
-                    wip_limit: None,
-                },
+                Column::new("done"),
             ],
             cards: Vec::new(),
+            labels: Vec::new(),
+            strict_labels: false,
+            last_seq: 0,
+            swimlanes: None,
+            partial: false,
         };
 
         let mut c1 = Card::new("Task A", "todo");
@@ -669,18 +1631,23 @@ mod tests {
     fn test_week_start_monday() {
         // 2026-02-25 is a Wednesday
         let wed = NaiveDate::from_ymd_opt(2026, 2, 25).unwrap();
-        let mon = week_start_monday(wed);
+        let mon = Calendar::default().week_start(wed);
         assert_eq!(mon, NaiveDate::from_ymd_opt(2026, 2, 23).unwrap());
 
         // Monday stays Monday
         let already_mon = NaiveDate::from_ymd_opt(2026, 2, 23).unwrap();
-        assert_eq!(week_start_monday(already_mon), already_mon);
+        assert_eq!(Calendar::default().week_start(already_mon), already_mon);
     }
 
     #[test]
     fn test_velocity_with_done_cards() {
         let board = make_board_with_cards();
-        let report = calculate_velocity(&[board], 4);
+        let report = calculate_velocity(
+            &board.cards,
+            4,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
         assert_eq!(report.weeks.len(), 4);
         assert!(report.average >= 0.0);
     }
@@ -688,7 +1655,12 @@ mod tests {
     #[test]
     fn test_velocity_empty_board() {
         let board = Board::default_board();
-        let report = calculate_velocity(&[board], 4);
+        let report = calculate_velocity(
+            &board.cards,
+            4,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
         assert_eq!(report.weeks.len(), 4);
         assert_eq!(report.average, 0.0);
         assert_eq!(report.trend, "stable");
@@ -697,8 +1669,13 @@ mod tests {
     #[test]
     fn test_velocity_render_contains_headers() {
         let board = make_board_with_cards();
-        let report = calculate_velocity(&[board], 4);
-        let text = render_velocity_text(&report);
+        let report = calculate_velocity(
+            &board.cards,
+            4,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        let text = render_velocity_text(&report, &Locale::default());
         assert!(text.contains("Velocity"));
         assert!(text.contains("Average"));
         assert!(text.contains("Trend"));
@@ -707,7 +1684,12 @@ mod tests {
     #[test]
     fn test_stats_basic() {
         let board = make_board_with_cards();
-        let stats = calculate_stats(&board);
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
         assert_eq!(stats.board_name, "test");
         assert_eq!(stats.active_cards, 4);
         assert_eq!(stats.wip_count, 1);
@@ -718,27 +1700,449 @@ mod tests {
     fn test_stats_wip_violation() {
         let mut board = make_board_with_cards();
         for i in 0..4 {
-            let mut c = Card::new(&format!("Extra {i}"), "doing");
+            let mut c = Card::new(format!("Extra {i}"), "doing");
             c.order = (i + 1) as u32;
             board.cards.push(c);
         }
-        let stats = calculate_stats(&board);
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
         assert_eq!(stats.wip_count, 5);
         assert!(stats.wip_violation);
     }
 
+    #[test]
+    fn test_stats_counts_queues_apart_from_wip() {
+        let mut board = make_board_with_cards();
+        board.columns.insert(
+            2,
+            Column {
+                wip_limit: Some(1),
+                queue: true,
+                ..Column::new("ready for review")
+            },
+        );
+        for title in ["Waiting 1", "Waiting 2"] {
+            board.add_card(Card::new(title, "ready for review"));
+        }
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        assert_eq!((stats.wip_count, stats.queued), (1, 2));
+        assert_eq!(stats.wip_limit, Some(3));
+        assert!(!stats.wip_violation);
+        assert!(
+            render_stats_text(&stats, &Locale::default()).contains("Queued:             2 cards")
+        );
+    }
+
+    #[test]
+    fn test_stats_counts_overdue_open_cards() {
+        let mut board = make_board_with_cards();
+        let yesterday = Utc::now() - chrono::Duration::days(1);
+        for (title, column) in [("Late", "todo"), ("Shipped late", "done")] {
+            let mut c = Card::new(title, column);
+            c.due = Some(yesterday);
+            board.cards.push(c);
+        }
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        assert_eq!(stats.overdue, 1);
+        assert!(
+            render_stats_text(&stats, &Locale::default()).contains("Overdue:            1 cards")
+        );
+    }
+
+    #[test]
+    fn test_stats_history_trends() {
+        let at = |d: &str| {
+            NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let mut board = make_board_with_cards();
+        board.cards.clear();
+        for (title, column, created, finished) in [
+            ("Shipped early", "done", "2026-02-20", "2026-02-25"),
+            ("In flight", "doing", "2026-03-03", "2026-03-03"),
+            ("Not started", "todo", "2026-02-01", "2026-02-01"),
+            ("Shipped late", "done", "2026-03-01", "2026-03-10"),
+        ] {
+            let mut c = Card::new(title, column);
+            c.created_at = at(created);
+            c.updated_at = at(finished);
+            board.cards.push(c);
+        }
+        let window = ReportWindow::parse(None, Some("2026-03-11")).unwrap();
+        let history = calculate_stats_history(&board, 3, &window, &Calendar::default());
+
+        let wip: Vec<usize> = history.weeks.iter().map(|w| w.wip).collect();
+        let done: Vec<usize> = history.weeks.iter().map(|w| w.throughput).collect();
+        assert_eq!(wip, [1, 2, 1]);
+        assert_eq!(done, [1, 0, 1]);
+        assert_eq!(history.weeks[1].avg_cycle_days, None);
+
+        let mut stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &window,
+            &Calendar::default(),
+        );
+        stats.history = Some(history);
+        let text = render_stats_text(&stats, &Locale::default());
+        assert!(text.contains("WIP          ▅█▅  now 1"));
+        assert!(text.contains("Cycle time   ▅ █  latest 9.0 days"));
+    }
+
     #[test]
     fn test_stats_cycle_time() {
         let board = make_board_with_cards();
-        let stats = calculate_stats(&board);
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
         assert!(stats.avg_cycle_days.is_some());
     }
 
+    #[test]
+    fn test_stats_cycle_time_ends_when_card_reached_done() {
+        let mut board = make_board_with_cards();
+        board.cards.retain(|c| !is_done_column(&c.column));
+        let mut card = Card::new("Finished", "doing");
+        card.created_at = Utc::now() - chrono::TimeDelta::try_days(4).expect("valid delta");
+        let id = card.id.clone();
+        board.cards.push(card);
+        board.move_card(&id, "done");
+
+        // Touching the card later must not stretch its cycle time
+        let card = board.find_card_mut(&id).unwrap();
+        let moved_at = card.entered_column_at().unwrap();
+        card.updated_at = moved_at + chrono::TimeDelta::try_days(3).expect("valid delta");
+        assert_eq!(completed_at(card), moved_at);
+
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        assert_eq!(stats.avg_cycle_days, Some(4.0));
+    }
+
+    #[test]
+    fn test_stats_average_time_in_each_column() {
+        let mut board = make_board_with_cards();
+        board.cards.retain(|c| !is_done_column(&c.column));
+        let start = Utc::now() - chrono::TimeDelta::try_days(10).expect("valid delta");
+        for (title, todo_days, doing_days) in [("Quick", 1, 2), ("Slow", 3, 6)] {
+            let mut card = Card::new(title, "todo");
+            card.created_at = start;
+            let id = card.id.clone();
+            board.cards.push(card);
+            board.move_card(&id, "doing");
+            board.move_card(&id, "done");
+            // Backdate the moves to lay out the days spent in each column
+            let card = board.find_card_mut(&id).unwrap();
+            let mut moves = kuk::model::Transition::history(card);
+            moves[0].at = start + chrono::TimeDelta::try_days(todo_days).expect("valid delta");
+            moves[1].at =
+                moves[0].at + chrono::TimeDelta::try_days(doing_days).expect("valid delta");
+            card.metadata.insert(
+                kuk::model::TRANSITIONS_KEY.into(),
+                serde_json::to_value(moves).unwrap(),
+            );
+        }
+
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        let times: Vec<(&str, f64, usize)> = stats
+            .column_times
+            .iter()
+            .map(|t| (t.column.as_str(), t.avg_days, t.cards))
+            .collect();
+        assert_eq!(times, [("todo", 2.0, 2), ("doing", 4.0, 2)]);
+
+        let text = render_stats_text(&stats, &Locale::default());
+        assert!(text.contains("Time in column (avg):"));
+        assert!(text.contains("  doing            4.0 days (2 cards)"));
+    }
+
+    /// Done cards with the given cycle times in days.
+    fn board_with_cycle_times(days: &[i64]) -> Board {
+        let mut board = make_board_with_cards();
+        board.cards.retain(|c| !is_done_column(&c.column));
+        for (i, d) in days.iter().enumerate() {
+            let mut c = Card::new(format!("Done {d}"), "done");
+            c.order = i as u32;
+            c.created_at = c.updated_at - chrono::TimeDelta::try_days(*d).expect("valid delta");
+            board.cards.push(c);
+        }
+        board
+    }
+
+    #[test]
+    fn test_stats_flags_cycle_outliers() {
+        let board = board_with_cycle_times(&[2, 3, 4, 5, 90]);
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        assert_eq!(stats.cycle_outliers.len(), 1);
+        assert_eq!(stats.cycle_outliers[0].title, "Done 90");
+        assert!((stats.avg_cycle_days.unwrap() - 20.8).abs() < 0.01);
+
+        let text = render_stats_text(&stats, &Locale::default());
+        assert!(text.contains("Cycle-time outliers"));
+        assert!(text.contains("Done 90"));
+    }
+
+    #[test]
+    fn test_stats_outlier_policies() {
+        let board = board_with_cycle_times(&[2, 3, 4, 5, 90]);
+        let p95 = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        )
+        .cycle_p95_days
+        .unwrap();
+
+        let excluded = calculate_stats(
+            &board,
+            OutlierPolicy::Exclude,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        assert!((excluded.avg_cycle_days.unwrap() - 3.5).abs() < 0.01);
+        assert!(render_stats_text(&excluded, &Locale::default()).contains("1 outliers excluded"));
+
+        let winsorized = calculate_stats(
+            &board,
+            OutlierPolicy::Winsorize,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        let expected = (2.0 + 3.0 + 4.0 + 5.0 + p95) / 5.0;
+        assert!((winsorized.avg_cycle_days.unwrap() - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_stats_small_sample_has_no_outliers() {
+        let board = board_with_cycle_times(&[1, 60]);
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Exclude,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        assert!(stats.cycle_p95_days.is_none());
+        assert!(stats.cycle_outliers.is_empty());
+        assert!((stats.avg_cycle_days.unwrap() - 30.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_report_window_parse() {
+        let w = ReportWindow::parse(Some("2026-01-01"), Some("2026-03-31")).unwrap();
+        assert!(w.contains(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()));
+        assert!(!w.contains(NaiveDate::from_ymd_opt(2026, 4, 1).unwrap()));
+        assert!(ReportWindow::parse(None, None).unwrap().is_open());
+        assert!(ReportWindow::parse(Some("2026-04-01"), Some("2026-03-01")).is_err());
+        assert!(ReportWindow::parse(Some("last quarter"), None).is_err());
+    }
+
+    #[test]
+    fn test_velocity_window_spans_range() {
+        let mut board = board_with_cycle_times(&[1]);
+        let done = board.cards.iter_mut().find(|c| c.column == "done").unwrap();
+        done.updated_at = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 1, 14, 12, 0, 0).unwrap();
+
+        let window = ReportWindow::parse(Some("2026-01-05"), Some("2026-02-01")).unwrap();
+        let report = calculate_velocity(&board.cards, 4, &window, &Calendar::default());
+        // Mondays 2026-01-05 .. 2026-01-26
+        assert_eq!(report.weeks.len(), 4);
+        assert_eq!(
+            report.weeks[0].week_start,
+            NaiveDate::from_ymd_opt(2026, 1, 5).unwrap()
+        );
+        assert_eq!(report.weeks[1].count, 1);
+        assert!(
+            render_velocity_text(&report, &Locale::default()).contains("2026-01-05 → 2026-02-01")
+        );
+    }
+
+    #[test]
+    fn test_velocity_normalized_by_contributors() {
+        let ts = |d| chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 1, d, 12, 0, 0).unwrap();
+        // Two cards a week while two people commit, then one a week alone
+        let cards: Vec<Card> = [6, 7, 13, 14, 20, 27]
+            .into_iter()
+            .map(|d| {
+                let mut c = Card::new(format!("Done {d}"), "done");
+                c.updated_at = ts(d);
+                c
+            })
+            .collect();
+        let commit = |email: &str, d| crate::git::CommitInfo {
+            sha: String::new(),
+            message: "work".into(),
+            author: email.into(),
+            email: email.into(),
+            time: ts(d).timestamp(),
+        };
+        let commits = [
+            commit("ana@example.com", 6),
+            commit("ANA@example.com", 7),
+            commit("bo@example.com", 8),
+            commit("ana@example.com", 13),
+            commit("bo@example.com", 14),
+            commit("ana@example.com", 21),
+            commit("ana@example.com", 28),
+            commit("bo@example.com", 31),
+        ];
+
+        let window = ReportWindow::parse(Some("2026-01-05"), Some("2026-02-01")).unwrap();
+        let cal = Calendar::default();
+        let mut report = calculate_velocity(&cards, 4, &window, &cal);
+        assert_eq!(report.trend, "declining");
+
+        normalize_velocity(&mut report, &commits, &cal);
+        let contributors: Vec<Option<usize>> =
+            report.weeks.iter().map(|w| w.contributors).collect();
+        assert_eq!(contributors, [Some(2), Some(2), Some(1), Some(2)]);
+        assert_eq!(report.normalized, Some(6.0 / 7.0));
+        // The first half's rate predicts 3 cards for 3 contributor-weeks
+        assert_eq!(report.trend, "stable");
+
+        let text = render_velocity_text(&report, &Locale::default());
+        assert!(text.contains("1 contributor\n"), "{text}");
+        assert!(text.contains("Per contributor: 0.86 cards/contributor-week"));
+        assert!(render_velocity_oneline(&report, &Locale::default()).contains("(0.9/contributor)"));
+    }
+
+    #[test]
+    fn test_velocity_render_follows_locale() {
+        let mut board = board_with_cycle_times(&[1]);
+        let done = board.cards.iter_mut().find(|c| c.column == "done").unwrap();
+        done.updated_at = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 1, 14, 12, 0, 0).unwrap();
+        let window = ReportWindow::parse(Some("2026-01-05"), Some("2026-02-01")).unwrap();
+        let report = calculate_velocity(&board.cards, 4, &window, &Calendar::default());
+
+        let text = render_velocity_text(&report, &"de-DE".parse().unwrap());
+        assert!(text.contains("Velocity (05.01.2026 → 01.02.2026, 4 weeks)"));
+        assert!(text.contains("  12.01.2026    1  "));
+        assert!(text.contains("Average: 0,2 cards/week"));
+        let us = render_velocity_oneline(&report, &"en-US".parse().unwrap());
+        assert!(us.starts_with("velocity: 0.2/wk "), "{us}");
+    }
+
+    #[test]
+    fn test_stats_window_filters_completed_cards() {
+        let mut board = board_with_cycle_times(&[2, 10]);
+        let ts = |d| chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 3, d, 12, 0, 0).unwrap();
+        for (card, day) in board
+            .cards
+            .iter_mut()
+            .filter(|c| c.column == "done")
+            .zip([5, 25])
+        {
+            let cycle = card.updated_at - card.created_at;
+            card.updated_at = ts(day);
+            card.created_at = card.updated_at - cycle;
+        }
+
+        let window = ReportWindow::parse(None, Some("2026-03-10")).unwrap();
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &window,
+            &Calendar::default(),
+        );
+        assert_eq!(stats.done_7d, 1);
+        assert_eq!(stats.done_30d, 1);
+        assert!((stats.avg_cycle_days.unwrap() - 2.0).abs() < 0.01);
+        assert!(
+            render_stats_text(&stats, &Locale::default()).contains("Window: start → 2026-03-10")
+        );
+    }
+
+    #[test]
+    fn test_compare_velocity_uses_preceding_weeks() {
+        let mut board = board_with_cycle_times(&[1, 1, 1]);
+        let ts = |m, d| chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, m, d, 12, 0, 0).unwrap();
+        // One card in the previous four weeks, two in the current four
+        for (card, at) in board.cards.iter_mut().filter(|c| c.column == "done").zip([
+            ts(1, 14),
+            ts(2, 10),
+            ts(2, 18),
+        ]) {
+            card.updated_at = at;
+        }
+
+        let window = ReportWindow::parse(None, Some("2026-03-01")).unwrap();
+        let cmp = compare_velocity(&board.cards, 4, &window, &Calendar::default());
+        assert_eq!(
+            cmp.previous.weeks[0].week_start,
+            NaiveDate::from_ymd_opt(2026, 1, 5).unwrap()
+        );
+        assert_eq!(cmp.previous.weeks.len(), 4);
+        let completed = &cmp.changes[0];
+        assert_eq!((completed.previous, completed.current), (1.0, 2.0));
+        assert_eq!(completed.percent, Some(100.0));
+
+        let text = render_changes_text(&cmp.previous.window, &cmp.changes, &Locale::default());
+        assert!(text.contains("2026-01-05 → 2026-02-01"));
+        assert!(text.contains("(+100%)"));
+    }
+
+    #[test]
+    fn test_compare_stats_default_thirty_days() {
+        let board = board_with_cycle_times(&[2, 4]);
+        let cmp = compare_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        assert_eq!(cmp.current.completed, 2);
+        assert_eq!(cmp.previous.completed, 0);
+        let prev = cmp.previous.window;
+        assert_eq!((prev.until.unwrap() - prev.since.unwrap()).num_days(), 29);
+        // No prior cycle time to compare against
+        assert_eq!(cmp.changes.len(), 1);
+        assert!(cmp.changes[0].percent.is_none());
+    }
+
     #[test]
     fn test_stats_render() {
         let board = make_board_with_cards();
-        let stats = calculate_stats(&board);
-        let text = render_stats_text(&stats);
+        let stats = calculate_stats(
+            &board,
+            OutlierPolicy::Include,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        let text = render_stats_text(&stats, &Locale::default());
         assert!(text.contains("Project Statistics"));
         assert!(text.contains("Work in Progress"));
         assert!(text.contains("Throughput"));
@@ -760,8 +2164,9 @@ mod tests {
             goal: None,
             boards: vec!["test".into()],
             status: crate::model::SprintStatus::Active,
+            milestone: None,
         };
-        let report = calculate_burndown(&[board], &sprint);
+        let report = calculate_burndown(&board.cards, &sprint, &Calendar::default());
         assert_eq!(report.sprint_name, "test-sprint");
         assert_eq!(report.total_cards, 4);
         assert!(!report.points.is_empty());
@@ -783,17 +2188,76 @@ mod tests {
             goal: None,
             boards: vec!["test".into()],
             status: crate::model::SprintStatus::Active,
+            milestone: None,
         };
-        let report = calculate_burndown(&[board], &sprint);
-        let text = render_burndown_text(&report);
+        let report = calculate_burndown(&board.cards, &sprint, &Calendar::default());
+        let text = render_burndown_text(&report, &Locale::default());
         assert!(text.contains("Burndown: test-sprint"));
         assert!(text.contains("Total scope"));
+        assert!(!text.contains("Milestone"));
+    }
+
+    #[test]
+    fn test_burndown_includes_milestone_progress() {
+        let board = make_board_with_cards();
+        let today = Utc::now().date_naive();
+        let sprint = Sprint {
+            name: "milestone-sprint".into(),
+            start: today.checked_sub_days(Days::new(7)).unwrap(),
+            end: today.checked_add_days(Days::new(7)).unwrap(),
+            goal: None,
+            boards: vec!["test".into()],
+            status: crate::model::SprintStatus::Active,
+            milestone: Some(Milestone {
+                number: 2,
+                url: "https://github.com/u/r/milestone/2".into(),
+                open_issues: 1,
+                closed_issues: 3,
+                due_on: None,
+                last_synced: None,
+            }),
+        };
+        let report = calculate_burndown(&board.cards, &sprint, &Calendar::default());
+        assert_eq!(report.milestone.as_ref().unwrap().number, 2);
+        let text = render_burndown_text(&report, &Locale::default());
+        assert!(text.contains("Milestone:   #2 — 3/4 issues closed (75%)"));
+    }
+
+    #[test]
+    fn test_burndown_and_velocity_oneline() {
+        let board = make_board_with_cards();
+        // Two of four cards were finished within the last week
+        let today = Utc::now().date_naive();
+        let sprint = Sprint {
+            name: "s1".into(),
+            start: today.checked_sub_days(Days::new(5)).unwrap(),
+            end: today.checked_add_days(Days::new(8)).unwrap(),
+            goal: None,
+            boards: vec!["test".into()],
+            status: crate::model::SprintStatus::Active,
+            milestone: None,
+        };
+        let report = calculate_burndown(&board.cards, &sprint, &Calendar::default());
+        assert_eq!(report.current.date, today);
+        let line = render_burndown_oneline(&report);
+        assert!(line.starts_with("s1: 2/4 done, day 6/14, "), "{line}");
+        assert_eq!(line.lines().count(), 1);
+
+        let report = calculate_velocity(
+            &board.cards,
+            4,
+            &ReportWindow::default(),
+            &Calendar::default(),
+        );
+        let line = render_velocity_oneline(&report, &Locale::default());
+        assert!(line.starts_with("velocity: 0.5/wk "), "{line}");
+        assert!(line.trim_end().ends_with(&report.trend));
     }
 
     #[test]
     fn test_roadmap_basic() {
         let board = make_board_with_cards();
-        let report = calculate_roadmap(&[board], &[], 8, 2.0);
+        let report = calculate_roadmap(&board.cards, &[], 8, 2.0, &Calendar::default());
         assert_eq!(report.weeks.len(), 8);
         assert_eq!(report.velocity, 2.0);
         assert_eq!(report.weeks[0].todo, 1);
@@ -801,17 +2265,83 @@ mod tests {
         assert_eq!(report.weeks[0].done, 2);
     }
 
+    #[test]
+    fn test_roadmap_epic_progress() {
+        let mut board = make_board_with_cards();
+        let mut epic = Card::new("Auth overhaul", "doing");
+        epic.epic = true;
+        let epic_id = board.add_card(epic).id.clone();
+        for card in &mut board.cards[..3] {
+            card.parent = Some(epic_id.clone());
+        }
+        board.cards[2].archived = true;
+
+        let report = calculate_roadmap(&board.cards, &[], 4, 2.0, &Calendar::default());
+        assert_eq!(report.weeks[0].wip, 1);
+        assert_eq!(report.epics.len(), 1);
+        assert_eq!((report.epics[0].done, report.epics[0].total), (1, 3));
+        let text = render_roadmap_text(&report, &Locale::default());
+        assert!(text.contains("KUK-1 Auth overhaul"));
+        assert!(text.contains("1/3   done (33%)"));
+    }
+
     #[test]
     fn test_roadmap_render() {
         let board = make_board_with_cards();
-        let report = calculate_roadmap(&[board], &[], 8, 2.0);
-        let text = render_roadmap_text(&report);
+        let report = calculate_roadmap(&board.cards, &[], 8, 2.0, &Calendar::default());
+        let text = render_roadmap_text(&report, &Locale::default());
         assert!(text.contains("Roadmap"));
         assert!(text.contains("Todo"));
         assert!(text.contains("Doing"));
         assert!(text.contains("Done"));
     }
 
+    #[test]
+    fn test_burndown_ideal_counts_working_days() {
+        let board = make_board_with_cards();
+        // Mon 2026-02-02 → Mon 2026-02-16: ten working days
+        let sprint = Sprint {
+            name: "two-weeks".into(),
+            start: NaiveDate::from_ymd_opt(2026, 2, 2).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 2, 16).unwrap(),
+            goal: None,
+            boards: vec!["test".into()],
+            status: crate::model::SprintStatus::Closed,
+            milestone: None,
+        };
+        let mut cal = Calendar::default();
+        let report = calculate_burndown(&board.cards, &sprint, &cal);
+        // One week in, half the working days have passed
+        assert_eq!(report.points[1].ideal, 2.0);
+
+        cal.team.holidays.push(crate::model::Holiday {
+            date: NaiveDate::from_ymd_opt(2026, 2, 10).unwrap(),
+            name: None,
+        });
+        let report = calculate_burndown(&board.cards, &sprint, &cal);
+        assert!((report.points[1].ideal - 4.0 * (1.0 - 5.0 / 9.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_roadmap_capacity_and_eta() {
+        let board = make_board_with_cards();
+        let mut cal = Calendar::default();
+        let full = calculate_roadmap(&board.cards, &[], 4, 2.0, &cal);
+        assert!(full.eta.is_some());
+
+        // Everyone off for the next three weeks pushes the ETA out
+        let today = cal.today();
+        cal.team.vacations.push(crate::model::Vacation {
+            person: "solo".into(),
+            start: today,
+            end: today.checked_add_days(Days::new(20)).unwrap(),
+        });
+        let away = calculate_roadmap(&board.cards, &[], 4, 2.0, &cal);
+        assert_eq!(away.weeks[1].capacity, 0.0);
+        assert!(away.eta.unwrap() > full.eta.unwrap());
+        assert!(render_roadmap_text(&away, &Locale::default()).contains("0% capacity"));
+    }
+
     #[test]
     fn test_roadmap_with_sprint_milestones() {
         let board = make_board_with_cards();
@@ -825,8 +2355,9 @@ mod tests {
             goal: None,
             boards: Vec::new(),
             status: crate::model::SprintStatus::Active,
+            milestone: None,
         };
-        let report = calculate_roadmap(&[board], &[sprint], 4, 1.0);
+        let report = calculate_roadmap(&board.cards, &[sprint], 4, 1.0, &Calendar::default());
         let has_milestone = report.weeks.iter().any(|w| !w.milestones.is_empty());
         assert!(has_milestone);
     }
@@ -838,18 +2369,21 @@ mod tests {
                 sha: "abc".into(),
                 message: "feat: add login".into(),
                 author: "dev".into(),
+                email: "dev@example.com".into(),
                 time: 0,
             },
             crate::git::CommitInfo {
                 sha: "def".into(),
                 message: "fix: null pointer".into(),
                 author: "dev".into(),
+                email: "dev@example.com".into(),
                 time: 0,
             },
             crate::git::CommitInfo {
                 sha: "ghi".into(),
                 message: "chore: update deps".into(),
                 author: "dev".into(),
+                email: "dev@example.com".into(),
                 time: 0,
             },
         ];
@@ -859,16 +2393,59 @@ mod tests {
         assert_eq!(report.other.len(), 1);
     }
 
+    #[test]
+    fn test_count_contributors() {
+        let commit = |author: &str, email: &str| crate::git::CommitInfo {
+            sha: String::new(),
+            message: "chore: tidy".into(),
+            author: author.into(),
+            email: email.into(),
+            time: 0,
+        };
+        let commits = [
+            commit("Bo", "bo@example.com"),
+            commit("Ana Lima", "ana@example.com"),
+            commit("Ana", "ANA@example.com"),
+            commit("dev", ""),
+        ];
+        let earlier = HashSet::from(["ana@example.com".to_string()]);
+        let contributors = count_contributors(&commits, &earlier);
+        let summary: Vec<(&str, usize, bool)> = contributors
+            .iter()
+            .map(|c| (c.name.as_str(), c.commits, c.first_time))
+            .collect();
+        assert_eq!(
+            summary,
+            [("Ana Lima", 2, false), ("Bo", 1, true), ("dev", 1, true)]
+        );
+
+        let mut report = categorize_commits(&commits);
+        report.contributors = Some(contributors);
+        let text = render_release_notes_text(&report);
+        assert!(text.contains("Contributors (3, 2 first-time)"));
+        assert!(text.contains("  - Ana Lima (2 commits)"));
+        assert!(text.contains("  - Bo (1 commit, first contribution)"));
+    }
+
     #[test]
     fn test_release_notes_render() {
         let report = ReleaseNotesReport {
             since: "v0.1.0".into(),
+            to: None,
+            already_released: Vec::new(),
             features: vec!["feat: add login".into()],
             fixes: vec!["fix: null pointer".into()],
             other: vec!["chore: update deps".into()],
+            cards: vec![ReleaseCard {
+                id: "01JMXK3V".into(),
+                title: "Login page".into(),
+                column: "done".into(),
+            }],
+            contributors: None,
         };
         let text = render_release_notes_text(&report);
         assert!(text.contains("Release Notes"));
+        assert!(text.contains("  - Login page [done]"));
         assert!(text.contains("Features"));
         assert!(text.contains("Fixes"));
         assert!(text.contains("3 commits total"));
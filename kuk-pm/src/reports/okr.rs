@@ -0,0 +1,149 @@
+//! Objective progress, rolled up from the cards linked to each key result.
+
+use serde::{Deserialize, Serialize};
+
+use kuk::model::Card;
+
+use super::is_done_column;
+use crate::model::Objective;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyResultProgress {
+    /// 1-based, as used in `kuk-pm okr link <objective>/<n>`.
+    pub number: usize,
+    pub title: String,
+    pub done: usize,
+    pub total: usize,
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectiveProgress {
+    pub name: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quarter: Option<String>,
+    /// Mean of the key results' percentages.
+    pub percent: f64,
+    pub key_results: Vec<KeyResultProgress>,
+}
+
+/// Each key result's share of linked cards that are done. A key result
+/// with no cards yet stands at 0%.
+pub fn calculate_okr_progress(objectives: &[Objective], cards: &[Card]) -> Vec<ObjectiveProgress> {
+    objectives
+        .iter()
+        .map(|objective| {
+            let key_results: Vec<KeyResultProgress> = objective
+                .key_results
+                .iter()
+                .enumerate()
+                .map(|(i, kr)| {
+                    let linked: Vec<&Card> = cards.iter().filter(|c| kr.covers(c)).collect();
+                    let done = linked.iter().filter(|c| is_done_column(&c.column)).count();
+                    KeyResultProgress {
+                        number: i + 1,
+                        title: kr.title.clone(),
+                        done,
+                        total: linked.len(),
+                        percent: percent(done, linked.len()),
+                    }
+                })
+                .collect();
+            let percent = if key_results.is_empty() {
+                0.0
+            } else {
+                key_results.iter().map(|kr| kr.percent).sum::<f64>() / key_results.len() as f64
+            };
+            ObjectiveProgress {
+                name: objective.name.clone(),
+                title: objective.title.clone(),
+                quarter: objective.quarter.clone(),
+                percent,
+                key_results,
+            }
+        })
+        .collect()
+}
+
+fn percent(done: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        done as f64 / total as f64 * 100.0
+    }
+}
+
+pub fn render_okr_text(progress: &[ObjectiveProgress]) -> String {
+    if progress.is_empty() {
+        return "No objectives defined. Create one with `kuk-pm okr create`.\n".into();
+    }
+
+    let mut out = String::from("Objectives\n──────────\n");
+    for objective in progress {
+        let quarter = objective
+            .quarter
+            .as_ref()
+            .map(|q| format!(" ({q})"))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "\n{} [{}]{quarter}  {:.0}%\n",
+            objective.title, objective.name, objective.percent
+        ));
+        if objective.key_results.is_empty() {
+            out.push_str("  No key results.\n");
+        }
+        for kr in &objective.key_results {
+            let filled = (kr.percent / 10.0).round() as usize;
+            out.push_str(&format!(
+                "  {}. {:<32} {}{}  {}/{} cards  {:.0}%\n",
+                kr.number,
+                kr.title,
+                "█".repeat(filled),
+                "░".repeat(10 - filled),
+                kr.done,
+                kr.total,
+                kr.percent
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::KeyResult;
+
+    #[test]
+    fn key_results_roll_up_into_objective() {
+        let mut cards = Vec::new();
+        for (title, column) in [("A", "done"), ("B", "doing"), ("C", "done"), ("D", "todo")] {
+            let mut card = Card::new(title, column);
+            card.labels = vec!["onboarding".into()];
+            cards.push(card);
+        }
+        let mut wizard = KeyResult::new("Ship onboarding");
+        wizard.epics.push("onboarding".into());
+        let mut single = KeyResult::new("Fix signup bug");
+        single.cards.push(cards[1].id.clone());
+        let objectives = vec![Objective {
+            name: "adoption".into(),
+            title: "Grow adoption".into(),
+            quarter: Some("2026-Q2".into()),
+            key_results: vec![wizard, single, KeyResult::new("Not started")],
+        }];
+
+        let progress = calculate_okr_progress(&objectives, &cards);
+        let krs = &progress[0].key_results;
+        assert_eq!((krs[0].done, krs[0].total), (2, 4));
+        assert_eq!(krs[0].percent, 50.0);
+        assert_eq!(krs[1].percent, 0.0);
+        assert!((progress[0].percent - 50.0 / 3.0).abs() < 1e-9);
+
+        let text = render_okr_text(&progress);
+        assert!(text.contains("Grow adoption [adoption] (2026-Q2)  17%"));
+        assert!(text.contains("1. Ship onboarding"));
+        assert!(text.contains("█████░░░░░  2/4 cards  50%"));
+    }
+}
@@ -0,0 +1,336 @@
+//! A one-call summary of a project: every board's headline numbers, the
+//! active sprint, and the risks most worth a look.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use kuk::model::{Board, idle_days};
+
+use super::{
+    BurndownReport, ReportWindow, StatsReport, calculate_stats, is_done_column, is_in_progress,
+};
+use crate::model::{Calendar, OutlierPolicy};
+
+/// Days a WIP card may sit untouched before it counts as stale, as with
+/// `kuk stale`.
+pub const STALE_DAYS: i64 = 14;
+
+/// How far back a queue column's size is compared to find it growing.
+pub const QUEUE_TREND_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardOverview {
+    pub board: String,
+    pub active_cards: usize,
+    pub wip_count: usize,
+    pub done_7d: usize,
+    pub overdue: usize,
+    pub avg_cycle_days: Option<f64>,
+}
+
+impl From<StatsReport> for BoardOverview {
+    fn from(stats: StatsReport) -> Self {
+        BoardOverview {
+            board: stats.board_name,
+            active_cards: stats.active_cards,
+            wip_count: stats.wip_count,
+            done_7d: stats.done_7d,
+            overdue: stats.overdue,
+            avg_cycle_days: stats.avg_cycle_days,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintOverview {
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Working days left, today and the last day included.
+    pub days_left: u32,
+    pub total_cards: usize,
+    pub remaining: usize,
+    /// Where the ideal burndown line stands today.
+    pub ideal_remaining: f64,
+    /// At least one card more left than the ideal line.
+    pub behind: bool,
+}
+
+/// What makes a [`Risk`], most pressing first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskKind {
+    SprintBehind,
+    WipLimit,
+    QueueGrowth,
+    Overdue,
+    Stale,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Risk {
+    pub kind: RiskKind,
+    /// The board, or the sprint for `sprint_behind`.
+    pub board: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_id: Option<String>,
+    /// The card's title, or the column for `wip_limit` and `queue_growth`.
+    pub title: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Overview {
+    pub boards: Vec<BoardOverview>,
+    pub active_cards: usize,
+    pub wip_count: usize,
+    pub done_7d: usize,
+    pub overdue: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sprint: Option<SprintOverview>,
+    /// The top risks across every board, most pressing first.
+    pub risks: Vec<Risk>,
+}
+
+/// Summarize `boards` and the active sprint's burndown, keeping at most
+/// `max_risks` risks. Risks rank by kind, then by how far gone they are:
+/// days overdue, days idle, cards over the limit, or cards a queue gained
+/// over [`QUEUE_TREND_DAYS`].
+pub fn calculate_overview(
+    boards: &[Board],
+    sprint: Option<&BurndownReport>,
+    policy: OutlierPolicy,
+    cal: &Calendar,
+    now: DateTime<Utc>,
+    max_risks: usize,
+) -> Overview {
+    let today = cal.date_of(now);
+    let mut risks: Vec<(RiskKind, i64, Risk)> = Vec::new();
+
+    for board in boards {
+        for column in &board.columns {
+            let count = board.column_cards(&column.name).len();
+            if let Some(limit) = column.wip_limit.filter(|l| count > *l as usize) {
+                let over = (count - limit as usize) as i64;
+                risks.push((
+                    RiskKind::WipLimit,
+                    over,
+                    Risk {
+                        kind: RiskKind::WipLimit,
+                        board: board.name.clone(),
+                        card_id: None,
+                        title: column.name.clone(),
+                        detail: format!("{count} cards, limit {limit}"),
+                    },
+                ));
+            }
+        }
+
+        let then = now - chrono::Duration::days(QUEUE_TREND_DAYS);
+        for column in board.columns.iter().filter(|c| c.queue) {
+            let count = board.column_cards(&column.name).len();
+            let before = board
+                .cards
+                .iter()
+                .filter(|c| c.column_at(then).is_some_and(|at| at == column.name))
+                .count();
+            if count > before {
+                risks.push((
+                    RiskKind::QueueGrowth,
+                    (count - before) as i64,
+                    Risk {
+                        kind: RiskKind::QueueGrowth,
+                        board: board.name.clone(),
+                        card_id: None,
+                        title: column.name.clone(),
+                        detail: format!(
+                            "{count} cards waiting, up from {before} {QUEUE_TREND_DAYS} days ago"
+                        ),
+                    },
+                ));
+            }
+        }
+
+        let open = board
+            .cards
+            .iter()
+            .filter(|c| !c.archived && !is_done_column(&c.column));
+        for card in open {
+            let card_risk = |kind, detail| Risk {
+                kind,
+                board: board.name.clone(),
+                card_id: Some(card.id.clone()),
+                title: card.title.clone(),
+                detail,
+            };
+            if let Some(due) = card.due.map(|d| cal.date_of(d)).filter(|d| *d < today) {
+                let days = (today - due).num_days();
+                let detail = format!("{days} days overdue (due {due})");
+                risks.push((
+                    RiskKind::Overdue,
+                    days,
+                    card_risk(RiskKind::Overdue, detail),
+                ));
+            } else if is_in_progress(board, &card.column) && !card.is_snoozed(now) {
+                let idle = idle_days(card, now);
+                if idle >= STALE_DAYS {
+                    let detail = format!("idle {idle} days in {}", card.column);
+                    risks.push((RiskKind::Stale, idle, card_risk(RiskKind::Stale, detail)));
+                }
+            }
+        }
+    }
+
+    let sprint = sprint.map(|burndown| {
+        let current = &burndown.current;
+        let last = burndown.end.succ_opt().unwrap_or(burndown.end);
+        let overview = SprintOverview {
+            name: burndown.sprint_name.clone(),
+            start: burndown.start,
+            end: burndown.end,
+            days_left: cal
+                .team
+                .working_days_between(today.max(burndown.start), last),
+            total_cards: burndown.total_cards,
+            remaining: current.actual,
+            ideal_remaining: current.ideal,
+            behind: current.actual as f64 >= current.ideal + 1.0,
+        };
+        if overview.behind {
+            let over = (current.actual as f64 - current.ideal).round() as i64;
+            risks.push((
+                RiskKind::SprintBehind,
+                over,
+                Risk {
+                    kind: RiskKind::SprintBehind,
+                    board: overview.name.clone(),
+                    card_id: None,
+                    title: format!("Sprint {}", overview.name),
+                    detail: format!(
+                        "{} of {} cards left, {:.1} on the ideal line",
+                        current.actual, burndown.total_cards, current.ideal
+                    ),
+                },
+            ));
+        }
+        overview
+    });
+
+    risks.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+    let boards: Vec<BoardOverview> = boards
+        .iter()
+        .map(|board| calculate_stats(board, policy, &ReportWindow::default(), cal).into())
+        .collect();
+
+    Overview {
+        active_cards: boards.iter().map(|b| b.active_cards).sum(),
+        wip_count: boards.iter().map(|b| b.wip_count).sum(),
+        done_7d: boards.iter().map(|b| b.done_7d).sum(),
+        overdue: boards.iter().map(|b| b.overdue).sum(),
+        boards,
+        sprint,
+        risks: risks
+            .into_iter()
+            .take(max_risks)
+            .map(|(_, _, risk)| risk)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kuk::model::{Card, Column};
+
+    #[test]
+    fn overview_ranks_risks_across_boards() {
+        let now = Utc::now();
+        let cal = Calendar::default();
+        let mut web = Board::default_board();
+        web.name = "web".into();
+        web.columns[1] = Column {
+            wip_limit: Some(1),
+            ..Column::new("doing")
+        };
+        let mut late = Card::new("Ship login", "todo");
+        late.due = Some(now - chrono::Duration::days(3));
+        web.add_card(late);
+        let mut idle = Card::new("Refactor auth", "doing");
+        idle.updated_at = now - chrono::Duration::days(20);
+        web.add_card(idle);
+        web.add_card(Card::new("Fresh", "doing"));
+
+        let mut api = Board::default_board();
+        api.name = "api".into();
+        let mut later = Card::new("Rate limits", "doing");
+        later.due = Some(now - chrono::Duration::days(9));
+        api.add_card(later);
+        api.add_card(Card::new("Finished", "done"));
+
+        let overview =
+            calculate_overview(&[web, api], None, OutlierPolicy::default(), &cal, now, 3);
+        assert_eq!(overview.boards.len(), 2);
+        assert_eq!((overview.active_cards, overview.overdue), (5, 2));
+        assert!(overview.sprint.is_none());
+        let risks: Vec<(RiskKind, &str)> = overview
+            .risks
+            .iter()
+            .map(|r| (r.kind, r.title.as_str()))
+            .collect();
+        assert_eq!(
+            risks,
+            [
+                (RiskKind::WipLimit, "doing"),
+                (RiskKind::Overdue, "Rate limits"),
+                (RiskKind::Overdue, "Ship login"),
+            ]
+        );
+        assert_eq!(overview.risks[1].board, "api");
+    }
+
+    #[test]
+    fn growing_queues_are_risks() {
+        let now = Utc::now();
+        let mut board = Board::default_board();
+        board.columns.insert(
+            2,
+            Column {
+                queue: true,
+                ..Column::new("review")
+            },
+        );
+        let mut waiting = Card::new("Waiting since last month", "review");
+        waiting.created_at = now - chrono::Duration::days(30);
+        board.add_card(waiting);
+        for title in ["New 1", "New 2"] {
+            board.add_card(Card::new(title, "review"));
+        }
+
+        let overview = calculate_overview(
+            std::slice::from_ref(&board),
+            None,
+            OutlierPolicy::default(),
+            &Calendar::default(),
+            now,
+            5,
+        );
+        let risk = &overview.risks[0];
+        assert_eq!(
+            (risk.kind, risk.title.as_str()),
+            (RiskKind::QueueGrowth, "review")
+        );
+        assert_eq!(risk.detail, "3 cards waiting, up from 1 7 days ago");
+        assert_eq!(overview.wip_count, 0);
+
+        board.set_queue("review", false).unwrap();
+        let overview = calculate_overview(
+            &[board],
+            None,
+            OutlierPolicy::default(),
+            &Calendar::default(),
+            now,
+            5,
+        );
+        assert!(overview.risks.is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+//! When columns went over their WIP limits, rebuilt from the transitions
+//! kuk records on every move. Boards keep no history of their limits, so
+//! each column is measured against the limit it has now.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use kuk::model::{Board, Card};
+
+use super::ReportWindow;
+use crate::model::{Calendar, Locale};
+
+/// A card that sat in a column while it was over its limit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViolationCard {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_id: Option<String>,
+    pub title: String,
+}
+
+/// One stretch of time a column held more cards than its limit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipViolation {
+    pub board: String,
+    pub column: String,
+    pub limit: u32,
+    pub start: DateTime<Utc>,
+    /// None while the column is still over.
+    pub end: Option<DateTime<Utc>>,
+    pub duration_days: f64,
+    /// Most cards the column held during the stretch.
+    pub peak: usize,
+    /// Every card in the column at some point during the stretch.
+    pub cards: Vec<ViolationCard>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipHistoryReport {
+    /// Violations that overlap the window, oldest first.
+    pub violations: Vec<WipViolation>,
+    /// Columns with a WIP limit, the only ones checked.
+    pub limited_columns: usize,
+    /// Cards moved at least once; older moves left no history.
+    pub cards_with_history: usize,
+    #[serde(default, skip_serializing_if = "ReportWindow::is_open")]
+    pub window: ReportWindow,
+}
+
+/// Every time a column of `boards` with a WIP limit held more cards than
+/// it, as of `now`.
+pub fn calculate_wip_history(
+    boards: &[Board],
+    window: &ReportWindow,
+    cal: &Calendar,
+    now: DateTime<Utc>,
+) -> WipHistoryReport {
+    let end = window.end(cal);
+    let mut violations = Vec::new();
+    let mut limited_columns = 0;
+
+    for board in boards {
+        let spans: Vec<_> = board
+            .cards
+            .iter()
+            .map(|c| (c, c.column_spans(now)))
+            .collect();
+        for column in &board.columns {
+            let Some(limit) = column.wip_limit else {
+                continue;
+            };
+            limited_columns += 1;
+            // (when, +1 arriving / -1 leaving, card); leaving sorts first so
+            // a swap at the same moment never counts both cards
+            let mut events: Vec<(DateTime<Utc>, i32, &Card)> = Vec::new();
+            for (card, card_spans) in &spans {
+                for (name, from, to) in card_spans {
+                    if *name == column.name && from < to {
+                        events.push((*from, 1, card));
+                        if *to < now {
+                            events.push((*to, -1, card));
+                        }
+                    }
+                }
+            }
+            events.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+
+            let mut present: Vec<&Card> = Vec::new();
+            let mut open: Option<WipViolation> = None;
+            for (at, change, card) in events {
+                if change > 0 {
+                    present.push(card);
+                } else if let Some(i) = present.iter().position(|c| c.id == card.id) {
+                    present.remove(i);
+                }
+                let over = present.len() > limit as usize;
+                match &mut open {
+                    Some(violation) if over => {
+                        violation.peak = violation.peak.max(present.len());
+                        if change > 0 {
+                            violation.cards.push(violation_card(card));
+                        }
+                    }
+                    Some(_) => {
+                        let mut violation = open.take().expect("violation is open");
+                        violation.end = Some(at);
+                        violations.push(violation);
+                    }
+                    None if over => {
+                        open = Some(WipViolation {
+                            board: board.name.clone(),
+                            column: column.name.clone(),
+                            limit,
+                            start: at,
+                            end: None,
+                            duration_days: 0.0,
+                            peak: present.len(),
+                            cards: present.iter().map(|c| violation_card(c)).collect(),
+                        });
+                    }
+                    None => {}
+                }
+            }
+            violations.extend(open);
+        }
+    }
+
+    for violation in &mut violations {
+        let until = violation.end.unwrap_or(now);
+        violation.duration_days = (until - violation.start).num_minutes().max(0) as f64 / 1440.0;
+    }
+    violations.retain(|v| {
+        let ended = v.end.map_or(end, |e| cal.date_of(e));
+        cal.date_of(v.start) <= end && window.since.is_none_or(|since| ended >= since)
+    });
+    violations.sort_by_key(|v| v.start);
+
+    let cards_with_history = boards
+        .iter()
+        .flat_map(|b| &b.cards)
+        .filter(|c| c.entered_column_at().is_some())
+        .count();
+
+    WipHistoryReport {
+        violations,
+        limited_columns,
+        cards_with_history,
+        window: *window,
+    }
+}
+
+fn violation_card(card: &Card) -> ViolationCard {
+    ViolationCard {
+        id: card.id.clone(),
+        short_id: card.short_id(),
+        title: card.title.clone(),
+    }
+}
+
+pub fn render_wip_history_text(report: &WipHistoryReport, locale: &Locale) -> String {
+    let mut out = format!("WIP limit violations ({})\n", report.violations.len());
+    out.push_str("────────────────────────────────\n");
+
+    if report.limited_columns == 0 {
+        out.push_str("  No column has a WIP limit; set one with `kuk column set-wip`.\n");
+        return out;
+    }
+    if report.violations.is_empty() {
+        out.push_str("  No column went over its limit in this period.\n");
+        if report.cards_with_history == 0 {
+            out.push_str(
+                "  kuk records column moves from now on; earlier moves left no history.\n",
+            );
+        }
+        return out;
+    }
+
+    for v in &report.violations {
+        let end = match v.end {
+            Some(end) => locale.date(end.date_naive()),
+            None => "now".into(),
+        };
+        out.push_str(&format!(
+            "  {}/{}  limit {}, peak {}  {} → {end}  {}d\n",
+            v.board,
+            v.column,
+            v.limit,
+            v.peak,
+            locale.date(v.start.date_naive()),
+            locale.number(v.duration_days, 1)
+        ));
+        for card in &v.cards {
+            match &card.short_id {
+                Some(short) => out.push_str(&format!("      {short}  {}\n", card.title)),
+                None => out.push_str(&format!("      {}\n", card.title)),
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use kuk::model::{Column, TRANSITIONS_KEY, Transition};
+
+    fn at(day: &str) -> DateTime<Utc> {
+        NaiveDate::parse_from_str(day, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    /// A card created in todo on `created` and moved to doing, then on to
+    /// done if `done` is given.
+    fn card(title: &str, created: &str, doing: &str, done: Option<&str>) -> Card {
+        let mut card = Card::new(title, if done.is_some() { "done" } else { "doing" });
+        card.created_at = at(created);
+        let mut history = vec![Transition {
+            at: at(doing),
+            from: "todo".into(),
+            to: "doing".into(),
+            origin: None,
+        }];
+        if let Some(done) = done {
+            history.push(Transition {
+                at: at(done),
+                from: "doing".into(),
+                to: "done".into(),
+                origin: None,
+            });
+        }
+        card.metadata.insert(
+            TRANSITIONS_KEY.into(),
+            serde_json::to_value(history).unwrap(),
+        );
+        card
+    }
+
+    #[test]
+    fn stretches_over_the_limit_with_their_cards() {
+        let mut board = Board::default_board();
+        board.columns[1] = Column {
+            wip_limit: Some(1),
+            ..Column::new("doing")
+        };
+        board.cards = vec![
+            card("First", "2026-03-01", "2026-03-02", Some("2026-03-06")),
+            card("Second", "2026-03-01", "2026-03-04", Some("2026-03-05")),
+            card("Third", "2026-03-01", "2026-03-10", None),
+            card("Fourth", "2026-03-01", "2026-03-12", None),
+        ];
+        let now = at("2026-03-13");
+        let report = calculate_wip_history(
+            std::slice::from_ref(&board),
+            &ReportWindow::default(),
+            &Calendar::default(),
+            now,
+        );
+
+        assert_eq!(report.limited_columns, 1);
+        assert_eq!(report.violations.len(), 2);
+        let first = &report.violations[0];
+        assert_eq!(
+            (first.start, first.end),
+            (at("2026-03-04"), Some(at("2026-03-05")))
+        );
+        assert_eq!(first.duration_days, 1.0);
+        let titles: Vec<&str> = first.cards.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, ["First", "Second"]);
+
+        let ongoing = &report.violations[1];
+        assert_eq!((ongoing.start, ongoing.end), (at("2026-03-12"), None));
+        assert_eq!(ongoing.peak, 2);
+
+        let text = render_wip_history_text(&report, &Locale::default());
+        assert!(text.contains("default/doing  limit 1, peak 2  2026-03-04 → 2026-03-05  1.0d"));
+        assert!(text.contains("2026-03-12 → now"));
+
+        // A window after the first stretch leaves only the ongoing one
+        let window = ReportWindow::parse(Some("2026-03-08"), None).unwrap();
+        let report = calculate_wip_history(&[board], &window, &Calendar::default(), now);
+        assert_eq!(report.violations.len(), 1);
+    }
+
+    #[test]
+    fn columns_without_limits_are_not_checked() {
+        let mut board = Board::default_board();
+        board.add_card(Card::new("One", "doing"));
+        board.add_card(Card::new("Two", "doing"));
+        let report = calculate_wip_history(
+            &[board],
+            &ReportWindow::default(),
+            &Calendar::default(),
+            Utc::now(),
+        );
+        assert_eq!(report.limited_columns, 0);
+        assert!(render_wip_history_text(&report, &Locale::default()).contains("set-wip"));
+    }
+}
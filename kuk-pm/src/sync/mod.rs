@@ -1,17 +1,35 @@
+mod provider;
+mod retry;
+
+pub use provider::{
+    CREATE_ISSUE, CREATE_MILESTONE, CREATE_PR, FIXTURE_ENV, GhCli, MockProvider, RECORD_ENV,
+    Recorder, SyncProvider, provider,
+};
+pub use retry::{RetryPolicy, Retrying};
+
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
-use std::process::Command;
 
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use kuk::model::Card;
-use kuk::storage::Store;
+use kuk::model::{
+    Board, Card, ChecklistItem, LabelDef, Origin, Transition, Via, merge_checklist,
+    parse_checklist, parse_color,
+};
+use kuk::storage::{CardQuery, Store};
 
 use crate::error::{PmError, Result};
-use crate::model::GitMetadata;
+use crate::git::{self, CardRef, CommitInfo};
+use crate::model::{
+    Calendar, ChecklistSync, ForgeHosts, ForgeUrl, GitMetadata, Link, LinkKind, LinkRole,
+    Milestone, Objective, PmConfig, PostedComment, Provider, Sprint, SprintStatus, TeamCalendar,
+};
+use crate::reports::ReleaseCard;
 
 // ─── Types ───────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncAction {
     pub card_title: String,
     pub card_id: String,
@@ -19,242 +37,1096 @@ pub struct SyncAction {
     pub detail: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SyncActionType {
     UpdateColumn,
     UpdateUrl,
+    UpdateSprint,
+    UpdateLabel,
+    UpdateChecklist,
+    Comment,
     Skip,
 }
 
+/// One sync run as recorded in `.kuk/sync-log.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncLogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub dry_run: bool,
+    pub actions: Vec<SyncAction>,
+    /// Forge calls that failed for a moment and were tried again.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retried: Vec<String>,
+}
+
+/// What a sync run did.
+#[derive(Debug, Clone, Default)]
+pub struct SyncRun {
+    pub actions: Vec<SyncAction>,
+    /// Forge calls that needed more than one try, and how they ended.
+    pub retried: Vec<String>,
+}
+
+/// Restricts which cards a sync run considers. Empty fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct SyncScope {
+    /// Board to sync; defaults to the kuk default board.
+    pub board: Option<String>,
+    /// Cards must carry at least one of these labels.
+    pub labels: Vec<String>,
+    /// Cards must sit in one of these columns.
+    pub columns: Vec<String>,
+}
+
+impl SyncScope {
+    pub fn includes(&self, card: &Card) -> bool {
+        (self.labels.is_empty() || card.labels.iter().any(|l| self.labels.contains(l)))
+            && (self.columns.is_empty() || self.columns.contains(&card.column))
+    }
+}
+
+/// Issue fields copied onto a card when it is linked.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct IssueDetails {
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Forge colors of the issue's labels, keyed by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub label_colors: BTreeMap<String, String>,
+}
+
 // ─── Sync logic ──────────────────────────────────────────────
 
-/// Run bidirectional sync. Returns list of actions taken (or that would be
-/// taken if dry_run is true).
-pub fn run_sync(repo: &Path, dry_run: bool, json_output: bool) -> Result<Vec<SyncAction>> {
-    let store = Store::new(repo);
-    if !store.is_initialized() {
-        return Err(PmError::KukNotInitialized);
+/// Run bidirectional sync and print what it did, with cards that could not
+/// be synced listed at the end. Returns list of actions taken (or that
+/// would be taken if dry_run is true).
+pub fn run_sync(
+    provider: &dyn SyncProvider,
+    repo: &Path,
+    scope: &SyncScope,
+    create_missing: bool,
+    dry_run: bool,
+    json_output: bool,
+) -> Result<Vec<SyncAction>> {
+    let retry = RetryPolicy::default();
+    let run = sync_board(provider, repo, scope, create_missing, dry_run, &retry)?;
+    let Some(SyncRun { actions, retried }) = run else {
+        if json_output {
+            println!("[]");
+        } else {
+            let board_name = match &scope.board {
+                Some(board) => board.clone(),
+                None => Store::new(repo).load_config()?.default_board,
+            };
+            println!("Sync is disabled for board '{board_name}' in .kuk/pm.json.");
+        }
+        return Ok(Vec::new());
+    };
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&actions)?);
+    } else if actions.is_empty() && retried.is_empty() {
+        println!("Everything up to date.");
+    } else {
+        if dry_run {
+            println!("Dry run — no changes applied:\n");
+        }
+        let (failed, done): (Vec<_>, Vec<_>) = actions
+            .iter()
+            .partition(|a| matches!(a.action, SyncActionType::Skip));
+        for action in &done {
+            println!("{}", render_action(action));
+        }
+        if !done.is_empty() {
+            println!(
+                "\n{} action(s){}",
+                done.len(),
+                if dry_run { " (dry run)" } else { " applied" }
+            );
+        }
+        if !retried.is_empty() {
+            println!("\nRetried {} forge call(s):", retried.len());
+            for call in &retried {
+                println!("  {call}");
+            }
+        }
+        if !failed.is_empty() {
+            println!("\nCould not sync {} item(s):", failed.len());
+            for action in &failed {
+                println!("{}", render_action(action));
+            }
+        }
     }
 
-    // Check gh CLI availability
-    if !is_gh_available() {
-        return Err(PmError::Other(
-            "GitHub CLI (gh) not found. Install it from https://cli.github.com/".into(),
-        ));
+    Ok(actions)
+}
+
+/// Run bidirectional sync without printing anything, for callers that own
+/// the terminal or stdout. Returns the actions taken (or that would be taken
+/// if dry_run is true), or `None` when `.kuk/pm.json` turns sync off for the
+/// board. With `create_missing`, cards in scope that have no linked issue get
+/// one created and linked. Forge calls that fail for a moment are retried
+/// as `retry` allows; ones that still fail become Skip actions.
+#[tracing::instrument(level = "info", skip(provider, repo, scope, retry))]
+pub fn sync_board(
+    provider: &dyn SyncProvider,
+    repo: &Path,
+    scope: &SyncScope,
+    create_missing: bool,
+    dry_run: bool,
+    retry: &RetryPolicy,
+) -> Result<Option<SyncRun>> {
+    let retrying = Retrying::new(provider, retry);
+    let provider: &dyn SyncProvider = &retrying;
+    let store = Store::new(repo).with_origin(Origin::new(Via::Sync));
+    if !store.is_initialized() {
+        return Err(PmError::KukNotInitialized);
     }
 
+    let pm_config = load_pm_config(&store)?;
     let config = store.load_config()?;
-    let mut board = store.load_board(&config.default_board)?;
+    let board_name = scope.board.as_deref().unwrap_or(&config.default_board);
+    let mut board = store.load_board(board_name)?;
+
+    if !pm_config.board_sync_enabled(board_name) {
+        return Ok(None);
+    }
 
     let mut actions = Vec::new();
+    let mut finished_ids = Vec::new();
+    let mut stamped = false;
 
     for card in &mut board.cards {
-        if card.archived {
+        if card.archived || !scope.includes(card) {
             continue;
         }
 
-        let meta = get_pm_metadata(card);
+        let mut meta = get_pm_metadata(card);
 
-        // Check linked issues
-        if let Some(ref issue_url) = meta.issue_url {
-            match fetch_issue_state(issue_url) {
-                Ok(state) => {
-                    let target_column = match state.as_str() {
-                        "closed" => Some("done"),
-                        "open" => None, // don't move open issues
-                        _ => None,
-                    };
+        // Publish local-only cards; finished work isn't worth an open issue
+        if create_missing && meta.issues().next().is_none() && card.column != "done" {
+            let (action, detail) = if dry_run {
+                (SyncActionType::UpdateUrl, "would create issue".to_string())
+            } else {
+                let body = card.description.clone().unwrap_or_default();
+                match provider.create_issue(repo, &card.title, &body) {
+                    Ok(url) => {
+                        meta.add_link(&url, LinkKind::Issue, LinkRole::Implements);
+                        set_pm_metadata(card, &meta);
+                        card.updated_at = chrono::Utc::now();
+                        (SyncActionType::UpdateUrl, format!("created issue {url}"))
+                    }
+                    Err(e) => (SyncActionType::Skip, format!("failed to create issue: {e}")),
+                }
+            };
+            actions.push(SyncAction {
+                card_title: card.title.clone(),
+                card_id: card.id.clone(),
+                action,
+                detail,
+            });
+        }
+
+        if pm_config.comment_on_move {
+            match announce_moves(provider, card, &pm_config.forge_hosts, dry_run) {
+                Announced::Nothing => {}
+                Announced::Stamped => stamped = true,
+                Announced::Action(action) => actions.push(action),
+            }
+        }
+
+        // Reverts don't signal completion, so they never drive column moves
+        let tracked: Vec<&Link> = meta
+            .links
+            .iter()
+            .filter(|l| l.role != LinkRole::Reverts)
+            .collect();
+        if tracked.is_empty() {
+            continue;
+        }
 
-                    if let Some(col) = target_column
-                        && card.column != col
-                    {
+        if pm_config.checklist_sync != ChecklistSync::Off {
+            for link in tracked.iter().filter(|l| l.kind == LinkKind::Pr) {
+                let synced = parse_url(&link.url, &pm_config.forge_hosts).and_then(|forge| {
+                    sync_checklist(provider, &forge, card, pm_config.checklist_sync, dry_run)
+                });
+                let detail = match synced {
+                    Ok(Some(detail)) => detail,
+                    Ok(None) => continue,
+                    Err(e) => {
                         actions.push(SyncAction {
                             card_title: card.title.clone(),
                             card_id: card.id.clone(),
-                            action: SyncActionType::UpdateColumn,
-                            detail: format!("{} → {col} (issue {state})", card.column),
+                            action: SyncActionType::Skip,
+                            detail: format!("failed to sync checklist of {}: {e}", link.url),
                         });
-                        if !dry_run {
-                            card.column = col.to_string();
-                            card.updated_at = chrono::Utc::now();
-                        }
+                        continue;
                     }
-                }
-                Err(e) => {
-                    actions.push(SyncAction {
-                        card_title: card.title.clone(),
-                        card_id: card.id.clone(),
-                        action: SyncActionType::Skip,
-                        detail: format!("failed to fetch issue: {e}"),
-                    });
-                }
+                };
+                actions.push(SyncAction {
+                    card_title: card.title.clone(),
+                    card_id: card.id.clone(),
+                    action: SyncActionType::UpdateChecklist,
+                    detail,
+                });
             }
         }
 
-        // Check linked PRs
-        if let Some(ref pr_url) = meta.pr_url {
-            match fetch_pr_state(pr_url) {
+        // A card is done only once every tracked issue/PR is closed or merged
+        let mut finished = Vec::new();
+        let mut all_finished = true;
+        for link in &tracked {
+            let state = parse_url(&link.url, &pm_config.forge_hosts)
+                .and_then(|forge| provider.state(&forge));
+            match state {
                 Ok(state) => {
-                    let target_column = match state.as_str() {
-                        "merged" | "closed" => Some("done"),
-                        "open" => None,
-                        _ => None,
+                    let done = match link.kind {
+                        LinkKind::Issue => state == "closed",
+                        LinkKind::Pr => state == "merged" || state == "closed",
                     };
-
-                    if let Some(col) = target_column
-                        && card.column != col
-                    {
-                        actions.push(SyncAction {
-                            card_title: card.title.clone(),
-                            card_id: card.id.clone(),
-                            action: SyncActionType::UpdateColumn,
-                            detail: format!("{} → {col} (PR {state})", card.column),
-                        });
-                        if !dry_run {
-                            card.column = col.to_string();
-                            card.updated_at = chrono::Utc::now();
-                        }
+                    if done {
+                        finished.push(format!("{} {state}", link.kind));
+                    } else {
+                        all_finished = false;
                     }
                 }
                 Err(e) => {
+                    all_finished = false;
                     actions.push(SyncAction {
                         card_title: card.title.clone(),
                         card_id: card.id.clone(),
                         action: SyncActionType::Skip,
-                        detail: format!("failed to fetch PR: {e}"),
+                        detail: format!("failed to fetch {}: {e}", link.kind),
                     });
                 }
             }
         }
+
+        let col = "done";
+        if all_finished && card.column != col {
+            actions.push(SyncAction {
+                card_title: card.title.clone(),
+                card_id: card.id.clone(),
+                action: SyncActionType::UpdateColumn,
+                detail: format!("{} → {col} ({})", card.column, finished.join(", ")),
+            });
+            if !dry_run {
+                finished_ids.push(card.id.clone());
+            }
+        }
+    }
+    // Moved once the loop lets go of the cards, so column rules apply
+    for id in &finished_ids {
+        board.move_card(id, "done");
     }
 
+    actions.extend(sync_label_colors(
+        provider,
+        &mut board,
+        &pm_config.forge_hosts,
+    ));
+    actions.extend(sync_milestones(provider, repo, &store, dry_run)?);
+
     if !dry_run
-        && actions
-            .iter()
-            .any(|a| matches!(a.action, SyncActionType::UpdateColumn))
+        && (stamped
+            || actions.iter().any(|a| {
+                matches!(
+                    a.action,
+                    SyncActionType::UpdateColumn
+                        | SyncActionType::UpdateUrl
+                        | SyncActionType::UpdateLabel
+                        | SyncActionType::UpdateChecklist
+                        | SyncActionType::Comment
+                )
+            }))
     {
         store.save_board(&board)?;
     }
 
-    // Output
-    if json_output {
-        println!("{}", serde_json::to_string_pretty(&actions)?);
-    } else if actions.is_empty() {
-        println!("Everything up to date.");
-    } else {
+    append_sync_log(
+        &store,
+        &SyncLogEntry {
+            timestamp: Utc::now(),
+            dry_run,
+            actions: actions.clone(),
+            retried: retrying.retried(),
+        },
+    )?;
+
+    Ok(Some(SyncRun {
+        actions,
+        retried: retrying.retried(),
+    }))
+}
+
+enum Announced {
+    Nothing,
+    /// First sync with comments on; the card now records when it was synced.
+    Stamped,
+    Action(SyncAction),
+}
+
+/// With `comment_on_move`, post one comment on the card's issue (or PR)
+/// listing the columns it moved through since it was last synced. Moves
+/// from before the card's first such sync are not announced.
+fn announce_moves(
+    provider: &dyn SyncProvider,
+    card: &mut Card,
+    hosts: &ForgeHosts,
+    dry_run: bool,
+) -> Announced {
+    let mut meta = get_pm_metadata(card);
+    let Some(url) = meta.comment_target().map(|l| l.url.clone()) else {
+        return Announced::Nothing;
+    };
+    let now = Utc::now();
+    let Some(since) = meta.last_synced else {
         if dry_run {
-            println!("Dry run — no changes applied:\n");
+            return Announced::Nothing;
         }
-        for action in &actions {
-            let prefix = match action.action {
-                SyncActionType::UpdateColumn => "  [SYNC]",
-                SyncActionType::UpdateUrl => "  [LINK]",
-                SyncActionType::Skip => "  [SKIP]",
-            };
-            println!("{prefix} {} — {}", action.card_title, action.detail);
-        }
-        println!(
-            "\n{} action(s){}",
-            actions.len(),
-            if dry_run { " (dry run)" } else { " applied" }
+        meta.last_synced = Some(now);
+        set_pm_metadata(card, &meta);
+        return Announced::Stamped;
+    };
+    let Some(body) = move_comment(&Transition::history(card), since) else {
+        return Announced::Nothing;
+    };
+
+    let (card_title, card_id) = (card.title.clone(), card.id.clone());
+    let action = |action, detail| {
+        Announced::Action(SyncAction {
+            card_title,
+            card_id,
+            action,
+            detail,
+        })
+    };
+    if dry_run {
+        return action(
+            SyncActionType::Comment,
+            format!("would comment on {url}: {body}"),
         );
     }
+    match post_comment(provider, &url, &body, hosts) {
+        Ok(comment_url) => {
+            meta.comments.push(PostedComment {
+                at: now,
+                url: url.clone(),
+                body: body.clone(),
+                comment_url,
+            });
+            meta.last_synced = Some(now);
+            set_pm_metadata(card, &meta);
+            card.updated_at = now;
+            action(
+                SyncActionType::Comment,
+                format!("commented on {url}: {body}"),
+            )
+        }
+        Err(e) => action(
+            SyncActionType::Skip,
+            format!("failed to comment on {url}: {e}"),
+        ),
+    }
+}
+
+/// The status comment for moves after `since`, e.g.
+/// `Moved on the board: todo → doing → review`.
+fn move_comment(history: &[Transition], since: DateTime<Utc>) -> Option<String> {
+    let moves: Vec<&Transition> = history.iter().filter(|t| t.at > since).collect();
+    let first = moves.first()?;
+    let mut path = vec![first.from.as_str()];
+    path.extend(moves.iter().map(|t| t.to.as_str()));
+    Some(format!("Moved on the board: {}", path.join(" → ")))
+}
+
+pub fn render_action(action: &SyncAction) -> String {
+    let prefix = match action.action {
+        SyncActionType::UpdateColumn => "  [SYNC]",
+        SyncActionType::UpdateUrl => "  [LINK]",
+        SyncActionType::UpdateSprint => "  [SPRINT]",
+        SyncActionType::UpdateLabel => "  [LABEL]",
+        SyncActionType::UpdateChecklist => "  [CHECK]",
+        SyncActionType::Comment => "  [COMMENT]",
+        SyncActionType::Skip => "  [SKIP]",
+    };
+    format!("{prefix} {} — {}", action.card_title, action.detail)
+}
+
+/// Copy forge label colors into the board's label registry for every
+/// project the board links to. The board is only saved on a real run.
+fn sync_label_colors(
+    provider: &dyn SyncProvider,
+    board: &mut Board,
+    hosts: &ForgeHosts,
+) -> Vec<SyncAction> {
+    // One label fetch per linked project
+    let projects: BTreeMap<(String, String), ForgeUrl> = board
+        .cards
+        .iter()
+        .filter(|c| !c.archived)
+        .flat_map(|c| get_pm_metadata(c).links)
+        .filter_map(|l| ForgeUrl::parse(&l.url, hosts))
+        .filter(|f| f.provider != Provider::Bitbucket)
+        .map(|f| ((f.host.clone(), f.project.clone()), f))
+        .collect();
+
+    let mut colors = BTreeMap::new();
+    let mut actions = Vec::new();
+    for ((_, project), forge) in projects {
+        match provider.label_colors(&forge) {
+            Ok(fetched) => colors.extend(fetched),
+            Err(e) => actions.push(SyncAction {
+                card_title: project,
+                card_id: String::new(),
+                action: SyncActionType::Skip,
+                detail: format!("failed to fetch labels: {e}"),
+            }),
+        }
+    }
+
+    for (name, color) in apply_label_colors(board, &colors) {
+        actions.push(SyncAction {
+            card_title: name,
+            card_id: String::new(),
+            action: SyncActionType::UpdateLabel,
+            detail: format!("color {color}"),
+        });
+    }
+    actions
+}
+
+/// Give registry labels without a color, and card labels not yet in the
+/// registry, their forge color. Colors set locally are never overwritten.
+/// Returns the labels that changed with their new color.
+pub fn apply_label_colors(
+    board: &mut Board,
+    colors: &BTreeMap<String, String>,
+) -> Vec<(String, String)> {
+    let used: BTreeSet<String> = board
+        .cards
+        .iter()
+        .filter(|c| !c.archived)
+        .flat_map(|c| c.labels.iter().cloned())
+        .collect();
+
+    let mut changed = Vec::new();
+    for (name, raw) in colors {
+        let Ok(color) = parse_color(raw) else {
+            continue;
+        };
+        match board.labels.iter_mut().find(|l| &l.name == name) {
+            Some(def) if def.color.is_none() => def.color = Some(color.clone()),
+            Some(_) => continue,
+            None if used.contains(name) => {
+                let mut def = LabelDef::new(name);
+                def.color = Some(color.clone());
+                board.labels.push(def);
+            }
+            None => continue,
+        }
+        changed.push((name.clone(), color));
+    }
+    changed
+}
+
+/// Refresh open/closed counts and due dates of sprints mapped to GitHub
+/// milestones. A changed milestone due date moves the sprint's end date.
+fn sync_milestones(
+    provider: &dyn SyncProvider,
+    repo: &Path,
+    store: &Store,
+    dry_run: bool,
+) -> Result<Vec<SyncAction>> {
+    let mut sprints = load_sprints(store)?;
+    let mut actions = Vec::new();
+    let mut changed = false;
+
+    for sprint in sprints.iter_mut() {
+        let Some(current) = sprint.milestone.as_ref() else {
+            continue;
+        };
+        let fetched = match provider.milestone(repo, current.number) {
+            Ok(m) => m,
+            Err(e) => {
+                actions.push(SyncAction {
+                    card_title: sprint.name.clone(),
+                    card_id: String::new(),
+                    action: SyncActionType::Skip,
+                    detail: format!("failed to fetch milestone #{}: {e}", current.number),
+                });
+                continue;
+            }
+        };
+
+        if let Some(due) = fetched.due_on
+            && due != sprint.end
+        {
+            actions.push(SyncAction {
+                card_title: sprint.name.clone(),
+                card_id: String::new(),
+                action: SyncActionType::UpdateSprint,
+                detail: format!("end {} → {due} (milestone due date)", sprint.end),
+            });
+            if !dry_run {
+                sprint.end = due;
+            }
+        }
 
+        if !dry_run {
+            sprint.milestone = Some(Milestone {
+                last_synced: Some(Utc::now()),
+                ..fetched
+            });
+            changed = true;
+        }
+    }
+
+    if changed {
+        save_sprints(store, &sprints)?;
+    }
     Ok(actions)
 }
 
-// ─── GitHub API helpers ──────────────────────────────────────
+// ─── Sync log ────────────────────────────────────────────────
 
-fn is_gh_available() -> bool {
-    Command::new("gh")
-        .arg("--version")
-        .output()
-        .is_ok_and(|o| o.status.success())
+fn sync_log_path(store: &Store) -> std::path::PathBuf {
+    store.kuk_dir().join("sync-log.jsonl")
 }
 
-/// Parse a GitHub issue/PR URL into (owner, repo, number).
-fn parse_github_url(url: &str) -> Option<(String, String, String)> {
-    // https://github.com/owner/repo/issues/42
-    // https://github.com/owner/repo/pull/42
-    let parts: Vec<&str> = url.trim_end_matches('/').split('/').collect();
-    if parts.len() >= 5 {
-        let owner = parts[parts.len() - 4].to_string();
-        let repo = parts[parts.len() - 3].to_string();
-        let number = parts[parts.len() - 1].to_string();
-        Some((owner, repo, number))
-    } else {
-        None
+/// Append a run to the sync log, one JSON object per line.
+pub fn append_sync_log(store: &Store, entry: &SyncLogEntry) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(sync_log_path(store))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Load logged sync runs, oldest first. `last` keeps only the N most recent.
+pub fn load_sync_log(store: &Store, last: Option<usize>) -> Result<Vec<SyncLogEntry>> {
+    let path = sync_log_path(store);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(&path)?;
+    let mut entries = data
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<Vec<SyncLogEntry>, _>>()?;
+    if let Some(n) = last {
+        let skip = entries.len().saturating_sub(n);
+        entries.drain(..skip);
+    }
+    Ok(entries)
+}
+
+// ─── Forge API helpers ───────────────────────────────────────
+
+fn parse_url(url: &str, hosts: &ForgeHosts) -> Result<ForgeUrl> {
+    ForgeUrl::parse(url, hosts).ok_or_else(|| {
+        PmError::Other(format!(
+            "unrecognized issue/PR URL: {url} (self-hosted forges must be listed in forge_hosts in .kuk/pm.json)"
+        ))
+    })
+}
+
+/// Post a comment on a linked issue or PR. Returns the comment's web
+/// address when the forge reports one.
+pub fn post_comment(
+    provider: &dyn SyncProvider,
+    url: &str,
+    body: &str,
+    hosts: &ForgeHosts,
+) -> Result<Option<String>> {
+    provider.post_comment(&parse_url(url, hosts)?, body)
+}
+
+/// Mirror the task list of a linked PR onto the card's description, and with
+/// `ChecklistSync::Both` check off PR items done on the card. Returns a
+/// description of what changed, if anything did.
+fn sync_checklist(
+    provider: &dyn SyncProvider,
+    forge: &ForgeUrl,
+    card: &mut Card,
+    direction: ChecklistSync,
+    dry_run: bool,
+) -> Result<Option<String>> {
+    let body = provider.pr_body(forge)?;
+    let (description, body) = reconcile_checklists(
+        card.description.as_deref().unwrap_or_default(),
+        &body,
+        direction,
+    );
+    if description.is_none() && body.is_none() {
+        return Ok(None);
+    }
+
+    let mut changed = Vec::new();
+    if let Some(description) = description {
+        let items = parse_checklist(&description);
+        let done = items.iter().filter(|i| i.done).count();
+        changed.push(format!("card checklist {done}/{} done", items.len()));
+        if !dry_run {
+            card.description = Some(description);
+            card.updated_at = Utc::now();
+        }
+    }
+    if let Some(body) = body {
+        changed.push(format!("updated PR #{} task list", forge.number));
+        if !dry_run {
+            provider.update_pr_body(forge, &body)?;
+        }
+    }
+    Ok(Some(changed.join(", ")))
+}
+
+/// The new card description and PR body after syncing their task lists,
+/// each `None` when unchanged. PR items missing on the card are appended to
+/// it; with `ChecklistSync::Both`, an item checked on either side ends up
+/// checked on both.
+fn reconcile_checklists(
+    description: &str,
+    body: &str,
+    direction: ChecklistSync,
+) -> (Option<String>, Option<String>) {
+    let mut pr_items = parse_checklist(body);
+    if pr_items.is_empty() {
+        return (None, None);
+    }
+    let card_items = parse_checklist(description);
+    let body = match direction {
+        ChecklistSync::Both => {
+            let done_on_card: Vec<ChecklistItem> =
+                card_items.into_iter().filter(|i| i.done).collect();
+            for item in &mut pr_items {
+                item.done |= done_on_card.iter().any(|c| c.text == item.text);
+            }
+            merge_checklist(body, &done_on_card, false)
+        }
+        _ => None,
+    };
+    (merge_checklist(description, &pr_items, true), body)
+}
+
+/// Fetch the assignee, label names, and label colors of a linked issue.
+pub fn fetch_issue_details(
+    provider: &dyn SyncProvider,
+    url: &str,
+    hosts: &ForgeHosts,
+) -> Result<IssueDetails> {
+    provider.issue_details(&parse_url(url, hosts)?)
+}
+
+/// Copy an issue's assignee and labels onto a card. Existing card labels are
+/// kept; missing ones are appended. Returns true if the card changed.
+pub fn apply_issue_details(card: &mut Card, details: &IssueDetails) -> bool {
+    let mut changed = false;
+
+    if let Some(ref assignee) = details.assignee {
+        changed |= card.set_assignee(Some(assignee.clone()));
+    }
+
+    for label in &details.labels {
+        changed |= card.add_label(label);
+    }
+
+    changed
+}
+
+// ─── Sprint boards ───────────────────────────────────────────
+
+/// Cards brought onto a sprint's own board from the default board, by ID,
+/// short ID or number.
+#[derive(Debug, Clone, Default)]
+pub struct SprintBoardCards {
+    /// Taken off the default board.
+    pub moved: Vec<String>,
+    /// Copied, leaving the original in place. A copy starts without the
+    /// original's links, comments and history.
+    pub copied: Vec<String>,
+}
+
+/// What [`start_sprint`] did.
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintStart {
+    pub sprint: Sprint,
+    /// The board created for the sprint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board: Option<String>,
+    /// Short IDs of the moved cards on the sprint board.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub moved: Vec<String>,
+    /// Short IDs of the copies on the sprint board.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub copied: Vec<String>,
+}
+
+/// The board created for a sprint: its name, with anything other than
+/// letters, digits, `-`, `_` and `.` replaced by `-`.
+pub fn sprint_board_name(sprint: &str) -> String {
+    sprint
+        .trim()
+        .chars()
+        .map(|c| match c {
+            c if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') => c,
+            _ => '-',
+        })
+        .collect()
+}
+
+/// Start a planned sprint. With `cards`, first create a board named after
+/// the sprint with the default board's columns, bring the cards over, and
+/// add the board to `Sprint.boards` so burndown and stats cover just it.
+pub fn start_sprint(
+    store: &Store,
+    name: &str,
+    cards: Option<&SprintBoardCards>,
+) -> Result<SprintStart> {
+    let mut sprints = load_sprints(store)?;
+    let sprint = sprints
+        .iter_mut()
+        .find(|s| s.name == name)
+        .ok_or_else(|| PmError::SprintNotFound(name.into()))?;
+    match sprint.status {
+        SprintStatus::Active => {
+            return Err(PmError::Other(format!("Sprint already active: {name}")));
+        }
+        SprintStatus::Closed => return Err(PmError::SprintAlreadyClosed(name.into())),
+        SprintStatus::Planned => {}
+    }
+
+    let mut started = SprintStart {
+        sprint: sprint.clone(),
+        board: None,
+        moved: Vec::new(),
+        copied: Vec::new(),
+    };
+    if let Some(cards) = cards {
+        let board_name = sprint_board_name(name);
+        let config = store.load_config()?;
+        let mut backlog = store.load_board(&config.default_board)?;
+        if board_name.is_empty() || board_name == backlog.name {
+            return Err(PmError::Other(format!(
+                "Cannot create a board named {board_name:?} for sprint {name}"
+            )));
+        }
+        let resolve = |ids: &[String]| {
+            ids.iter()
+                .map(|id| {
+                    backlog
+                        .resolve_card_id(id)
+                        .ok_or_else(|| PmError::CardNotFound(id.clone()))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        let moved = resolve(&cards.moved)?;
+        let copied = resolve(&cards.copied)?;
+        if let Some(id) = moved.iter().find(|id| copied.contains(id)) {
+            let title = backlog
+                .find_card(id)
+                .map(|c| c.title.as_str())
+                .unwrap_or(id);
+            return Err(PmError::Other(format!(
+                "Cannot both move and copy: {title}"
+            )));
+        }
+
+        store.create_board(&board_name, backlog.columns.clone())?;
+        let mut board = store.load_board(&board_name)?;
+        let mut place = |mut card: Card| {
+            card.order = board.next_order(&card.column);
+            board.add_card(card).short_id().unwrap_or_default()
+        };
+        for id in &moved {
+            let index = backlog
+                .cards
+                .iter()
+                .position(|c| c.id == *id)
+                .expect("resolved above");
+            let card = backlog.cards.remove(index);
+            backlog.normalize_column(&card.column);
+            started.moved.push(place(card));
+        }
+        for id in &copied {
+            let original = backlog.find_card(id).expect("resolved above");
+            let mut copy = Card::new(original.title.clone(), original.column.clone());
+            copy.description = original.description.clone();
+            copy.assignee = original.assignee.clone();
+            copy.labels = original.labels.clone();
+            copy.due = original.due;
+            copy.priority = original.priority;
+            started.copied.push(place(copy));
+        }
+        store.save_board(&board)?;
+        if !moved.is_empty() {
+            store.save_board(&backlog)?;
+        }
+
+        if !sprint.boards.contains(&board_name) {
+            sprint.boards.push(board_name.clone());
+        }
+        started.board = Some(board_name);
+    }
+
+    sprint.status = SprintStatus::Active;
+    started.sprint = sprint.clone();
+    save_sprints(store, &sprints)?;
+    Ok(started)
+}
+
+/// The cards a sprint's burndown counts: the active cards on its boards,
+/// or on every board when it lists none.
+pub fn sprint_cards(store: &Store, sprint: &Sprint) -> Result<Vec<Card>> {
+    if sprint.boards.is_empty() {
+        return Ok(store.active_cards()?);
     }
+    let query = CardQuery {
+        boards: sprint.boards.clone(),
+        ..CardQuery::default()
+    };
+    let cards = store
+        .iter_cards(query)?
+        .map(|item| item.map(|(_, card)| card))
+        .collect::<kuk::error::Result<_>>()?;
+    Ok(cards)
 }
 
-fn fetch_issue_state(url: &str) -> Result<String> {
-    let (owner, repo, number) =
-        parse_github_url(url).ok_or_else(|| PmError::Other(format!("invalid URL: {url}")))?;
+/// The board stats report on: the active sprint's boards, merged, when it
+/// lists any, otherwise the default board. Boards are loaded whole, with
+/// the card history time in column is measured from.
+pub fn stats_board(store: &Store) -> Result<Board> {
+    let active = load_sprints(store)?
+        .into_iter()
+        .find(|s| s.status == SprintStatus::Active && !s.boards.is_empty());
+    let Some(sprint) = active else {
+        let config = store.load_config()?;
+        return Ok(store.load_board(&config.default_board)?);
+    };
 
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{owner}/{repo}/issues/{number}"),
-            "--jq",
-            ".state",
-        ])
-        .output()
-        .map_err(|e| PmError::Other(format!("gh api failed: {e}")))?;
+    let mut board = store.load_board(&sprint.boards[0])?;
+    for name in &sprint.boards[1..] {
+        board.cards.extend(store.load_board(name)?.cards);
+    }
+    if sprint.boards.len() > 1 {
+        board.name = sprint.boards.join(" + ");
+    }
+    Ok(board)
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(PmError::Other(format!("gh api error: {stderr}")));
+// ─── Config helpers ──────────────────────────────────────────
+
+/// Load `.kuk/pm.json`, falling back to defaults if kuk-pm isn't initialized.
+pub fn load_pm_config(store: &Store) -> Result<PmConfig> {
+    let path = store.kuk_dir().join("pm.json");
+    if !path.exists() {
+        return Ok(PmConfig::default());
     }
+    let data = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&data)?)
+}
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+pub fn load_sprints(store: &Store) -> Result<Vec<Sprint>> {
+    let path = store.kuk_dir().join("sprints.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&data)?)
 }
 
-fn fetch_pr_state(url: &str) -> Result<String> {
-    let (owner, repo, number) =
-        parse_github_url(url).ok_or_else(|| PmError::Other(format!("invalid URL: {url}")))?;
+pub fn save_sprints(store: &Store, sprints: &[Sprint]) -> Result<()> {
+    let json = serde_json::to_string_pretty(sprints)?;
+    std::fs::write(store.kuk_dir().join("sprints.json"), json)?;
+    Ok(())
+}
 
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{owner}/{repo}/pulls/{number}"),
-            "--jq",
-            "if .merged then \"merged\" elif .state == \"closed\" then \"closed\" else .state end",
-        ])
-        .output()
-        .map_err(|e| PmError::Other(format!("gh api failed: {e}")))?;
+pub fn load_okrs(store: &Store) -> Result<Vec<Objective>> {
+    let path = store.kuk_dir().join("okrs.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+pub fn save_okrs(store: &Store, objectives: &[Objective]) -> Result<()> {
+    let json = serde_json::to_string_pretty(objectives)?;
+    std::fs::write(store.kuk_dir().join("okrs.json"), json)?;
+    Ok(())
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(PmError::Other(format!("gh api error: {stderr}")));
+pub fn load_team_calendar(store: &Store) -> Result<TeamCalendar> {
+    let path = store.kuk_dir().join("calendar.json");
+    if !path.exists() {
+        return Ok(TeamCalendar::default());
     }
+    let data = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+pub fn save_team_calendar(store: &Store, calendar: &TeamCalendar) -> Result<()> {
+    let json = serde_json::to_string_pretty(calendar)?;
+    std::fs::write(store.kuk_dir().join("calendar.json"), json)?;
+    Ok(())
+}
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+/// Everything the date-bucketed reports need: timezone and week start from
+/// pm.json plus the team calendar.
+pub fn load_calendar(store: &Store) -> Result<Calendar> {
+    Ok(load_pm_config(store)?.calendar(load_team_calendar(store)?))
 }
 
-// ─── PR creation ─────────────────────────────────────────────
+/// Resolve an issue/PR URL's kind, preferring the parsed forge layout and
+/// falling back to path heuristics for unrecognized hosts.
+pub fn link_kind(url: &str, hosts: &ForgeHosts) -> LinkKind {
+    ForgeUrl::parse(url, hosts)
+        .map(|f| f.kind)
+        .unwrap_or_else(|| LinkKind::from_url(url))
+}
+
+// ─── Commit trailers ─────────────────────────────────────────
 
-/// Create a GitHub PR from the current branch. Returns the PR URL.
-pub fn create_pr(repo: &Path, title: &str, body: &str) -> Result<String> {
-    if !is_gh_available() {
-        return Err(PmError::Other(
-            "GitHub CLI (gh) not found. Install it from https://cli.github.com/".into(),
-        ));
+/// A commit newly recorded on a card by [`backfill_links`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfilledLink {
+    pub card_id: String,
+    pub card_title: String,
+    pub sha: String,
+    pub subject: String,
+}
+
+/// The IDs of the cards a commit message's trailers point at: `Kuk-Card`
+/// names a card, `Closes`/`Fixes`/`Resolves` an issue linked to one.
+/// References that match no card are dropped.
+pub fn cards_named_in(board: &Board, message: &str, hosts: &ForgeHosts) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for card_ref in git::card_refs(message) {
+        let found: Vec<String> = match card_ref {
+            CardRef::Card(id) => board.resolve_card_id(&id).into_iter().collect(),
+            CardRef::Issue(number) => board
+                .cards
+                .iter()
+                .filter(|c| {
+                    get_pm_metadata(c)
+                        .issues()
+                        .any(|l| ForgeUrl::parse(&l.url, hosts).is_some_and(|f| f.number == number))
+                })
+                .map(|c| c.id.clone())
+                .collect(),
+            CardRef::Url(url) => {
+                let url = url.trim_end_matches('/');
+                board
+                    .cards
+                    .iter()
+                    .filter(|c| {
+                        get_pm_metadata(c)
+                            .links
+                            .iter()
+                            .any(|l| l.url.trim_end_matches('/') == url)
+                    })
+                    .map(|c| c.id.clone())
+                    .collect()
+            }
+        };
+        for id in found {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
     }
+    ids
+}
 
-    let output = Command::new("gh")
-        .args(["pr", "create", "--title", title, "--body", body])
-        .current_dir(repo)
-        .output()
-        .map_err(|e| PmError::Other(format!("gh pr create failed: {e}")))?;
+/// Whether a card's metadata records `sha`, in full or abbreviated.
+fn records_commit(meta: &GitMetadata, sha: &str) -> bool {
+    meta.commits
+        .iter()
+        .any(|c| !c.is_empty() && sha.starts_with(c.as_str()))
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(PmError::Other(format!("gh pr create error: {stderr}")));
+/// Cards tied to any of `commits`, in the order first seen: those named in
+/// a commit's trailers and those whose metadata records the commit.
+pub fn cards_for_commits<'a>(
+    board: &'a Board,
+    commits: &[CommitInfo],
+    hosts: &ForgeHosts,
+) -> Vec<&'a Card> {
+    let mut ids: Vec<String> = Vec::new();
+    for commit in commits {
+        let recorded = board
+            .cards
+            .iter()
+            .filter(|c| records_commit(&get_pm_metadata(c), &commit.sha))
+            .map(|c| c.id.clone());
+        for id in cards_named_in(board, &commit.message, hosts)
+            .into_iter()
+            .chain(recorded)
+        {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
     }
+    ids.iter().filter_map(|id| board.find_card(id)).collect()
+}
 
-    let pr_url = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    Ok(pr_url)
+/// The cards on the default board that `commits` belong to, for release
+/// notes. Empty when kuk isn't initialized.
+pub fn release_cards(repo: &Path, commits: &[CommitInfo]) -> Result<Vec<ReleaseCard>> {
+    let store = Store::new(repo);
+    if !store.is_initialized() {
+        return Ok(Vec::new());
+    }
+    let pm_config = load_pm_config(&store)?;
+    let config = store.load_config()?;
+    let board = store.load_board(&config.default_board)?;
+    Ok(cards_for_commits(&board, commits, &pm_config.forge_hosts)
+        .into_iter()
+        .map(|c| ReleaseCard {
+            id: c.id.clone(),
+            title: c.title.clone(),
+            column: c.column.clone(),
+        })
+        .collect())
+}
+
+/// Record every commit whose trailers name a card in that card's
+/// `metadata.pm.commits`, for history made outside kuk-pm. Commits a card
+/// already records are skipped; returns what was added, oldest first.
+pub fn backfill_links(
+    board: &mut Board,
+    commits: &[CommitInfo],
+    hosts: &ForgeHosts,
+) -> Vec<BackfilledLink> {
+    let mut added = Vec::new();
+    for commit in commits.iter().rev() {
+        for card_id in cards_named_in(board, &commit.message, hosts) {
+            let Some(card) = board.find_card_mut(&card_id) else {
+                continue;
+            };
+            let mut meta = get_pm_metadata(card);
+            if records_commit(&meta, &commit.sha) {
+                continue;
+            }
+            meta.commits.push(commit.sha.clone());
+            set_pm_metadata(card, &meta);
+            added.push(BackfilledLink {
+                card_id,
+                card_title: card.title.clone(),
+                sha: commit.sha.clone(),
+                subject: commit.message.lines().next().unwrap_or_default().into(),
+            });
+        }
+    }
+    added
 }
 
 // ─── Card metadata helpers ───────────────────────────────────
@@ -277,50 +1149,254 @@ pub fn set_pm_metadata(card: &mut Card, meta: &GitMetadata) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::NaiveDate;
+    use std::time::Duration;
 
     #[test]
-    fn parse_github_issue_url() {
-        let (owner, repo, number) =
-            parse_github_url("https://github.com/user/myrepo/issues/42").unwrap();
-        assert_eq!(owner, "user");
-        assert_eq!(repo, "myrepo");
-        assert_eq!(number, "42");
+    fn parse_url_reports_unknown_host() {
+        let err = parse_url("https://git.corp.example/a/b/issues/1", &ForgeHosts::new())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("forge_hosts"));
     }
 
     #[test]
-    fn parse_github_pr_url() {
-        let (owner, repo, number) =
-            parse_github_url("https://github.com/org/project/pull/7").unwrap();
-        assert_eq!(owner, "org");
-        assert_eq!(repo, "project");
-        assert_eq!(number, "7");
+    fn link_kind_prefers_parsed_layout() {
+        let hosts = ForgeHosts::new();
+        assert_eq!(
+            link_kind("https://bitbucket.org/t/r/pull-requests/1", &hosts),
+            LinkKind::Pr
+        );
+        assert_eq!(
+            link_kind("https://gitlab.com/g/p/-/issues/1", &hosts),
+            LinkKind::Issue
+        );
+        // Unrecognized host falls back to path heuristics
+        assert_eq!(
+            link_kind("https://example.com/x/y/pull/1", &hosts),
+            LinkKind::Pr
+        );
     }
 
     #[test]
-    fn parse_invalid_url() {
-        assert!(parse_github_url("not-a-url").is_none());
-        assert!(parse_github_url("https://github.com/user").is_none());
+    fn pm_metadata_roundtrip_on_card() {
+        let mut card = Card::new("Test", "todo");
+        let mut meta = GitMetadata {
+            branch: Some("feature/test".into()),
+            ..Default::default()
+        };
+        meta.add_link(
+            "https://github.com/u/r/issues/1",
+            LinkKind::Issue,
+            LinkRole::Implements,
+        );
+        set_pm_metadata(&mut card, &meta);
+
+        let loaded = get_pm_metadata(&card);
+        assert_eq!(loaded.branch.as_deref(), Some("feature/test"));
+        assert_eq!(loaded.issues().count(), 1);
     }
 
     #[test]
-    fn parse_trailing_slash() {
-        let (_, _, number) = parse_github_url("https://github.com/user/repo/issues/99/").unwrap();
-        assert_eq!(number, "99");
+    fn issue_details_from_gh_output() {
+        let details: IssueDetails =
+            serde_json::from_str(r#"{"assignee":"octocat","labels":["bug","p1"]}"#).unwrap();
+        assert_eq!(details.assignee.as_deref(), Some("octocat"));
+        assert_eq!(details.labels, vec!["bug", "p1"]);
+
+        let unassigned: IssueDetails =
+            serde_json::from_str(r#"{"assignee":null,"labels":[]}"#).unwrap();
+        assert!(unassigned.assignee.is_none());
     }
 
     #[test]
-    fn pm_metadata_roundtrip_on_card() {
+    fn apply_issue_details_merges_labels() {
         let mut card = Card::new("Test", "todo");
-        let meta = GitMetadata {
-            branch: Some("feature/test".into()),
-            issue_url: Some("https://github.com/u/r/issues/1".into()),
+        card.labels = vec!["bug".into()];
+        let details = IssueDetails {
+            assignee: Some("octocat".into()),
+            labels: vec!["bug".into(), "backend".into()],
             ..Default::default()
         };
-        set_pm_metadata(&mut card, &meta);
 
-        let loaded = get_pm_metadata(&card);
-        assert_eq!(loaded.branch.unwrap(), "feature/test");
-        assert!(loaded.issue_url.is_some());
+        assert!(apply_issue_details(&mut card, &details));
+        assert_eq!(card.assignee.as_deref(), Some("octocat"));
+        assert_eq!(card.labels, vec!["bug", "backend"]);
+
+        // Applying again is a no-op
+        assert!(!apply_issue_details(&mut card, &details));
+    }
+
+    #[test]
+    fn apply_label_colors_fills_gaps_only() {
+        let mut board = Board::default_board();
+        let mut card = Card::new("Test", "todo");
+        card.labels = vec!["bug".into(), "ops".into()];
+        board.cards.push(card);
+        let mut custom = LabelDef::new("ops");
+        custom.color = Some("#000000".into());
+        board.labels.push(custom);
+        board.labels.push(LabelDef::new("docs"));
+
+        let colors: BTreeMap<String, String> = [
+            ("bug", "d73a4a"),
+            ("ops", "ffffff"),
+            ("docs", "#0075CA"),
+            ("unused", "cccccc"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.into(), v.into()))
+        .collect();
+
+        let changed = apply_label_colors(&mut board, &colors);
+        assert_eq!(
+            changed,
+            vec![
+                ("bug".to_string(), "#d73a4a".to_string()),
+                ("docs".to_string(), "#0075ca".to_string()),
+            ]
+        );
+        assert_eq!(
+            board.label_def("ops").unwrap().color.as_deref(),
+            Some("#000000")
+        );
+        assert!(board.label_def("unused").is_none());
+        assert!(apply_label_colors(&mut board, &colors).is_empty());
+    }
+
+    #[test]
+    fn apply_issue_details_keeps_assignee_when_issue_unassigned() {
+        let mut card = Card::new("Test", "todo");
+        card.assignee = Some("leslie".into());
+        let details = IssueDetails::default();
+
+        assert!(!apply_issue_details(&mut card, &details));
+        assert_eq!(card.assignee.as_deref(), Some("leslie"));
+    }
+
+    #[test]
+    fn pm_metadata_migrates_legacy_card_metadata() {
+        let mut card = Card::new("Legacy", "todo");
+        card.metadata.insert(
+            "pm".into(),
+            serde_json::json!({"pr_url": "https://github.com/u/r/pull/5", "commits": []}),
+        );
+        let meta = get_pm_metadata(&card);
+        assert_eq!(meta.prs().count(), 1);
+        assert_eq!(meta.links[0].url, "https://github.com/u/r/pull/5");
+    }
+
+    #[test]
+    fn sync_scope_filters_by_label_and_column() {
+        let mut card = Card::new("Test", "doing");
+        card.labels = vec!["bug".into()];
+
+        assert!(SyncScope::default().includes(&card));
+
+        let by_label = SyncScope {
+            labels: vec!["bug".into(), "ops".into()],
+            ..Default::default()
+        };
+        assert!(by_label.includes(&card));
+
+        let by_column = SyncScope {
+            columns: vec!["review".into()],
+            ..Default::default()
+        };
+        assert!(!by_column.includes(&card));
+
+        let both = SyncScope {
+            labels: vec!["feature".into()],
+            columns: vec!["doing".into()],
+            ..Default::default()
+        };
+        assert!(!both.includes(&card));
+    }
+
+    #[test]
+    fn sync_log_appends_and_limits() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+        assert!(load_sync_log(&store, None).unwrap().is_empty());
+
+        for i in 0..3 {
+            let entry = SyncLogEntry {
+                timestamp: Utc::now(),
+                dry_run: i == 0,
+                actions: vec![SyncAction {
+                    card_title: format!("Card {i}"),
+                    card_id: format!("id{i}"),
+                    action: SyncActionType::Skip,
+                    detail: "test".into(),
+                }],
+                retried: Vec::new(),
+            };
+            append_sync_log(&store, &entry).unwrap();
+        }
+
+        let all = load_sync_log(&store, None).unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(all[0].dry_run);
+
+        let last = load_sync_log(&store, Some(2)).unwrap();
+        assert_eq!(last.len(), 2);
+        assert_eq!(last[1].actions[0].card_title, "Card 2");
+    }
+
+    #[test]
+    fn move_comment_lists_columns_since_last_sync() {
+        let at = |h: u32| {
+            NaiveDate::from_ymd_opt(2026, 3, 2)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let step = |h, from: &str, to: &str| Transition {
+            at: at(h),
+            from: from.into(),
+            to: to.into(),
+            origin: None,
+        };
+        let history = [
+            step(9, "todo", "doing"),
+            step(11, "doing", "review"),
+            step(12, "review", "doing"),
+        ];
+        assert_eq!(
+            move_comment(&history, at(10)).as_deref(),
+            Some("Moved on the board: doing → review → doing")
+        );
+        assert_eq!(move_comment(&history, at(12)), None);
+    }
+
+    #[test]
+    fn checklists_reconcile_both_ways() {
+        let card = "Plan:\n- [ ] Schema\n- [x] Docs";
+        let pr = "- [x] Schema\n- [ ] Docs\n- [ ] Migration";
+
+        let (description, body) = reconcile_checklists(card, pr, ChecklistSync::Pull);
+        assert_eq!(
+            description.as_deref(),
+            Some("Plan:\n- [x] Schema\n- [ ] Docs\n\n- [ ] Migration")
+        );
+        assert!(body.is_none());
+
+        let (description, body) = reconcile_checklists(card, pr, ChecklistSync::Both);
+        assert_eq!(
+            description.as_deref(),
+            Some("Plan:\n- [x] Schema\n- [x] Docs\n\n- [ ] Migration")
+        );
+        assert_eq!(
+            body.as_deref(),
+            Some("- [x] Schema\n- [x] Docs\n- [ ] Migration")
+        );
+
+        assert_eq!(
+            reconcile_checklists(card, "No tasks here", ChecklistSync::Both),
+            (None, None)
+        );
     }
 
     #[test]
@@ -328,7 +1404,274 @@ mod tests {
         let card = Card::new("Clean", "todo");
         let meta = get_pm_metadata(&card);
         assert!(meta.branch.is_none());
-        assert!(meta.issue_url.is_none());
-        assert!(meta.pr_url.is_none());
+        assert!(meta.links.is_empty());
+    }
+
+    #[test]
+    fn trailers_link_commits_to_cards() {
+        let mut board = Board::default_board();
+        let named = Card::new("Named", "todo");
+        let mut linked = Card::new("Linked", "doing");
+        let mut meta = GitMetadata::default();
+        meta.add_link(
+            "https://github.com/o/r/issues/17",
+            LinkKind::Issue,
+            LinkRole::Fixes,
+        );
+        set_pm_metadata(&mut linked, &meta);
+        let (named_id, linked_id) = (named.id.clone(), linked.id.clone());
+        board.cards.extend([named, linked]);
+
+        let commit = |sha: &str, message: &str| CommitInfo {
+            sha: sha.into(),
+            message: message.into(),
+            author: "Ana".into(),
+            email: "ana@example.com".into(),
+            time: 0,
+        };
+        let commits = [
+            commit("bbb", "Fix redirect\n\nFixes: #17"),
+            commit(
+                "aaa",
+                &format!("Add page\n\nKuk-Card: {named_id}\nCloses: #99"),
+            ),
+        ];
+        let hosts = ForgeHosts::new();
+
+        let added = backfill_links(&mut board, &commits, &hosts);
+        let pairs: Vec<(&str, &str)> = added
+            .iter()
+            .map(|l| (l.card_id.as_str(), l.sha.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [(named_id.as_str(), "aaa"), (linked_id.as_str(), "bbb")]
+        );
+        assert!(backfill_links(&mut board, &commits, &hosts).is_empty());
+
+        let released: Vec<&str> = cards_for_commits(&board, &commits[..1], &hosts)
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(released, ["Linked"]);
+    }
+
+    /// An initialized repo whose default board holds `cards`, each a title,
+    /// column and the issue/PR URLs it links to.
+    fn repo_with(cards: &[(&str, &str, &[&str])]) -> (tempfile::TempDir, Store) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+        let mut board = store.load_board("default").unwrap();
+        for &(title, column, urls) in cards {
+            let mut card = Card::new(title, column);
+            let mut meta = GitMetadata::default();
+            for url in urls {
+                meta.add_link(url, LinkKind::from_url(url), LinkRole::Implements);
+            }
+            set_pm_metadata(&mut card, &meta);
+            board.cards.push(card);
+        }
+        store.save_board(&board).unwrap();
+        (dir, store)
+    }
+
+    fn no_wait() -> RetryPolicy {
+        RetryPolicy {
+            backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        }
+    }
+
+    fn column_of(store: &Store, title: &str) -> String {
+        let board = store.load_board("default").unwrap();
+        let card = board.cards.iter().find(|c| c.title == title).unwrap();
+        card.column.clone()
+    }
+
+    #[test]
+    fn sync_finishes_cards_once_every_link_is_closed_or_merged() {
+        let gh = "https://github.com/o/r";
+        let (dir, store) = repo_with(&[
+            (
+                "Login",
+                "doing",
+                &[&format!("{gh}/issues/1"), &format!("{gh}/pull/2")],
+            ),
+            (
+                "Search",
+                "doing",
+                &[&format!("{gh}/issues/3"), &format!("{gh}/pull/4")],
+            ),
+            ("Export", "todo", &[&format!("{gh}/issues/5")]),
+            ("Abandoned", "review", &[&format!("{gh}/pull/6")]),
+        ]);
+        let forge = MockProvider::new()
+            .with_state(&format!("{gh}/issues/1"), "closed")
+            .with_state(&format!("{gh}/pull/2"), "merged")
+            .with_state(&format!("{gh}/issues/3"), "closed")
+            .with_state(&format!("{gh}/pull/6"), "closed")
+            .with_error(&format!("{gh}/issues/5"), "gh api error: HTTP 502");
+
+        let actions = sync_board(
+            &forge,
+            dir.path(),
+            &SyncScope::default(),
+            false,
+            true,
+            &no_wait(),
+        )
+        .unwrap()
+        .unwrap()
+        .actions;
+        let lines: Vec<String> = actions.iter().map(render_action).collect();
+        assert_eq!(
+            lines,
+            [
+                "  [SYNC] Login — doing → done (issue closed, PR merged)",
+                "  [SKIP] Export — failed to fetch issue: gh api error: HTTP 502",
+                "  [SYNC] Abandoned — review → done (PR closed)",
+            ]
+        );
+        assert_eq!(column_of(&store, "Login"), "doing");
+
+        sync_board(
+            &forge,
+            dir.path(),
+            &SyncScope::default(),
+            false,
+            false,
+            &no_wait(),
+        )
+        .unwrap();
+        let columns: Vec<String> = ["Login", "Search", "Export", "Abandoned"]
+            .iter()
+            .map(|t| column_of(&store, t))
+            .collect();
+        assert_eq!(columns, ["done", "doing", "todo", "done"]);
+        assert!(forge.log().is_empty());
+        assert_eq!(load_sync_log(&store, None).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn sync_retries_a_flaky_forge_and_logs_it() {
+        let issue = "https://github.com/o/r/issues/1";
+        let (dir, store) = repo_with(&[("Login", "doing", &[issue])]);
+        let forge = MockProvider::new().with_state(issue, "closed").with_flaky(
+            issue,
+            "gh: Bad Gateway (HTTP 502)",
+            1,
+        );
+
+        let run = sync_board(
+            &forge,
+            dir.path(),
+            &SyncScope::default(),
+            false,
+            false,
+            &no_wait(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(run.actions.len(), 1);
+        assert_eq!(run.retried, [format!("state of {issue}: ok after 2 tries")]);
+        assert_eq!(column_of(&store, "Login"), "done");
+        assert_eq!(load_sync_log(&store, None).unwrap()[0].retried, run.retried);
+    }
+
+    #[test]
+    fn sync_writes_to_the_forge_through_the_provider() {
+        let (issue, pr) = (
+            "https://github.com/o/r/issues/1",
+            "https://github.com/o/r/pull/2",
+        );
+        let (dir, store) =
+            repo_with(&[("Schema", "doing", &[issue, pr]), ("Unlinked", "todo", &[])]);
+        let mut board = store.load_board("default").unwrap();
+        board.cards[0].description = Some("- [x] Docs".into());
+        store.save_board(&board).unwrap();
+        let config = PmConfig {
+            checklist_sync: ChecklistSync::Both,
+            ..Default::default()
+        };
+        std::fs::write(
+            store.kuk_dir().join("pm.json"),
+            serde_json::to_string(&config).unwrap(),
+        )
+        .unwrap();
+
+        let forge = MockProvider::new().with_pr_body(pr, "- [x] Migrate\n- [ ] Docs");
+        let actions = sync_board(
+            &forge,
+            dir.path(),
+            &SyncScope::default(),
+            true,
+            false,
+            &no_wait(),
+        )
+        .unwrap()
+        .unwrap()
+        .actions;
+        let details: Vec<&str> = actions.iter().map(|a| a.detail.as_str()).collect();
+        assert_eq!(
+            details,
+            [
+                "card checklist 2/2 done, updated PR #2 task list",
+                "created issue https://github.com/mock/repo/issues/1",
+            ]
+        );
+        assert_eq!(
+            forge.log(),
+            [
+                format!("update {pr}: - [x] Migrate\n- [x] Docs"),
+                "create issue https://github.com/mock/repo/issues/1: Unlinked".into(),
+            ]
+        );
+        let board = store.load_board("default").unwrap();
+        assert_eq!(get_pm_metadata(&board.cards[1]).issues().count(), 1);
+
+        let (dir, _) = repo_with(&[("Offline", "todo", &[])]);
+        let down = MockProvider::new().with_error(CREATE_ISSUE, "gh issue create error: HTTP 401");
+        let actions = sync_board(
+            &down,
+            dir.path(),
+            &SyncScope::default(),
+            true,
+            false,
+            &no_wait(),
+        )
+        .unwrap()
+        .unwrap()
+        .actions;
+        assert_eq!(
+            actions[0].detail,
+            "failed to create issue: gh issue create error: HTTP 401"
+        );
+        assert!(down.log().is_empty());
+    }
+
+    #[test]
+    fn link_details_and_comments_go_through_the_provider() {
+        let issue = "https://github.com/o/r/issues/7";
+        let forge = MockProvider::new().with_issue(
+            issue,
+            IssueDetails {
+                assignee: Some("octocat".into()),
+                labels: vec!["bug".into()],
+                ..Default::default()
+            },
+        );
+        let hosts = ForgeHosts::new();
+        let details = fetch_issue_details(&forge, issue, &hosts).unwrap();
+        assert_eq!(details.assignee.as_deref(), Some("octocat"));
+
+        let posted = post_comment(&forge, issue, "Moved on the board: todo → doing", &hosts);
+        assert!(posted.unwrap().is_some());
+        assert_eq!(
+            forge.log(),
+            [format!("comment {issue}: Moved on the board: todo → doing")]
+        );
+        let err = post_comment(&forge, "https://git.corp.example/a/b/issues/1", "x", &hosts);
+        assert!(err.unwrap_err().to_string().contains("forge_hosts"));
     }
 }
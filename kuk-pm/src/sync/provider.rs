@@ -0,0 +1,918 @@
+//! Everything kuk-pm asks of a forge goes through a [`SyncProvider`]. The
+//! real one, [`GhCli`], runs `gh` and `glab`; [`MockProvider`] answers from
+//! canned responses so sync, link and pr can be tested without either.
+//!
+//! Set `KUK_PM_SYNC_FIXTURE` to a JSON fixture (a serialized
+//! [`MockProvider`]) to have `kuk-pm` replay it instead of calling out, or
+//! `KUK_PM_SYNC_RECORD` to a path to record what the real forge answers
+//! into one.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::IssueDetails;
+use crate::error::{PmError, Result};
+use crate::model::{ForgeUrl, LinkKind, Milestone, Provider};
+
+/// Environment variable naming a fixture to replay instead of the forge.
+pub const FIXTURE_ENV: &str = "KUK_PM_SYNC_FIXTURE";
+
+/// Environment variable naming a fixture to record forge responses into.
+pub const RECORD_ENV: &str = "KUK_PM_SYNC_RECORD";
+
+/// The forge calls sync, link, comment and pr make. Items are addressed by
+/// parsed URL; issue, PR and milestone creation act on the repo's own
+/// GitHub remote.
+pub trait SyncProvider {
+    /// An issue's state, "open" or "closed", or a PR's: "open", "closed",
+    /// or "merged".
+    fn state(&self, forge: &ForgeUrl) -> Result<String>;
+
+    /// A PR's description.
+    fn pr_body(&self, forge: &ForgeUrl) -> Result<String>;
+
+    /// Replace a PR's description.
+    fn update_pr_body(&self, forge: &ForgeUrl, body: &str) -> Result<()>;
+
+    /// Post a comment on an issue or PR. Returns the comment's web address
+    /// when the forge reports one.
+    fn post_comment(&self, forge: &ForgeUrl, body: &str) -> Result<Option<String>>;
+
+    /// The assignee, label names, and label colors of an issue.
+    fn issue_details(&self, forge: &ForgeUrl) -> Result<IssueDetails>;
+
+    /// The label colors of the URL's project, keyed by name.
+    fn label_colors(&self, forge: &ForgeUrl) -> Result<BTreeMap<String, String>>;
+
+    /// Create an issue. Returns its URL.
+    fn create_issue(&self, repo: &Path, title: &str, body: &str) -> Result<String>;
+
+    /// Create a PR from the current branch. Returns its URL.
+    fn create_pr(&self, repo: &Path, title: &str, body: &str) -> Result<String>;
+
+    fn milestone(&self, repo: &Path, number: u64) -> Result<Milestone>;
+
+    /// Create a milestone due on the given date.
+    fn create_milestone(&self, repo: &Path, title: &str, due: NaiveDate) -> Result<Milestone>;
+}
+
+/// The provider `kuk-pm` uses: a replayed fixture with
+/// `KUK_PM_SYNC_FIXTURE`, the real forge recorded into one with
+/// `KUK_PM_SYNC_RECORD`, and otherwise just the real forge.
+pub fn provider() -> Result<Box<dyn SyncProvider>> {
+    if let Some(path) = std::env::var_os(FIXTURE_ENV) {
+        return Ok(Box::new(MockProvider::load(Path::new(&path))?));
+    }
+    if let Some(path) = std::env::var_os(RECORD_ENV) {
+        return Ok(Box::new(Recorder::new(GhCli, path.into())));
+    }
+    Ok(Box::new(GhCli))
+}
+
+// ─── gh / glab ───────────────────────────────────────────────
+
+/// The real forge, through the GitHub CLI (`gh`) and GitLab CLI (`glab`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GhCli;
+
+impl SyncProvider for GhCli {
+    fn state(&self, forge: &ForgeUrl) -> Result<String> {
+        if forge.provider == Provider::GitLab {
+            let item = fetch_gitlab_item(forge)?;
+            return Ok(normalize_gitlab_state(&item.state));
+        }
+        let out = match forge.kind {
+            LinkKind::Issue => {
+                let endpoint = format!("repos/{}/issues/{}", forge.project, forge.number);
+                forge_api(forge, &endpoint, Some(".state"))?
+            }
+            LinkKind::Pr => {
+                let endpoint = format!("repos/{}/pulls/{}", forge.project, forge.number);
+                forge_api(
+                    forge,
+                    &endpoint,
+                    Some(
+                        "if .merged then \"merged\" elif .state == \"closed\" then \"closed\" else .state end",
+                    ),
+                )?
+            }
+        };
+        Ok(String::from_utf8_lossy(&out).trim().to_string())
+    }
+
+    fn pr_body(&self, forge: &ForgeUrl) -> Result<String> {
+        let (endpoint, jq) = match forge.provider {
+            Provider::GitLab => (
+                format!(
+                    "projects/{}/merge_requests/{}",
+                    gitlab_project(forge),
+                    forge.number
+                ),
+                ".description // \"\"",
+            ),
+            _ => (
+                format!("repos/{}/pulls/{}", forge.project, forge.number),
+                ".body // \"\"",
+            ),
+        };
+        let out = forge_api(forge, &endpoint, Some(jq))?;
+        Ok(String::from_utf8_lossy(&out).trim_end().to_string())
+    }
+
+    fn update_pr_body(&self, forge: &ForgeUrl, body: &str) -> Result<()> {
+        match forge.provider {
+            Provider::GitLab => {
+                let endpoint = format!(
+                    "projects/{}/merge_requests/{}",
+                    gitlab_project(forge),
+                    forge.number
+                );
+                forge_api_call(forge, "PUT", &endpoint, &[("description", body)], None)?;
+            }
+            _ => {
+                let endpoint = format!("repos/{}/pulls/{}", forge.project, forge.number);
+                forge_api_call(forge, "PATCH", &endpoint, &[("body", body)], None)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn post_comment(&self, forge: &ForgeUrl, body: &str) -> Result<Option<String>> {
+        let out = match forge.provider {
+            Provider::GitLab => {
+                let collection = match forge.kind {
+                    LinkKind::Issue => "issues",
+                    LinkKind::Pr => "merge_requests",
+                };
+                let endpoint = format!(
+                    "projects/{}/{collection}/{}/notes",
+                    gitlab_project(forge),
+                    forge.number
+                );
+                forge_api_call(forge, "POST", &endpoint, &[("body", body)], Some(".id"))?;
+                return Ok(None);
+            }
+            // GitHub takes PR conversation comments on the issues endpoint too
+            _ => {
+                let endpoint = format!("repos/{}/issues/{}/comments", forge.project, forge.number);
+                forge_api_call(
+                    forge,
+                    "POST",
+                    &endpoint,
+                    &[("body", body)],
+                    Some(".html_url"),
+                )?
+            }
+        };
+        let comment_url = String::from_utf8_lossy(&out).trim().to_string();
+        Ok((!comment_url.is_empty()).then_some(comment_url))
+    }
+
+    fn issue_details(&self, forge: &ForgeUrl) -> Result<IssueDetails> {
+        match forge.provider {
+            Provider::GitLab => {
+                let item = fetch_gitlab_item(forge)?;
+                // GitLab only returns label names here; colors come from sync
+                Ok(IssueDetails {
+                    assignee: item.assignees.into_iter().next().map(|u| u.username),
+                    labels: item.labels,
+                    label_colors: BTreeMap::new(),
+                })
+            }
+            _ => {
+                let endpoint = format!("repos/{}/issues/{}", forge.project, forge.number);
+                let out = forge_api(
+                    forge,
+                    &endpoint,
+                    Some(
+                        "{assignee: .assignee.login, labels: [.labels[].name], label_colors: (.labels | map({(.name): .color}) | add // {})}",
+                    ),
+                )?;
+                Ok(serde_json::from_slice(&out)?)
+            }
+        }
+    }
+
+    /// GitHub reports bare hex (`d73a4a`), GitLab `#d73a4a`.
+    fn label_colors(&self, forge: &ForgeUrl) -> Result<BTreeMap<String, String>> {
+        let endpoint = match forge.provider {
+            Provider::GitLab => format!("projects/{}/labels?per_page=100", gitlab_project(forge)),
+            _ => format!("repos/{}/labels?per_page=100", forge.project),
+        };
+        let labels: Vec<ForgeLabel> = serde_json::from_slice(&forge_api(forge, &endpoint, None)?)?;
+        Ok(labels.into_iter().map(|l| (l.name, l.color)).collect())
+    }
+
+    #[tracing::instrument(level = "info", skip_all, err(level = "info"))]
+    fn create_issue(&self, repo: &Path, title: &str, body: &str) -> Result<String> {
+        gh(
+            repo,
+            &["issue", "create", "--title", title, "--body", body],
+            "gh issue create",
+        )
+    }
+
+    #[tracing::instrument(level = "info", skip_all, err(level = "info"))]
+    fn create_pr(&self, repo: &Path, title: &str, body: &str) -> Result<String> {
+        gh(
+            repo,
+            &["pr", "create", "--title", title, "--body", body],
+            "gh pr create",
+        )
+    }
+
+    fn milestone(&self, repo: &Path, number: u64) -> Result<Milestone> {
+        gh_milestone_api(
+            repo,
+            &[&format!("repos/{{owner}}/{{repo}}/milestones/{number}")],
+        )
+    }
+
+    fn create_milestone(&self, repo: &Path, title: &str, due: NaiveDate) -> Result<Milestone> {
+        gh_milestone_api(
+            repo,
+            &[
+                "repos/{owner}/{repo}/milestones",
+                "-f",
+                &format!("title={title}"),
+                "-f",
+                &format!("due_on={due}T00:00:00Z"),
+            ],
+        )
+    }
+}
+
+fn is_gh_available() -> bool {
+    Command::new("gh")
+        .arg("--version")
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+/// Run `gh` in the repo and return its trimmed stdout. `what` names the
+/// command in errors.
+fn gh(repo: &Path, args: &[&str], what: &str) -> Result<String> {
+    if !is_gh_available() {
+        return Err(PmError::Other(
+            "GitHub CLI (gh) not found. Install it from https://cli.github.com/".into(),
+        ));
+    }
+
+    let output = Command::new("gh")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .map_err(|e| PmError::Other(format!("{what} failed: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = format!("{what} error: {}", stderr.trim_end());
+        return Err(forge_error(message, "", Utc::now()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Run `gh api` or `glab api` against the URL's host and return stdout.
+#[tracing::instrument(level = "info", skip(forge, jq), fields(host = %forge.host), err(level = "info"))]
+fn forge_api(forge: &ForgeUrl, endpoint: &str, jq: Option<&str>) -> Result<Vec<u8>> {
+    forge_api_call(forge, "GET", endpoint, &[], jq)
+}
+
+/// Like `forge_api`, with an HTTP method and string fields for the body.
+fn forge_api_call(
+    forge: &ForgeUrl,
+    method: &str,
+    endpoint: &str,
+    fields: &[(&str, &str)],
+    jq: Option<&str>,
+) -> Result<Vec<u8>> {
+    let program = match forge.provider {
+        Provider::GitHub => "gh",
+        Provider::GitLab => "glab",
+        Provider::Bitbucket => {
+            return Err(PmError::Other(format!(
+                "{} API access is not supported yet",
+                forge.provider
+            )));
+        }
+    };
+
+    let mut cmd = Command::new(program);
+    cmd.args(["api", "--hostname", &forge.host, endpoint]);
+    if method != "GET" {
+        cmd.args(["-X", method]);
+    }
+    for (name, value) in fields {
+        cmd.arg("-f").arg(format!("{name}={value}"));
+    }
+    if let Some(jq) = jq {
+        cmd.args(["--jq", jq]);
+    }
+    // The status line and headers tell rate limits from other failures
+    cmd.arg("--include");
+    let output = cmd.output().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => PmError::Other(format!(
+            "{program} CLI not found; it is needed for {} links",
+            forge.provider
+        )),
+        _ => PmError::Other(format!("{program} api failed: {e}")),
+    })?;
+
+    let (head, body) = split_response(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = format!("{program} api error: {}", stderr.trim_end());
+        return Err(forge_error(message, &head, Utc::now()));
+    }
+
+    Ok(body.to_vec())
+}
+
+/// Split `--include` output into the status line and headers, and the
+/// body. Output without a status line is all body.
+fn split_response(out: &[u8]) -> (String, &[u8]) {
+    if !out.starts_with(b"HTTP/") {
+        return (String::new(), out);
+    }
+    for end in [&b"\r\n\r\n"[..], b"\n\n"] {
+        if let Some(i) = out.windows(end.len()).position(|w| w == end) {
+            let head = String::from_utf8_lossy(&out[..i]).into_owned();
+            return (head, &out[i + end.len()..]);
+        }
+    }
+    (String::from_utf8_lossy(out).into_owned(), &[])
+}
+
+/// The error for a failed forge call. Its status comes from the response
+/// `head` (status line and headers) or else the message, e.g. `gh: Not
+/// Found (HTTP 404)`; the wait from `Retry-After`, or from the reset time
+/// of a used-up GitHub or GitLab rate limit.
+pub(super) fn forge_error(message: String, head: &str, now: DateTime<Utc>) -> PmError {
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse().ok())
+        .or_else(|| {
+            message
+                .match_indices("HTTP ")
+                .find_map(|(i, m)| message.get(i + m.len()..i + m.len() + 3)?.parse().ok())
+        });
+    let headers: BTreeMap<String, &str> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim()))
+        .collect();
+    let header = |name: &str| headers.get(name).copied();
+    let retry_after = header("retry-after")
+        .and_then(|s| s.parse().ok())
+        .map(Duration::from_secs)
+        .or_else(|| {
+            let remaining = header("x-ratelimit-remaining").or(header("ratelimit-remaining"))?;
+            let reset: i64 = header("x-ratelimit-reset")
+                .or(header("ratelimit-reset"))?
+                .parse()
+                .ok()?;
+            (remaining == "0").then(|| Duration::from_secs((reset - now.timestamp()).max(1) as u64))
+        });
+    PmError::Forge {
+        message,
+        status,
+        retry_after,
+    }
+}
+
+/// GitLab addresses projects by URL-encoded path.
+fn gitlab_project(forge: &ForgeUrl) -> String {
+    forge.project.replace('/', "%2F")
+}
+
+/// GitLab's API response fields we read.
+#[derive(Deserialize)]
+struct GitLabItem {
+    state: String,
+    #[serde(default)]
+    assignees: Vec<GitLabUser>,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+fn fetch_gitlab_item(forge: &ForgeUrl) -> Result<GitLabItem> {
+    let collection = match forge.kind {
+        LinkKind::Issue => "issues",
+        LinkKind::Pr => "merge_requests",
+    };
+    let endpoint = format!(
+        "projects/{}/{collection}/{}",
+        gitlab_project(forge),
+        forge.number
+    );
+    Ok(serde_json::from_slice(&forge_api(forge, &endpoint, None)?)?)
+}
+
+/// GitLab reports open items as "opened".
+fn normalize_gitlab_state(state: &str) -> String {
+    match state {
+        "opened" => "open".into(),
+        other => other.into(),
+    }
+}
+
+#[derive(Deserialize)]
+struct ForgeLabel {
+    name: String,
+    color: String,
+}
+
+/// Milestone fields as returned by the GitHub API.
+#[derive(Deserialize)]
+struct GhMilestone {
+    number: u64,
+    html_url: String,
+    #[serde(default)]
+    open_issues: u32,
+    #[serde(default)]
+    closed_issues: u32,
+    due_on: Option<DateTime<Utc>>,
+}
+
+impl From<GhMilestone> for Milestone {
+    fn from(m: GhMilestone) -> Self {
+        Milestone {
+            number: m.number,
+            url: m.html_url,
+            open_issues: m.open_issues,
+            closed_issues: m.closed_issues,
+            due_on: m.due_on.map(|d| d.date_naive()),
+            last_synced: None,
+        }
+    }
+}
+
+/// Run `gh api` against the repo's own GitHub remote and parse a milestone.
+#[tracing::instrument(level = "info", skip(repo), err(level = "info"))]
+fn gh_milestone_api(repo: &Path, args: &[&str]) -> Result<Milestone> {
+    let mut gh_args = vec!["api"];
+    gh_args.extend_from_slice(args);
+    let out = gh(repo, &gh_args, "gh api")?;
+    let milestone: GhMilestone = serde_json::from_str(&out)?;
+    Ok(milestone.into())
+}
+
+// ─── Mock and fixtures ───────────────────────────────────────
+
+/// Keys in [`MockProvider::errors`] for calls that aren't about one URL.
+pub const CREATE_ISSUE: &str = "create issue";
+pub const CREATE_PR: &str = "create pr";
+pub const CREATE_MILESTONE: &str = "create milestone";
+
+/// A forge that answers from canned responses, keyed by canonical URL
+/// (see `ForgeUrl`'s `Display`). Anything not listed is open, with an
+/// empty description and no labels. Serialized, it is the fixture format
+/// of `KUK_PM_SYNC_FIXTURE`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MockProvider {
+    /// Issue and PR states by URL.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub states: BTreeMap<String, String>,
+    /// PR descriptions by URL.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub pr_bodies: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub issues: BTreeMap<String, IssueDetails>,
+    /// Label colors by project URL, e.g. `https://github.com/owner/repo`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, BTreeMap<String, String>>,
+    /// Milestones by number. Created milestones are added here.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub milestones: BTreeMap<u64, Milestone>,
+    /// URLs handed to created issues and PRs, in order. Once used up, new
+    /// ones are numbered on from 1 under `https://github.com/mock/repo`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub created: Vec<String>,
+    /// Calls that fail, with their error: by item URL, by project URL for
+    /// label colors, `milestone N`, or [`CREATE_ISSUE`], [`CREATE_PR`] and
+    /// [`CREATE_MILESTONE`].
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<String, String>,
+    /// Changes made so far, e.g. `comment <url>: <body>`.
+    #[serde(skip)]
+    log: RefCell<Vec<String>>,
+    #[serde(skip)]
+    made: RefCell<BTreeMap<u64, Milestone>>,
+    #[serde(skip)]
+    flaky: RefCell<BTreeMap<String, (String, u32)>>,
+}
+
+impl MockProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a fixture written by hand or by `KUK_PM_SYNC_RECORD`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path).map_err(|e| {
+            PmError::Other(format!("cannot read sync fixture {}: {e}", path.display()))
+        })?;
+        serde_json::from_str(&data)
+            .map_err(|e| PmError::Other(format!("invalid sync fixture {}: {e}", path.display())))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    pub fn with_state(mut self, url: &str, state: &str) -> Self {
+        self.states.insert(url.into(), state.into());
+        self
+    }
+
+    pub fn with_pr_body(mut self, url: &str, body: &str) -> Self {
+        self.pr_bodies.insert(url.into(), body.into());
+        self
+    }
+
+    pub fn with_issue(mut self, url: &str, details: IssueDetails) -> Self {
+        self.issues.insert(url.into(), details);
+        self
+    }
+
+    /// Make calls under `key` fail with `message`.
+    pub fn with_error(mut self, key: &str, message: &str) -> Self {
+        self.errors.insert(key.into(), message.into());
+        self
+    }
+
+    /// The changes made through this provider, in order.
+    pub fn log(&self) -> Vec<String> {
+        self.log.borrow().clone()
+    }
+
+    /// Make the next `times` calls under `key` fail with `message`, as a
+    /// forge having a bad moment would.
+    pub fn with_flaky(self, key: &str, message: &str, times: u32) -> Self {
+        self.flaky
+            .borrow_mut()
+            .insert(key.into(), (message.into(), times));
+        self
+    }
+
+    fn fail(&self, key: &str) -> Result<()> {
+        let mut flaky = self.flaky.borrow_mut();
+        let message = match flaky.get_mut(key) {
+            Some((message, times)) if *times > 0 => {
+                *times -= 1;
+                message.clone()
+            }
+            _ => match self.errors.get(key) {
+                Some(message) => message.clone(),
+                None => return Ok(()),
+            },
+        };
+        Err(forge_error(message, "", Utc::now()))
+    }
+
+    fn record(&self, change: String) {
+        self.log.borrow_mut().push(change);
+    }
+
+    /// The next URL for a created item, `kind` being `issues` or `pull`.
+    fn next_created(&self, kind: &str) -> String {
+        let n = self
+            .log
+            .borrow()
+            .iter()
+            .filter(|c| c.starts_with("create issue ") || c.starts_with("create pr "))
+            .count();
+        self.created
+            .get(n)
+            .cloned()
+            .unwrap_or_else(|| format!("https://github.com/mock/repo/{kind}/{}", n + 1))
+    }
+}
+
+impl SyncProvider for MockProvider {
+    fn state(&self, forge: &ForgeUrl) -> Result<String> {
+        let url = forge.to_string();
+        self.fail(&url)?;
+        Ok(self
+            .states
+            .get(&url)
+            .cloned()
+            .unwrap_or_else(|| "open".into()))
+    }
+
+    fn pr_body(&self, forge: &ForgeUrl) -> Result<String> {
+        let url = forge.to_string();
+        self.fail(&url)?;
+        Ok(self.pr_bodies.get(&url).cloned().unwrap_or_default())
+    }
+
+    fn update_pr_body(&self, forge: &ForgeUrl, body: &str) -> Result<()> {
+        self.fail(&forge.to_string())?;
+        self.record(format!("update {forge}: {body}"));
+        Ok(())
+    }
+
+    fn post_comment(&self, forge: &ForgeUrl, body: &str) -> Result<Option<String>> {
+        self.fail(&forge.to_string())?;
+        self.record(format!("comment {forge}: {body}"));
+        Ok(Some(format!(
+            "{forge}#issuecomment-{}",
+            self.log.borrow().len()
+        )))
+    }
+
+    fn issue_details(&self, forge: &ForgeUrl) -> Result<IssueDetails> {
+        let url = forge.to_string();
+        self.fail(&url)?;
+        Ok(self.issues.get(&url).cloned().unwrap_or_default())
+    }
+
+    fn label_colors(&self, forge: &ForgeUrl) -> Result<BTreeMap<String, String>> {
+        let project = forge.project_url();
+        self.fail(&project)?;
+        Ok(self.labels.get(&project).cloned().unwrap_or_default())
+    }
+
+    fn create_issue(&self, _repo: &Path, title: &str, _body: &str) -> Result<String> {
+        self.fail(CREATE_ISSUE)?;
+        let url = self.next_created("issues");
+        self.record(format!("create issue {url}: {title}"));
+        Ok(url)
+    }
+
+    fn create_pr(&self, _repo: &Path, title: &str, _body: &str) -> Result<String> {
+        self.fail(CREATE_PR)?;
+        let url = self.next_created("pull");
+        self.record(format!("create pr {url}: {title}"));
+        Ok(url)
+    }
+
+    fn milestone(&self, _repo: &Path, number: u64) -> Result<Milestone> {
+        self.fail(&format!("milestone {number}"))?;
+        let made = self.made.borrow();
+        self.milestones
+            .get(&number)
+            .or(made.get(&number))
+            .cloned()
+            .ok_or_else(|| PmError::Other(format!("no milestone #{number}")))
+    }
+
+    fn create_milestone(&self, _repo: &Path, title: &str, due: NaiveDate) -> Result<Milestone> {
+        self.fail(CREATE_MILESTONE)?;
+        let number = (self.milestones.len() + self.made.borrow().len() + 1) as u64;
+        let milestone = Milestone {
+            number,
+            url: format!("https://github.com/mock/repo/milestone/{number}"),
+            open_issues: 0,
+            closed_issues: 0,
+            due_on: Some(due),
+            last_synced: None,
+        };
+        self.made.borrow_mut().insert(number, milestone.clone());
+        self.record(format!("create milestone #{number}: {title}"));
+        Ok(milestone)
+    }
+}
+
+/// Passes calls through to another provider and writes what it answered to
+/// a fixture after every call, errors included, for [`MockProvider`] to
+/// replay.
+pub struct Recorder<P> {
+    inner: P,
+    path: PathBuf,
+    fixture: RefCell<MockProvider>,
+}
+
+impl<P: SyncProvider> Recorder<P> {
+    /// Record into `path`, adding to the fixture already there.
+    pub fn new(inner: P, path: PathBuf) -> Self {
+        let fixture = MockProvider::load(&path).unwrap_or_default();
+        Self {
+            inner,
+            path,
+            fixture: RefCell::new(fixture),
+        }
+    }
+
+    /// Note the answer to a call under `key`, then hand it back.
+    fn keep<T: Clone>(
+        &self,
+        key: String,
+        answer: Result<T>,
+        store: impl FnOnce(&mut MockProvider, String, T),
+    ) -> Result<T> {
+        let mut fixture = self.fixture.borrow_mut();
+        match &answer {
+            Ok(value) => {
+                fixture.errors.remove(&key);
+                store(&mut fixture, key, value.clone());
+            }
+            Err(e) => {
+                fixture.errors.insert(key, e.to_string());
+            }
+        }
+        if let Err(e) = fixture.save(&self.path) {
+            tracing::warn!("could not record sync fixture {}: {e}", self.path.display());
+        }
+        answer
+    }
+}
+
+impl<P: SyncProvider> SyncProvider for Recorder<P> {
+    fn state(&self, forge: &ForgeUrl) -> Result<String> {
+        self.keep(forge.to_string(), self.inner.state(forge), |f, k, v| {
+            f.states.insert(k, v);
+        })
+    }
+
+    fn pr_body(&self, forge: &ForgeUrl) -> Result<String> {
+        self.keep(forge.to_string(), self.inner.pr_body(forge), |f, k, v| {
+            f.pr_bodies.insert(k, v);
+        })
+    }
+
+    fn update_pr_body(&self, forge: &ForgeUrl, body: &str) -> Result<()> {
+        let answer = self.inner.update_pr_body(forge, body);
+        self.keep(forge.to_string(), answer, |f, k, ()| {
+            f.pr_bodies.insert(k, body.into());
+        })
+    }
+
+    fn post_comment(&self, forge: &ForgeUrl, body: &str) -> Result<Option<String>> {
+        let answer = self.inner.post_comment(forge, body);
+        self.keep(forge.to_string(), answer, |_, _, _| {})
+    }
+
+    fn issue_details(&self, forge: &ForgeUrl) -> Result<IssueDetails> {
+        self.keep(
+            forge.to_string(),
+            self.inner.issue_details(forge),
+            |f, k, v| {
+                f.issues.insert(k, v);
+            },
+        )
+    }
+
+    fn label_colors(&self, forge: &ForgeUrl) -> Result<BTreeMap<String, String>> {
+        self.keep(
+            forge.project_url(),
+            self.inner.label_colors(forge),
+            |f, k, v| {
+                f.labels.insert(k, v);
+            },
+        )
+    }
+
+    fn create_issue(&self, repo: &Path, title: &str, body: &str) -> Result<String> {
+        let answer = self.inner.create_issue(repo, title, body);
+        self.keep(CREATE_ISSUE.into(), answer, |f, _, url| f.created.push(url))
+    }
+
+    fn create_pr(&self, repo: &Path, title: &str, body: &str) -> Result<String> {
+        let answer = self.inner.create_pr(repo, title, body);
+        self.keep(CREATE_PR.into(), answer, |f, _, url| f.created.push(url))
+    }
+
+    fn milestone(&self, repo: &Path, number: u64) -> Result<Milestone> {
+        let answer = self.inner.milestone(repo, number);
+        self.keep(format!("milestone {number}"), answer, |f, _, m| {
+            f.milestones.insert(number, m);
+        })
+    }
+
+    fn create_milestone(&self, repo: &Path, title: &str, due: NaiveDate) -> Result<Milestone> {
+        let answer = self.inner.create_milestone(repo, title, due);
+        self.keep(CREATE_MILESTONE.into(), answer, |f, _, m| {
+            f.milestones.insert(m.number, m);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ForgeHosts;
+
+    fn forge(url: &str) -> ForgeUrl {
+        ForgeUrl::parse(url, &ForgeHosts::new()).unwrap()
+    }
+
+    #[test]
+    fn gitlab_project_is_url_encoded() {
+        let forge = forge("https://gitlab.com/group/sub/proj/-/merge_requests/4");
+        assert_eq!(gitlab_project(&forge), "group%2Fsub%2Fproj");
+    }
+
+    #[test]
+    fn gitlab_opened_state_normalized() {
+        assert_eq!(normalize_gitlab_state("opened"), "open");
+        assert_eq!(normalize_gitlab_state("merged"), "merged");
+    }
+
+    #[test]
+    fn bitbucket_fetch_is_unsupported() {
+        let forge = forge("https://bitbucket.org/t/r/issues/1");
+        let err = GhCli.state(&forge).unwrap_err().to_string();
+        assert!(err.contains("not supported"));
+    }
+
+    #[test]
+    fn forge_errors_read_status_and_rate_limits() {
+        let out = b"HTTP/2.0 403 Forbidden\r\nX-Ratelimit-Remaining: 0\r\n\
+                    X-Ratelimit-Reset: 1700000030\r\n\r\n{\"message\":\"API rate limit exceeded\"}";
+        let (head, body) = split_response(out);
+        assert!(body.starts_with(b"{"));
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let err = forge_error("gh api error: rate limit".into(), &head, now);
+        assert!(matches!(
+            err,
+            PmError::Forge { status: Some(403), retry_after: Some(wait), .. }
+                if wait == Duration::from_secs(30)
+        ));
+        assert!(err.is_transient());
+
+        assert_eq!(split_response(b"[1]"), (String::new(), &b"[1]"[..]));
+        let err = forge_error("gh: Not Found (HTTP 404)".into(), "", now);
+        assert!(matches!(
+            err,
+            PmError::Forge {
+                status: Some(404),
+                ..
+            }
+        ));
+        assert!(!err.is_transient());
+        let err = forge_error("gh: Bad Gateway (HTTP 502)".into(), "", now);
+        assert!(err.is_transient());
+    }
+
+    #[test]
+    fn milestone_from_github_response() {
+        let gh: GhMilestone = serde_json::from_str(
+            r#"{
+                "number": 4,
+                "html_url": "https://github.com/u/r/milestone/4",
+                "open_issues": 2,
+                "closed_issues": 6,
+                "due_on": "2026-03-31T07:00:00Z"
+            }"#,
+        )
+        .unwrap();
+        let milestone = Milestone::from(gh);
+        assert_eq!(milestone.number, 4);
+        assert_eq!(milestone.due_on, NaiveDate::from_ymd_opt(2026, 3, 31));
+        assert_eq!(milestone.progress(), 75.0);
+    }
+
+    #[test]
+    fn recorder_writes_a_fixture_the_mock_replays() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("forge.json");
+        let live = MockProvider::new()
+            .with_state("https://github.com/o/r/pull/2", "merged")
+            .with_error("https://github.com/o/r/issues/3", "HTTP 404");
+        let recorder = Recorder::new(live, path.clone());
+        let repo = dir.path();
+
+        assert_eq!(
+            recorder
+                .state(&forge("https://github.com/o/r/pulls/2"))
+                .unwrap(),
+            "merged"
+        );
+        assert!(
+            recorder
+                .state(&forge("https://github.com/o/r/issues/3"))
+                .is_err()
+        );
+        let created = recorder.create_issue(repo, "Login", "").unwrap();
+
+        let replay = MockProvider::load(&path).unwrap();
+        assert_eq!(
+            replay
+                .state(&forge("https://github.com/o/r/pull/2"))
+                .unwrap(),
+            "merged"
+        );
+        let err = replay
+            .state(&forge("https://github.com/o/r/issues/3"))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "HTTP 404");
+        assert_eq!(replay.create_issue(repo, "Login", "").unwrap(), created);
+        assert_eq!(replay.log(), [format!("create issue {created}: Login")]);
+    }
+}
@@ -0,0 +1,302 @@
+//! Retries for forge calls that fail for a moment: a 502, a timeout, a
+//! used-up rate limit. [`Retrying`] wraps a [`SyncProvider`] so one bad
+//! response doesn't cost a card its sync, and stops calling a host that
+//! keeps failing so a down forge doesn't cost every card a slow timeout.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+
+use super::{IssueDetails, SyncProvider};
+use crate::error::{PmError, Result};
+use crate::model::{ForgeUrl, Milestone};
+
+/// Host key for calls on the repo's own remote (issue, PR and milestone
+/// creation), which `gh` resolves itself.
+const REPO_HOST: &str = "the repo's remote";
+
+/// How hard to try a forge call before giving up on it.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Calls made in all, counting the first.
+    pub attempts: u32,
+    /// Wait before the first retry, doubled for each one after.
+    pub backoff: Duration,
+    /// Longest wait worth sitting through; a rate limit resetting later
+    /// than this fails the call instead.
+    pub max_wait: Duration,
+    /// Calls in a row a host may fail before the rest of the run skips it.
+    pub host_failures: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            attempts: 3,
+            backoff: Duration::from_secs(1),
+            max_wait: Duration::from_secs(60),
+            host_failures: 3,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The wait before retry number `retry` (from 1), or None when it would
+    /// run past `max_wait`. A wait the forge asked for wins over backoff.
+    pub fn wait(&self, retry: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        let wait = retry_after.unwrap_or_else(|| {
+            self.backoff
+                .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+        });
+        (wait <= self.max_wait).then_some(wait)
+    }
+}
+
+/// A provider that retries transient failures of the one it wraps.
+pub struct Retrying<'a> {
+    inner: &'a dyn SyncProvider,
+    policy: &'a RetryPolicy,
+    /// Failed calls in a row, by host.
+    failures: RefCell<BTreeMap<String, u32>>,
+    retried: RefCell<Vec<String>>,
+}
+
+impl<'a> Retrying<'a> {
+    pub fn new(inner: &'a dyn SyncProvider, policy: &'a RetryPolicy) -> Self {
+        Retrying {
+            inner,
+            policy,
+            failures: RefCell::default(),
+            retried: RefCell::default(),
+        }
+    }
+
+    /// The calls that needed more than one try, and how they ended.
+    pub fn retried(&self) -> Vec<String> {
+        self.retried.borrow().clone()
+    }
+
+    /// Make `call` on `host`, retrying transient failures. Calls that are
+    /// not `idempotent` are only retried when the forge turned them away
+    /// unprocessed (a rate limit), so a lost response never creates twice.
+    fn call<T>(
+        &self,
+        host: &str,
+        what: &str,
+        idempotent: bool,
+        call: impl Fn() -> Result<T>,
+    ) -> Result<T> {
+        let failed = self.failures.borrow().get(host).copied().unwrap_or(0);
+        if failed >= self.policy.host_failures {
+            return Err(PmError::Other(format!(
+                "skipped: {host} failed {failed} calls in a row"
+            )));
+        }
+
+        let mut tries = 1;
+        let result = loop {
+            let err = match call() {
+                Ok(value) => break Ok(value),
+                Err(err) => err,
+            };
+            // A rate limit turns the request away before it does anything
+            let (retry_after, turned_away) = match &err {
+                PmError::Forge {
+                    status,
+                    retry_after,
+                    ..
+                } => (*retry_after, *status == Some(429) || retry_after.is_some()),
+                _ => (None, false),
+            };
+            let wait = self.policy.wait(tries, retry_after);
+            match wait {
+                Some(wait)
+                    if tries < self.policy.attempts
+                        && err.is_transient()
+                        && (idempotent || turned_away) =>
+                {
+                    tracing::warn!(what, tries, ?wait, "retrying forge call: {err}");
+                    std::thread::sleep(wait);
+                    tries += 1;
+                }
+                _ => break Err(err),
+            }
+        };
+
+        if tries > 1 {
+            let outcome = match &result {
+                Ok(_) => format!("{what}: ok after {tries} tries"),
+                Err(e) => format!("{what}: gave up after {tries} tries: {e}"),
+            };
+            self.retried.borrow_mut().push(outcome);
+        }
+        let mut failures = self.failures.borrow_mut();
+        match &result {
+            Ok(_) => {
+                failures.remove(host);
+            }
+            Err(e) if e.is_transient() => *failures.entry(host.to_string()).or_default() += 1,
+            // The host answered; the request itself was wrong
+            Err(_) => {
+                failures.remove(host);
+            }
+        }
+        result
+    }
+}
+
+impl SyncProvider for Retrying<'_> {
+    fn state(&self, forge: &ForgeUrl) -> Result<String> {
+        let what = format!("state of {forge}");
+        self.call(&forge.host, &what, true, || self.inner.state(forge))
+    }
+
+    fn pr_body(&self, forge: &ForgeUrl) -> Result<String> {
+        let what = format!("body of {forge}");
+        self.call(&forge.host, &what, true, || self.inner.pr_body(forge))
+    }
+
+    fn update_pr_body(&self, forge: &ForgeUrl, body: &str) -> Result<()> {
+        let what = format!("update of {forge}");
+        self.call(&forge.host, &what, true, || {
+            self.inner.update_pr_body(forge, body)
+        })
+    }
+
+    fn post_comment(&self, forge: &ForgeUrl, body: &str) -> Result<Option<String>> {
+        let what = format!("comment on {forge}");
+        self.call(&forge.host, &what, false, || {
+            self.inner.post_comment(forge, body)
+        })
+    }
+
+    fn issue_details(&self, forge: &ForgeUrl) -> Result<IssueDetails> {
+        let what = format!("details of {forge}");
+        self.call(&forge.host, &what, true, || self.inner.issue_details(forge))
+    }
+
+    fn label_colors(&self, forge: &ForgeUrl) -> Result<BTreeMap<String, String>> {
+        let what = format!("labels of {}", forge.project_url());
+        self.call(&forge.host, &what, true, || self.inner.label_colors(forge))
+    }
+
+    fn create_issue(&self, repo: &Path, title: &str, body: &str) -> Result<String> {
+        let what = format!("create issue '{title}'");
+        self.call(REPO_HOST, &what, false, || {
+            self.inner.create_issue(repo, title, body)
+        })
+    }
+
+    fn create_pr(&self, repo: &Path, title: &str, body: &str) -> Result<String> {
+        let what = format!("create PR '{title}'");
+        self.call(REPO_HOST, &what, false, || {
+            self.inner.create_pr(repo, title, body)
+        })
+    }
+
+    fn milestone(&self, repo: &Path, number: u64) -> Result<Milestone> {
+        let what = format!("milestone #{number}");
+        self.call(REPO_HOST, &what, true, || {
+            self.inner.milestone(repo, number)
+        })
+    }
+
+    fn create_milestone(&self, repo: &Path, title: &str, due: NaiveDate) -> Result<Milestone> {
+        let what = format!("create milestone '{title}'");
+        self.call(REPO_HOST, &what, false, || {
+            self.inner.create_milestone(repo, title, due)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ForgeHosts;
+    use crate::sync::MockProvider;
+
+    fn quick() -> RetryPolicy {
+        RetryPolicy {
+            backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        }
+    }
+
+    fn forge(url: &str) -> ForgeUrl {
+        ForgeUrl::parse(url, &ForgeHosts::new()).unwrap()
+    }
+
+    #[test]
+    fn backoff_doubles_and_rate_limits_win() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.wait(1, None), Some(Duration::from_secs(1)));
+        assert_eq!(policy.wait(3, None), Some(Duration::from_secs(4)));
+        assert_eq!(
+            policy.wait(1, Some(Duration::from_secs(30))),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(policy.wait(1, Some(Duration::from_secs(3600))), None);
+    }
+
+    #[test]
+    fn transient_failures_are_retried() {
+        let url = "https://github.com/o/r/issues/1";
+        let mock = MockProvider::new().with_state(url, "closed").with_flaky(
+            url,
+            "gh: Bad Gateway (HTTP 502)",
+            2,
+        );
+        let policy = quick();
+        let retrying = Retrying::new(&mock, &policy);
+
+        assert_eq!(retrying.state(&forge(url)).unwrap(), "closed");
+        assert_eq!(
+            retrying.retried(),
+            [format!("state of {url}: ok after 3 tries")]
+        );
+    }
+
+    #[test]
+    fn permanent_failures_and_lost_creates_are_not_retried() {
+        let url = "https://github.com/o/r/issues/1";
+        let mock = MockProvider::new()
+            .with_flaky(url, "gh: Not Found (HTTP 404)", 1)
+            .with_flaky(super::super::CREATE_ISSUE, "gh: Bad Gateway (HTTP 502)", 1);
+        let policy = quick();
+        let retrying = Retrying::new(&mock, &policy);
+
+        assert!(retrying.state(&forge(url)).is_err());
+        assert!(retrying.create_issue(Path::new("."), "T", "").is_err());
+        assert!(retrying.retried().is_empty());
+    }
+
+    #[test]
+    fn a_failing_host_is_skipped() {
+        let down = "gh: error connecting to api.github.com";
+        let mut mock = MockProvider::new();
+        for n in 1..=4 {
+            mock = mock.with_error(&format!("https://github.com/o/r/issues/{n}"), down);
+        }
+        let policy = quick();
+        let retrying = Retrying::new(&mock, &policy);
+
+        for n in 1..=3 {
+            let url = format!("https://github.com/o/r/issues/{n}");
+            assert!(
+                retrying
+                    .state(&forge(&url))
+                    .unwrap_err()
+                    .to_string()
+                    .contains(down)
+            );
+        }
+        let url = "https://github.com/o/r/issues/4";
+        let err = retrying.state(&forge(url)).unwrap_err().to_string();
+        assert_eq!(err, "skipped: github.com failed 3 calls in a row");
+        // Three calls, three tries each, then nothing for the fourth
+        assert_eq!(retrying.retried().len(), 3);
+    }
+}
@@ -0,0 +1,538 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use kuk::storage::Store;
+use kuk::tui::Theme;
+use kuk::tui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use kuk::tui::crossterm::execute;
+use kuk::tui::crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use kuk::tui::ratatui::Terminal;
+use kuk::tui::ratatui::backend::CrosstermBackend;
+
+use crate::error::{PmError, Result};
+use crate::model::{Locale, Sprint, SprintStatus};
+use crate::reports::{self, ReportWindow};
+use crate::sync::{self, SyncScope, load_sprints, save_sprints};
+
+use super::ui;
+
+/// Weeks of trends on the stats tab, as `kuk-pm stats --history` defaults to.
+const STATS_HISTORY_WEEKS: u32 = 12;
+
+/// Weeks shown on the velocity tab, as `kuk-pm velocity` defaults to.
+const VELOCITY_WEEKS: u32 = 4;
+
+/// Weeks projected on the roadmap tab, as `kuk-pm roadmap` defaults to.
+const ROADMAP_WEEKS: u32 = 12;
+
+/// Sync runs listed on the sync tab.
+const SYNC_LOG_RUNS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tab {
+    Stats,
+    Velocity,
+    Burndown,
+    Roadmap,
+    Sprints,
+    Sync,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 6] = [
+        Tab::Stats,
+        Tab::Velocity,
+        Tab::Burndown,
+        Tab::Roadmap,
+        Tab::Sprints,
+        Tab::Sync,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Stats => "Stats",
+            Tab::Velocity => "Velocity",
+            Tab::Burndown => "Burndown",
+            Tab::Roadmap => "Roadmap",
+            Tab::Sprints => "Sprints",
+            Tab::Sync => "Sync",
+        }
+    }
+
+    fn index(self) -> usize {
+        Tab::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+}
+
+pub struct App {
+    pub repo: PathBuf,
+    pub store: Store,
+    pub tab: Tab,
+    pub theme: Theme,
+    pub locale: Locale,
+    /// What the current tab shows below the tabs: a rendered report, or
+    /// sync output. The sprints tab draws `sprints` instead.
+    pub text: String,
+    pub scroll: u16,
+    pub sprints: Vec<Sprint>,
+    /// The sprint picked on the sprints tab, which the burndown tab shows.
+    pub sprint_selected: usize,
+    pub message: Option<String>,
+    pub should_quit: bool,
+}
+
+impl App {
+    pub fn new(repo: &Path) -> Result<Self> {
+        let store = Store::new(repo);
+        if !store.is_initialized() {
+            return Err(PmError::KukNotInitialized);
+        }
+        let config = store.load_config()?;
+        let locale = sync::load_pm_config(&store)?.locale;
+        let sprints = load_sprints(&store)?;
+        let sprint_selected = sprints
+            .iter()
+            .position(|s| s.status == SprintStatus::Active)
+            .unwrap_or(0);
+
+        let mut app = Self {
+            repo: repo.to_path_buf(),
+            store,
+            tab: Tab::Stats,
+            theme: Theme::new(config.color_scheme),
+            locale,
+            text: String::new(),
+            scroll: 0,
+            sprints,
+            sprint_selected,
+            message: None,
+            should_quit: false,
+        };
+        app.refresh();
+        Ok(app)
+    }
+
+    pub fn selected_sprint(&self) -> Option<&Sprint> {
+        self.sprints.get(self.sprint_selected)
+    }
+
+    /// Switch tabs and load what the new one shows.
+    pub fn show(&mut self, tab: Tab) {
+        self.tab = tab;
+        self.scroll = 0;
+        self.refresh();
+    }
+
+    /// Recompute the current tab from the files on disk.
+    pub fn refresh(&mut self) {
+        match load_sprints(&self.store) {
+            Ok(sprints) => {
+                self.sprints = sprints;
+                self.sprint_selected = self
+                    .sprint_selected
+                    .min(self.sprints.len().saturating_sub(1));
+            }
+            Err(e) => self.message = Some(e.to_string()),
+        }
+        self.text = match self.tab_text() {
+            Ok(text) => text,
+            Err(e) => format!("Error: {e}\n"),
+        };
+    }
+
+    fn tab_text(&self) -> Result<String> {
+        let store = &self.store;
+        let locale = &self.locale;
+        match self.tab {
+            Tab::Stats => {
+                let window = ReportWindow::default();
+                let params = (window, false, Some(STATS_HISTORY_WEEKS));
+                let report = reports::cached(store, "stats", &params, || {
+                    let board = sync::stats_board(store)?;
+                    let policy = sync::load_pm_config(store)?.cycle_outliers;
+                    let cal = sync::load_calendar(store)?;
+                    let mut report = reports::calculate_stats(&board, policy, &window, &cal);
+                    report.history = Some(reports::calculate_stats_history(
+                        &board,
+                        STATS_HISTORY_WEEKS,
+                        &window,
+                        &cal,
+                    ));
+                    Ok(report)
+                })?;
+                Ok(reports::render_stats_text(&report, locale))
+            }
+            Tab::Velocity => {
+                let params = (
+                    VELOCITY_WEEKS,
+                    ReportWindow::default(),
+                    false,
+                    None::<String>,
+                );
+                let report = reports::cached(store, "velocity", &params, || {
+                    let cards = store.active_cards()?;
+                    let cal = sync::load_calendar(store)?;
+                    Ok(reports::calculate_velocity(
+                        &cards,
+                        VELOCITY_WEEKS,
+                        &ReportWindow::default(),
+                        &cal,
+                    ))
+                })?;
+                Ok(reports::render_velocity_text(&report, locale))
+            }
+            Tab::Burndown => {
+                let Some(sprint) = self.selected_sprint() else {
+                    return Ok(
+                        "No sprints defined. Create one with `kuk-pm sprint create`.\n".into(),
+                    );
+                };
+                let report = reports::cached(store, "burndown", &sprint.name, || {
+                    let cards = sync::sprint_cards(store, sprint)?;
+                    let cal = sync::load_calendar(store)?;
+                    Ok(reports::calculate_burndown(&cards, sprint, &cal))
+                })?;
+                Ok(reports::render_burndown_text(&report, locale))
+            }
+            Tab::Roadmap => {
+                let report = reports::cached(store, "roadmap", &ROADMAP_WEEKS, || {
+                    let cards = store.active_cards()?;
+                    let cal = sync::load_calendar(store)?;
+                    let recent = reports::calculate_velocity(
+                        &cards,
+                        VELOCITY_WEEKS,
+                        &ReportWindow::default(),
+                        &cal,
+                    );
+                    let velocity = if recent.average > 0.0 {
+                        recent.average
+                    } else {
+                        1.0
+                    };
+                    Ok(reports::calculate_roadmap(
+                        &store.all_cards()?,
+                        &self.sprints,
+                        ROADMAP_WEEKS,
+                        velocity,
+                        &cal,
+                    ))
+                })?;
+                Ok(reports::render_roadmap_text(&report, locale))
+            }
+            Tab::Sprints => Ok(String::new()),
+            Tab::Sync => {
+                let entries = sync::load_sync_log(store, Some(SYNC_LOG_RUNS))?;
+                if entries.is_empty() {
+                    return Ok("No sync runs recorded.\n".into());
+                }
+                let mut out = String::new();
+                for entry in entries.iter().rev() {
+                    out.push_str(&format!(
+                        "{}  {} action(s){}\n",
+                        entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                        entry.actions.len(),
+                        if entry.dry_run { " (dry run)" } else { "" }
+                    ));
+                    for action in &entry.actions {
+                        out.push_str(&sync::render_action(action));
+                        out.push('\n');
+                    }
+                }
+                Ok(out)
+            }
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        // Ctrl+C always quits
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.should_quit = true;
+            return;
+        }
+        self.message = None;
+
+        match key.code {
+            KeyCode::Char('q') => self.should_quit = true,
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+                self.show(Tab::ALL[(self.tab.index() + 1) % Tab::ALL.len()]);
+            }
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                let len = Tab::ALL.len();
+                self.show(Tab::ALL[(self.tab.index() + len - 1) % len]);
+            }
+            KeyCode::Char(c @ '1'..='6') => {
+                self.show(Tab::ALL[c as usize - '1' as usize]);
+            }
+            KeyCode::Char('r') => {
+                self.refresh();
+                self.message = Some("Refreshed".into());
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_down(),
+            KeyCode::Up | KeyCode::Char('k') => self.move_up(),
+            _ => match self.tab {
+                Tab::Sprints => self.handle_sprints(key),
+                Tab::Sync => self.handle_sync(key),
+                _ => {}
+            },
+        }
+    }
+
+    fn move_down(&mut self) {
+        if self.tab == Tab::Sprints {
+            if self.sprint_selected + 1 < self.sprints.len() {
+                self.sprint_selected += 1;
+            }
+        } else {
+            let lines = self.text.lines().count() as u16;
+            self.scroll = (self.scroll + 1).min(lines.saturating_sub(1));
+        }
+    }
+
+    fn move_up(&mut self) {
+        if self.tab == Tab::Sprints {
+            self.sprint_selected = self.sprint_selected.saturating_sub(1);
+        } else {
+            self.scroll = self.scroll.saturating_sub(1);
+        }
+    }
+
+    fn handle_sprints(&mut self, key: KeyEvent) {
+        let result = match key.code {
+            KeyCode::Enter => {
+                self.show(Tab::Burndown);
+                return;
+            }
+            KeyCode::Char('s') => self.set_sprint_status(SprintStatus::Active),
+            KeyCode::Char('c') => self.set_sprint_status(SprintStatus::Closed),
+            _ => return,
+        };
+        self.message = Some(match result {
+            Ok(message) => message,
+            Err(e) => e.to_string(),
+        });
+        self.refresh();
+    }
+
+    /// Start or close the selected sprint. Started sprints must be planned,
+    /// as with `kuk-pm sprint start`.
+    fn set_sprint_status(&mut self, status: SprintStatus) -> Result<String> {
+        let name = self
+            .selected_sprint()
+            .map(|s| s.name.clone())
+            .ok_or_else(|| PmError::Other("No sprint selected".into()))?;
+        if status == SprintStatus::Active {
+            sync::start_sprint(&self.store, &name, None)?;
+            return Ok(format!("Started sprint: {name}"));
+        }
+
+        let mut sprints = load_sprints(&self.store)?;
+        let sprint = sprints
+            .iter_mut()
+            .find(|s| s.name == name)
+            .ok_or_else(|| PmError::SprintNotFound(name.clone()))?;
+        if sprint.status == SprintStatus::Closed {
+            return Err(PmError::SprintAlreadyClosed(name));
+        }
+        sprint.status = status;
+        save_sprints(&self.store, &sprints)?;
+        Ok(format!("Closed sprint: {name}"))
+    }
+
+    fn handle_sync(&mut self, key: KeyEvent) {
+        let dry_run = match key.code {
+            KeyCode::Char('d') => true,
+            KeyCode::Char('s') => false,
+            _ => return,
+        };
+        let retry = sync::RetryPolicy::default();
+        let synced = sync::provider().and_then(|p| {
+            sync::sync_board(
+                &*p,
+                &self.repo,
+                &SyncScope::default(),
+                false,
+                dry_run,
+                &retry,
+            )
+        });
+        match synced {
+            Ok(Some(sync::SyncRun { actions, .. })) => {
+                let mut text = String::new();
+                for action in &actions {
+                    text.push_str(&sync::render_action(action));
+                    text.push('\n');
+                }
+                self.text = text;
+                self.scroll = 0;
+                self.message = Some(match (actions.len(), dry_run) {
+                    (0, _) => "Everything up to date.".into(),
+                    (n, true) => format!("{n} action(s) (dry run)"),
+                    (n, false) => format!("{n} action(s) applied"),
+                });
+            }
+            Ok(None) => {
+                self.message = Some("Sync is disabled for this board in .kuk/pm.json.".into());
+            }
+            Err(e) => self.message = Some(format!("Sync failed: {e}")),
+        }
+    }
+}
+
+/// Open the report dashboard for `repo`.
+pub fn run_tui(repo: &Path) -> Result<()> {
+    let mut app = App::new(repo)?;
+
+    let term_err = |e: io::Error| PmError::Other(format!("Terminal error: {e}"));
+    enable_raw_mode().map_err(term_err)?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen).map_err(term_err)?;
+
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend).map_err(term_err)?;
+
+    let result = run_loop(&mut terminal, &mut app);
+
+    // Restore terminal
+    disable_raw_mode().ok();
+    execute!(terminal.backend_mut(), LeaveAlternateScreen).ok();
+    terminal.show_cursor().ok();
+
+    result
+}
+
+fn run_loop(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
+    loop {
+        terminal
+            .draw(|f| ui::draw(f, app))
+            .map_err(|e| PmError::Other(format!("Draw error: {e}")))?;
+
+        if event::poll(Duration::from_millis(250))
+            .map_err(|e| PmError::Other(format!("Event error: {e}")))?
+            && let Event::Key(key) =
+                event::read().map_err(|e| PmError::Other(format!("Event error: {e}")))?
+        {
+            app.handle_key(key);
+        }
+
+        if app.should_quit {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use kuk::model::Card;
+    use kuk::tui::crossterm::event::{KeyEventKind, KeyEventState};
+    use tempfile::TempDir;
+
+    fn make_key(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn test_app() -> (TempDir, App) {
+        let dir = TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+        let mut board = store.load_board("default").unwrap();
+        board.add_card(Card::new("Shipped", "done"));
+        board.add_card(Card::new("Next", "todo"));
+        store.save_board(&board).unwrap();
+
+        let sprint = |name: &str, status| Sprint {
+            name: name.into(),
+            start: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 1, 14).unwrap(),
+            goal: None,
+            boards: Vec::new(),
+            status,
+            milestone: None,
+        };
+        save_sprints(
+            &store,
+            &[
+                sprint("s1", SprintStatus::Closed),
+                sprint("s2", SprintStatus::Active),
+                sprint("s3", SprintStatus::Planned),
+            ],
+        )
+        .unwrap();
+
+        let app = App::new(dir.path()).unwrap();
+        (dir, app)
+    }
+
+    #[test]
+    fn tabs_render_reports() {
+        let (_dir, mut app) = test_app();
+        assert_eq!(app.tab, Tab::Stats);
+        assert!(app.text.contains("Project Statistics"));
+        assert!(app.text.contains("Trends (12 weeks from"));
+
+        app.handle_key(make_key(KeyCode::Tab));
+        assert_eq!(app.tab, Tab::Velocity);
+        assert!(app.text.contains("Velocity (last 4 weeks)"));
+
+        app.handle_key(make_key(KeyCode::Char('4')));
+        assert!(app.text.contains("Roadmap (next 12 weeks"));
+
+        // The active sprint is picked for the burndown
+        app.handle_key(make_key(KeyCode::BackTab));
+        assert_eq!(app.tab, Tab::Burndown);
+        assert!(app.text.contains("Burndown: s2"), "{}", app.text);
+
+        app.handle_key(make_key(KeyCode::Left));
+        app.handle_key(make_key(KeyCode::Left));
+        app.handle_key(make_key(KeyCode::Left));
+        assert_eq!(app.tab, Tab::Sync);
+        assert!(app.text.contains("No sync runs recorded."));
+
+        app.handle_key(make_key(KeyCode::Char('q')));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn sprints_tab_starts_and_closes_sprints() {
+        let (_dir, mut app) = test_app();
+        app.show(Tab::Sprints);
+        assert_eq!(app.sprint_selected, 1);
+
+        app.handle_key(make_key(KeyCode::Char('s')));
+        assert_eq!(app.message.as_deref(), Some("Sprint already active: s2"));
+
+        app.handle_key(make_key(KeyCode::Char('j')));
+        app.handle_key(make_key(KeyCode::Char('s')));
+        assert_eq!(app.message.as_deref(), Some("Started sprint: s3"));
+        app.handle_key(make_key(KeyCode::Char('k')));
+        app.handle_key(make_key(KeyCode::Char('c')));
+        assert_eq!(app.message.as_deref(), Some("Closed sprint: s2"));
+
+        let statuses: Vec<SprintStatus> = load_sprints(&app.store)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.status)
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                SprintStatus::Closed,
+                SprintStatus::Closed,
+                SprintStatus::Active
+            ]
+        );
+
+        app.handle_key(make_key(KeyCode::Enter));
+        assert_eq!(app.tab, Tab::Burndown);
+        assert!(app.text.contains("Burndown: s2"));
+    }
+}
@@ -0,0 +1,4 @@
+mod app;
+mod ui;
+
+pub use app::run_tui;
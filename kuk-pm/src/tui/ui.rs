@@ -0,0 +1,128 @@
+use kuk::tui::ratatui::Frame;
+use kuk::tui::ratatui::layout::{Constraint, Direction, Layout, Rect};
+use kuk::tui::ratatui::text::{Line, Span};
+use kuk::tui::ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Tabs};
+
+use crate::model::SprintStatus;
+
+use super::app::{App, Tab};
+
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Title bar
+            Constraint::Length(1), // Tabs
+            Constraint::Min(0),    // Tab content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(f.area());
+
+    draw_title_bar(f, chunks[0], app);
+    draw_tabs(f, chunks[1], app);
+    match app.tab {
+        Tab::Sprints => draw_sprints(f, chunks[2], app),
+        _ => draw_text(f, chunks[2], app),
+    }
+    draw_status_bar(f, chunks[3], app);
+}
+
+fn draw_title_bar(f: &mut Frame, area: Rect, app: &App) {
+    let project = app
+        .repo
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| app.repo.display().to_string());
+    let active = app
+        .sprints
+        .iter()
+        .find(|s| s.status == SprintStatus::Active)
+        .map(|s| format!("  │  sprint {}", s.name))
+        .unwrap_or_default();
+    let bar = Paragraph::new(format!(" kuk-pm  │  {project}{active}")).style(app.theme.selected);
+    f.render_widget(bar, area);
+}
+
+fn draw_tabs(f: &mut Frame, area: Rect, app: &App) {
+    let titles: Vec<Line> = Tab::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| Line::from(format!("{} {}", i + 1, tab.title())))
+        .collect();
+    let selected = Tab::ALL.iter().position(|t| *t == app.tab).unwrap_or(0);
+    let tabs = Tabs::new(titles)
+        .select(selected)
+        .style(app.theme.muted)
+        .highlight_style(app.theme.accent);
+    f.render_widget(tabs, area);
+}
+
+fn draw_text(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(format!(" {} ", app.tab.title()))
+        .borders(Borders::ALL)
+        .border_style(app.theme.accent);
+    let text = Paragraph::new(app.text.as_str())
+        .style(app.theme.text)
+        .scroll((app.scroll, 0))
+        .block(block);
+    f.render_widget(text, area);
+}
+
+fn draw_sprints(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Sprints ")
+        .borders(Borders::ALL)
+        .border_style(app.theme.accent);
+
+    if app.sprints.is_empty() {
+        let empty = Paragraph::new("No sprints defined. Create one with `kuk-pm sprint create`.")
+            .style(app.theme.muted)
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .sprints
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let status = match s.status {
+                SprintStatus::Planned => "planned",
+                SprintStatus::Active => "active",
+                SprintStatus::Closed => "closed",
+            };
+            let text = format!(
+                "  {:<20} {} → {}  [{status}]",
+                s.name,
+                app.locale.date(s.start),
+                app.locale.date(s.end)
+            );
+            let style = if i == app.sprint_selected {
+                app.theme.selected
+            } else if s.status == SprintStatus::Closed {
+                app.theme.muted
+            } else {
+                app.theme.text
+            };
+            ListItem::new(Line::from(Span::styled(text, style)))
+        })
+        .collect();
+
+    f.render_widget(List::new(items).block(block), area);
+}
+
+fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
+    let keys = match app.tab {
+        Tab::Sprints => "j/k select  s start  c close  Enter burndown",
+        Tab::Sync => "d dry run  s sync",
+        _ => "j/k scroll",
+    };
+    let left = match &app.message {
+        Some(msg) => format!(" {msg}"),
+        None => format!(" Tab/1-6 switch  {keys}  r refresh  q quit"),
+    };
+    let bar = Paragraph::new(left).style(app.theme.status);
+    f.render_widget(bar, area);
+}
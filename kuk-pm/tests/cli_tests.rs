@@ -2,10 +2,14 @@ use assert_cmd::Command;
 use predicates::prelude::*;
 use tempfile::TempDir;
 
+// `cargo_bin` is deprecated in newer assert_cmd releases, but the
+// replacement macro isn't available across the whole "2" range.
+#[allow(deprecated)]
 fn kuk() -> Command {
     Command::cargo_bin("kuk").unwrap()
 }
 
+#[allow(deprecated)]
 fn kuk_pm() -> Command {
     Command::cargo_bin("kuk-pm").unwrap()
 }
@@ -487,6 +491,79 @@ fn sprint_close_already_closed_fails() {
         .stderr(predicate::str::contains("already closed"));
 }
 
+#[test]
+fn sprint_start_creates_scoped_board() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    for title in ["Login page", "Logout", "Docs"] {
+        kuk_in(&dir).args(["add", title]).assert().success();
+    }
+    kuk_pm_in(&dir)
+        .args([
+            "sprint",
+            "create",
+            "Sprint 1",
+            "--start",
+            "2026-03-01",
+            "--end",
+            "2026-03-14",
+        ])
+        .assert()
+        .success();
+
+    kuk_pm_in(&dir)
+        .args(["sprint", "start", "Sprint 1", "--move", "KUK-1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--create-board"));
+    kuk_pm_in(&dir)
+        .args([
+            "sprint",
+            "start",
+            "Sprint 1",
+            "--create-board",
+            "--move",
+            "KUK-1",
+            "--copy",
+            "KUK-2",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Started sprint: Sprint 1"))
+        .stdout(predicate::str::contains("Created board: Sprint-1"))
+        .stdout(predicate::str::contains("Moved: KUK-1"))
+        .stdout(predicate::str::contains("Copied: KUK-2"));
+    kuk_pm_in(&dir)
+        .args(["sprint", "start", "Sprint 1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Sprint already active: Sprint 1"));
+
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Login page").not())
+        .stdout(predicate::str::contains("Logout"));
+
+    let output = kuk_pm_in(&dir)
+        .args(["sprint", "list", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["boards"], serde_json::json!(["Sprint-1"]));
+    let output = kuk_pm_in(&dir)
+        .args(["burndown", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total_cards"], 2);
+    let output = kuk_pm_in(&dir).args(["stats", "--json"]).output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["board_name"], "Sprint-1");
+    assert_eq!(json["active_cards"], 2);
+}
+
 #[test]
 fn sprint_list_empty() {
     let dir = TempDir::new().unwrap();
@@ -528,6 +605,65 @@ fn sprint_list_json() {
     assert_eq!(json[0]["name"], "s1");
 }
 
+// ─── OKRs ────────────────────────────────────────────────────
+
+#[test]
+fn okr_create_link_and_progress() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir)
+        .args(["add", "Onboarding wizard"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["add", "Onboarding docs", "--label", "onboarding"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["move", "2", "--to", "done"])
+        .assert()
+        .success();
+
+    kuk_pm_in(&dir)
+        .args([
+            "okr",
+            "create",
+            "adoption",
+            "--title",
+            "Grow adoption",
+            "--kr",
+            "Ship onboarding",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Created objective: adoption (1 key results)",
+        ));
+    kuk_pm_in(&dir)
+        .args(["okr", "link", "adoption/1", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Linked card Onboarding wizard to adoption/1",
+        ));
+    kuk_pm_in(&dir)
+        .args(["okr", "link", "adoption/1", "--epic", "onboarding"])
+        .assert()
+        .success();
+    kuk_pm_in(&dir)
+        .args(["okr", "link", "adoption/2", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No key result 2 in adoption"));
+
+    kuk_pm_in(&dir)
+        .arg("okr")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Grow adoption [adoption]  50%"))
+        .stdout(predicate::str::contains("1/2 cards"));
+}
+
 // ─── Link ────────────────────────────────────────────────────
 
 #[test]
@@ -581,159 +717,953 @@ fn link_json_output() {
 }
 
 #[test]
-fn link_nonexistent_card_fails() {
+fn link_no_fetch_leaves_card_fields() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
-
-    kuk_pm_in(&dir)
-        .args(["link", "99", "https://github.com/u/r/issues/1"])
+    kuk_in(&dir)
+        .args(["add", "Test card", "--label", "local"])
         .assert()
-        .failure()
-        .stderr(predicate::str::contains("Card not found"));
+        .success();
+
+    let output = kuk_pm_in(&dir)
+        .args([
+            "link",
+            "1",
+            "https://github.com/u/r/issues/1",
+            "--no-fetch",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["labels"], serde_json::json!(["local"]));
+    assert!(json["assignee"].is_null());
 }
 
 #[test]
-fn link_before_init_fails() {
+fn link_multiple_urls_with_roles() {
     let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
+
     kuk_pm_in(&dir)
-        .args(["link", "1", "https://github.com/u/r/issues/1"])
+        .args(["link", "1", "https://github.com/u/r/pull/1", "--no-fetch"])
         .assert()
-        .failure()
-        .stderr(predicate::str::contains("kuk init"));
-}
+        .success()
+        .stdout(predicate::str::contains("PR (implements)"));
+    kuk_pm_in(&dir)
+        .args([
+            "link",
+            "1",
+            "https://github.com/u/r/pull/2",
+            "--role",
+            "reverts",
+            "--no-fetch",
+        ])
+        .assert()
+        .success();
 
-// ─── Velocity ────────────────────────────────────────────────
+    let output = kuk_in(&dir).args(["list", "--json"]).output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let links = &json["cards"][0]["metadata"]["pm"]["links"];
+    assert_eq!(links.as_array().unwrap().len(), 2);
+    assert_eq!(links[0]["role"], "implements");
+    assert_eq!(links[1]["role"], "reverts");
+    assert_eq!(links[1]["kind"], "pr");
+}
 
 #[test]
-fn velocity_shows_report() {
+fn link_invalid_role_fails() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
 
     kuk_pm_in(&dir)
-        .arg("velocity")
+        .args([
+            "link",
+            "1",
+            "https://github.com/u/r/pull/1",
+            "--role",
+            "breaks",
+        ])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("Velocity"))
-        .stdout(predicate::str::contains("Average"))
-        .stdout(predicate::str::contains("Trend"));
+        .failure()
+        .stderr(predicate::str::contains("Invalid link role"));
 }
 
 #[test]
-fn velocity_json() {
+fn unlink_removes_single_url() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
+    for url in [
+        "https://github.com/u/r/pull/1",
+        "https://github.com/u/r/pull/2",
+    ] {
+        kuk_pm_in(&dir)
+            .args(["link", "1", url, "--no-fetch"])
+            .assert()
+            .success();
+    }
+
+    kuk_pm_in(&dir)
+        .args(["unlink", "1", "--url", "https://github.com/u/r/pull/1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unlinked"));
 
     let output = kuk_pm_in(&dir)
-        .args(["velocity", "--json"])
+        .args(["meta", "1", "--json"])
         .output()
         .unwrap();
     assert!(output.status.success());
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
-    assert!(json["weeks"].is_array());
-    assert!(json["average"].is_number());
-    assert!(json["trend"].is_string());
+    let links = json["pm"]["links"].as_array().unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0]["url"], "https://github.com/u/r/pull/2");
 }
 
 #[test]
-fn velocity_custom_weeks() {
+fn unlink_all_clears_links() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
+    kuk_pm_in(&dir)
+        .args(["link", "1", "https://github.com/u/r/pull/1", "--no-fetch"])
+        .assert()
+        .success();
+
+    let output = kuk_pm_in(&dir)
+        .args(["unlink", "1", "--all", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["removed"].as_array().unwrap().len(), 1);
+    assert_eq!(json["remaining"], 0);
 
     kuk_pm_in(&dir)
-        .args(["velocity", "--weeks", "8"])
+        .args(["meta", "1"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("Velocity (last 8 weeks)"));
+        .stdout(predicate::str::contains("Links:       (none)"));
 }
 
 #[test]
-fn velocity_before_init_fails() {
+fn unlink_unknown_url_fails() {
     let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
+
     kuk_pm_in(&dir)
-        .arg("velocity")
+        .args(["unlink", "1", "--url", "https://github.com/u/r/pull/9"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("kuk init"));
+        .stderr(predicate::str::contains("not linked"));
 }
 
-// ─── Stats ───────────────────────────────────────────────────
+#[test]
+fn unlink_requires_url_or_all() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
+
+    kuk_pm_in(&dir).args(["unlink", "1"]).assert().failure();
+}
 
 #[test]
-fn stats_shows_report() {
+fn link_unknown_host_warns() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
 
     kuk_pm_in(&dir)
-        .arg("stats")
+        .args([
+            "link",
+            "1",
+            "https://git.corp.example/team/app/-/merge_requests/3",
+            "--no-fetch",
+        ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("Project Statistics"))
-        .stdout(predicate::str::contains("Work in Progress"))
-        .stdout(predicate::str::contains("Throughput"));
+        .stderr(predicate::str::contains("forge_hosts"));
 }
 
 #[test]
-fn stats_json() {
+fn link_self_hosted_gitlab_from_forge_hosts() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
+    std::fs::write(
+        dir.path().join(".kuk/pm.json"),
+        r#"{"version": "0.1.0", "auto_branch": false, "forge_hosts": {"git.corp.example": "gitlab"}}"#,
+    )
+    .unwrap();
 
-    let output = kuk_pm_in(&dir).args(["stats", "--json"]).output().unwrap();
+    let output = kuk_pm_in(&dir)
+        .args([
+            "link",
+            "1",
+            "https://git.corp.example/group/sub/app/merge_requests/3",
+            "--no-fetch",
+            "--json",
+        ])
+        .output()
+        .unwrap();
     assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Warning"));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
-    assert_eq!(json["board_name"], "default");
-    assert!(json["wip_count"].is_number());
+    assert_eq!(json["type"], "pr");
 }
 
 #[test]
-fn stats_with_cards() {
+fn link_nonexistent_card_fails() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
-    kuk_in(&dir).args(["add", "Task A"]).assert().success();
-    kuk_in(&dir).args(["add", "Task B"]).assert().success();
 
     kuk_pm_in(&dir)
-        .arg("stats")
+        .args(["link", "99", "https://github.com/u/r/issues/1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Card not found"));
+}
+
+#[test]
+fn comment_needs_a_linked_target() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Test card"]).assert().success();
+
+    kuk_pm_in(&dir)
+        .args(["comment", "1", "Started on this"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has no linked issue or PR"));
+
+    kuk_pm_in(&dir)
+        .args([
+            "link",
+            "1",
+            "https://bitbucket.org/t/r/issues/1",
+            "--no-fetch",
+        ])
+        .assert()
+        .success();
+    kuk_pm_in(&dir)
+        .args([
+            "comment",
+            "1",
+            "Started on this",
+            "--url",
+            "https://github.com/u/r/issues/9",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not linked to"));
+
+    // Nothing is recorded when the forge can't take the comment
+    kuk_pm_in(&dir)
+        .args(["comment", "1", "Started on this"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not supported"));
+    kuk_pm_in(&dir)
+        .args(["meta", "1"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("2 active"));
+        .stdout(predicate::str::contains("Comments:").not());
 }
 
 #[test]
-fn stats_before_init_fails() {
+fn link_before_init_fails() {
     let dir = TempDir::new().unwrap();
     kuk_pm_in(&dir)
-        .arg("stats")
+        .args(["link", "1", "https://github.com/u/r/issues/1"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("kuk init"));
 }
 
-// ─── Burndown ────────────────────────────────────────────────
+// ─── Velocity ────────────────────────────────────────────────
 
 #[test]
-fn burndown_with_sprint() {
+fn velocity_shows_report() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
 
-    // Create a sprint
     kuk_pm_in(&dir)
-        .args([
-            "sprint",
-            "create",
-            "s1",
-            "--start",
-            "2026-02-01",
-            "--end",
-            "2026-03-01",
-        ])
+        .arg("velocity")
         .assert()
-        .success();
+        .success()
+        .stdout(predicate::str::contains("Velocity"))
+        .stdout(predicate::str::contains("Average"))
+        .stdout(predicate::str::contains("Trend"));
+}
 
-    kuk_pm_in(&dir)
-        .args(["burndown", "--sprint", "s1"])
+#[test]
+fn velocity_json() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    let output = kuk_pm_in(&dir)
+        .args(["velocity", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["weeks"].is_array());
+    assert!(json["average"].is_number());
+    assert!(json["trend"].is_string());
+}
+
+/// Rewrite the default board so card `index` was created `created` days
+/// ago and made its recorded moves the given numbers of days ago, in order.
+fn backdate(dir: &TempDir, index: usize, created: i64, moves: &[i64]) {
+    let path = dir.path().join(".kuk/boards/default.json");
+    let mut board: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let days_ago = |days: i64| (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+    let card = &mut board["cards"][index];
+    card["created_at"] = days_ago(created).into();
+    let transitions = card["metadata"]["transitions"].as_array_mut().unwrap();
+    assert_eq!(transitions.len(), moves.len());
+    for (transition, days) in transitions.iter_mut().zip(moves) {
+        transition["at"] = days_ago(*days).into();
+    }
+    std::fs::write(&path, board.to_string()).unwrap();
+}
+
+#[test]
+fn velocity_counts_cards_in_the_week_they_moved_to_done() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Shipped"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "done"])
+        .assert()
+        .success();
+    backdate(&dir, 0, 30, &[15]);
+    // A later edit bumps updated_at to now, which must not count
+    kuk_in(&dir)
+        .args(["edit", "1", "--title", "Shipped it"])
+        .assert()
+        .success();
+
+    let output = kuk_pm_in(&dir)
+        .args(["velocity", "--json", "--weeks", "4"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let weeks = json["weeks"].as_array().unwrap();
+    let moved = (chrono::Utc::now() - chrono::Duration::days(15)).date_naive();
+    for week in weeks {
+        let start: chrono::NaiveDate = week["week_start"].as_str().unwrap().parse().unwrap();
+        let expected = usize::from((start..start + chrono::Days::new(7)).contains(&moved));
+        assert_eq!(week["count"], expected, "{weeks:?}");
+    }
+    assert_eq!(weeks.iter().filter(|w| w["count"] == 1).count(), 1);
+}
+
+#[test]
+fn velocity_custom_weeks() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .args(["velocity", "--weeks", "8"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Velocity (last 8 weeks)"));
+}
+
+#[test]
+fn velocity_weeks_follow_configured_week_start() {
+    use chrono::Datelike;
+
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    std::fs::write(
+        dir.path().join(".kuk/pm.json"),
+        r#"{"version": "0.1.0", "auto_branch": false, "timezone": "+09:00", "week_start": "sunday"}"#,
+    )
+    .unwrap();
+
+    let output = kuk_pm_in(&dir)
+        .args(["velocity", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    for week in json["weeks"].as_array().unwrap() {
+        let start: chrono::NaiveDate = week["week_start"].as_str().unwrap().parse().unwrap();
+        assert_eq!(start.weekday(), chrono::Weekday::Sun);
+    }
+}
+
+#[test]
+fn reports_follow_locale_in_pm_json() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    std::fs::write(
+        dir.path().join(".kuk/pm.json"),
+        r#"{"version": "0.1.0", "auto_branch": false, "locale": "de-DE"}"#,
+    )
+    .unwrap();
+
+    let window = ["--since", "2026-01-05", "--until", "2026-03-29"];
+    kuk_pm_in(&dir)
+        .arg("velocity")
+        .args(window)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Velocity (05.01.2026 → 29.03.2026, 12 weeks)",
+        ))
+        .stdout(predicate::str::contains("Average: 0,0 cards/week"));
+
+    let output = kuk_pm_in(&dir)
+        .args(["velocity", "--json"])
+        .args(window)
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["weeks"][0]["week_start"], "2026-01-05");
+
+    std::fs::write(
+        dir.path().join(".kuk/pm.json"),
+        r#"{"version": "0.1.0", "auto_branch": false, "locale": "xx-YY"}"#,
+    )
+    .unwrap();
+    kuk_pm_in(&dir)
+        .arg("velocity")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported locale: xx-YY"));
+}
+
+#[test]
+fn invalid_timezone_in_pm_json_fails() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    std::fs::write(
+        dir.path().join(".kuk/pm.json"),
+        r#"{"version": "0.1.0", "auto_branch": false, "timezone": "Mars/Olympus"}"#,
+    )
+    .unwrap();
+
+    kuk_pm_in(&dir)
+        .arg("velocity")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid timezone"));
+}
+
+#[test]
+fn velocity_with_window() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .args(["velocity", "--since", "2026-01-05", "--until", "2026-03-29"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Velocity (2026-01-05 → 2026-03-29, 12 weeks)",
+        ));
+}
+
+#[test]
+fn velocity_compare_previous() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Shipped"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "done"])
+        .assert()
+        .success();
+
+    kuk_pm_in(&dir)
+        .args(["velocity", "--compare-previous"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Compared to previous period"))
+        .stdout(predicate::str::contains("Completed"));
+
+    let output = kuk_pm_in(&dir)
+        .args(["velocity", "--compare-previous", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["current"]["weeks"].as_array().unwrap().len(), 4);
+    assert_eq!(json["previous"]["weeks"].as_array().unwrap().len(), 4);
+    assert_eq!(json["changes"][0]["metric"], "Completed");
+    assert_eq!(json["changes"][0]["current"], 1.0);
+    assert_eq!(json["changes"][0]["previous"], 0.0);
+}
+
+#[test]
+fn velocity_normalize_counts_commit_authors() {
+    let dir = TempDir::new().unwrap();
+    init_git_and_kuk(&dir);
+    kuk_in(&dir).args(["add", "Shipped"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "done"])
+        .assert()
+        .success();
+    add_git_commits(&dir, &["feat: search"]);
+    let commit_as = |name: &str| {
+        std::process::Command::new("git")
+            .args(["-c", &format!("user.name={name}")])
+            .args(["-c", &format!("user.email={name}@example.com")])
+            .args(["commit", "--allow-empty", "-m", "chore: tidy"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+    };
+    commit_as("bo");
+
+    kuk_pm_in(&dir)
+        .arg("velocity")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("contributor").not());
+    kuk_pm_in(&dir)
+        .args(["velocity", "--normalize"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 contributors"))
+        .stdout(predicate::str::contains(
+            "Per contributor: 0.50 cards/contributor-week",
+        ));
+
+    // A new commit isn't served from the cache
+    commit_as("cy");
+    let output = kuk_pm_in(&dir)
+        .args(["velocity", "--normalize", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["weeks"][3]["contributors"], 3);
+    assert_eq!(json["normalized"], 1.0 / 3.0);
+
+    let plain = TempDir::new().unwrap();
+    init_both(&plain);
+    kuk_pm_in(&plain)
+        .args(["velocity", "--normalize"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn stats_compare_previous() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    let output = kuk_pm_in(&dir)
+        .args([
+            "stats",
+            "--since",
+            "2026-03-01",
+            "--until",
+            "2026-03-31",
+            "--compare-previous",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["previous"]["window"]["since"], "2026-01-29");
+    assert_eq!(json["previous"]["window"]["until"], "2026-02-28");
+}
+
+#[test]
+fn reports_reject_inverted_window() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    for cmd in ["velocity", "stats"] {
+        kuk_pm_in(&dir)
+            .args([cmd, "--since", "2026-04-01", "--until", "2026-01-01"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "--since must not be after --until",
+            ));
+    }
+}
+
+#[test]
+fn velocity_before_init_fails() {
+    let dir = TempDir::new().unwrap();
+    kuk_pm_in(&dir)
+        .arg("velocity")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("kuk init"));
+}
+
+#[test]
+fn reports_run_against_target_repo() {
+    let here = TempDir::new().unwrap();
+    let other = TempDir::new().unwrap();
+    init_both(&other);
+    kuk_in(&other)
+        .args(["add", "Shipped", "--to", "done"])
+        .assert()
+        .success();
+
+    for report in ["velocity", "stats", "roadmap"] {
+        kuk_pm_in(&here)
+            .args([report, "--target"])
+            .arg(other.path())
+            .assert()
+            .success();
+    }
+    kuk_pm_in(&here)
+        .args(["burndown", "--target"])
+        .arg(other.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No active sprint"));
+    kuk_pm_in(&here)
+        .args(["velocity", "--target", "no-such-project"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No repo or kuk project named"));
+}
+
+#[test]
+fn reports_target_all_covers_indexed_projects() {
+    let home = TempDir::new().unwrap();
+    let ready = TempDir::new().unwrap();
+    let bare = TempDir::new().unwrap();
+    for dir in [&ready, &bare] {
+        kuk_in(dir)
+            .env("HOME", home.path())
+            .arg("init")
+            .assert()
+            .success();
+    }
+    kuk_pm_in(&ready).arg("init").assert().success();
+    std::fs::remove_dir_all(bare.path().join(".kuk")).unwrap();
+
+    let output = kuk_pm()
+        .env("HOME", home.path())
+        .args(["stats", "--target", "all", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let projects = json.as_array().unwrap();
+    assert_eq!(projects.len(), 2);
+    assert!(projects[0]["report"]["total_cards"].is_number());
+    assert!(projects[1]["error"].as_str().unwrap().contains("kuk init"));
+}
+
+// ─── Calendar ────────────────────────────────────────────────
+
+#[test]
+fn calendar_shows_default_working_days() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .arg("calendar")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Working days: Mon, Tue, Wed, Thu, Fri",
+        ));
+}
+
+#[test]
+fn calendar_manages_entries() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .args(["calendar", "workdays", "sun,mon,tue,wed,thu"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Mon, Tue, Wed, Thu, Sun"));
+    kuk_pm_in(&dir)
+        .args(["calendar", "holiday", "2026-12-25", "--name", "Christmas"])
+        .assert()
+        .success();
+    kuk_pm_in(&dir)
+        .args([
+            "calendar",
+            "vacation",
+            "alice",
+            "--from",
+            "2026-08-03",
+            "--to",
+            "2026-08-14",
+        ])
+        .assert()
+        .success();
+
+    let output = kuk_pm_in(&dir)
+        .args(["calendar", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["working_days"].as_array().unwrap().len(), 5);
+    assert_eq!(json["holidays"][0]["name"], "Christmas");
+    assert_eq!(json["vacations"][0]["person"], "alice");
+
+    kuk_pm_in(&dir)
+        .args(["calendar", "remove", "alice"])
+        .assert()
+        .success();
+    kuk_pm_in(&dir)
+        .args(["calendar", "remove", "2026-12-25"])
+        .assert()
+        .success();
+    kuk_pm_in(&dir)
+        .args(["calendar", "remove", "bob"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No holiday or vacation matches"));
+}
+
+#[test]
+fn calendar_rejects_bad_input() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .args(["calendar", "workdays", "mon,funday"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid weekday: funday"));
+    kuk_pm_in(&dir)
+        .args([
+            "calendar",
+            "vacation",
+            "bob",
+            "--from",
+            "2026-08-10",
+            "--to",
+            "2026-08-01",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid date"));
+}
+
+// ─── Stats ───────────────────────────────────────────────────
+
+#[test]
+fn stats_shows_report() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Project Statistics"))
+        .stdout(predicate::str::contains("Work in Progress"))
+        .stdout(predicate::str::contains("Throughput"));
+}
+
+#[test]
+fn stats_json() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    let output = kuk_pm_in(&dir).args(["stats", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["board_name"], "default");
+    assert!(json["wip_count"].is_number());
+}
+
+#[test]
+fn stats_json_times_columns_by_recorded_moves() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Shipped"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "doing"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "done"])
+        .assert()
+        .success();
+    // Created 15 days ago, 2 days in todo and 5 in doing, edited today
+    backdate(&dir, 0, 15, &[13, 8]);
+    kuk_in(&dir)
+        .args(["edit", "1", "--title", "Shipped it"])
+        .assert()
+        .success();
+
+    let output = kuk_pm_in(&dir).args(["stats", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["avg_cycle_days"], 7.0);
+    assert_eq!(json["done_7d"], 0);
+    assert_eq!(
+        json["column_times"],
+        serde_json::json!([
+            {"column": "todo", "avg_days": 2.0, "cards": 1},
+            {"column": "doing", "avg_days": 5.0, "cards": 1},
+        ])
+    );
+}
+
+#[test]
+fn stats_until_excludes_later_completions() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Shipped"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "done"])
+        .assert()
+        .success();
+
+    let output = kuk_pm_in(&dir)
+        .args(["stats", "--until", "2000-01-31", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["done_30d"], 0);
+    assert_eq!(json["window"]["until"], "2000-01-31");
+    assert!(json["avg_cycle_days"].is_null());
+}
+
+#[test]
+fn stats_with_cards() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir).args(["add", "Task A"]).assert().success();
+    kuk_in(&dir).args(["add", "Task B"]).assert().success();
+
+    kuk_pm_in(&dir)
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 active"));
+}
+
+#[test]
+fn wip_history_lists_columns_over_their_limit() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir)
+        .args(["column", "set-wip", "doing", "1"])
+        .assert()
+        .success();
+    kuk_in(&dir).args(["add", "Task A"]).assert().success();
+    kuk_in(&dir).args(["add", "Task B"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "doing"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["move", "2", "--to", "doing", "--force"])
+        .assert()
+        .success();
+
+    kuk_pm_in(&dir)
+        .arg("wip-history")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("WIP limit violations (1)"))
+        .stdout(predicate::str::contains("default/doing  limit 1, peak 2"))
+        .stdout(predicate::str::contains("KUK-2  Task B"));
+
+    let output = kuk_pm_in(&dir)
+        .args(["wip-history", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let violation = &json["violations"][0];
+    assert_eq!(violation["column"], "doing");
+    assert!(violation["end"].is_null());
+    assert_eq!(violation["cards"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn stats_before_init_fails() {
+    let dir = TempDir::new().unwrap();
+    kuk_pm_in(&dir)
+        .arg("stats")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("kuk init"));
+}
+
+// ─── Burndown ────────────────────────────────────────────────
+
+#[test]
+fn burndown_with_sprint() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    // Create a sprint
+    kuk_pm_in(&dir)
+        .args([
+            "sprint",
+            "create",
+            "s1",
+            "--start",
+            "2026-02-01",
+            "--end",
+            "2026-03-01",
+        ])
+        .assert()
+        .success();
+
+    kuk_pm_in(&dir)
+        .args(["burndown", "--sprint", "s1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Burndown: s1"));
+}
+
+#[test]
+fn burndown_shows_milestone_progress() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    std::fs::write(
+        dir.path().join(".kuk/sprints.json"),
+        r#"[{
+            "name": "s1",
+            "start": "2026-02-01",
+            "end": "2026-03-01",
+            "status": "active",
+            "milestone": {
+                "number": 7,
+                "url": "https://github.com/u/r/milestone/7",
+                "open_issues": 1,
+                "closed_issues": 1
+            }
+        }]"#,
+    )
+    .unwrap();
+
+    kuk_pm_in(&dir)
+        .arg("burndown")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Burndown: s1"));
+        .stdout(predicate::str::contains(
+            "Milestone:   #7 — 1/2 issues closed (50%)",
+        ));
+
+    let output = kuk_pm_in(&dir)
+        .args(["burndown", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["milestone"]["number"], 7);
 }
 
 #[test]
@@ -789,6 +1719,45 @@ fn burndown_json() {
     assert!(json["total_cards"].is_number());
 }
 
+#[test]
+fn burndown_and_velocity_oneline() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_pm_in(&dir)
+        .args([
+            "sprint",
+            "create",
+            "s1",
+            "--start",
+            "2026-02-01",
+            "--end",
+            "2026-02-14",
+        ])
+        .assert()
+        .success();
+
+    let output = kuk_pm_in(&dir)
+        .args(["burndown", "--sprint", "s1", "--oneline"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.starts_with("s1: 0/0 done, day 14/14, "), "{stdout}");
+
+    kuk_pm_in(&dir)
+        .args(["velocity", "--oneline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "velocity: 0.0/wk ▁▁▁▁ → stable",
+        ));
+    kuk_pm_in(&dir)
+        .args(["velocity", "--oneline", "--compare-previous"])
+        .assert()
+        .failure();
+}
+
 #[test]
 fn burndown_before_init_fails() {
     let dir = TempDir::new().unwrap();
@@ -912,6 +1881,212 @@ fn release_notes_categorizes_commits() {
     assert_eq!(json["fixes"].as_array().unwrap().len(), 1);
 }
 
+#[test]
+fn release_notes_range_leaves_out_released_cherry_picks() {
+    let dir = TempDir::new().unwrap();
+    init_git_and_kuk(&dir);
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+    };
+
+    git(&["tag", "v1.2.0"]);
+    git(&["branch", "release/1.2"]);
+    add_git_commits(&dir, &["feat: dark mode", "fix: crash on empty input"]);
+    git(&["checkout", "-q", "release/1.2"]);
+    git(&["cherry-pick", "HEAD@{1}"]);
+    git(&["checkout", "-q", "-"]);
+
+    kuk_pm_in(&dir)
+        .args(["release-notes", "--from", "v1.2.0", "--to", "release/1.2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Release Notes (v1.2.0..release/1.2)",
+        ))
+        .stdout(predicate::str::contains("fix: crash on empty input"))
+        .stdout(predicate::str::contains("dark mode").not());
+
+    let output = kuk_pm_in(&dir)
+        .args([
+            "release-notes",
+            "--json",
+            "--from",
+            "v1.2.0",
+            "--exclude",
+            "release/1.2",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["features"][0], "feat: dark mode");
+    assert_eq!(json["fixes"].as_array().unwrap().len(), 0);
+    assert_eq!(json["already_released"][0], "fix: crash on empty input");
+}
+
+#[test]
+fn release_notes_start_from_latest_version_tag() {
+    let dir = TempDir::new().unwrap();
+    init_git_and_kuk(&dir);
+    let tag = |name: &str| {
+        std::process::Command::new("git")
+            .args(["tag", name])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+    };
+
+    tag("v1.9.0");
+    add_git_commits(&dir, &["feat: search"]);
+    tag("v1.10.0");
+    add_git_commits(&dir, &["fix: crash"]);
+    tag("v2.0.0-rc.1");
+    add_git_commits(&dir, &["feat: dark mode"]);
+
+    kuk_pm_in(&dir)
+        .arg("tags")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("v2.0.0-rc.1  "))
+        .stdout(predicate::str::contains("pre-release"))
+        .stdout(predicate::str::is_match(r"v1\.10\.0 .*← latest").unwrap());
+
+    let notes = |args: &[&str]| -> serde_json::Value {
+        let output = kuk_pm_in(&dir)
+            .args(["release-notes", "--json"])
+            .args(args)
+            .output()
+            .unwrap();
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+    let json = notes(&[]);
+    assert_eq!(json["features"].as_array().unwrap().len(), 1);
+    assert_eq!(json["fixes"][0], "fix: crash");
+    let json = notes(&["--pre-release"]);
+    assert_eq!(json["features"][0], "feat: dark mode");
+    assert_eq!(json["fixes"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn release_notes_count_contributors() {
+    let dir = TempDir::new().unwrap();
+    init_git_and_kuk(&dir);
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+    };
+
+    git(&["tag", "v1.0.0"]);
+    add_git_commits(&dir, &["feat: search", "fix: crash"]);
+    git(&[
+        "-c",
+        "user.name=Bo",
+        "-c",
+        "user.email=bo@example.com",
+        "commit",
+        "--allow-empty",
+        "-m",
+        "docs: typo",
+    ]);
+
+    kuk_pm_in(&dir)
+        .arg("release-notes")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Contributors").not());
+    kuk_pm_in(&dir)
+        .args(["release-notes", "--contributors"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Contributors (2, 1 first-time)"))
+        .stdout(predicate::str::contains("  - Test (2 commits)"))
+        .stdout(predicate::str::contains(
+            "  - Bo (1 commit, first contribution)",
+        ));
+
+    let output = kuk_pm_in(&dir)
+        .args(["release-notes", "--contributors", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let contributors = json["contributors"].as_array().unwrap();
+    assert_eq!(contributors.len(), 2);
+    assert_eq!(contributors[1]["email"], "bo@example.com");
+    assert_eq!(contributors[1]["first_time"], true);
+}
+
+#[test]
+fn trailers_link_commits_to_cards() {
+    let dir = TempDir::new().unwrap();
+    init_git_and_kuk(&dir);
+    kuk_in(&dir).args(["add", "Login page"]).assert().success();
+    kuk_in(&dir).args(["add", "Crash fix"]).assert().success();
+    kuk_pm_in(&dir)
+        .args([
+            "link",
+            "2",
+            "https://github.com/u/r/issues/17",
+            "--no-fetch",
+        ])
+        .assert()
+        .success();
+    std::process::Command::new("git")
+        .args(["tag", "v1.0.0"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    for (subject, trailer) in [
+        ("feat: login page", "Kuk-Card: #1"),
+        ("fix: crash on empty input", "Fixes: #17"),
+    ] {
+        std::process::Command::new("git")
+            .args(["commit", "--allow-empty", "-m", subject, "-m", trailer])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+    }
+
+    kuk_pm_in(&dir)
+        .arg("release-notes")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cards"))
+        .stdout(predicate::str::contains("  - Login page [todo]"))
+        .stdout(predicate::str::contains("  - Crash fix [todo]"));
+
+    kuk_pm_in(&dir)
+        .args(["backfill-links", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would link 2 commit(s)"));
+    kuk_pm_in(&dir)
+        .args(["backfill-links", "--since", "v1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feat: login page → Login page"))
+        .stdout(predicate::str::contains(
+            "Linked 2 commit(s) to cards (2 scanned)",
+        ));
+    kuk_pm_in(&dir)
+        .arg("backfill-links")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Linked 0 commit(s)"));
+
+    let output = kuk_pm_in(&dir)
+        .args(["meta", "1", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["pm"]["commits"].as_array().unwrap().len(), 1);
+}
+
 #[test]
 fn release_notes_without_git_fails() {
     let dir = TempDir::new().unwrap();
@@ -939,20 +2114,194 @@ fn sync_dry_run_no_linked_cards() {
     let dir = TempDir::new().unwrap();
     init_both(&dir);
 
-    // sync with no linked cards should succeed and show "up to date"
-    // (only if gh CLI is available, otherwise we get a graceful error)
+    // Nothing is linked, so no forge CLI is needed
+    kuk_pm_in(&dir)
+        .args(["sync", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"));
+}
+
+#[test]
+fn sync_skips_board_disabled_in_pm_json() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    std::fs::write(
+        dir.path().join(".kuk/pm.json"),
+        r#"{"version": "0.1.0", "auto_branch": false, "sync_boards": {"default": false}}"#,
+    )
+    .unwrap();
+
+    kuk_pm_in(&dir)
+        .args(["sync", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Sync is disabled for board 'default'",
+        ));
+}
+
+#[test]
+fn sync_unknown_board_fails() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .args(["sync", "--dry-run", "--board", "nope"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn sync_accepts_label_and_column_scope() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .args([
+            "sync",
+            "--dry-run",
+            "--label",
+            "bug",
+            "--columns",
+            "doing,review",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"));
+}
+
+#[test]
+fn sync_create_missing_dry_run_respects_label_filter() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    kuk_in(&dir)
+        .args(["add", "Publish me", "--label", "public"])
+        .assert()
+        .success();
+    kuk_in(&dir).args(["add", "Keep local"]).assert().success();
+    kuk_in(&dir)
+        .args(["add", "Already linked", "--label", "public"])
+        .assert()
+        .success();
+    kuk_pm_in(&dir)
+        .args(["link", "3", "https://github.com/u/r/issues/1", "--no-fetch"])
+        .assert()
+        .success();
+
     let output = kuk_pm_in(&dir)
+        .args([
+            "sync",
+            "--create-missing",
+            "--dry-run",
+            "--label",
+            "public",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let created: Vec<&serde_json::Value> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|a| a["action"] == "updateurl")
+        .collect();
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0]["card_title"], "Publish me");
+    assert_eq!(created[0]["detail"], "would create issue");
+}
+
+#[test]
+fn sync_runs_are_logged() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+
+    kuk_pm_in(&dir)
+        .args(["sync", "log"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No sync runs recorded"));
+
+    kuk_pm_in(&dir)
         .args(["sync", "--dry-run"])
+        .assert()
+        .success();
+    kuk_pm_in(&dir).arg("sync").assert().success();
+
+    let output = kuk_pm_in(&dir)
+        .args(["sync", "log", "--json"])
         .output()
         .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let runs = json.as_array().unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0]["dry_run"], true);
+    assert_eq!(runs[1]["dry_run"], false);
 
-    // Either succeeds (gh available) or fails with gh error (acceptable)
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(
-        stdout.contains("up to date") || stderr.contains("gh"),
-        "Expected 'up to date' or gh error, got stdout={stdout} stderr={stderr}"
-    );
+    let output = kuk_pm_in(&dir)
+        .args(["sync", "log", "--last", "1", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 1);
+    assert_eq!(json[0]["dry_run"], false);
+}
+
+#[test]
+fn link_pr_and_sync_replay_a_forge_fixture() {
+    let dir = TempDir::new().unwrap();
+    init_git_and_kuk(&dir);
+    kuk_in(&dir).args(["add", "Login page"]).assert().success();
+    let fixture = dir.path().join("forge.json");
+    std::fs::write(
+        &fixture,
+        r#"{
+            "states": {
+                "https://github.com/u/r/issues/4": "closed",
+                "https://github.com/u/r/pull/5": "merged"
+            },
+            "issues": {
+                "https://github.com/u/r/issues/4": {"assignee": "octocat", "labels": ["auth"]}
+            },
+            "created": ["https://github.com/u/r/pull/5"]
+        }"#,
+    )
+    .unwrap();
+    let replay = |args: &[&str]| {
+        let mut cmd = kuk_pm_in(&dir);
+        cmd.env("KUK_PM_SYNC_FIXTURE", &fixture).args(args);
+        cmd
+    };
+
+    replay(&["link", "1", "https://github.com/u/r/issues/4"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning").not());
+    replay(&["pr", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("https://github.com/u/r/pull/5"));
+    replay(&["sync"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[SYNC] Login page — todo → done (issue closed, PR merged)",
+        ));
+
+    let output = kuk_in(&dir).args(["--json", "show", "1"]).output().unwrap();
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["column"], "done");
+    assert_eq!(card["assignee"], "octocat");
+    assert_eq!(card["labels"], serde_json::json!(["auth"]));
+
+    std::fs::write(&fixture, "{not json").unwrap();
+    replay(&["sync"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid sync fixture"));
 }
 
 // ─── PR ──────────────────────────────────────────────────────
@@ -992,6 +2341,101 @@ fn pr_nonexistent_card_fails() {
         .stderr(predicate::str::contains("Card not found"));
 }
 
+// ─── Export ──────────────────────────────────────────────────
+
+#[test]
+fn export_card_creates_and_links_the_issue() {
+    let dir = TempDir::new().unwrap();
+    init_git_and_kuk(&dir);
+    kuk_in(&dir)
+        .args(["add", "Dark mode", "--label", "ui"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["edit", "1", "--description=* [ ] Toggle"])
+        .assert()
+        .success();
+    let fixture = dir.path().join("forge.json");
+    std::fs::write(
+        &fixture,
+        r#"{"created": ["https://github.com/u/r/issues/7"]}"#,
+    )
+    .unwrap();
+    let replay = |args: &[&str]| {
+        let mut cmd = kuk_pm_in(&dir);
+        cmd.env("KUK_PM_SYNC_FIXTURE", &fixture).args(args);
+        cmd
+    };
+
+    replay(&["export", "card", "1"])
+        .assert()
+        .success()
+        .stdout("# Dark mode\n\n- [ ] Toggle\n\n---\nLabels: `ui`\n");
+    replay(&["export", "card", "1", "--create"])
+        .assert()
+        .success()
+        .stdout("Created issue: https://github.com/u/r/issues/7\n  Card: Dark mode\n");
+    replay(&["export", "card", "1", "--create"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "already tracked by https://github.com/u/r/issues/7",
+        ));
+}
+
+// ─── MCP ─────────────────────────────────────────────────────
+
+#[test]
+fn mcp_with_board_serves_both_tool_sets() {
+    let dir = TempDir::new().unwrap();
+    init_both(&dir);
+    let session = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"clientInfo":{"name":"claude-code"}}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+        r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"kuk_add_card","arguments":{"title":"Agent card"}}}"#,
+        r#"{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{"name":"pm_stats","arguments":{}}}"#,
+    ];
+    let responses = |args: &[&str]| -> Vec<serde_json::Value> {
+        let output = kuk_pm_in(&dir)
+            .args(args)
+            .write_stdin(session.join("\n"))
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    };
+    let tool_names = |list: &serde_json::Value| -> Vec<String> {
+        list["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let alone = responses(&["mcp"]);
+    assert!(tool_names(&alone[1]).iter().all(|n| n.starts_with("pm_")));
+    assert_eq!(alone[2]["error"]["message"], "Unknown tool: kuk_add_card");
+
+    let both = responses(&["mcp", "--with-board"]);
+    let names = tool_names(&both[1]);
+    assert!(names.contains(&"pm_stats".to_string()));
+    assert!(names.contains(&"kuk_add_card".to_string()));
+    assert!(both[2]["result"].is_object(), "{}", both[2]);
+    assert!(both[3]["result"].is_object(), "{}", both[3]);
+
+    kuk_in(&dir)
+        .args(["log", "--origin", "mcp"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Agent card: created in todo  via mcp (claude-code)",
+        ));
+}
+
 // ─── Commands before init ────────────────────────────────────
 
 #[test]
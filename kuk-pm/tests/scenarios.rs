@@ -0,0 +1,287 @@
+//! End-to-end workflows across `kuk` and `kuk-pm`, checked against golden
+//! transcripts in `tests/golden/`. Each scenario runs real commands in a
+//! fresh repo and records what they print, with timestamps, ULIDs and the
+//! temp paths swapped for placeholders so runs compare equal.
+//!
+//! After an intended output change, rewrite the transcripts with
+//! `UPDATE_GOLDEN=1 cargo test -p kuk-pm --test scenarios` and review the
+//! diff.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+/// A repo with git, kuk and kuk-pm set up, and the transcript so far.
+struct Scenario {
+    repo: TempDir,
+    home: TempDir,
+    transcript: String,
+}
+
+impl Scenario {
+    fn new() -> Self {
+        let scenario = Scenario {
+            repo: TempDir::new().unwrap(),
+            home: TempDir::new().unwrap(),
+            transcript: String::new(),
+        };
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.email", "test@test.com"],
+            &["config", "user.name", "Test"],
+            &["commit", "-q", "--allow-empty", "-m", "Initial commit"],
+        ] {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(scenario.repo.path())
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?}");
+        }
+        scenario
+    }
+
+    fn kuk(&mut self, args: &[&str]) -> &mut Self {
+        self.run("kuk", args)
+    }
+
+    fn kuk_pm(&mut self, args: &[&str]) -> &mut Self {
+        self.run("kuk-pm", args)
+    }
+
+    /// Run `bin` in the repo and add the command and its output to the
+    /// transcript, with the exit code when it failed.
+    #[allow(deprecated)]
+    fn run(&mut self, bin: &str, args: &[&str]) -> &mut Self {
+        let output = Command::cargo_bin(bin)
+            .unwrap()
+            .arg("--repo")
+            .arg(self.repo.path())
+            .args(args)
+            .env("HOME", self.home.path())
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_TOKEN")
+            .env_remove("GH_TOKEN")
+            .output()
+            .unwrap();
+        let _ = writeln!(self.transcript, "$ {bin} {}", args.join(" "));
+        self.transcript
+            .push_str(&String::from_utf8_lossy(&output.stdout));
+        self.transcript
+            .push_str(&String::from_utf8_lossy(&output.stderr));
+        if !output.status.success() {
+            let code = output.status.code().unwrap_or(-1);
+            let _ = writeln!(self.transcript, "[exit {code}]");
+        }
+        self.transcript.push('\n');
+        self
+    }
+
+    /// Compare the normalized transcript with `tests/golden/<name>.txt`,
+    /// or write it there when `UPDATE_GOLDEN` is set.
+    fn check(&self, name: &str) {
+        let actual = normalize(&self.transcript, &self.replacements());
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(format!("{name}.txt"));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &actual).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "{}: {e}. Run with UPDATE_GOLDEN=1 to create it.",
+                path.display()
+            )
+        });
+        if expected != actual {
+            let line = expected
+                .lines()
+                .zip(actual.lines())
+                .position(|(e, a)| e != a)
+                .unwrap_or(expected.lines().count().min(actual.lines().count()));
+            panic!(
+                "{name}: output differs from {} at line {}.\n\
+                 Rerun with UPDATE_GOLDEN=1 if the change is intended.\n\n\
+                 --- expected\n{}\n--- actual\n{}",
+                path.display(),
+                line + 1,
+                expected.lines().nth(line).unwrap_or("<end>"),
+                actual.lines().nth(line).unwrap_or("<end>"),
+            );
+        }
+    }
+
+    /// Paths that differ between runs, longest first.
+    fn replacements(&self) -> Vec<(String, &'static str)> {
+        let mut paths = Vec::new();
+        for (dir, name) in [(&self.repo, "<REPO>"), (&self.home, "<HOME>")] {
+            let path = dir.path().to_string_lossy().to_string();
+            if let Ok(real) = dir.path().canonicalize() {
+                paths.push((real.to_string_lossy().to_string(), name));
+            }
+            paths.push((path, name));
+        }
+        paths.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+        paths
+    }
+}
+
+/// Replace what changes from run to run: the temp paths, then timestamps
+/// (`<TIME>`), and ULIDs (`<ULID>`).
+fn normalize(text: &str, paths: &[(String, &str)]) -> String {
+    let mut text = text.to_string();
+    for (path, name) in paths {
+        text = text.replace(path.as_str(), name);
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let at_boundary = i == 0 || !chars[i - 1].is_ascii_alphanumeric();
+        if at_boundary && let Some(len) = timestamp_len(&chars[i..]) {
+            out.push_str("<TIME>");
+            i += len;
+        } else if at_boundary && is_ulid(&chars[i..]) {
+            out.push_str("<ULID>");
+            i += 26;
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// The length of an RFC 3339 timestamp (`2026-03-02T09:14:05.123Z`) or a
+/// printed one (`2026-03-02 09:14 UTC`) at the start of `s`. Dates alone
+/// are left alone, since scenarios pick their own.
+fn timestamp_len(s: &[char]) -> Option<usize> {
+    let matches = |pattern: &str, at: usize| {
+        pattern.chars().enumerate().all(|(j, p)| {
+            s.get(at + j).is_some_and(|&c| match p {
+                '9' => c.is_ascii_digit(),
+                p => c == p,
+            })
+        })
+    };
+    if !matches("9999-99-99", 0) {
+        return None;
+    }
+    if matches(" 99:99 UTC", 10) {
+        return Some(20);
+    }
+    if !matches("T99:99:99", 10) {
+        return None;
+    }
+    let mut len = 19;
+    if s.get(len) == Some(&'.') {
+        len += 1;
+        while s.get(len).is_some_and(|c| c.is_ascii_digit()) {
+            len += 1;
+        }
+    }
+    if s.get(len) == Some(&'Z') {
+        len += 1;
+    } else if matches("+99:99", len) {
+        len += 6;
+    }
+    Some(len)
+}
+
+/// Whether `s` starts with a 26-character ULID on its own.
+fn is_ulid(s: &[char]) -> bool {
+    s.len() >= 26
+        && s[..26]
+            .iter()
+            .all(|c| c.is_ascii_digit() || (c.is_ascii_uppercase() && !"ILOU".contains(*c)))
+        && s[..26].iter().any(|c| c.is_ascii_digit())
+        && s.get(26).is_none_or(|c| !c.is_ascii_alphanumeric())
+}
+
+// ─── Scenarios ───────────────────────────────────────────────
+
+#[test]
+fn sprint_workflow() {
+    let mut s = Scenario::new();
+    s.kuk(&["init"]).kuk_pm(&["init"]);
+    s.kuk(&["add", "Login page", "--label", "frontend"])
+        .kuk(&["add", "Session timeout", "--priority", "high"])
+        .kuk(&["add", "Publish docs", "--label", "public"])
+        .kuk(&["add", "Rate limits"])
+        .kuk(&["move", "2", "--to", "doing"])
+        .kuk(&["move", "KUK-4", "--to", "done"])
+        .kuk(&["list"]);
+
+    s.kuk_pm(&[
+        "sprint",
+        "create",
+        "Sprint 1",
+        "--start",
+        "2026-03-02",
+        "--end",
+        "2026-03-13",
+    ])
+    .kuk_pm(&[
+        "sprint",
+        "start",
+        "Sprint 1",
+        "--create-board",
+        "--move",
+        "KUK-1",
+        "--copy",
+        "KUK-2",
+    ])
+    .kuk_pm(&["sprint", "list"])
+    .kuk(&["list"]);
+
+    s.kuk_pm(&["sync", "--dry-run"]).kuk_pm(&[
+        "sync",
+        "--dry-run",
+        "--board",
+        "default",
+        "--create-missing",
+        "--label",
+        "public",
+        "--json",
+    ]);
+
+    s.kuk_pm(&["stats", "--json"])
+        .kuk_pm(&["burndown", "--json"])
+        .kuk(&["board", "switch", "Sprint-1"])
+        .kuk(&["list"])
+        .kuk(&["--json", "show", "1"])
+        .kuk(&["log", "--last", "3"]);
+    s.check("sprint_workflow");
+}
+
+#[test]
+fn mistakes_are_explained() {
+    let mut s = Scenario::new();
+    s.kuk_pm(&["init"])
+        .kuk(&["init"])
+        .kuk(&["init"])
+        .kuk(&["move", "1", "--to", "doing"])
+        .kuk(&["add", "Ship", "--to", "review"])
+        .kuk_pm(&["init"])
+        .kuk_pm(&["sprint", "start", "Sprint 9"])
+        .kuk_pm(&["burndown"]);
+    s.check("mistakes_are_explained");
+}
+
+#[test]
+fn normalize_hides_what_changes_between_runs() {
+    let paths = [("/tmp/.tmpAbC".to_string(), "<REPO>")];
+    let text = "at 2026-03-02T09:14:05.123456Z in /tmp/.tmpAbC/.kuk\n\
+                id 01HXYZ1234567890ABCDEFGHJK, seen 2026-03-02 09:14 UTC\n\
+                due 2026-03-13, KUK-12, DESCRIPTIONSARENOTULIDSATALL";
+    assert_eq!(
+        normalize(text, &paths),
+        "at <TIME> in <REPO>/.kuk\n\
+         id <ULID>, seen <TIME>\n\
+         due 2026-03-13, KUK-12, DESCRIPTIONSARENOTULIDSATALL"
+    );
+}
@@ -0,0 +1,394 @@
+//! Stable API for embedding kuk in other Rust tools.
+//!
+//! Everything reachable from this module follows semver: breaking changes
+//! only come with a major version bump. The other modules (`storage`, `cli`,
+//! `tui`, ...) back the kuk binaries and may change in any release.
+//!
+//! ```no_run
+//! use kuk::api::{CardBuilder, Project};
+//!
+//! # fn main() -> kuk::api::Result<()> {
+//! let project = Project::open(".")?;
+//! let mut board = project.default_board()?;
+//! let id = board
+//!     .add(CardBuilder::new("Write release notes").label("docs"))?
+//!     .id
+//!     .clone();
+//! board.move_card(&id, "doing")?;
+//! board.save()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::model::{Board, DuplicatePolicy};
+use crate::storage::Store;
+
+pub use crate::error::{KukError, Result};
+pub use crate::model::{Card, Column, LabelDef, Origin, Priority, Via};
+pub use crate::storage::{CardIter, CardQuery};
+
+/// A repository with a `.kuk/` directory.
+#[derive(Debug, Clone)]
+pub struct Project {
+    store: Store,
+}
+
+impl Project {
+    /// Open the kuk project at `root`.
+    pub fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let store = Store::new(root.as_ref());
+        if !store.is_initialized() {
+            return Err(KukError::NotInitialized);
+        }
+        Ok(Self { store })
+    }
+
+    /// Create `.kuk/` at `root` with the default board, like `kuk init`.
+    pub fn init(root: impl AsRef<Path>) -> Result<Self> {
+        let store = Store::new(root.as_ref());
+        store.init()?;
+        Ok(Self { store })
+    }
+
+    /// Attribute the changes saved through this project to `origin`, as
+    /// the kuk CLI, TUI and servers do for their own.
+    pub fn with_origin(self, origin: Origin) -> Self {
+        Self {
+            store: self.store.with_origin(origin),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        self.store.repo_root()
+    }
+
+    /// Names of all boards, sorted.
+    pub fn boards(&self) -> Result<Vec<String>> {
+        self.store.list_boards()
+    }
+
+    /// The board `kuk` commands act on when none is named.
+    pub fn default_board(&self) -> Result<BoardHandle> {
+        let config = self.store.load_config()?;
+        self.board(&config.default_board)
+    }
+
+    pub fn board(&self, name: &str) -> Result<BoardHandle> {
+        let config = self.store.load_config()?;
+        Ok(BoardHandle {
+            store: self.store.clone(),
+            board: self.store.load_board(name)?,
+            duplicates: config.duplicates,
+        })
+    }
+
+    /// Create an empty board with the given columns.
+    pub fn create_board(&self, name: &str, columns: &[&str]) -> Result<BoardHandle> {
+        let columns = columns.iter().map(|name| Column::new(*name)).collect();
+        self.store.create_board(name, columns)?;
+        self.board(name)
+    }
+
+    /// Cards across boards matching `query`, loaded one board at a time.
+    pub fn cards(&self, query: CardQuery) -> Result<CardIter<'_>> {
+        self.store.iter_cards(query)
+    }
+}
+
+/// A board loaded into memory. Changes apply to the in-memory copy and are
+/// written by `save`.
+///
+/// Card arguments take either a card id or a 1-based display number, as on
+/// the command line.
+#[derive(Debug, Clone)]
+pub struct BoardHandle {
+    store: Store,
+    board: Board,
+    duplicates: DuplicatePolicy,
+}
+
+impl BoardHandle {
+    pub fn name(&self) -> &str {
+        &self.board.name
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.board.columns
+    }
+
+    pub fn labels(&self) -> &[LabelDef] {
+        &self.board.labels
+    }
+
+    /// Active cards in display order: by column, then position.
+    pub fn cards(&self) -> Vec<&Card> {
+        self.board
+            .columns
+            .iter()
+            .flat_map(|c| self.board.column_cards(&c.name))
+            .collect()
+    }
+
+    /// Active cards in `column`, in display order.
+    pub fn column_cards(&self, column: &str) -> Vec<&Card> {
+        self.board.column_cards(column)
+    }
+
+    pub fn card(&self, id_or_num: &str) -> Option<&Card> {
+        let id = self.board.resolve_card_id(id_or_num)?;
+        self.board.find_card(&id)
+    }
+
+    /// Add a card at the bottom of its column. Fails on an unknown column,
+    /// on labels a strict board doesn't define, and on a similar title when
+    /// the project rejects duplicates and the builder isn't forced.
+    pub fn add(&mut self, builder: CardBuilder) -> Result<&Card> {
+        let column = match builder.column {
+            Some(column) => column,
+            None => self
+                .board
+                .columns
+                .first()
+                .map(|c| c.name.clone())
+                .ok_or_else(|| KukError::Other(format!("Board {} has no columns", self.name())))?,
+        };
+        if !self.board.has_column(&column) {
+            return Err(KukError::ColumnNotFound(column));
+        }
+        self.board
+            .check_labels(&builder.labels)
+            .map_err(|l| KukError::UnknownLabel(l.into()))?;
+        if self.duplicates == DuplicatePolicy::Reject
+            && !builder.force
+            && let Some(dup) = self.board.duplicate_of(&builder.title, self.duplicates)
+        {
+            return Err(KukError::DuplicateCard(format!(
+                "{} ({})",
+                dup.title, dup.column
+            )));
+        }
+
+        let mut card = Card::new(builder.title, &column);
+        card.order = self.board.next_order(&column);
+        card.description = builder.description;
+        card.assignee = builder.assignee;
+        card.labels = builder.labels;
+        card.due = builder.due;
+        card.priority = builder.priority;
+        card.metadata = builder.metadata;
+        self.board.cards.push(card);
+        Ok(self.board.cards.last().expect("card was just added"))
+    }
+
+    /// Move a card to the bottom of `to`.
+    pub fn move_card(&mut self, id_or_num: &str, to: &str) -> Result<&Card> {
+        if !self.board.has_column(to) {
+            return Err(KukError::ColumnNotFound(to.into()));
+        }
+        let id = self.resolve(id_or_num)?;
+        self.board.move_card(&id, to);
+        self.get(&id)
+    }
+
+    /// Move a card to the top of its column.
+    pub fn hoist(&mut self, id_or_num: &str) -> Result<&Card> {
+        let id = self.resolve(id_or_num)?;
+        self.board.hoist_card(&id);
+        self.get(&id)
+    }
+
+    /// Move a card to the bottom of its column.
+    pub fn demote(&mut self, id_or_num: &str) -> Result<&Card> {
+        let id = self.resolve(id_or_num)?;
+        self.board.demote_card(&id);
+        self.get(&id)
+    }
+
+    pub fn archive(&mut self, id_or_num: &str) -> Result<&Card> {
+        self.update(id_or_num, |card| {
+            card.archive();
+        })
+    }
+
+    /// Remove a card from the board, returning it.
+    pub fn delete(&mut self, id_or_num: &str) -> Result<Card> {
+        let id = self.resolve(id_or_num)?;
+        let pos = self
+            .board
+            .cards
+            .iter()
+            .position(|c| c.id == id)
+            .expect("resolved ids exist");
+        Ok(self.board.cards.remove(pos))
+    }
+
+    /// Set or clear a card's assignee.
+    pub fn assign(&mut self, id_or_num: &str, user: Option<&str>) -> Result<&Card> {
+        self.update(id_or_num, |card| {
+            card.set_assignee(user.map(Into::into));
+        })
+    }
+
+    /// Retitle a card.
+    pub fn set_title(&mut self, id_or_num: &str, title: &str) -> Result<&Card> {
+        if title.trim().is_empty() {
+            return Err(KukError::Other("Title must not be empty".into()));
+        }
+        self.update(id_or_num, |card| card.title = title.trim().into())
+    }
+
+    /// Set or clear a card's description.
+    pub fn set_description(&mut self, id_or_num: &str, description: Option<&str>) -> Result<&Card> {
+        self.update(id_or_num, |card| {
+            card.description = description.map(Into::into)
+        })
+    }
+
+    /// Leave a comment on a card.
+    pub fn comment(&mut self, id_or_num: &str, author: &str, body: &str) -> Result<&Card> {
+        if body.trim().is_empty() {
+            return Err(KukError::Other("Comment must not be empty".into()));
+        }
+        self.update(id_or_num, |card| {
+            card.add_comment(author, body.trim());
+        })
+    }
+
+    /// Set or clear a card's priority.
+    pub fn set_priority(&mut self, id_or_num: &str, priority: Option<Priority>) -> Result<&Card> {
+        self.update(id_or_num, |card| card.priority = priority)
+    }
+
+    pub fn add_label(&mut self, id_or_num: &str, label: &str) -> Result<&Card> {
+        let label = label.to_string();
+        self.board
+            .check_labels(std::slice::from_ref(&label))
+            .map_err(|l| KukError::UnknownLabel(l.into()))?;
+        self.update(id_or_num, |card| {
+            card.add_label(&label);
+        })
+    }
+
+    pub fn remove_label(&mut self, id_or_num: &str, label: &str) -> Result<&Card> {
+        let id = self.resolve(id_or_num)?;
+        if !self.get(&id)?.labels.iter().any(|l| l == label) {
+            return Err(KukError::LabelNotFound(label.into()));
+        }
+        self.update(&id, |card| {
+            card.remove_label(label);
+        })
+    }
+
+    /// Write the board to disk.
+    pub fn save(&self) -> Result<()> {
+        self.store.save_board(&self.board)
+    }
+
+    /// Discard unsaved changes and load the board from disk again.
+    pub fn reload(&mut self) -> Result<()> {
+        self.board = self.store.load_board(&self.board.name)?;
+        Ok(())
+    }
+
+    fn resolve(&self, id_or_num: &str) -> Result<String> {
+        self.board
+            .resolve_card_id(id_or_num)
+            .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))
+    }
+
+    fn get(&self, id: &str) -> Result<&Card> {
+        self.board
+            .find_card(id)
+            .ok_or_else(|| KukError::CardNotFound(id.into()))
+    }
+
+    fn update(&mut self, id_or_num: &str, change: impl FnOnce(&mut Card)) -> Result<&Card> {
+        let id = self.resolve(id_or_num)?;
+        let card = self.board.find_card_mut(&id).expect("resolved ids exist");
+        change(card);
+        card.updated_at = Utc::now();
+        Ok(card)
+    }
+}
+
+/// A new card for `BoardHandle::add`. Without a column it goes to the
+/// board's first column.
+#[derive(Debug, Clone)]
+pub struct CardBuilder {
+    title: String,
+    column: Option<String>,
+    description: Option<String>,
+    assignee: Option<String>,
+    labels: Vec<String>,
+    due: Option<DateTime<Utc>>,
+    priority: Option<Priority>,
+    metadata: HashMap<String, serde_json::Value>,
+    force: bool,
+}
+
+impl CardBuilder {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            column: None,
+            description: None,
+            assignee: None,
+            labels: Vec::new(),
+            due: None,
+            priority: None,
+            metadata: HashMap::new(),
+            force: false,
+        }
+    }
+
+    pub fn column(mut self, column: impl Into<String>) -> Self {
+        self.column = Some(column.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn assignee(mut self, assignee: impl Into<String>) -> Self {
+        self.assignee = Some(assignee.into());
+        self
+    }
+
+    /// Add a label; repeated labels are kept once.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        if !self.labels.contains(&label) {
+            self.labels.push(label);
+        }
+        self
+    }
+
+    pub fn due(mut self, due: DateTime<Utc>) -> Self {
+        self.due = Some(due);
+        self
+    }
+
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Set a metadata entry, the place for tool-specific data.
+    pub fn metadata(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.metadata.insert(key.into(), value);
+        self
+    }
+
+    /// Add the card even if the project rejects similar titles.
+    pub fn force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+}
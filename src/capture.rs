@@ -0,0 +1,267 @@
+//! `kuk capture`: file cards into a repo's inbox from anywhere.
+//!
+//! `kuk capture --daemon`, started in a repo, listens on a Unix socket
+//! (`~/.kuk/capture.sock` unless `--socket` says otherwise). `kuk capture`
+//! run from any directory sends its titles there, so a shell alias or a
+//! desktop shortcut can add cards without cd'ing into the repo. With no
+//! daemon listening, it files them into the current repo instead.
+//!
+//! One JSON object per line each way: a [`Capture`] in, then a [`Captured`]
+//! or `{"error": "..."}` back.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{KukError, Result};
+use crate::model::{InboxSource, Origin, Via};
+use crate::storage::Store;
+
+/// The `InboxSource` of captured cards.
+pub const CAPTURE_SOURCE: &str = "capture";
+
+/// One item to file: a card title and, optionally, the board to file it on
+/// instead of the active one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Capture {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<String>,
+}
+
+/// Where a [`Capture`] ended up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Captured {
+    pub title: String,
+    pub board: String,
+    pub column: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_id: Option<String>,
+}
+
+/// The daemon's default socket.
+pub fn default_socket() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".kuk").join("capture.sock"))
+}
+
+/// File `item` at the bottom of its board's inbox column.
+pub fn capture(store: &Store, item: &Capture) -> Result<Captured> {
+    let title = item.title.trim();
+    if title.is_empty() {
+        return Err(KukError::Other(
+            "Nothing to capture: the title is empty".into(),
+        ));
+    }
+    let config = store.load_config()?;
+    let name = item.board.as_deref().unwrap_or(&config.default_board);
+    let column = config.inbox_column;
+    let card = store.update_board(name, |board| {
+        Ok(board
+            .receive(&column, title, None, InboxSource::new(CAPTURE_SOURCE))
+            .clone())
+    })?;
+    Ok(Captured {
+        title: card.title.clone(),
+        board: name.to_string(),
+        column,
+        short_id: card.short_id(),
+    })
+}
+
+/// Answer one request line of the protocol.
+fn handle_line(root: &Path, line: &str) -> serde_json::Value {
+    let origin = Origin::new(Via::Cli).client(Some(CAPTURE_SOURCE));
+    let result = serde_json::from_str::<Capture>(line)
+        .map_err(|e| KukError::Other(format!("Invalid capture request: {e}")))
+        .and_then(|item| capture(&Store::new(root).with_origin(origin), &item));
+    match result {
+        Ok(captured) => serde_json::to_value(captured).expect("captures serialize"),
+        Err(e) => serde_json::json!({"error": e.to_string()}),
+    }
+}
+
+/// Listen on `socket` and file every capture sent there into `store`'s
+/// repo, until killed. A socket left behind by a daemon that died is
+/// replaced; one with a daemon still behind it is an error.
+#[cfg(unix)]
+pub fn serve(store: &Store, socket: &Path) -> Result<()> {
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    store.load_config()?;
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            return Err(KukError::Other(format!(
+                "A capture daemon is already listening on {}",
+                socket.display()
+            )));
+        }
+        std::fs::remove_file(socket).map_err(KukError::write(socket))?;
+    }
+    if let Some(dir) = socket.parent() {
+        std::fs::create_dir_all(dir).map_err(KukError::write(dir))?;
+    }
+    let listener = UnixListener::bind(socket).map_err(KukError::write(socket))?;
+    tracing::info!(socket = %socket.display(), "capture daemon listening");
+
+    // Each connection gets its own thread, so a client that connects and
+    // sends nothing holds up nobody else; captures save through
+    // `Store::update_board`, whose lock keeps them from interleaving
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let root = store.repo_root().to_path_buf();
+        std::thread::spawn(move || serve_connection(&root, stream));
+    }
+    Ok(())
+}
+
+/// Answer every request line `stream` sends until it closes.
+#[cfg(unix)]
+fn serve_connection(root: &Path, stream: std::os::unix::net::UnixStream) {
+    use std::io::{BufRead, BufReader, Write};
+
+    let Ok(reader) = stream.try_clone() else {
+        return;
+    };
+    let mut writer = stream;
+    for line in BufReader::new(reader).lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = handle_line(root, &line);
+        if writeln!(writer, "{reply}").is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn serve(_store: &Store, _socket: &Path) -> Result<()> {
+    Err(KukError::Other(
+        "kuk capture --daemon needs Unix domain sockets, which this platform lacks".into(),
+    ))
+}
+
+/// Send `items` to the daemon on `socket`, one result per item, or None
+/// when no daemon is listening there.
+#[cfg(unix)]
+pub fn send(socket: &Path, items: &[Capture]) -> Result<Option<Vec<Result<Captured>>>> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let Ok(stream) = UnixStream::connect(socket) else {
+        return Ok(None);
+    };
+    let mut writer = stream.try_clone()?;
+    let mut replies = BufReader::new(stream).lines();
+    let mut results = Vec::new();
+    for item in items {
+        writeln!(writer, "{}", serde_json::to_string(item)?)?;
+        let reply = replies
+            .next()
+            .ok_or_else(|| KukError::Other("The capture daemon closed the connection".into()))??;
+        let reply: serde_json::Value = serde_json::from_str(&reply)?;
+        results.push(match reply.get("error").and_then(|e| e.as_str()) {
+            Some(error) => Err(KukError::Other(error.into())),
+            None => Ok(serde_json::from_value(reply)?),
+        });
+    }
+    Ok(Some(results))
+}
+
+#[cfg(not(unix))]
+pub fn send(_socket: &Path, _items: &[Capture]) -> Result<Option<Vec<Result<Captured>>>> {
+    Ok(None)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn daemon_files_captures_into_the_inbox() {
+        let repo = TempDir::new().unwrap();
+        let store = Store::new(repo.path());
+        store.init().unwrap();
+        store.create_board("ideas", Vec::new()).unwrap();
+        let socket = repo.path().join("capture.sock");
+
+        assert!(send(&socket, &[]).unwrap().is_none());
+        let daemon = (store.clone(), socket.clone());
+        std::thread::spawn(move || serve(&daemon.0, &daemon.1));
+        while !socket.exists() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(serve(&store, &socket).is_err());
+
+        let items = [
+            Capture {
+                title: "Fix the flaky test".into(),
+                board: None,
+            },
+            Capture {
+                title: "  ".into(),
+                board: None,
+            },
+            Capture {
+                title: "Try sqlite".into(),
+                board: Some("ideas".into()),
+            },
+        ];
+        let results = send(&socket, &items).unwrap().unwrap();
+        let first = results[0].as_ref().unwrap();
+        assert_eq!(first.board, "default");
+        assert_eq!(first.column, "inbox");
+        assert_eq!(first.short_id.as_deref(), Some("KUK-1"));
+        assert!(
+            results[1]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("empty")
+        );
+        assert_eq!(results[2].as_ref().unwrap().board, "ideas");
+
+        let board = store.load_board("default").unwrap();
+        let card = &board.cards[0];
+        assert_eq!(card.title, "Fix the flaky test");
+        assert_eq!(InboxSource::of(card).unwrap().source, CAPTURE_SOURCE);
+        assert_eq!(
+            Origin::of_card(card).unwrap().client.as_deref(),
+            Some(CAPTURE_SOURCE)
+        );
+    }
+
+    #[test]
+    fn an_idle_client_does_not_block_others() {
+        let repo = TempDir::new().unwrap();
+        let store = Store::new(repo.path());
+        store.init().unwrap();
+        let socket = repo.path().join("capture.sock");
+        let daemon = (store.clone(), socket.clone());
+        std::thread::spawn(move || serve(&daemon.0, &daemon.1));
+        while !socket.exists() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        // Connects and never says anything
+        let _idle = std::os::unix::net::UnixStream::connect(&socket).unwrap();
+        let clients: Vec<_> = (0..4)
+            .map(|n| {
+                let socket = socket.clone();
+                std::thread::spawn(move || {
+                    let item = Capture {
+                        title: format!("Idea {n}"),
+                        board: None,
+                    };
+                    send(&socket, &[item]).unwrap().unwrap()
+                })
+            })
+            .collect();
+        for client in clients {
+            assert!(client.join().unwrap()[0].is_ok());
+        }
+        assert_eq!(store.load_board("default").unwrap().cards.len(), 4);
+    }
+}
@@ -0,0 +1,59 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{KukError, Result};
+use crate::model::RepoConfig;
+use crate::storage::Store;
+
+/// Commit what `command` changed under `.kuk/` when the repo's config has
+/// `auto_commit` on. Only commands that logged an operation commit, and
+/// only the files that operation touched, so reads never commit and other
+/// changes under `.kuk/` are left for their own commands. The subject says
+/// what changed, like `kuk: move KUK-12 to done`, and the body is the
+/// command as typed. A failed commit only warns, since the command itself
+/// went through.
+pub fn commit(store: &Store, config: &RepoConfig, command: &str) {
+    if !config.auto_commit {
+        return;
+    }
+    if let Err(e) = try_commit(store, command) {
+        eprintln!("Warning: could not commit .kuk/: {e}");
+    }
+}
+
+fn try_commit(store: &Store, command: &str) -> Result<()> {
+    let Some(op) = store.current_operation()? else {
+        return Ok(());
+    };
+    let root = store.repo_root();
+    let paths: Vec<String> = store
+        .operation_paths(&op)
+        .iter()
+        .filter_map(|path| path.strip_prefix(root).ok())
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+
+    let mut add = vec!["add", "-A", "--"];
+    add.extend(paths.iter().map(String::as_str));
+    git(root, &add)?;
+    let subject = format!("kuk: {}", op.summary());
+    let mut commit = vec!["commit", "-q", "-m", &subject, "-m", command, "--"];
+    commit.extend(paths.iter().map(String::as_str));
+    git(root, &commit)?;
+    tracing::debug!(%subject, "committed .kuk/");
+    Ok(())
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|e| KukError::Other(format!("could not run git: {e}")))?;
+    if !output.status.success() {
+        return Err(KukError::Other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
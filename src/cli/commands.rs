@@ -1,11 +1,25 @@
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
-
+use colored::Colorize;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, IsTerminal};
+use std::path::{Path, PathBuf};
+
+use crate::capture::{self, Capture};
 use crate::error::{KukError, Result};
-use crate::model::{Card, Column};
+use crate::model::{
+    Attachment, Board, BoardTemplate, Card, CardDefaults, CardSort, CodeComment, Column, DueStatus,
+    DuplicatePolicy, Escalation, Event, InboxSource, LabelDef, ORIGIN_KEY, Origin, Priority,
+    RenderedTemplate, RepoConfig, SearchQuery, Snooze, StorageLayout, Swimlanes, Transition, Via,
+    idle_days, parse_color, parse_markdown_cards,
+};
+use crate::resolve;
 use crate::storage::Store;
 
+use super::init_wizard;
+
 #[derive(Parser, Debug)]
 #[command(name = "kuk", version, about = "Kanban that ships with your code.")]
 pub struct Cli {
@@ -23,6 +37,10 @@ pub struct Cli {
     /// Path to repo root (defaults to current directory)
     #[arg(long, global = true)]
     pub repo: Option<PathBuf>,
+
+    /// Log diagnostics to stderr (-v requests and API calls, -vv storage and git)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
 }
 
 #[derive(Subcommand, Debug)]
@@ -32,6 +50,15 @@ pub enum Commands {
         /// Name of the initial board
         #[arg(long, default_value = "default")]
         board_name: String,
+
+        /// Prompt for the board, columns, WIP limits and repo settings
+        #[arg(long, short)]
+        interactive: bool,
+
+        /// Lay the board out by a template: kanban, scrum, bugtracker, one
+        /// saved with `kuk board save-template`, or a .yaml/.json file
+        #[arg(long, conflicts_with = "interactive")]
+        template: Option<String>,
     },
 
     /// List all cards on the board
@@ -39,74 +66,367 @@ pub enum Commands {
         /// Board name (defaults to active board)
         #[arg(long)]
         board: Option<String>,
+        /// Only cards at this priority or above (low, medium, high, urgent)
+        #[arg(long)]
+        priority: Option<Priority>,
+        /// Card order within each column: position, priority, created,
+        /// updated, due or title, optionally with :asc or :desc
+        #[arg(long, default_value = "position")]
+        sort: CardSort,
+        /// List epics with the progress of their cards instead of columns
+        #[arg(long)]
+        epics: bool,
+        /// Group cards into swimlanes by assignee, label or lane (default:
+        /// the board's swimlanes)
+        #[arg(long, value_name = "BY", num_args = 0..=1)]
+        lanes: Option<Option<Swimlanes>>,
+    },
+
+    /// Find cards on every board, e.g. "label:bug assignee:leslie text:login"
+    Search {
+        /// Terms that must all match: board:, column:, label:, assignee:,
+        /// text: (title and description), or bare words for text
+        query: String,
+        /// Include archived cards
+        #[arg(long)]
+        archived: bool,
+    },
+
+    /// Show every field of a card, including its metadata
+    Show {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+    },
+
+    /// Show what happened to a card: created, moved, labeled, assigned, archived
+    History {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+    },
+
+    /// Write a board or a card out for another tool
+    #[command(args_conflicts_with_subcommands = true)]
+    Export {
+        #[command(subcommand)]
+        command: Option<ExportCmd>,
+        /// Write the board as a CSV of its cards or a Markdown summary
+        #[arg(long, value_enum)]
+        format: Option<BoardFormat>,
+        /// Board to export (default: the active board)
+        #[arg(long)]
+        board: Option<String>,
+    },
+
+    /// Turn a file from another tool into cards
+    Import {
+        #[command(subcommand)]
+        command: ImportCmd,
+    },
+
+    /// Show recent activity on a board, newest first, and where it came from
+    Log {
+        /// Only changes made through cli, tui, rest, mcp, sync or rpc
+        #[arg(long)]
+        origin: Option<Via>,
+        /// Board (default: the active board)
+        #[arg(long)]
+        board: Option<String>,
+        /// Show only the last N changes
+        #[arg(long, default_value_t = 20)]
+        last: usize,
     },
 
     /// Add a new card
     Add {
         /// Card title
-        title: String,
-        /// Target column
-        #[arg(long, default_value = "todo")]
-        to: String,
-        /// Labels to add
+        #[arg(required_unless_present = "stdin")]
+        title: Option<String>,
+        /// Add a card for each line of stdin instead: a title, or a JSON
+        /// object with title, column, labels, assignee, due and priority
+        #[arg(long, conflicts_with_all = ["title", "template"])]
+        stdin: bool,
+        /// Target column (defaults to `new_cards.column` in config.json,
+        /// else the board's first column)
+        #[arg(long)]
+        to: Option<String>,
+        /// Labels to add, after any `new_cards.labels`
         #[arg(long)]
         label: Vec<String>,
-        /// Assignee
+        /// Assignee (defaults to `new_cards.assignee`)
         #[arg(long)]
         assignee: Option<String>,
+        /// Ignore the `new_cards` defaults in config.json
+        #[arg(long)]
+        no_defaults: bool,
+        /// Add even if a similar card exists (see `duplicates` in config.json)
+        /// or the column is at its WIP limit
+        #[arg(long)]
+        force: bool,
+        /// Card template from .kuk/card-templates/
+        #[arg(long)]
+        template: Option<String>,
+        /// Template placeholder value, as key=value
+        #[arg(long = "var", value_name = "KEY=VALUE", requires = "template")]
+        vars: Vec<String>,
+        /// Due date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        due: Option<String>,
+        /// Priority (low, medium, high, urgent)
+        #[arg(long)]
+        priority: Option<Priority>,
     },
 
     /// Move a card to a different column
     Move {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
         id: String,
         /// Target column
         #[arg(long)]
         to: String,
+        /// Move even if the target column is at its WIP limit
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Copy a card's title, description, labels and checklist to a new card
+    Clone {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+        /// Column for the copy (default: the card's own column, or the
+        /// board's first)
+        #[arg(long)]
+        to: Option<String>,
+        /// Board for the copy (default: the active board)
+        #[arg(long)]
+        board: Option<String>,
+        /// Add even if the column is at its WIP limit
+        #[arg(long)]
+        force: bool,
     },
 
     /// Move a card to the top of its column
     Hoist {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
         id: String,
     },
 
     /// Move a card to the bottom of its column
     Demote {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+    },
+
+    /// Set or clear a card's due date
+    Due {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+        /// Due date (YYYY-MM-DD, meaning the end of that day UTC, or RFC 3339)
+        #[arg(required_unless_present = "clear")]
+        date: Option<String>,
+        /// Remove the due date
+        #[arg(long, conflicts_with = "date")]
+        clear: bool,
+    },
+
+    /// Set or clear a card's priority
+    Priority {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+        /// low, medium, high, or urgent
+        #[arg(required_unless_present = "clear")]
+        level: Option<Priority>,
+        /// Remove the priority
+        #[arg(long, conflicts_with = "level")]
+        clear: bool,
+    },
+
+    /// Hide a card from board views until a date
+    Snooze {
+        /// Card ID, short ID (KUK-42) or number
         id: String,
+        /// Date the card resurfaces (YYYY-MM-DD or RFC 3339)
+        #[arg(long, required_unless_present = "wake")]
+        until: Option<String>,
+        /// Put the card at the top of its column when it resurfaces
+        #[arg(long, conflicts_with = "wake")]
+        hoist: bool,
+        /// Bring a snoozed card back now
+        #[arg(long, conflicts_with = "until")]
+        wake: bool,
     },
 
     /// Archive a card
     Archive {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+    },
+
+    /// List archived cards, most recently archived first
+    Archived {
+        /// Board name (defaults to active board)
+        #[arg(long)]
+        board: Option<String>,
+    },
+
+    /// Restore an archived card to the column it was archived from
+    Unarchive {
+        /// Card ID or short ID (KUK-42), as `kuk archived` shows it
         id: String,
+        /// Restore even if the column is at its WIP limit
+        #[arg(long)]
+        force: bool,
     },
 
     /// Delete a card permanently
     Delete {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
         id: String,
     },
 
-    /// Add or remove labels from a card
+    /// Reverse the most recent command that changed a board
+    Undo,
+
+    /// Replay the most recently undone command
+    Redo,
+
+    /// Add or remove labels from a card, or rename/merge labels board-wide
+    #[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
     Label {
-        /// Card ID or number
-        id: String,
+        #[command(subcommand)]
+        command: Option<LabelCmd>,
+        /// Card ID, short ID (KUK-42) or number
+        #[arg(required = true)]
+        id: Option<String>,
         /// Action: add or remove
-        action: String,
+        #[arg(required = true)]
+        action: Option<String>,
         /// Tag name
-        tag: String,
+        #[arg(required = true)]
+        tag: Option<String>,
+    },
+
+    /// Manage the board's label registry
+    LabelDef {
+        #[command(subcommand)]
+        command: LabelDefCmd,
+    },
+
+    /// Triage items filed from email, forms, and webhooks
+    Inbox {
+        #[command(subcommand)]
+        command: Option<InboxCmd>,
+    },
+
+    /// File quick notes into the inbox, through a running capture daemon
+    /// when there is one, so it works from any directory
+    Capture {
+        /// Card title; without one, each line of stdin is a title
+        #[arg(conflicts_with = "daemon")]
+        title: Vec<String>,
+        /// Board to file into (defaults to the daemon's active board)
+        #[arg(long, conflicts_with = "daemon")]
+        board: Option<String>,
+        /// Listen for captures for this repo until killed
+        #[arg(long)]
+        daemon: bool,
+        /// Daemon socket (defaults to ~/.kuk/capture.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+
+    /// Merge board files a git merge left conflicted, card by card
+    Resolve {
+        /// Register kuk as the git merge driver for board files
+        #[arg(long)]
+        install: bool,
+        /// Run as the merge driver git calls (BASE OURS THEIRS)
+        #[arg(long, num_args = 3, value_names = ["BASE", "OURS", "THEIRS"], hide = true, conflicts_with = "install")]
+        driver: Option<Vec<PathBuf>>,
+    },
+
+    /// Group cards under epics
+    Epic {
+        #[command(subcommand)]
+        command: EpicCmd,
+    },
+
+    /// Add, rename, reorder or delete columns, or set WIP limits
+    Column {
+        #[command(subcommand)]
+        command: ColumnCmd,
     },
 
     /// Assign a user to a card
     Assign {
-        /// Card ID or number
+        /// Card ID, short ID (KUK-42) or number
         id: String,
         /// Username
         user: String,
     },
 
+    /// Change a card's title or description, in $EDITOR when no flags are given
+    Edit {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+        /// New title
+        #[arg(long)]
+        title: Option<String>,
+        /// New description; an empty string removes it
+        #[arg(long)]
+        description: Option<String>,
+        /// Swimlane for boards split by lane; an empty string removes it
+        #[arg(long)]
+        lane: Option<String>,
+    },
+
+    /// Leave a comment on a card
+    Comment {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+        /// Comment text
+        body: String,
+        /// Who is commenting (default: git user.name, then $USER)
+        #[arg(long)]
+        author: Option<String>,
+    },
+
+    /// Attach a URL or a file in the repo to a card
+    Attach {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+        /// URL, or path relative to the repo root
+        target: String,
+        /// What the attachment is, shown in place of the target
+        #[arg(long, conflicts_with = "remove")]
+        title: Option<String>,
+        /// Remove the attachment instead
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Report cards left untouched, and escalate them by the `aging` rules
+    Stale {
+        /// Days without changes before a card counts as stale
+        #[arg(long, default_value = "14")]
+        days: u32,
+        /// Apply the `aging` rules from config.json first
+        #[arg(long)]
+        escalate: bool,
+    },
+
+    /// Track TODO/FIXME/HACK comments in the source as cards
+    Scan {
+        /// Files or directories to scan, relative to the repo root
+        #[arg(long, num_args = 1.., default_value = ".")]
+        paths: Vec<PathBuf>,
+        /// Column for cards of new comments
+        #[arg(long, default_value = "todo")]
+        to: String,
+        /// Update the board; without it the changes are only listed
+        #[arg(long)]
+        apply: bool,
+    },
+
     /// Board management
     Board {
         #[command(subcommand)]
@@ -119,6 +439,17 @@ pub enum Commands {
     /// Launch the TUI
     Tui,
 
+    /// Open the TUI on a throwaway sample project
+    Demo {
+        /// Write the sample project here and keep it, instead of a temp dir
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Only write the project and print its path
+        #[arg(long)]
+        no_tui: bool,
+    },
+
     /// Start the REST + MCP server
     Serve {
         /// Port to listen on
@@ -133,11 +464,41 @@ pub enum Commands {
     /// Run as MCP server (stdio transport for Claude Code / AI agents)
     Mcp,
 
+    /// Serve board queries and changes as JSON-RPC on stdio, for editor plugins
+    Rpc,
+
     /// Health check
-    Doctor,
+    Doctor {
+        /// Repair card order drift (duplicates and gaps)
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Show how often each command ran and how long it took, from the
+    /// local usage log
+    Usage {
+        /// Turn recording on or off, or clear what was recorded
+        #[arg(value_parser = ["on", "off", "clear"])]
+        action: Option<String>,
+    },
 
     /// Show version
     Version,
+
+    /// Update kuk to the latest GitHub release
+    #[cfg(feature = "self-update")]
+    SelfUpdate {
+        /// Only report whether a newer release exists
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Developer utilities
+    #[command(hide = true)]
+    Dev {
+        #[command(subcommand)]
+        command: DevCmd,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -146,6 +507,13 @@ pub enum BoardCmd {
     Create {
         /// Board name
         name: String,
+        /// Comma-separated columns, e.g. "backlog,doing,review,done"
+        #[arg(long, conflicts_with = "template")]
+        columns: Option<String>,
+        /// Lay the board out by a template: kanban, scrum, bugtracker, one
+        /// saved with `kuk board save-template`, or a .yaml/.json file
+        #[arg(long)]
+        template: Option<String>,
     },
     /// Switch default board
     Switch {
@@ -154,155 +522,1971 @@ pub enum BoardCmd {
     },
     /// List all boards
     List,
+    /// Rename a board, along with the config and sprints that name it
+    Rename {
+        /// Current name
+        old: String,
+        /// New name
+        new: String,
+    },
+    /// Delete a board, optionally moving its cards to another board first
+    Delete {
+        /// Board name
+        name: String,
+        /// Board that receives the deleted board's cards
+        #[arg(long)]
+        to: Option<String>,
+        /// Don't ask for confirmation
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Save a board's columns, labels and swimlanes as a template for new
+    /// boards in any repo
+    SaveTemplate {
+        /// Template name
+        name: String,
+        /// Board to copy (defaults to active board)
+        #[arg(long)]
+        board: Option<String>,
+    },
+    /// List the built-in and saved board templates
+    Templates,
+    /// Show or set what splits the board into swimlanes
+    Lanes {
+        /// assignee, label, lane, or off
+        by: Option<String>,
+    },
+    /// Show or set what happens to cards moved into a column
+    Rules {
+        /// Column name
+        column: String,
+        /// Assign cards entering the column to this user
+        #[arg(long)]
+        assignee: Option<String>,
+        /// Add this label to cards entering the column (repeatable)
+        #[arg(long = "label")]
+        labels: Vec<String>,
+        /// Remove the column's rules before applying the flags
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Show or set how boards are stored, converting every board
+    Layout {
+        /// file (one JSON file per board), cards (one file per card) or
+        /// sqlite (one .kuk/kuk.db database)
+        layout: Option<StorageLayout>,
+    },
 }
 
-// --- Command implementations ---
+#[derive(Subcommand, Debug)]
+pub enum LabelCmd {
+    /// Rename a label on every card of the board
+    Rename {
+        /// Current name
+        old: String,
+        /// New name
+        new: String,
+    },
+    /// Fold one label into another on every card of the board
+    Merge {
+        /// Label to remove
+        from: String,
+        /// Label to keep
+        into: String,
+    },
+}
 
-pub fn init(store: &Store, _board_name: &str) -> Result<()> {
-    store.init()?;
-    println!("Initialized kuk board in {}", store.kuk_dir().display());
-    Ok(())
+#[derive(Subcommand, Debug)]
+pub enum InboxCmd {
+    /// File a new item into the intake column
+    Add {
+        /// Item title
+        title: String,
+        /// Item body, kept as the card description
+        #[arg(long)]
+        body: Option<String>,
+        /// Where the item came from, e.g. email or form
+        #[arg(long, default_value = "cli")]
+        source: String,
+        /// Who sent it
+        #[arg(long)]
+        from: Option<String>,
+        /// Link back to the original
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// List items waiting for triage (the default)
+    List,
+    /// Move an item onto the board
+    Promote {
+        /// Inbox number or card ID
+        id: String,
+        /// Target column
+        #[arg(long, default_value = "todo")]
+        to: String,
+        /// Labels to add
+        #[arg(long)]
+        label: Vec<String>,
+        /// Assignee
+        #[arg(long)]
+        assignee: Option<String>,
+    },
+    /// Archive an item without acting on it
+    Discard {
+        /// Inbox number or card ID
+        id: String,
+    },
 }
 
-pub fn list(store: &Store, board_name: Option<&str>, json_output: bool) -> Result<()> {
-    let config = store.load_config()?;
-    let board_name = board_name.unwrap_or(&config.default_board);
-    let board = store.load_board(board_name)?;
+#[derive(Subcommand, Debug)]
+pub enum EpicCmd {
+    /// Add an epic card
+    Create {
+        /// Epic title
+        title: String,
+        /// Target column
+        #[arg(long, default_value = "todo")]
+        to: String,
+    },
+    /// Put cards under an epic
+    Add {
+        /// Epic ID, short ID (KUK-42) or number
+        epic: String,
+        /// Cards to add
+        #[arg(required = true)]
+        cards: Vec<String>,
+    },
+    /// Take cards out of their epic
+    Remove {
+        /// Cards to remove
+        #[arg(required = true)]
+        cards: Vec<String>,
+    },
+}
 
-    if json_output {
-        println!("{}", serde_json::to_string_pretty(&board)?);
-        return Ok(());
-    }
+#[derive(Subcommand, Debug)]
+pub enum ColumnCmd {
+    /// Add a column, after the last one unless --at says otherwise
+    Add {
+        /// Column name
+        name: String,
+        /// Position, 1 for leftmost
+        #[arg(long)]
+        at: Option<usize>,
+        /// WIP limit
+        #[arg(long)]
+        wip: Option<u32>,
+    },
+    /// Rename a column and update its cards
+    Rename {
+        /// Current name
+        old: String,
+        /// New name
+        new: String,
+    },
+    /// Move a column to another position
+    Move {
+        /// Column name
+        name: String,
+        /// New position, 1 for leftmost
+        position: usize,
+    },
+    /// Delete a column, moving its cards to another one
+    Delete {
+        /// Column name
+        name: String,
+        /// Column that receives the deleted column's cards
+        #[arg(long)]
+        to: Option<String>,
+    },
+    /// Set a column's WIP limit; 0 removes it
+    SetWip {
+        /// Column name
+        name: String,
+        /// Most cards allowed in the column
+        limit: u32,
+    },
+    /// Mark a column as a queue, where finished work waits for the next
+    /// step; reports leave its cards out of WIP
+    Queue {
+        /// Column name
+        name: String,
+        #[arg(value_parser = ["on", "off"])]
+        mode: String,
+    },
+}
 
-    for col in &board.columns {
-        let cards: Vec<&Card> = board
-            .cards
-            .iter()
-            .filter(|c| c.column == col.name && !c.archived)
-            .collect();
+#[derive(Subcommand, Debug)]
+pub enum LabelDefCmd {
+    /// Define a label, or update its color and description
+    Add {
+        /// Label name
+        name: String,
+        /// Hex color, e.g. '#d73a4a'
+        #[arg(long)]
+        color: Option<String>,
+        /// What the label means
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List defined labels and how many cards use them
+    List,
+    /// Rename a label in the registry and on every card
+    Rename {
+        /// Current name
+        old: String,
+        /// New name
+        new: String,
+    },
+    /// Remove a label from the registry (cards keep it)
+    Delete {
+        /// Label name
+        name: String,
+    },
+    /// Only allow card labels that are defined: on or off
+    Strict {
+        #[arg(value_parser = ["on", "off"])]
+        mode: String,
+    },
+}
 
-        let wip = col
-            .wip_limit
-            .map(|l| format!(" [{}/{}]", cards.len(), l))
-            .unwrap_or_default();
+#[derive(Subcommand, Debug)]
+pub enum ExportCmd {
+    /// Print a card as a ready-to-paste issue body
+    Card {
+        /// Card ID, short ID (KUK-42) or number
+        id: String,
+        #[arg(long, value_enum, default_value = "gh-issue")]
+        format: ExportFormat,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ImportCmd {
+    /// Add a card for every top-level `- [ ]` item in a Markdown file
+    Markdown {
+        /// Markdown file to read
+        file: PathBuf,
+        /// Column for open items (default: first column); ticked ones go to
+        /// the last
+        #[arg(long)]
+        to: Option<String>,
+        /// Add even if a column is at its WIP limit
+        #[arg(long)]
+        force: bool,
+    },
+}
 
-        println!("── {} ({}){}──", col.name.to_uppercase(), cards.len(), wip);
+/// Formats `kuk export card` writes.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A GitHub issue: title heading, description with the checklist as a
+    /// task list, and a labels footer
+    GhIssue,
+}
 
-        let mut sorted = cards;
-        sorted.sort_by_key(|c| c.order);
+/// Formats `kuk export --format` writes a board in.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardFormat {
+    /// A row per card, archived ones included, with the fields of `--json`
+    Csv,
+    /// A heading per column listing its active cards
+    Md,
+}
 
-        for (i, card) in sorted.iter().enumerate() {
-            let labels = if card.labels.is_empty() {
-                String::new()
-            } else {
-                format!(" [{}]", card.labels.join(", "))
-            };
-            let assignee = card
-                .assignee
-                .as_ref()
-                .map(|a| format!(" @{a}"))
-                .unwrap_or_default();
-            println!("  {}. {}{}{}", i + 1, card.title, labels, assignee);
-        }
-        println!();
-    }
-    Ok(())
+#[derive(Subcommand, Debug)]
+pub enum DevCmd {
+    /// Write synthetic boards named gen-1 .. gen-N for benchmarking
+    Generate {
+        /// Total number of cards across all boards
+        #[arg(long, default_value = "1000")]
+        cards: usize,
+        /// Number of boards
+        #[arg(long, default_value = "1")]
+        boards: usize,
+        /// Seed for reproducible output
+        #[arg(long, default_value = "1")]
+        seed: u64,
+    },
 }
 
-pub fn add(
+// --- Command implementations ---
+
+pub fn init(
     store: &Store,
-    title: &str,
-    column: &str,
+    board_name: &str,
+    interactive: bool,
+    template: Option<&str>,
+) -> Result<()> {
+    if !interactive {
+        let board = match template {
+            Some(template) => Store::load_board_template(template)?
+                .board(board_name)
+                .map_err(KukError::Other)?,
+            None => Board {
+                name: board_name.into(),
+                ..Board::default_board()
+            },
+        };
+        let config = RepoConfig {
+            default_board: board_name.into(),
+            ..RepoConfig::default()
+        };
+        store.init_with(&config, &board)?;
+        println!("Initialized kuk board in {}", store.kuk_dir().display());
+        if template.is_some() {
+            let columns: Vec<&str> = board.columns.iter().map(|c| c.name.as_str()).collect();
+            println!("  columns: {}", columns.join(" → "));
+        }
+        return Ok(());
+    }
+
+    if store.is_initialized() {
+        return Err(KukError::AlreadyInitialized(
+            store.kuk_dir().display().to_string(),
+        ));
+    }
+    let answers = init_wizard::ask(&mut std::io::stdin().lock(), &mut std::io::stdout())?;
+    store.init_with(&answers.config, &answers.board)?;
+    println!();
+    println!("Initialized kuk board in {}", store.kuk_dir().display());
+    if !answers.track_in_git && init_wizard::ignore_kuk_dir(store.repo_root())? {
+        println!("  added .kuk/ to .gitignore");
+    }
+    if answers.pm_init {
+        init_wizard::run_pm_init(store.repo_root());
+    }
+    Ok(())
+}
+
+pub fn list(
+    store: &Store,
+    board_name: Option<&str>,
+    min_priority: Option<Priority>,
+    sort: CardSort,
+    epics: bool,
+    lanes: Option<Option<Swimlanes>>,
+    json_output: bool,
+) -> Result<()> {
+    let config = store.load_config()?;
+    let board_name = board_name.unwrap_or(&config.default_board);
+
+    if epics {
+        let board = store.load_board_summary(board_name)?;
+        if json_output {
+            println!("{}", serde_json::to_string_pretty(&epics_json(&board))?);
+        } else {
+            print!("{}", render_epics(&board));
+        }
+        return Ok(());
+    }
+
+    let mut board = if json_output {
+        store.load_board(board_name)?
+    } else {
+        store.load_board_summary(board_name)?
+    };
+    if let Some(min) = min_priority {
+        board.cards.retain(|c| c.priority >= Some(min));
+    }
+
+    if let Some(by) = lanes {
+        let by = by.or(board.swimlanes).ok_or_else(|| {
+            KukError::Other(format!(
+                "Board {} has no swimlanes. Pass --lanes assignee, label or lane, or set them with `kuk board lanes`.",
+                board.name
+            ))
+        })?;
+        if json_output {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&lanes_json(&board, by, sort))?
+            );
+        } else {
+            print!(
+                "{}",
+                render_lanes(&board, by, sort, std::io::stdout().is_terminal())
+            );
+        }
+        return Ok(());
+    }
+
+    if json_output {
+        if sort != CardSort::default() {
+            board.sort_cards_by(sort);
+        }
+        println!("{}", serde_json::to_string_pretty(&board)?);
+        return Ok(());
+    }
+
+    print!(
+        "{}",
+        render_list(&board, sort, std::io::stdout().is_terminal())
+    );
+    Ok(())
+}
+
+pub fn show(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    let card = board
+        .find_card(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else {
+        print!(
+            "{}",
+            render_card(
+                &board,
+                card,
+                store.repo_root(),
+                std::io::stdout().is_terminal()
+            )
+        );
+    }
+    Ok(())
+}
+
+pub fn search(store: &Store, query: &str, archived: bool, json_output: bool) -> Result<()> {
+    let query: SearchQuery = query.parse().map_err(KukError::Other)?;
+    let mut boards = Vec::new();
+    for name in store.list_boards()? {
+        if query.includes_board(&name) {
+            let mut board = store.load_board(&name)?;
+            board.sort_cards();
+            board
+                .cards
+                .retain(|c| (archived || !c.archived) && query.matches(c));
+            boards.push(board);
+        }
+    }
+    let found: usize = boards.iter().map(|b| b.cards.len()).sum();
+
+    if json_output {
+        let hits = boards
+            .iter()
+            .flat_map(|board| board.cards.iter().map(move |card| (board, card)))
+            .map(|(board, card)| {
+                let mut hit = serde_json::to_value(card)?;
+                hit["board"] = board.name.clone().into();
+                Ok(hit)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+
+    if found == 0 {
+        println!("No cards match.");
+        return Ok(());
+    }
+    let color = std::io::stdout().is_terminal();
+    for board in &boards {
+        for card in &board.cards {
+            let id = card.short_id().unwrap_or_else(|| card.id.clone());
+            println!(
+                "  {}/{}  {id}  {}",
+                board.name,
+                card.column,
+                card_line(board, card, color)
+            );
+        }
+    }
+    println!("\n{found} card(s) found.");
+    Ok(())
+}
+
+pub fn export_card(
+    store: &Store,
+    id_or_num: &str,
+    format: ExportFormat,
+    json_output: bool,
+) -> Result<()> {
+    let config = store.load_config()?;
+    let board = store.load_board(&config.default_board)?;
+    let card = board
+        .resolve_card_id(id_or_num)
+        .and_then(|id| board.find_card(&id))
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    let ExportFormat::GhIssue = format;
+    let issue = card.to_issue();
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&issue)?);
+    } else {
+        print!("{}", issue.markdown());
+    }
+    Ok(())
+}
+
+pub fn export_board(
+    store: &Store,
+    board: Option<&str>,
+    format: Option<BoardFormat>,
+    json_output: bool,
+) -> Result<()> {
+    let name = match board {
+        Some(name) => name.to_string(),
+        None => store.load_config()?.default_board,
+    };
+    let board = store.load_board(&name)?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&board)?);
+        return Ok(());
+    }
+    match format {
+        Some(BoardFormat::Csv) => print!("{}", board.to_csv()),
+        Some(BoardFormat::Md) => print!("{}", board.to_markdown()),
+        None => {
+            return Err(KukError::Other(
+                "Pass --format csv or md, or export one card with `kuk export card`".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn history(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    let card = board
+        .find_card(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    let events = Event::history(card);
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&events)?);
+    } else {
+        println!("{}", card.title);
+        for event in &events {
+            println!(
+                "  {}  {}{}",
+                event.at.format("%Y-%m-%d %H:%M UTC"),
+                event.kind,
+                via(event.origin.as_ref())
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Where a change came from, as printed after it.
+fn via(origin: Option<&Origin>) -> String {
+    origin.map(|o| format!("  via {o}")).unwrap_or_default()
+}
+
+pub fn log(
+    store: &Store,
+    board_name: Option<&str>,
+    origin: Option<Via>,
+    last: usize,
+    json_output: bool,
+) -> Result<()> {
+    let board_name = match board_name {
+        Some(name) => name.to_string(),
+        None => store.load_config()?.default_board,
+    };
+    let board = store.load_board(&board_name)?;
+
+    let mut entries: Vec<(&Card, Event)> = board
+        .cards
+        .iter()
+        .flat_map(|card| Event::history(card).into_iter().map(move |e| (card, e)))
+        .filter(|(_, e)| origin.is_none_or(|via| e.origin.as_ref().is_some_and(|o| o.via == via)))
+        .collect();
+    entries.sort_by_key(|(_, e)| std::cmp::Reverse(e.at));
+    entries.truncate(last);
+
+    if json_output {
+        let entries = entries
+            .iter()
+            .map(|(card, event)| {
+                let mut entry = serde_json::to_value(event)?;
+                entry["card_id"] = card.id.clone().into();
+                entry["short_id"] = serde_json::json!(card.short_id());
+                entry["title"] = card.title.clone().into();
+                Ok(entry)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        match origin {
+            Some(via) => println!("No activity on {board_name} came through {via}."),
+            None => println!("No activity on {board_name}."),
+        }
+        return Ok(());
+    }
+    for (card, event) in &entries {
+        let id = card.short_id().unwrap_or_else(|| card.id.clone());
+        println!(
+            "  {}  {id}  {}: {}{}",
+            event.at.format("%Y-%m-%d %H:%M UTC"),
+            card.title,
+            event.kind,
+            via(event.origin.as_ref())
+        );
+    }
+    Ok(())
+}
+
+/// Epics with their progress and cards, as printed by `kuk list --epics --json`.
+fn epics_json(board: &Board) -> serde_json::Value {
+    let card_json = |c: &Card| {
+        serde_json::json!({
+            "id": c.id,
+            "short_id": c.short_id(),
+            "title": c.title,
+            "column": c.column,
+            "archived": c.archived,
+        })
+    };
+    board
+        .epics()
+        .into_iter()
+        .map(|epic| {
+            let mut json = card_json(epic);
+            json["progress"] =
+                serde_json::to_value(board.epic_progress(&epic.id)).expect("progress serializes");
+            json["cards"] = board
+                .children(&epic.id)
+                .into_iter()
+                .map(card_json)
+                .collect();
+            json
+        })
+        .collect()
+}
+
+/// Text rendering of `kuk list --epics`: each epic with a progress line and
+/// its cards, finished ones checked.
+pub fn render_epics(board: &Board) -> String {
+    let epics = board.epics();
+    if epics.is_empty() {
+        return "No epics. Create one with `kuk epic create <title>`.\n".into();
+    }
+    let mut out = String::new();
+    let _ = writeln!(out, "── EPICS ({})──", epics.len());
+    for epic in epics {
+        let progress = board.epic_progress(&epic.id);
+        let short_id = epic.short_id().unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "  {short_id} {} [{}]  {}/{} done ({}%)",
+            epic.title,
+            epic.column,
+            progress.done,
+            progress.total,
+            progress.percent()
+        );
+        for card in board.children(&epic.id) {
+            let mark = if board.is_finished(card) { "x" } else { " " };
+            let short_id = card.short_id().unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "    [{mark}] {short_id} {} [{}]",
+                card.title, card.column
+            );
+        }
+    }
+    out
+}
+
+/// Text rendering of one card as printed by `kuk show`: every field that is
+/// set, then the description, attachments, comments and each metadata
+/// entry. Attached paths are looked up under `root`.
+pub fn render_card(board: &Board, card: &Card, root: &Path, color: bool) -> String {
+    let time = |t: DateTime<Utc>| t.format("%Y-%m-%d %H:%M UTC").to_string();
+    let mut out = String::new();
+    let _ = writeln!(out, "{}{}", card.title, priority_marker(card, color));
+    let _ = writeln!(out, "  ID:        {}", card.id);
+    if let Some(short_id) = card.short_id() {
+        let _ = writeln!(out, "  Short ID:  {short_id}");
+    }
+    let position = board
+        .column_cards(&card.column)
+        .iter()
+        .position(|c| c.id == card.id)
+        .map(|i| format!(" (#{})", i + 1))
+        .unwrap_or_default();
+    let _ = writeln!(out, "  Column:    {}{position}", card.column);
+    if card.epic {
+        let progress = board.epic_progress(&card.id);
+        let _ = writeln!(
+            out,
+            "  Epic:      {}/{} cards done ({}%)",
+            progress.done,
+            progress.total,
+            progress.percent()
+        );
+    }
+    if let Some(epic) = card.parent.as_deref().and_then(|id| board.find_card(id)) {
+        let short_id = epic.short_id().map(|s| format!("{s} ")).unwrap_or_default();
+        let _ = writeln!(out, "  Parent:    {short_id}{}", epic.title);
+    }
+    if let Some(priority) = card.priority {
+        let _ = writeln!(out, "  Priority:  {priority}");
+    }
+    if !card.labels.is_empty() {
+        let painted: Vec<String> = card
+            .labels
+            .iter()
+            .map(|l| paint_label(l, board.label_def(l), color))
+            .collect();
+        let _ = writeln!(out, "  Labels:    {}", painted.join(", "));
+    }
+    if let Some(assignee) = &card.assignee {
+        let _ = writeln!(out, "  Assignee:  @{assignee}");
+    }
+    if let Some(due) = card.due {
+        let status = match board.due_status(card, Utc::now()) {
+            Some(DueStatus::Overdue) if color => " (overdue)".red().bold().to_string(),
+            Some(DueStatus::Overdue) => " (overdue)".into(),
+            Some(DueStatus::Soon) if color => " (due soon)".yellow().to_string(),
+            Some(DueStatus::Soon) => " (due soon)".into(),
+            _ => String::new(),
+        };
+        let _ = writeln!(out, "  Due:       {}{status}", time(due));
+    }
+    if let Some(snooze) = card.snooze {
+        let hoist = if snooze.hoist { ", back on top" } else { "" };
+        let _ = writeln!(out, "  Snoozed:   until {}{hoist}", time(snooze.until));
+    }
+    if card.archived {
+        let _ = writeln!(out, "  Archived:  yes");
+    }
+    let _ = writeln!(
+        out,
+        "  Created:   {}{}",
+        time(card.created_at),
+        via(Origin::of_card(card).as_ref())
+    );
+    let _ = writeln!(out, "  Updated:   {}", time(card.updated_at));
+    if !Transition::history(card).is_empty() {
+        let spent: Vec<String> = card
+            .time_in_columns(Utc::now())
+            .iter()
+            .map(|(column, t)| format!("{column} {}", span(*t)))
+            .collect();
+        let _ = writeln!(out, "  Time in:   {}", spent.join(", "));
+    }
+
+    let checklist = card.checklist();
+    if !checklist.is_empty() {
+        let done = checklist.iter().filter(|i| i.done).count();
+        let _ = writeln!(out, "  Checklist: {done}/{} done", checklist.len());
+    }
+
+    if let Some(description) = &card.description {
+        out.push('\n');
+        for line in description.lines() {
+            let _ = writeln!(out, "  {line}");
+        }
+    }
+
+    if !card.attachments.is_empty() {
+        out.push_str("\nAttachments:\n");
+        for attachment in &card.attachments {
+            let title = attachment
+                .title
+                .as_deref()
+                .map(|t| format!("{t}: "))
+                .unwrap_or_default();
+            let missing = match attachment.exists(root) {
+                true => String::new(),
+                false if color => " (missing)".red().to_string(),
+                false => " (missing)".into(),
+            };
+            let _ = writeln!(out, "  {title}{}{missing}", attachment.target);
+        }
+    }
+
+    if !card.comments.is_empty() {
+        out.push_str("\nComments:\n");
+        for comment in &card.comments {
+            let _ = writeln!(out, "  {}, {}", comment.author, time(comment.at));
+            for line in comment.body.lines() {
+                let _ = writeln!(out, "    {line}");
+            }
+        }
+    }
+
+    // The origin is shown with the creation date above
+    let mut keys: Vec<&String> = card.metadata.keys().filter(|k| *k != ORIGIN_KEY).collect();
+    if !keys.is_empty() {
+        out.push_str("\nMetadata:\n");
+        keys.sort();
+        for key in keys {
+            match &card.metadata[key] {
+                serde_json::Value::String(s) => {
+                    let _ = writeln!(out, "  {key}: {s}");
+                }
+                value @ (serde_json::Value::Array(_) | serde_json::Value::Object(_)) => {
+                    let _ = writeln!(out, "  {key}:");
+                    let pretty = serde_json::to_string_pretty(value).unwrap_or_default();
+                    for line in pretty.lines() {
+                        let _ = writeln!(out, "    {line}");
+                    }
+                }
+                value => {
+                    let _ = writeln!(out, "  {key}: {value}");
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Text rendering of a board as printed by `kuk list`. With `color`,
+/// labels are drawn in their registry colors.
+pub fn render_list(board: &Board, sort: CardSort, color: bool) -> String {
+    let mut out = String::new();
+    for col in &board.columns {
+        let mut cards = board.column_cards(&col.name);
+        if sort != CardSort::default() {
+            cards.sort_by(|a, b| sort.cmp(a, b));
+        }
+
+        let wip = col
+            .wip_limit
+            .map(|l| format!(" [{}/{}]", cards.len(), l))
+            .unwrap_or_default();
+        let queue = if col.queue { " queue" } else { "" };
+
+        let _ = writeln!(
+            out,
+            "── {} ({}){}{}──",
+            col.name.to_uppercase(),
+            cards.len(),
+            wip,
+            queue
+        );
+
+        for (i, card) in cards.iter().enumerate() {
+            let _ = writeln!(out, "  {}. {}", i + 1, card_line(board, card, color));
+        }
+        out.push('\n');
+    }
+    out.push_str(&snoozed_note(board));
+    out
+}
+
+/// The board as `kuk list --lanes` prints it: each swimlane with the
+/// columns it has cards in.
+pub fn render_lanes(board: &Board, by: Swimlanes, sort: CardSort, color: bool) -> String {
+    let mut out = String::new();
+    for lane in board.lanes(by) {
+        let total: usize = lane.columns.iter().map(Vec::len).sum();
+        let _ = writeln!(
+            out,
+            "══ {} ({total}) ══",
+            lane.name.unwrap_or(by.unset_name())
+        );
+        for (col, mut cards) in board.columns.iter().zip(lane.columns) {
+            if cards.is_empty() {
+                continue;
+            }
+            if sort != CardSort::default() {
+                cards.sort_by(|a, b| sort.cmp(a, b));
+            }
+            let _ = writeln!(out, "── {} ({})──", col.name.to_uppercase(), cards.len());
+            for (i, card) in cards.iter().enumerate() {
+                let _ = writeln!(out, "  {}. {}", i + 1, card_line(board, card, color));
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str(&snoozed_note(board));
+    out
+}
+
+/// Swimlanes with their cards per column, as printed by
+/// `kuk list --lanes --json`.
+fn lanes_json(board: &Board, by: Swimlanes, sort: CardSort) -> serde_json::Value {
+    board
+        .lanes(by)
+        .into_iter()
+        .map(|lane| {
+            let columns: Vec<serde_json::Value> = board
+                .columns
+                .iter()
+                .zip(lane.columns)
+                .map(|(col, mut cards)| {
+                    if sort != CardSort::default() {
+                        cards.sort_by(|a, b| sort.cmp(a, b));
+                    }
+                    serde_json::json!({"name": col.name, "cards": cards})
+                })
+                .collect();
+            serde_json::json!({"lane": lane.name, "columns": columns})
+        })
+        .collect()
+}
+
+/// A card's title with its priority, labels, assignee and due date.
+fn card_line(board: &Board, card: &Card, color: bool) -> String {
+    let labels = if card.labels.is_empty() {
+        String::new()
+    } else {
+        let painted: Vec<String> = card
+            .labels
+            .iter()
+            .map(|l| paint_label(l, board.label_def(l), color))
+            .collect();
+        format!(" [{}]", painted.join(", "))
+    };
+    let assignee = card
+        .assignee
+        .as_ref()
+        .map(|a| format!(" @{a}"))
+        .unwrap_or_default();
+    let priority = priority_marker(card, color);
+    let due = due_marker(board, card, color);
+    format!("{}{}{}{}{}", card.title, priority, labels, assignee, due)
+}
+
+/// The line under a listing about snoozed cards, if there are any.
+fn snoozed_note(board: &Board) -> String {
+    let snoozed = board.snoozed_cards(Utc::now());
+    match snoozed.first().and_then(|c| c.snooze) {
+        Some(next) => {
+            let cards = if snoozed.len() == 1 { "card" } else { "cards" };
+            format!(
+                "{} snoozed {cards} hidden, next back {}\n",
+                snoozed.len(),
+                next.until.format("%Y-%m-%d")
+            )
+        }
+        None => String::new(),
+    }
+}
+
+/// Ask a yes/no question on stderr and read the answer from stdin. Only
+/// `y` or `yes` agrees, so closed input declines.
+fn confirm(question: &str) -> Result<bool> {
+    eprint!("{question} [y/N] ");
+    std::io::Write::flush(&mut std::io::stderr())?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Optional fields for `kuk add`.
+#[derive(Debug, Default)]
+pub struct AddOptions {
+    pub labels: Vec<String>,
+    pub assignee: Option<String>,
+    /// Leave out the `new_cards` defaults from config.json.
+    pub no_defaults: bool,
+    pub force: bool,
+    pub template: Option<String>,
+    /// `key=value` pairs for template placeholders.
+    pub vars: Vec<String>,
+    pub due: Option<String>,
+    pub priority: Option<Priority>,
+}
+
+pub fn add(
+    store: &Store,
+    title: &str,
+    column: Option<&str>,
+    opts: AddOptions,
+    json_output: bool,
+) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+    let defaults = new_card_defaults(&config, &opts);
+
+    let column = match column {
+        Some(column) => column.to_string(),
+        None => defaults
+            .column(&board)
+            .ok_or_else(|| KukError::Other(format!("Board {} has no columns", board.name)))?,
+    };
+    let column = column.as_str();
+    if !board.has_column(column) {
+        return Err(KukError::ColumnNotFound(column.into()));
+    }
+
+    let due = opts.due.as_deref().map(parse_due).transpose()?;
+    let mut title = title.to_string();
+    let mut labels = opts.labels;
+    let mut assignee = opts.assignee;
+    let mut description = None;
+    if let Some(name) = &opts.template {
+        let rendered = render_template(store, name, &title, &opts.vars)?;
+        title = rendered.title;
+        description = rendered.description;
+        assignee = assignee.or(rendered.assignee);
+        // Template labels first, then any extra --label
+        let extra = std::mem::replace(&mut labels, rendered.labels);
+        for label in extra {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+    }
+
+    let mut card = Card::new(title, column);
+    card.labels = labels;
+    card.assignee = assignee;
+    card.description = description;
+    card.due = due;
+    card.priority = opts.priority;
+    defaults.apply(&mut card);
+
+    let card = add_checked(&mut board, card, config.duplicates, opts.force)?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&card)?);
+    } else {
+        let short_id = card.short_id().unwrap_or_default();
+        println!("Added: {} → {} ({short_id})", card.title, card.column);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+/// The `new_cards` defaults `kuk add` applies: none with `--no-defaults`.
+fn new_card_defaults(config: &RepoConfig, opts: &AddOptions) -> CardDefaults {
+    if opts.no_defaults {
+        CardDefaults::default()
+    } else {
+        config.new_cards.clone()
+    }
+}
+
+/// Add `card` to the end of its column after checking its labels, the
+/// column's WIP limit and the duplicate policy. Duplicates the policy lets
+/// through get a warning.
+fn add_checked(
+    board: &mut Board,
+    mut card: Card,
+    duplicates: DuplicatePolicy,
+    force: bool,
+) -> Result<&Card> {
+    let column = card.column.clone();
+    board
+        .check_labels(&card.labels)
+        .map_err(|l| KukError::UnknownLabel(l.into()))?;
+
+    if !force && let Some(limit) = board.wip_exceeded(&column, None) {
+        return Err(KukError::WipLimitReached(format!(
+            "{column} is at its limit of {limit} cards. Use --force to add anyway."
+        )));
+    }
+
+    if let Some(dup) = board.duplicate_of(&card.title, duplicates) {
+        let existing = format!("{} ({})", dup.title, dup.column);
+        if duplicates == DuplicatePolicy::Reject && !force {
+            return Err(KukError::DuplicateCard(format!(
+                "{existing}. Use --force to add anyway."
+            )));
+        }
+        eprintln!("Warning: similar card already exists: {existing}");
+    }
+
+    card.order = board.next_order(&column);
+    Ok(board.add_card(card))
+}
+
+/// A card on one line of `kuk add --stdin`, given as JSON.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LineCard {
+    title: String,
+    #[serde(default)]
+    column: Option<String>,
+    #[serde(default)]
     labels: Vec<String>,
+    #[serde(default)]
     assignee: Option<String>,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(default)]
+    priority: Option<Priority>,
+}
+
+/// Read the card on one line: a JSON object, or a title with any list
+/// marker (`-`, `*`, `- [ ]`) dropped. `None` for blank lines.
+fn parse_card_line(line: &str) -> std::result::Result<Option<LineCard>, String> {
+    let line = line.trim();
+    if line.starts_with('{') {
+        return serde_json::from_str(line)
+            .map(Some)
+            .map_err(|e| format!("invalid JSON: {e}"));
+    }
+    let mut title = line;
+    for marker in ["- [ ] ", "* [ ] ", "- ", "* "] {
+        if let Some(rest) = title.strip_prefix(marker) {
+            title = rest.trim_start();
+            break;
+        }
+    }
+    Ok((!title.is_empty()).then(|| LineCard {
+        title: title.to_string(),
+        column: None,
+        labels: Vec::new(),
+        assignee: None,
+        due: None,
+        priority: None,
+    }))
+}
+
+/// The card a line describes, with `opts` filling in what it leaves out.
+fn line_card(
+    spec: LineCard,
+    column: String,
+    opts: &AddOptions,
+    default_due: Option<DateTime<Utc>>,
+) -> Result<Card> {
+    let mut card = Card::new(spec.title, column);
+    card.labels = opts.labels.clone();
+    for label in spec.labels {
+        if !card.labels.contains(&label) {
+            card.labels.push(label);
+        }
+    }
+    card.assignee = spec.assignee.or_else(|| opts.assignee.clone());
+    card.due = match spec.due {
+        Some(due) => Some(parse_due(&due)?),
+        None => default_due,
+    };
+    card.priority = spec.priority.or(opts.priority);
+    Ok(card)
+}
+
+/// `kuk add --stdin`: add a card for every line of `input`. The options
+/// apply to every card; a JSON line's column replaces `column`, and its
+/// labels come on top of `--label`. Either every card is added or, when a
+/// line has a problem, none are.
+pub fn add_lines(
+    store: &Store,
+    input: impl BufRead,
+    column: Option<&str>,
+    opts: AddOptions,
+    json_output: bool,
+) -> Result<()> {
+    // Read all of stdin before locking, since it may be typed at leisure
+    let lines = input
+        .lines()
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(|e| KukError::Other(format!("Cannot read stdin: {e}")))?;
+    let _lock = store.lock()?;
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+    let defaults = new_card_defaults(&config, &opts);
+    let default_column = match column {
+        Some(column) => column.to_string(),
+        None => defaults
+            .column(&board)
+            .ok_or_else(|| KukError::Other(format!("Board {} has no columns", board.name)))?,
+    };
+    let default_due = opts.due.as_deref().map(parse_due).transpose()?;
+
+    let mut added = Vec::new();
+    let mut problems = Vec::new();
+    for (n, line) in lines.iter().enumerate() {
+        let spec = match parse_card_line(line) {
+            Ok(Some(spec)) => spec,
+            Ok(None) => continue,
+            Err(e) => {
+                problems.push(format!("line {}: {e}", n + 1));
+                continue;
+            }
+        };
+        let column = spec
+            .column
+            .clone()
+            .unwrap_or_else(|| default_column.clone());
+        let result = if board.has_column(&column) {
+            line_card(spec, column, &opts, default_due).and_then(|mut card| {
+                defaults.apply(&mut card);
+                add_checked(&mut board, card, config.duplicates, opts.force).map(|c| c.id.clone())
+            })
+        } else {
+            Err(KukError::ColumnNotFound(column))
+        };
+        match result {
+            Ok(id) => added.push(id),
+            Err(e) => problems.push(format!("line {}: {e}", n + 1)),
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(KukError::Other(format!(
+            "No cards added. Fix these lines and try again:\n  {}",
+            problems.join("\n  ")
+        )));
+    }
+
+    let cards: Vec<&Card> = added.iter().filter_map(|id| board.find_card(id)).collect();
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&cards)?);
+    } else if cards.is_empty() {
+        println!("No cards on stdin.");
+    } else {
+        for card in &cards {
+            let short_id = card.short_id().unwrap_or_default();
+            println!("Added: {} → {} ({short_id})", card.title, card.column);
+        }
+    }
+
+    if !added.is_empty() {
+        store.save_board(&board)?;
+    }
+    Ok(())
+}
+
+/// `kuk import markdown`: add a card for every top-level task in the
+/// file, open ones to `column` and ticked ones to the board's last column.
+/// Like `add --stdin`, either every card is added or none are.
+pub fn import_markdown(
+    store: &Store,
+    file: &Path,
+    column: Option<&str>,
+    force: bool,
+    json_output: bool,
+) -> Result<()> {
+    let markdown = std::fs::read_to_string(file)
+        .map_err(|e| KukError::Other(format!("Cannot read {}: {e}", file.display())))?;
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+    let (Some(first), Some(last)) = (board.columns.first(), board.columns.last()) else {
+        return Err(KukError::Other(format!(
+            "Board {} has no columns",
+            board.name
+        )));
+    };
+    let open_column = column.unwrap_or(&first.name).to_string();
+    let done_column = last.name.clone();
+    if !board.has_column(&open_column) {
+        return Err(KukError::ColumnNotFound(open_column));
+    }
+
+    let mut added = Vec::new();
+    let mut problems = Vec::new();
+    for item in parse_markdown_cards(&markdown) {
+        let column = if item.done {
+            &done_column
+        } else {
+            &open_column
+        };
+        let mut card = Card::new(item.title, column.clone());
+        card.labels = item.labels;
+        card.assignee = item.assignee;
+        card.description = item.description;
+        match add_checked(&mut board, card, config.duplicates, force) {
+            Ok(card) => added.push(card.id.clone()),
+            Err(e) => problems.push(format!("line {}: {e}", item.line)),
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(KukError::Other(format!(
+            "No cards imported. Fix these lines and try again:\n  {}",
+            problems.join("\n  ")
+        )));
+    }
+
+    let cards: Vec<&Card> = added.iter().filter_map(|id| board.find_card(id)).collect();
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&cards)?);
+    } else if cards.is_empty() {
+        println!("No `- [ ]` items in {}.", file.display());
+    } else {
+        for card in &cards {
+            let short_id = card.short_id().unwrap_or_default();
+            println!("Imported: {} → {} ({short_id})", card.title, card.column);
+        }
+    }
+
+    if !added.is_empty() {
+        store.save_board(&board)?;
+    }
+    Ok(())
+}
+
+fn render_template(
+    store: &Store,
+    name: &str,
+    title: &str,
+    vars: &[String],
+) -> Result<RenderedTemplate> {
+    let template = match store.load_card_template(name) {
+        Err(KukError::TemplateNotFound(_)) => {
+            let available = store.list_card_templates()?;
+            let hint = if available.is_empty() {
+                "No templates in .kuk/card-templates/".to_string()
+            } else {
+                format!("Available: {}", available.join(", "))
+            };
+            return Err(KukError::Other(format!(
+                "Card template not found: {name}. {hint}"
+            )));
+        }
+        other => other?,
+    };
+
+    let mut values = HashMap::new();
+    for var in vars {
+        let (key, value) = var
+            .split_once('=')
+            .ok_or_else(|| KukError::Other(format!("Invalid --var {var}: expected key=value")))?;
+        values.insert(key.trim().to_string(), value.to_string());
+    }
+
+    template.render(title, &values).map_err(|missing| {
+        let flags: Vec<String> = missing.iter().map(|m| format!("--var {m}=...")).collect();
+        KukError::Other(format!(
+            "Template {name} needs: {}. Pass {}",
+            missing.join(", "),
+            flags.join(" ")
+        ))
+    })
+}
+
+pub fn move_card(
+    store: &Store,
+    id_or_num: &str,
+    to: &str,
+    force: bool,
+    json_output: bool,
+) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    if !board.has_column(to) {
+        return Err(KukError::ColumnNotFound(to.into()));
+    }
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    if !force && let Some(limit) = board.wip_exceeded(to, Some(&card_id)) {
+        return Err(KukError::WipLimitReached(format!(
+            "{to} is at its limit of {limit} cards. Use --force to move anyway."
+        )));
+    }
+
+    board.move_card(&card_id, to);
+    let card = board.find_card(&card_id).unwrap();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else {
+        println!("Moved: {} → {}", card.title, to);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn clone_card(
+    store: &Store,
+    id_or_num: &str,
+    to: Option<&str>,
+    board_name: Option<&str>,
+    force: bool,
+    json_output: bool,
+) -> Result<()> {
+    let config = store.load_config()?;
+    let source = store.load_board(&config.default_board)?;
+    let card = source
+        .resolve_card_id(id_or_num)
+        .and_then(|id| source.find_card(&id))
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    let mut board = match board_name {
+        Some(name) if name != source.name => store.load_board(name)?,
+        _ => source.clone(),
+    };
+    let column = match to {
+        Some(to) => to.to_string(),
+        None if board.has_column(&card.column) => card.column.clone(),
+        None => board
+            .columns
+            .first()
+            .map(|c| c.name.clone())
+            .ok_or_else(|| KukError::Other(format!("Board {} has no columns", board.name)))?,
+    };
+    if !board.has_column(&column) {
+        return Err(KukError::ColumnNotFound(column));
+    }
+    board
+        .check_labels(&card.labels)
+        .map_err(|l| KukError::UnknownLabel(l.into()))?;
+    if !force && let Some(limit) = board.wip_exceeded(&column, None) {
+        return Err(KukError::WipLimitReached(format!(
+            "{column} is at its limit of {limit} cards. Use --force to add anyway."
+        )));
+    }
+
+    let on_board = if board.name == source.name {
+        String::new()
+    } else {
+        format!(" on {}", board.name)
+    };
+    let mut copy = card.duplicate(&column);
+    copy.order = board.next_order(&column);
+    let copy = board.add_card(copy);
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&copy)?);
+    } else {
+        let short_id = copy.short_id().unwrap_or_default();
+        println!(
+            "Cloned: {} → {}{on_board} ({short_id})",
+            copy.title, copy.column
+        );
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn hoist(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    board.hoist_card(&card_id);
+    let card = board.find_card(&card_id).unwrap();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else {
+        println!("Hoisted: {} to top of {}", card.title, card.column);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn demote(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    board.demote_card(&card_id);
+    let card = board.find_card(&card_id).unwrap();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else {
+        println!("Demoted: {} to bottom of {}", card.title, card.column);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn due(store: &Store, id_or_num: &str, date: Option<&str>, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    let due = date.map(parse_due).transpose()?;
+
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    card.due = due;
+    card.updated_at = Utc::now();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else if let Some(due) = due {
+        println!("Due: {} on {}", card.title, due.format("%Y-%m-%d"));
+    } else {
+        println!("Cleared due date: {}", card.title);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn priority(
+    store: &Store,
+    id_or_num: &str,
+    level: Option<Priority>,
+    json_output: bool,
+) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    card.priority = level;
+    card.updated_at = Utc::now();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else if let Some(level) = level {
+        println!("Priority: {} is {level}", card.title);
+    } else {
+        println!("Cleared priority: {}", card.title);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+/// Parse a date argument, either RFC 3339 or a bare date taken at `time`
+/// UTC.
+fn parse_date(s: &str, time: NaiveTime) -> Result<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(s) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|d| d.and_time(time).and_utc())
+        .map_err(|_| KukError::Other(format!("Invalid date: {s} (expected YYYY-MM-DD)")))
+}
+
+/// A due date: a bare date runs to the end of that day.
+fn parse_due(s: &str) -> Result<DateTime<Utc>> {
+    let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).expect("valid time");
+    parse_date(s, end_of_day)
+}
+
+pub fn snooze(
+    store: &Store,
+    id_or_num: &str,
+    until: Option<&str>,
+    hoist: bool,
+    json_output: bool,
+) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    let snooze = match until {
+        Some(until) => {
+            let until = parse_date(until, NaiveTime::MIN)?;
+            if until <= Utc::now() {
+                return Err(KukError::Other(format!(
+                    "Snooze date is in the past: {}",
+                    until.format("%Y-%m-%d")
+                )));
+            }
+            Some(Snooze { until, hoist })
+        }
+        None => None,
+    };
+
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    card.snooze = snooze;
+    card.updated_at = Utc::now();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else if let Some(snooze) = snooze {
+        println!(
+            "Snoozed: {} until {}",
+            card.title,
+            snooze.until.format("%Y-%m-%d")
+        );
+    } else {
+        println!("Woke: {}", card.title);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn archive(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    card.archive();
+    card.updated_at = Utc::now();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else {
+        println!("Archived: {}", card.title);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn archived(store: &Store, board_name: Option<&str>, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let board = store.load_board(board_name.unwrap_or(&config.default_board))?;
+    let cards = board.archived_cards();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&cards)?);
+        return Ok(());
+    }
+
+    if cards.is_empty() {
+        println!("No archived cards on {}.", board.name);
+        return Ok(());
+    }
+    let color = std::io::stdout().is_terminal();
+    for card in &cards {
+        let id = card.short_id().unwrap_or_else(|| card.id.clone());
+        let when = card
+            .archived_at()
+            .map(|at| format!("  archived {}", at.format("%Y-%m-%d")))
+            .unwrap_or_default();
+        println!(
+            "  {id}  {}  {}{when}",
+            card.column,
+            card_line(&board, card, color)
+        );
+    }
+    println!(
+        "\n{} archived card(s). Restore one with `kuk unarchive <id>`.",
+        cards.len()
+    );
+    Ok(())
+}
+
+pub fn unarchive(store: &Store, id: &str, force: bool, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    // Numbers count active cards, so an archived card needs its ID
+    let card = board
+        .resolve_card_id(id)
+        .and_then(|card_id| board.find_card(&card_id))
+        .ok_or_else(|| KukError::CardNotFound(id.into()))?;
+    if !card.archived {
+        return Err(KukError::Other(format!("{} is not archived.", card.title)));
+    }
+    let card_id = card.id.clone();
+    let column = if board.has_column(&card.column) {
+        card.column.clone()
+    } else {
+        board
+            .columns
+            .first()
+            .map(|c| c.name.clone())
+            .unwrap_or_default()
+    };
+    if !force && let Some(limit) = board.wip_exceeded(&column, Some(&card_id)) {
+        return Err(KukError::WipLimitReached(format!(
+            "{column} is at its limit of {limit} cards. Use --force to restore anyway."
+        )));
+    }
+
+    let column = board.unarchive_card(&card_id).unwrap_or(column);
+    let card = board.find_card(&card_id).unwrap();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(card)?);
+    } else {
+        println!("Unarchived: {} → {column}", card.title);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn delete(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    let card = board
+        .find_card(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    let title = card.title.clone();
+    board.cards.retain(|c| c.id != card_id);
+
+    if json_output {
+        println!(
+            "{}",
+            serde_json::json!({"deleted": card_id, "title": title})
+        );
+    } else {
+        println!("Deleted: {}", title);
+    }
+
+    store.save_board(&board)?;
+    Ok(())
+}
+
+pub fn undo(store: &Store, redo: bool, json_output: bool) -> Result<()> {
+    let op = if redo { store.redo()? } else { store.undo()? };
+    let (verb, key) = if redo {
+        ("Redid", "redone")
+    } else {
+        ("Undid", "undone")
+    };
+    if json_output {
+        let boards: Vec<&str> = op.boards.iter().map(|b| b.board.as_str()).collect();
+        println!(
+            "{}",
+            serde_json::json!({key: op.command, "at": op.at, "boards": boards})
+        );
+    } else {
+        println!("{verb}: {}", op.command);
+    }
+    Ok(())
+}
+
+pub fn usage(store: &Store, action: Option<&str>, json_output: bool) -> Result<()> {
+    let mut config = store.load_config()?;
+    match action {
+        Some("on" | "off") => {
+            config.usage_log = action == Some("on");
+            store.save_config(&config)?;
+            if json_output {
+                println!("{}", serde_json::json!({"usage_log": config.usage_log}));
+            } else if config.usage_log {
+                println!(
+                    "Recording command usage in .kuk/usage.json (it never leaves this machine)"
+                );
+            } else {
+                println!(
+                    "Stopped recording command usage; `kuk usage clear` removes what was kept"
+                );
+            }
+            return Ok(());
+        }
+        Some(_) => {
+            store.clear_usage()?;
+            if json_output {
+                println!("{}", serde_json::json!({"cleared": true}));
+            } else {
+                println!("Cleared the usage log");
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let log = store.load_usage()?;
+    if json_output {
+        let mut value = serde_json::to_value(&log)?;
+        value["usage_log"] = config.usage_log.into();
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+    if log.commands.is_empty() {
+        if config.usage_log {
+            println!("No commands recorded yet.");
+        } else {
+            println!("Usage logging is off; `kuk usage on` records runs in .kuk/usage.json.");
+        }
+        return Ok(());
+    }
+    if let Some(since) = log.since {
+        println!("Commands run since {}", since.format("%Y-%m-%d"));
+    }
+    let width = log.commands.keys().map(String::len).max().unwrap_or(0);
+    println!(
+        "  {:<width$}  {:>6}  {:>8}  {:>8}  {:>8}",
+        "command", "runs", "failed", "avg ms", "max ms"
+    );
+    for (name, usage) in log.by_runs() {
+        println!(
+            "  {name:<width$}  {:>6}  {:>8}  {:>8}  {:>8}",
+            usage.runs,
+            usage.failures,
+            usage.avg_ms(),
+            usage.max_ms
+        );
+    }
+    if !config.usage_log {
+        println!("Usage logging is off; these are from before it was turned off.");
+    }
+    Ok(())
+}
+
+pub fn label(
+    store: &Store,
+    id_or_num: &str,
+    action: &str,
+    tag: &str,
     json_output: bool,
 ) -> Result<()> {
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
 
-    if !board.has_column(column) {
-        return Err(KukError::ColumnNotFound(column.into()));
+    let card_id = board
+        .resolve_card_id(id_or_num)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    if action == "add" {
+        board
+            .check_labels(std::slice::from_ref(&tag.to_string()))
+            .map_err(|l| KukError::UnknownLabel(l.into()))?;
     }
 
-    let mut card = Card::new(title, column);
-    card.order = board.next_order(column);
-    card.labels = labels;
-    card.assignee = assignee;
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+
+    match action {
+        "add" => {
+            card.add_label(tag);
+        }
+        "remove" => {
+            if !card.remove_label(tag) {
+                return Err(KukError::LabelNotFound(tag.into()));
+            }
+        }
+        _ => {
+            return Err(KukError::Other(format!(
+                "Invalid label action: {action}. Use 'add' or 'remove'."
+            )));
+        }
+    }
+
+    card.updated_at = Utc::now();
 
     if json_output {
-        println!("{}", serde_json::to_string_pretty(&card)?);
+        println!("{}", serde_json::to_string_pretty(card)?);
     } else {
-        println!("Added: {} → {}", card.title, card.column);
+        println!("Labels on {}: [{}]", card.title, card.labels.join(", "));
     }
 
-    board.cards.push(card);
     store.save_board(&board)?;
     Ok(())
 }
 
-pub fn move_card(store: &Store, id_or_num: &str, to: &str, json_output: bool) -> Result<()> {
+pub fn assign(store: &Store, id_or_num: &str, user: &str, json_output: bool) -> Result<()> {
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
 
-    if !board.has_column(to) {
-        return Err(KukError::ColumnNotFound(to.into()));
-    }
-
     let card_id = board
         .resolve_card_id(id_or_num)
         .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
 
-    let next_order = board.next_order(to);
     let card = board
         .find_card_mut(&card_id)
         .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
 
-    card.column = to.into();
-    card.order = next_order;
+    card.set_assignee(Some(user.into()));
     card.updated_at = Utc::now();
 
     if json_output {
         println!("{}", serde_json::to_string_pretty(card)?);
     } else {
-        println!("Moved: {} → {}", card.title, to);
+        println!("Assigned {} to @{}", card.title, user);
     }
 
     store.save_board(&board)?;
     Ok(())
 }
 
-pub fn hoist(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+pub fn edit(
+    store: &Store,
+    id_or_num: &str,
+    title: Option<String>,
+    description: Option<String>,
+    lane: Option<String>,
+    json_output: bool,
+) -> Result<()> {
     let config = store.load_config()?;
+    let (title, description) = if title.is_none() && description.is_none() && lane.is_none() {
+        let board = store.load_board(&config.default_board)?;
+        let card = board
+            .resolve_card_id(id_or_num)
+            .and_then(|id| board.find_card(&id))
+            .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+        let (title, description) = edit_in_editor(store, card)?;
+        (Some(title), Some(description))
+    } else {
+        (title, description)
+    };
+
+    // Load afresh under the lock, as the editor may have been open a while
+    let _lock = store.lock()?;
     let mut board = store.load_board(&config.default_board)?;
 
     let card_id = board
         .resolve_card_id(id_or_num)
         .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
 
-    let column = board.find_card(&card_id).unwrap().column.clone();
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
 
-    // Shift all other cards in the column down by 1
-    for c in board.cards.iter_mut() {
-        if c.column == column && !c.archived && c.id != card_id {
-            c.order += 1;
+    if let Some(title) = title {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(KukError::Other("Title must not be empty".into()));
         }
+        card.title = title.into();
+    }
+    if let Some(description) = description {
+        let description = description.trim();
+        card.description = (!description.is_empty()).then(|| description.into());
+    }
+    if let Some(lane) = lane {
+        let lane = lane.trim();
+        card.lane = (!lane.is_empty()).then(|| lane.into());
     }
-
-    let card = board.find_card_mut(&card_id).unwrap();
-    card.order = 0;
     card.updated_at = Utc::now();
 
     if json_output {
         println!("{}", serde_json::to_string_pretty(card)?);
     } else {
-        println!("Hoisted: {} to top of {}", card.title, column);
+        println!("Edited {}", card.title);
     }
 
     store.save_board(&board)?;
     Ok(())
 }
 
-pub fn demote(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+pub fn comment(
+    store: &Store,
+    id_or_num: &str,
+    body: &str,
+    author: Option<String>,
+    json_output: bool,
+) -> Result<()> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(KukError::Other("Comment must not be empty".into()));
+    }
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
 
@@ -310,207 +2494,1268 @@ pub fn demote(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
         .resolve_card_id(id_or_num)
         .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
 
-    let column = board.find_card(&card_id).unwrap().column.clone();
-    let max_order = board.next_order(&column);
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
 
-    let card = board.find_card_mut(&card_id).unwrap();
-    card.order = max_order;
-    card.updated_at = Utc::now();
+    let author = author.unwrap_or_else(|| default_author(store));
+    card.add_comment(author, body);
 
     if json_output {
         println!("{}", serde_json::to_string_pretty(card)?);
     } else {
-        println!("Demoted: {} to bottom of {}", card.title, column);
+        println!("Commented on {}", card.title);
     }
 
     store.save_board(&board)?;
     Ok(())
 }
 
-pub fn archive(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+pub fn attach(
+    store: &Store,
+    id_or_num: &str,
+    target: &str,
+    title: Option<String>,
+    remove: bool,
+    json_output: bool,
+) -> Result<()> {
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
-
-    let card_id = board
-        .resolve_card_id(id_or_num)
-        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
-
     let card = board
-        .find_card_mut(&card_id)
+        .resolve_card_id(id_or_num)
+        .and_then(|id| board.find_card_mut(&id))
         .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
 
-    card.archived = true;
-    card.updated_at = Utc::now();
+    let message = if remove {
+        let removed = card
+            .detach(target)
+            .ok_or_else(|| KukError::Other(format!("{} has no attachment {target}", card.title)))?;
+        format!("Detached {} from {}", removed.target, card.title)
+    } else {
+        let attachment =
+            Attachment::new(target, title, store.repo_root()).map_err(KukError::Other)?;
+        let shown = attachment.target.clone();
+        if card.attach(attachment) {
+            format!("Attached {shown} to {}", card.title)
+        } else {
+            format!("Updated {shown} on {}", card.title)
+        }
+    };
 
     if json_output {
         println!("{}", serde_json::to_string_pretty(card)?);
     } else {
-        println!("Archived: {}", card.title);
+        println!("{message}");
     }
-
     store.save_board(&board)?;
     Ok(())
 }
 
-pub fn delete(store: &Store, id_or_num: &str, json_output: bool) -> Result<()> {
+/// Who a comment is from when `--author` isn't given: the repo's git
+/// `user.name`, then the login name.
+fn default_author(store: &Store) -> String {
+    std::process::Command::new("git")
+        .args(["config", "user.name"])
+        .current_dir(store.repo_root())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Open the card in `$VISUAL` or `$EDITOR` as a title line, a blank line and
+/// the description, and read both back once the editor exits.
+fn edit_in_editor(store: &Store, card: &Card) -> Result<(String, String)> {
+    let path = store.kuk_dir().join("EDIT_CARD.md");
+    let draft = format!(
+        "{}\n\n{}\n",
+        card.title,
+        card.description.as_deref().unwrap_or_default()
+    );
+    std::fs::write(&path, draft).map_err(KukError::write(&path))?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".into());
+    // Run through the shell so an editor with arguments, like `code --wait`, works
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("sh")
+        .arg(&path)
+        .status();
+    let edited = std::fs::read_to_string(&path).map_err(KukError::read(&path));
+    let _ = std::fs::remove_file(&path);
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(_) => return Err(KukError::Other(format!("{editor} exited with an error"))),
+        Err(e) => return Err(KukError::Other(format!("Could not run {editor}: {e}"))),
+    }
+
+    let edited = edited?;
+    let (title, description) = edited.split_once('\n').unwrap_or((&edited, ""));
+    Ok((title.into(), description.into()))
+}
+
+/// Directories `kuk scan` never descends into, besides hidden ones.
+const SCAN_SKIP_DIRS: &[&str] = &["target", "node_modules", "vendor"];
+
+pub fn scan(
+    store: &Store,
+    paths: &[PathBuf],
+    column: &str,
+    apply: bool,
+    json_output: bool,
+) -> Result<()> {
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
+    if !board.has_column(column) {
+        return Err(KukError::ColumnNotFound(column.into()));
+    }
 
-    let card_id = board
-        .resolve_card_id(id_or_num)
-        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    let root = store.repo_root();
+    let mut files = Vec::new();
+    for path in paths {
+        let full = root.join(path);
+        if !full.exists() {
+            return Err(KukError::Other(format!("No such path: {}", path.display())));
+        }
+        collect_source_files(&full, &mut files);
+    }
+    files.sort();
+    files.dedup();
+
+    let relative = |path: &std::path::Path| {
+        let rel = path.strip_prefix(root).unwrap_or(path);
+        rel.components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+    let mut found = Vec::new();
+    for file in &files {
+        // Binary and unreadable files have no comments worth tracking
+        if let Ok(contents) = std::fs::read_to_string(file) {
+            found.extend(CodeComment::find(&relative(file), &contents));
+        }
+    }
+    let count = found.len();
+
+    let scanned: Vec<String> = paths.iter().map(|p| relative(&root.join(p))).collect();
+    let covers = |file: &str| {
+        scanned.iter().any(|dir| {
+            dir.is_empty()
+                || file == dir
+                || file
+                    .strip_prefix(dir.as_str())
+                    .is_some_and(|r| r.starts_with('/'))
+        })
+    };
+    let result = board.sync_code_comments(found, column, covers);
+    if apply && !result.is_empty() {
+        store.save_board(&board)?;
+    }
 
-    let card = board
-        .find_card(&card_id)
-        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    if json_output {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "comments": count,
+                "files": files.len(),
+                "applied": apply,
+                "added": result.added,
+                "moved": result.moved,
+                "closed": result.closed,
+            }))?
+        );
+        return Ok(());
+    }
+
+    println!("Found {count} comments in {} files", files.len());
+    for c in &result.added {
+        println!("  + {} ({}:{})", c.title(), c.file, c.line);
+    }
+    for c in &result.moved {
+        println!("  ~ {} now at {}:{}", c.title(), c.file, c.line);
+    }
+    for c in &result.closed {
+        println!("  ✓ {} gone from {}", c.title(), c.file);
+    }
+    if result.is_empty() {
+        println!("Board is up to date.");
+    } else if !apply {
+        println!("Dry run; pass --apply to update the board.");
+    }
+    Ok(())
+}
+
+/// Every file under `path`, skipping hidden and build directories.
+fn collect_source_files(path: &std::path::Path, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let path = entry.path();
+        if path.is_dir() {
+            if !name.starts_with('.') && !SCAN_SKIP_DIRS.contains(&name.as_ref()) {
+                collect_source_files(&path, files);
+            }
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+}
+
+pub fn stale(store: &Store, days: u32, escalate: bool, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+    let now = Utc::now();
+
+    let escalated = if escalate {
+        if config.aging.is_empty() {
+            return Err(KukError::Other(
+                "No aging rules. Add an \"aging\" list to .kuk/config.json.".into(),
+            ));
+        }
+        let escalated = board.escalate_stale(&config.aging, now);
+        if !escalated.is_empty() {
+            store.save_board(&board)?;
+        }
+        escalated
+    } else {
+        Vec::new()
+    };
+
+    // The last column holds finished work, which is never stale
+    let done = board.columns.last().map(|c| c.name.as_str());
+    let mut stale: Vec<&Card> = board
+        .cards
+        .iter()
+        .filter(|c| !c.archived && !c.is_snoozed(now) && Some(c.column.as_str()) != done)
+        .filter(|c| idle_days(c, now) >= i64::from(days))
+        .collect();
+    stale.sort_by_key(|c| c.updated_at);
+    let since = now - chrono::Duration::days(i64::from(days));
+    let recent = board
+        .cards
+        .iter()
+        .flat_map(Escalation::history)
+        .filter(|e| e.at >= since)
+        .count();
+
+    if json_output {
+        let title = |id: &str| board.find_card(id).map(|c| c.title.clone());
+        let escalated: Vec<_> = escalated
+            .iter()
+            .map(|(id, e)| serde_json::json!({"id": id, "title": title(id), "escalation": e}))
+            .collect();
+        let stale: Vec<_> = stale
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "id": c.id,
+                    "title": c.title,
+                    "column": c.column,
+                    "idle_days": idle_days(c, now),
+                    "escalations": Escalation::history(c).len(),
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "escalated": escalated,
+                "stale": stale,
+                "recent_escalations": recent,
+            }))?
+        );
+        return Ok(());
+    }
+
+    for (id, e) in &escalated {
+        let card = board.find_card(id).expect("escalated cards exist");
+        println!(
+            "Escalated: {} ({}, {} days idle in {})",
+            card.title, e.rule, e.idle_days, e.column
+        );
+    }
+    if stale.is_empty() {
+        println!("No cards untouched for {days}+ days");
+    } else {
+        println!("Untouched for {days}+ days:");
+        for card in &stale {
+            let count = Escalation::history(card).len();
+            let note = if count > 0 {
+                format!("  escalated {count}x")
+            } else {
+                String::new()
+            };
+            println!(
+                "  {:>3}d  {} [{}]{note}",
+                idle_days(card, now),
+                card.title,
+                card.column
+            );
+        }
+    }
+    if recent > 0 {
+        println!("{recent} escalation(s) in the last {days} days");
+    }
+    Ok(())
+}
+
+pub fn board(store: &Store, cmd: BoardCmd, json_output: bool) -> Result<()> {
+    match cmd {
+        BoardCmd::Create {
+            name,
+            columns,
+            template,
+        } => {
+            let template = match (columns, template) {
+                (Some(columns), _) => {
+                    BoardTemplate::from_columns(&columns).map_err(KukError::Other)?
+                }
+                (None, Some(template)) => Store::load_board_template(&template)?,
+                (None, None) => BoardTemplate::builtin("kanban").expect("built-in template"),
+            };
+            store.create_board_from(&name, &template)?;
+            if json_output {
+                println!("{}", serde_json::json!({"created": name}));
+            } else {
+                println!("Created board: {}", name);
+            }
+        }
+        BoardCmd::Switch { name } => {
+            // Verify board exists
+            store.load_board(&name)?;
+            let mut config = store.load_config()?;
+            config.default_board = name.clone();
+            store.save_config(&config)?;
+            if json_output {
+                println!("{}", serde_json::json!({"active": name}));
+            } else {
+                println!("Switched to board: {}", name);
+            }
+        }
+        BoardCmd::List => {
+            let config = store.load_config()?;
+            let boards = store.list_boards()?;
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&boards)?);
+            } else {
+                for b in &boards {
+                    if *b == config.default_board {
+                        println!("* {}", b);
+                    } else {
+                        println!("  {}", b);
+                    }
+                }
+            }
+        }
+        BoardCmd::Rename { old, new } => {
+            store.rename_board(&old, &new)?;
+            if json_output {
+                println!("{}", serde_json::json!({"renamed": old, "to": new}));
+            } else {
+                println!("Renamed board: {old} → {new}");
+            }
+        }
+        BoardCmd::Delete { name, to, yes } => {
+            if to.as_deref() == Some(name.as_str()) {
+                return Err(KukError::Other(format!(
+                    "Cannot move cards from {name} into itself"
+                )));
+            }
+            if !yes {
+                let count = store.load_board(&name)?.cards.len();
+                let question = match &to {
+                    Some(to) => {
+                        let to = store.load_board(to)?.name;
+                        format!("Delete board {name} and move its {count} cards to {to}?")
+                    }
+                    None => format!("Delete board {name} and its {count} cards?"),
+                };
+                if !confirm(&question)? {
+                    return Err(KukError::Other(format!("Board not deleted: {name}")));
+                }
+            }
+
+            let _lock = store.lock()?;
+            let board = store.load_board(&name)?;
+            let mut target = to.as_deref().map(|t| store.load_board(t)).transpose()?;
+
+            let was_active = store.load_config()?.default_board == name;
+            let moved = match target.as_mut() {
+                Some(target) => {
+                    let moved = target.absorb(board).map_err(KukError::Other)?;
+                    store.save_board(target)?;
+                    moved
+                }
+                None => 0,
+            };
+            store.delete_board(&name, to.as_deref())?;
+            let active = store.load_config()?.default_board;
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"deleted": name, "to": to, "moved": moved, "active": active})
+                );
+            } else {
+                match &to {
+                    Some(to) => println!("Deleted board: {name} ({moved} cards moved to {to})"),
+                    None => println!("Deleted board: {name}"),
+                }
+                if was_active {
+                    println!("Switched to board: {active}");
+                }
+            }
+        }
+        BoardCmd::SaveTemplate { name, board } => {
+            let board = match board {
+                Some(board) => board,
+                None => store.load_config()?.default_board,
+            };
+            let template = BoardTemplate::from_board(&store.load_board(&board)?);
+            let path = Store::save_board_template(&name, &template)?;
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"saved": name, "board": board, "path": path})
+                );
+            } else {
+                println!("Saved board template: {name} ({})", path.display());
+            }
+        }
+        BoardCmd::Templates => {
+            let templates = Store::list_board_templates()?;
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&templates)?);
+            } else {
+                for name in &templates {
+                    let template = Store::load_board_template(name);
+                    match template {
+                        Ok(t) => {
+                            let columns: Vec<&str> =
+                                t.columns.iter().map(|c| c.name.as_str()).collect();
+                            println!("  {name:<14} {}", columns.join(" → "));
+                        }
+                        Err(e) => println!("  {name:<14} ({e})"),
+                    }
+                }
+            }
+        }
+        BoardCmd::Lanes { by } => {
+            let config = store.load_config()?;
+            let mut board = store.load_board(&config.default_board)?;
+            if let Some(by) = by {
+                board.swimlanes = match by.to_lowercase().as_str() {
+                    "off" | "none" => None,
+                    _ => Some(by.parse().map_err(KukError::Other)?),
+                };
+                store.save_board(&board)?;
+            }
+            if json_output {
+                println!("{}", serde_json::json!({"swimlanes": board.swimlanes}));
+            } else {
+                match board.swimlanes {
+                    Some(by) => println!("Swimlanes for {}: by {by}", board.name),
+                    None => println!("No swimlanes for {}", board.name),
+                }
+            }
+        }
+        BoardCmd::Layout { layout } => {
+            let converted = match layout {
+                Some(layout) => store.set_layout(layout)?,
+                None => Vec::new(),
+            };
+            let layout = store.load_config()?.layout;
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"layout": layout, "converted": converted})
+                );
+            } else if converted.is_empty() {
+                println!("Boards are stored in the {layout} layout");
+            } else {
+                println!("Converted to the {layout} layout: {}", converted.join(", "));
+            }
+        }
+        BoardCmd::Rules {
+            column,
+            assignee,
+            labels,
+            clear,
+        } => {
+            let config = store.load_config()?;
+            let mut board = store.load_board(&config.default_board)?;
+            board
+                .check_labels(&labels)
+                .map_err(|l| KukError::UnknownLabel(l.into()))?;
+            let col = board
+                .columns
+                .iter_mut()
+                .find(|c| c.name == column)
+                .ok_or_else(|| KukError::ColumnNotFound(column.clone()))?;
+            let changed = clear || assignee.is_some() || !labels.is_empty();
+            if clear {
+                col.default_assignee = None;
+                col.auto_labels.clear();
+            }
+            if let Some(user) = assignee {
+                col.default_assignee = Some(user.trim_start_matches('@').to_string());
+            }
+            for label in labels {
+                if !col.auto_labels.contains(&label) {
+                    col.auto_labels.push(label);
+                }
+            }
+            let col = col.clone();
+            if changed {
+                store.save_board(&board)?;
+            }
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "column": col.name,
+                        "assignee": col.default_assignee,
+                        "labels": col.auto_labels,
+                    })
+                );
+            } else if col.default_assignee.is_none() && col.auto_labels.is_empty() {
+                println!("No rules for {}", col.name);
+            } else {
+                println!("Cards moved into {}:", col.name);
+                if let Some(user) = &col.default_assignee {
+                    println!("  assign @{user}");
+                }
+                for label in &col.auto_labels {
+                    println!("  label +{label}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
 
-    let title = card.title.clone();
-    board.cards.retain(|c| c.id != card_id);
+pub fn label_bulk(store: &Store, cmd: LabelCmd, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+    let known = |board: &Board, name: &str| {
+        board.label_def(name).is_some()
+            || board
+                .cards
+                .iter()
+                .any(|c| c.labels.iter().any(|l| l == name))
+    };
+
+    let (verb, from, into) = match cmd {
+        LabelCmd::Rename { old, new } => {
+            if known(&board, &new) {
+                return Err(KukError::Other(format!(
+                    "Label already exists: {new}. Use `kuk label merge {old} {new}` to combine them."
+                )));
+            }
+            ("renamed", old, new)
+        }
+        LabelCmd::Merge { from, into } => ("merged", from, into),
+    };
+    if !known(&board, &from) {
+        return Err(KukError::Other(format!("Label not found: {from}")));
+    }
+    if from == into {
+        return Err(KukError::Other(format!("Label is already named {into}")));
+    }
+
+    let cards = board.rename_label(&from, &into);
+    store.save_board(&board)?;
 
     if json_output {
         println!(
             "{}",
-            serde_json::json!({"deleted": card_id, "title": title})
+            serde_json::json!({verb: from, "to": into, "cards": cards})
         );
     } else {
-        println!("Deleted: {}", title);
+        let verb = if verb == "renamed" {
+            "Renamed"
+        } else {
+            "Merged"
+        };
+        println!("{verb} label: {from} → {into} ({cards} cards updated)");
     }
-
-    store.save_board(&board)?;
     Ok(())
 }
 
-pub fn label(
-    store: &Store,
-    id_or_num: &str,
-    action: &str,
-    tag: &str,
-    json_output: bool,
-) -> Result<()> {
+/// Resolve a 1-based position within the intake column, or a card ID or
+/// short ID of a card that is still in it.
+fn resolve_inbox_card(board: &Board, column: &str, id_or_num: &str) -> Result<String> {
+    let card = match id_or_num.parse::<usize>() {
+        Ok(num) => board.column_cards(column).get(num.wrapping_sub(1)).copied(),
+        Err(_) => board
+            .resolve_card_id(id_or_num)
+            .and_then(|id| board.find_card(&id))
+            .filter(|c| c.column == column && !c.archived),
+    };
+    card.map(|c| c.id.clone())
+        .ok_or_else(|| KukError::CardNotFound(format!("{id_or_num} (not in {column})")))
+}
+
+pub fn epic(store: &Store, cmd: EpicCmd, json_output: bool) -> Result<()> {
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
+    let resolve = |board: &Board, id_or_num: &str| {
+        board
+            .resolve_card_id(id_or_num)
+            .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))
+    };
 
-    let card_id = board
-        .resolve_card_id(id_or_num)
-        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+    match cmd {
+        EpicCmd::Create { title, to } => {
+            if !board.has_column(&to) {
+                return Err(KukError::ColumnNotFound(to));
+            }
+            let mut card = Card::new(title, &to);
+            card.order = board.next_order(&to);
+            card.epic = true;
+            let card = board.add_card(card).clone();
+            store.save_board(&board)?;
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&card)?);
+            } else {
+                let short_id = card.short_id().unwrap_or_default();
+                println!(
+                    "Created epic: {} → {} ({short_id})",
+                    card.title, card.column
+                );
+            }
+        }
+        EpicCmd::Add { epic, cards } => {
+            let epic_id = resolve(&board, &epic)?;
+            let ids = cards
+                .iter()
+                .map(|c| resolve(&board, c))
+                .collect::<Result<Vec<_>>>()?;
+            for id in &ids {
+                board
+                    .set_parent(id, Some(&epic_id))
+                    .map_err(KukError::Other)?;
+            }
+            store.save_board(&board)?;
+            let progress = board.epic_progress(&epic_id);
+            let epic = board.find_card(&epic_id).expect("resolved ids exist");
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"epic": epic_id, "cards": ids, "progress": progress})
+                );
+            } else {
+                println!(
+                    "Added {} card(s) to {} ({}/{} done)",
+                    ids.len(),
+                    epic.title,
+                    progress.done,
+                    progress.total
+                );
+            }
+        }
+        EpicCmd::Remove { cards } => {
+            let ids = cards
+                .iter()
+                .map(|c| resolve(&board, c))
+                .collect::<Result<Vec<_>>>()?;
+            let mut removed = Vec::new();
+            for id in &ids {
+                if board.set_parent(id, None).map_err(KukError::Other)? {
+                    removed.push(id.clone());
+                }
+            }
+            store.save_board(&board)?;
+            if json_output {
+                println!("{}", serde_json::json!({"removed": removed}));
+            } else {
+                println!("Removed {} card(s) from their epic", removed.len());
+            }
+        }
+    }
+    Ok(())
+}
 
-    let card = board
-        .find_card_mut(&card_id)
-        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
+pub fn column(store: &Store, cmd: ColumnCmd, json_output: bool) -> Result<()> {
+    let mut config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+    let existing = |board: &Board, name: &str| {
+        if board.has_column(name) {
+            Ok(())
+        } else {
+            Err(KukError::ColumnNotFound(name.into()))
+        }
+    };
 
-    match action {
-        "add" => {
-            if !card.labels.contains(&tag.to_string()) {
-                card.labels.push(tag.to_string());
+    match cmd {
+        ColumnCmd::Add { name, at, wip } => {
+            let mut column = Column::new(name.trim());
+            column.wip_limit = wip.filter(|&n| n > 0);
+            let index = at.map(|p| p.saturating_sub(1));
+            board
+                .add_column(column.clone(), index)
+                .map_err(KukError::Other)?;
+            store.save_board(&board)?;
+            let position = board
+                .columns
+                .iter()
+                .position(|c| c.name == column.name)
+                .unwrap_or_default()
+                + 1;
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"column": column, "position": position})
+                );
+            } else {
+                println!(
+                    "Added column: {} (position {position} of {})",
+                    column.name,
+                    board.columns.len()
+                );
             }
         }
-        "remove" => {
-            if !card.labels.contains(&tag.to_string()) {
-                return Err(KukError::LabelNotFound(tag.into()));
+        ColumnCmd::Rename { old, new } => {
+            existing(&board, &old)?;
+            let new = new.trim().to_string();
+            let updated = board.rename_column(&old, &new).map_err(KukError::Other)?;
+            store.save_board(&board)?;
+
+            // Keep config that names the column pointing at it.
+            let mut config_changed = false;
+            if config.inbox_column == old {
+                config.inbox_column = new.clone();
+                config_changed = true;
+            }
+            for rule in config.aging.iter_mut().filter(|r| r.column == old) {
+                rule.column = new.clone();
+                config_changed = true;
+            }
+            if config.new_cards.column.as_ref() == Some(&old) {
+                config.new_cards.column = Some(new.clone());
+                config_changed = true;
+            }
+            if config_changed {
+                store.save_config(&config)?;
+            }
+
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"renamed": old, "to": new, "cards": updated})
+                );
+            } else {
+                println!("Renamed column: {old} → {new} ({updated} cards updated)");
             }
-            card.labels.retain(|l| l != tag);
         }
-        _ => {
+        ColumnCmd::Move { name, position } => {
+            existing(&board, &name)?;
+            board
+                .move_column(&name, position.saturating_sub(1))
+                .map_err(KukError::Other)?;
+            store.save_board(&board)?;
+            let position = board
+                .columns
+                .iter()
+                .position(|c| c.name == name)
+                .unwrap_or_default()
+                + 1;
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"column": name, "position": position})
+                );
+            } else {
+                let order: Vec<&str> = board.columns.iter().map(|c| c.name.as_str()).collect();
+                println!(
+                    "Moved column: {name} to position {position} ({})",
+                    order.join(" → ")
+                );
+            }
+        }
+        ColumnCmd::Delete { name, to } => {
+            existing(&board, &name)?;
+            if let Some(to) = &to {
+                existing(&board, to)?;
+            }
+            let moved = board
+                .remove_column(&name, to.as_deref())
+                .map_err(KukError::Other)?;
+            store.save_board(&board)?;
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"deleted": name, "to": to, "cards": moved})
+                );
+            } else if let Some(to) = to.filter(|_| moved > 0) {
+                println!("Deleted column: {name} ({moved} cards moved to {to})");
+            } else {
+                println!("Deleted column: {name}");
+            }
+        }
+        ColumnCmd::SetWip { name, limit } => {
+            existing(&board, &name)?;
+            let limit = (limit > 0).then_some(limit);
+            board.set_wip_limit(&name, limit).map_err(KukError::Other)?;
+            store.save_board(&board)?;
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"column": name, "wip_limit": limit})
+                );
+            } else if let Some(limit) = limit {
+                println!("WIP limit for {name}: {limit}");
+            } else {
+                println!("Removed WIP limit from {name}");
+            }
+        }
+        ColumnCmd::Queue { name, mode } => {
+            existing(&board, &name)?;
+            let queue = mode == "on";
+            board.set_queue(&name, queue).map_err(KukError::Other)?;
+            store.save_board(&board)?;
+            if json_output {
+                println!("{}", serde_json::json!({"column": name, "queue": queue}));
+            } else if queue {
+                println!("{name} is now a queue");
+            } else {
+                println!("{name} is no longer a queue");
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn capture(
+    store: &Store,
+    title: Vec<String>,
+    board: Option<String>,
+    daemon: bool,
+    socket: Option<PathBuf>,
+    json_output: bool,
+) -> Result<()> {
+    let socket = socket
+        .or_else(capture::default_socket)
+        .ok_or_else(|| KukError::Other("Cannot find the home directory for the socket".into()))?;
+    if daemon {
+        println!(
+            "Capturing into {} via {}",
+            store.repo_root().display(),
+            socket.display()
+        );
+        return capture::serve(store, &socket);
+    }
+
+    let titles = if title.is_empty() {
+        let mut titles = Vec::new();
+        for line in std::io::stdin().lock().lines() {
+            let line = line.map_err(|e| KukError::Other(format!("Cannot read stdin: {e}")))?;
+            if !line.trim().is_empty() {
+                titles.push(line);
+            }
+        }
+        titles
+    } else {
+        vec![title.join(" ")]
+    };
+    let items: Vec<Capture> = titles
+        .into_iter()
+        .map(|title| Capture {
+            title,
+            board: board.clone(),
+        })
+        .collect();
+    let results = match capture::send(&socket, &items)? {
+        Some(results) => results,
+        // No daemon: file into the repo we're in
+        None if store.is_initialized() => items
+            .iter()
+            .map(|item| capture::capture(store, item))
+            .collect(),
+        None => {
             return Err(KukError::Other(format!(
-                "Invalid label action: {action}. Use 'add' or 'remove'."
+                "No capture daemon on {} and no kuk board here. Start one in a repo with `kuk capture --daemon`.",
+                socket.display()
             )));
         }
+    };
+
+    let mut captured = Vec::new();
+    for result in results {
+        let card = result?;
+        if !json_output {
+            let id = card
+                .short_id
+                .as_deref()
+                .map(|s| format!(" ({s})"))
+                .unwrap_or_default();
+            println!(
+                "Captured: {} → {}/{}{id}",
+                card.title, card.board, card.column
+            );
+        }
+        captured.push(card);
+    }
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&captured)?);
     }
+    Ok(())
+}
 
-    card.updated_at = Utc::now();
+pub fn resolve(
+    store: &Store,
+    install: bool,
+    driver: Option<Vec<PathBuf>>,
+    json_output: bool,
+) -> Result<()> {
+    let root = store.repo_root();
+    if let Some(paths) = driver {
+        let conflicts = resolve::run_driver(&paths[0], &paths[1], &paths[2])?;
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+        // git leaves the file conflicted when the driver fails
+        return Err(KukError::Other(format!(
+            "Kept ours where both sides changed:\n  {}",
+            conflicts.join("\n  ")
+        )));
+    }
+    if install {
+        let added = resolve::install(root)?;
+        if json_output {
+            println!(
+                "{}",
+                serde_json::json!({"installed": true, "gitattributes": added})
+            );
+        } else {
+            println!("Installed the kuk merge driver for board files");
+            if added {
+                println!("Added to .gitattributes: {}", resolve::ATTRIBUTES);
+            }
+        }
+        return Ok(());
+    }
 
+    let resolutions = resolve::resolve_all(root)?;
     if json_output {
-        println!("{}", serde_json::to_string_pretty(card)?);
-    } else {
-        println!("Labels on {}: [{}]", card.title, card.labels.join(", "));
+        println!("{}", serde_json::to_string_pretty(&resolutions)?);
+    } else if resolutions.is_empty() {
+        println!("No conflicted board files");
+    }
+    let mut unresolved = 0;
+    for resolution in &resolutions {
+        if resolution.conflicts.is_empty() {
+            if !json_output {
+                println!("Resolved {}", resolution.path.display());
+            }
+            continue;
+        }
+        unresolved += 1;
+        if !json_output {
+            println!("Merged {}, check:", resolution.path.display());
+            for conflict in &resolution.conflicts {
+                println!("  {conflict}");
+            }
+        }
+    }
+    if unresolved > 0 {
+        return Err(KukError::Other(format!(
+            "{unresolved} file(s) need a look; `git add` them once they're right"
+        )));
     }
-
-    store.save_board(&board)?;
     Ok(())
 }
 
-pub fn assign(store: &Store, id_or_num: &str, user: &str, json_output: bool) -> Result<()> {
+pub fn inbox(store: &Store, cmd: Option<InboxCmd>, json_output: bool) -> Result<()> {
     let config = store.load_config()?;
     let mut board = store.load_board(&config.default_board)?;
-
-    let card_id = board
-        .resolve_card_id(id_or_num)
-        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
-
-    let card = board
-        .find_card_mut(&card_id)
-        .ok_or_else(|| KukError::CardNotFound(id_or_num.into()))?;
-
-    card.assignee = Some(user.into());
-    card.updated_at = Utc::now();
-
-    if json_output {
-        println!("{}", serde_json::to_string_pretty(card)?);
-    } else {
-        println!("Assigned {} to @{}", card.title, user);
+    let column = config.inbox_column.as_str();
+
+    match cmd.unwrap_or(InboxCmd::List) {
+        InboxCmd::Add {
+            title,
+            body,
+            source,
+            from,
+            url,
+        } => {
+            let mut source = InboxSource::new(source);
+            source.sender = from;
+            source.url = url;
+            let card = board.receive(column, &title, body, source).clone();
+            store.save_board(&board)?;
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&card)?);
+            } else {
+                println!("Received: {} → {column}", card.title);
+            }
+        }
+        InboxCmd::List => {
+            let items = board.column_cards(column);
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&items)?);
+                return Ok(());
+            }
+            if items.is_empty() {
+                println!("Inbox is empty.");
+                return Ok(());
+            }
+            println!("Inbox ({})", items.len());
+            for (i, card) in items.iter().enumerate() {
+                let mut line = format!("  {}. {}", i + 1, card.title);
+                if let Some(src) = InboxSource::of(card) {
+                    let _ = write!(line, "  [{}", src.source);
+                    if let Some(sender) = &src.sender {
+                        let _ = write!(line, " from {sender}");
+                    }
+                    let _ = write!(line, ", {}]", src.received_at.format("%Y-%m-%d"));
+                }
+                println!("{line}");
+            }
+            println!(
+                "\nPromote with `kuk inbox promote <n> --to <column>`, or `kuk inbox discard <n>`."
+            );
+        }
+        InboxCmd::Promote {
+            id,
+            to,
+            label,
+            assignee,
+        } => {
+            if to == column || !board.has_column(&to) {
+                return Err(KukError::ColumnNotFound(to));
+            }
+            board
+                .check_labels(&label)
+                .map_err(|l| KukError::UnknownLabel(l.into()))?;
+            let card_id = resolve_inbox_card(&board, column, &id)?;
+            board.move_card(&card_id, &to);
+            let card = board.find_card_mut(&card_id).unwrap();
+            for l in label {
+                card.add_label(&l);
+            }
+            if assignee.is_some() {
+                card.set_assignee(assignee);
+            }
+            let card = card.clone();
+            store.save_board(&board)?;
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&card)?);
+            } else {
+                println!("Promoted: {} → {to}", card.title);
+            }
+        }
+        InboxCmd::Discard { id } => {
+            let card_id = resolve_inbox_card(&board, column, &id)?;
+            let card = board.find_card_mut(&card_id).unwrap();
+            card.archive();
+            let card = card.clone();
+            store.save_board(&board)?;
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&card)?);
+            } else {
+                println!("Discarded: {}", card.title);
+            }
+        }
     }
-
-    store.save_board(&board)?;
     Ok(())
 }
 
-pub fn board(store: &Store, cmd: BoardCmd, json_output: bool) -> Result<()> {
+pub fn label_def(store: &Store, cmd: LabelDefCmd, json_output: bool) -> Result<()> {
+    let config = store.load_config()?;
+    let mut board = store.load_board(&config.default_board)?;
+
     match cmd {
-        BoardCmd::Create { name } => {
-            store.create_board(
-                &name,
-                vec![
-                    Column {
-                        name: "todo".into(),
-                        wip_limit: None,
-                    },
-                    Column {
-                        name: "doing".into(),
-                        wip_limit: None,
-                    },
-                    Column {
-                        name: "done".into(),
-                        wip_limit: None,
-                    },
-                ],
-            )?;
+        LabelDefCmd::Add {
+            name,
+            color,
+            description,
+        } => {
+            let color = color
+                .map(|c| parse_color(&c))
+                .transpose()
+                .map_err(KukError::Other)?;
+            let def = match board.labels.iter_mut().find(|l| l.name == name) {
+                Some(def) => def,
+                None => {
+                    board.labels.push(LabelDef::new(&name));
+                    board.labels.last_mut().unwrap()
+                }
+            };
+            if color.is_some() {
+                def.color = color;
+            }
+            if description.is_some() {
+                def.description = description;
+            }
+            let def = def.clone();
+            store.save_board(&board)?;
             if json_output {
-                println!("{}", serde_json::json!({"created": name}));
+                println!("{}", serde_json::to_string_pretty(&def)?);
             } else {
-                println!("Created board: {}", name);
+                println!(
+                    "Defined label: {}",
+                    paint_label(&def.name, Some(&def), std::io::stdout().is_terminal())
+                );
             }
         }
-        BoardCmd::Switch { name } => {
-            // Verify board exists
-            store.load_board(&name)?;
-            let mut config = store.load_config()?;
-            config.default_board = name.clone();
-            store.save_config(&config)?;
+        LabelDefCmd::List => {
+            let usage = |name: &str| {
+                board
+                    .cards
+                    .iter()
+                    .filter(|c| !c.archived && c.labels.iter().any(|l| l == name))
+                    .count()
+            };
             if json_output {
-                println!("{}", serde_json::json!({"active": name}));
+                let defs: Vec<_> = board
+                    .labels
+                    .iter()
+                    .map(|d| {
+                        let mut v = serde_json::to_value(d).unwrap();
+                        v["cards"] = usage(&d.name).into();
+                        v
+                    })
+                    .collect();
+                println!(
+                    "{}",
+                    serde_json::json!({"strict": board.strict_labels, "labels": defs})
+                );
+            } else if board.labels.is_empty() {
+                println!("No labels defined. Add one with `kuk label-def add <name>`.");
             } else {
-                println!("Switched to board: {}", name);
+                let color = std::io::stdout().is_terminal();
+                for def in &board.labels {
+                    let desc = def
+                        .description
+                        .as_ref()
+                        .map(|d| format!("  {d}"))
+                        .unwrap_or_default();
+                    println!(
+                        "  {}  {}{}  ({} cards)",
+                        paint_label(&def.name, Some(def), color),
+                        def.color.as_deref().unwrap_or("-"),
+                        desc,
+                        usage(&def.name)
+                    );
+                }
+                if board.strict_labels {
+                    println!("\nStrict: only these labels are allowed on cards.");
+                }
             }
         }
-        BoardCmd::List => {
-            let config = store.load_config()?;
-            let boards = store.list_boards()?;
+        LabelDefCmd::Rename { old, new } => {
+            if board.label_def(&old).is_none() {
+                return Err(KukError::Other(format!("Label not defined: {old}")));
+            }
+            if board.label_def(&new).is_some() {
+                return Err(KukError::Other(format!("Label already defined: {new}")));
+            }
+            let cards = board.rename_label(&old, &new);
+            store.save_board(&board)?;
             if json_output {
-                println!("{}", serde_json::to_string_pretty(&boards)?);
+                println!(
+                    "{}",
+                    serde_json::json!({"renamed": old, "to": new, "cards": cards})
+                );
             } else {
-                for b in &boards {
-                    if *b == config.default_board {
-                        println!("* {}", b);
-                    } else {
-                        println!("  {}", b);
-                    }
-                }
+                println!("Renamed label: {old} → {new} ({cards} cards updated)");
+            }
+        }
+        LabelDefCmd::Delete { name } => {
+            if board.label_def(&name).is_none() {
+                return Err(KukError::Other(format!("Label not defined: {name}")));
+            }
+            board.labels.retain(|l| l.name != name);
+            store.save_board(&board)?;
+            if json_output {
+                println!("{}", serde_json::json!({"deleted": name}));
+            } else {
+                println!("Deleted label definition: {name}");
+            }
+        }
+        LabelDefCmd::Strict { mode } => {
+            board.strict_labels = mode == "on";
+            store.save_board(&board)?;
+            if json_output {
+                println!("{}", serde_json::json!({"strict": board.strict_labels}));
+            } else {
+                println!("Strict labels: {mode}");
             }
         }
     }
     Ok(())
 }
 
+/// A label name, drawn in its registry color when `color` is set.
+/// ` due 2026-03-01`, flagged `!` and red when overdue and yellow when
+/// due soon.
+fn due_marker(board: &Board, card: &Card, color: bool) -> String {
+    let (Some(status), Some(due)) = (board.due_status(card, Utc::now()), card.due) else {
+        return String::new();
+    };
+    let date = due.format("%Y-%m-%d").to_string();
+    let marker = match status {
+        DueStatus::Overdue => format!("!due {date}"),
+        _ => format!("due {date}"),
+    };
+    let marker = match status {
+        DueStatus::Overdue if color => marker.red().bold().to_string(),
+        DueStatus::Soon if color => marker.yellow().to_string(),
+        _ => marker,
+    };
+    format!(" {marker}")
+}
+
+/// A stretch of time in its largest whole unit: `3d`, `5h` or `12m`.
+fn span(t: TimeDelta) -> String {
+    if t.num_days() > 0 {
+        format!("{}d", t.num_days())
+    } else if t.num_hours() > 0 {
+        format!("{}h", t.num_hours())
+    } else {
+        format!("{}m", t.num_minutes())
+    }
+}
+
+fn priority_marker(card: &Card, color: bool) -> String {
+    let Some(priority) = card.priority else {
+        return String::new();
+    };
+    let marker = format!("({priority})");
+    let marker = match priority {
+        Priority::Urgent if color => marker.red().bold().to_string(),
+        Priority::High if color => marker.yellow().to_string(),
+        _ => marker,
+    };
+    format!(" {marker}")
+}
+
+fn paint_label(name: &str, def: Option<&LabelDef>, color: bool) -> String {
+    match def.and_then(LabelDef::rgb) {
+        Some((r, g, b)) if color => name.truecolor(r, g, b).bold().to_string(),
+        _ => name.to_string(),
+    }
+}
+
 pub fn projects(json_output: bool) -> Result<()> {
     let index = Store::load_global_index().unwrap_or_default();
 
@@ -530,7 +3775,7 @@ pub fn projects(json_output: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn doctor(store: &Store) -> Result<()> {
+pub fn doctor(store: &Store, fix: bool) -> Result<()> {
     println!("kuk doctor");
     println!("──────────");
 
@@ -549,15 +3794,27 @@ pub fn doctor(store: &Store) -> Result<()> {
     }
 
     // Check boards
+    let mut needs_fix = false;
     match store.list_boards() {
         Ok(boards) => {
             println!("  [OK] {} board(s): {}", boards.len(), boards.join(", "));
             for b in &boards {
                 match store.load_board(b) {
-                    Ok(board) => {
+                    Ok(mut board) => {
                         let active = board.cards.iter().filter(|c| !c.archived).count();
                         let archived = board.cards.iter().filter(|c| c.archived).count();
                         println!("       └─ {}: {} active, {} archived", b, active, archived);
+
+                        let drifted = board.normalize_orders();
+                        if drifted > 0 && fix {
+                            store.save_board(&board)?;
+                            println!("          [OK] renumbered {drifted} card(s)");
+                        } else if drifted > 0 {
+                            println!(
+                                "          [!!] {drifted} card(s) with duplicate or gapped order"
+                            );
+                            needs_fix = true;
+                        }
                     }
                     Err(e) => println!("       └─ {}: ERROR: {}", b, e),
                 }
@@ -572,15 +3829,109 @@ pub fn doctor(store: &Store) -> Result<()> {
         None => println!("  [--] global index: not found (optional)"),
     }
 
-    println!("\nAll checks passed.");
+    if needs_fix {
+        println!("\nCard order drift found. Run `kuk doctor --fix` to repair.");
+    } else {
+        println!("\nAll checks passed.");
+    }
+    Ok(())
+}
+
+pub fn dev(store: &Store, cmd: DevCmd, json_output: bool) -> Result<()> {
+    match cmd {
+        DevCmd::Generate {
+            cards,
+            boards,
+            seed,
+        } => {
+            let names = crate::dev::generate(store, boards, cards, seed)?;
+            if json_output {
+                println!("{}", serde_json::json!({"boards": names, "cards": cards}));
+            } else {
+                println!("Generated {} cards across {}", cards, names.join(", "));
+            }
+        }
+    }
     Ok(())
 }
 
+pub fn demo(dir: Option<PathBuf>, no_tui: bool, json_output: bool) -> Result<()> {
+    let keep = dir.is_some() || no_tui;
+    let root = dir
+        .unwrap_or_else(|| std::env::temp_dir().join(format!("kuk-demo-{}", std::process::id())));
+    crate::demo::write_demo(&root)?;
+
+    if no_tui {
+        if json_output {
+            println!("{}", serde_json::json!({"path": root}));
+        } else {
+            println!("Demo project written to {}", root.display());
+            println!("Run `kuk --repo {} tui` to open it.", root.display());
+        }
+        return Ok(());
+    }
+
+    let result = crate::tui::run_tui(&root);
+    if keep {
+        println!("Demo project kept at {}", root.display());
+    } else {
+        let _ = std::fs::remove_dir_all(&root);
+    }
+    result
+}
+
 pub fn version() -> Result<()> {
     println!("kuk {}", env!("CARGO_PKG_VERSION"));
     Ok(())
 }
 
+#[cfg(feature = "self-update")]
+pub fn self_update(check_only: bool, json_output: bool) -> Result<()> {
+    let check = crate::self_update::check()?;
+    let available = check.update_available();
+    if check_only || !available {
+        if json_output {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "current": check.current.to_string(),
+                    "latest": check.latest.to_string(),
+                    "update_available": available,
+                })
+            );
+        } else if available {
+            println!(
+                "kuk {} is available (installed: {}). Run `kuk self-update` to install it.",
+                check.latest, check.current
+            );
+        } else {
+            println!("kuk {} is up to date.", check.current);
+        }
+        return Ok(());
+    }
+
+    let path = crate::self_update::install(&check.release)?;
+    if json_output {
+        println!(
+            "{}",
+            serde_json::json!({
+                "updated": true,
+                "from": check.current.to_string(),
+                "to": check.latest.to_string(),
+                "path": path,
+            })
+        );
+    } else {
+        println!(
+            "Updated kuk {} -> {} at {}",
+            check.current,
+            check.latest,
+            path.display()
+        );
+    }
+    Ok(())
+}
+
 pub fn default_action() -> Result<()> {
     println!("kuk — Kanban that ships with your code.");
     println!();
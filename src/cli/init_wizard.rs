@@ -0,0 +1,233 @@
+//! Prompts behind `kuk init --interactive`.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+
+use crate::error::{KukError, Result};
+use crate::model::{Board, ColorScheme, Column, RepoConfig};
+
+/// Everything the wizard asked for, applied in one go once all questions are
+/// answered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitAnswers {
+    pub config: RepoConfig,
+    pub board: Board,
+    /// Commit `.kuk/` with the repo; otherwise it is added to `.gitignore`.
+    pub track_in_git: bool,
+    pub pm_init: bool,
+}
+
+/// Ask each question on `out`, reading answers from `input`. Blank answers
+/// and end of input take the default shown in brackets.
+pub fn ask(input: &mut impl BufRead, out: &mut impl Write) -> Result<InitAnswers> {
+    let mut p = Prompter { input, out };
+
+    let board_name = p.ask_with("Board name", "default", |s| {
+        if s.contains(['/', '\\']) || s.starts_with('.') {
+            Err("Board names can't contain slashes or start with '.'".into())
+        } else {
+            Ok(s.to_string())
+        }
+    })?;
+    let names = p.ask_with("Columns (comma-separated)", "todo, doing, done", |s| {
+        let mut names: Vec<String> = Vec::new();
+        for name in s.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.into());
+            }
+        }
+        if names.is_empty() {
+            Err("Enter at least one column".into())
+        } else {
+            Ok(names)
+        }
+    })?;
+    let mut columns = Vec::with_capacity(names.len());
+    for name in names {
+        let wip_limit = p.ask_with(&format!("WIP limit for {name}"), "none", |s| match s {
+            "none" | "0" => Ok(None),
+            _ => s
+                .parse::<u32>()
+                .map(Some)
+                .map_err(|_| "Enter a number, or leave blank for no limit".into()),
+        })?;
+        columns.push(Column {
+            wip_limit,
+            ..Column::new(name)
+        });
+    }
+    let track_in_git = p.confirm("Track .kuk/ in git?", true)?;
+    let auto_commit =
+        track_in_git && p.confirm("Commit .kuk/ to git after every change?", false)?;
+    let color_scheme = p.ask_with("TUI color scheme (default, light, mono)", "default", |s| {
+        ColorScheme::from_str(s)
+    })?;
+    let pm_init = p.confirm("Also run `kuk-pm init`?", false)?;
+
+    let board = Board {
+        name: board_name.clone(),
+        columns,
+        ..Board::default_board()
+    };
+    let config = RepoConfig {
+        default_board: board_name,
+        color_scheme,
+        auto_commit,
+        ..RepoConfig::default()
+    };
+    Ok(InitAnswers {
+        config,
+        board,
+        track_in_git,
+        pm_init,
+    })
+}
+
+struct Prompter<'a, R, W> {
+    input: &'a mut R,
+    out: &'a mut W,
+}
+
+impl<R: BufRead, W: Write> Prompter<'_, R, W> {
+    /// Ask until `parse` accepts the answer.
+    fn ask_with<T>(
+        &mut self,
+        question: &str,
+        default: &str,
+        parse: impl Fn(&str) -> std::result::Result<T, String>,
+    ) -> Result<T> {
+        loop {
+            write!(self.out, "{question} [{default}]: ")?;
+            self.out.flush()?;
+            let mut line = String::new();
+            let eof = self.input.read_line(&mut line)? == 0;
+            let answer = match line.trim() {
+                "" => default,
+                answer => answer,
+            };
+            match parse(answer) {
+                Ok(value) => return Ok(value),
+                // Don't spin on a bad default at end of input
+                Err(e) if eof => return Err(KukError::Other(e)),
+                Err(e) => writeln!(self.out, "  {e}")?,
+            }
+        }
+    }
+
+    fn confirm(&mut self, question: &str, default: bool) -> Result<bool> {
+        let default = if default { "Y/n" } else { "y/N" };
+        self.ask_with(question, default, |s| match s {
+            "Y/n" => Ok(true),
+            "y/N" => Ok(false),
+            _ => match s.to_lowercase().as_str() {
+                "y" | "yes" => Ok(true),
+                "n" | "no" => Ok(false),
+                _ => Err("Answer y or n".into()),
+            },
+        })
+    }
+}
+
+/// Add `.kuk/` to the repo's `.gitignore` unless it is already listed.
+/// Returns whether the file changed.
+pub fn ignore_kuk_dir(repo_root: &Path) -> Result<bool> {
+    let path = repo_root.join(".gitignore");
+    let existing = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(KukError::read(&path)(e)),
+    };
+    if existing
+        .lines()
+        .any(|l| matches!(l.trim(), ".kuk" | ".kuk/" | "/.kuk" | "/.kuk/"))
+    {
+        return Ok(false);
+    }
+    let sep = if existing.is_empty() || existing.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(&path, format!("{existing}{sep}.kuk/\n")).map_err(KukError::write(&path))?;
+    Ok(true)
+}
+
+/// Run `kuk-pm init` for the repo, preferring the binary installed next to
+/// this one. Failures are reported but don't undo the kuk setup.
+pub fn run_pm_init(repo_root: &Path) {
+    let sibling = std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join("kuk-pm")))
+        .filter(|path| path.exists());
+    let program = sibling.unwrap_or_else(|| "kuk-pm".into());
+    match Command::new(&program)
+        .arg("--repo")
+        .arg(repo_root)
+        .arg("init")
+        .status()
+    {
+        Ok(status) if status.success() => {}
+        Ok(_) => eprintln!("Warning: `kuk-pm init` failed; run it again once fixed."),
+        Err(_) => eprintln!("Warning: kuk-pm not found; run `kuk-pm init` after installing it."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn answer(lines: &str) -> (InitAnswers, String) {
+        let mut out = Vec::new();
+        let answers = ask(&mut lines.as_bytes(), &mut out).unwrap();
+        (answers, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn blank_input_takes_defaults() {
+        let (answers, out) = answer("");
+        assert_eq!(answers.board.columns, Board::default_board().columns);
+        assert_eq!(answers.config, RepoConfig::default());
+        assert!(answers.track_in_git);
+        assert!(!answers.pm_init);
+        assert!(out.contains("Board name [default]: "));
+    }
+
+    #[test]
+    fn reasks_until_answers_parse() {
+        let (answers, out) =
+            answer("work\nbacklog, doing, doing, done\n\nlots\n3\n\nn\npink\nmono\ny\n");
+        assert_eq!(answers.board.name, "work");
+        assert_eq!(answers.config.default_board, "work");
+        let limits: Vec<_> = answers
+            .board
+            .columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.wip_limit))
+            .collect();
+        assert_eq!(
+            limits,
+            vec![("backlog", None), ("doing", Some(3)), ("done", None)]
+        );
+        assert!(!answers.track_in_git);
+        assert_eq!(answers.config.color_scheme, ColorScheme::Mono);
+        assert!(answers.pm_init);
+        assert!(out.contains("Enter a number"));
+        assert!(out.contains("Invalid color scheme: pink"));
+    }
+
+    #[test]
+    fn ignore_kuk_dir_appends_once() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".gitignore"), "target").unwrap();
+        assert!(ignore_kuk_dir(dir.path()).unwrap());
+        assert!(!ignore_kuk_dir(dir.path()).unwrap());
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".gitignore")).unwrap(),
+            "target\n.kuk/\n"
+        );
+    }
+}
@@ -1,46 +1,316 @@
+mod auto_commit;
 mod commands;
+mod init_wizard;
+mod usage_log;
 
 pub use commands::BoardCmd;
 pub use commands::Cli;
+pub use commands::ColumnCmd;
 pub use commands::Commands;
+pub use commands::DevCmd;
+pub use commands::EpicCmd;
+pub use commands::InboxCmd;
+pub use commands::LabelCmd;
+pub use commands::LabelDefCmd;
+pub use commands::render_list;
 
 use crate::error::Result;
+use crate::model::{Origin, Via};
 use crate::storage::Store;
 
 pub fn run(cli: Cli) -> Result<()> {
     let repo = cli.repo.unwrap_or_else(|| std::env::current_dir().unwrap());
-    let store = Store::new(&repo);
+    let store = Store::new(&repo).with_origin(Origin::new(Via::Cli));
+    // Servers save for many requests; only one-shot commands are undoable
+    // and committed, and read the config just once
+    let command = match cli.command {
+        Some(Commands::Mcp | Commands::Rpc | Commands::Capture { daemon: true, .. }) => None,
+        _ => Some(command_line(std::env::args().skip(1))),
+    };
+    let store = match &command {
+        Some(command) => store.with_oplog(command).with_config_cache(),
+        None => store,
+    };
     let json_output = cli.json;
+    let hooks = runs_hooks(&cli.command);
+    let started = std::time::Instant::now();
+    // Another command's save can't land between this one's loads and saves
+    let lock = match holds_lock(&cli.command) && store.is_initialized() {
+        true => store.lock()?,
+        false => None,
+    };
 
-    match cli.command {
-        Some(Commands::Init { board_name }) => commands::init(&store, &board_name),
-        Some(Commands::List { board }) => commands::list(&store, board.as_deref(), json_output),
+    let result = match cli.command {
+        Some(Commands::Init {
+            board_name,
+            interactive,
+            template,
+        }) => commands::init(&store, &board_name, interactive, template.as_deref()),
+        Some(Commands::List {
+            board,
+            priority,
+            sort,
+            epics,
+            lanes,
+        }) => commands::list(
+            &store,
+            board.as_deref(),
+            priority,
+            sort,
+            epics,
+            lanes,
+            json_output,
+        ),
         Some(Commands::Add {
             title,
+            stdin,
             to,
             label,
             assignee,
-        }) => commands::add(&store, &title, &to, label, assignee, json_output),
-        Some(Commands::Move { id, to }) => commands::move_card(&store, &id, &to, json_output),
+            no_defaults,
+            force,
+            template,
+            vars,
+            due,
+            priority,
+        }) => {
+            let opts = commands::AddOptions {
+                labels: label,
+                assignee,
+                no_defaults,
+                force,
+                template,
+                vars,
+                due,
+                priority,
+            };
+            match title {
+                Some(title) if !stdin => {
+                    commands::add(&store, &title, to.as_deref(), opts, json_output)
+                }
+                _ => commands::add_lines(
+                    &store,
+                    std::io::stdin().lock(),
+                    to.as_deref(),
+                    opts,
+                    json_output,
+                ),
+            }
+        }
+        Some(Commands::Move { id, to, force }) => {
+            commands::move_card(&store, &id, &to, force, json_output)
+        }
+        Some(Commands::Clone {
+            id,
+            to,
+            board,
+            force,
+        }) => commands::clone_card(
+            &store,
+            &id,
+            to.as_deref(),
+            board.as_deref(),
+            force,
+            json_output,
+        ),
         Some(Commands::Hoist { id }) => commands::hoist(&store, &id, json_output),
         Some(Commands::Demote { id }) => commands::demote(&store, &id, json_output),
+        Some(Commands::Due { id, date, clear: _ }) => {
+            commands::due(&store, &id, date.as_deref(), json_output)
+        }
+        Some(Commands::Priority {
+            id,
+            level,
+            clear: _,
+        }) => commands::priority(&store, &id, level, json_output),
+        Some(Commands::Snooze {
+            id,
+            until,
+            hoist,
+            wake: _,
+        }) => commands::snooze(&store, &id, until.as_deref(), hoist, json_output),
         Some(Commands::Archive { id }) => commands::archive(&store, &id, json_output),
+        Some(Commands::Archived { board }) => {
+            commands::archived(&store, board.as_deref(), json_output)
+        }
+        Some(Commands::Unarchive { id, force }) => {
+            commands::unarchive(&store, &id, force, json_output)
+        }
         Some(Commands::Delete { id }) => commands::delete(&store, &id, json_output),
-        Some(Commands::Label { id, action, tag }) => {
-            commands::label(&store, &id, &action, &tag, json_output)
+        Some(Commands::Undo) => commands::undo(&store, false, json_output),
+        Some(Commands::Redo) => commands::undo(&store, true, json_output),
+        Some(Commands::Label {
+            command: Some(command),
+            ..
+        }) => commands::label_bulk(&store, command, json_output),
+        Some(Commands::Label {
+            id, action, tag, ..
+        }) => commands::label(
+            &store,
+            &id.unwrap_or_default(),
+            &action.unwrap_or_default(),
+            &tag.unwrap_or_default(),
+            json_output,
+        ),
+        Some(Commands::LabelDef { command }) => commands::label_def(&store, command, json_output),
+        Some(Commands::Inbox { command }) => commands::inbox(&store, command, json_output),
+        Some(Commands::Capture {
+            title,
+            board,
+            daemon,
+            socket,
+        }) => commands::capture(&store, title, board, daemon, socket, json_output),
+        Some(Commands::Resolve { install, driver }) => {
+            commands::resolve(&store, install, driver, json_output)
         }
+        Some(Commands::Epic { command }) => commands::epic(&store, command, json_output),
+        Some(Commands::Column { command }) => commands::column(&store, command, json_output),
+        Some(Commands::Search { query, archived }) => {
+            commands::search(&store, &query, archived, json_output)
+        }
+        Some(Commands::Show { id }) => commands::show(&store, &id, json_output),
+        Some(Commands::History { id }) => commands::history(&store, &id, json_output),
+        Some(Commands::Export {
+            command: Some(commands::ExportCmd::Card { id, format }),
+            ..
+        }) => commands::export_card(&store, &id, format, json_output),
+        Some(Commands::Export {
+            command: None,
+            format,
+            board,
+        }) => commands::export_board(&store, board.as_deref(), format, json_output),
+        Some(Commands::Import {
+            command: commands::ImportCmd::Markdown { file, to, force },
+        }) => commands::import_markdown(&store, &file, to.as_deref(), force, json_output),
+        Some(Commands::Log {
+            origin,
+            board,
+            last,
+        }) => commands::log(&store, board.as_deref(), origin, last, json_output),
         Some(Commands::Assign { id, user }) => commands::assign(&store, &id, &user, json_output),
+        Some(Commands::Edit {
+            id,
+            title,
+            description,
+            lane,
+        }) => commands::edit(&store, &id, title, description, lane, json_output),
+        Some(Commands::Comment { id, body, author }) => {
+            commands::comment(&store, &id, &body, author, json_output)
+        }
+        Some(Commands::Attach {
+            id,
+            target,
+            title,
+            remove,
+        }) => commands::attach(&store, &id, &target, title, remove, json_output),
+        Some(Commands::Scan { paths, to, apply }) => {
+            commands::scan(&store, &paths, &to, apply, json_output)
+        }
         Some(Commands::Board { command }) => commands::board(&store, command, json_output),
         Some(Commands::Projects) => commands::projects(json_output),
         Some(Commands::Tui) => crate::tui::run_tui(&repo),
+        Some(Commands::Demo { dir, no_tui }) => commands::demo(dir, no_tui, json_output),
         Some(Commands::Serve { port, mcp }) => {
             let rt = tokio::runtime::Runtime::new()
                 .map_err(|e| crate::error::KukError::Other(format!("Runtime error: {e}")))?;
             rt.block_on(crate::server::serve(repo, port, mcp))
         }
         Some(Commands::Mcp) => crate::mcp_stdio::run(&store),
-        Some(Commands::Doctor) => commands::doctor(&store),
+        Some(Commands::Rpc) => crate::rpc::run(&store),
+        Some(Commands::Stale { days, escalate }) => {
+            commands::stale(&store, days, escalate, json_output)
+        }
+        Some(Commands::Doctor { fix }) => commands::doctor(&store, fix),
+        Some(Commands::Usage { action }) => commands::usage(&store, action.as_deref(), json_output),
         Some(Commands::Version) => commands::version(),
+        #[cfg(feature = "self-update")]
+        Some(Commands::SelfUpdate { check }) => commands::self_update(check, json_output),
+        Some(Commands::Dev { command }) => commands::dev(&store, command, json_output),
         None => commands::default_action(),
+    };
+    drop(lock);
+    // The hooks reuse the config the command read, if it read one
+    if hooks
+        && store.is_initialized()
+        && let Ok(config) = store.load_config()
+    {
+        if let (Ok(()), Some(command)) = (&result, &command) {
+            auto_commit::commit(&store, &config, command);
+        }
+        usage_log::record(
+            &store,
+            &config,
+            std::env::args().skip(1),
+            started.elapsed(),
+            result.is_ok(),
+        );
+    }
+    result
+}
+
+/// Whether the auto-commit and usage hooks run after `command`. Version,
+/// self-update and the bare intro read no project files, and shouldn't
+/// start to for the hooks' sake.
+fn runs_hooks(command: &Option<Commands>) -> bool {
+    match command {
+        None | Some(Commands::Version) => false,
+        #[cfg(feature = "self-update")]
+        Some(Commands::SelfUpdate { .. }) => false,
+        _ => true,
+    }
+}
+
+/// Whether `run` holds the store lock for all of `command`. Servers, the
+/// TUI and the capture daemon save while running for as long as they like,
+/// so they lock each save themselves, as do commands that wait on the user:
+/// `add --stdin`, `edit` in `$EDITOR` and an unconfirmed `board delete`.
+fn holds_lock(command: &Option<Commands>) -> bool {
+    match command {
+        None
+        | Some(
+            Commands::Init { .. }
+            | Commands::Version
+            | Commands::Projects
+            | Commands::Tui
+            | Commands::Demo { .. }
+            | Commands::Serve { .. }
+            | Commands::Mcp
+            | Commands::Rpc
+            | Commands::Capture { .. },
+        ) => false,
+        #[cfg(feature = "self-update")]
+        Some(Commands::SelfUpdate { .. }) => false,
+        Some(Commands::Add { title, stdin, .. }) => title.is_some() && !stdin,
+        Some(Commands::Edit {
+            title,
+            description,
+            lane,
+            ..
+        }) => title.is_some() || description.is_some() || lane.is_some(),
+        Some(Commands::Board {
+            command: commands::BoardCmd::Delete { yes, .. },
+        }) => *yes,
+        _ => true,
+    }
+}
+
+/// The command as typed, for the operation log: `kuk` and its arguments,
+/// without the flags that only say where and how to print.
+fn command_line(mut args: impl Iterator<Item = String>) -> String {
+    let mut words = vec!["kuk".to_string()];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--repo" => {
+                args.next();
+            }
+            "--json" | "--quiet" | "--verbose" => {}
+            _ if arg.starts_with("--repo=") => {}
+            _ if arg.starts_with("-v") && arg[1..].chars().all(|c| c == 'v') => {}
+            _ if arg.is_empty() || arg.contains(char::is_whitespace) => {
+                words.push(format!("\"{arg}\""))
+            }
+            _ => words.push(arg),
+        }
     }
+    words.join(" ")
 }
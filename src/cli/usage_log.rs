@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use clap::CommandFactory;
+
+use super::Cli;
+use crate::model::RepoConfig;
+use crate::storage::Store;
+
+/// Count this run in `.kuk/usage.json` when `config` has `usage_log` on.
+/// Only the command's name is kept, never its arguments, and a failure to
+/// record is ignored, since the command itself is done.
+pub fn record(
+    store: &Store,
+    config: &RepoConfig,
+    args: impl Iterator<Item = String>,
+    took: Duration,
+    ok: bool,
+) {
+    if !config.usage_log {
+        return;
+    }
+    let result = match command_name(args) {
+        // Looking at the log isn't usage worth logging
+        Some(name) if name != "usage" => store.record_usage(&name, took, ok),
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        tracing::debug!("could not record usage: {e}");
+    }
+}
+
+/// The subcommands named in `args`, like `board create` for `kuk board
+/// create web --template scrum`, with aliases resolved. None when no
+/// subcommand was given.
+fn command_name(mut args: impl Iterator<Item = String>) -> Option<String> {
+    let cli = Cli::command();
+    let mut current = &cli;
+    let mut names = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--repo" {
+            args.next();
+            continue;
+        }
+        if arg.starts_with('-') {
+            continue;
+        }
+        match current.find_subcommand(&arg) {
+            Some(sub) => {
+                names.push(sub.get_name().to_string());
+                current = sub;
+            }
+            None => break,
+        }
+    }
+    (!names.is_empty()).then(|| names.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(line: &str) -> Option<String> {
+        command_name(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn names_leave_out_arguments_and_flags() {
+        assert_eq!(name("add Fix login --to doing").as_deref(), Some("add"));
+        assert_eq!(
+            name("--repo /tmp/x --json board create web --template scrum").as_deref(),
+            Some("board create")
+        );
+        assert_eq!(
+            name("column queue review on").as_deref(),
+            Some("column queue")
+        );
+        assert_eq!(name("--json"), None);
+    }
+}
@@ -0,0 +1,310 @@
+//! The sample project behind `kuk demo`: a populated board with varied
+//! columns, labels, ages and an active kuk-pm sprint, for trying kuk out and
+//! for screenshots.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{Duration, Utc};
+
+use crate::error::{KukError, Result};
+use crate::model::{Board, Card, Column, InboxSource, LabelDef, RepoConfig, SCHEMA_VERSION};
+use crate::storage::Store;
+
+/// Demo sprint name, also written to `.kuk/sprints.json`.
+pub const DEMO_SPRINT: &str = "Sprint 12";
+
+/// (title, column, labels, assignee, age in days, description)
+type Seed = (
+    &'static str,
+    &'static str,
+    &'static [&'static str],
+    Option<&'static str>,
+    i64,
+    Option<&'static str>,
+);
+
+const CARDS: &[Seed] = &[
+    (
+        "Dark mode for settings page",
+        "backlog",
+        &["feature", "ux"],
+        None,
+        41,
+        None,
+    ),
+    (
+        "Evaluate SQLite for the search index",
+        "backlog",
+        &["spike"],
+        None,
+        33,
+        Some("Compare against the current JSON scan on a 10k card board."),
+    ),
+    (
+        "Onboarding checklist for new contributors",
+        "backlog",
+        &["docs"],
+        None,
+        19,
+        None,
+    ),
+    (
+        "Rate limit the public API",
+        "todo",
+        &["feature", "api"],
+        Some("priya"),
+        12,
+        Some("- [ ] Token bucket per key\n- [ ] 429 with Retry-After\n- [ ] Docs"),
+    ),
+    (
+        "Crash when exporting an empty board",
+        "todo",
+        &["bug"],
+        Some("marco"),
+        3,
+        Some("Steps: create a board, export to CSV. Panics on unwrap in exporter."),
+    ),
+    (
+        "Upgrade CI runners to the new image",
+        "todo",
+        &["chore"],
+        None,
+        6,
+        None,
+    ),
+    (
+        "Keyboard shortcuts cheat sheet",
+        "todo",
+        &["docs", "ux"],
+        Some("lena"),
+        8,
+        None,
+    ),
+    (
+        "Webhook retries with backoff",
+        "doing",
+        &["feature", "api"],
+        Some("priya"),
+        9,
+        Some("Retry 5 times, doubling from 1s. Dead-letter after the last attempt."),
+    ),
+    (
+        "Login redirect loop on Safari",
+        "doing",
+        &["bug", "urgent"],
+        Some("marco"),
+        2,
+        None,
+    ),
+    (
+        "Split the settings form into tabs",
+        "doing",
+        &["ux"],
+        Some("lena"),
+        5,
+        None,
+    ),
+    (
+        "Cache board lookups in the API layer",
+        "review",
+        &["perf", "api"],
+        Some("sam"),
+        7,
+        Some("PR is up; p95 went from 38ms to 9ms locally."),
+    ),
+    (
+        "Fix flaky date test around DST",
+        "review",
+        &["bug", "chore"],
+        Some("marco"),
+        4,
+        None,
+    ),
+    (
+        "Release notes for 2.3",
+        "done",
+        &["docs"],
+        Some("lena"),
+        10,
+        None,
+    ),
+    (
+        "Paginate the activity feed",
+        "done",
+        &["feature", "perf"],
+        Some("sam"),
+        13,
+        None,
+    ),
+    (
+        "Broken avatar on the team page",
+        "done",
+        &["bug"],
+        Some("priya"),
+        11,
+        None,
+    ),
+    (
+        "Drop support for the v1 config format",
+        "done",
+        &["chore"],
+        Some("sam"),
+        24,
+        None,
+    ),
+];
+
+const LABELS: &[(&str, &str, &str)] = &[
+    ("bug", "#d73a4a", "Something isn't working"),
+    ("feature", "#0e8a16", "New functionality"),
+    ("ux", "#c5def5", "Look and feel"),
+    ("docs", "#0075ca", "Documentation"),
+    ("chore", "#bfbfbf", "Maintenance"),
+    ("perf", "#fbca04", "Speed and memory"),
+    ("api", "#5319e7", "REST API"),
+    ("spike", "#f9d0c4", "Research"),
+    ("urgent", "#b60205", "Drop everything"),
+];
+
+/// Build the demo board. Ages are relative to now, and cards already done
+/// were finished at points across the last two weeks.
+pub fn demo_board() -> Board {
+    let now = Utc::now();
+    let mut board = Board {
+        schema_version: SCHEMA_VERSION,
+        name: "default".into(),
+        columns: [
+            ("backlog", None),
+            ("todo", None),
+            ("doing", Some(3)),
+            ("review", Some(2)),
+            ("done", None),
+        ]
+        .into_iter()
+        .map(|(name, wip_limit)| Column {
+            wip_limit,
+            ..Column::new(name)
+        })
+        .collect(),
+        cards: Vec::new(),
+        labels: LABELS
+            .iter()
+            .map(|(name, color, description)| LabelDef {
+                name: (*name).into(),
+                color: Some((*color).into()),
+                description: Some((*description).into()),
+            })
+            .collect(),
+        strict_labels: false,
+        last_seq: 0,
+        swimlanes: None,
+        partial: false,
+    };
+
+    for (i, (title, column, labels, assignee, age, description)) in CARDS.iter().enumerate() {
+        let mut card = Card::new(*title, *column);
+        card.order = board.next_order(column);
+        card.labels = labels.iter().map(|l| (*l).into()).collect();
+        card.assignee = assignee.map(Into::into);
+        card.description = description.map(Into::into);
+        card.created_at = now - Duration::days(*age) - Duration::hours(i as i64);
+        card.updated_at = match *column {
+            "done" => card.created_at + Duration::days(age / 2 + 1),
+            "backlog" => card.created_at,
+            _ => now - Duration::hours(3 * i as i64 + 1),
+        };
+        if *column == "todo" && labels.contains(&"bug") {
+            card.due = Some(now + Duration::days(2));
+        } else if *column == "doing" && labels.contains(&"urgent") {
+            card.due = Some(now - Duration::days(1));
+        }
+        board.cards.push(card);
+    }
+
+    let mut archived = Card::new("Prototype the old sync daemon", "done");
+    archived.order = board.next_order("done");
+    archived.created_at = now - Duration::days(60);
+    archived.updated_at = now - Duration::days(45);
+    archived.archived = true;
+    board.cards.push(archived);
+
+    let mut source = InboxSource::new("email");
+    source.sender = Some("customer@example.com".into());
+    source.received_at = now - Duration::hours(5);
+    board.receive(
+        &RepoConfig::default().inbox_column,
+        "CSV import drops the last row",
+        Some("Attached file has 120 rows, only 119 show up.".into()),
+        source,
+    );
+    board
+}
+
+/// Write the demo project under `root`, which must not already contain a
+/// `.kuk/` directory. The project is not added to the global index.
+pub fn write_demo(root: &Path) -> Result<Store> {
+    let store = Store::new(root);
+    if store.is_initialized() {
+        return Err(KukError::AlreadyInitialized(
+            store.kuk_dir().display().to_string(),
+        ));
+    }
+    let boards = store.kuk_dir().join("boards");
+    fs::create_dir_all(&boards).map_err(KukError::write(&boards))?;
+    store.save_config(&RepoConfig::default())?;
+    store.save_board(&demo_board())?;
+
+    let today = Utc::now().date_naive();
+    let sprints = serde_json::json!([
+        {
+            "name": "Sprint 11",
+            "start": today - Duration::days(21),
+            "end": today - Duration::days(8),
+            "goal": "Ship 2.3",
+            "boards": ["default"],
+            "status": "closed",
+        },
+        {
+            "name": DEMO_SPRINT,
+            "start": today - Duration::days(7),
+            "end": today + Duration::days(6),
+            "goal": "Public API hardening",
+            "boards": ["default"],
+            "status": "active",
+        },
+    ]);
+    let path = store.kuk_dir().join("sprints.json");
+    fs::write(&path, serde_json::to_string_pretty(&sprints)?).map_err(KukError::write(&path))?;
+    Ok(store)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn demo_project_loads_and_uses_every_column() {
+        let dir = TempDir::new().unwrap();
+        let store = write_demo(dir.path()).unwrap();
+
+        let board = store.load_board("default").unwrap();
+        assert_eq!(board.columns[0].name, "inbox");
+        for column in &board.columns {
+            assert!(
+                !board.column_cards(&column.name).is_empty(),
+                "{}",
+                column.name
+            );
+        }
+        for card in &board.cards {
+            assert!(card.updated_at >= card.created_at, "{}", card.title);
+            assert!(card.labels.iter().all(|l| board.label_def(l).is_some()));
+        }
+        let sprints = std::fs::read_to_string(store.kuk_dir().join("sprints.json")).unwrap();
+        assert!(sprints.contains(DEMO_SPRINT));
+
+        assert!(write_demo(dir.path()).is_err());
+    }
+}
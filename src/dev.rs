@@ -0,0 +1,154 @@
+//! Synthetic data for benchmarks and manual testing with large boards.
+
+use chrono::{Duration, Utc};
+
+use crate::error::Result;
+use crate::model::{Board, Card};
+use crate::storage::Store;
+
+const VERBS: &[&str] = &[
+    "Fix", "Add", "Refactor", "Document", "Test", "Remove", "Speed up", "Review",
+];
+const NOUNS: &[&str] = &[
+    "login flow",
+    "board loader",
+    "sync log",
+    "card ordering",
+    "CLI output",
+    "MCP tools",
+    "REST API",
+    "TUI layout",
+    "config parsing",
+    "error messages",
+];
+const LABELS: &[&str] = &["bug", "feature", "chore", "docs", "perf", "ux"];
+const ASSIGNEES: &[&str] = &["alice", "bob", "carol", "dave"];
+
+/// Small deterministic xorshift generator so generated boards are
+/// reproducible for a given seed.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+}
+
+/// Build a default-columns board with `cards` synthetic cards: random titles,
+/// labels and assignees, about 5% archived, and timestamps spread over the
+/// past 180 days.
+pub fn generate_board(name: &str, cards: usize, seed: u64) -> Board {
+    let mut rng = Rng::new(seed);
+    let mut board = Board::default_board();
+    board.name = name.into();
+
+    let columns: Vec<String> = board.columns.iter().map(|c| c.name.clone()).collect();
+    // Board::next_order scans every card, which is quadratic at this size
+    let mut next_order = vec![0u32; columns.len()];
+    let now = Utc::now();
+
+    for i in 0..cards {
+        let col = rng.below(columns.len());
+        let title = format!("{} {} #{}", rng.pick(VERBS), rng.pick(NOUNS), i + 1);
+        let mut card = Card::new(title, &columns[col]);
+        card.order = next_order[col];
+        next_order[col] += 1;
+
+        let label_count = rng.below(3);
+        for _ in 0..label_count {
+            let label = rng.pick(LABELS).to_string();
+            if !card.labels.contains(&label) {
+                card.labels.push(label);
+            }
+        }
+        if rng.chance(70) {
+            card.assignee = Some(rng.pick(ASSIGNEES).into());
+        }
+
+        let age = Duration::minutes(rng.below(180 * 24 * 60) as i64);
+        card.created_at = now - age;
+        let worked = Duration::minutes(rng.below(age.num_minutes() as usize + 1) as i64);
+        card.updated_at = card.created_at + worked;
+        card.archived = rng.chance(5);
+
+        board.cards.push(card);
+    }
+    board
+}
+
+/// Write `boards` generated boards named `gen-1` .. `gen-N` into the store,
+/// replacing any existing boards with those names. `cards` is the total
+/// across all boards. Returns the board names.
+pub fn generate(store: &Store, boards: usize, cards: usize, seed: u64) -> Result<Vec<String>> {
+    let boards = boards.max(1);
+    let mut names = Vec::with_capacity(boards);
+    for b in 0..boards {
+        let name = format!("gen-{}", b + 1);
+        // Spread the remainder over the first boards
+        let count = cards / boards + usize::from(b < cards % boards);
+        let board = generate_board(&name, count, seed.wrapping_add(b as u64));
+        store.save_board(&board)?;
+        names.push(name);
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn generated_boards_are_deterministic() {
+        let a = generate_board("gen", 200, 42);
+        let b = generate_board("gen", 200, 42);
+        assert_eq!(a.cards.len(), 200);
+        let titles = |board: &Board| {
+            board
+                .cards
+                .iter()
+                .map(|c| c.title.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles(&a), titles(&b));
+        assert_ne!(titles(&a), titles(&generate_board("gen", 200, 7)));
+        assert!(a.cards.iter().all(|c| a.has_column(&c.column)));
+        assert!(a.cards.iter().all(|c| c.updated_at >= c.created_at));
+    }
+
+    #[test]
+    fn generate_splits_cards_across_boards() {
+        let dir = TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+
+        let names = generate(&store, 3, 10, 1).unwrap();
+        assert_eq!(names, vec!["gen-1", "gen-2", "gen-3"]);
+        let counts: Vec<usize> = names
+            .iter()
+            .map(|n| store.load_board(n).unwrap().cards.len())
+            .collect();
+        assert_eq!(counts, vec![4, 3, 3]);
+    }
+}
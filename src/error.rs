@@ -1,3 +1,7 @@
+use std::fmt::Write as _;
+use std::io;
+use std::path::PathBuf;
+
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -17,11 +21,43 @@ pub enum KukError {
     #[error("Column not found: {0}")]
     ColumnNotFound(String),
 
+    #[error("Similar card already exists: {0}")]
+    DuplicateCard(String),
+
+    #[error("WIP limit reached: {0}")]
+    WipLimitReached(String),
+
+    #[error("Unknown label: {0}. This board only allows labels defined with `kuk label-def add`.")]
+    UnknownLabel(String),
+
+    #[error("Card template not found: {0}")]
+    TemplateNotFound(String),
+
     #[error("Label not found on card: {0}")]
     LabelNotFound(String),
 
+    #[error("Failed to {op} {}", path.display())]
+    File {
+        op: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error(
+        "{} is invalid JSON at line {}, column {}",
+        path.display(),
+        source.line(),
+        source.column()
+    )]
+    InvalidJson {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(#[from] io::Error),
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
@@ -30,4 +66,97 @@ pub enum KukError {
     Other(String),
 }
 
+impl KukError {
+    /// A reading error with the file it came from.
+    pub fn read(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        move |source| KukError::File {
+            op: "read",
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// A writing error with the file it came from.
+    pub fn write(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        move |source| KukError::File {
+            op: "write",
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// A parse error with the file it came from.
+    pub fn invalid_json(path: impl Into<PathBuf>) -> impl FnOnce(serde_json::Error) -> Self {
+        move |source| KukError::InvalidJson {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// What the user can do about the error, where there is something.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            KukError::BoardNotFound(_) => {
+                Some("Run `kuk board list` to see the boards in this repo.".into())
+            }
+            KukError::CardNotFound(_) => Some("Run `kuk list` to see card numbers.".into()),
+            KukError::InvalidJson { path, .. } => Some(format!(
+                "Fix the file by hand, or restore it with `git checkout -- {}`.",
+                path.display()
+            )),
+            KukError::File { source, .. } if source.kind() == io::ErrorKind::PermissionDenied => {
+                Some("Check the file permissions of the .kuk directory.".into())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The text printed for a failed command: the error, each underlying cause,
+/// and an optional hint.
+pub fn render_report(err: &(dyn std::error::Error + 'static), hint: Option<&str>) -> String {
+    let mut out = format!("Error: {err}");
+    let mut source = err.source();
+    while let Some(cause) = source {
+        let _ = write!(out, "\n  caused by: {cause}");
+        source = cause.source();
+    }
+    if let Some(hint) = hint {
+        let _ = write!(out, "\n  hint: {hint}");
+    }
+    out
+}
+
 pub type Result<T> = std::result::Result<T, KukError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn invalid_json_names_file_and_line() {
+        let source = serde_json::from_str::<serde_json::Value>("{\n  \"a\": 1,\n}").unwrap_err();
+        let err = KukError::invalid_json("/repo/.kuk/boards/default.json")(source);
+        assert_eq!(
+            err.to_string(),
+            "/repo/.kuk/boards/default.json is invalid JSON at line 3, column 1"
+        );
+        let report = render_report(&err, err.hint().as_deref());
+        assert!(report.contains("\n  caused by: trailing comma"));
+        assert!(report.contains("\n  hint: Fix the file by hand"));
+    }
+
+    #[test]
+    fn report_walks_source_chain() {
+        let err = KukError::read("/repo/.kuk/config.json")(io::Error::new(
+            io::ErrorKind::NotFound,
+            "missing",
+        ));
+        assert_eq!(
+            render_report(&err, None),
+            "Error: Failed to read /repo/.kuk/config.json\n  caused by: missing"
+        );
+        assert!(err.source().is_some());
+    }
+}
@@ -1,7 +1,22 @@
+pub mod api;
+pub mod capture;
+#[cfg(feature = "app")]
 pub mod cli;
+pub mod demo;
+pub mod dev;
 pub mod error;
+#[cfg(feature = "app")]
+pub mod logging;
 pub mod mcp_stdio;
+mod mcp_tools;
 pub mod model;
+pub mod policy;
+pub mod resolve;
+pub mod rpc;
+#[cfg(feature = "self-update")]
+pub mod self_update;
+#[cfg(feature = "app")]
 pub mod server;
 pub mod storage;
+#[cfg(feature = "app")]
 pub mod tui;
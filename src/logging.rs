@@ -0,0 +1,54 @@
+//! Diagnostic logging to stderr for `kuk` and `kuk-pm`.
+//!
+//! Off by default apart from warnings. `-v` shows server requests and forge
+//! API calls, `-vv` adds storage and git operations, and `-vvv` everything.
+//! `KUK_LOG` takes an `EnvFilter` directive (e.g. `kuk::storage=debug`) and
+//! overrides the flags. Timed operations log their duration when they finish.
+
+use std::io::IsTerminal;
+
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Environment variable holding a filter directive.
+pub const LOG_ENV: &str = "KUK_LOG";
+
+/// Install the stderr subscriber. Safe to call more than once.
+pub fn init(verbose: u8) {
+    let filter = match std::env::var(LOG_ENV) {
+        Ok(spec) if !spec.trim().is_empty() => EnvFilter::try_new(&spec).unwrap_or_else(|e| {
+            eprintln!("Warning: ignoring invalid {LOG_ENV}: {e}");
+            EnvFilter::new(default_filter(verbose))
+        }),
+        _ => EnvFilter::new(default_filter(verbose)),
+    };
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_span_events(FmtSpan::CLOSE)
+        .try_init();
+}
+
+fn default_filter(verbose: u8) -> &'static str {
+    match verbose {
+        0 => "warn",
+        1 => "warn,kuk=info,kuk_pm=info,tower_http=info",
+        2 => "warn,kuk=debug,kuk_pm=debug,tower_http=debug",
+        _ => "trace",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_filters_parse() {
+        for v in 0..4 {
+            assert!(EnvFilter::try_new(default_filter(v)).is_ok());
+        }
+        assert_eq!(default_filter(9), "trace");
+    }
+}
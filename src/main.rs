@@ -2,9 +2,10 @@ use clap::Parser;
 
 fn main() {
     let cli = kuk::cli::Cli::parse();
+    kuk::logging::init(cli.verbose);
 
     if let Err(e) = kuk::cli::run(cli) {
-        eprintln!("Error: {e}");
+        eprintln!("{}", kuk::error::render_report(&e, e.hint().as_deref()));
         std::process::exit(1);
     }
 }
@@ -9,7 +9,9 @@ use std::io::{self, BufRead, Write};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::model::Card;
+use crate::mcp_tools::{self, ToolResult, move_card_on, text_content};
+use crate::model::{Origin, Via};
+use crate::policy::{Guard, POLICY_VIOLATION};
 use crate::storage::Store;
 
 #[derive(Debug, Deserialize)]
@@ -61,12 +63,65 @@ impl JsonRpcResponse {
     }
 }
 
+/// The `kuk_*` board tools for one MCP session, with the agent policy
+/// that guards them. `kuk mcp` serves these alone; `kuk-pm mcp --with-board`
+/// serves them next to its `pm_*` tools.
+pub struct BoardTools {
+    store: Store,
+    guard: Guard,
+}
+
+impl BoardTools {
+    pub fn new(store: &Store) -> Self {
+        BoardTools {
+            store: store.clone().with_origin(Origin::new(Via::Mcp)),
+            guard: Guard::load(&store.kuk_dir()),
+        }
+    }
+
+    /// Credit the session's changes to the client named in `initialize`.
+    pub fn set_client(&mut self, client: Option<&str>) {
+        self.store = self
+            .store
+            .clone()
+            .with_origin(Origin::new(Via::Mcp).client(client));
+    }
+
+    /// The tool definitions, as listed by `tools/list`.
+    pub fn definitions() -> Vec<Value> {
+        match handle_tools_list(Value::Null).result {
+            Some(Value::Object(mut result)) => match result.remove("tools") {
+                Some(Value::Array(tools)) => tools,
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether `name` is one of these tools.
+    pub fn has(name: &str) -> bool {
+        name.starts_with("kuk_")
+    }
+
+    /// Run a `tools/call`, giving its result or a JSON-RPC error code and
+    /// message.
+    pub fn call(&mut self, params: &Value) -> Result<Value, (i32, String)> {
+        let response = handle_tools_call(Value::Null, params, &self.store, &mut self.guard);
+        match (response.result, response.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(error)) => Err((error.code, error.message)),
+            (None, None) => Ok(Value::Null),
+        }
+    }
+}
+
 /// Run the stdio MCP server loop. Blocks until stdin is closed.
 pub fn run(store: &Store) -> crate::error::Result<()> {
     let stdin = io::stdin();
     let stdout = io::stdout();
     let reader = stdin.lock();
     let mut writer = stdout.lock();
+    let mut tools = BoardTools::new(store);
 
     for line in reader.lines() {
         let line = match line {
@@ -94,10 +149,17 @@ pub fn run(store: &Store) -> crate::error::Result<()> {
         let id = req.id.clone().unwrap_or(Value::Null);
 
         let response = match req.method.as_str() {
-            "initialize" => Some(handle_initialize(id)),
+            "initialize" => {
+                // Changes this session makes are credited to the client
+                tools.set_client(req.params["clientInfo"]["name"].as_str());
+                Some(handle_initialize(id))
+            }
             "notifications/initialized" | "initialized" => None,
             "tools/list" => Some(handle_tools_list(id)),
-            "tools/call" => Some(handle_tools_call(id, &req.params, store)),
+            "tools/call" => Some(match tools.call(&req.params) {
+                Ok(result) => JsonRpcResponse::success(id, result),
+                Err((code, message)) => JsonRpcResponse::error(id, code, message),
+            }),
             "ping" => Some(JsonRpcResponse::success(id, serde_json::json!({}))),
             _ => {
                 if is_notification {
@@ -147,10 +209,12 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                     "type": "object",
                     "properties": {
                         "title": {"type": "string", "description": "Card title"},
-                        "column": {"type": "string", "description": "Target column (default: todo)"},
+                        "column": {"type": "string", "description": "Target column (default: the repo's new-card column, else the first column)"},
                         "labels": {"type": "array", "items": {"type": "string"}, "description": "Labels to attach"},
                         "assignee": {"type": "string", "description": "Assignee username"},
-                        "board": {"type": "string", "description": "Board name (default: default)"}
+                        "priority": {"type": "string", "enum": ["low", "medium", "high", "urgent"], "description": "Card priority"},
+                        "board": {"type": "string", "description": "Board name (default: default)"},
+                        "force": {"type": "boolean", "description": "Add even if a similar card exists and the repo rejects duplicates, or the column is at its WIP limit"}
                     },
                     "required": ["title"]
                 }
@@ -173,11 +237,63 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                     "properties": {
                         "id": {"type": "string", "description": "Card ID or short number (e.g. #1)"},
                         "to": {"type": "string", "description": "Target column name"},
-                        "board": {"type": "string", "description": "Board name (default: default)"}
+                        "board": {"type": "string", "description": "Board name (default: default)"},
+                        "force": {"type": "boolean", "description": "Move even if the target column is at its WIP limit"}
                     },
                     "required": ["id", "to"]
                 }
             },
+            {
+                "name": "kuk_add_cards",
+                "description": "Add several cards in one call, saving the board once. Each card is checked like kuk_add_card; failures are reported per card and do not stop the rest",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "cards": {
+                            "type": "array",
+                            "description": "Cards to add, in order",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "title": {"type": "string", "description": "Card title"},
+                                    "column": {"type": "string", "description": "Target column (default: the repo's new-card column, else the first column)"},
+                                    "labels": {"type": "array", "items": {"type": "string"}, "description": "Labels to attach"},
+                                    "assignee": {"type": "string", "description": "Assignee username"},
+                                    "priority": {"type": "string", "enum": ["low", "medium", "high", "urgent"], "description": "Card priority"},
+                                    "force": {"type": "boolean", "description": "Add even if a similar card exists and the repo rejects duplicates, or the column is at its WIP limit"}
+                                },
+                                "required": ["title"]
+                            }
+                        },
+                        "board": {"type": "string", "description": "Board name (default: default)"}
+                    },
+                    "required": ["cards"]
+                }
+            },
+            {
+                "name": "kuk_move_cards",
+                "description": "Move several cards in one call, saving the board once. Each move is checked like kuk_move_card; failures are reported per move and do not stop the rest",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "moves": {
+                            "type": "array",
+                            "description": "Moves to apply, in order",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "id": {"type": "string", "description": "Card ID or short number (e.g. #1)"},
+                                    "to": {"type": "string", "description": "Target column name"},
+                                    "force": {"type": "boolean", "description": "Move even if the target column is at its WIP limit"}
+                                },
+                                "required": ["id", "to"]
+                            }
+                        },
+                        "board": {"type": "string", "description": "Board name (default: default)"}
+                    },
+                    "required": ["moves"]
+                }
+            },
             {
                 "name": "kuk_archive_card",
                 "description": "Archive a card (hide from board but keep data)",
@@ -202,6 +318,32 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
                     "required": ["id"]
                 }
             },
+            {
+                "name": "kuk_add_comment",
+                "description": "Leave a comment on a card, such as a progress note for the humans on the project",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string", "description": "Card ID or short number"},
+                        "body": {"type": "string", "description": "Comment text"},
+                        "author": {"type": "string", "description": "Who is commenting (default: mcp)"},
+                        "board": {"type": "string", "description": "Board name (default: default)"}
+                    },
+                    "required": ["id", "body"]
+                }
+            },
+            {
+                "name": "kuk_list_comments",
+                "description": "List the comments on a card, oldest first",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string", "description": "Card ID or short number"},
+                        "board": {"type": "string", "description": "Board name (default: default)"}
+                    },
+                    "required": ["id"]
+                }
+            },
             {
                 "name": "kuk_list_boards",
                 "description": "List all kanban boards in this repository",
@@ -225,66 +367,50 @@ fn handle_tools_list(id: Value) -> JsonRpcResponse {
     JsonRpcResponse::success(id, tools)
 }
 
-fn handle_tools_call(id: Value, params: &Value, store: &Store) -> JsonRpcResponse {
+fn handle_tools_call(
+    id: Value,
+    params: &Value,
+    store: &Store,
+    guard: &mut Guard,
+) -> JsonRpcResponse {
     let tool_name = params["name"].as_str().unwrap_or("");
     let args = &params["arguments"];
 
-    match tool_name {
-        "kuk_add_card" => tool_add_card(id, args, store),
+    let board = args["board"].as_str().unwrap_or("default");
+    if let Err(rule) = guard.check(tool_name, args, board) {
+        return JsonRpcResponse::error(id, POLICY_VIOLATION, rule);
+    }
+    // Held from each tool's load to its save, so other writers take turns
+    let _lock = match store.lock() {
+        Ok(lock) => lock,
+        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+    };
+    let response = match tool_name {
+        "kuk_add_card" => respond(id, mcp_tools::add_card(args, store)),
         "kuk_list_cards" => tool_list_cards(id, args, store),
         "kuk_move_card" => tool_move_card(id, args, store),
+        "kuk_add_cards" => respond(id, mcp_tools::add_cards(args, store)),
+        "kuk_move_cards" => respond(id, mcp_tools::move_cards(args, store)),
         "kuk_archive_card" => tool_archive_card(id, args, store),
         "kuk_delete_card" => tool_delete_card(id, args, store),
+        "kuk_add_comment" => tool_add_comment(id, args, store),
+        "kuk_list_comments" => tool_list_comments(id, args, store),
         "kuk_list_boards" => tool_list_boards(id, store),
         "kuk_board_info" => tool_board_info(id, args, store),
         _ => JsonRpcResponse::error(id, -32602, format!("Unknown tool: {tool_name}")),
-    }
-}
-
-fn text_content(text: &str) -> Value {
-    serde_json::json!({
-        "content": [{"type": "text", "text": text}]
-    })
-}
-
-fn tool_add_card(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
-    let title = match args["title"].as_str() {
-        Some(t) => t,
-        None => return JsonRpcResponse::error(id, -32602, "title is required"),
-    };
-    let column = args["column"].as_str().unwrap_or("todo");
-    let board_name = args["board"].as_str().unwrap_or("default");
-
-    let mut board = match store.load_board(board_name) {
-        Ok(b) => b,
-        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
     };
-
-    if !board.has_column(column) {
-        return JsonRpcResponse::error(id, -32602, format!("Column not found: {column}"));
-    }
-
-    let mut card = Card::new(title, column);
-    card.order = board.next_order(column);
-
-    if let Some(labels) = args["labels"].as_array() {
-        card.labels = labels
-            .iter()
-            .filter_map(|v| v.as_str().map(String::from))
-            .collect();
+    if let Some(result) = &response.result {
+        guard.record(tool_name, result);
     }
-    if let Some(assignee) = args["assignee"].as_str() {
-        card.assignee = Some(assignee.into());
-    }
-
-    let result = serde_json::to_string_pretty(&card).unwrap();
-    board.cards.push(card);
+    response
+}
 
-    if let Err(e) = store.save_board(&board) {
-        return JsonRpcResponse::error(id, -32603, e.to_string());
+/// `result` as the response to request `id`.
+fn respond(id: Value, result: ToolResult) -> JsonRpcResponse {
+    match result {
+        Ok(content) => JsonRpcResponse::success(id, content),
+        Err(e) => JsonRpcResponse::error(id, e.code, e.message),
     }
-
-    JsonRpcResponse::success(id, text_content(&result))
 }
 
 fn tool_list_cards(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
@@ -295,11 +421,7 @@ fn tool_list_cards(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
             // Format as a readable summary rather than raw JSON
             let mut lines = Vec::new();
             for col in &board.columns {
-                let cards: Vec<&Card> = board
-                    .cards
-                    .iter()
-                    .filter(|c| c.column == col.name && !c.archived)
-                    .collect();
+                let cards = board.column_cards(&col.name);
                 lines.push(format!("## {} ({})", col.name, cards.len()));
                 for (i, card) in cards.iter().enumerate() {
                     let labels = if card.labels.is_empty() {
@@ -312,8 +434,12 @@ fn tool_list_cards(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
                         .as_ref()
                         .map(|a| format!(" @{a}"))
                         .unwrap_or_default();
+                    let priority = card
+                        .priority
+                        .map(|p| format!(" ({p})"))
+                        .unwrap_or_default();
                     lines.push(format!(
-                        "  {}. {} ({}){}{}", i + 1, card.title, &card.id[..8], labels, assignee
+                        "  {}. {} ({}){}{}{}", i + 1, card.title, &card.id[..8], priority, labels, assignee
                     ));
                 }
                 if cards.is_empty() {
@@ -343,23 +469,12 @@ fn tool_move_card(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
         Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
     };
 
-    if !board.has_column(to) {
-        return JsonRpcResponse::error(id, -32602, format!("Column not found: {to}"));
-    }
-
-    let resolved = match board.resolve_card_id(card_id_str) {
-        Some(id) => id,
-        None => {
-            return JsonRpcResponse::error(id, -32602, format!("Card not found: {card_id_str}"))
-        }
+    let force = args["force"].as_bool().unwrap_or(false);
+    let resolved = match move_card_on(&mut board, card_id_str, to, force) {
+        Ok(resolved) => resolved,
+        Err(e) => return JsonRpcResponse::error(id, -32602, e),
     };
-
-    let next_order = board.next_order(to);
-    let card = board.find_card_mut(&resolved).unwrap();
-    card.column = to.into();
-    card.order = next_order;
-    card.updated_at = chrono::Utc::now();
-    let title = card.title.clone();
+    let title = board.find_card(&resolved).unwrap().title.clone();
 
     if let Err(e) = store.save_board(&board) {
         return JsonRpcResponse::error(id, -32603, e.to_string());
@@ -388,7 +503,7 @@ fn tool_archive_card(id: Value, args: &Value, store: &Store) -> JsonRpcResponse
     };
 
     let card = board.find_card_mut(&resolved).unwrap();
-    card.archived = true;
+    card.archive();
     card.updated_at = chrono::Utc::now();
     let title = card.title.clone();
 
@@ -431,6 +546,75 @@ fn tool_delete_card(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
     JsonRpcResponse::success(id, text_content(&format!("Deleted \"{title}\"")))
 }
 
+fn tool_add_comment(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
+    let card_id_str = match args["id"].as_str() {
+        Some(s) => s,
+        None => return JsonRpcResponse::error(id, -32602, "id is required"),
+    };
+    let body = match args["body"].as_str().map(str::trim) {
+        Some(s) if !s.is_empty() => s,
+        _ => return JsonRpcResponse::error(id, -32602, "body is required"),
+    };
+    let author = args["author"].as_str().unwrap_or("mcp");
+    let board_name = args["board"].as_str().unwrap_or("default");
+
+    let mut board = match store.load_board(board_name) {
+        Ok(b) => b,
+        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+    };
+
+    let resolved = match board.resolve_card_id(card_id_str) {
+        Some(id) => id,
+        None => {
+            return JsonRpcResponse::error(id, -32602, format!("Card not found: {card_id_str}"))
+        }
+    };
+
+    let card = board.find_card_mut(&resolved).unwrap();
+    card.add_comment(author, body);
+    let title = card.title.clone();
+
+    if let Err(e) = store.save_board(&board) {
+        return JsonRpcResponse::error(id, -32603, e.to_string());
+    }
+
+    JsonRpcResponse::success(id, text_content(&format!("Commented on \"{title}\"")))
+}
+
+fn tool_list_comments(id: Value, args: &Value, store: &Store) -> JsonRpcResponse {
+    let card_id_str = match args["id"].as_str() {
+        Some(s) => s,
+        None => return JsonRpcResponse::error(id, -32602, "id is required"),
+    };
+    let board_name = args["board"].as_str().unwrap_or("default");
+
+    let board = match store.load_board(board_name) {
+        Ok(b) => b,
+        Err(e) => return JsonRpcResponse::error(id, -32603, e.to_string()),
+    };
+
+    let Some(card) = board
+        .resolve_card_id(card_id_str)
+        .and_then(|resolved| board.find_card(&resolved))
+    else {
+        return JsonRpcResponse::error(id, -32602, format!("Card not found: {card_id_str}"));
+    };
+
+    let text = if card.comments.is_empty() {
+        format!("No comments on \"{}\"", card.title)
+    } else {
+        card.comments
+            .iter()
+            .map(|c| {
+                let at = c.at.format("%Y-%m-%d %H:%M UTC");
+                format!("{} ({at}): {}", c.author, c.body)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    JsonRpcResponse::success(id, text_content(&text))
+}
+
 fn tool_list_boards(id: Value, store: &Store) -> JsonRpcResponse {
     match store.list_boards() {
         Ok(boards) => {
@@ -0,0 +1,232 @@
+//! The MCP tools both transports serve the same way: `kuk mcp` over stdio
+//! and `POST /mcp` on the server. Each returns the tool's result content,
+//! or the JSON-RPC error to answer with.
+
+use serde_json::Value;
+
+use crate::model::{Board, Card, DuplicatePolicy, RepoConfig};
+use crate::storage::Store;
+
+/// JSON-RPC code for a call with missing or bad arguments.
+pub const INVALID_PARAMS: i32 = -32602;
+/// JSON-RPC code for a call that failed reading or writing the board.
+pub const INTERNAL_ERROR: i32 = -32603;
+
+/// Why a tool call failed: the JSON-RPC error code and message.
+#[derive(Debug)]
+pub struct ToolError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl ToolError {
+    fn invalid(message: impl Into<String>) -> Self {
+        ToolError {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+
+    fn internal(e: impl ToString) -> Self {
+        ToolError {
+            code: INTERNAL_ERROR,
+            message: e.to_string(),
+        }
+    }
+}
+
+pub type ToolResult = Result<Value, ToolError>;
+
+/// `text` as a tool result's content.
+pub fn text_content(text: &str) -> Value {
+    serde_json::json!({
+        "content": [{"type": "text", "text": text}]
+    })
+}
+
+/// `kuk_add_card`: add one card and return it, with any duplicate warning.
+pub fn add_card(args: &Value, store: &Store) -> ToolResult {
+    if args["title"].as_str().is_none() {
+        return Err(ToolError::invalid("title is required"));
+    }
+    let board_name = args["board"].as_str().unwrap_or("default");
+
+    let mut board = store.load_board(board_name).map_err(ToolError::internal)?;
+    let config = store.load_config().unwrap_or_default();
+
+    let (card_id, warning) =
+        add_card_from(&mut board, args, &config).map_err(ToolError::invalid)?;
+    let result = serde_json::to_string_pretty(board.find_card(&card_id).unwrap()).unwrap();
+
+    store.save_board(&board).map_err(ToolError::internal)?;
+
+    let mut content = text_content(&result);
+    if let Some(msg) = warning {
+        content["content"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({"type": "text", "text": format!("Warning: {msg}")}));
+    }
+    Ok(content)
+}
+
+/// `kuk_add_cards`: add every card in `cards` that can be added.
+pub fn add_cards(args: &Value, store: &Store) -> ToolResult {
+    let specs = args["cards"]
+        .as_array()
+        .ok_or_else(|| ToolError::invalid("cards is required"))?;
+    let board_name = args["board"].as_str().unwrap_or("default");
+
+    let mut board = store.load_board(board_name).map_err(ToolError::internal)?;
+    let config = store.load_config().unwrap_or_default();
+
+    let mut results = Vec::new();
+    for (index, spec) in specs.iter().enumerate() {
+        results.push(match add_card_from(&mut board, spec, &config) {
+            Ok((card_id, warning)) => {
+                let mut item = batch_item(index, board.find_card(&card_id).unwrap());
+                if let Some(msg) = warning {
+                    item["warning"] = msg.into();
+                }
+                item
+            }
+            Err(e) => serde_json::json!({"index": index, "ok": false, "error": e}),
+        });
+    }
+    batch_response(store, &board, results)
+}
+
+/// `kuk_move_cards`: make every move in `moves` that can be made.
+pub fn move_cards(args: &Value, store: &Store) -> ToolResult {
+    let moves = args["moves"]
+        .as_array()
+        .ok_or_else(|| ToolError::invalid("moves is required"))?;
+    let board_name = args["board"].as_str().unwrap_or("default");
+
+    let mut board = store.load_board(board_name).map_err(ToolError::internal)?;
+
+    let mut results = Vec::new();
+    for (index, item) in moves.iter().enumerate() {
+        let moved = match (item["id"].as_str(), item["to"].as_str()) {
+            (Some(card), Some(to)) => {
+                let force = item["force"].as_bool().unwrap_or(false);
+                move_card_on(&mut board, card, to, force)
+            }
+            (None, _) => Err("id is required".into()),
+            (_, None) => Err("to is required".into()),
+        };
+        results.push(match moved {
+            Ok(resolved) => batch_item(index, board.find_card(&resolved).unwrap()),
+            Err(e) => serde_json::json!({"index": index, "ok": false, "error": e}),
+        });
+    }
+    batch_response(store, &board, results)
+}
+
+/// Add the card `spec` describes to `board`, as `kuk_add_card` does.
+/// Returns the new card's ID and any duplicate warning.
+fn add_card_from(
+    board: &mut Board,
+    spec: &Value,
+    config: &RepoConfig,
+) -> Result<(String, Option<String>), String> {
+    let title = spec["title"].as_str().ok_or("title is required")?;
+    let column = match spec["column"].as_str() {
+        Some(column) => column.to_string(),
+        None => config.new_cards.column(board).unwrap_or_default(),
+    };
+    let column = column.as_str();
+    let policy = config.duplicates;
+
+    if !board.has_column(column) {
+        return Err(format!("Column not found: {column}"));
+    }
+    let force = spec["force"].as_bool().unwrap_or(false);
+    if !force && let Some(limit) = board.wip_exceeded(column, None) {
+        return Err(format!(
+            "WIP limit reached: {column} is at its limit of {limit} cards. Pass force: true to add anyway."
+        ));
+    }
+
+    let mut warning = None;
+    if let Some(dup) = board.duplicate_of(title, policy) {
+        let msg = format!("Similar card already exists: {} ({})", dup.title, dup.id);
+        if policy == DuplicatePolicy::Reject && !force {
+            return Err(format!("{msg}. Pass force: true to add anyway."));
+        }
+        warning = Some(msg);
+    }
+
+    let mut card = Card::new(title, column);
+    card.order = board.next_order(column);
+
+    if let Some(labels) = spec["labels"].as_array() {
+        card.labels = labels
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+    }
+    if let Some(assignee) = spec["assignee"].as_str() {
+        card.assignee = Some(assignee.into());
+    }
+    config.new_cards.apply(&mut card);
+    if let Err(label) = board.check_labels(&card.labels) {
+        return Err(crate::error::KukError::UnknownLabel(label.into()).to_string());
+    }
+    if let Some(priority) = spec["priority"].as_str() {
+        card.priority = Some(priority.parse()?);
+    }
+
+    Ok((board.add_card(card).id.clone(), warning))
+}
+
+/// Move the card `card` names to `to`, as `kuk_move_card` does. Returns
+/// the card's full ID.
+pub fn move_card_on(
+    board: &mut Board,
+    card: &str,
+    to: &str,
+    force: bool,
+) -> Result<String, String> {
+    if !board.has_column(to) {
+        return Err(format!("Column not found: {to}"));
+    }
+    let resolved = board
+        .resolve_card_id(card)
+        .ok_or_else(|| format!("Card not found: {card}"))?;
+    if !force && let Some(limit) = board.wip_exceeded(to, Some(&resolved)) {
+        return Err(format!(
+            "WIP limit reached: {to} is at its limit of {limit} cards. Pass force: true to move anyway."
+        ));
+    }
+    board.move_card(&resolved, to);
+    Ok(resolved)
+}
+
+/// A successful entry in a batch tool's results.
+fn batch_item(index: usize, card: &Card) -> Value {
+    serde_json::json!({
+        "index": index,
+        "ok": true,
+        "id": card.id,
+        "short_id": card.short_id(),
+        "title": card.title,
+        "column": card.column,
+    })
+}
+
+/// Save `board` once if any item succeeded and report every item's result.
+fn batch_response(store: &Store, board: &Board, results: Vec<Value>) -> ToolResult {
+    let applied = results.iter().filter(|r| r["ok"] == true).count();
+    if applied > 0 {
+        store.save_board(board).map_err(ToolError::internal)?;
+    }
+    let summary = serde_json::json!({
+        "applied": applied,
+        "failed": results.len() - applied,
+        "results": results,
+    });
+    Ok(text_content(
+        &serde_json::to_string_pretty(&summary).unwrap(),
+    ))
+}
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Card};
+
+/// Card metadata key holding the card's [`Escalation`] history.
+pub const ESCALATIONS_KEY: &str = "escalations";
+
+/// Escalate cards left untouched in `column` for `after_days`, configured
+/// under `aging` in config.json. Escalating hoists the card to the top of
+/// its column, which is where kuk keeps priority.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AgingRule {
+    /// Only cards with this label; every card when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default = "default_aging_column")]
+    pub column: String,
+    pub after_days: u32,
+}
+
+fn default_aging_column() -> String {
+    "todo".into()
+}
+
+impl AgingRule {
+    fn applies_to(&self, card: &Card) -> bool {
+        card.column == self.column && self.label.as_ref().is_none_or(|l| card.labels.contains(l))
+    }
+}
+
+/// One escalation, appended to `metadata.escalations` on the card.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Escalation {
+    pub at: DateTime<Utc>,
+    /// The rule's label, or `*` for a rule covering every card.
+    pub rule: String,
+    pub column: String,
+    pub idle_days: i64,
+}
+
+impl Escalation {
+    /// Every escalation recorded on a card, oldest first.
+    pub fn history(card: &Card) -> Vec<Self> {
+        card.metadata
+            .get(ESCALATIONS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Whole days since the card was last touched.
+pub fn idle_days(card: &Card, now: DateTime<Utc>) -> i64 {
+    (now - card.updated_at).num_days()
+}
+
+impl Board {
+    /// Cards that `rules` would escalate at `now`, least idle first. Each card
+    /// is matched by the first rule that covers it and is due. Archived and
+    /// snoozed cards are left alone.
+    pub fn due_escalations(
+        &self,
+        rules: &[AgingRule],
+        now: DateTime<Utc>,
+    ) -> Vec<(String, Escalation)> {
+        let mut due: Vec<(&Card, Escalation)> = self
+            .cards
+            .iter()
+            .filter(|c| !c.archived && !c.is_snoozed(now))
+            .filter_map(|card| {
+                let idle = idle_days(card, now);
+                let rule = rules
+                    .iter()
+                    .find(|r| r.applies_to(card) && idle >= i64::from(r.after_days))?;
+                Some((
+                    card,
+                    Escalation {
+                        at: now,
+                        rule: rule.label.clone().unwrap_or_else(|| "*".into()),
+                        column: card.column.clone(),
+                        idle_days: idle,
+                    },
+                ))
+            })
+            .collect();
+        due.sort_by_key(|(c, _)| std::cmp::Reverse(c.updated_at));
+        due.into_iter().map(|(c, e)| (c.id.clone(), e)).collect()
+    }
+
+    /// Hoist every card `rules` find due and record the escalation on it.
+    /// The longest-idle card ends up on top. Returns what was escalated.
+    pub fn escalate_stale(
+        &mut self,
+        rules: &[AgingRule],
+        now: DateTime<Utc>,
+    ) -> Vec<(String, Escalation)> {
+        let due = self.due_escalations(rules, now);
+        for (id, escalation) in &due {
+            self.hoist_card(id);
+            let card = self.find_card_mut(id).expect("due cards exist");
+            let mut history = Escalation::history(card);
+            history.push(escalation.clone());
+            card.metadata.insert(
+                ESCALATIONS_KEY.into(),
+                serde_json::to_value(history).expect("escalations serialize"),
+            );
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn card(board: &mut Board, title: &str, label: &str, idle: i64, now: DateTime<Utc>) {
+        let mut c = Card::new(title, "todo");
+        c.order = board.next_order("todo");
+        c.labels = vec![label.into()];
+        c.updated_at = now - Duration::days(idle);
+        board.cards.push(c);
+    }
+
+    #[test]
+    fn stale_cards_are_hoisted_and_logged() {
+        let now = Utc::now();
+        let mut board = Board::default_board();
+        card(&mut board, "Fresh bug", "bug", 1, now);
+        card(&mut board, "Old bug", "bug", 10, now);
+        card(&mut board, "Older bug", "bug", 20, now);
+        card(&mut board, "Old chore", "chore", 20, now);
+        let rules = vec![AgingRule {
+            label: Some("bug".into()),
+            column: "todo".into(),
+            after_days: 7,
+        }];
+
+        let escalated = board.escalate_stale(&rules, now);
+        assert_eq!(escalated.len(), 2);
+        let titles: Vec<&str> = board
+            .column_cards("todo")
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Older bug", "Old bug", "Fresh bug", "Old chore"]);
+
+        let older = board.column_cards("todo")[0];
+        let history = Escalation::history(older);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].rule, "bug");
+        assert_eq!(history[0].idle_days, 20);
+
+        // Escalating touches the card, so the clock starts over
+        assert!(board.due_escalations(&rules, now).is_empty());
+    }
+}
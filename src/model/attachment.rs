@@ -0,0 +1,135 @@
+use std::path::{Component, Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::Card;
+
+/// Something a card points at: a URL, or a path relative to the repo root
+/// for designs, specs and logs committed alongside the code.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Attachment {
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl Attachment {
+    /// An attachment to `target`, checked against the repo at `root`: a URL
+    /// is taken as is, a path must stay inside the repo and exist.
+    pub fn new(target: &str, title: Option<String>, root: &Path) -> Result<Self, String> {
+        let target = target.trim();
+        if target.is_empty() {
+            return Err("Attachment target must not be empty".into());
+        }
+        let attachment = Attachment {
+            target: target.trim_start_matches("./").to_string(),
+            title: title.filter(|t| !t.trim().is_empty()),
+        };
+        if attachment.is_url() {
+            return Ok(attachment);
+        }
+        let escapes = Path::new(&attachment.target)
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+        if escapes {
+            return Err(format!(
+                "{target} isn't inside the repo; give a path relative to its root or a URL"
+            ));
+        }
+        if !attachment.exists(root) {
+            return Err(format!("{target} doesn't exist in the repo"));
+        }
+        Ok(attachment)
+    }
+
+    /// Whether the target is a URL rather than a repo path.
+    pub fn is_url(&self) -> bool {
+        self.target
+            .split_once("://")
+            .is_some_and(|(scheme, _)| !scheme.is_empty() && !scheme.contains('/'))
+            || self.target.starts_with("mailto:")
+    }
+
+    /// Whether a repo path still exists under `root`. URLs always do, as
+    /// far as kuk can tell.
+    pub fn exists(&self, root: &Path) -> bool {
+        self.is_url() || root.join(&self.target).exists()
+    }
+
+    /// The title, or the target when there is none.
+    pub fn label(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.target)
+    }
+}
+
+impl Card {
+    /// Attach `attachment`, replacing the title of an existing one with the
+    /// same target. Returns whether it was new.
+    pub fn attach(&mut self, attachment: Attachment) -> bool {
+        self.updated_at = chrono::Utc::now();
+        match self
+            .attachments
+            .iter_mut()
+            .find(|a| a.target == attachment.target)
+        {
+            Some(existing) => {
+                existing.title = attachment.title;
+                false
+            }
+            None => {
+                self.attachments.push(attachment);
+                true
+            }
+        }
+    }
+
+    /// Remove the attachment to `target`, if there is one.
+    pub fn detach(&mut self, target: &str) -> Option<Attachment> {
+        let target = target.trim().trim_start_matches("./");
+        let i = self.attachments.iter().position(|a| a.target == target)?;
+        self.updated_at = chrono::Utc::now();
+        Some(self.attachments.remove(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn urls_pass_and_paths_must_exist_inside_the_repo() {
+        let repo = TempDir::new().unwrap();
+        std::fs::create_dir(repo.path().join("docs")).unwrap();
+        std::fs::write(repo.path().join("docs/spec.md"), "# Spec").unwrap();
+        let root = repo.path();
+
+        let url = Attachment::new("https://example.com/design", None, root).unwrap();
+        assert!(url.is_url());
+        let spec = Attachment::new("./docs/spec.md", Some("Spec".into()), root).unwrap();
+        assert_eq!(spec.target, "docs/spec.md");
+        assert_eq!(spec.label(), "Spec");
+
+        assert!(Attachment::new("docs/missing.md", None, root).is_err());
+        assert!(Attachment::new("../outside.md", None, root).is_err());
+        assert!(Attachment::new("/etc/hosts", None, root).is_err());
+        assert!(Attachment::new(" ", None, root).is_err());
+    }
+
+    #[test]
+    fn attaching_the_same_target_again_retitles_it() {
+        let mut card = Card::new("Login page", "todo");
+        let link = |title: Option<&str>| Attachment {
+            target: "https://example.com/mock".into(),
+            title: title.map(Into::into),
+        };
+        assert!(card.attach(link(None)));
+        assert!(!card.attach(link(Some("Mockup"))));
+        assert_eq!(card.attachments.len(), 1);
+        assert_eq!(card.attachments[0].label(), "Mockup");
+
+        assert!(card.detach("https://example.com/mock").is_some());
+        assert!(card.detach("https://example.com/mock").is_none());
+        assert!(card.attachments.is_empty());
+    }
+}
@@ -1,43 +1,143 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::Card;
+use super::title::{SIMILARITY_THRESHOLD, title_similarity};
+use super::{
+    Card, CardSort, DueStatus, DuplicatePolicy, LabelDef, Swimlanes, Transition, parse_short_id,
+};
+
+/// Display order of cards within a column: by `order`, with ties broken by
+/// creation time and then id so every view agrees on duplicates.
+pub fn cmp_position(a: &Card, b: &Card) -> Ordering {
+    a.order
+        .cmp(&b.order)
+        .then(a.created_at.cmp(&b.created_at))
+        .then_with(|| a.id.cmp(&b.id))
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Column {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub wip_limit: Option<u32>,
+    /// Cards moved into this column are handed to this assignee.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_assignee: Option<String>,
+    /// Labels added to cards moved into this column.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_labels: Vec<String>,
+    /// A buffer where finished work waits for the next step, like "ready
+    /// for review". Reports count its cards as queued rather than in
+    /// progress.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub queue: bool,
+}
+
+impl Column {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            wip_limit: None,
+            default_assignee: None,
+            auto_labels: Vec::new(),
+            queue: false,
+        }
+    }
 }
 
+/// The version of the board and config file formats this kuk writes; see
+/// `storage::migrate` for how older files are upgraded.
+pub const SCHEMA_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Board {
+    /// Format the board was written in; 0 for files from before it was
+    /// recorded.
+    #[serde(default)]
+    pub schema_version: u32,
     pub name: String,
     pub columns: Vec<Column>,
+    /// Absent from the `board.json` of the cards layout, which keeps each
+    /// card in its own file.
+    #[serde(default)]
     pub cards: Vec<Card>,
+    /// Label registry: names, colors, and descriptions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<LabelDef>,
+    /// Only allow card labels that are in the registry.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_labels: bool,
+    /// The last card sequence number given out; see [`Card::seq`].
+    #[serde(default)]
+    pub last_seq: u64,
+    /// How the TUI and `kuk list --lanes` split the board into rows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swimlanes: Option<Swimlanes>,
+    /// Loaded with `Store::load_board_summary`: card descriptions and
+    /// metadata were skipped, so the store refuses to save it.
+    #[serde(skip)]
+    pub partial: bool,
 }
 
 impl Board {
     pub fn default_board() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             name: "default".into(),
             columns: vec![
-                Column {
-                    name: "todo".into(),
-                    wip_limit: None,
-                },
-                Column {
-                    name: "doing".into(),
-                    wip_limit: None,
-                },
-                Column {
-                    name: "done".into(),
-                    wip_limit: None,
-                },
+                Column::new("todo"),
+                Column::new("doing"),
+                Column::new("done"),
             ],
             cards: Vec::new(),
+            labels: Vec::new(),
+            strict_labels: false,
+            last_seq: 0,
+            swimlanes: None,
+            partial: false,
         }
     }
 
+    /// Add a card, giving it the next sequence number.
+    pub fn add_card(&mut self, mut card: Card) -> &mut Card {
+        self.last_seq += 1;
+        card.seq = Some(self.last_seq);
+        self.cards.push(card);
+        self.cards.last_mut().expect("just pushed")
+    }
+
+    /// Give sequence numbers to cards that lack one, oldest first, and to
+    /// cards whose number is taken by an earlier card, such as one brought
+    /// over from another board. Returns whether anything changed. The
+    /// store calls this on load, so boards from before short IDs get them.
+    pub fn number_cards(&mut self) -> bool {
+        let mut taken = HashSet::new();
+        let mut unnumbered = Vec::new();
+        for (i, card) in self.cards.iter().enumerate() {
+            match card.seq {
+                Some(seq) if taken.insert(seq) => {}
+                _ => unnumbered.push(i),
+            }
+        }
+        if let Some(&max) = taken.iter().max() {
+            self.last_seq = self.last_seq.max(max);
+        }
+        unnumbered.sort_by(|&a, &b| {
+            let (a, b) = (&self.cards[a], &self.cards[b]);
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        for &i in &unnumbered {
+            self.last_seq += 1;
+            self.cards[i].seq = Some(self.last_seq);
+        }
+        !unnumbered.is_empty()
+    }
+
     pub fn has_column(&self, name: &str) -> bool {
         self.columns.iter().any(|c| c.name == name)
     }
@@ -48,10 +148,201 @@ impl Board {
             .filter(|c| c.column == column && !c.archived)
             .map(|c| c.order)
             .max()
-            .map(|m| m + 1)
+            .map(|m| m.saturating_add(1))
             .unwrap_or(0)
     }
 
+    /// Active (non-archived, non-snoozed) cards in `column`, in display
+    /// order. Cards that resurfaced from a hoisting snooze come first.
+    pub fn column_cards(&self, column: &str) -> Vec<&Card> {
+        let now = Utc::now();
+        let mut cards: Vec<&Card> = self
+            .cards
+            .iter()
+            .filter(|c| c.column == column && !c.archived && !c.is_snoozed(now))
+            .collect();
+        cards.sort_by(|a, b| {
+            b.resurfaced_on_top(now)
+                .cmp(&a.resurfaced_on_top(now))
+                .then(cmp_position(a, b))
+        });
+        cards
+    }
+
+    /// A card's due status for display. Cards in the last column are done,
+    /// so their due dates no longer matter.
+    pub fn due_status(&self, card: &Card, now: DateTime<Utc>) -> Option<DueStatus> {
+        if self.columns.last().is_some_and(|c| c.name == card.column) {
+            return None;
+        }
+        card.due_status(now)
+    }
+
+    /// Cards hidden by a snooze at `now`, soonest to resurface first.
+    pub fn snoozed_cards(&self, now: DateTime<Utc>) -> Vec<&Card> {
+        let mut cards: Vec<&Card> = self
+            .cards
+            .iter()
+            .filter(|c| !c.archived && c.is_snoozed(now))
+            .collect();
+        cards.sort_by_key(|c| c.snooze.map(|s| s.until));
+        cards
+    }
+
+    /// Archived cards, most recently archived first. Cards archived before
+    /// kuk recorded when go last, most recently updated first.
+    pub fn archived_cards(&self) -> Vec<&Card> {
+        let mut cards: Vec<&Card> = self.cards.iter().filter(|c| c.archived).collect();
+        cards.sort_by_key(|c| std::cmp::Reverse((c.archived_at(), c.updated_at)));
+        cards
+    }
+
+    /// Sort all cards into display order: by column, then position. Cards in
+    /// columns the board doesn't have go last.
+    pub fn sort_cards(&mut self) {
+        self.sort_cards_by(CardSort::default());
+    }
+
+    /// Sort all cards by column, then `sort` within each column.
+    pub fn sort_cards_by(&mut self, sort: CardSort) {
+        let columns = &self.columns;
+        let index = |c: &Card| {
+            columns
+                .iter()
+                .position(|col| col.name == c.column)
+                .unwrap_or(usize::MAX)
+        };
+        self.cards
+            .sort_by(|a, b| index(a).cmp(&index(b)).then_with(|| sort.cmp(a, b)));
+    }
+
+    /// Renumber the active cards in `column` to 0..n in display order,
+    /// removing duplicates and gaps. Returns how many cards changed.
+    pub fn normalize_column(&mut self, column: &str) -> usize {
+        let ids: Vec<String> = self
+            .column_cards(column)
+            .iter()
+            .map(|c| c.id.clone())
+            .collect();
+        self.renumber(&ids)
+    }
+
+    /// Normalize every column. Returns how many cards changed.
+    pub fn normalize_orders(&mut self) -> usize {
+        let columns: Vec<String> = self.columns.iter().map(|c| c.name.clone()).collect();
+        columns.iter().map(|c| self.normalize_column(c)).sum()
+    }
+
+    /// Bring an archived card back to the bottom of the column it was
+    /// archived from, or of the first column if that one is gone. Returns
+    /// the column, or None if the card isn't archived.
+    pub fn unarchive_card(&mut self, id: &str) -> Option<String> {
+        let column = self.find_card(id).filter(|c| c.archived)?.column.clone();
+        let order = self.next_order(&column);
+        let card = self.find_card_mut(id)?;
+        card.unarchive();
+        card.order = order;
+        card.updated_at = Utc::now();
+        if self.has_column(&column) {
+            self.normalize_column(&column);
+            return Some(column);
+        }
+        let first = self.columns.first()?.name.clone();
+        self.move_card(id, &first);
+        Some(first)
+    }
+
+    /// Move a card to the bottom of `to` and normalize both columns. A card
+    /// entering a different column records the [`Transition`] and picks up
+    /// that column's default assignee and auto labels. Returns false if the
+    /// card doesn't exist.
+    pub fn move_card(&mut self, id: &str, to: &str) -> bool {
+        let order = self.next_order(to);
+        let rules = self.columns.iter().find(|c| c.name == to).cloned();
+        let Some(card) = self.find_card_mut(id) else {
+            return false;
+        };
+        let from = std::mem::replace(&mut card.column, to.into());
+        card.order = order;
+        card.updated_at = Utc::now();
+        card.snooze = None;
+        if from != to {
+            Transition::record(card, &from, card.updated_at);
+            if let Some(rules) = rules {
+                if rules.default_assignee.is_some() {
+                    card.set_assignee(rules.default_assignee);
+                }
+                for label in rules.auto_labels {
+                    card.add_label(&label);
+                }
+            }
+        }
+        self.normalize_column(&from);
+        self.normalize_column(to);
+        true
+    }
+
+    /// Move a card to the top of its column. Returns false if the card
+    /// doesn't exist.
+    pub fn hoist_card(&mut self, id: &str) -> bool {
+        self.reposition(id, true)
+    }
+
+    /// Move a card to the bottom of its column. Returns false if the card
+    /// doesn't exist.
+    pub fn demote_card(&mut self, id: &str) -> bool {
+        self.reposition(id, false)
+    }
+
+    fn reposition(&mut self, id: &str, to_top: bool) -> bool {
+        let Some(card) = self.find_card_mut(id) else {
+            return false;
+        };
+        card.updated_at = Utc::now();
+        card.snooze = None;
+        let column = card.column.clone();
+
+        let mut ids: Vec<String> = self
+            .column_cards(&column)
+            .iter()
+            .filter(|c| c.id != id)
+            .map(|c| c.id.clone())
+            .collect();
+        if to_top {
+            ids.insert(0, id.into());
+        } else {
+            ids.push(id.into());
+        }
+        self.renumber(&ids);
+        true
+    }
+
+    /// Assign orders 0..n to `ids` in sequence. Returns how many changed.
+    /// Expired snoozes are dropped, since the new orders already place the
+    /// cards where they surfaced.
+    fn renumber(&mut self, ids: &[String]) -> usize {
+        let now = Utc::now();
+        let position: HashMap<&str, u32> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.as_str(), i as u32))
+            .collect();
+        let mut changed = 0;
+        for card in &mut self.cards {
+            let Some(&order) = position.get(card.id.as_str()) else {
+                continue;
+            };
+            if card.snooze.is_some_and(|s| s.until <= now) {
+                card.snooze = None;
+            }
+            if card.order != order {
+                card.order = order;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
     pub fn find_card(&self, id: &str) -> Option<&Card> {
         self.cards.iter().find(|c| c.id == id)
     }
@@ -64,14 +355,97 @@ impl Board {
     /// Cards are ordered by their `order` field ascending, non-archived only.
     pub fn find_card_by_number(&self, number: usize) -> Option<&Card> {
         let mut active: Vec<&Card> = self.cards.iter().filter(|c| !c.archived).collect();
-        active.sort_by_key(|c| c.order);
+        active.sort_by(|a, b| cmp_position(a, b));
         active.get(number.wrapping_sub(1)).copied()
     }
 
-    /// Resolve an ID string: either a ULID or a 1-based number.
+    /// The active card whose title best matches `title`, if any is at least
+    /// `SIMILARITY_THRESHOLD` similar. Exact matches (ignoring case and
+    /// punctuation) always win; ties go to the earliest card.
+    pub fn find_similar(&self, title: &str) -> Option<&Card> {
+        self.cards
+            .iter()
+            .filter(|c| !c.archived)
+            .map(|c| (c, title_similarity(&c.title, title)))
+            .filter(|(_, score)| *score >= SIMILARITY_THRESHOLD)
+            // Keep the earliest card on ties
+            .fold(None, |best: Option<(&Card, f64)>, (c, score)| match best {
+                Some((_, top)) if top >= score => best,
+                _ => Some((c, score)),
+            })
+            .map(|(c, _)| c)
+    }
+
+    /// The similar card `policy` should report for a new `title`; always
+    /// None when duplicates are allowed.
+    pub fn duplicate_of(&self, title: &str, policy: DuplicatePolicy) -> Option<&Card> {
+        if policy.is_allow() {
+            return None;
+        }
+        self.find_similar(title)
+    }
+
+    pub fn label_def(&self, name: &str) -> Option<&LabelDef> {
+        self.labels.iter().find(|l| l.name == name)
+    }
+
+    /// Check card labels against the registry. Always passes unless the
+    /// board uses strict labels; otherwise returns the first unknown label.
+    pub fn check_labels<'a>(&self, labels: &'a [String]) -> std::result::Result<(), &'a str> {
+        if !self.strict_labels {
+            return Ok(());
+        }
+        match labels.iter().find(|l| self.label_def(l).is_none()) {
+            Some(unknown) => Err(unknown),
+            None => Ok(()),
+        }
+    }
+
+    /// Rename a label in the registry and on every card. If `new` already
+    /// exists the two are merged: cards carrying both keep only `new`, and
+    /// the registry keeps `new`'s definition. Returns how many cards changed.
+    pub fn rename_label(&mut self, old: &str, new: &str) -> usize {
+        if old == new {
+            return 0;
+        }
+        if self.label_def(new).is_some() {
+            self.labels.retain(|l| l.name != old);
+        } else if let Some(def) = self.labels.iter_mut().find(|l| l.name == old) {
+            def.name = new.into();
+        }
+        for column in &mut self.columns {
+            if column.auto_labels.iter().any(|l| l == new) {
+                column.auto_labels.retain(|l| l != old);
+            } else if let Some(label) = column.auto_labels.iter_mut().find(|l| *l == old) {
+                *label = new.into();
+            }
+        }
+        let mut changed = 0;
+        for card in &mut self.cards {
+            let Some(pos) = card.labels.iter().position(|l| l == old) else {
+                continue;
+            };
+            if card.labels.iter().any(|l| l == new) {
+                card.labels.remove(pos);
+            } else {
+                card.labels[pos] = new.into();
+            }
+            card.updated_at = Utc::now();
+            changed += 1;
+        }
+        changed
+    }
+
+    /// Resolve an ID string: a ULID, a short ID like `KUK-42`, or a 1-based
+    /// number.
     pub fn resolve_card_id(&self, id_or_num: &str) -> Option<String> {
         if let Ok(num) = id_or_num.parse::<usize>() {
             self.find_card_by_number(num).map(|c| c.id.clone())
+        } else if let Some(seq) = parse_short_id(id_or_num) {
+            self.cards
+                .iter()
+                .find(|c| c.seq == Some(seq))
+                .map(|c| c.id.clone())
         } else {
             self.find_card(id_or_num).map(|c| c.id.clone())
         }
@@ -130,6 +504,33 @@ mod tests {
         assert_eq!(board.next_order("todo"), 0);
     }
 
+    #[test]
+    fn unarchive_returns_cards_to_the_bottom_of_their_column() {
+        let mut board = Board::default_board();
+        for title in ["Old", "Gone", "Stays"] {
+            let mut card = Card::new(title, "todo");
+            card.order = board.next_order("todo");
+            board.add_card(card);
+        }
+        let old = board.cards[0].id.clone();
+        let gone = board.cards[1].id.clone();
+        board.cards[0].archive();
+        board.cards[1].archive();
+        board.cards[1].column = "icebox".into();
+        assert_eq!(board.archived_cards()[0].title, "Gone");
+
+        assert_eq!(board.unarchive_card(&old).as_deref(), Some("todo"));
+        assert_eq!(board.unarchive_card(&old), None);
+        let titles: Vec<&str> = board
+            .column_cards("todo")
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Stays", "Old"]);
+        assert_eq!(board.unarchive_card(&gone).as_deref(), Some("todo"));
+        assert!(board.archived_cards().is_empty());
+    }
+
     #[test]
     fn find_card_by_id() {
         let mut board = Board::default_board();
@@ -168,6 +569,160 @@ mod tests {
         assert!(board.resolve_card_id("99").is_none());
     }
 
+    #[test]
+    fn short_ids_are_stable_sequence_numbers() {
+        let mut board = Board::default_board();
+        let mut legacy = Card::new("Legacy", "todo");
+        legacy.created_at -= chrono::Duration::days(1);
+        let mut moved_in = Card::new("From another board", "todo");
+        moved_in.seq = Some(1);
+        let newer = Card::new("Newer legacy", "todo");
+        board.cards.extend([newer, legacy]);
+
+        assert!(board.number_cards());
+        assert!(!board.number_cards());
+        let seqs: Vec<Option<u64>> = board.cards.iter().map(|c| c.seq).collect();
+        assert_eq!(seqs, [Some(2), Some(1)]);
+
+        board.cards.push(moved_in);
+        board.number_cards();
+        assert_eq!(board.cards[2].seq, Some(3));
+        let id = board.add_card(Card::new("Fresh", "doing")).id.clone();
+        assert_eq!(board.last_seq, 4);
+
+        board.cards.retain(|c| c.title != "Legacy");
+        assert_eq!(board.resolve_card_id("kuk-4"), Some(id.clone()));
+        assert_eq!(board.resolve_card_id("KUK-4"), Some(id));
+        assert_eq!(board.cards[0].short_id().as_deref(), Some("KUK-2"));
+        assert!(board.resolve_card_id("KUK-1").is_none());
+        assert!(board.resolve_card_id("JIRA-4").is_none());
+    }
+
+    fn titles(board: &Board, column: &str) -> Vec<String> {
+        board
+            .column_cards(column)
+            .iter()
+            .map(|c| c.title.clone())
+            .collect()
+    }
+
+    #[test]
+    fn duplicate_orders_break_ties_by_created_at() {
+        let mut board = Board::default_board();
+        let mut older = Card::new("Older", "todo");
+        older.order = 3;
+        older.created_at -= chrono::Duration::hours(1);
+        let mut newer = Card::new("Newer", "todo");
+        newer.order = 3;
+        board.cards.push(newer);
+        board.cards.push(older);
+
+        assert_eq!(titles(&board, "todo"), vec!["Older", "Newer"]);
+        assert_eq!(board.normalize_orders(), 2);
+        let orders: Vec<u32> = board.column_cards("todo").iter().map(|c| c.order).collect();
+        assert_eq!(orders, vec![0, 1]);
+        assert_eq!(board.normalize_orders(), 0);
+    }
+
+    #[test]
+    fn demote_at_max_order_does_not_overflow() {
+        let mut board = Board::default_board();
+        let mut a = Card::new("A", "todo");
+        a.order = u32::MAX;
+        let b = Card::new("B", "todo");
+        let b_id = b.id.clone();
+        board.cards.push(a);
+        board.cards.push(b);
+
+        assert!(board.demote_card(&b_id));
+        assert_eq!(titles(&board, "todo"), vec!["A", "B"]);
+        assert_eq!(board.find_card(&b_id).unwrap().order, 1);
+    }
+
+    #[test]
+    fn hoist_and_move_keep_orders_dense() {
+        let mut board = Board::default_board();
+        for (i, title) in ["A", "B", "C"].iter().enumerate() {
+            let mut c = Card::new(*title, "todo");
+            c.order = i as u32 * 10;
+            board.cards.push(c);
+        }
+        let c_id = board.cards[2].id.clone();
+        let a_id = board.cards[0].id.clone();
+
+        assert!(board.hoist_card(&c_id));
+        assert_eq!(titles(&board, "todo"), vec!["C", "A", "B"]);
+
+        assert!(board.move_card(&a_id, "doing"));
+        let orders: Vec<u32> = board.column_cards("todo").iter().map(|c| c.order).collect();
+        assert_eq!(orders, vec![0, 1]);
+        assert_eq!(board.find_card(&a_id).unwrap().order, 0);
+        assert!(!board.move_card("missing", "doing"));
+    }
+
+    #[test]
+    fn snoozed_cards_leave_and_rejoin_their_column() {
+        let now = Utc::now();
+        let mut board = Board::default_board();
+        for title in ["A", "B"] {
+            let mut c = Card::new(title, "todo");
+            c.order = board.next_order("todo");
+            board.cards.push(c);
+        }
+        board.cards[1].snooze = Some(crate::model::Snooze {
+            until: now + chrono::Duration::days(1),
+            hoist: true,
+        });
+        assert_eq!(titles(&board, "todo"), vec!["A"]);
+        assert_eq!(board.snoozed_cards(now).len(), 1);
+
+        // Resurfaced with hoist: shown first, then settled by a renumber
+        board.cards[1].snooze.as_mut().unwrap().until = now - chrono::Duration::days(1);
+        assert_eq!(titles(&board, "todo"), vec!["B", "A"]);
+        board.normalize_column("todo");
+        assert_eq!(board.cards[1].snooze, None);
+        assert_eq!(titles(&board, "todo"), vec!["B", "A"]);
+    }
+
+    #[test]
+    fn moving_into_a_column_applies_its_rules() {
+        let mut board = Board::default_board();
+        board.columns[1].default_assignee = Some("qa-team".into());
+        board.columns[1].auto_labels = vec!["needs-qa".into(), "bug".into()];
+        let mut card = Card::new("A", "todo");
+        card.labels = vec!["bug".into()];
+        card.assignee = Some("alice".into());
+        let id = card.id.clone();
+        board.cards.push(card);
+
+        assert!(board.move_card(&id, "doing"));
+        let card = board.find_card(&id).unwrap();
+        assert_eq!(card.assignee.as_deref(), Some("qa-team"));
+        assert_eq!(card.labels, vec!["bug", "needs-qa"]);
+
+        // Moving within the column leaves later edits alone
+        board.find_card_mut(&id).unwrap().assignee = None;
+        assert!(board.move_card(&id, "doing"));
+        assert_eq!(board.find_card(&id).unwrap().assignee, None);
+    }
+
+    #[test]
+    fn find_similar_ignores_archived_and_distant_titles() {
+        let mut board = Board::default_board();
+        let mut archived = Card::new("Fix login bug", "todo");
+        archived.archived = true;
+        board.cards.push(archived);
+        assert!(board.find_similar("fix login bug").is_none());
+
+        board.cards.push(Card::new("Fix login bug", "doing"));
+        board.cards.push(Card::new("Write release notes", "todo"));
+        assert_eq!(
+            board.find_similar("fix login bugs").unwrap().column,
+            "doing"
+        );
+        assert!(board.find_similar("Refactor storage").is_none());
+    }
+
     #[test]
     fn board_roundtrip_json() {
         let mut board = Board::default_board();
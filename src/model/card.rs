@@ -1,11 +1,19 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use ulid::Ulid;
 
+use super::{Attachment, ChecklistItem, Comment, merge_checklist};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Card {
     pub id: String,
+    /// Sequence number on its board, shown as the short ID `KUK-<seq>`.
+    /// Unlike positional numbers it never changes once given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
     pub title: String,
     pub column: String,
     pub order: u32,
@@ -17,12 +25,100 @@ pub struct Card {
     pub labels: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub due: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
     #[serde(default)]
     pub archived: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snooze: Option<Snooze>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    /// This card is an epic that other cards roll up into.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub epic: bool,
+    /// ID of the epic this card belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Swimlane for boards split by [`Swimlanes::Lane`](super::Swimlanes::Lane).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lane: Option<String>,
+}
+
+/// Prefix of card short IDs, as in `KUK-42`.
+pub const SHORT_ID_PREFIX: &str = "KUK";
+
+/// The sequence number in a short ID like `KUK-42`, ignoring case.
+pub fn parse_short_id(s: &str) -> Option<u64> {
+    let (prefix, seq) = s.split_once('-')?;
+    if !prefix.eq_ignore_ascii_case(SHORT_ID_PREFIX) {
+        return None;
+    }
+    seq.parse().ok()
+}
+
+/// How pressing a card is, least to most. Cards without one sort below
+/// `Low`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            "urgent" => Ok(Priority::Urgent),
+            _ => Err(format!(
+                "Invalid priority: {s}. Use 'low', 'medium', 'high', or 'urgent'."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Priority::Low => write!(f, "low"),
+            Priority::Medium => write!(f, "medium"),
+            Priority::High => write!(f, "high"),
+            Priority::Urgent => write!(f, "urgent"),
+        }
+    }
+}
+
+/// Cards due within this many days are flagged as due soon.
+pub const DUE_SOON_DAYS: i64 = 3;
+
+/// Where a card stands against its due date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueStatus {
+    Overdue,
+    /// Due within [`DUE_SOON_DAYS`].
+    Soon,
+    Later,
+}
+
+/// Keeps a card out of board views until `until`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Snooze {
+    pub until: DateTime<Utc>,
+    /// Show the card at the top of its column once it resurfaces.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hoist: bool,
 }
 
 impl Card {
@@ -30,6 +126,7 @@ impl Card {
         let now = Utc::now();
         Self {
             id: Ulid::new().to_string(),
+            seq: None,
             title: title.into(),
             column: column.into(),
             order: 0,
@@ -37,12 +134,68 @@ impl Card {
             assignee: None,
             labels: Vec::new(),
             due: None,
+            priority: None,
             created_at: now,
             updated_at: now,
             metadata: HashMap::new(),
             archived: false,
+            snooze: None,
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            epic: false,
+            parent: None,
+            lane: None,
         }
     }
+
+    /// The card's short ID, like `KUK-42`, once it has a sequence number.
+    pub fn short_id(&self) -> Option<String> {
+        self.seq.map(|seq| format!("{SHORT_ID_PREFIX}-{seq}"))
+    }
+
+    /// Where the card stands against its due date at `now`, if it has one.
+    pub fn due_status(&self, now: DateTime<Utc>) -> Option<DueStatus> {
+        let due = self.due?;
+        Some(if due < now {
+            DueStatus::Overdue
+        } else if due - now <= chrono::Duration::days(DUE_SOON_DAYS) {
+            DueStatus::Soon
+        } else {
+            DueStatus::Later
+        })
+    }
+
+    /// Whether the card is hidden from board views at `now`.
+    pub fn is_snoozed(&self, now: DateTime<Utc>) -> bool {
+        self.snooze.is_some_and(|s| s.until > now)
+    }
+
+    /// Whether the card has resurfaced from a snooze that asked to be
+    /// hoisted, and hasn't been moved since.
+    pub fn resurfaced_on_top(&self, now: DateTime<Utc>) -> bool {
+        self.snooze.is_some_and(|s| s.hoist && s.until <= now)
+    }
+
+    /// A fresh card in `column` with this one's title, description and
+    /// labels, and its checklist unticked. It gets a new ID and timestamps;
+    /// everything else, history included, stays with the original.
+    pub fn duplicate(&self, column: impl Into<String>) -> Card {
+        let mut card = Card::new(self.title.clone(), column);
+        card.labels = self.labels.clone();
+        card.description = self.description.as_deref().map(|description| {
+            let unticked: Vec<ChecklistItem> = self
+                .checklist()
+                .into_iter()
+                .map(|item| ChecklistItem {
+                    done: false,
+                    ..item
+                })
+                .collect();
+            merge_checklist(description, &unticked, false)
+                .unwrap_or_else(|| description.to_string())
+        });
+        card
+    }
 }
 
 #[cfg(test)]
@@ -107,6 +260,72 @@ mod tests {
         assert!(!card.archived);
     }
 
+    #[test]
+    fn snooze_hides_until_the_date() {
+        let now = Utc::now();
+        let mut card = Card::new("Waiting on vendor", "todo");
+        assert!(!card.is_snoozed(now));
+        card.snooze = Some(Snooze {
+            until: now + chrono::Duration::days(1),
+            hoist: true,
+        });
+        assert!(card.is_snoozed(now));
+        assert!(!card.resurfaced_on_top(now));
+        let later = now + chrono::Duration::days(2);
+        assert!(!card.is_snoozed(later));
+        assert!(card.resurfaced_on_top(later));
+    }
+
+    #[test]
+    fn due_status_by_distance() {
+        let now = Utc::now();
+        let mut card = Card::new("Ship", "todo");
+        assert_eq!(card.due_status(now), None);
+        card.due = Some(now - chrono::Duration::hours(1));
+        assert_eq!(card.due_status(now), Some(DueStatus::Overdue));
+        card.due = Some(now + chrono::Duration::days(2));
+        assert_eq!(card.due_status(now), Some(DueStatus::Soon));
+        card.due = Some(now + chrono::Duration::days(10));
+        assert_eq!(card.due_status(now), Some(DueStatus::Later));
+    }
+
+    #[test]
+    fn priority_parses_and_orders() {
+        assert_eq!("High".parse::<Priority>(), Ok(Priority::High));
+        assert!("someday".parse::<Priority>().is_err());
+        assert!(None < Some(Priority::Low) && Priority::High < Priority::Urgent);
+        assert_eq!(Priority::Medium.to_string(), "medium");
+
+        let mut card = Card::new("Page on-call", "todo");
+        card.priority = Some(Priority::Urgent);
+        let json = serde_json::to_value(&card).unwrap();
+        assert_eq!(json["priority"], "urgent");
+    }
+
+    #[test]
+    fn duplicates_start_fresh() {
+        let mut card = Card::new("Release", "done");
+        card.description = Some("Steps:\n- [x] Tag\n- [ ] Publish".into());
+        card.labels = vec!["ops".into()];
+        card.assignee = Some("ana".into());
+        card.seq = Some(4);
+        card.created_at -= chrono::Duration::days(3);
+
+        let copy = card.duplicate("todo");
+        assert_ne!(copy.id, card.id);
+        assert_eq!(
+            (copy.title.as_str(), copy.column.as_str()),
+            ("Release", "todo")
+        );
+        assert_eq!(copy.labels, ["ops"]);
+        assert_eq!(
+            copy.description.as_deref(),
+            Some("Steps:\n- [ ] Tag\n- [ ] Publish")
+        );
+        assert!(copy.created_at > card.created_at);
+        assert_eq!((copy.seq, copy.assignee), (None, None));
+    }
+
     #[test]
     fn unique_ids() {
         let c1 = Card::new("A", "todo");
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use super::Card;
+
+/// One `- [ ] item` line of a Markdown task list, the form card templates
+/// write their checklist in.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub done: bool,
+}
+
+/// Split a task list line into what comes before the box, the box state and
+/// the item text.
+pub(super) fn parse_line(line: &str) -> Option<(&str, bool, &str)> {
+    let body = line.trim_start();
+    let indent = &line[..line.len() - body.len()];
+    let rest = ["- [", "* [", "+ ["]
+        .iter()
+        .find_map(|bullet| body.strip_prefix(bullet))?;
+    let mut chars = rest.chars();
+    let done = match chars.next()? {
+        ' ' => false,
+        'x' | 'X' => true,
+        _ => return None,
+    };
+    let text = chars.as_str().strip_prefix(']')?;
+    if !(text.is_empty() || text.starts_with(' ')) {
+        return None;
+    }
+    Some((&line[..indent.len() + 2], done, text.trim()))
+}
+
+/// The task list items in a Markdown text, in order.
+pub fn parse_checklist(markdown: &str) -> Vec<ChecklistItem> {
+    markdown
+        .lines()
+        .filter_map(parse_line)
+        .map(|(_, done, text)| ChecklistItem {
+            text: text.into(),
+            done,
+        })
+        .collect()
+}
+
+/// Give each task in `markdown` the state of the item in `items` with the
+/// same text. With `append_missing`, items the text lacks are added as a
+/// task list at the end. Returns the new text, or `None` when nothing
+/// changed.
+pub fn merge_checklist(
+    markdown: &str,
+    items: &[ChecklistItem],
+    append_missing: bool,
+) -> Option<String> {
+    let mut changed = false;
+    let mut seen = Vec::new();
+    let mut lines: Vec<String> = markdown
+        .lines()
+        .map(|line| {
+            let Some((bullet, done, text)) = parse_line(line) else {
+                return line.to_string();
+            };
+            seen.push(text.to_string());
+            match items.iter().find(|i| i.text == text) {
+                Some(item) if item.done != done => {
+                    changed = true;
+                    let mark = if item.done { 'x' } else { ' ' };
+                    format!("{bullet}[{mark}] {text}")
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect();
+
+    if append_missing {
+        let missing: Vec<&ChecklistItem> =
+            items.iter().filter(|i| !seen.contains(&i.text)).collect();
+        if !missing.is_empty() {
+            changed = true;
+            if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            for item in missing {
+                let mark = if item.done { 'x' } else { ' ' };
+                lines.push(format!("- [{mark}] {}", item.text));
+            }
+        }
+    }
+    changed.then(|| lines.join("\n"))
+}
+
+impl Card {
+    /// The task list in the card's description.
+    pub fn checklist(&self) -> Vec<ChecklistItem> {
+        self.description
+            .as_deref()
+            .map(parse_checklist)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = "Steps:\n\n- [ ] Reproduce\n  * [x] Add a test\n- [link](x)\n";
+
+    #[test]
+    fn parses_task_lists() {
+        let items = parse_checklist(DESCRIPTION);
+        assert_eq!(
+            items,
+            [
+                ChecklistItem {
+                    text: "Reproduce".into(),
+                    done: false
+                },
+                ChecklistItem {
+                    text: "Add a test".into(),
+                    done: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_sets_states_and_appends() {
+        let items = parse_checklist("- [x] Reproduce\n- [x] Add a test\n- [ ] Release");
+        let merged = merge_checklist(DESCRIPTION, &items, false).unwrap();
+        assert!(merged.contains("- [x] Reproduce\n  * [x] Add a test\n- [link](x)"));
+        assert!(!merged.contains("Release"));
+
+        let merged = merge_checklist(&merged, &items, true).unwrap();
+        assert!(merged.ends_with("- [link](x)\n\n- [ ] Release"));
+        assert_eq!(merge_checklist(&merged, &items, true), None);
+    }
+}
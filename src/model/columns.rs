@@ -0,0 +1,260 @@
+use chrono::Utc;
+
+use super::{Board, Card, Column, Transition, cmp_position};
+
+impl Board {
+    /// Insert `column` at 0-based `index`, or after the last column.
+    pub fn add_column(&mut self, column: Column, index: Option<usize>) -> Result<(), String> {
+        let name = column.name.trim();
+        if name.is_empty() {
+            return Err("Column name cannot be empty".into());
+        }
+        if self.has_column(name) {
+            return Err(format!("Column already exists: {name}"));
+        }
+        let column = Column {
+            name: name.to_string(),
+            ..column
+        };
+        let index = index.unwrap_or(self.columns.len()).min(self.columns.len());
+        self.columns.insert(index, column);
+        Ok(())
+    }
+
+    /// Rename a column and point its cards, archived ones included, at the
+    /// new name. Transition history keeps the old name. Returns how many
+    /// cards were updated.
+    pub fn rename_column(&mut self, old: &str, new: &str) -> Result<usize, String> {
+        let new = new.trim();
+        if new.is_empty() {
+            return Err("Column name cannot be empty".into());
+        }
+        if old != new && self.has_column(new) {
+            return Err(format!("Column already exists: {new}"));
+        }
+        let column = self
+            .columns
+            .iter_mut()
+            .find(|c| c.name == old)
+            .ok_or_else(|| format!("Column not found: {old}"))?;
+        column.name = new.to_string();
+
+        let mut updated = 0;
+        for card in self.cards.iter_mut().filter(|c| c.column == old) {
+            card.column = new.to_string();
+            updated += 1;
+        }
+        Ok(updated)
+    }
+
+    /// Move a column to 0-based `index`, clamped to the last position.
+    pub fn move_column(&mut self, name: &str, index: usize) -> Result<(), String> {
+        let from = self
+            .columns
+            .iter()
+            .position(|c| c.name == name)
+            .ok_or_else(|| format!("Column not found: {name}"))?;
+        let column = self.columns.remove(from);
+        let index = index.min(self.columns.len());
+        self.columns.insert(index, column);
+        Ok(())
+    }
+
+    /// Delete a column. Its cards go to the bottom of `to`, keeping their
+    /// relative order; active ones get a recorded transition, but `to`'s
+    /// column rules are not applied. A column with cards needs a `to`.
+    /// Returns how many cards were re-homed.
+    pub fn remove_column(&mut self, name: &str, to: Option<&str>) -> Result<usize, String> {
+        let index = self
+            .columns
+            .iter()
+            .position(|c| c.name == name)
+            .ok_or_else(|| format!("Column not found: {name}"))?;
+        if self.columns.len() == 1 {
+            return Err(format!(
+                "Cannot delete {name}: a board needs at least one column"
+            ));
+        }
+
+        let mut cards: Vec<&Card> = self.cards.iter().filter(|c| c.column == name).collect();
+        cards.sort_by(|a, b| cmp_position(a, b));
+        let ids: Vec<String> = cards.iter().map(|c| c.id.clone()).collect();
+
+        if !ids.is_empty() {
+            let to = match to {
+                None => {
+                    return Err(format!(
+                        "{name} holds {} card(s); pass a column to move them to",
+                        ids.len()
+                    ));
+                }
+                Some(to) if to == name => {
+                    return Err(format!("Cannot move cards from {name} into itself"));
+                }
+                Some(to) if !self.has_column(to) => {
+                    return Err(format!("Column not found: {to}"));
+                }
+                Some(to) => to.to_string(),
+            };
+            let now = Utc::now();
+            for id in &ids {
+                let order = self.next_order(&to);
+                let card = self.find_card_mut(id).expect("card listed above");
+                card.column = to.clone();
+                if !card.archived {
+                    card.order = order;
+                    card.updated_at = now;
+                    Transition::record(card, name, now);
+                }
+            }
+            self.normalize_column(&to);
+        }
+
+        self.columns.remove(index);
+        Ok(ids.len())
+    }
+
+    /// Take over every card of `other`, archived ones included, as when its
+    /// board is deleted. Cards keep their column if this board has one by
+    /// that name and go to the first column otherwise, with a transition
+    /// when active. They are numbered anew and placed at the bottom in their
+    /// old order. Returns how many cards came over.
+    pub fn absorb(&mut self, other: Board) -> Result<usize, String> {
+        let first = match self.columns.first() {
+            Some(c) => c.name.clone(),
+            None => return Err(format!("Board {} has no columns", self.name)),
+        };
+        let mut cards = other.cards;
+        cards.sort_by(cmp_position);
+        let count = cards.len();
+        let now = Utc::now();
+        for mut card in cards {
+            if !self.has_column(&card.column) {
+                let from = std::mem::replace(&mut card.column, first.clone());
+                if !card.archived {
+                    card.updated_at = now;
+                    Transition::record(&mut card, &from, now);
+                }
+            }
+            card.order = self.next_order(&card.column);
+            self.add_card(card);
+        }
+        Ok(count)
+    }
+
+    /// The WIP limit of `column` when putting one more card in it would go
+    /// over the limit. `card_id` is the card being moved, if any; a card
+    /// already in the column never counts as one more.
+    pub fn wip_exceeded(&self, column: &str, card_id: Option<&str>) -> Option<u32> {
+        let limit = self.columns.iter().find(|c| c.name == column)?.wip_limit?;
+        if card_id
+            .and_then(|id| self.find_card(id))
+            .is_some_and(|c| c.column == column)
+        {
+            return None;
+        }
+        (self.column_cards(column).len() >= limit as usize).then_some(limit)
+    }
+
+    /// Set a column's WIP limit; `None` removes it.
+    pub fn set_wip_limit(&mut self, name: &str, limit: Option<u32>) -> Result<(), String> {
+        let column = self
+            .columns
+            .iter_mut()
+            .find(|c| c.name == name)
+            .ok_or_else(|| format!("Column not found: {name}"))?;
+        column.wip_limit = limit;
+        Ok(())
+    }
+
+    /// Mark a column as a queue, or as ordinary work again.
+    pub fn set_queue(&mut self, name: &str, queue: bool) -> Result<(), String> {
+        let column = self
+            .columns
+            .iter_mut()
+            .find(|c| c.name == name)
+            .ok_or_else(|| format!("Column not found: {name}"))?;
+        column.queue = queue;
+        Ok(())
+    }
+
+    /// Whether `column` is a queue; see [`Column::queue`].
+    pub fn is_queue(&self, column: &str) -> bool {
+        self.columns.iter().any(|c| c.name == column && c.queue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deleting_a_column_rehomes_its_cards() {
+        let mut board = Board::default_board();
+        board.add_card(Card::new("Existing", "done"));
+        let mut review = Column::new(" review ");
+        review.wip_limit = Some(2);
+        board.add_column(review, Some(2)).unwrap();
+        assert!(board.add_column(Column::new("review"), None).is_err());
+        let names: Vec<&str> = board.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["todo", "doing", "review", "done"]);
+
+        let mut first = Card::new("First", "review");
+        first.order = 0;
+        let first = board.add_card(first).id.clone();
+        let mut archived = Card::new("Old", "review");
+        archived.archived = true;
+        let archived = board.add_card(archived).id.clone();
+
+        assert_eq!(board.rename_column("review", "qa"), Ok(2));
+        assert!(board.rename_column("qa", "done").is_err());
+        assert!(board.remove_column("qa", None).is_err());
+        assert!(board.remove_column("qa", Some("qa")).is_err());
+
+        assert_eq!(board.remove_column("qa", Some("done")), Ok(2));
+        assert!(!board.has_column("qa"));
+        let done: Vec<&str> = board
+            .column_cards("done")
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(done, ["Existing", "First"]);
+        let history = Transition::history(board.find_card(&first).unwrap());
+        assert_eq!(
+            (history[0].from.as_str(), history[0].to.as_str()),
+            ("qa", "done")
+        );
+        let archived = board.find_card(&archived).unwrap();
+        assert_eq!(archived.column, "done");
+        assert!(Transition::history(archived).is_empty());
+
+        assert_eq!(board.wip_exceeded("todo", None), None);
+        board.set_wip_limit("done", Some(2)).unwrap();
+        assert_eq!(board.wip_exceeded("done", None), Some(2));
+        assert_eq!(board.wip_exceeded("done", Some(&first)), None);
+
+        let mut other = Board::default_board();
+        other.add_card(Card::new("Migrated", "qa"));
+        other.add_card(Card::new("Kept", "todo"));
+        let seq = board.last_seq;
+        assert_eq!(board.absorb(other), Ok(2));
+        let migrated = board.cards.iter().find(|c| c.title == "Migrated").unwrap();
+        assert_eq!(
+            (migrated.column.as_str(), migrated.seq),
+            ("todo", Some(seq + 1))
+        );
+        assert_eq!(Transition::history(migrated)[0].from, "qa");
+
+        board.move_column("done", 0).unwrap();
+        assert_eq!(board.columns[0].name, "done");
+        board.set_wip_limit("todo", None).unwrap();
+        assert!(board.set_wip_limit("nope", Some(1)).is_err());
+
+        board.set_queue("doing", true).unwrap();
+        assert!(board.is_queue("doing"));
+        assert!(!board.is_queue("todo"));
+        board.set_queue("doing", false).unwrap();
+        assert!(!board.is_queue("doing"));
+        assert!(board.set_queue("nope", true).is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Card;
+
+/// A note left on a card, by a person or an agent reporting progress.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Comment {
+    pub author: String,
+    pub at: DateTime<Utc>,
+    pub body: String,
+}
+
+impl Card {
+    /// Append a comment stamped with the current time.
+    pub fn add_comment(&mut self, author: impl Into<String>, body: impl Into<String>) -> &Comment {
+        let now = Utc::now();
+        self.comments.push(Comment {
+            author: author.into(),
+            at: now,
+            body: body.into(),
+        });
+        self.updated_at = now;
+        self.comments.last().expect("just pushed")
+    }
+}
@@ -1,21 +1,254 @@
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use super::{AgingRule, Board, Card, SCHEMA_VERSION};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RepoConfig {
+    /// Format of this file, as for boards; `version` is the kuk that
+    /// created the repo.
+    #[serde(default)]
+    pub schema_version: u32,
     pub version: String,
     #[serde(default = "default_board")]
     pub default_board: String,
+    /// What to do when a new card's title matches an existing active card.
+    #[serde(default, skip_serializing_if = "DuplicatePolicy::is_allow")]
+    pub duplicates: DuplicatePolicy,
+    /// Column that `kuk inbox` and `POST /v1/inbox` file new items into.
+    #[serde(
+        default = "default_inbox_column",
+        skip_serializing_if = "is_default_inbox_column"
+    )]
+    pub inbox_column: String,
+    /// Colors used by `kuk tui`.
+    #[serde(default, skip_serializing_if = "ColorScheme::is_default")]
+    pub color_scheme: ColorScheme,
+    /// Rules `kuk stale --escalate` uses to bump forgotten cards.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aging: Vec<AgingRule>,
+    /// Column, labels and assignee for new cards that don't give their own.
+    #[serde(default, skip_serializing_if = "CardDefaults::is_empty")]
+    pub new_cards: CardDefaults,
+    /// How new boards are stored; `kuk board layout` converts existing ones.
+    #[serde(default, skip_serializing_if = "StorageLayout::is_file")]
+    pub layout: StorageLayout,
+    /// Commit `.kuk` to git after every command that changes it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_commit: bool,
+    /// Count command runs and their durations in `.kuk/usage.json`, for
+    /// `kuk usage`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub usage_log: bool,
+}
+
+/// What a card added with `kuk add`, the TUI, the REST API or the MCP tools
+/// gets when the request leaves it out.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CardDefaults {
+    /// Column to add to, on boards that have it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// Labels every new card gets, before any it is given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Assignee for cards added without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+}
+
+impl CardDefaults {
+    pub fn is_empty(&self) -> bool {
+        *self == CardDefaults::default()
+    }
+
+    /// The column a new card goes to on `board` when it names none: the
+    /// default column if the board has it, else the board's first.
+    pub fn column(&self, board: &Board) -> Option<String> {
+        self.column
+            .clone()
+            .filter(|c| board.has_column(c))
+            .or_else(|| board.columns.first().map(|c| c.name.clone()))
+    }
+
+    /// Give `card` the default labels ahead of its own, and the default
+    /// assignee if it has none.
+    pub fn apply(&self, card: &mut Card) {
+        let mut labels = self.labels.clone();
+        for label in std::mem::take(&mut card.labels) {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+        card.labels = labels;
+        if card.assignee.is_none() {
+            card.assignee = self.assignee.clone();
+        }
+    }
+}
+
+/// Handling of new cards whose title closely matches an existing active card.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    #[default]
+    Allow,
+    /// Add the card but report the match.
+    Warn,
+    /// Refuse to add the card unless forced.
+    Reject,
+}
+
+impl DuplicatePolicy {
+    pub fn is_allow(&self) -> bool {
+        *self == DuplicatePolicy::Allow
+    }
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "allow" => Ok(DuplicatePolicy::Allow),
+            "warn" => Ok(DuplicatePolicy::Warn),
+            "reject" => Ok(DuplicatePolicy::Reject),
+            _ => Err(format!(
+                "Invalid duplicate policy: {s}. Use 'allow', 'warn', or 'reject'."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DuplicatePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuplicatePolicy::Allow => write!(f, "allow"),
+            DuplicatePolicy::Warn => write!(f, "warn"),
+            DuplicatePolicy::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+/// How a board is laid out under `.kuk/`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageLayout {
+    /// The whole board in one `<board>.json`.
+    #[default]
+    File,
+    /// A `<board>/` directory with the columns and labels in `board.json`
+    /// and each card in its own file under `cards/`, so branches that touch
+    /// different cards merge without conflicts.
+    Cards,
+    /// A row per board and per card in the `.kuk/kuk.db` SQLite database,
+    /// for boards with thousands of cards. Needs the `sqlite` feature.
+    Sqlite,
+}
+
+impl StorageLayout {
+    pub fn is_file(&self) -> bool {
+        *self == StorageLayout::File
+    }
+}
+
+impl FromStr for StorageLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "file" => Ok(StorageLayout::File),
+            "cards" => Ok(StorageLayout::Cards),
+            "sqlite" => Ok(StorageLayout::Sqlite),
+            _ => Err(format!(
+                "Invalid storage layout: {s}. Use 'file', 'cards' or 'sqlite'."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for StorageLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageLayout::File => write!(f, "file"),
+            StorageLayout::Cards => write!(f, "cards"),
+            StorageLayout::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+/// TUI palette.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorScheme {
+    /// Cyan accents for dark terminals.
+    #[default]
+    Default,
+    /// Blue accents and dark text for light terminals.
+    Light,
+    /// No colors, only bold and reverse video.
+    Mono,
+}
+
+impl ColorScheme {
+    pub fn is_default(&self) -> bool {
+        *self == ColorScheme::Default
+    }
+}
+
+impl FromStr for ColorScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(ColorScheme::Default),
+            "light" => Ok(ColorScheme::Light),
+            "mono" => Ok(ColorScheme::Mono),
+            _ => Err(format!(
+                "Invalid color scheme: {s}. Use 'default', 'light', or 'mono'."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorScheme::Default => write!(f, "default"),
+            ColorScheme::Light => write!(f, "light"),
+            ColorScheme::Mono => write!(f, "mono"),
+        }
+    }
 }
 
 fn default_board() -> String {
     "default".into()
 }
 
+fn default_inbox_column() -> String {
+    "inbox".into()
+}
+
+fn is_default_inbox_column(column: &String) -> bool {
+    *column == default_inbox_column()
+}
+
 impl Default for RepoConfig {
     fn default() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             version: "0.1.0".into(),
             default_board: "default".into(),
+            duplicates: DuplicatePolicy::Allow,
+            inbox_column: default_inbox_column(),
+            color_scheme: ColorScheme::Default,
+            aging: Vec::new(),
+            new_cards: CardDefaults::default(),
+            layout: StorageLayout::File,
+            auto_commit: false,
+            usage_log: false,
         }
     }
 }
@@ -23,6 +256,7 @@ impl Default for RepoConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::Column;
 
     #[test]
     fn default_config() {
@@ -45,4 +279,46 @@ mod tests {
         let config: RepoConfig = serde_json::from_str(json).unwrap();
         assert_eq!(config.default_board, "default");
     }
+
+    #[test]
+    fn duplicate_policy_defaults_to_allow_and_is_omitted() {
+        let config: RepoConfig = serde_json::from_str(r#"{"version": "0.1.0"}"#).unwrap();
+        assert_eq!(config.duplicates, DuplicatePolicy::Allow);
+        assert!(
+            !serde_json::to_string(&config)
+                .unwrap()
+                .contains("duplicates")
+        );
+
+        let json = r#"{"version": "0.1.0", "duplicates": "reject"}"#;
+        let config: RepoConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.duplicates, DuplicatePolicy::Reject);
+        assert!("nope".parse::<DuplicatePolicy>().is_err());
+    }
+
+    #[test]
+    fn card_defaults_fill_in_what_a_card_leaves_out() {
+        let json = r#"{"version": "0.1.0", "new_cards": {"column": "inbox", "labels": ["triage"], "assignee": "sam"}}"#;
+        let config: RepoConfig = serde_json::from_str(json).unwrap();
+        let defaults = &config.new_cards;
+
+        let board = Board::default_board();
+        assert_eq!(defaults.column(&board).as_deref(), Some("todo"));
+        let mut board = board;
+        board.add_column(Column::new("inbox"), Some(0)).unwrap();
+        assert_eq!(defaults.column(&board).as_deref(), Some("inbox"));
+
+        let mut card = Card::new("Bug", "inbox");
+        card.labels = vec!["bug".into(), "triage".into()];
+        card.assignee = Some("ana".into());
+        defaults.apply(&mut card);
+        assert_eq!(card.labels, ["triage", "bug"]);
+        assert_eq!(card.assignee.as_deref(), Some("ana"));
+
+        assert!(
+            !serde_json::to_string(&RepoConfig::default())
+                .unwrap()
+                .contains("new_cards")
+        );
+    }
 }
@@ -0,0 +1,128 @@
+use chrono::Utc;
+use serde::Serialize;
+
+use super::{Board, Card, cmp_position};
+
+/// How many of an epic's cards are finished.
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
+pub struct EpicProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl EpicProgress {
+    /// Share of cards finished, 0–100. An empty epic is at 0.
+    pub fn percent(&self) -> u32 {
+        if self.total == 0 {
+            return 0;
+        }
+        (self.done * 100 / self.total) as u32
+    }
+}
+
+impl Board {
+    /// Active epics in board order: by column, then position.
+    pub fn epics(&self) -> Vec<&Card> {
+        self.columns
+            .iter()
+            .flat_map(|col| self.column_cards(&col.name))
+            .filter(|c| c.epic)
+            .collect()
+    }
+
+    /// Every card under `epic_id`, archived ones included, in board order.
+    pub fn children(&self, epic_id: &str) -> Vec<&Card> {
+        let column_index = |c: &Card| self.columns.iter().position(|col| col.name == c.column);
+        let mut children: Vec<&Card> = self
+            .cards
+            .iter()
+            .filter(|c| c.parent.as_deref() == Some(epic_id))
+            .collect();
+        children.sort_by(|a, b| {
+            column_index(a)
+                .cmp(&column_index(b))
+                .then(cmp_position(a, b))
+        });
+        children
+    }
+
+    /// Whether a card counts as finished for its epic: it is in the last
+    /// column or archived.
+    pub fn is_finished(&self, card: &Card) -> bool {
+        card.archived || self.columns.last().is_some_and(|c| c.name == card.column)
+    }
+
+    pub fn epic_progress(&self, epic_id: &str) -> EpicProgress {
+        let children = self.children(epic_id);
+        EpicProgress {
+            done: children.iter().filter(|c| self.is_finished(c)).count(),
+            total: children.len(),
+        }
+    }
+
+    /// Put a card under an epic, or take it out with `None`. Epics don't
+    /// nest, so the parent must be an epic and the card must not be one.
+    /// Returns whether the parent changed.
+    pub fn set_parent(&mut self, card_id: &str, epic_id: Option<&str>) -> Result<bool, String> {
+        if let Some(epic_id) = epic_id {
+            let epic = self
+                .find_card(epic_id)
+                .ok_or_else(|| format!("Card not found: {epic_id}"))?;
+            if !epic.epic {
+                return Err(format!("Not an epic: {}", epic.title));
+            }
+        }
+        let card = self
+            .find_card_mut(card_id)
+            .ok_or_else(|| format!("Card not found: {card_id}"))?;
+        if card.epic {
+            return Err(format!("Epics can't be put under an epic: {}", card.title));
+        }
+        if card.parent.as_deref() == epic_id {
+            return Ok(false);
+        }
+        card.parent = epic_id.map(String::from);
+        card.updated_at = Utc::now();
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_counts_finished_children() {
+        let mut board = Board::default_board();
+        let mut epic = Card::new("Auth overhaul", "todo");
+        epic.epic = true;
+        let epic_id = board.add_card(epic).id.clone();
+        let ids: Vec<String> = [("Login", "done"), ("Logout", "doing"), ("SSO", "todo")]
+            .into_iter()
+            .map(|(title, column)| board.add_card(Card::new(title, column)).id.clone())
+            .collect();
+
+        for id in &ids {
+            assert_eq!(board.set_parent(id, Some(&epic_id)), Ok(true));
+        }
+        assert_eq!(board.set_parent(&ids[0], Some(&epic_id)), Ok(false));
+        assert!(board.set_parent(&ids[0], Some(&ids[1])).is_err());
+        assert!(board.set_parent(&epic_id, Some(&epic_id)).is_err());
+
+        let titles: Vec<&str> = board
+            .children(&epic_id)
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, ["SSO", "Logout", "Login"]);
+        assert_eq!(board.epics().len(), 1);
+
+        board.find_card_mut(&ids[1]).unwrap().archive();
+        let progress = board.epic_progress(&epic_id);
+        assert_eq!(progress, EpicProgress { done: 2, total: 3 });
+        assert_eq!(progress.percent(), 66);
+
+        assert_eq!(board.set_parent(&ids[2], None), Ok(true));
+        assert_eq!(board.epic_progress(&epic_id).total, 2);
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Card, Origin, Transition};
+
+/// Card metadata key holding the [`Event`]s kuk records on a card besides
+/// moves, which live under `metadata.transitions`.
+pub const EVENTS_KEY: &str = "events";
+
+/// What happened to a card.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum EventKind {
+    Created { column: String },
+    Moved { from: String, to: String },
+    Labeled { label: String },
+    Unlabeled { label: String },
+    Assigned { assignee: String },
+    Unassigned,
+    Archived,
+    Unarchived,
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventKind::Created { column } => write!(f, "created in {column}"),
+            EventKind::Moved { from, to } => write!(f, "moved {from} → {to}"),
+            EventKind::Labeled { label } => write!(f, "labeled {label}"),
+            EventKind::Unlabeled { label } => write!(f, "unlabeled {label}"),
+            EventKind::Assigned { assignee } => write!(f, "assigned to @{assignee}"),
+            EventKind::Unassigned => write!(f, "unassigned"),
+            EventKind::Archived => write!(f, "archived"),
+            EventKind::Unarchived => write!(f, "unarchived"),
+        }
+    }
+}
+
+/// One entry in a card's activity history.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Event {
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: EventKind,
+    /// Where the change came from, for changes kuk recorded it for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<Origin>,
+}
+
+impl Event {
+    /// The card's whole history, oldest first: its creation, every move
+    /// from `metadata.transitions`, and the events recorded under
+    /// `metadata.events`. Changes made before kuk kept history are missing.
+    pub fn history(card: &Card) -> Vec<Self> {
+        let moves = Transition::history(card);
+        let first_column = moves.first().map_or(&card.column, |t| &t.from);
+        let mut events = vec![Event {
+            at: card.created_at,
+            kind: EventKind::Created {
+                column: first_column.clone(),
+            },
+            origin: Origin::of_card(card),
+        }];
+        events.extend(moves.into_iter().map(|t| Event {
+            at: t.at,
+            kind: EventKind::Moved {
+                from: t.from,
+                to: t.to,
+            },
+            origin: t.origin,
+        }));
+        events.extend(Self::recorded(card));
+        // Stable, so events at the same instant keep the order above
+        events.sort_by_key(|e| e.at);
+        events
+    }
+
+    pub(super) fn recorded(card: &Card) -> Vec<Self> {
+        card.metadata
+            .get(EVENTS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Append an event to the card's `metadata.events`, stamped now.
+    fn record(card: &mut Card, kind: EventKind) {
+        let now = Utc::now();
+        let mut events = Self::recorded(card);
+        events.push(Event {
+            at: now,
+            kind,
+            origin: None,
+        });
+        card.metadata.insert(
+            EVENTS_KEY.into(),
+            serde_json::to_value(events).expect("events serialize"),
+        );
+        card.updated_at = now;
+    }
+}
+
+impl Card {
+    /// Add a label unless the card has it already; returns whether it was
+    /// added.
+    pub fn add_label(&mut self, label: &str) -> bool {
+        if self.labels.iter().any(|l| l == label) {
+            return false;
+        }
+        self.labels.push(label.into());
+        Event::record(
+            self,
+            EventKind::Labeled {
+                label: label.into(),
+            },
+        );
+        true
+    }
+
+    /// Remove a label; returns whether the card had it.
+    pub fn remove_label(&mut self, label: &str) -> bool {
+        if !self.labels.iter().any(|l| l == label) {
+            return false;
+        }
+        self.labels.retain(|l| l != label);
+        Event::record(
+            self,
+            EventKind::Unlabeled {
+                label: label.into(),
+            },
+        );
+        true
+    }
+
+    /// Assign the card, or unassign it with `None`; returns whether that
+    /// changed anything.
+    pub fn set_assignee(&mut self, assignee: Option<String>) -> bool {
+        if self.assignee == assignee {
+            return false;
+        }
+        let kind = match &assignee {
+            Some(a) => EventKind::Assigned {
+                assignee: a.clone(),
+            },
+            None => EventKind::Unassigned,
+        };
+        self.assignee = assignee;
+        Event::record(self, kind);
+        true
+    }
+
+    /// Archive the card; returns whether it wasn't archived already.
+    pub fn archive(&mut self) -> bool {
+        if self.archived {
+            return false;
+        }
+        self.archived = true;
+        Event::record(self, EventKind::Archived);
+        true
+    }
+
+    /// Bring the card back from the archive; returns whether it was
+    /// archived.
+    pub fn unarchive(&mut self) -> bool {
+        if !self.archived {
+            return false;
+        }
+        self.archived = false;
+        Event::record(self, EventKind::Unarchived);
+        true
+    }
+
+    /// When the card was last archived, if kuk recorded it.
+    pub fn archived_at(&self) -> Option<DateTime<Utc>> {
+        Event::recorded(self)
+            .into_iter()
+            .rev()
+            .find(|e| e.kind == EventKind::Archived)
+            .map(|e| e.at)
+    }
+
+    /// When the card last arrived in its current column, if it was ever
+    /// moved there.
+    pub fn entered_column_at(&self) -> Option<DateTime<Utc>> {
+        Transition::history(self)
+            .into_iter()
+            .rev()
+            .find(|t| t.to == self.column)
+            .map(|t| t.at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Board;
+
+    #[test]
+    fn history_merges_moves_and_recorded_events() {
+        let mut board = Board::default_board();
+        let card = Card::new("Ship it", "todo");
+        let id = card.id.clone();
+        board.cards.push(card);
+
+        board.move_card(&id, "doing");
+        let card = board.find_card_mut(&id).unwrap();
+        assert!(card.add_label("bug"));
+        assert!(!card.add_label("bug"));
+        assert!(card.set_assignee(Some("ana".into())));
+        assert!(!card.set_assignee(Some("ana".into())));
+        assert!(card.remove_label("bug"));
+        board.move_card(&id, "done");
+        let card = board.find_card_mut(&id).unwrap();
+        assert!(card.archive());
+        assert!(!card.archive());
+        assert!(card.archived_at().is_some());
+        assert!(card.unarchive());
+        assert!(!card.unarchive());
+
+        let card = board.find_card(&id).unwrap();
+        let steps: Vec<String> = Event::history(card)
+            .iter()
+            .map(|e| e.kind.to_string())
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                "created in todo",
+                "moved todo → doing",
+                "labeled bug",
+                "assigned to @ana",
+                "unlabeled bug",
+                "moved doing → done",
+                "archived",
+                "unarchived",
+            ]
+        );
+        let done_at = Transition::history(card).last().unwrap().at;
+        assert_eq!(card.entered_column_at(), Some(done_at));
+    }
+
+    #[test]
+    fn events_serialize_tagged() {
+        let event = Event {
+            at: Utc::now(),
+            kind: EventKind::Labeled {
+                label: "bug".into(),
+            },
+            origin: Some(Origin::new(crate::model::Via::Mcp)),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "labeled");
+        assert_eq!(json["label"], "bug");
+        assert_eq!(json["origin"]["via"], "mcp");
+        let back: Event = serde_json::from_value(json).unwrap();
+        assert_eq!(back, event);
+    }
+}
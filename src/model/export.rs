@@ -0,0 +1,159 @@
+use serde_json::Value;
+
+use super::Board;
+
+/// Columns of [`Board::to_csv`]: the card fields as they are serialized for
+/// `--json`, in the same order, plus the short ID. Metadata and comments
+/// don't flatten into one cell, so they're left out.
+pub const CSV_FIELDS: &[&str] = &[
+    "id",
+    "short_id",
+    "title",
+    "column",
+    "order",
+    "description",
+    "assignee",
+    "labels",
+    "due",
+    "priority",
+    "created_at",
+    "updated_at",
+    "archived",
+    "snooze",
+    "attachments",
+    "epic",
+    "parent",
+    "lane",
+];
+
+impl Board {
+    /// Every card, archived ones included, as one CSV row each under a
+    /// [`CSV_FIELDS`] header. Lists of names are joined with `; `; other
+    /// nested values are written as JSON.
+    pub fn to_csv(&self) -> String {
+        let mut out = CSV_FIELDS.join(",");
+        out.push('\n');
+        for card in &self.cards {
+            let mut value = serde_json::to_value(card).expect("cards serialize");
+            value["short_id"] = card.short_id().into();
+            let row: Vec<String> = CSV_FIELDS
+                .iter()
+                .map(|field| csv_field(&cell(&value[*field])))
+                .collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The board as a Markdown kanban summary: a heading per column with
+    /// its active cards as a list, for pasting into a wiki.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", self.name);
+        for column in &self.columns {
+            let cards = self.column_cards(&column.name);
+            let limit = column
+                .wip_limit
+                .map(|l| format!(", limit {l}"))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "\n## {} ({}{limit})\n\n",
+                column.name,
+                cards.len()
+            ));
+            if cards.is_empty() {
+                out.push_str("_No cards_\n");
+            }
+            for card in cards {
+                let mut line = match card.short_id() {
+                    Some(short) => format!("- **{short}** {}", card.title),
+                    None => format!("- {}", card.title),
+                };
+                for label in &card.labels {
+                    line.push_str(&format!(" `{label}`"));
+                }
+                if let Some(assignee) = &card.assignee {
+                    line.push_str(&format!(" @{assignee}"));
+                }
+                if let Some(due) = card.due {
+                    line.push_str(&format!(" (due {})", due.format("%Y-%m-%d")));
+                }
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+/// One field's cell text: empty for missing values, strings as they are.
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) if items.iter().all(Value::is_string) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("; "),
+        other => other.to_string(),
+    }
+}
+
+/// Quote a cell that holds a comma, quote or line break, as RFC 4180 asks.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Card;
+
+    #[test]
+    fn csv_has_a_row_per_card_with_quoted_cells() {
+        let mut board = Board::default_board();
+        let mut card = Card::new("Fix login, then logout", "todo");
+        card.description = Some("Says \"nope\"\nevery time".into());
+        card.labels = vec!["bug".into(), "auth".into()];
+        board.add_card(card);
+        let mut done = Card::new("Old", "done");
+        done.archived = true;
+        board.add_card(done);
+
+        let csv = board.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), CSV_FIELDS.join(","));
+        assert!(csv.contains(
+            ",KUK-1,\"Fix login, then logout\",todo,0,\"Says \"\"nope\"\"\nevery time\",,bug; auth,"
+        ));
+        assert!(csv.contains(",KUK-2,Old,done,"));
+        // Header plus two rows, one spanning two lines
+        assert_eq!(csv.lines().count(), 4);
+    }
+
+    #[test]
+    fn markdown_lists_active_cards_by_column() {
+        let mut board = Board::default_board();
+        board.columns[1].wip_limit = Some(2);
+        let mut card = Card::new("Fix login", "doing");
+        card.labels = vec!["bug".into()];
+        card.assignee = Some("leslie".into());
+        board.add_card(card);
+        let mut archived = Card::new("Gone", "done");
+        archived.archived = true;
+        board.add_card(archived);
+
+        assert_eq!(
+            board.to_markdown(),
+            "# default\n\n\
+             ## todo (0)\n\n_No cards_\n\n\
+             ## doing (1, limit 2)\n\n- **KUK-1** Fix login `bug` @leslie\n\n\
+             ## done (0)\n\n_No cards_\n"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Card, Column};
+
+/// Card metadata key holding the [`InboxSource`] of an intake item.
+pub const INBOX_KEY: &str = "inbox";
+
+/// Where an inbox item came from, stored under `metadata.inbox`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InboxSource {
+    /// Free-form source name, e.g. `email`, `form`, `webhook`.
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub received_at: DateTime<Utc>,
+}
+
+impl InboxSource {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            sender: None,
+            url: None,
+            received_at: Utc::now(),
+        }
+    }
+
+    /// The source recorded on a card, if it came in through the inbox.
+    pub fn of(card: &Card) -> Option<Self> {
+        serde_json::from_value(card.metadata.get(INBOX_KEY)?.clone()).ok()
+    }
+}
+
+impl Board {
+    /// File a new card at the bottom of the intake `column`, adding the
+    /// column at the front of the board if it doesn't exist yet.
+    pub fn receive(
+        &mut self,
+        column: &str,
+        title: &str,
+        body: Option<String>,
+        source: InboxSource,
+    ) -> &Card {
+        if !self.has_column(column) {
+            self.columns.insert(0, Column::new(column));
+        }
+        let mut card = Card::new(title, column);
+        card.order = self.next_order(column);
+        card.description = body.filter(|b| !b.trim().is_empty());
+        card.metadata.insert(
+            INBOX_KEY.into(),
+            serde_json::to_value(source).expect("inbox source serializes"),
+        );
+        self.add_card(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receive_creates_intake_column_and_records_source() {
+        let mut board = Board::default_board();
+        let mut source = InboxSource::new("email");
+        source.sender = Some("ana@example.com".into());
+
+        let card = board
+            .receive("inbox", "Export is broken", Some("  ".into()), source)
+            .clone();
+        assert_eq!(board.columns[0].name, "inbox");
+        assert_eq!(card.column, "inbox");
+        assert!(card.description.is_none());
+        let source = InboxSource::of(&card).unwrap();
+        assert_eq!(source.source, "email");
+        assert_eq!(source.sender.as_deref(), Some("ana@example.com"));
+
+        board.receive("inbox", "Second", None, InboxSource::new("form"));
+        assert_eq!(board.columns.len(), 4);
+        assert_eq!(board.column_cards("inbox").len(), 2);
+        assert!(InboxSource::of(&Card::new("Plain", "todo")).is_none());
+    }
+}
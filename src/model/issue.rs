@@ -0,0 +1,112 @@
+use serde::Serialize;
+
+use super::Card;
+use super::checklist::parse_line;
+
+/// A card written up as a GitHub issue: what goes in the title field, a
+/// Markdown body, and the labels to put on it.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IssueExport {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+}
+
+impl IssueExport {
+    /// The title as a heading over the body, for pasting in one go.
+    pub fn markdown(&self) -> String {
+        format!("# {}\n\n{}", self.title, self.body)
+    }
+}
+
+impl Card {
+    /// The card as an issue body: its description with the checklist as a
+    /// `- [ ]` task list, which GitHub tracks, its attachments as a list of
+    /// links, and a footer naming its labels.
+    pub fn to_issue(&self) -> IssueExport {
+        let mut body: Vec<String> = self
+            .description
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end()
+            .lines()
+            .map(|line| match parse_line(line) {
+                Some((bullet, done, text)) => {
+                    let indent = &bullet[..bullet.len() - 2];
+                    let mark = if done { 'x' } else { ' ' };
+                    format!("{indent}- [{mark}] {text}")
+                }
+                None => line.to_string(),
+            })
+            .collect();
+        if !self.attachments.is_empty() {
+            if !body.is_empty() {
+                body.push(String::new());
+            }
+            body.push("Attachments:".into());
+            for attachment in &self.attachments {
+                // Repo paths don't resolve from an issue, so they stay code
+                body.push(match (attachment.is_url(), &attachment.title) {
+                    (true, _) => format!("- [{}]({})", attachment.label(), attachment.target),
+                    (false, Some(title)) => format!("- {title}: `{}`", attachment.target),
+                    (false, None) => format!("- `{}`", attachment.target),
+                });
+            }
+        }
+        if !self.labels.is_empty() {
+            if !body.is_empty() {
+                body.push(String::new());
+            }
+            let labels: Vec<String> = self.labels.iter().map(|l| format!("`{l}`")).collect();
+            body.push("---".into());
+            body.push(format!("Labels: {}", labels.join(", ")));
+        }
+        let mut body = body.join("\n");
+        if !body.is_empty() {
+            body.push('\n');
+        }
+        IssueExport {
+            title: self.title.clone(),
+            body,
+            labels: self.labels.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Attachment;
+
+    #[test]
+    fn checklist_becomes_a_task_list_with_a_labels_footer() {
+        let mut card = Card::new("Login page", "todo");
+        card.description = Some("Sign in with email.\n\n* [X] Form\n  + [ ] Errors\n".into());
+        card.labels = vec!["frontend".into(), "auth".into()];
+
+        let issue = card.to_issue();
+        assert_eq!(issue.title, "Login page");
+        assert_eq!(
+            issue.body,
+            "Sign in with email.\n\n- [x] Form\n  - [ ] Errors\n\n---\nLabels: `frontend`, `auth`\n"
+        );
+        assert!(issue.markdown().starts_with("# Login page\n\nSign in"));
+
+        card.attachments = vec![
+            Attachment {
+                target: "https://example.com/mock".into(),
+                title: Some("Mockup".into()),
+            },
+            Attachment {
+                target: "docs/login.md".into(),
+                title: None,
+            },
+        ];
+        assert!(card.to_issue().body.contains(
+            "- [ ] Errors\n\nAttachments:\n- [Mockup](https://example.com/mock)\n- `docs/login.md`\n\n---\n"
+        ));
+
+        let bare = Card::new("Bare", "todo").to_issue();
+        assert_eq!(bare.body, "");
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// A label in a board's registry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LabelDef {
+    pub name: String,
+    /// Hex color, normalized to `#rrggbb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl LabelDef {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            color: None,
+            description: None,
+        }
+    }
+
+    /// The label color as RGB components, if set and valid.
+    pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+        let hex = self.color.as_deref()?.strip_prefix('#')?;
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        Some((channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// Parse a hex color like `#D73A4A` or GitHub's bare `d73a4a` into `#d73a4a`.
+pub fn parse_color(s: &str) -> Result<String, String> {
+    let hex = s.trim().trim_start_matches('#');
+    if hex.len() == 6 && hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        Ok(format!("#{}", hex.to_ascii_lowercase()))
+    } else {
+        Err(format!(
+            "Invalid color: {s}. Use a hex color like '#d73a4a'."
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_color("#D73A4A").unwrap(), "#d73a4a");
+        assert_eq!(parse_color("0e8a16").unwrap(), "#0e8a16");
+        assert!(parse_color("red").is_err());
+        assert!(parse_color("#12345").is_err());
+    }
+
+    #[test]
+    fn rgb_components() {
+        let mut def = LabelDef::new("bug");
+        assert_eq!(def.rgb(), None);
+        def.color = Some("#d73a4a".into());
+        assert_eq!(def.rgb(), Some((0xd7, 0x3a, 0x4a)));
+    }
+
+    #[test]
+    fn omits_empty_fields() {
+        let json = serde_json::to_string(&LabelDef::new("docs")).unwrap();
+        assert_eq!(json, r#"{"name":"docs"}"#);
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Card};
+
+/// What splits a board into swimlanes: rows that cut across every column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Swimlanes {
+    /// One lane per assignee.
+    Assignee,
+    /// One lane per label. A card with several labels goes in the lane of
+    /// its first one.
+    Label,
+    /// The card's own `lane` field.
+    Lane,
+}
+
+impl Swimlanes {
+    /// The lane `card` belongs to, or `None` for cards without one.
+    pub fn lane_of(self, card: &Card) -> Option<&str> {
+        match self {
+            Swimlanes::Assignee => card.assignee.as_deref(),
+            Swimlanes::Label => card.labels.first().map(String::as_str),
+            Swimlanes::Lane => card.lane.as_deref(),
+        }
+    }
+
+    /// What to call the lane of cards without one.
+    pub fn unset_name(self) -> &'static str {
+        match self {
+            Swimlanes::Assignee => "unassigned",
+            Swimlanes::Label => "unlabeled",
+            Swimlanes::Lane => "no lane",
+        }
+    }
+}
+
+/// Lane display order: named lanes alphabetically, then cards without one.
+pub fn cmp_lanes(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (a, b) => a.is_none().cmp(&b.is_none()),
+    }
+}
+
+/// One swimlane: its name and its cards in each column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lane<'a> {
+    /// `None` for the lane of cards without one.
+    pub name: Option<&'a str>,
+    /// Cards per column, in the order of the columns given.
+    pub columns: Vec<Vec<&'a Card>>,
+}
+
+/// Split per-column card lists into lanes by `by`, in [`cmp_lanes`] order.
+/// Cards keep their order within each column, and lanes are only made for
+/// values some card has.
+pub fn split_lanes<'a>(by: Swimlanes, columns: &[Vec<&'a Card>]) -> Vec<Lane<'a>> {
+    let mut lanes: BTreeMap<(bool, &str), Lane<'a>> = BTreeMap::new();
+    for (i, cards) in columns.iter().enumerate() {
+        for &card in cards {
+            let name = by.lane_of(card);
+            lanes
+                .entry((name.is_none(), name.unwrap_or("")))
+                .or_insert_with(|| Lane {
+                    name,
+                    columns: vec![Vec::new(); columns.len()],
+                })
+                .columns[i]
+                .push(card);
+        }
+    }
+    lanes.into_values().collect()
+}
+
+impl Board {
+    /// The board's active cards split into lanes by `by`, with each lane's
+    /// cards per column in board position.
+    pub fn lanes(&self, by: Swimlanes) -> Vec<Lane<'_>> {
+        let columns: Vec<Vec<&Card>> = self
+            .columns
+            .iter()
+            .map(|col| self.column_cards(&col.name))
+            .collect();
+        split_lanes(by, &columns)
+    }
+}
+
+impl FromStr for Swimlanes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "assignee" => Ok(Swimlanes::Assignee),
+            "label" => Ok(Swimlanes::Label),
+            "lane" => Ok(Swimlanes::Lane),
+            _ => Err(format!(
+                "Invalid swimlanes: {s}. Use 'assignee', 'label', or 'lane'."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Swimlanes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Swimlanes::Assignee => write!(f, "assignee"),
+            Swimlanes::Label => write!(f, "label"),
+            Swimlanes::Lane => write!(f, "lane"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lanes_group_cards_across_columns() {
+        let mut board = Board::default_board();
+        let mut card = |title: &str, column: &str, assignee: Option<&str>, order: u32| {
+            let mut c = Card::new(title, column);
+            c.assignee = assignee.map(String::from);
+            c.order = order;
+            board.add_card(c);
+        };
+        card("Write docs", "todo", Some("bo"), 0);
+        card("Triage", "todo", None, 1);
+        card("Fix login", "doing", Some("al"), 0);
+        card("Review", "todo", Some("al"), 2);
+
+        let lanes = board.lanes(Swimlanes::Assignee);
+        let names: Vec<Option<&str>> = lanes.iter().map(|l| l.name).collect();
+        assert_eq!(names, [Some("al"), Some("bo"), None]);
+        let titles = |cards: &[&Card]| cards.iter().map(|c| c.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&lanes[0].columns[0]), ["Review"]);
+        assert_eq!(titles(&lanes[0].columns[1]), ["Fix login"]);
+        assert!(lanes[0].columns[2].is_empty());
+        assert_eq!(titles(&lanes[2].columns[0]), ["Triage"]);
+
+        assert_eq!(board.lanes(Swimlanes::Lane).len(), 1);
+        assert_eq!(cmp_lanes(None, Some("a")), Ordering::Greater);
+        assert_eq!("Label".parse(), Ok(Swimlanes::Label));
+        assert!("team".parse::<Swimlanes>().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use super::checklist::parse_line;
+
+/// A card read from a Markdown task list by `kuk import markdown`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownCard {
+    /// 1-based line the item is on.
+    pub line: usize,
+    pub title: String,
+    pub labels: Vec<String>,
+    pub assignee: Option<String>,
+    /// The item was ticked.
+    pub done: bool,
+    /// The lines nested under the item, so sub-items become its checklist.
+    pub description: Option<String>,
+}
+
+/// Every top-level task (`- [ ] Title`) in `markdown`, with `#label` and
+/// `@assignee` words taken out of the title. Lines indented under a task
+/// become its description; anything else, headings and prose included, is
+/// left out.
+pub fn parse_markdown_cards(markdown: &str) -> Vec<MarkdownCard> {
+    let mut cards: Vec<MarkdownCard> = Vec::new();
+    // The task taking nested lines, and how far it is indented
+    let mut open: Option<usize> = None;
+    let mut nested: Vec<&str> = Vec::new();
+
+    for (n, line) in markdown.lines().enumerate() {
+        let depth = line.len() - line.trim_start().len();
+        if let Some(indent) = open
+            && (depth > indent || line.trim().is_empty())
+        {
+            nested.push(line);
+            continue;
+        }
+        if open.take().is_some()
+            && let Some(card) = cards.last_mut()
+        {
+            card.description = nested_text(&std::mem::take(&mut nested));
+        }
+        if let Some((_, done, text)) = parse_line(line) {
+            open = Some(depth);
+            cards.push(titled(n + 1, text, done));
+        }
+    }
+    if open.is_some()
+        && let Some(card) = cards.last_mut()
+    {
+        card.description = nested_text(&nested);
+    }
+    cards.retain(|c| !c.title.is_empty());
+    cards
+}
+
+/// `lines` without the indent they share or the blank lines around them.
+fn nested_text(lines: &[&str]) -> Option<String> {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()?;
+    let text: Vec<&str> = lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or_default())
+        .collect();
+    Some(text.join("\n").trim_matches('\n').trim_end().to_string())
+}
+
+fn titled(line: usize, text: &str, done: bool) -> MarkdownCard {
+    let mut card = MarkdownCard {
+        line,
+        title: String::new(),
+        labels: Vec::new(),
+        assignee: None,
+        done,
+        description: None,
+    };
+    let mut words = Vec::new();
+    for word in text.split_whitespace() {
+        // `#12` is an issue reference, not a label
+        match (word.strip_prefix('#'), word.strip_prefix('@')) {
+            (Some(label), _) if label.chars().any(|c| c.is_alphabetic()) => {
+                if !card.labels.iter().any(|l| l == label) {
+                    card.labels.push(label.to_string());
+                }
+            }
+            (_, Some(user)) if !user.is_empty() => card.assignee = Some(user.to_string()),
+            _ => words.push(word),
+        }
+    }
+    card.title = words.join(" ");
+    card
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_level_tasks_become_cards_with_nested_checklists() {
+        let markdown = "\
+# Launch
+
+Some notes.
+
+- [ ] Write docs #docs @leslie
+  - [x] Outline
+  - [ ] Screenshots
+
+    Show the dark theme too.
+- [x] Fix #12 crash #bug
+* [ ] #ui
+- Plain bullet
+";
+        let cards = parse_markdown_cards(markdown);
+        assert_eq!(cards.len(), 2);
+
+        assert_eq!(cards[0].line, 5);
+        assert_eq!(cards[0].title, "Write docs");
+        assert_eq!(cards[0].labels, ["docs"]);
+        assert_eq!(cards[0].assignee.as_deref(), Some("leslie"));
+        assert_eq!(
+            cards[0].description.as_deref(),
+            Some("- [x] Outline\n- [ ] Screenshots\n\n  Show the dark theme too.")
+        );
+
+        assert_eq!(cards[1].title, "Fix #12 crash");
+        assert_eq!(cards[1].labels, ["bug"]);
+        assert!(cards[1].done);
+        assert_eq!(cards[1].description, None);
+    }
+}
@@ -0,0 +1,286 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use super::{Board, Card, ESCALATIONS_KEY, EVENTS_KEY, TRANSITIONS_KEY};
+
+/// The outcome of a three-way merge. Where both sides changed the same field
+/// differently, ours is kept and the clash is described in `conflicts`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merged<T> {
+    pub merged: T,
+    pub conflicts: Vec<String>,
+}
+
+/// Lists that only ever grow: both sides' additions are kept, by time.
+const LOG_KEYS: &[&str] = &["comments", TRANSITIONS_KEY, EVENTS_KEY, ESCALATIONS_KEY];
+
+impl Board {
+    /// Merge two boards that both started from `base` (None when both
+    /// sides created the board) card by card: cards added, removed or
+    /// changed on one side carry over, and a card changed on both sides is
+    /// merged field by field. Cards numbered on both sides get new numbers
+    /// on theirs.
+    pub fn merge3(base: Option<&Board>, ours: &Board, theirs: &Board) -> Merged<Board> {
+        let mut conflicts = Vec::new();
+        let base_v = base.map(to_object);
+        let mut fields = merge_objects(
+            base_v.as_ref(),
+            &to_object(ours),
+            &to_object(theirs),
+            &["cards", "last_seq"],
+            &[],
+            "board",
+            &mut conflicts,
+        );
+        fields.insert("cards".into(), Value::Array(Vec::new()));
+        let mut merged: Board = from_object(fields, ours);
+        merged.last_seq = ours.last_seq.max(theirs.last_seq);
+
+        let ids = ours
+            .cards
+            .iter()
+            .chain(&theirs.cards)
+            .map(|c| c.id.as_str())
+            .fold(Vec::new(), |mut ids, id| {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+                ids
+            });
+        for id in ids {
+            let base = find(base, id);
+            let card = match (find(Some(ours), id), find(Some(theirs), id)) {
+                (Some(o), Some(t)) => {
+                    let card = Card::merge3(base, o, t);
+                    conflicts.extend(card.conflicts);
+                    Some(card.merged)
+                }
+                (Some(one), None) | (None, Some(one)) => match base {
+                    // Added on one side
+                    None => Some(one.clone()),
+                    // Removed on the other, which wins unless this side edited it
+                    Some(base) if !edited(base, one) => None,
+                    Some(_) => {
+                        conflicts.push(format!(
+                            "{}: removed on one side but changed on the other; kept it",
+                            card_name(one)
+                        ));
+                        Some(one.clone())
+                    }
+                },
+                (None, None) => None,
+            };
+            merged.cards.extend(card);
+        }
+        merged.number_cards();
+        Merged { merged, conflicts }
+    }
+}
+
+impl Card {
+    /// Merge two versions of a card field by field. History lists (moves,
+    /// events, comments) keep both sides' additions and `updated_at` takes
+    /// the later time.
+    pub fn merge3(base: Option<&Card>, ours: &Card, theirs: &Card) -> Merged<Card> {
+        let mut conflicts = Vec::new();
+        let base_v = base.map(to_object);
+        let fields = merge_objects(
+            base_v.as_ref(),
+            &to_object(ours),
+            &to_object(theirs),
+            &["updated_at"],
+            &["metadata"],
+            &card_name(ours),
+            &mut conflicts,
+        );
+        let mut merged: Card = from_object(fields, ours);
+        merged.updated_at = ours.updated_at.max(theirs.updated_at);
+        Merged { merged, conflicts }
+    }
+}
+
+fn find<'a>(board: Option<&'a Board>, id: &str) -> Option<&'a Card> {
+    board.and_then(|b| b.cards.iter().find(|c| c.id == id))
+}
+
+/// Whether `card` differs from `base` by more than its position, which
+/// shifts whenever a neighbour moves.
+fn edited(base: &Card, card: &Card) -> bool {
+    let mut card = card.clone();
+    card.order = base.order;
+    card != *base
+}
+
+fn card_name(card: &Card) -> String {
+    format!("card {:?}", card.title)
+}
+
+fn to_object<T: Serialize>(value: &T) -> Map<String, Value> {
+    match serde_json::to_value(value).expect("boards and cards serialize") {
+        Value::Object(fields) => fields,
+        _ => unreachable!("boards and cards serialize to objects"),
+    }
+}
+
+/// Read merged fields back, falling back to ours if the mix doesn't parse.
+fn from_object<T: DeserializeOwned + Clone>(fields: Map<String, Value>, ours: &T) -> T {
+    serde_json::from_value(Value::Object(fields)).unwrap_or_else(|_| ours.clone())
+}
+
+/// Three-way merge of JSON objects key by key. `skip` keys are taken from
+/// ours for the caller to settle; `nested` keys holding objects are merged
+/// a level down.
+fn merge_objects(
+    base: Option<&Map<String, Value>>,
+    ours: &Map<String, Value>,
+    theirs: &Map<String, Value>,
+    skip: &[&str],
+    nested: &[&str],
+    what: &str,
+    conflicts: &mut Vec<String>,
+) -> Map<String, Value> {
+    let mut merged = Map::new();
+    let keys = ours
+        .keys()
+        .chain(theirs.keys().filter(|k| !ours.contains_key(*k)));
+    for key in keys {
+        let (b, o, t) = (
+            base.and_then(|b| b.get(key)),
+            ours.get(key),
+            theirs.get(key),
+        );
+        // Positions clash whenever both sides reorder a column; ours wins quietly
+        let value = if skip.contains(&key.as_str()) || o == t || t == b {
+            o.cloned()
+        } else if o == b {
+            t.cloned()
+        } else if LOG_KEYS.contains(&key.as_str()) {
+            Some(merge_logs(b, o, t))
+        } else if let (true, Some(Value::Object(o)), Some(Value::Object(t))) =
+            (nested.contains(&key.as_str()), o, t)
+        {
+            let b = match b {
+                Some(Value::Object(b)) => Some(b),
+                _ => None,
+            };
+            Some(Value::Object(merge_objects(
+                b,
+                o,
+                t,
+                &[],
+                &[],
+                what,
+                conflicts,
+            )))
+        } else {
+            if key != "order" {
+                conflicts.push(format!("{what}: {key} changed on both sides; kept ours"));
+            }
+            o.cloned()
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value);
+        }
+    }
+    merged
+}
+
+/// Union of two grown copies of a list, ordered by each entry's `at`.
+fn merge_logs(base: Option<&Value>, ours: Option<&Value>, theirs: Option<&Value>) -> Value {
+    let items = |v: Option<&Value>| v.and_then(Value::as_array).cloned().unwrap_or_default();
+    let base = items(base);
+    let mut merged = items(ours);
+    for item in items(theirs) {
+        if !merged.contains(&item) && !base.contains(&item) {
+            merged.push(item);
+        }
+    }
+    merged.sort_by_key(|v| {
+        v.get("at")
+            .and_then(Value::as_str)
+            .and_then(|at| at.parse::<DateTime<Utc>>().ok())
+    });
+    Value::Array(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boards() -> (Board, Board, Board) {
+        let mut base = Board::default_board();
+        for title in ["Keep", "Edit", "Drop"] {
+            base.add_card(Card::new(title, "todo"));
+        }
+        (base.clone(), base.clone(), base)
+    }
+
+    #[test]
+    fn changes_to_different_cards_both_land() {
+        let (base, mut ours, mut theirs) = boards();
+        let keep = base.cards[0].id.clone();
+        let edit = base.cards[1].id.clone();
+        let drop = base.cards[2].id.clone();
+
+        ours.move_card(&keep, "doing");
+        ours.add_card(Card::new("Ours", "todo"));
+        theirs.find_card_mut(&edit).unwrap().title = "Edited".into();
+        theirs.cards.retain(|c| c.id != drop);
+        theirs.add_card(Card::new("Theirs", "todo"));
+
+        let result = Board::merge3(Some(&base), &ours, &theirs);
+        assert!(result.conflicts.is_empty(), "{:?}", result.conflicts);
+        let board = result.merged;
+        assert_eq!(board.find_card(&keep).unwrap().column, "doing");
+        assert_eq!(board.find_card(&edit).unwrap().title, "Edited");
+        assert!(board.find_card(&drop).is_none());
+        let titles: Vec<&str> = board.cards.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, ["Keep", "Edited", "Ours", "Theirs"]);
+        // Both sides numbered their new card 4
+        let seqs: Vec<u64> = board.cards.iter().filter_map(|c| c.seq).collect();
+        assert_eq!(seqs, [1, 2, 4, 5]);
+        assert_eq!(board.last_seq, 5);
+    }
+
+    #[test]
+    fn same_card_merges_fields_and_keeps_ours_on_clashes() {
+        let (base, mut ours, mut theirs) = boards();
+        let id = base.cards[0].id.clone();
+
+        ours.move_card(&id, "doing");
+        ours.find_card_mut(&id).unwrap().title = "Ours".into();
+        theirs.find_card_mut(&id).unwrap().labels.push("bug".into());
+        theirs.find_card_mut(&id).unwrap().title = "Theirs".into();
+        theirs
+            .find_card_mut(&id)
+            .unwrap()
+            .add_comment("ana", "Seen it");
+        theirs.columns.push(super::super::Column::new("review"));
+
+        let result = Board::merge3(Some(&base), &ours, &theirs);
+        let card = result.merged.find_card(&id).unwrap();
+        assert_eq!(card.column, "doing");
+        assert_eq!(card.labels, ["bug"]);
+        assert_eq!(card.title, "Ours");
+        assert_eq!(card.comments.len(), 1);
+        assert!(result.merged.has_column("review"));
+        assert_eq!(
+            result.conflicts,
+            ["card \"Ours\": title changed on both sides; kept ours"]
+        );
+    }
+
+    #[test]
+    fn changed_card_survives_removal_on_the_other_side() {
+        let (base, mut ours, mut theirs) = boards();
+        let id = base.cards[1].id.clone();
+        ours.cards.retain(|c| c.id != id);
+        theirs.find_card_mut(&id).unwrap().title = "Still needed".into();
+
+        let result = Board::merge3(Some(&base), &ours, &theirs);
+        assert!(result.merged.find_card(&id).is_some());
+        assert_eq!(result.conflicts.len(), 1);
+    }
+}
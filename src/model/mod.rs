@@ -1,9 +1,53 @@
+mod aging;
+mod attachment;
 mod board;
 mod card;
+mod checklist;
+mod columns;
+mod comment;
 mod config;
+mod epic;
+mod event;
+mod export;
+mod inbox;
 mod index;
+mod issue;
+mod label;
+mod lane;
+mod markdown;
+mod merge;
+mod oplog;
+mod origin;
+mod scan;
+mod search;
+mod sort;
+mod template;
+mod title;
+mod transition;
+mod usage;
 
-pub use board::{Board, Column};
-pub use card::Card;
-pub use config::RepoConfig;
+pub use aging::{AgingRule, ESCALATIONS_KEY, Escalation, idle_days};
+pub use attachment::Attachment;
+pub use board::{Board, Column, SCHEMA_VERSION, cmp_position};
+pub use card::{Card, DUE_SOON_DAYS, DueStatus, Priority, SHORT_ID_PREFIX, Snooze, parse_short_id};
+pub use checklist::{ChecklistItem, merge_checklist, parse_checklist};
+pub use comment::Comment;
+pub use config::{CardDefaults, ColorScheme, DuplicatePolicy, RepoConfig, StorageLayout};
+pub use epic::EpicProgress;
+pub use event::{EVENTS_KEY, Event, EventKind};
+pub use export::CSV_FIELDS;
+pub use inbox::{INBOX_KEY, InboxSource};
 pub use index::{GlobalIndex, IndexEntry};
+pub use issue::IssueExport;
+pub use label::{LabelDef, parse_color};
+pub use lane::{Lane, Swimlanes, cmp_lanes, split_lanes};
+pub use markdown::{MarkdownCard, parse_markdown_cards};
+pub use merge::Merged;
+pub use oplog::{BoardChange, CardChange, OPLOG_LIMIT, OpLog, Operation};
+pub use origin::{ORIGIN_KEY, Origin, Via};
+pub use scan::{CODE_COMMENT_KEY, CodeComment, SCAN_TAGS, ScanResult};
+pub use search::SearchQuery;
+pub use sort::{CardSort, SortKey};
+pub use template::{BUILTIN_BOARD_TEMPLATES, BoardTemplate, CardTemplate, RenderedTemplate};
+pub use transition::{TRANSITIONS_KEY, Transition};
+pub use usage::{CommandUsage, UsageLog};
@@ -0,0 +1,407 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Card};
+
+/// Operations `.kuk/oplog.json` keeps; the oldest drop off past this.
+pub const OPLOG_LIMIT: usize = 100;
+
+/// The changes made by recent commands, oldest first, for `kuk undo` and
+/// `kuk redo`. Undo reverses the newest operation still in effect; redo
+/// replays the oldest one undone since, until a new command is recorded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OpLog {
+    pub ops: Vec<Operation>,
+}
+
+/// What one command changed, board by board.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Operation {
+    /// When the command started; every save it makes joins this operation.
+    pub at: DateTime<Utc>,
+    /// The command as typed, like `kuk move KUK-3 doing`.
+    pub command: String,
+    pub boards: Vec<BoardChange>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub undone: bool,
+}
+
+/// The cards a save added, changed or removed on a board, and its
+/// columns, labels and other settings when those changed too.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BoardChange {
+    pub board: String,
+    /// The board without its cards, before and after, when anything but
+    /// the cards changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Board>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Board>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<CardChange>,
+}
+
+/// A card before and after, missing on the side where it didn't exist.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CardChange {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Card>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Card>,
+}
+
+impl CardChange {
+    /// The card's short ID if it has one, else its ID.
+    fn name(&self) -> String {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .and_then(Card::short_id)
+            .unwrap_or_else(|| self.id.clone())
+    }
+
+    /// What happened to the card, like `move KUK-12 to done`.
+    fn summary(&self) -> String {
+        let name = self.name();
+        match (&self.before, &self.after) {
+            (None, _) => format!("add {name}"),
+            (_, None) => format!("delete {name}"),
+            (Some(before), Some(after)) if before.archived != after.archived => {
+                let verb = if after.archived {
+                    "archive"
+                } else {
+                    "unarchive"
+                };
+                format!("{verb} {name}")
+            }
+            (Some(before), Some(after)) if before.column != after.column => {
+                format!("move {name} to {}", after.column)
+            }
+            _ => format!("edit {name}"),
+        }
+    }
+}
+
+/// Cards a summary names one by one before it just counts them.
+const SUMMARY_CARDS: usize = 3;
+
+impl Operation {
+    /// A one-line account of what the operation changed, like
+    /// `move KUK-12 to done`, for commit messages.
+    pub fn summary(&self) -> String {
+        let boards: Vec<String> = self
+            .boards
+            .iter()
+            .map(|change| {
+                let mut parts = Vec::new();
+                // Numbering a new card bumps `last_seq`, which isn't news
+                let settings = match (&change.before, &change.after) {
+                    (Some(before), Some(after)) => {
+                        *before
+                            != Board {
+                                last_seq: before.last_seq,
+                                ..after.clone()
+                            }
+                    }
+                    _ => false,
+                };
+                if settings {
+                    parts.push(format!("change board {}", change.board));
+                }
+                if change.cards.len() > SUMMARY_CARDS {
+                    parts.push(format!(
+                        "update {} cards on {}",
+                        change.cards.len(),
+                        change.board
+                    ));
+                } else {
+                    parts.extend(change.cards.iter().map(CardChange::summary));
+                }
+                parts.join(", ")
+            })
+            .collect();
+        boards.join("; ")
+    }
+}
+
+fn without_cards(board: &Board) -> Board {
+    Board {
+        cards: Vec::new(),
+        ..board.clone()
+    }
+}
+
+impl BoardChange {
+    /// What saving `new` over `old` changes, or None when nothing does.
+    pub fn between(old: &Board, new: &Board) -> Option<BoardChange> {
+        let mut cards: Vec<CardChange> = Vec::new();
+        for card in &old.cards {
+            let after = new.cards.iter().find(|c| c.id == card.id);
+            if after != Some(card) {
+                cards.push(CardChange {
+                    id: card.id.clone(),
+                    before: Some(card.clone()),
+                    after: after.cloned(),
+                });
+            }
+        }
+        for card in &new.cards {
+            if !old.cards.iter().any(|c| c.id == card.id) {
+                cards.push(CardChange {
+                    id: card.id.clone(),
+                    before: None,
+                    after: Some(card.clone()),
+                });
+            }
+        }
+        let (before, after) = (without_cards(old), without_cards(new));
+        let settings = before != after;
+        if cards.is_empty() && !settings {
+            return None;
+        }
+        Some(BoardChange {
+            board: new.name.clone(),
+            before: settings.then_some(before),
+            after: settings.then_some(after),
+            cards,
+        })
+    }
+
+    /// Fold a later change to the same board into this one, keeping the
+    /// earliest before and the latest after of everything touched.
+    fn merge(&mut self, later: BoardChange) {
+        if later.after.is_some() {
+            if self.before.is_none() {
+                self.before = later.before;
+            }
+            self.after = later.after;
+        }
+        for change in later.cards {
+            match self.cards.iter_mut().find(|c| c.id == change.id) {
+                Some(card) => card.after = change.after,
+                None => self.cards.push(change),
+            }
+        }
+        self.cards.retain(|c| c.before != c.after);
+        if self.before == self.after {
+            self.before = None;
+            self.after = None;
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cards.is_empty() && self.after.is_none()
+    }
+
+    /// Put `board` back the way it was before the change, or forward to
+    /// after it when `undo` is false. Changes nothing and names the first
+    /// card that has changed since if the board isn't as the change left it.
+    pub fn apply(&self, board: &mut Board, undo: bool) -> Result<(), String> {
+        let pick = |before: &Option<Card>, after: &Option<Card>| {
+            if undo {
+                (after.clone(), before.clone())
+            } else {
+                (before.clone(), after.clone())
+            }
+        };
+        for change in &self.cards {
+            let (expected, _) = pick(&change.before, &change.after);
+            if board.cards.iter().find(|c| c.id == change.id) != expected.as_ref() {
+                return Err(change.name());
+            }
+        }
+        let (expected, target) = if undo {
+            (&self.after, &self.before)
+        } else {
+            (&self.before, &self.after)
+        };
+        if let (Some(expected), Some(target)) = (expected, target) {
+            if without_cards(board) != *expected {
+                return Err(format!("the {} board", self.board));
+            }
+            let cards = std::mem::take(&mut board.cards);
+            *board = Board {
+                cards,
+                ..target.clone()
+            };
+        }
+        for change in &self.cards {
+            let (_, target) = pick(&change.before, &change.after);
+            let index = board.cards.iter().position(|c| c.id == change.id);
+            match (index, target) {
+                (Some(i), Some(card)) => board.cards[i] = card,
+                (Some(i), None) => {
+                    board.cards.remove(i);
+                }
+                (None, Some(card)) => board.cards.push(card),
+                (None, None) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OpLog {
+    /// Add `change` to the operation started `at` by `command`, or start
+    /// that operation if it's new.
+    pub fn record(&mut self, at: DateTime<Utc>, command: &str, change: BoardChange) {
+        let current = self
+            .ops
+            .last_mut()
+            .filter(|op| op.at == at && op.command == command && !op.undone);
+        let op = match current {
+            Some(op) => op,
+            None => {
+                self.ops.push(Operation {
+                    at,
+                    command: command.to_string(),
+                    boards: Vec::new(),
+                    undone: false,
+                });
+                if self.ops.len() > OPLOG_LIMIT {
+                    self.ops.drain(..self.ops.len() - OPLOG_LIMIT);
+                }
+                self.ops.last_mut().expect("just pushed")
+            }
+        };
+        match op.boards.iter_mut().find(|b| b.board == change.board) {
+            Some(board) => board.merge(change),
+            None => op.boards.push(change),
+        }
+        op.boards.retain(|b| !b.is_empty());
+        if op.boards.is_empty() {
+            self.ops.pop();
+        }
+    }
+
+    /// Point logged changes to board `old` at `new`, after a rename.
+    pub fn rename_board(&mut self, old: &str, new: &str) {
+        for change in self.ops.iter_mut().flat_map(|op| &mut op.boards) {
+            if change.board == old {
+                change.board = new.into();
+                for board in change.before.iter_mut().chain(&mut change.after) {
+                    board.name = new.into();
+                }
+            }
+        }
+    }
+
+    /// Drop the operations that changed board `name`, after it's deleted.
+    pub fn forget_board(&mut self, name: &str) {
+        self.ops
+            .retain(|op| !op.boards.iter().any(|change| change.board == name));
+    }
+
+    /// The operation `kuk undo` reverses: the newest one in effect.
+    pub fn undo_target(&self) -> Option<usize> {
+        self.ops.iter().rposition(|op| !op.undone)
+    }
+
+    /// The operation `kuk redo` replays: the oldest one undone since the
+    /// last operation in effect.
+    pub fn redo_target(&self) -> Option<usize> {
+        let next = self.undo_target().map_or(0, |i| i + 1);
+        (next < self.ops.len()).then_some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Column;
+
+    #[test]
+    fn changes_merge_per_command_and_apply_both_ways() {
+        let mut board = Board::default_board();
+        board.add_card(Card::new("Keep", "todo"));
+        board.add_card(Card::new("Move", "todo"));
+        let original = board.clone();
+
+        let mut moved = board.clone();
+        moved.cards[1].column = "doing".into();
+        let mut added = moved.clone();
+        added.add_card(Card::new("New", "todo"));
+        added.columns.push(Column::new("review"));
+
+        let at = Utc::now();
+        let mut log = OpLog::default();
+        log.record(
+            at,
+            "kuk move",
+            BoardChange::between(&board, &moved).unwrap(),
+        );
+        log.record(
+            at,
+            "kuk move",
+            BoardChange::between(&moved, &added).unwrap(),
+        );
+        assert!(BoardChange::between(&added, &added).is_none());
+        assert_eq!(log.ops.len(), 1);
+        let change = &log.ops[0].boards[0];
+        assert_eq!(change.cards.len(), 2);
+        assert!(change.after.is_some());
+
+        let mut current = added.clone();
+        change.apply(&mut current, true).unwrap();
+        assert_eq!(current, original);
+        change.apply(&mut current, false).unwrap();
+        assert_eq!(current, added);
+
+        // A card changed since blocks the undo and leaves the board alone
+        current.cards[1].title = "Edited".into();
+        let before = current.clone();
+        assert_eq!(change.apply(&mut current, true), Err("KUK-2".to_string()));
+        assert_eq!(current, before);
+    }
+
+    #[test]
+    fn summaries_name_what_changed() {
+        let mut board = Board::default_board();
+        board.add_card(Card::new("One", "todo"));
+        let old = board.clone();
+        board.move_card(&board.cards[0].id.clone(), "done");
+        board.add_card(Card::new("Two", "todo"));
+        let mut log = OpLog::default();
+        log.record(
+            Utc::now(),
+            "kuk",
+            BoardChange::between(&old, &board).unwrap(),
+        );
+        assert_eq!(log.ops[0].summary(), "move KUK-1 to done, add KUK-2");
+
+        let old = board.clone();
+        for n in 0..4 {
+            board.add_card(Card::new(format!("Bulk {n}"), "todo"));
+        }
+        board.columns.push(Column::new("review"));
+        let change = BoardChange::between(&old, &board).unwrap();
+        log.record(Utc::now() + chrono::Duration::seconds(1), "kuk", change);
+        assert_eq!(
+            log.ops[1].summary(),
+            "change board default, update 4 cards on default"
+        );
+    }
+
+    #[test]
+    fn undo_and_redo_targets() {
+        let mut board = Board::default_board();
+        let mut log = OpLog::default();
+        for (n, title) in ["One", "Two"].iter().enumerate() {
+            let old = board.clone();
+            board.add_card(Card::new(*title, "todo"));
+            let at = Utc::now() + chrono::Duration::seconds(n as i64);
+            log.record(at, title, BoardChange::between(&old, &board).unwrap());
+        }
+        assert_eq!(log.undo_target(), Some(1));
+        assert_eq!(log.redo_target(), None);
+
+        log.ops[1].undone = true;
+        assert_eq!(log.undo_target(), Some(0));
+        assert_eq!(log.redo_target(), Some(1));
+
+        log.ops[0].undone = true;
+        assert_eq!(log.undo_target(), None);
+        assert_eq!(log.redo_target(), Some(0));
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Card, EVENTS_KEY, Event, TRANSITIONS_KEY, Transition};
+
+/// Card metadata key holding the [`Origin`] of the card's creation.
+pub const ORIGIN_KEY: &str = "origin";
+
+/// The way into kuk a change came through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Via {
+    Cli,
+    Tui,
+    /// The REST API of `kuk serve`.
+    Rest,
+    /// Either MCP transport: `kuk mcp` or `kuk serve --mcp`.
+    Mcp,
+    /// `kuk-pm sync`, bringing in GitHub state.
+    Sync,
+    /// The editor protocol of `kuk rpc`.
+    Rpc,
+}
+
+/// Who made a change: the surface it came through and, when known, the
+/// client behind it, like the MCP client's name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Origin {
+    pub via: Via,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+}
+
+impl Origin {
+    pub fn new(via: Via) -> Self {
+        Self { via, client: None }
+    }
+
+    /// Name the client, ignoring blank names.
+    pub fn client(self, client: Option<&str>) -> Self {
+        let client = client.map(str::trim).filter(|c| !c.is_empty());
+        Self {
+            client: client.map(String::from),
+            ..self
+        }
+    }
+
+    /// Where the card was created from, when that was recorded.
+    pub fn of_card(card: &Card) -> Option<Self> {
+        card.metadata
+            .get(ORIGIN_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+}
+
+impl Board {
+    /// Record `origin` on every card created, moved or otherwise changed at
+    /// or after `since` whose change doesn't say where it came from yet.
+    /// `Store::save_board` does this for stores given an origin.
+    pub fn stamp_origin(&mut self, origin: &Origin, since: DateTime<Utc>) {
+        for card in &mut self.cards {
+            card.stamp_origin(origin, since);
+        }
+    }
+}
+
+impl Card {
+    fn stamp_origin(&mut self, origin: &Origin, since: DateTime<Utc>) {
+        // Recording a change always bumps updated_at
+        if self.updated_at < since && self.created_at < since {
+            return;
+        }
+        if self.created_at >= since && !self.metadata.contains_key(ORIGIN_KEY) {
+            self.metadata.insert(
+                ORIGIN_KEY.into(),
+                serde_json::to_value(origin).expect("origin serializes"),
+            );
+        }
+
+        let mut moves = Transition::history(self);
+        let mut stamped = false;
+        for t in moves
+            .iter_mut()
+            .filter(|t| t.at >= since && t.origin.is_none())
+        {
+            t.origin = Some(origin.clone());
+            stamped = true;
+        }
+        if stamped {
+            self.metadata.insert(
+                TRANSITIONS_KEY.into(),
+                serde_json::to_value(moves).expect("transitions serialize"),
+            );
+        }
+
+        let mut events = Event::recorded(self);
+        let mut stamped = false;
+        for e in events
+            .iter_mut()
+            .filter(|e| e.at >= since && e.origin.is_none())
+        {
+            e.origin = Some(origin.clone());
+            stamped = true;
+        }
+        if stamped {
+            self.metadata.insert(
+                EVENTS_KEY.into(),
+                serde_json::to_value(events).expect("events serialize"),
+            );
+        }
+    }
+}
+
+impl FromStr for Via {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cli" => Ok(Via::Cli),
+            "tui" => Ok(Via::Tui),
+            "rest" => Ok(Via::Rest),
+            "mcp" => Ok(Via::Mcp),
+            "sync" => Ok(Via::Sync),
+            "rpc" => Ok(Via::Rpc),
+            _ => Err(format!(
+                "Invalid origin: {s}. Use 'cli', 'tui', 'rest', 'mcp', 'sync', or 'rpc'."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Via {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Via::Cli => write!(f, "cli"),
+            Via::Tui => write!(f, "tui"),
+            Via::Rest => write!(f, "rest"),
+            Via::Mcp => write!(f, "mcp"),
+            Via::Sync => write!(f, "sync"),
+            Via::Rpc => write!(f, "rpc"),
+        }
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.client {
+            Some(client) => write!(f, "{} ({client})", self.via),
+            None => write!(f, "{}", self.via),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saving_stamps_only_new_changes() {
+        let mut board = Board::default_board();
+        let mut old = Card::new("Old", "todo");
+        old.created_at -= chrono::Duration::days(1);
+        old.updated_at = old.created_at;
+        let old = board.add_card(old).id.clone();
+        let since = Utc::now();
+        let new = board.add_card(Card::new("New", "todo")).id.clone();
+
+        let agent = Origin::new(Via::Mcp).client(Some("claude-code"));
+        board.move_card(&old, "doing");
+        board.find_card_mut(&new).unwrap().add_label("bug");
+        board.stamp_origin(&agent, since);
+        board.move_card(&old, "done");
+        board.stamp_origin(&Origin::new(Via::Cli), since);
+
+        let old = board.find_card(&old).unwrap();
+        assert_eq!(Origin::of_card(old), None);
+        let origins: Vec<String> = Event::history(old)
+            .iter()
+            .map(|e| e.origin.as_ref().map_or("-".into(), |o| o.to_string()))
+            .collect();
+        assert_eq!(origins, ["-", "mcp (claude-code)", "cli"]);
+        let new = board.find_card(&new).unwrap();
+        assert_eq!(Origin::of_card(new), Some(agent.clone()));
+        assert_eq!(Event::history(new)[1].origin, Some(agent));
+
+        assert_eq!("MCP".parse(), Ok(Via::Mcp));
+        assert!("agent".parse::<Via>().is_err());
+        assert_eq!(Origin::new(Via::Rest).client(Some(" ")).client, None);
+    }
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Card};
+
+/// Card metadata key holding the [`CodeComment`] a card was filed from.
+pub const CODE_COMMENT_KEY: &str = "code_comment";
+
+/// Comment markers `kuk scan` turns into cards.
+pub const SCAN_TAGS: &[&str] = &["TODO", "FIXME", "HACK"];
+
+/// What may come right before a tag for it to count as a comment.
+const COMMENT_MARKERS: &[&str] = &["//", "#", "/*", "*", "--", ";", "<!--"];
+
+/// A `TODO`, `FIXME` or `HACK` comment in the source, stored under
+/// `metadata.code_comment` on the card that tracks it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CodeComment {
+    pub tag: String,
+    /// Path relative to the repo root, with `/` separators.
+    pub file: String,
+    pub line: usize,
+    pub text: String,
+}
+
+impl CodeComment {
+    /// The comment a card was filed from, if any.
+    pub fn of(card: &Card) -> Option<Self> {
+        serde_json::from_value(card.metadata.get(CODE_COMMENT_KEY)?.clone()).ok()
+    }
+
+    /// Every tagged comment in `contents`. A tag counts when it opens a
+    /// comment, as in `// TODO: x`, `# FIXME(ana) x` or `/* HACK x */`.
+    pub fn find(file: &str, contents: &str) -> Vec<Self> {
+        contents
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let (tag, rest) = SCAN_TAGS.iter().find_map(|tag| {
+                    let at = line.find(tag)?;
+                    let before = line[..at].trim_end();
+                    let rest = &line[at + tag.len()..];
+                    let opens_comment = COMMENT_MARKERS.iter().any(|m| before.ends_with(m));
+                    let whole_word = rest
+                        .chars()
+                        .next()
+                        .is_none_or(|c| c == ':' || c == '(' || c.is_whitespace());
+                    (opens_comment && whole_word).then_some((*tag, rest))
+                })?;
+                // Drop an owner like `TODO(ana):`, then the colon
+                let rest = match rest.strip_prefix('(') {
+                    Some(owned) => owned.split_once(')').map_or(owned, |(_, r)| r),
+                    None => rest,
+                };
+                let text = rest
+                    .trim_start_matches(':')
+                    .trim()
+                    .trim_end_matches("*/")
+                    .trim_end_matches("-->")
+                    .trim();
+                Some(Self {
+                    tag: tag.into(),
+                    file: file.into(),
+                    line: i + 1,
+                    text: text.into(),
+                })
+            })
+            .collect()
+    }
+
+    /// Title of the card tracking this comment.
+    pub fn title(&self) -> String {
+        if self.text.is_empty() {
+            format!("{} in {}", self.tag, self.file)
+        } else {
+            format!("{}: {}", self.tag, self.text)
+        }
+    }
+
+    fn same_comment(&self, other: &Self) -> bool {
+        self.tag == other.tag && self.file == other.file && self.text == other.text
+    }
+}
+
+/// What [`Board::sync_code_comments`] changed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScanResult {
+    /// Comments without a card; each now has one.
+    pub added: Vec<CodeComment>,
+    /// Comments whose card recorded another line; the card now has this one.
+    pub moved: Vec<CodeComment>,
+    /// Comments that disappeared; their cards were moved to the last column.
+    pub closed: Vec<CodeComment>,
+}
+
+impl ScanResult {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.moved.is_empty() && self.closed.is_empty()
+    }
+}
+
+impl Board {
+    /// Bring the board in line with the comments `found` by a scan. Open
+    /// cards are matched on tag, file and text, so a comment that shifts
+    /// lines keeps its card. New comments become cards at the bottom of
+    /// `column`; cards whose comment is gone from a file the scan `covers`
+    /// are moved to the last column.
+    pub fn sync_code_comments(
+        &mut self,
+        found: Vec<CodeComment>,
+        column: &str,
+        covers: impl Fn(&str) -> bool,
+    ) -> ScanResult {
+        let done = self.columns.last().map(|c| c.name.clone());
+        let mut open: Vec<(String, CodeComment)> = self
+            .cards
+            .iter()
+            .filter(|c| !c.archived && Some(&c.column) != done.as_ref())
+            .filter_map(|c| Some((c.id.clone(), CodeComment::of(c)?)))
+            .collect();
+
+        let mut result = ScanResult::default();
+        for comment in found {
+            let matched = open.iter().position(|(_, c)| c.same_comment(&comment));
+            match matched {
+                Some(i) => {
+                    let (id, known) = open.swap_remove(i);
+                    if known.line != comment.line {
+                        let card = self.find_card_mut(&id).expect("open cards exist");
+                        set_comment(card, &comment);
+                        result.moved.push(comment);
+                    }
+                }
+                None => {
+                    let mut card = Card::new(comment.title(), column);
+                    card.order = self.next_order(column);
+                    set_comment(&mut card, &comment);
+                    self.add_card(card);
+                    result.added.push(comment);
+                }
+            }
+        }
+
+        if let Some(done) = done {
+            for (id, comment) in open {
+                if covers(&comment.file) {
+                    self.move_card(&id, &done);
+                    result.closed.push(comment);
+                }
+            }
+        }
+        result
+    }
+}
+
+fn set_comment(card: &mut Card, comment: &CodeComment) {
+    card.metadata.insert(
+        CODE_COMMENT_KEY.into(),
+        serde_json::to_value(comment).expect("code comments serialize"),
+    );
+    card.updated_at = chrono::Utc::now();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"fn main() {
+    // TODO: handle errors
+    let s = "TODO: not a comment";
+    let x = 1; // FIXME(ana): off by one
+    /* HACK work around the parser */
+    // TODOS are not tags
+}
+"#;
+
+    #[test]
+    fn finds_tags_that_open_comments() {
+        let found = CodeComment::find("src/main.rs", SOURCE);
+        let got: Vec<(&str, usize, &str)> = found
+            .iter()
+            .map(|c| (c.tag.as_str(), c.line, c.text.as_str()))
+            .collect();
+        assert_eq!(
+            got,
+            [
+                ("TODO", 2, "handle errors"),
+                ("FIXME", 4, "off by one"),
+                ("HACK", 5, "work around the parser"),
+            ]
+        );
+        assert_eq!(found[0].title(), "TODO: handle errors");
+    }
+
+    #[test]
+    fn sync_adds_moves_and_closes() {
+        let mut board = Board::default_board();
+        let found = CodeComment::find("src/main.rs", SOURCE);
+        let result = board.sync_code_comments(found, "todo", |_| true);
+        assert_eq!(result.added.len(), 3);
+        assert_eq!(board.column_cards("todo").len(), 3);
+
+        // The TODO moved down a line and the HACK was fixed
+        let edited = SOURCE
+            .replace("fn main() {", "fn main() {\n")
+            .replace("    /* HACK work around the parser */\n", "");
+        let found = CodeComment::find("src/main.rs", &edited);
+        let result = board.sync_code_comments(found.clone(), "todo", |_| true);
+        assert!(result.added.is_empty());
+        assert_eq!(result.moved.len(), 2);
+        assert_eq!(result.closed[0].tag, "HACK");
+        assert_eq!(board.column_cards("done").len(), 1);
+
+        let todo = board
+            .cards
+            .iter()
+            .find(|c| c.title == "TODO: handle errors")
+            .unwrap();
+        assert_eq!(CodeComment::of(todo).unwrap().line, 3);
+
+        // Comments outside the scanned paths are left alone
+        let result = board.sync_code_comments(Vec::new(), "todo", |f| f.starts_with("docs/"));
+        assert!(result.is_empty());
+        assert!(board.sync_code_comments(found, "todo", |_| true).is_empty());
+    }
+}
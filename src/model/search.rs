@@ -0,0 +1,156 @@
+use std::str::FromStr;
+
+use super::Card;
+
+/// A `kuk search` query such as `label:bug assignee:leslie text:login`.
+/// Every term must match: a card needs all the labels and text terms, and
+/// one of the boards, columns and assignees given. Words without a key
+/// are text terms, and `"quotes"` keep spaces in a value. Matching
+/// ignores case.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    pub boards: Vec<String>,
+    pub columns: Vec<String>,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    /// Found in the title or description.
+    pub text: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn includes_board(&self, board: &str) -> bool {
+        any_equal(&self.boards, board)
+    }
+
+    pub fn matches(&self, card: &Card) -> bool {
+        let haystack = format!(
+            "{}\n{}",
+            card.title,
+            card.description.as_deref().unwrap_or_default()
+        )
+        .to_lowercase();
+        any_equal(&self.columns, &card.column)
+            && self
+                .labels
+                .iter()
+                .all(|l| card.labels.iter().any(|c| c.eq_ignore_ascii_case(l)))
+            && (self.assignees.is_empty()
+                || card
+                    .assignee
+                    .as_deref()
+                    .is_some_and(|a| any_equal(&self.assignees, a)))
+            && self
+                .text
+                .iter()
+                .all(|t| haystack.contains(&t.to_lowercase()))
+    }
+}
+
+/// Whether `value` is one of `wanted`, or nothing is wanted.
+fn any_equal(wanted: &[String], value: &str) -> bool {
+    wanted.is_empty() || wanted.iter().any(|w| w.eq_ignore_ascii_case(value))
+}
+
+/// Split on whitespace outside double quotes, dropping the quotes.
+fn terms(query: &str) -> Result<Vec<String>, String> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut quoted = false;
+    let mut started = false;
+    for c in query.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if started {
+                    terms.push(std::mem::take(&mut term));
+                    started = false;
+                }
+            }
+            c => {
+                term.push(c);
+                started = true;
+            }
+        }
+    }
+    if quoted {
+        return Err(format!("Unclosed quote in search: {query}"));
+    }
+    if started {
+        terms.push(term);
+    }
+    Ok(terms)
+}
+
+impl FromStr for SearchQuery {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut query = SearchQuery::default();
+        for term in terms(s)? {
+            let Some((key, value)) = term.split_once(':') else {
+                query.text.push(term);
+                continue;
+            };
+            let value = value.to_string();
+            if value.is_empty() {
+                return Err(format!("Search term '{term}' has no value."));
+            }
+            match key.to_lowercase().as_str() {
+                "board" => query.boards.push(value),
+                "column" => query.columns.push(value),
+                "label" => query.labels.push(value),
+                "assignee" => query
+                    .assignees
+                    .push(value.trim_start_matches('@').to_string()),
+                "text" => query.text.push(value),
+                _ => {
+                    return Err(format!(
+                        "Unknown search key '{key}'. Use board, column, label, assignee, or text."
+                    ));
+                }
+            }
+        }
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_keys_quotes_and_bare_words() {
+        let query: SearchQuery = r#"label:bug assignee:@leslie column:doing text:"log in" crash"#
+            .parse()
+            .unwrap();
+        assert_eq!(query.labels, ["bug"]);
+        assert_eq!(query.assignees, ["leslie"]);
+        assert_eq!(query.columns, ["doing"]);
+        assert_eq!(query.text, ["log in", "crash"]);
+
+        assert!("owner:me".parse::<SearchQuery>().is_err());
+        assert!("label:".parse::<SearchQuery>().is_err());
+        assert!(r#"text:"open"#.parse::<SearchQuery>().is_err());
+    }
+
+    #[test]
+    fn every_term_must_match() {
+        let mut card = Card::new("Login fails", "doing");
+        card.description = Some("Crash on submit".into());
+        card.labels = vec!["bug".into(), "auth".into()];
+        card.assignee = Some("Leslie".into());
+        let matches = |q: &str| q.parse::<SearchQuery>().unwrap().matches(&card);
+
+        assert!(matches(""));
+        assert!(matches(
+            "label:BUG label:auth assignee:leslie text:crash login"
+        ));
+        assert!(matches("column:todo column:doing"));
+        assert!(!matches("label:bug label:ui"));
+        assert!(!matches("assignee:sam"));
+        assert!(!matches("login text:timeout"));
+    }
+}
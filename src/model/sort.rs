@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Card, cmp_position};
+
+/// What cards in a column can be ordered by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    /// Board position, as in the TUI.
+    #[default]
+    Position,
+    Priority,
+    Created,
+    Updated,
+    Due,
+    Title,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 6] = [
+        SortKey::Position,
+        SortKey::Priority,
+        SortKey::Due,
+        SortKey::Updated,
+        SortKey::Created,
+        SortKey::Title,
+    ];
+
+    /// Whether the key reads best high to low: most urgent, most recently
+    /// touched.
+    fn descends(self) -> bool {
+        matches!(self, SortKey::Priority | SortKey::Updated)
+    }
+}
+
+/// A card order for `kuk list --sort`, the TUI and the REST API, written
+/// `key` or `key:asc` / `key:desc`. Without a direction each key runs the
+/// way it reads best: top of the column, most urgent, soonest due, most
+/// recently updated, oldest created, A to Z. Cards without a priority or
+/// due date go last either way, and ties keep their board position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CardSort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl CardSort {
+    pub fn new(key: SortKey) -> Self {
+        CardSort {
+            key,
+            descending: key.descends(),
+        }
+    }
+
+    pub fn cmp(&self, a: &Card, b: &Card) -> Ordering {
+        let directed = |ord: Ordering| if self.descending { ord.reverse() } else { ord };
+        let by_key = match self.key {
+            SortKey::Position => directed(cmp_position(a, b)),
+            SortKey::Priority => missing_last(a.priority, b.priority, directed),
+            SortKey::Created => directed(a.created_at.cmp(&b.created_at)),
+            SortKey::Updated => directed(a.updated_at.cmp(&b.updated_at)),
+            SortKey::Due => missing_last(a.due, b.due, directed),
+            SortKey::Title => directed(a.title.to_lowercase().cmp(&b.title.to_lowercase())),
+        };
+        by_key.then_with(|| cmp_position(a, b))
+    }
+}
+
+/// Compare two optional values in `directed` order, with `None` after
+/// every value.
+fn missing_last<T: Ord>(
+    a: Option<T>,
+    b: Option<T>,
+    directed: impl Fn(Ordering) -> Ordering,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => directed(a.cmp(&b)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+impl FromStr for CardSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, direction) = match s.split_once(':') {
+            Some((key, direction)) => (key, Some(direction)),
+            None => (s, None),
+        };
+        let key = match key.to_lowercase().as_str() {
+            "position" => SortKey::Position,
+            "priority" => SortKey::Priority,
+            "created" => SortKey::Created,
+            "updated" => SortKey::Updated,
+            "due" => SortKey::Due,
+            "title" | "alpha" => SortKey::Title,
+            _ => {
+                return Err(format!(
+                    "Invalid sort: {s}. Use 'position', 'priority', 'created', 'updated', 'due', or 'title', optionally with ':asc' or ':desc'."
+                ));
+            }
+        };
+        let descending = match direction.map(str::to_lowercase).as_deref() {
+            None => key.descends(),
+            Some("asc") => false,
+            Some("desc") => true,
+            Some(other) => {
+                return Err(format!(
+                    "Invalid sort direction: {other}. Use 'asc' or 'desc'."
+                ));
+            }
+        };
+        Ok(CardSort { key, descending })
+    }
+}
+
+impl fmt::Display for CardSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self.key {
+            SortKey::Position => "position",
+            SortKey::Priority => "priority",
+            SortKey::Created => "created",
+            SortKey::Updated => "updated",
+            SortKey::Due => "due",
+            SortKey::Title => "title",
+        };
+        write!(f, "{key}")?;
+        if self.descending != self.key.descends() {
+            write!(f, ":{}", if self.descending { "desc" } else { "asc" })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Priority;
+    use chrono::{Duration, Utc};
+
+    fn titles(cards: &[Card], sort: &str) -> Vec<String> {
+        let sort: CardSort = sort.parse().unwrap();
+        let mut cards: Vec<&Card> = cards.iter().collect();
+        cards.sort_by(|a, b| sort.cmp(a, b));
+        cards.iter().map(|c| c.title.clone()).collect()
+    }
+
+    #[test]
+    fn keys_sort_their_natural_way_and_missing_values_go_last() {
+        let now = Utc::now();
+        let mut cards: Vec<Card> = ["beta", "Alpha", "gamma"]
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                let mut card = Card::new(*title, "todo");
+                card.order = i as u32;
+                card
+            })
+            .collect();
+        cards[0].priority = Some(Priority::Low);
+        cards[2].priority = Some(Priority::Urgent);
+        cards[0].due = Some(now + Duration::days(3));
+        cards[1].due = Some(now + Duration::days(1));
+        cards[1].updated_at = now + Duration::hours(1);
+
+        assert_eq!(titles(&cards, "position"), ["beta", "Alpha", "gamma"]);
+        assert_eq!(titles(&cards, "position:desc"), ["gamma", "Alpha", "beta"]);
+        assert_eq!(titles(&cards, "priority"), ["gamma", "beta", "Alpha"]);
+        assert_eq!(titles(&cards, "priority:asc"), ["beta", "gamma", "Alpha"]);
+        assert_eq!(titles(&cards, "due"), ["Alpha", "beta", "gamma"]);
+        assert_eq!(titles(&cards, "due:desc"), ["beta", "Alpha", "gamma"]);
+        assert_eq!(titles(&cards, "updated")[0], "Alpha");
+        assert_eq!(titles(&cards, "title"), ["Alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn parses_and_displays_directions() {
+        let sort: CardSort = "Due:DESC".parse().unwrap();
+        assert_eq!(sort.key, SortKey::Due);
+        assert!(sort.descending);
+        assert_eq!(sort.to_string(), "due:desc");
+        assert_eq!(
+            "priority".parse::<CardSort>().unwrap().to_string(),
+            "priority"
+        );
+        assert_eq!(CardSort::default(), CardSort::new(SortKey::Position));
+        assert_eq!("alpha".parse::<CardSort>().unwrap().key, SortKey::Title);
+        assert!("size".parse::<CardSort>().is_err());
+        assert!("due:up".parse::<CardSort>().is_err());
+    }
+}
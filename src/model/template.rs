@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Column, LabelDef, SCHEMA_VERSION, Swimlanes};
+
+/// A card template from `.kuk/card-templates/<name>.{yaml,yml,json}`.
+///
+/// Text fields may contain `{placeholders}`, filled from `--var key=value`;
+/// `{title}` is the title given to `kuk add`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CardTemplate {
+    /// Title pattern, e.g. `"[{component}] {title}"`. Defaults to `{title}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Rendered as a Markdown task list under the description.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+}
+
+/// The card fields a template produced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedTemplate {
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub assignee: Option<String>,
+}
+
+impl CardTemplate {
+    /// Fill every placeholder. Fails with the names of any placeholders that
+    /// have no value.
+    pub fn render(
+        &self,
+        title: &str,
+        vars: &HashMap<String, String>,
+    ) -> Result<RenderedTemplate, Vec<String>> {
+        let mut vars = vars.clone();
+        vars.insert("title".into(), title.into());
+        let mut missing = Vec::new();
+        let mut fill = |text: &str| fill_placeholders(text, &vars, &mut missing);
+
+        let title = fill(self.title.as_deref().unwrap_or("{title}"));
+        let mut description = self.description.as_deref().map(&mut fill);
+        if !self.checklist.is_empty() {
+            let items: Vec<String> = self
+                .checklist
+                .iter()
+                .map(|item| format!("- [ ] {}", fill(item)))
+                .collect();
+            let body = description.take().unwrap_or_default();
+            let sep = if body.is_empty() || body.ends_with("\n\n") {
+                ""
+            } else if body.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            description = Some(format!("{body}{sep}{}", items.join("\n")));
+        }
+        let labels = self.labels.iter().map(|l| fill(l)).collect();
+        let assignee = self.assignee.as_deref().map(&mut fill);
+
+        if !missing.is_empty() {
+            return Err(missing);
+        }
+        Ok(RenderedTemplate {
+            title,
+            description,
+            labels,
+            assignee,
+        })
+    }
+}
+
+/// The board templates kuk ships with.
+pub const BUILTIN_BOARD_TEMPLATES: &[&str] = &["kanban", "scrum", "bugtracker"];
+
+/// A starting layout for new boards: a built-in, a template saved under
+/// `~/.kuk/board-templates/`, or any `.yaml`/`.yml`/`.json` file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BoardTemplate {
+    pub columns: Vec<Column>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<LabelDef>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_labels: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swimlanes: Option<Swimlanes>,
+}
+
+impl BoardTemplate {
+    /// One of [`BUILTIN_BOARD_TEMPLATES`].
+    pub fn builtin(name: &str) -> Option<Self> {
+        let columns: &[&str] = match name {
+            "kanban" => &["todo", "doing", "done"],
+            "scrum" => &["backlog", "todo", "doing", "review", "done"],
+            "bugtracker" => &["triage", "confirmed", "fixing", "verifying", "closed"],
+            _ => return None,
+        };
+        let mut template =
+            Self::from_columns(&columns.join(",")).expect("built-in templates have valid columns");
+        if name == "bugtracker" {
+            template.labels = [
+                ("bug", "#d73a4a"),
+                ("regression", "#b60205"),
+                ("needs-repro", "#fbca04"),
+            ]
+            .into_iter()
+            .map(|(label, color)| LabelDef {
+                color: Some(color.into()),
+                ..LabelDef::new(label)
+            })
+            .collect();
+        }
+        Some(template)
+    }
+
+    /// Plain columns from a comma-separated list such as
+    /// `"backlog,doing,review,done"`.
+    pub fn from_columns(list: &str) -> Result<Self, String> {
+        let template = Self {
+            columns: list
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(Column::new)
+                .collect(),
+            labels: Vec::new(),
+            strict_labels: false,
+            swimlanes: None,
+        };
+        template.validate()?;
+        Ok(template)
+    }
+
+    /// The layout of `board`, without its cards, to reuse for others.
+    pub fn from_board(board: &Board) -> Self {
+        Self {
+            columns: board.columns.clone(),
+            labels: board.labels.clone(),
+            strict_labels: board.strict_labels,
+            swimlanes: board.swimlanes,
+        }
+    }
+
+    /// A new, empty board called `name` laid out by the template.
+    pub fn board(&self, name: &str) -> Result<Board, String> {
+        self.validate()?;
+        Ok(Board {
+            schema_version: SCHEMA_VERSION,
+            name: name.into(),
+            columns: self.columns.clone(),
+            cards: Vec::new(),
+            labels: self.labels.clone(),
+            strict_labels: self.strict_labels,
+            last_seq: 0,
+            swimlanes: self.swimlanes,
+            partial: false,
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.columns.is_empty() {
+            return Err("A board template needs at least one column".into());
+        }
+        for (i, column) in self.columns.iter().enumerate() {
+            if column.name.trim().is_empty() {
+                return Err("Column name cannot be empty".into());
+            }
+            if self.columns[..i].iter().any(|c| c.name == column.name) {
+                return Err(format!("Duplicate column: {}", column.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replace `{name}` with `vars[name]`. Braces around anything that isn't a
+/// plain identifier are left alone, so code snippets survive.
+fn fill_placeholders(
+    text: &str,
+    vars: &HashMap<String, String>,
+    missing: &mut Vec<String>,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let name = after.find('}').map(|end| &after[..end]).filter(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        });
+        match name {
+            Some(name) => {
+                match vars.get(name) {
+                    Some(value) => out.push_str(value),
+                    None => {
+                        if !missing.iter().any(|m| m == name) {
+                            missing.push(name.into());
+                        }
+                    }
+                }
+                rest = &after[name.len() + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn renders_placeholders_and_checklist() {
+        let template = CardTemplate {
+            title: Some("[{component}] {title}".into()),
+            description: Some("Broken in {component}.".into()),
+            labels: vec!["bug".into(), "{component}".into()],
+            checklist: vec!["Reproduce".into(), "Add a test for {component}".into()],
+            assignee: None,
+        };
+        let card = template
+            .render("Login fails", &vars(&[("component", "auth")]))
+            .unwrap();
+        assert_eq!(card.title, "[auth] Login fails");
+        assert_eq!(
+            card.description.as_deref(),
+            Some("Broken in auth.\n\n- [ ] Reproduce\n- [ ] Add a test for auth")
+        );
+        assert_eq!(card.labels, vec!["bug", "auth"]);
+    }
+
+    #[test]
+    fn reports_missing_vars_once() {
+        let template = CardTemplate {
+            title: Some("{component}: {title}".into()),
+            description: Some("{component} on {os}".into()),
+            ..Default::default()
+        };
+        let err = template.render("Crash", &HashMap::new()).unwrap_err();
+        assert_eq!(err, vec!["component", "os"]);
+    }
+
+    #[test]
+    fn leaves_non_placeholder_braces() {
+        let template = CardTemplate {
+            description: Some("fn main() { }\n{\"a\": 1} {title}".into()),
+            ..Default::default()
+        };
+        let card = template.render("T", &HashMap::new()).unwrap();
+        assert_eq!(card.title, "T");
+        assert_eq!(
+            card.description.as_deref(),
+            Some("fn main() { }\n{\"a\": 1} T")
+        );
+    }
+
+    #[test]
+    fn board_templates_lay_out_new_boards() {
+        let scrum = BoardTemplate::builtin("scrum")
+            .unwrap()
+            .board("sprint")
+            .unwrap();
+        let names: Vec<&str> = scrum.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["backlog", "todo", "doing", "review", "done"]);
+        assert_eq!(scrum.name, "sprint");
+        assert!(BoardTemplate::builtin("bugtracker").unwrap().labels.len() == 3);
+        assert!(BoardTemplate::builtin("waterfall").is_none());
+
+        let custom = BoardTemplate::from_columns(" backlog, doing ,,done").unwrap();
+        assert_eq!(custom.columns.len(), 3);
+        assert_eq!(custom.columns[1].name, "doing");
+        assert!(BoardTemplate::from_columns(" , ").is_err());
+        assert!(BoardTemplate::from_columns("todo,done,todo").is_err());
+
+        let mut board = custom.board("ops").unwrap();
+        board.columns[1].wip_limit = Some(2);
+        board.add_card(crate::model::Card::new("Task", "doing"));
+        let saved = BoardTemplate::from_board(&board);
+        assert_eq!(saved.columns[1].wip_limit, Some(2));
+        assert!(saved.board("next").unwrap().cards.is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+/// Minimum similarity (0.0..=1.0) for two titles to count as near duplicates.
+pub const SIMILARITY_THRESHOLD: f64 = 0.85;
+
+/// Lowercase, drop punctuation, and collapse whitespace so "Fix login!" and
+/// "fix  login" compare equal.
+pub fn normalize_title(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two titles after normalization: 1.0 for identical, falling
+/// with edit distance relative to the longer title.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = normalize_title(a).chars().collect();
+    let b: Vec<char> = normalize_title(b).chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_punctuation_and_spaces() {
+        assert_eq!(
+            normalize_title("  Fix   the LOGIN-flow! "),
+            "fix the login flow"
+        );
+        assert_eq!(title_similarity("Fix login", "fix login."), 1.0);
+    }
+
+    #[test]
+    fn near_duplicates_score_high() {
+        assert!(title_similarity("Add dark mode", "Add dark-mode toggle") < SIMILARITY_THRESHOLD);
+        assert!(title_similarity("Implement auth", "Implement oauth") >= SIMILARITY_THRESHOLD);
+        assert!(title_similarity("Write docs", "Fix parser") < 0.5);
+    }
+}
@@ -0,0 +1,155 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Card, Origin};
+
+/// Card metadata key holding the card's [`Transition`] history.
+pub const TRANSITIONS_KEY: &str = "transitions";
+
+/// One move between columns, appended to `metadata.transitions` on the card
+/// by `Board::move_card`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Transition {
+    pub at: DateTime<Utc>,
+    pub from: String,
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<Origin>,
+}
+
+impl Transition {
+    /// Every transition recorded on a card, oldest first. Cards moved before
+    /// kuk kept this history have none.
+    pub fn history(card: &Card) -> Vec<Self> {
+        card.metadata
+            .get(TRANSITIONS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Append a move to the card's history.
+    pub(super) fn record(card: &mut Card, from: &str, at: DateTime<Utc>) {
+        let mut history = Self::history(card);
+        history.push(Transition {
+            at,
+            from: from.into(),
+            to: card.column.clone(),
+            origin: None,
+        });
+        card.metadata.insert(
+            TRANSITIONS_KEY.into(),
+            serde_json::to_value(history).expect("transitions serialize"),
+        );
+    }
+}
+
+impl Card {
+    /// How long the card spent in each column it passed through, summed over
+    /// repeat visits, in the order it first entered them. Time runs from
+    /// creation until `now`, or until it was archived. Cards moved before
+    /// kuk kept history count all of it in their current column.
+    pub fn time_in_columns(&self, now: DateTime<Utc>) -> Vec<(String, TimeDelta)> {
+        let mut times: Vec<(String, TimeDelta)> = Vec::new();
+        for (column, from, to) in self.column_spans(now) {
+            let spent = (to - from).max(TimeDelta::zero());
+            match times.iter_mut().find(|(c, _)| *c == column) {
+                Some((_, total)) => *total += spent,
+                None => times.push((column, spent)),
+            }
+        }
+        times
+    }
+
+    /// Each stay in a column as (column, arrived, left), oldest first, with
+    /// the last running until `now` or until the card was archived. See
+    /// [`Card::time_in_columns`] for cards without history.
+    pub fn column_spans(&self, now: DateTime<Utc>) -> Vec<(String, DateTime<Utc>, DateTime<Utc>)> {
+        let history = Transition::history(self);
+        let end = if self.archived {
+            self.archived_at().unwrap_or(self.updated_at)
+        } else {
+            now
+        };
+
+        let mut spans = Vec::new();
+        let mut column = history.first().map_or(&self.column, |t| &t.from);
+        let mut since = self.created_at;
+        for t in &history {
+            spans.push((column.clone(), since, t.at));
+            column = &t.to;
+            since = t.at;
+        }
+        spans.push((column.clone(), since, end));
+        spans
+    }
+
+    /// The column the card was in at `at`, or None if it didn't exist yet
+    /// or had been archived by then.
+    pub fn column_at(&self, at: DateTime<Utc>) -> Option<String> {
+        let archived_by = self.archived && self.archived_at().unwrap_or(self.updated_at) <= at;
+        if at < self.created_at || archived_by {
+            return None;
+        }
+        let history = Transition::history(self);
+        let column = match history.iter().rfind(|t| t.at <= at) {
+            Some(t) => &t.to,
+            None => history.first().map_or(&self.column, |t| &t.from),
+        };
+        Some(column.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Board;
+
+    #[test]
+    fn moves_between_columns_are_recorded() {
+        let mut board = Board::default_board();
+        let card = Card::new("Ship it", "todo");
+        let id = card.id.clone();
+        board.cards.push(card);
+
+        board.move_card(&id, "doing");
+        board.move_card(&id, "doing");
+        board.move_card(&id, "done");
+
+        let history = Transition::history(board.find_card(&id).unwrap());
+        let steps: Vec<(&str, &str)> = history
+            .iter()
+            .map(|t| (t.from.as_str(), t.to.as_str()))
+            .collect();
+        assert_eq!(steps, [("todo", "doing"), ("doing", "done")]);
+    }
+
+    #[test]
+    fn time_in_columns_sums_repeat_visits() {
+        let start = Utc::now() - TimeDelta::days(10);
+        let day = |n: i64| start + TimeDelta::days(n);
+        let mut card = Card::new("Ship it", "todo");
+        card.created_at = start;
+        for (at, from, to) in [
+            (3, "todo", "doing"),
+            (6, "doing", "review"),
+            (7, "review", "doing"),
+        ] {
+            card.column = to.into();
+            Transition::record(&mut card, from, day(at));
+        }
+        card.column = "done".into();
+        Transition::record(&mut card, "doing", day(9));
+
+        let times: Vec<String> = card
+            .time_in_columns(day(10))
+            .iter()
+            .map(|(column, t)| format!("{column} {}d", t.num_days()))
+            .collect();
+        assert_eq!(times, ["todo 3d", "doing 5d", "review 1d", "done 1d"]);
+
+        assert_eq!(card.column_at(day(1)).as_deref(), Some("todo"));
+        assert_eq!(card.column_at(day(6)).as_deref(), Some("review"));
+        assert_eq!(card.column_at(day(10)).as_deref(), Some("done"));
+        assert_eq!(card.column_at(day(-1)), None);
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How often each `kuk` command ran and how long it took, kept in
+/// `.kuk/usage.json` while the repo's config has `usage_log` on. It is
+/// only ever read by `kuk usage`; nothing is sent anywhere.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageLog {
+    /// When recording started, or last restarted after a clear.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    /// Keyed by the command's name without its arguments, like `board create`.
+    #[serde(default)]
+    pub commands: BTreeMap<String, CommandUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CommandUsage {
+    pub runs: u64,
+    /// Runs that ended in an error.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub failures: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
+impl UsageLog {
+    /// Count one run of `command` that took `took` and finished at `at`.
+    pub fn record(&mut self, command: &str, took: Duration, ok: bool, at: DateTime<Utc>) {
+        self.since.get_or_insert(at);
+        let ms = took.as_millis().try_into().unwrap_or(u64::MAX);
+        let usage = self.commands.entry(command.to_string()).or_default();
+        usage.runs += 1;
+        if !ok {
+            usage.failures += 1;
+        }
+        usage.total_ms = usage.total_ms.saturating_add(ms);
+        usage.max_ms = usage.max_ms.max(ms);
+        usage.last_run = Some(at);
+    }
+
+    /// Commands run most first, ties by name.
+    pub fn by_runs(&self) -> Vec<(&str, &CommandUsage)> {
+        let mut commands: Vec<_> = self
+            .commands
+            .iter()
+            .map(|(name, usage)| (name.as_str(), usage))
+            .collect();
+        commands.sort_by(|a, b| b.1.runs.cmp(&a.1.runs).then(a.0.cmp(b.0)));
+        commands
+    }
+}
+
+impl CommandUsage {
+    pub fn avg_ms(&self) -> u64 {
+        self.total_ms.checked_div(self.runs).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_counts_runs_and_times() {
+        let at = Utc::now();
+        let mut log = UsageLog::default();
+        log.record("add", Duration::from_millis(30), true, at);
+        log.record("add", Duration::from_millis(10), false, at);
+        log.record("board create", Duration::from_millis(5), true, at);
+        log.record("move", Duration::from_millis(8), true, at);
+
+        assert_eq!(log.since, Some(at));
+        let add = &log.commands["add"];
+        assert_eq!((add.runs, add.failures), (2, 1));
+        assert_eq!((add.total_ms, add.max_ms, add.avg_ms()), (40, 30, 20));
+        let names: Vec<&str> = log.by_runs().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["add", "board create", "move"]);
+    }
+}
@@ -0,0 +1,236 @@
+//! Guardrails for agents, read from `.kuk/agent-policy.toml`: which MCP
+//! tools they may call, how many cards they may create in a session, and
+//! which boards and columns they may change. Both MCP transports enforce
+//! it; without the file agents may do whatever the tools allow.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{KukError, Result};
+
+/// The policy file, inside `.kuk/`.
+pub const POLICY_FILE: &str = "agent-policy.toml";
+
+/// JSON-RPC error code for a call the policy forbids.
+pub const POLICY_VIOLATION: i32 = -32001;
+
+/// The MCP tools that change boards. Board and column rules only apply to
+/// these; the rest just read.
+pub const MUTATING_TOOLS: &[&str] = &[
+    "kuk_add_card",
+    "kuk_add_cards",
+    "kuk_move_card",
+    "kuk_move_cards",
+    "kuk_archive_card",
+    "kuk_delete_card",
+    "kuk_add_comment",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgentPolicy {
+    /// Tools agents may not call, e.g. `["kuk_delete_card"]`.
+    #[serde(default)]
+    pub deny_tools: Vec<String>,
+    /// Most cards agents may create in one session: one `kuk mcp` process,
+    /// or one MCP session on `kuk serve --mcp`.
+    #[serde(default)]
+    pub max_cards_per_session: Option<usize>,
+    /// Boards agents may change; any board when empty.
+    #[serde(default)]
+    pub boards: Vec<String>,
+    /// Columns agents may add cards to or move cards into; any column when
+    /// empty.
+    #[serde(default)]
+    pub columns: Vec<String>,
+}
+
+impl AgentPolicy {
+    /// Read the policy file from `kuk_dir`. `None` when there is none.
+    pub fn load(kuk_dir: &Path) -> Result<Option<Self>> {
+        let path = kuk_dir.join(POLICY_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = std::fs::read_to_string(&path).map_err(KukError::read(&path))?;
+        toml::from_str(&data)
+            .map(Some)
+            .map_err(|e| KukError::Other(format!("Invalid {}: {}", path.display(), e.message())))
+    }
+}
+
+/// The policy for one MCP session, and the cards created in it so far.
+/// A clone of a fresh guard starts a new session.
+#[derive(Debug, Clone)]
+pub struct Guard {
+    /// The policy, or why it could not be read. A broken policy file
+    /// blocks every change rather than allowing them all.
+    policy: std::result::Result<Option<AgentPolicy>, String>,
+    created: usize,
+}
+
+impl Guard {
+    pub fn new(policy: Option<AgentPolicy>) -> Self {
+        Self {
+            policy: Ok(policy),
+            created: 0,
+        }
+    }
+
+    /// The guard for the policy in `kuk_dir`.
+    pub fn load(kuk_dir: &Path) -> Self {
+        Self {
+            policy: AgentPolicy::load(kuk_dir).map_err(|e| e.to_string()),
+            created: 0,
+        }
+    }
+
+    /// Check a call of `tool` with `args`, acting on `board`, before it
+    /// runs. The error names the rule that forbids it.
+    pub fn check(&self, tool: &str, args: &Value, board: &str) -> std::result::Result<(), String> {
+        let policy = match &self.policy {
+            Ok(Some(policy)) => policy,
+            Ok(None) => return Ok(()),
+            Err(e) if MUTATING_TOOLS.contains(&tool) => {
+                return Err(format!(
+                    "{e}. Agents can't change boards until it is fixed."
+                ));
+            }
+            Err(_) => return Ok(()),
+        };
+        let rule = |rule: String| Err(format!("Blocked by .kuk/{POLICY_FILE}: {rule}"));
+
+        if policy.deny_tools.iter().any(|t| t == tool) {
+            return rule(format!("{tool} is listed in deny_tools"));
+        }
+        if !MUTATING_TOOLS.contains(&tool) {
+            return Ok(());
+        }
+        if !policy.boards.is_empty() && !policy.boards.iter().any(|b| b == board) {
+            return rule(format!(
+                "board {board} is not one of boards = {:?}",
+                policy.boards
+            ));
+        }
+        let columns = target_columns(tool, args);
+        if !policy.columns.is_empty()
+            && let Some(column) = columns.iter().find(|c| !policy.columns.contains(*c))
+        {
+            return rule(format!(
+                "cards may only be added to or moved into columns = {:?}, not {column}",
+                policy.columns
+            ));
+        }
+        let new_cards = match tool {
+            "kuk_add_card" => 1,
+            "kuk_add_cards" => columns.len(),
+            _ => 0,
+        };
+        if let Some(max) = policy.max_cards_per_session
+            && self.created + new_cards > max
+        {
+            return rule(format!(
+                "max_cards_per_session = {max}, and this session has created {}",
+                self.created
+            ));
+        }
+        Ok(())
+    }
+
+    /// Count the cards a successful call of `tool` created, from its
+    /// `result`.
+    pub fn record(&mut self, tool: &str, result: &Value) {
+        self.created += match tool {
+            "kuk_add_card" => 1,
+            "kuk_add_cards" => result["content"][0]["text"]
+                .as_str()
+                .and_then(|text| serde_json::from_str::<Value>(text).ok())
+                .and_then(|batch| batch["applied"].as_u64())
+                .unwrap_or(0) as usize,
+            _ => 0,
+        };
+    }
+}
+
+/// The columns a call would put cards in, with the tools' own defaults.
+fn target_columns(tool: &str, args: &Value) -> Vec<String> {
+    let items = |key: &str| args[key].as_array().cloned().unwrap_or_default();
+    let column = |spec: &Value, key: &str, default: Option<&str>| {
+        spec[key].as_str().or(default).map(String::from)
+    };
+    match tool {
+        "kuk_add_card" => column(args, "column", Some("todo")).into_iter().collect(),
+        "kuk_add_cards" => items("cards")
+            .iter()
+            .filter_map(|spec| column(spec, "column", Some("todo")))
+            .collect(),
+        "kuk_move_card" => column(args, "to", None).into_iter().collect(),
+        "kuk_move_cards" => items("moves")
+            .iter()
+            .filter_map(|spec| column(spec, "to", None))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn guard_explains_the_rule_it_enforces() {
+        let policy: AgentPolicy = toml::from_str(
+            r#"
+            deny_tools = ["kuk_delete_card"]
+            max_cards_per_session = 2
+            boards = ["default"]
+            columns = ["todo", "doing"]
+            "#,
+        )
+        .unwrap();
+        let mut guard = Guard::new(Some(policy));
+
+        let err = guard
+            .check("kuk_delete_card", &json!({"id": "1"}), "default")
+            .unwrap_err();
+        assert!(
+            err.contains("kuk_delete_card is listed in deny_tools"),
+            "{err}"
+        );
+        let err = guard
+            .check("kuk_add_card", &json!({"title": "A"}), "ops")
+            .unwrap_err();
+        assert!(err.contains("board ops is not one of"), "{err}");
+        let err = guard
+            .check(
+                "kuk_move_cards",
+                &json!({"moves": [{"id": "1", "to": "done"}]}),
+                "default",
+            )
+            .unwrap_err();
+        assert!(err.contains("not done"), "{err}");
+        assert!(guard.check("kuk_list_cards", &json!({}), "ops").is_ok());
+
+        let batch = json!({"cards": [{"title": "A"}, {"title": "B", "column": "doing"}]});
+        assert!(guard.check("kuk_add_cards", &batch, "default").is_ok());
+        let text = json!({"applied": 2, "failed": 0}).to_string();
+        guard.record(
+            "kuk_add_cards",
+            &json!({"content": [{"type": "text", "text": text}]}),
+        );
+        let err = guard
+            .check("kuk_add_card", &json!({"title": "C"}), "default")
+            .unwrap_err();
+        assert!(err.contains("max_cards_per_session = 2"), "{err}");
+
+        assert!(toml::from_str::<AgentPolicy>("allow_delete = false").is_err());
+        assert!(
+            Guard::new(None)
+                .check("kuk_delete_card", &json!({}), "x")
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! `kuk resolve`: merge conflicting board files card by card.
+//!
+//! Board files are JSON, so git's line merge turns two branches that
+//! touched nearby cards into conflict markers that break the board. kuk
+//! instead merges three versions of a file (the common base, ours and
+//! theirs) with [`Board::merge3`] or, for a cards-layout card file,
+//! [`Card::merge3`].
+//!
+//! `kuk resolve --install` registers that as a git merge driver named
+//! `kuk` for `.kuk/boards/`, so merges and rebases use it directly.
+//! `kuk resolve` alone fixes files a merge already left conflicted.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::error::{KukError, Result};
+use crate::model::{Board, Card, Merged};
+
+/// The `.gitattributes` line routing board files to the driver.
+pub const ATTRIBUTES: &str = ".kuk/boards/**/*.json merge=kuk";
+
+/// The outcome for one conflicted file. It is staged when `conflicts` is
+/// empty; otherwise it holds ours for the fields listed and is left for
+/// review.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Resolution {
+    pub path: PathBuf,
+    pub conflicts: Vec<String>,
+}
+
+/// Three-way merge the contents of a board or card file. An empty `base`
+/// means both sides added the file.
+pub fn merge_file(path: &Path, base: &str, ours: &str, theirs: &str) -> Result<Merged<String>> {
+    let is_board = serde_json::from_str::<Value>(ours)
+        .map_err(KukError::invalid_json(path))?
+        .get("columns")
+        .is_some();
+    let base = Some(base).filter(|b| !b.trim().is_empty());
+    if is_board {
+        merge_parsed(path, base, ours, theirs, Board::merge3)
+    } else {
+        merge_parsed(path, base, ours, theirs, Card::merge3)
+    }
+}
+
+fn merge_parsed<T: Serialize + DeserializeOwned>(
+    path: &Path,
+    base: Option<&str>,
+    ours: &str,
+    theirs: &str,
+    merge3: fn(Option<&T>, &T, &T) -> Merged<T>,
+) -> Result<Merged<String>> {
+    let parse = |data: &str| serde_json::from_str::<T>(data).map_err(KukError::invalid_json(path));
+    let base = base.map(parse).transpose()?;
+    let result = merge3(base.as_ref(), &parse(ours)?, &parse(theirs)?);
+    Ok(Merged {
+        merged: serde_json::to_string_pretty(&result.merged)?,
+        conflicts: result.conflicts,
+    })
+}
+
+/// The merge driver git runs as `kuk resolve --driver %O %A %B`: merge into
+/// `ours` in place, failing if any field had to be settled by keeping ours.
+pub fn run_driver(base: &Path, ours: &Path, theirs: &Path) -> Result<Vec<String>> {
+    let read = |path: &Path| std::fs::read_to_string(path).map_err(KukError::read(path));
+    let result = merge_file(ours, &read(base)?, &read(ours)?, &read(theirs)?)?;
+    std::fs::write(ours, result.merged).map_err(KukError::write(ours))?;
+    Ok(result.conflicts)
+}
+
+/// Register the merge driver in the repo's git config and route board
+/// files to it. Returns whether `.gitattributes` needed the line.
+pub fn install(root: &Path) -> Result<bool> {
+    git(root, &["config", "merge.kuk.name", "kuk board merge"])?;
+    git(
+        root,
+        &[
+            "config",
+            "merge.kuk.driver",
+            "kuk resolve --driver %O %A %B",
+        ],
+    )?;
+
+    let path = root.join(".gitattributes");
+    let mut attributes = if path.exists() {
+        std::fs::read_to_string(&path).map_err(KukError::read(&path))?
+    } else {
+        String::new()
+    };
+    if attributes.lines().any(|line| line.trim() == ATTRIBUTES) {
+        return Ok(false);
+    }
+    if !attributes.is_empty() && !attributes.ends_with('\n') {
+        attributes.push('\n');
+    }
+    attributes.push_str(ATTRIBUTES);
+    attributes.push('\n');
+    std::fs::write(&path, attributes).map_err(KukError::write(&path))?;
+    Ok(true)
+}
+
+/// Merge every board file the current merge left conflicted, staging those
+/// that merge cleanly.
+pub fn resolve_all(root: &Path) -> Result<Vec<Resolution>> {
+    let listing = git(root, &["ls-files", "-u", "-z", "--", ".kuk/boards"])?;
+    let mut paths: Vec<String> = Vec::new();
+    for entry in String::from_utf8_lossy(&listing.stdout).split('\0') {
+        // "<mode> <object> <stage>\t<path>"
+        let Some((_, path)) = entry.split_once('\t') else {
+            continue;
+        };
+        if !paths.iter().any(|p| p == path) {
+            paths.push(path.to_string());
+        }
+    }
+
+    let mut resolutions = Vec::new();
+    for path in paths {
+        let stage = |n: u8| {
+            git(root, &["show", &format!(":{n}:{path}")])
+                .ok()
+                .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        };
+        let conflicts = match (stage(1), stage(2), stage(3)) {
+            (base, Some(ours), Some(theirs)) => {
+                let file = root.join(&path);
+                let result = merge_file(&file, &base.unwrap_or_default(), &ours, &theirs)?;
+                std::fs::write(&file, result.merged).map_err(KukError::write(&file))?;
+                if result.conflicts.is_empty() {
+                    git(root, &["add", "--", &path])?;
+                }
+                result.conflicts
+            }
+            _ => vec!["deleted on one side; resolve it with git".into()],
+        };
+        resolutions.push(Resolution {
+            path: path.into(),
+            conflicts,
+        });
+    }
+    Ok(resolutions)
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<Output> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|e| KukError::Other(format!("Could not run git: {e}")))?;
+    if !output.status.success() {
+        return Err(KukError::Other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    tracing::debug!(?args, "git");
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_card_files_and_board_files() {
+        let path = Path::new("card.json");
+        let card = Card::new("Write docs", "todo");
+        let base = serde_json::to_string_pretty(&card).unwrap();
+        let mut ours = card.clone();
+        ours.column = "doing".into();
+        let mut theirs = card;
+        theirs.labels.push("docs".into());
+        let result = merge_file(
+            path,
+            &base,
+            &serde_json::to_string(&ours).unwrap(),
+            &serde_json::to_string(&theirs).unwrap(),
+        )
+        .unwrap();
+        assert!(result.conflicts.is_empty());
+        let merged: Card = serde_json::from_str(&result.merged).unwrap();
+        assert_eq!(merged.column, "doing");
+        assert_eq!(merged.labels, ["docs"]);
+
+        // Both sides created the board
+        let mut ours = Board::default_board();
+        ours.add_card(Card::new("Ours", "todo"));
+        let mut theirs = Board::default_board();
+        theirs.add_card(Card::new("Theirs", "todo"));
+        let result = merge_file(
+            path,
+            "",
+            &serde_json::to_string(&ours).unwrap(),
+            &serde_json::to_string(&theirs).unwrap(),
+        )
+        .unwrap();
+        let merged: Board = serde_json::from_str(&result.merged).unwrap();
+        assert_eq!(merged.cards.len(), 2);
+    }
+}
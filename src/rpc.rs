@@ -0,0 +1,520 @@
+//! `kuk rpc`: JSON-RPC 2.0 over stdio for editor plugins.
+//!
+//! One message per line, in and out. A line holding an array is a batch and
+//! gets an array of responses back. Unlike `kuk mcp`, methods map directly to
+//! board operations and return plain JSON.
+//!
+//! After a change the server sends a `changed` notification naming the
+//! board, with `"source": "rpc"` for its own changes (once per batch) and
+//! `"source": "external"` when a board file is changed by something else,
+//! such as the CLI or a `git pull`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::api::{BoardHandle, CardBuilder, CardQuery, KukError, Origin, Project, Via};
+use crate::storage::Store;
+
+/// How often board files are checked for outside changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+const PARSE_ERROR: i32 = -32700;
+/// kuk errors such as an unknown card or column.
+const KUK_ERROR: i32 = -32000;
+
+/// Size and modification time of each of a board's files.
+type Stamp = Vec<(u64, Option<SystemTime>)>;
+
+/// Request handling, separate from stdio so it can be driven directly.
+pub struct Session {
+    project: Project,
+    store: Store,
+    /// Last known state of each board file. Held while the session writes a
+    /// board so the watcher never mistakes that write for an outside change.
+    seen: Mutex<HashMap<String, Stamp>>,
+}
+
+struct Failure {
+    code: i32,
+    message: String,
+}
+
+impl From<KukError> for Failure {
+    fn from(e: KukError) -> Self {
+        Failure {
+            code: KUK_ERROR,
+            message: e.to_string(),
+        }
+    }
+}
+
+type Outcome = std::result::Result<Value, Failure>;
+
+#[derive(Deserialize)]
+struct Request {
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct BoardParams {
+    board: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CardParams {
+    board: Option<String>,
+    card: String,
+}
+
+#[derive(Deserialize)]
+struct CardsParams {
+    board: Option<String>,
+    column: Option<String>,
+    label: Option<String>,
+    assignee: Option<String>,
+    #[serde(default)]
+    archived: bool,
+}
+
+#[derive(Deserialize)]
+struct AddParams {
+    board: Option<String>,
+    title: String,
+    column: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    assignee: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Deserialize)]
+struct MoveParams {
+    board: Option<String>,
+    card: String,
+    to: String,
+}
+
+#[derive(Deserialize)]
+struct AssignParams {
+    board: Option<String>,
+    card: String,
+    user: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LabelParams {
+    board: Option<String>,
+    card: String,
+    #[serde(default)]
+    add: Vec<String>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+impl Session {
+    pub fn new(project: Project) -> Self {
+        let store = Store::new(project.root());
+        let session = Self {
+            project,
+            store,
+            seen: Mutex::new(HashMap::new()),
+        };
+        *session.seen.lock().unwrap() = session.stamps();
+        session
+    }
+
+    /// Handle one input line. Returns the messages to send, in order: the
+    /// response (or batch of responses), then any change notifications.
+    pub fn handle_line(&self, line: &str) -> Vec<Value> {
+        let mut changed = BTreeSet::new();
+        let reply = match serde_json::from_str::<Value>(line) {
+            Err(e) => Some(error(Value::Null, PARSE_ERROR, format!("Parse error: {e}"))),
+            Ok(Value::Array(batch)) if batch.is_empty() => {
+                Some(error(Value::Null, INVALID_REQUEST, "Empty batch"))
+            }
+            Ok(Value::Array(batch)) => {
+                let replies: Vec<Value> = batch
+                    .into_iter()
+                    .filter_map(|msg| self.handle_message(msg, &mut changed))
+                    .collect();
+                (!replies.is_empty()).then_some(Value::Array(replies))
+            }
+            Ok(msg) => self.handle_message(msg, &mut changed),
+        };
+        reply
+            .into_iter()
+            .chain(
+                changed
+                    .into_iter()
+                    .map(|board| changed_notice(&board, "rpc")),
+            )
+            .collect()
+    }
+
+    /// Notifications for boards changed outside this session since the last
+    /// check.
+    pub fn poll_changes(&self) -> Vec<Value> {
+        let mut seen = self.seen.lock().unwrap();
+        let now = self.stamps();
+        let mut boards: BTreeSet<&String> = now
+            .iter()
+            .filter(|(board, stamp)| seen.get(*board) != Some(stamp))
+            .map(|(board, _)| board)
+            .collect();
+        boards.extend(seen.keys().filter(|board| !now.contains_key(*board)));
+        let notices = boards
+            .into_iter()
+            .map(|board| changed_notice(board, "external"))
+            .collect();
+        *seen = now;
+        notices
+    }
+
+    fn handle_message(&self, msg: Value, changed: &mut BTreeSet<String>) -> Option<Value> {
+        let req: Request = match serde_json::from_value(msg) {
+            Ok(req) => req,
+            Err(e) => {
+                return Some(error(
+                    Value::Null,
+                    INVALID_REQUEST,
+                    format!("Invalid request: {e}"),
+                ));
+            }
+        };
+        let outcome = self.call(&req.method, req.params, changed);
+        // Notifications get no reply, even on failure
+        let id = req.id?;
+        Some(match outcome {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(f) => error(id, f.code, f.message),
+        })
+    }
+
+    fn call(&self, method: &str, params: Value, changed: &mut BTreeSet<String>) -> Outcome {
+        match method {
+            "ping" => Ok(json!({})),
+            "boards" => Ok(json!(self.project.boards()?)),
+            "board" => {
+                let p: BoardParams = parse(params)?;
+                let board = self.board(p.board.as_deref())?;
+                Ok(json!({
+                    "name": board.name(),
+                    "columns": board.columns(),
+                    "labels": board.labels(),
+                    "cards": board.cards(),
+                }))
+            }
+            "cards" => {
+                let p: CardsParams = parse(params)?;
+                let query = CardQuery {
+                    boards: p.board.into_iter().collect(),
+                    include_archived: p.archived,
+                    column: p.column,
+                    label: p.label,
+                    assignee: p.assignee,
+                    full: true,
+                };
+                let mut cards = Vec::new();
+                for item in self.project.cards(query)? {
+                    let (board, card) = item?;
+                    cards.push(json!({"board": &*board, "card": card}));
+                }
+                Ok(Value::Array(cards))
+            }
+            "card" => {
+                let p: CardParams = parse(params)?;
+                let board = self.board(p.board.as_deref())?;
+                let card = board
+                    .card(&p.card)
+                    .ok_or_else(|| KukError::CardNotFound(p.card.clone()))?;
+                Ok(json!(card))
+            }
+            "add" => {
+                let p: AddParams = parse(params)?;
+                self.mutate(p.board.as_deref(), changed, |board| {
+                    let mut card = CardBuilder::new(p.title);
+                    if let Some(column) = p.column {
+                        card = card.column(column);
+                    }
+                    for label in p.labels {
+                        card = card.label(label);
+                    }
+                    if let Some(assignee) = p.assignee {
+                        card = card.assignee(assignee);
+                    }
+                    if let Some(description) = p.description {
+                        card = card.description(description);
+                    }
+                    if p.force {
+                        card = card.force();
+                    }
+                    Ok(json!(board.add(card)?))
+                })
+            }
+            "move" => {
+                let p: MoveParams = parse(params)?;
+                self.mutate(p.board.as_deref(), changed, |board| {
+                    Ok(json!(board.move_card(&p.card, &p.to)?))
+                })
+            }
+            "hoist" | "demote" | "archive" | "delete" => {
+                let p: CardParams = parse(params)?;
+                self.mutate(p.board.as_deref(), changed, |board| {
+                    Ok(match method {
+                        "hoist" => json!(board.hoist(&p.card)?),
+                        "demote" => json!(board.demote(&p.card)?),
+                        "archive" => json!(board.archive(&p.card)?),
+                        _ => json!(board.delete(&p.card)?),
+                    })
+                })
+            }
+            "assign" => {
+                let p: AssignParams = parse(params)?;
+                self.mutate(p.board.as_deref(), changed, |board| {
+                    Ok(json!(board.assign(&p.card, p.user.as_deref())?))
+                })
+            }
+            "label" => {
+                let p: LabelParams = parse(params)?;
+                self.mutate(p.board.as_deref(), changed, |board| {
+                    for label in &p.add {
+                        board.add_label(&p.card, label)?;
+                    }
+                    for label in &p.remove {
+                        board.remove_label(&p.card, label)?;
+                    }
+                    let card = board
+                        .card(&p.card)
+                        .ok_or_else(|| KukError::CardNotFound(p.card.clone()))?;
+                    Ok(json!(card))
+                })
+            }
+            _ => Err(Failure {
+                code: METHOD_NOT_FOUND,
+                message: format!("Method not found: {method}"),
+            }),
+        }
+    }
+
+    fn board(&self, name: Option<&str>) -> crate::error::Result<BoardHandle> {
+        match name {
+            Some(name) => self.project.board(name),
+            None => self.project.default_board(),
+        }
+    }
+
+    /// Load a board, change it and save it under the store lock, recording
+    /// the write so it isn't reported as an outside change.
+    fn mutate(
+        &self,
+        name: Option<&str>,
+        changed: &mut BTreeSet<String>,
+        change: impl FnOnce(&mut BoardHandle) -> crate::error::Result<Value>,
+    ) -> Outcome {
+        let mut seen = self.seen.lock().unwrap();
+        let _lock = self.store.lock()?;
+        let mut board = self.board(name)?;
+        let result = change(&mut board)?;
+        board.save()?;
+        let name = board.name().to_string();
+        if let Some(stamp) = self.stamp(&name) {
+            seen.insert(name.clone(), stamp);
+        }
+        changed.insert(name);
+        Ok(result)
+    }
+
+    fn stamps(&self) -> HashMap<String, Stamp> {
+        let Ok(boards) = self.store.list_boards() else {
+            return HashMap::new();
+        };
+        boards
+            .into_iter()
+            .filter_map(|name| Some((name.clone(), self.stamp(&name)?)))
+            .collect()
+    }
+
+    fn stamp(&self, board: &str) -> Option<Stamp> {
+        self.store
+            .board_files(board)
+            .ok()?
+            .iter()
+            .map(|path| {
+                let meta = fs::metadata(path).ok()?;
+                Some((meta.len(), meta.modified().ok()))
+            })
+            .collect()
+    }
+}
+
+fn parse<T: DeserializeOwned>(params: Value) -> std::result::Result<T, Failure> {
+    // Methods without required params accept a missing params member
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| Failure {
+        code: INVALID_PARAMS,
+        message: format!("Invalid params: {e}"),
+    })
+}
+
+fn error(id: Value, code: i32, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": code, "message": message.into()},
+    })
+}
+
+fn changed_notice(board: &str, source: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "changed",
+        "params": {"board": board, "source": source},
+    })
+}
+
+/// Serve stdin until it closes, watching board files in the background.
+pub fn run(store: &Store) -> crate::error::Result<()> {
+    let project = Project::open(store.repo_root())?.with_origin(Origin::new(Via::Rpc));
+    let session = Arc::new(Session::new(project));
+    let out = Arc::new(Mutex::new(io::stdout()));
+
+    let watcher = (Arc::clone(&session), Arc::clone(&out));
+    thread::spawn(move || {
+        let (session, out) = watcher;
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let notices = session.poll_changes();
+            if send(&out, &notices).is_err() {
+                break;
+            }
+        }
+    });
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        send(&out, &session.handle_line(&line))?;
+    }
+    Ok(())
+}
+
+fn send(out: &Mutex<io::Stdout>, messages: &[Value]) -> io::Result<()> {
+    if messages.is_empty() {
+        return Ok(());
+    }
+    let mut out = out.lock().unwrap();
+    for msg in messages {
+        writeln!(out, "{msg}")?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn session() -> (TempDir, Session) {
+        let dir = TempDir::new().unwrap();
+        let project = Project::init(dir.path()).unwrap();
+        (dir, Session::new(project))
+    }
+
+    fn call(session: &Session, request: Value) -> Vec<Value> {
+        session.handle_line(&request.to_string())
+    }
+
+    #[test]
+    fn mutations_reply_then_notify() {
+        let (_dir, session) = session();
+        let out = call(
+            &session,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "add", "params": {"title": "Ship", "labels": ["release"]}}),
+        );
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0]["id"], 1);
+        assert_eq!(out[0]["result"]["column"], "todo");
+        assert_eq!(out[1]["method"], "changed");
+        assert_eq!(
+            out[1]["params"],
+            json!({"board": "default", "source": "rpc"})
+        );
+
+        let out = call(
+            &session,
+            json!({"id": 2, "method": "cards", "params": {"label": "release"}}),
+        );
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0]["result"][0]["card"]["title"], "Ship");
+        // Its own writes are not reported again as outside changes
+        assert!(session.poll_changes().is_empty());
+    }
+
+    #[test]
+    fn batch_replies_together_and_notifies_once() {
+        let (_dir, session) = session();
+        let out = call(
+            &session,
+            json!([
+                {"id": 1, "method": "add", "params": {"title": "A"}},
+                {"id": 2, "method": "move", "params": {"card": "1", "to": "done"}},
+                {"method": "ping"},
+                {"id": 3, "method": "move", "params": {"card": "1", "to": "nowhere"}},
+                {"id": 4, "method": "explode"},
+                {"id": 5, "method": "move", "params": {}},
+            ]),
+        );
+        assert_eq!(out.len(), 2);
+        let replies = out[0].as_array().unwrap();
+        assert_eq!(replies.len(), 5);
+        assert_eq!(replies[1]["result"]["column"], "done");
+        assert_eq!(replies[2]["error"]["code"], KUK_ERROR);
+        assert_eq!(replies[3]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(replies[4]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(out[1]["params"]["board"], "default");
+
+        assert_eq!(session.handle_line("{")[0]["error"]["code"], PARSE_ERROR);
+        assert_eq!(
+            session.handle_line("[]")[0]["error"]["code"],
+            INVALID_REQUEST
+        );
+    }
+
+    #[test]
+    fn outside_changes_are_noticed() {
+        let (dir, session) = session();
+        assert!(session.poll_changes().is_empty());
+        let store = Store::new(dir.path());
+        let mut board = store.load_board("default").unwrap();
+        board.cards.push(crate::model::Card::new(
+            "From the CLI, with a longer title",
+            "todo",
+        ));
+        store.save_board(&board).unwrap();
+        store.create_board("ops", Vec::new()).unwrap();
+
+        let notices = session.poll_changes();
+        let boards: Vec<_> = notices.iter().map(|n| &n["params"]["board"]).collect();
+        assert_eq!(boards, vec!["default", "ops"]);
+        assert!(notices.iter().all(|n| n["params"]["source"] == "external"));
+        assert!(session.poll_changes().is_empty());
+    }
+}
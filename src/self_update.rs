@@ -0,0 +1,275 @@
+//! `kuk self-update`: replace the running binary with the latest GitHub
+//! release.
+//!
+//! Each release asset `kuk-<target>` is published with `kuk-<target>.sha256`
+//! (`sha256sum` output) and `kuk-<target>.sig` (hex ed25519 signature of the
+//! binary). Both are verified against the hex release public key the build
+//! was made with in `KUK_RELEASE_KEY`; a build without one refuses to
+//! install, since a checksum from the same release proves nothing about
+//! who made it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ring::digest::{SHA256, digest};
+use ring::signature::{ED25519, UnparsedPublicKey};
+use semver::Version;
+use serde::Deserialize;
+
+use crate::error::{KukError, Result};
+
+/// Latest-release endpoint. `KUK_RELEASES_URL` overrides it, e.g. for a mirror.
+pub const RELEASES_URL: &str = "https://api.github.com/repos/leslierussell/kuk/releases/latest";
+
+const RELEASE_KEY: Option<&str> = option_env!("KUK_RELEASE_KEY");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// The release version, from a tag like `v1.2.3`.
+    pub fn version(&self) -> Result<Version> {
+        Version::parse(self.tag_name.trim_start_matches('v')).map_err(|e| {
+            KukError::Other(format!(
+                "Release tag {} is not a version: {e}",
+                self.tag_name
+            ))
+        })
+    }
+
+    fn asset(&self, name: &str) -> Result<&Asset> {
+        self.assets.iter().find(|a| a.name == name).ok_or_else(|| {
+            KukError::Other(format!("Release {} has no {name} asset", self.tag_name))
+        })
+    }
+}
+
+/// Outcome of comparing the running binary with the latest release.
+#[derive(Debug, Clone)]
+pub struct UpdateCheck {
+    pub current: Version,
+    pub latest: Version,
+    pub release: Release,
+}
+
+impl UpdateCheck {
+    pub fn update_available(&self) -> bool {
+        self.latest > self.current
+    }
+}
+
+/// Name of the release asset built for this platform.
+pub fn asset_name() -> Option<&'static str> {
+    match (std::env::consts::ARCH, std::env::consts::OS) {
+        ("aarch64", "macos") => Some("kuk-aarch64-apple-darwin"),
+        ("x86_64", "macos") => Some("kuk-x86_64-apple-darwin"),
+        ("x86_64", "linux") => Some("kuk-x86_64-unknown-linux-gnu"),
+        ("aarch64", "linux") => Some("kuk-aarch64-unknown-linux-gnu"),
+        ("x86_64", "windows") => Some("kuk-x86_64-pc-windows-msvc.exe"),
+        _ => None,
+    }
+}
+
+/// Ask GitHub for the latest release.
+#[tracing::instrument(level = "info")]
+pub fn check() -> Result<UpdateCheck> {
+    let url = std::env::var("KUK_RELEASES_URL").unwrap_or_else(|_| RELEASES_URL.into());
+    let release: Release = client()?
+        .get(&url)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.json())
+        .map_err(|e| KukError::Other(format!("Could not fetch the latest release: {e}")))?;
+    Ok(UpdateCheck {
+        current: Version::parse(env!("CARGO_PKG_VERSION")).expect("crate version is semver"),
+        latest: release.version()?,
+        release,
+    })
+}
+
+/// Download this platform's asset from `release`, verify it, and replace the
+/// running executable. Returns the path that was replaced.
+#[tracing::instrument(level = "info", skip_all, fields(tag = %release.tag_name))]
+pub fn install(release: &Release) -> Result<PathBuf> {
+    let name = asset_name().ok_or_else(|| {
+        KukError::Other(format!(
+            "No release binary for {}-{}; build from source instead",
+            std::env::consts::ARCH,
+            std::env::consts::OS
+        ))
+    })?;
+    let key = release_key(RELEASE_KEY)?;
+    let client = client()?;
+    let binary = download(&client, release.asset(name)?)?;
+    let checksum = download(&client, release.asset(&format!("{name}.sha256"))?)?;
+    verify_checksum(&binary, &String::from_utf8_lossy(&checksum))?;
+    let signature = download(&client, release.asset(&format!("{name}.sig"))?)?;
+    verify_signature(&binary, &String::from_utf8_lossy(&signature), key)?;
+
+    let exe = std::env::current_exe()?;
+    replace_exe(&exe, &binary)?;
+    Ok(exe)
+}
+
+/// The key releases must be signed with, or an error saying why this build
+/// can't update itself.
+fn release_key(key: Option<&'static str>) -> Result<&'static str> {
+    key.ok_or_else(|| {
+        KukError::Other(
+            "This kuk was built without a release key (KUK_RELEASE_KEY), so it can't verify \
+             who signed a download. Update it the way you installed it instead."
+                .into(),
+        )
+    })
+}
+
+fn client() -> Result<reqwest::blocking::Client> {
+    reqwest::blocking::Client::builder()
+        .user_agent(concat!("kuk/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| KukError::Other(format!("HTTP client error: {e}")))
+}
+
+fn download(client: &reqwest::blocking::Client, asset: &Asset) -> Result<Vec<u8>> {
+    tracing::info!(asset = %asset.name, "downloading");
+    client
+        .get(&asset.browser_download_url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.bytes())
+        .map(|b| b.to_vec())
+        .map_err(|e| KukError::Other(format!("Could not download {}: {e}", asset.name)))
+}
+
+/// Check `data` against the first hex digest in a `sha256sum`-style file.
+pub fn verify_checksum(data: &[u8], checksum_file: &str) -> Result<()> {
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| KukError::Other("Checksum file is empty".into()))?;
+    let actual = to_hex(digest(&SHA256, data).as_ref());
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(KukError::Other(format!(
+            "Checksum mismatch: expected {expected}, got {actual}"
+        )))
+    }
+}
+
+/// Check a hex ed25519 `signature` of `data` against the hex `public_key`.
+pub fn verify_signature(data: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let bad = |what: &str| KukError::Other(format!("Release {what} is not valid hex"));
+    let signature = from_hex(signature.trim()).ok_or_else(|| bad("signature"))?;
+    let key = from_hex(public_key.trim()).ok_or_else(|| bad("key"))?;
+    UnparsedPublicKey::new(&ED25519, key)
+        .verify(data, &signature)
+        .map_err(|_| KukError::Other("Release signature does not match".into()))
+}
+
+/// Write the new binary next to `exe` and move it into place, so a failed
+/// download or write never leaves a half-written executable.
+fn replace_exe(exe: &Path, binary: &[u8]) -> Result<()> {
+    let staged = exe.with_extension("new");
+    fs::write(&staged, binary).map_err(KukError::write(&staged))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))
+            .map_err(KukError::write(&staged))?;
+    }
+    // Windows can't overwrite a running executable, but it can rename one
+    #[cfg(windows)]
+    {
+        let old = exe.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old).map_err(KukError::write(exe))?;
+    }
+    fs::rename(&staged, exe).map_err(KukError::write(exe))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    #[test]
+    fn checksum_accepts_sha256sum_output() {
+        let line = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9  kuk-x86_64-unknown-linux-gnu\n";
+        verify_checksum(b"hello world", line).unwrap();
+        assert!(verify_checksum(b"hello world!", line).is_err());
+        assert!(verify_checksum(b"", "").is_err());
+    }
+
+    #[test]
+    fn builds_without_a_release_key_refuse_to_install() {
+        let err = release_key(None).unwrap_err().to_string();
+        assert!(err.contains("KUK_RELEASE_KEY"));
+        assert_eq!(release_key(Some("ab")).unwrap(), "ab");
+    }
+
+    #[test]
+    fn signature_must_match_key() {
+        let rng = SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let key = to_hex(pair.public_key().as_ref());
+        let signature = to_hex(pair.sign(b"binary").as_ref());
+
+        verify_signature(b"binary", &signature, &key).unwrap();
+        assert!(verify_signature(b"tampered", &signature, &key).is_err());
+        assert!(verify_signature(b"binary", "zz", &key).is_err());
+    }
+
+    #[test]
+    fn release_versions_compare() {
+        let release: Release = serde_json::from_str(
+            r#"{"tag_name": "v0.2.0", "assets": [{"name": "kuk-x86_64-apple-darwin", "browser_download_url": "https://example.com/kuk"}]}"#,
+        )
+        .unwrap();
+        let check = UpdateCheck {
+            current: Version::new(0, 1, 0),
+            latest: release.version().unwrap(),
+            release,
+        };
+        assert!(check.update_available());
+        assert!(check.release.asset("kuk-x86_64-apple-darwin").is_ok());
+        assert!(check.release.asset("kuk-other").is_err());
+    }
+
+    #[test]
+    fn replace_exe_swaps_file_in_place() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let exe = dir.path().join("kuk");
+        fs::write(&exe, b"old").unwrap();
+        replace_exe(&exe, b"new").unwrap();
+        assert_eq!(fs::read(&exe).unwrap(), b"new");
+        assert!(!exe.with_extension("new").exists());
+    }
+}
@@ -2,18 +2,25 @@ use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
-use axum::Router;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use axum::response::Json;
-use axum::routing::{delete, get, post, put};
+use axum::routing::{get, post, put};
+use axum::{Extension, Router};
 use serde::{Deserialize, Serialize};
+use tower_http::LatencyUnit;
 use tower_http::cors::CorsLayer;
+use tower_http::trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer};
+use tracing::Level;
 
 use crate::error::KukError;
-use crate::model::{Board, Card, Column};
-use crate::storage::Store;
+use crate::model::{
+    Board, Card, CardSort, Column, Comment, DuplicatePolicy, InboxSource, Origin, Priority, Via,
+};
+use crate::policy::{Guard, POLICY_FILE};
+use crate::storage::{Store, StoreLock};
 
+use super::auth::{Access, protect};
 use super::mcp;
 
 type SharedStore = Arc<Mutex<Store>>;
@@ -35,6 +42,10 @@ impl ApiError {
         (StatusCode::NOT_FOUND, Json(ApiError { error: msg.into() }))
     }
 
+    fn conflict(msg: impl Into<String>) -> (StatusCode, Json<ApiError>) {
+        (StatusCode::CONFLICT, Json(ApiError { error: msg.into() }))
+    }
+
     fn internal(msg: impl Into<String>) -> (StatusCode, Json<ApiError>) {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -45,31 +56,119 @@ impl ApiError {
 
 type ApiResult<T> = Result<Json<T>, (StatusCode, Json<ApiError>)>;
 
-pub async fn serve(repo_root: PathBuf, port: u16, enable_mcp: bool) -> crate::error::Result<()> {
-    let store = Store::new(&repo_root);
-    if !store.is_initialized() {
-        return Err(KukError::NotInitialized);
+/// A response carrying headers, at least the board's `ETag`.
+type TaggedResult<T> = Result<(HeaderMap, Json<T>), (StatusCode, Json<ApiError>)>;
+
+/// An `ETag` header holding a board revision.
+fn etag(revision: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let value = HeaderValue::from_str(&format!("\"{revision}\"")).expect("revisions are hex");
+    headers.insert(header::ETAG, value);
+    headers
+}
+
+/// Refuse a change with 409 when the request has an `If-Match` that names
+/// neither `*` nor the board's current revision, so a client never
+/// overwrites changes it hasn't seen. Requests without `If-Match` go ahead.
+fn check_if_match(headers: &HeaderMap, revision: &str) -> Result<(), (StatusCode, Json<ApiError>)> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(());
+    };
+    let value = value.to_str().unwrap_or_default();
+    let matches = value
+        .split(',')
+        .map(|tag| tag.trim().trim_start_matches("W/").trim_matches('"'))
+        .any(|tag| tag == "*" || tag == revision);
+    if matches {
+        Ok(())
+    } else {
+        Err(ApiError::conflict(format!(
+            "Board has changed (now \"{revision}\"); reload and retry"
+        )))
     }
+}
+
+/// Take the store's file lock, for a handler to hold from loading a board
+/// to saving it, so a CLI or TUI save can't land in between and be lost.
+fn lock_files(store: &Store) -> Result<Option<StoreLock>, (StatusCode, Json<ApiError>)> {
+    store.lock().map_err(|e| ApiError::internal(e.to_string()))
+}
+
+/// Save the board and tag the response with its new revision.
+fn save_tagged(store: &Store, board: &Board) -> Result<HeaderMap, (StatusCode, Json<ApiError>)> {
+    store
+        .save_board(board)
+        .and_then(|()| store.board_revision(&board.name))
+        .map(|revision| etag(&revision))
+        .map_err(|e| ApiError::internal(e.to_string()))
+}
 
-    let shared = Arc::new(Mutex::new(store));
+/// The API routes, without transport-level layers like CORS.
+pub fn router(store: Store, enable_mcp: bool) -> Router {
+    let guard = enable_mcp.then(|| Guard::load(&store.kuk_dir()));
+    let shared = Arc::new(Mutex::new(store.with_origin(Origin::new(Via::Rest))));
 
     let mut routes = Router::new()
         .route("/v1/boards", get(list_boards))
         .route("/v1/boards/{name}", get(get_board))
         .route("/v1/boards", post(create_board))
         .route("/v1/cards", post(add_card))
+        .route("/v1/inbox", post(receive_inbox_item))
         .route("/v1/cards/{id}/move", put(move_card))
         .route("/v1/cards/{id}/archive", put(archive_card))
         .route("/v1/cards/{id}/label", put(label_card))
         .route("/v1/cards/{id}/assign", put(assign_card))
-        .route("/v1/cards/{id}", delete(delete_card))
+        .route("/v1/cards/{id}/priority", put(set_priority))
+        .route(
+            "/v1/cards/{id}/comments",
+            get(list_comments).post(add_comment),
+        )
+        .route(
+            "/v1/cards/{id}",
+            get(get_card).patch(edit_card).delete(delete_card),
+        )
+        .route("/v1/sync-log", get(sync_log))
         .route("/health", get(health));
 
-    if enable_mcp {
-        routes = routes.route("/mcp", post(mcp::mcp_handler));
+    if let Some(guard) = guard {
+        routes = routes.route(
+            "/mcp",
+            post(mcp::mcp_handler)
+                .delete(mcp::mcp_end_session)
+                .layer(Extension(mcp::McpState::new(guard))),
+        );
+    }
+
+    routes.with_state(shared)
+}
+
+pub async fn serve(repo_root: PathBuf, port: u16, enable_mcp: bool) -> crate::error::Result<()> {
+    let store = Store::new(&repo_root);
+    if !store.is_initialized() {
+        return Err(KukError::NotInitialized);
     }
 
-    let app = routes.layer(CorsLayer::permissive()).with_state(shared);
+    // Requests and their latency show up with `kuk serve -v`
+    let trace = TraceLayer::new_for_http()
+        .make_span_with(DefaultMakeSpan::new().level(Level::INFO))
+        .on_response(
+            DefaultOnResponse::new()
+                .level(Level::INFO)
+                .latency_unit(LatencyUnit::Millis),
+        );
+    let access = Access::load(&store.kuk_dir())?;
+    if enable_mcp && store.kuk_dir().join(POLICY_FILE).exists() {
+        println!("Agent policy: .kuk/{POLICY_FILE}");
+    }
+    let mut app = router(store, enable_mcp);
+    if let Some(access) = access {
+        println!(
+            "Access control: {} token(s) from .kuk/server.toml",
+            access.len()
+        );
+        app = protect(app, access);
+    }
+    let app = app.layer(CorsLayer::permissive()).layer(trace);
 
     let addr = SocketAddr::from(([127, 0, 0, 1], port));
     println!("kuk server listening on http://{addr}");
@@ -102,12 +201,63 @@ async fn list_boards(State(store): State<SharedStore>) -> ApiResult<Vec<String>>
         .map_err(|e| ApiError::internal(e.to_string()))
 }
 
-async fn get_board(State(store): State<SharedStore>, Path(name): Path<String>) -> ApiResult<Board> {
+#[derive(Deserialize)]
+struct BoardQuery {
+    /// Card order within columns, as `kuk list --sort` takes it.
+    sort: Option<String>,
+}
+
+/// A board with its cards, in column order and then `?sort=` order when
+/// one is given.
+async fn get_board(
+    State(store): State<SharedStore>,
+    Path(name): Path<String>,
+    Query(query): Query<BoardQuery>,
+) -> TaggedResult<Board> {
+    let sort: Option<CardSort> = query
+        .sort
+        .map(|s| s.parse().map_err(ApiError::new))
+        .transpose()?;
     let store = store.lock().unwrap();
-    store.load_board(&name).map(Json).map_err(|e| match e {
+    let (mut board, revision) = store.load_board_revision(&name).map_err(|e| match e {
         KukError::BoardNotFound(_) => ApiError::not_found(e.to_string()),
         _ => ApiError::internal(e.to_string()),
-    })
+    })?;
+    if let Some(sort) = sort {
+        board.sort_cards_by(sort);
+    }
+    Ok((etag(&revision), Json(board)))
+}
+
+#[derive(Deserialize)]
+struct CardQuery {
+    board: Option<String>,
+}
+
+/// A single card, tagged with the revision of the board it lives on.
+async fn get_card(
+    State(store): State<SharedStore>,
+    Path(id): Path<String>,
+    Query(query): Query<CardQuery>,
+) -> TaggedResult<Card> {
+    let store = store.lock().unwrap();
+    let name = match query.board {
+        Some(name) => name,
+        None => {
+            store
+                .load_config()
+                .map_err(|e| ApiError::internal(e.to_string()))?
+                .default_board
+        }
+    };
+    let (board, revision) = store
+        .load_board_revision(&name)
+        .map_err(|e| ApiError::not_found(e.to_string()))?;
+    let card = board
+        .resolve_card_id(&id)
+        .and_then(|id| board.find_card(&id))
+        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+    Ok((etag(&revision), Json(card.clone())))
 }
 
 #[derive(Deserialize)]
@@ -119,18 +269,9 @@ struct CreateBoardReq {
 
 fn default_columns() -> Vec<Column> {
     vec![
-        Column {
-            name: "todo".into(),
-            wip_limit: None,
-        },
-        Column {
-            name: "doing".into(),
-            wip_limit: None,
-        },
-        Column {
-            name: "done".into(),
-            wip_limit: None,
-        },
+        Column::new("todo"),
+        Column::new("doing"),
+        Column::new("done"),
     ]
 }
 
@@ -148,48 +289,131 @@ async fn create_board(
 #[derive(Deserialize)]
 struct AddCardReq {
     title: String,
-    #[serde(default = "default_column")]
-    column: String,
+    /// Defaults to `new_cards.column` in config.json, else the first column
+    #[serde(default)]
+    column: Option<String>,
     #[serde(default)]
     labels: Vec<String>,
     #[serde(default)]
     assignee: Option<String>,
+    #[serde(default)]
+    priority: Option<Priority>,
     #[serde(default = "default_board_name")]
     board: String,
+    /// Add even if the duplicate policy would reject the title or the
+    /// column is at its WIP limit
+    #[serde(default)]
+    force: bool,
 }
 
-fn default_column() -> String {
-    "todo".into()
-}
 fn default_board_name() -> String {
     "default".into()
 }
 
+/// Adds a card. Under the `warn` duplicate policy a similar existing card is
+/// reported in the `X-Kuk-Warning` header; under `reject` the request fails
+/// with 409 unless `force` is set. So does adding to a column at its WIP
+/// limit.
 async fn add_card(
     State(store): State<SharedStore>,
+    headers: HeaderMap,
     Json(req): Json<AddCardReq>,
-) -> ApiResult<Card> {
+) -> TaggedResult<Card> {
     let store = store.lock().unwrap();
-    let mut board = store
-        .load_board(&req.board)
+    let _lock = lock_files(&store)?;
+    let (mut board, revision) = store
+        .load_board_revision(&req.board)
         .map_err(|e| ApiError::not_found(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
 
-    if !board.has_column(&req.column) {
-        return Err(ApiError::new(format!("Column not found: {}", req.column)));
+    let config = store.load_config().unwrap_or_default();
+    let column = req
+        .column
+        .or_else(|| config.new_cards.column(&board))
+        .unwrap_or_default();
+    if !board.has_column(&column) {
+        return Err(ApiError::new(format!("Column not found: {column}")));
     }
 
-    let mut card = Card::new(&req.title, &req.column);
-    card.order = board.next_order(&req.column);
+    let mut card = Card::new(&req.title, &column);
     card.labels = req.labels;
     card.assignee = req.assignee;
+    card.priority = req.priority;
+    config.new_cards.apply(&mut card);
+    board
+        .check_labels(&card.labels)
+        .map_err(|l| ApiError::new(KukError::UnknownLabel(l.into()).to_string()))?;
+    if !req.force {
+        check_wip(&board, &column, None)?;
+    }
 
-    let result = card.clone();
-    board.cards.push(card);
-    store
-        .save_board(&board)
+    let policy = config.duplicates;
+    let mut response = HeaderMap::new();
+    if let Some(dup) = board.duplicate_of(&req.title, policy) {
+        let msg = format!("Similar card already exists: {} ({})", dup.title, dup.id);
+        if policy == DuplicatePolicy::Reject && !req.force {
+            return Err(ApiError::conflict(msg));
+        }
+        if let Ok(value) = HeaderValue::from_str(&msg) {
+            response.insert("x-kuk-warning", value);
+        }
+    }
+
+    card.order = board.next_order(&column);
+    let result = board.add_card(card).clone();
+    response.extend(save_tagged(&store, &board)?);
+    Ok((response, Json(result)))
+}
+
+#[derive(Deserialize)]
+struct InboxReq {
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default = "default_inbox_source")]
+    source: String,
+    #[serde(default)]
+    sender: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    board: Option<String>,
+}
+
+fn default_inbox_source() -> String {
+    "api".into()
+}
+
+/// Files a minimal payload from a form or webhook into the intake column
+/// (`inbox_column` in config.json) of the given or default board.
+async fn receive_inbox_item(
+    State(store): State<SharedStore>,
+    headers: HeaderMap,
+    Json(req): Json<InboxReq>,
+) -> TaggedResult<Card> {
+    if req.title.trim().is_empty() {
+        return Err(ApiError::new("Title must not be empty"));
+    }
+    let store = store.lock().unwrap();
+    let _lock = lock_files(&store)?;
+    let config = store
+        .load_config()
         .map_err(|e| ApiError::internal(e.to_string()))?;
+    let board_name = req.board.unwrap_or(config.default_board);
+    let (mut board, revision) = store
+        .load_board_revision(&board_name)
+        .map_err(|e| ApiError::not_found(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
 
-    Ok(Json(result))
+    let mut source = InboxSource::new(req.source);
+    source.sender = req.sender;
+    source.url = req.url;
+    let card = board
+        .receive(&config.inbox_column, req.title.trim(), req.body, source)
+        .clone();
+
+    let tag = save_tagged(&store, &board)?;
+    Ok((tag, Json(card)))
 }
 
 #[derive(Deserialize)]
@@ -197,17 +421,40 @@ struct MoveCardReq {
     to: String,
     #[serde(default = "default_board_name")]
     board: String,
+    /// Move even if the target column is at its WIP limit
+    #[serde(default)]
+    force: bool,
+}
+
+/// 409 when putting a card in `column` would go over its WIP limit.
+fn check_wip(
+    board: &Board,
+    column: &str,
+    card_id: Option<&str>,
+) -> Result<(), (StatusCode, Json<ApiError>)> {
+    match board.wip_exceeded(column, card_id) {
+        Some(limit) => Err(ApiError::conflict(
+            KukError::WipLimitReached(format!(
+                "{column} is at its limit of {limit} cards. Pass \"force\": true to go over it."
+            ))
+            .to_string(),
+        )),
+        None => Ok(()),
+    }
 }
 
 async fn move_card(
     State(store): State<SharedStore>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(req): Json<MoveCardReq>,
-) -> ApiResult<Card> {
+) -> TaggedResult<Card> {
     let store = store.lock().unwrap();
-    let mut board = store
-        .load_board(&req.board)
+    let _lock = lock_files(&store)?;
+    let (mut board, revision) = store
+        .load_board_revision(&req.board)
         .map_err(|e| ApiError::not_found(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
 
     if !board.has_column(&req.to) {
         return Err(ApiError::new(format!("Column not found: {}", req.to)));
@@ -216,32 +463,34 @@ async fn move_card(
     let card_id = board
         .resolve_card_id(&id)
         .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+    if !req.force {
+        check_wip(&board, &req.to, Some(&card_id))?;
+    }
 
-    let next_order = board.next_order(&req.to);
-    let card = board
-        .find_card_mut(&card_id)
-        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
-
-    card.column = req.to;
-    card.order = next_order;
-    card.updated_at = chrono::Utc::now();
+    if !board.move_card(&card_id, &req.to) {
+        return Err(ApiError::not_found(format!("Card not found: {id}")));
+    }
+    let card = board.find_card(&card_id).unwrap();
     let result = card.clone();
 
-    store
-        .save_board(&board)
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-
-    Ok(Json(result))
+    let tag = save_tagged(&store, &board)?;
+    Ok((tag, Json(result)))
 }
 
-async fn archive_card(State(store): State<SharedStore>, Path(id): Path<String>) -> ApiResult<Card> {
+async fn archive_card(
+    State(store): State<SharedStore>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> TaggedResult<Card> {
     let store = store.lock().unwrap();
+    let _lock = lock_files(&store)?;
     let config = store
         .load_config()
         .map_err(|e| ApiError::internal(e.to_string()))?;
-    let mut board = store
-        .load_board(&config.default_board)
+    let (mut board, revision) = store
+        .load_board_revision(&config.default_board)
         .map_err(|e| ApiError::internal(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
 
     let card_id = board
         .resolve_card_id(&id)
@@ -251,15 +500,12 @@ async fn archive_card(State(store): State<SharedStore>, Path(id): Path<String>)
         .find_card_mut(&card_id)
         .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
 
-    card.archived = true;
+    card.archive();
     card.updated_at = chrono::Utc::now();
     let result = card.clone();
 
-    store
-        .save_board(&board)
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-
-    Ok(Json(result))
+    let tag = save_tagged(&store, &board)?;
+    Ok((tag, Json(result)))
 }
 
 #[derive(Deserialize)]
@@ -271,32 +517,39 @@ struct LabelReq {
 async fn label_card(
     State(store): State<SharedStore>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(req): Json<LabelReq>,
-) -> ApiResult<Card> {
+) -> TaggedResult<Card> {
     let store = store.lock().unwrap();
+    let _lock = lock_files(&store)?;
     let config = store
         .load_config()
         .map_err(|e| ApiError::internal(e.to_string()))?;
-    let mut board = store
-        .load_board(&config.default_board)
+    let (mut board, revision) = store
+        .load_board_revision(&config.default_board)
         .map_err(|e| ApiError::internal(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
 
     let card_id = board
         .resolve_card_id(&id)
         .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
 
+    if req.action == "add" {
+        board
+            .check_labels(std::slice::from_ref(&req.tag))
+            .map_err(|l| ApiError::new(KukError::UnknownLabel(l.into()).to_string()))?;
+    }
+
     let card = board
         .find_card_mut(&card_id)
         .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
 
     match req.action.as_str() {
         "add" => {
-            if !card.labels.contains(&req.tag) {
-                card.labels.push(req.tag);
-            }
+            card.add_label(&req.tag);
         }
         "remove" => {
-            card.labels.retain(|l| l != &req.tag);
+            card.remove_label(&req.tag);
         }
         _ => return Err(ApiError::new("action must be 'add' or 'remove'")),
     }
@@ -304,11 +557,8 @@ async fn label_card(
     card.updated_at = chrono::Utc::now();
     let result = card.clone();
 
-    store
-        .save_board(&board)
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-
-    Ok(Json(result))
+    let tag = save_tagged(&store, &board)?;
+    Ok((tag, Json(result)))
 }
 
 #[derive(Deserialize)]
@@ -319,15 +569,18 @@ struct AssignReq {
 async fn assign_card(
     State(store): State<SharedStore>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(req): Json<AssignReq>,
-) -> ApiResult<Card> {
+) -> TaggedResult<Card> {
     let store = store.lock().unwrap();
+    let _lock = lock_files(&store)?;
     let config = store
         .load_config()
         .map_err(|e| ApiError::internal(e.to_string()))?;
-    let mut board = store
-        .load_board(&config.default_board)
+    let (mut board, revision) = store
+        .load_board_revision(&config.default_board)
         .map_err(|e| ApiError::internal(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
 
     let card_id = board
         .resolve_card_id(&id)
@@ -337,28 +590,184 @@ async fn assign_card(
         .find_card_mut(&card_id)
         .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
 
-    card.assignee = Some(req.user);
+    card.set_assignee(Some(req.user));
     card.updated_at = chrono::Utc::now();
     let result = card.clone();
 
-    store
-        .save_board(&board)
+    let tag = save_tagged(&store, &board)?;
+    Ok((tag, Json(result)))
+}
+
+/// Fields left out stay as they are; an empty description removes it.
+#[derive(Deserialize)]
+struct EditReq {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+async fn edit_card(
+    State(store): State<SharedStore>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<EditReq>,
+) -> TaggedResult<Card> {
+    if req.title.as_ref().is_some_and(|t| t.trim().is_empty()) {
+        return Err(ApiError::new("Title must not be empty"));
+    }
+    let store = store.lock().unwrap();
+    let _lock = lock_files(&store)?;
+    let config = store
+        .load_config()
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let (mut board, revision) = store
+        .load_board_revision(&config.default_board)
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
+
+    let card_id = board
+        .resolve_card_id(&id)
+        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+
+    if let Some(title) = req.title {
+        card.title = title.trim().into();
+    }
+    if let Some(description) = req.description {
+        let description = description.trim();
+        card.description = (!description.is_empty()).then(|| description.into());
+    }
+    card.updated_at = chrono::Utc::now();
+    let result = card.clone();
+
+    let tag = save_tagged(&store, &board)?;
+    Ok((tag, Json(result)))
+}
+
+async fn list_comments(
+    State(store): State<SharedStore>,
+    Path(id): Path<String>,
+    Query(query): Query<CardQuery>,
+) -> TaggedResult<Vec<Comment>> {
+    let store = store.lock().unwrap();
+    let name = match query.board {
+        Some(name) => name,
+        None => {
+            store
+                .load_config()
+                .map_err(|e| ApiError::internal(e.to_string()))?
+                .default_board
+        }
+    };
+    let (board, revision) = store
+        .load_board_revision(&name)
+        .map_err(|e| ApiError::not_found(e.to_string()))?;
+    let card = board
+        .resolve_card_id(&id)
+        .and_then(|id| board.find_card(&id))
+        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+    Ok((etag(&revision), Json(card.comments.clone())))
+}
+
+/// Without an author the comment is signed `api`.
+#[derive(Deserialize)]
+struct CommentReq {
+    body: String,
+    #[serde(default)]
+    author: Option<String>,
+}
+
+async fn add_comment(
+    State(store): State<SharedStore>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<CommentReq>,
+) -> TaggedResult<Card> {
+    if req.body.trim().is_empty() {
+        return Err(ApiError::new("Comment must not be empty"));
+    }
+    let store = store.lock().unwrap();
+    let _lock = lock_files(&store)?;
+    let config = store
+        .load_config()
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let (mut board, revision) = store
+        .load_board_revision(&config.default_board)
         .map_err(|e| ApiError::internal(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
+
+    let card_id = board
+        .resolve_card_id(&id)
+        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+
+    let author = req.author.unwrap_or_else(|| "api".into());
+    card.add_comment(author, req.body.trim());
+    let result = card.clone();
+
+    let tag = save_tagged(&store, &board)?;
+    Ok((tag, Json(result)))
+}
+
+/// `null` clears the priority.
+#[derive(Deserialize)]
+struct PriorityReq {
+    priority: Option<Priority>,
+}
+
+async fn set_priority(
+    State(store): State<SharedStore>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<PriorityReq>,
+) -> TaggedResult<Card> {
+    let store = store.lock().unwrap();
+    let _lock = lock_files(&store)?;
+    let config = store
+        .load_config()
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let (mut board, revision) = store
+        .load_board_revision(&config.default_board)
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
+
+    let card_id = board
+        .resolve_card_id(&id)
+        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+
+    let card = board
+        .find_card_mut(&card_id)
+        .ok_or_else(|| ApiError::not_found(format!("Card not found: {id}")))?;
+
+    card.priority = req.priority;
+    card.updated_at = chrono::Utc::now();
+    let result = card.clone();
 
-    Ok(Json(result))
+    let tag = save_tagged(&store, &board)?;
+    Ok((tag, Json(result)))
 }
 
 async fn delete_card(
     State(store): State<SharedStore>,
     Path(id): Path<String>,
-) -> ApiResult<serde_json::Value> {
+    headers: HeaderMap,
+) -> TaggedResult<serde_json::Value> {
     let store = store.lock().unwrap();
+    let _lock = lock_files(&store)?;
     let config = store
         .load_config()
         .map_err(|e| ApiError::internal(e.to_string()))?;
-    let mut board = store
-        .load_board(&config.default_board)
+    let (mut board, revision) = store
+        .load_board_revision(&config.default_board)
         .map_err(|e| ApiError::internal(e.to_string()))?;
+    check_if_match(&headers, &revision)?;
 
     let card_id = board
         .resolve_card_id(&id)
@@ -371,18 +780,48 @@ async fn delete_card(
 
     board.cards.retain(|c| c.id != card_id);
 
-    store
-        .save_board(&board)
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-
-    Ok(Json(
-        serde_json::json!({"deleted": card_id, "title": title}),
+    let tag = save_tagged(&store, &board)?;
+    Ok((
+        tag,
+        Json(serde_json::json!({"deleted": card_id, "title": title})),
     ))
 }
 
+#[derive(Deserialize)]
+struct SyncLogQuery {
+    last: Option<usize>,
+}
+
+/// Past `kuk-pm sync` runs from `.kuk/sync-log.jsonl`, oldest first.
+async fn sync_log(
+    State(store): State<SharedStore>,
+    Query(query): Query<SyncLogQuery>,
+) -> ApiResult<Vec<serde_json::Value>> {
+    let store = store.lock().unwrap();
+    let path = store.kuk_dir().join("sync-log.jsonl");
+    if !path.exists() {
+        return Ok(Json(Vec::new()));
+    }
+
+    let data = std::fs::read_to_string(&path).map_err(|e| ApiError::internal(e.to_string()))?;
+    let mut entries = data
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<serde_json::Value>, _>>()
+        .map_err(|e| ApiError::internal(format!("Invalid sync log: {e}")))?;
+
+    if let Some(n) = query.last {
+        let skip = entries.len().saturating_sub(n);
+        entries.drain(..skip);
+    }
+    Ok(Json(entries))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::Event;
     use axum::body::Body;
     use axum::http::{self, Request};
     use tempfile::TempDir;
@@ -393,20 +832,7 @@ mod tests {
         let store = Store::new(dir.path());
         store.init().unwrap();
 
-        let shared = Arc::new(Mutex::new(Store::new(dir.path())));
-        let app = Router::new()
-            .route("/v1/boards", get(list_boards))
-            .route("/v1/boards/{name}", get(get_board))
-            .route("/v1/boards", post(create_board))
-            .route("/v1/cards", post(add_card))
-            .route("/v1/cards/{id}/move", put(move_card))
-            .route("/v1/cards/{id}/archive", put(archive_card))
-            .route("/v1/cards/{id}/label", put(label_card))
-            .route("/v1/cards/{id}/assign", put(assign_card))
-            .route("/v1/cards/{id}", delete(delete_card))
-            .route("/health", get(health))
-            .route("/mcp", post(mcp::mcp_handler))
-            .with_state(shared);
+        let app = router(Store::new(dir.path()), true);
 
         (dir, app)
     }
@@ -469,6 +895,37 @@ mod tests {
         assert_eq!(json["columns"].as_array().unwrap().len(), 3);
     }
 
+    #[tokio::test]
+    async fn get_board_sorted() {
+        let (dir, app) = test_app();
+        let store = Store::new(dir.path());
+        let mut board = store.load_board("default").unwrap();
+        for title in ["beta", "Alpha", "gamma"] {
+            let mut card = Card::new(title, "todo");
+            card.order = board.next_order("todo");
+            board.cards.push(card);
+        }
+        store.save_board(&board).unwrap();
+        let get = |uri: &str| {
+            app.clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+
+        let resp = get("/v1/boards/default?sort=title:desc").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json = body_json(resp.into_body()).await;
+        let titles: Vec<&str> = json["cards"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles, ["gamma", "beta", "Alpha"]);
+
+        let resp = get("/v1/boards/default?sort=size").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn get_board_not_found() {
         let (_dir, app) = test_app();
@@ -544,56 +1001,214 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn move_card_via_api() {
-        let (_dir, app) = test_app();
+    async fn add_card_uses_the_configured_defaults() {
+        let (dir, app) = test_app();
+        let store = Store::new(dir.path());
+        let mut config = store.load_config().unwrap();
+        config.new_cards.column = Some("doing".into());
+        config.new_cards.labels = vec!["triage".into()];
+        store.save_config(&config).unwrap();
 
-        // Add card
         let resp = app
-            .clone()
             .oneshot(
                 Request::builder()
                     .method(http::Method::POST)
                     .uri("/v1/cards")
                     .header("content-type", "application/json")
                     .body(Body::from(
-                        serde_json::json!({"title": "Move me"}).to_string(),
+                        serde_json::json!({"title": "Triage me", "labels": ["bug"]}).to_string(),
                     ))
                     .unwrap(),
             )
             .await
             .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
         let card = body_json(resp.into_body()).await;
-        let card_id = card["id"].as_str().unwrap();
+        assert_eq!(card["column"], "doing");
+        assert_eq!(card["labels"], serde_json::json!(["triage", "bug"]));
+    }
+
+    #[tokio::test]
+    async fn add_duplicate_card_follows_policy() {
+        let (dir, app) = test_app();
+        let store = Store::new(dir.path());
+        let mut config = store.load_config().unwrap();
+        config.duplicates = DuplicatePolicy::Reject;
+        store.save_config(&config).unwrap();
+
+        let add = |body: serde_json::Value| {
+            Request::builder()
+                .method(http::Method::POST)
+                .uri("/v1/cards")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
 
-        // Move card
         let resp = app
-            .oneshot(
-                Request::builder()
-                    .method(http::Method::PUT)
-                    .uri(format!("/v1/cards/{card_id}/move"))
-                    .header("content-type", "application/json")
-                    .body(Body::from(serde_json::json!({"to": "doing"}).to_string()))
-                    .unwrap(),
-            )
+            .clone()
+            .oneshot(add(serde_json::json!({"title": "Fix login"})))
             .await
             .unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
-        let moved = body_json(resp.into_body()).await;
-        assert_eq!(moved["column"], "doing");
-    }
 
-    #[tokio::test]
-    async fn delete_card_via_api() {
-        let (_dir, app) = test_app();
+        let resp = app
+            .clone()
+            .oneshot(add(serde_json::json!({"title": "fix login!"})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
 
-        // Add card
         let resp = app
             .clone()
-            .oneshot(
-                Request::builder()
-                    .method(http::Method::POST)
-                    .uri("/v1/cards")
-                    .header("content-type", "application/json")
+            .oneshot(add(
+                serde_json::json!({"title": "fix login!", "force": true}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        config.duplicates = DuplicatePolicy::Warn;
+        store.save_config(&config).unwrap();
+        let resp = app
+            .oneshot(add(serde_json::json!({"title": "Fix login."})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(
+            resp.headers()["x-kuk-warning"]
+                .to_str()
+                .unwrap()
+                .contains("Fix login")
+        );
+    }
+
+    #[tokio::test]
+    async fn inbox_item_lands_in_intake_column() {
+        let (dir, app) = test_app();
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/v1/inbox")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({
+                            "title": "Export is broken",
+                            "body": "CSV export times out",
+                            "source": "email",
+                            "sender": "ana@example.com"
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let card = body_json(resp.into_body()).await;
+        assert_eq!(card["column"], "inbox");
+        assert_eq!(card["metadata"]["inbox"]["source"], "email");
+        assert_eq!(card["metadata"]["inbox"]["sender"], "ana@example.com");
+
+        let board = Store::new(dir.path()).load_board("default").unwrap();
+        assert_eq!(board.columns[0].name, "inbox");
+        assert_eq!(board.column_cards("inbox").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn move_card_via_api() {
+        let (_dir, app) = test_app();
+
+        // Add card
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/v1/cards")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({"title": "Move me"}).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let card = body_json(resp.into_body()).await;
+        let card_id = card["id"].as_str().unwrap();
+
+        // Move card
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PUT)
+                    .uri(format!("/v1/cards/{card_id}/move"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::json!({"to": "doing"}).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let moved = body_json(resp.into_body()).await;
+        assert_eq!(moved["column"], "doing");
+    }
+
+    #[tokio::test]
+    async fn move_over_wip_limit_needs_force() {
+        let (dir, app) = test_app();
+        let store = Store::new(dir.path());
+        let mut board = store.load_board("default").unwrap();
+        board.set_wip_limit("doing", Some(1)).unwrap();
+        board.add_card(Card::new("Busy", "doing"));
+        let card_id = board.add_card(Card::new("Waiting", "todo")).id.clone();
+        store.save_board(&board).unwrap();
+
+        let move_to_doing = |body: serde_json::Value| {
+            Request::builder()
+                .method(http::Method::PUT)
+                .uri(format!("/v1/cards/{card_id}/move"))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(move_to_doing(serde_json::json!({"to": "doing"})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let error = body_json(resp.into_body()).await;
+        assert!(
+            error["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("WIP limit reached: doing")
+        );
+
+        let resp = app
+            .oneshot(move_to_doing(
+                serde_json::json!({"to": "doing", "force": true}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn delete_card_via_api() {
+        let (_dir, app) = test_app();
+
+        // Add card
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/v1/cards")
+                    .header("content-type", "application/json")
                     .body(Body::from(
                         serde_json::json!({"title": "Delete me"}).to_string(),
                     ))
@@ -620,6 +1235,44 @@ mod tests {
         assert_eq!(result["deleted"], card_id);
     }
 
+    #[tokio::test]
+    async fn sync_log_empty_then_limited() {
+        let (dir, app) = test_app();
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/sync-log")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(body_json(resp.into_body()).await, serde_json::json!([]));
+
+        let log: String = (0..3)
+            .map(|i| format!("{{\"run\":{i},\"actions\":[]}}\n"))
+            .collect();
+        std::fs::write(dir.path().join(".kuk/sync-log.jsonl"), log).unwrap();
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/sync-log?last=2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json = body_json(resp.into_body()).await;
+        let runs = json.as_array().unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1]["run"], 2);
+    }
+
     #[tokio::test]
     async fn archive_card_via_api() {
         let (_dir, app) = test_app();
@@ -739,6 +1392,190 @@ mod tests {
         assert_eq!(result["assignee"], "leslie");
     }
 
+    #[tokio::test]
+    async fn edit_card_via_api() {
+        let (_dir, app) = test_app();
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/v1/cards")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({"title": "Typo in titel"}).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let card = body_json(resp.into_body()).await;
+        let card_id = card["id"].as_str().unwrap().to_string();
+
+        let patch = |body: serde_json::Value| {
+            Request::builder()
+                .method(http::Method::PATCH)
+                .uri(format!("/v1/cards/{card_id}"))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(patch(serde_json::json!({"description": "Old"})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = app
+            .clone()
+            .oneshot(patch(serde_json::json!({"title": "Typo in title"})))
+            .await
+            .unwrap();
+        let result = body_json(resp.into_body()).await;
+        assert_eq!(result["title"], "Typo in title");
+        assert_eq!(result["description"], "Old");
+
+        let resp = app
+            .clone()
+            .oneshot(patch(serde_json::json!({"description": ""})))
+            .await
+            .unwrap();
+        let result = body_json(resp.into_body()).await;
+        assert!(result.get("description").is_none_or(|d| d.is_null()));
+
+        let resp = app
+            .oneshot(patch(serde_json::json!({"title": "  "})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn comments_via_api() {
+        let (_dir, app) = test_app();
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/v1/cards")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({"title": "Port the parser"}).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let card = body_json(resp.into_body()).await;
+        let card_id = card["id"].as_str().unwrap().to_string();
+
+        let post = |body: serde_json::Value| {
+            Request::builder()
+                .method(http::Method::POST)
+                .uri(format!("/v1/cards/{card_id}/comments"))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(post(serde_json::json!({"body": "Lexer done"})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let result = body_json(resp.into_body()).await;
+        assert_eq!(result["comments"][0]["author"], "api");
+        app.clone()
+            .oneshot(post(
+                serde_json::json!({"body": "Parser next", "author": "agent"}),
+            ))
+            .await
+            .unwrap();
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/v1/cards/{card_id}/comments"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let comments = body_json(resp.into_body()).await;
+        let comments = comments.as_array().unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[1]["author"], "agent");
+        assert_eq!(comments[1]["body"], "Parser next");
+
+        let resp = app
+            .oneshot(post(serde_json::json!({"body": " "})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn priority_via_api() {
+        let (_dir, app) = test_app();
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/v1/cards")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({"title": "Hotfix", "priority": "high"}).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let card = body_json(resp.into_body()).await;
+        assert_eq!(card["priority"], "high");
+        let card_id = card["id"].as_str().unwrap().to_string();
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PUT)
+                    .uri(format!("/v1/cards/{card_id}/priority"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({"priority": "someday"}).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(resp.status().is_client_error());
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PUT)
+                    .uri(format!("/v1/cards/{card_id}/priority"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({"priority": null}).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let result = body_json(resp.into_body()).await;
+        assert!(result.get("priority").is_none());
+    }
+
     #[tokio::test]
     async fn create_board_via_api() {
         let (_dir, app) = test_app();
@@ -935,4 +1772,467 @@ mod tests {
         let moved: serde_json::Value = serde_json::from_str(content.as_str().unwrap()).unwrap();
         assert_eq!(moved["column"], "doing");
     }
+
+    #[tokio::test]
+    async fn mcp_batch_tools_report_each_item() {
+        let (_dir, app) = test_app();
+        let call = |name: &str, arguments: serde_json::Value| {
+            let req = Request::builder()
+                .method(http::Method::POST)
+                .uri("/mcp")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": 5,
+                        "method": "tools/call",
+                        "params": {"name": name, "arguments": arguments}
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let json = body_json(app.oneshot(req).await.unwrap().into_body()).await;
+                let text = json["result"]["content"][0]["text"].as_str().unwrap();
+                serde_json::from_str::<serde_json::Value>(text).unwrap()
+            }
+        };
+
+        let added = call(
+            "kuk_add_cards",
+            serde_json::json!({"cards": [
+                {"title": "Plan"},
+                {"title": "Lost", "column": "nowhere"},
+                {"title": "Build", "priority": "high"}
+            ]}),
+        )
+        .await;
+        assert_eq!(
+            (added["applied"].as_u64(), added["failed"].as_u64()),
+            (Some(2), Some(1))
+        );
+        assert_eq!(added["results"][1]["error"], "Column not found: nowhere");
+        let build = added["results"][2]["id"].as_str().unwrap();
+
+        let moved = call(
+            "kuk_move_cards",
+            serde_json::json!({"moves": [
+                {"id": build, "to": "doing"},
+                {"id": "missing", "to": "done"}
+            ]}),
+        )
+        .await;
+        assert_eq!(moved["results"][0]["column"], "doing");
+        assert_eq!(moved["results"][1]["ok"], false);
+
+        let board = call("kuk_list_cards", serde_json::json!({})).await;
+        let cards = board["cards"].as_array().unwrap();
+        assert_eq!(cards.len(), 2);
+        assert!(
+            cards
+                .iter()
+                .any(|c| c["title"] == "Build" && c["column"] == "doing")
+        );
+    }
+
+    #[tokio::test]
+    async fn mcp_sessions_over_streamable_http() {
+        let (dir, app) = test_app();
+        let post = |session: Option<&str>, body: serde_json::Value| {
+            let mut req = Request::builder()
+                .method(http::Method::POST)
+                .uri("/mcp")
+                .header("content-type", "application/json")
+                .header("accept", "application/json, text/event-stream");
+            if let Some(session) = session {
+                req = req.header("mcp-session-id", session);
+            }
+            let req = req.body(Body::from(body.to_string())).unwrap();
+            app.clone().oneshot(req)
+        };
+
+        let resp = post(
+            None,
+            serde_json::json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": {"protocolVersion": "2024-11-05", "clientInfo": {"name": "claude-code"}}
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let session = resp.headers()["mcp-session-id"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(resp.headers()["content-type"], "text/event-stream");
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let stream = String::from_utf8(bytes.to_vec()).unwrap();
+        let data = stream
+            .strip_prefix("event: message\ndata: ")
+            .unwrap()
+            .trim_end();
+        let init: serde_json::Value = serde_json::from_str(data).unwrap();
+        assert_eq!(init["result"]["protocolVersion"], "2024-11-05");
+
+        let resp = post(
+            Some(&session),
+            serde_json::json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let call = serde_json::json!({
+            "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+            "params": {"name": "kuk_add_card", "arguments": {"title": "Remote"}}
+        });
+        let resp = post(Some(&session), call.clone()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let board = Store::new(dir.path()).load_board("default").unwrap();
+        let origin = Event::history(&board.cards[0])[0].origin.clone().unwrap();
+        assert_eq!(origin.to_string(), "mcp (claude-code)");
+
+        let end = Request::builder()
+            .method(http::Method::DELETE)
+            .uri("/mcp")
+            .header("mcp-session-id", &session)
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(end).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        let resp = post(Some(&session), call).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let listen = Request::builder()
+            .method(http::Method::GET)
+            .uri("/mcp")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(listen).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn mcp_agent_policy_blocks_calls_with_the_rule() {
+        let dir = TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+        std::fs::write(
+            store.kuk_dir().join("agent-policy.toml"),
+            "deny_tools = [\"kuk_delete_card\"]\nmax_cards_per_session = 1\ncolumns = [\"todo\"]\n",
+        )
+        .unwrap();
+        let app = router(store, true);
+        let call = |name: &str, arguments: serde_json::Value| {
+            let req = Request::builder()
+                .method(http::Method::POST)
+                .uri("/mcp")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": 6,
+                        "method": "tools/call",
+                        "params": {"name": name, "arguments": arguments}
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            let app = app.clone();
+            async move { body_json(app.oneshot(req).await.unwrap().into_body()).await }
+        };
+
+        let moved = call(
+            "kuk_add_card",
+            serde_json::json!({"title": "Ship", "column": "doing"}),
+        )
+        .await;
+        assert_eq!(moved["error"]["code"], -32001);
+        assert!(
+            moved["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("columns = [\"todo\"], not doing")
+        );
+        let added = call("kuk_add_card", serde_json::json!({"title": "Plan"})).await;
+        assert!(added["result"].is_object());
+        let capped = call("kuk_add_card", serde_json::json!({"title": "More"})).await;
+        assert!(
+            capped["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("max_cards_per_session = 1")
+        );
+        let deleted = call("kuk_delete_card", serde_json::json!({"id": "1"})).await;
+        assert_eq!(
+            deleted["error"]["message"],
+            "Blocked by .kuk/agent-policy.toml: kuk_delete_card is listed in deny_tools"
+        );
+    }
+
+    #[tokio::test]
+    async fn mcp_sessions_each_get_their_own_card_limit() {
+        let dir = TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+        std::fs::write(
+            store.kuk_dir().join("agent-policy.toml"),
+            "max_cards_per_session = 1\n",
+        )
+        .unwrap();
+        let app = router(store, true);
+        let post = |session: Option<String>, body: serde_json::Value| {
+            let mut req = Request::builder()
+                .method(http::Method::POST)
+                .uri("/mcp")
+                .header("content-type", "application/json");
+            if let Some(session) = session {
+                req = req.header("mcp-session-id", session);
+            }
+            let req = req.body(Body::from(body.to_string())).unwrap();
+            let app = app.clone();
+            async move { app.oneshot(req).await.unwrap() }
+        };
+        let open = || async {
+            let init = serde_json::json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": {"protocolVersion": "2024-11-05", "clientInfo": {"name": "agent"}}
+            });
+            let resp = post(None, init).await;
+            resp.headers()["mcp-session-id"]
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+        let add = |session: String, title: &str| {
+            let call = serde_json::json!({
+                "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+                "params": {"name": "kuk_add_card", "arguments": {"title": title}}
+            });
+            let resp = post(Some(session), call);
+            async move { body_json(resp.await.into_body()).await }
+        };
+
+        let (first, second) = (open().await, open().await);
+        assert_ne!(first, second);
+        for session in [&first, &second] {
+            let added = add(session.clone(), "Plan").await;
+            assert!(added["result"].is_object(), "{added}");
+            let capped = add(session.clone(), "More").await;
+            assert!(
+                capped["error"]["message"]
+                    .as_str()
+                    .unwrap()
+                    .contains("max_cards_per_session = 1")
+            );
+        }
+        // A new session starts from zero again
+        let added = add(open().await, "Later").await;
+        assert!(added["result"].is_object(), "{added}");
+    }
+
+    #[tokio::test]
+    async fn changes_record_whether_rest_or_mcp_made_them() {
+        let (dir, app) = test_app();
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/v1/cards")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::json!({"title": "Ship"}).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let req = Request::builder()
+            .method(http::Method::POST)
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .header("user-agent", "claude-code")
+            .body(Body::from(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "tools/call",
+                    "params": {"name": "kuk_move_card", "arguments": {"id": "1", "to": "doing"}}
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let moved = body_json(app.oneshot(req).await.unwrap().into_body()).await;
+        assert!(moved["result"].is_object(), "{moved}");
+
+        let board = Store::new(dir.path()).load_board("default").unwrap();
+        let origins: Vec<String> = Event::history(&board.cards[0])
+            .iter()
+            .map(|e| e.origin.as_ref().unwrap().to_string())
+            .collect();
+        assert_eq!(origins, ["rest", "mcp (claude-code)"]);
+    }
+
+    #[tokio::test]
+    async fn tokens_are_limited_to_their_role() {
+        let (dir, app) = test_app();
+        std::fs::write(
+            dir.path().join(".kuk/server.toml"),
+            r#"
+            [[tokens]]
+            name = "stakeholders"
+            token = "view"
+            role = "viewer"
+
+            [[tokens]]
+            token = "edit"
+            role = "editor"
+            "#,
+        )
+        .unwrap();
+        let access = Access::load(&dir.path().join(".kuk")).unwrap().unwrap();
+        let app = protect(app, access);
+        let call =
+            |method: http::Method, uri: &str, token: Option<&str>, body: serde_json::Value| {
+                let mut req = Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json");
+                if let Some(token) = token {
+                    req = req.header("authorization", format!("Bearer {token}"));
+                }
+                let req = req.body(Body::from(body.to_string())).unwrap();
+                let app = app.clone();
+                async move { app.oneshot(req).await.unwrap().status() }
+            };
+        let none = serde_json::Value::Null;
+        let add = serde_json::json!({"title": "Shared"});
+        let mcp_add = serde_json::json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": {"name": "kuk_add_card", "arguments": {"title": "Via MCP"}}
+        });
+        let mcp_list = serde_json::json!({
+            "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+            "params": {"name": "kuk_list_cards", "arguments": {}}
+        });
+
+        assert_eq!(
+            call(http::Method::GET, "/health", None, none.clone()).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(http::Method::GET, "/v1/boards", None, none.clone()).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call(http::Method::GET, "/v1/boards", Some("nope"), none.clone()).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call(
+                http::Method::GET,
+                "/v1/boards/default",
+                Some("view"),
+                none.clone()
+            )
+            .await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(http::Method::POST, "/v1/cards", Some("view"), add.clone()).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(http::Method::POST, "/v1/cards", Some("edit"), add).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(
+                http::Method::POST,
+                "/v1/boards",
+                Some("edit"),
+                serde_json::json!({"name": "b"})
+            )
+            .await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(http::Method::POST, "/mcp", Some("view"), mcp_list).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(http::Method::POST, "/mcp", Some("view"), mcp_add.clone()).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(http::Method::POST, "/mcp", Some("edit"), mcp_add).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn if_match_rejects_stale_revisions() {
+        let (_dir, app) = test_app();
+        let send = |req: Request<Body>| {
+            let app = app.clone();
+            async move { app.oneshot(req).await.unwrap() }
+        };
+        let etag = |resp: &axum::response::Response| {
+            resp.headers()[header::ETAG].to_str().unwrap().to_string()
+        };
+        let add = |tag: &str| {
+            Request::builder()
+                .method(http::Method::POST)
+                .uri("/v1/cards")
+                .header("content-type", "application/json")
+                .header(header::IF_MATCH, tag)
+                .body(Body::from(
+                    serde_json::json!({"title": "Tagged"}).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let resp = send(
+            Request::builder()
+                .uri("/v1/boards/default")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        let first = etag(&resp);
+
+        let resp = send(add(&first)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let second = etag(&resp);
+        assert_ne!(first, second);
+
+        // A client still holding the first revision loses the race
+        let resp = send(add(&first)).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        assert_eq!(send(add("*")).await.status(), StatusCode::OK);
+
+        let resp = send(
+            Request::builder()
+                .uri("/v1/cards/1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let current = etag(&resp);
+        let board = send(
+            Request::builder()
+                .uri("/v1/boards/default")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(current, etag(&board));
+        let json = body_json(resp.into_body()).await;
+        assert_eq!(json["title"], "Tagged");
+    }
 }
@@ -0,0 +1,212 @@
+//! Token roles for a shared server, read from `.kuk/server.toml`. Without
+//! that file the server stays open, as it always has for solo use.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method, StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Json, Response};
+use serde::Deserialize;
+
+use crate::error::{KukError, Result};
+
+use super::mcp;
+
+/// What a token may do. Each role includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Read boards, cards and the sync log.
+    Viewer,
+    /// Also add, move, label, assign, archive and delete cards.
+    Editor,
+    /// Also create boards.
+    Admin,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServerConfig {
+    #[serde(default)]
+    tokens: Vec<TokenConfig>,
+}
+
+/// One `[[tokens]]` entry. The secret is given inline as `token`, or as the
+/// name of an environment variable in `token_env` to keep it out of git.
+#[derive(Debug, Deserialize)]
+struct TokenConfig {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    token_env: Option<String>,
+    role: Role,
+}
+
+#[derive(Debug, Clone)]
+struct Grant {
+    name: String,
+    token: String,
+    role: Role,
+}
+
+/// The tokens the server accepts.
+#[derive(Debug, Clone)]
+pub struct Access {
+    grants: Vec<Grant>,
+}
+
+impl Access {
+    /// Read `server.toml` from `kuk_dir`. `None` when there is no file, or it
+    /// lists no tokens.
+    pub fn load(kuk_dir: &Path) -> Result<Option<Self>> {
+        let path = kuk_dir.join("server.toml");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = std::fs::read_to_string(&path).map_err(KukError::read(&path))?;
+        let invalid = |msg: String| KukError::Other(format!("Invalid {}: {msg}", path.display()));
+        let config: ServerConfig = toml::from_str(&data).map_err(|e| invalid(e.to_string()))?;
+
+        let mut grants = Vec::new();
+        for (i, entry) in config.tokens.into_iter().enumerate() {
+            let name = entry.name.unwrap_or_else(|| format!("token {}", i + 1));
+            let token = match (entry.token, entry.token_env) {
+                (Some(token), None) => token,
+                (None, Some(var)) => std::env::var(&var)
+                    .map_err(|_| invalid(format!("{name}: ${var} is not set")))?,
+                _ => return Err(invalid(format!("{name}: set one of token or token_env"))),
+            };
+            if token.is_empty() {
+                return Err(invalid(format!("{name}: empty token")));
+            }
+            grants.push(Grant {
+                name,
+                token,
+                role: entry.role,
+            });
+        }
+        Ok((!grants.is_empty()).then_some(Self { grants }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.grants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.grants.is_empty()
+    }
+
+    /// The grant for the request's `Authorization: Bearer` token.
+    fn grant(&self, headers: &HeaderMap) -> Option<&Grant> {
+        let token = headers
+            .get(header::AUTHORIZATION)?
+            .to_str()
+            .ok()?
+            .strip_prefix("Bearer ")?;
+        self.grants.iter().find(|g| same(&g.token, token))
+    }
+}
+
+/// Compare secrets without stopping at the first differing byte.
+fn same(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+/// Require a token with a sufficient role on every route but `/health`.
+pub fn protect(router: Router, access: Access) -> Router {
+    router.layer(middleware::from_fn_with_state(Arc::new(access), authorize))
+}
+
+/// Role needed for a REST call: reads need a viewer, creating boards an
+/// admin, and every other change an editor.
+fn rest_role(method: &Method, path: &str) -> Role {
+    if method == Method::GET || method == Method::HEAD {
+        Role::Viewer
+    } else if method == Method::POST && path == "/v1/boards" {
+        Role::Admin
+    } else {
+        Role::Editor
+    }
+}
+
+async fn authorize(State(access): State<Arc<Access>>, req: Request, next: Next) -> Response {
+    if req.uri().path() == "/health" {
+        return next.run(req).await;
+    }
+    let Some(grant) = access.grant(req.headers()) else {
+        return deny(StatusCode::UNAUTHORIZED, "Missing or unknown bearer token");
+    };
+
+    let (req, needed) = if req.uri().path() == "/mcp" {
+        // The tool being called decides, so read the body and hand it on
+        let (parts, body) = req.into_parts();
+        let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+            return deny(StatusCode::BAD_REQUEST, "Unreadable request body");
+        };
+        let needed = mcp::required_role(&bytes);
+        (Request::from_parts(parts, Body::from(bytes)), needed)
+    } else {
+        let needed = rest_role(req.method(), req.uri().path());
+        (req, needed)
+    };
+
+    if grant.role < needed {
+        tracing::info!(token = %grant.name, role = ?grant.role, ?needed, "request denied");
+        return deny(
+            StatusCode::FORBIDDEN,
+            &format!(
+                "Token {} is a {:?}; this needs {:?}",
+                grant.name, grant.role, needed
+            ),
+        );
+    }
+    tracing::debug!(token = %grant.name, "request authorized");
+    next.run(req).await
+}
+
+fn deny(status: StatusCode, error: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": error }))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_parse_and_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(Access::load(dir.path()).unwrap().is_none());
+
+        std::fs::write(
+            dir.path().join("server.toml"),
+            r#"
+            [[tokens]]
+            name = "stakeholders"
+            token = "read-only"
+            role = "viewer"
+
+            [[tokens]]
+            token_env = "KUK_TEST_UNSET_TOKEN_VAR"
+            role = "admin"
+            "#,
+        )
+        .unwrap();
+        let err = Access::load(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("token 2: $KUK_TEST_UNSET_TOKEN_VAR is not set"));
+
+        assert!(Role::Viewer < Role::Editor && Role::Editor < Role::Admin);
+        assert_eq!(rest_role(&Method::GET, "/v1/boards"), Role::Viewer);
+        assert_eq!(rest_role(&Method::POST, "/v1/boards"), Role::Admin);
+        assert_eq!(rest_role(&Method::PUT, "/v1/cards/1/move"), Role::Editor);
+        assert!(same("abc", "abc") && !same("abc", "abd") && !same("abc", "ab"));
+    }
+}
@@ -1,21 +1,91 @@
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use axum::Extension;
+use axum::body::{Body, Bytes};
 use axum::extract::State;
-use axum::response::Json;
+use axum::http::header::{ACCEPT, CACHE_CONTROL, CONTENT_TYPE, USER_AGENT};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
 use serde::{Deserialize, Serialize};
 
-use crate::model::Card;
+use crate::mcp_tools::{self, ToolResult, move_card_on, text_content};
+use crate::model::{Origin, Via};
+use crate::policy::{Guard, POLICY_VIOLATION};
 use crate::storage::Store;
 
+use super::auth::Role;
+
 type SharedStore = Arc<Mutex<Store>>;
 
-/// Minimal MCP (Model Context Protocol) JSON-RPC handler.
-/// Supports: tools/list, tools/call
+/// Header carrying the session `initialize` opened, on every later request.
+const SESSION_HEADER: HeaderName = HeaderName::from_static("mcp-session-id");
+
+/// Protocol versions the endpoint speaks, newest first.
+const PROTOCOL_VERSIONS: [&str; 2] = ["2025-03-26", "2024-11-05"];
+
+/// How long a session may go unused before it is closed.
+const SESSION_IDLE: Duration = Duration::from_secs(60 * 60);
+
+/// What the MCP endpoint keeps between requests while the server runs: the
+/// agent policy each session starts from, and the open sessions.
+pub(super) struct McpState {
+    policy: Guard,
+    /// Shared by the calls that come without a session.
+    sessionless: Mutex<Guard>,
+    sessions: Mutex<HashMap<String, Session>>,
+    idle: Duration,
+}
+
+/// An open session: the client it belongs to, the policy's count of what
+/// it has done, and when it was last used.
+struct Session {
+    client: Option<String>,
+    guard: Guard,
+    last_used: Instant,
+}
+
+impl McpState {
+    pub(super) fn new(guard: Guard) -> Arc<Self> {
+        Arc::new(McpState {
+            sessionless: Mutex::new(guard.clone()),
+            policy: guard,
+            sessions: Mutex::new(HashMap::new()),
+            idle: SESSION_IDLE,
+        })
+    }
+
+    /// Open a session for `client` and return its ID.
+    fn open(&self, client: Option<String>) -> String {
+        let id = ulid::Ulid::new().to_string();
+        let session = Session {
+            client,
+            guard: self.policy.clone(),
+            last_used: Instant::now(),
+        };
+        self.live_sessions().insert(id.clone(), session);
+        id
+    }
+
+    /// The open sessions, once those idle too long are closed.
+    fn live_sessions(&self) -> std::sync::MutexGuard<'_, HashMap<String, Session>> {
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, s| s.last_used.elapsed() < self.idle);
+        sessions
+    }
+}
+
+/// MCP (Model Context Protocol) JSON-RPC message over the streamable HTTP
+/// transport. Supports: initialize, ping, tools/list, tools/call.
 #[derive(Debug, Deserialize)]
 pub struct McpRequest {
     #[allow(dead_code)]
     pub jsonrpc: String,
-    pub id: serde_json::Value,
+    /// None for notifications, which get no response.
+    #[serde(default)]
+    pub id: Option<serde_json::Value>,
+    #[serde(default)]
     pub method: String,
     #[serde(default)]
     pub params: serde_json::Value,
@@ -60,16 +130,164 @@ impl McpResponse {
     }
 }
 
+/// Role a token needs for an MCP request: listing is for viewers, every
+/// other tool changes the board.
+pub(super) fn required_role(body: &[u8]) -> Role {
+    let req: serde_json::Value = serde_json::from_slice(body).unwrap_or_default();
+    if req["method"] != "tools/call" {
+        return Role::Viewer;
+    }
+    match req["params"]["name"].as_str() {
+        Some("kuk_list_cards" | "kuk_list_comments") => Role::Viewer,
+        _ => Role::Editor,
+    }
+}
+
+/// `POST /mcp`: one JSON-RPC message. Requests are answered as JSON, or as
+/// an event stream when the client accepts one; notifications and client
+/// responses get 202 and no body. `initialize` opens a session, returned in
+/// the `Mcp-Session-Id` header. Clients that send one must send a live one;
+/// clients that never initialize can still list and call tools.
 pub async fn mcp_handler(
     State(store): State<SharedStore>,
-    Json(req): Json<McpRequest>,
-) -> Json<McpResponse> {
+    Extension(mcp): Extension<Arc<McpState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let req: McpRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            let error =
+                McpResponse::error(serde_json::Value::Null, -32700, format!("Parse error: {e}"));
+            return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+        }
+    };
+    let session = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok());
+    let session_client = match session {
+        Some(session) => match mcp.live_sessions().get_mut(session) {
+            Some(open) => {
+                open.last_used = Instant::now();
+                open.client.clone()
+            }
+            None => return unknown_session(req.id.unwrap_or_default()),
+        },
+        None => None,
+    };
+    // Notifications and the client's own responses get no answer
+    let Some(id) = req.id.filter(|_| !req.method.is_empty()) else {
+        return StatusCode::ACCEPTED.into_response();
+    };
+
+    // The client names itself in `initialize`, or else in its User-Agent
+    let client = session_client.or_else(|| {
+        headers
+            .get(USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    });
+    let origin = Origin::new(Via::Mcp).client(client.as_deref());
+    let mut opened = None;
     let response = match req.method.as_str() {
-        "tools/list" => handle_tools_list(req.id),
-        "tools/call" => handle_tools_call(req.id, req.params, &store),
-        _ => McpResponse::error(req.id, -32601, "Method not found"),
+        "initialize" => {
+            let (response, session) = handle_initialize(id, &req.params, &mcp);
+            opened = Some(session);
+            response
+        }
+        "ping" => McpResponse::success(id, serde_json::json!({})),
+        "tools/list" => handle_tools_list(id),
+        "tools/call" => match session {
+            Some(session) => match mcp.live_sessions().get_mut(session) {
+                Some(open) => handle_tools_call(id, req.params, &store, &mut open.guard, origin),
+                None => return unknown_session(id),
+            },
+            None => {
+                let mut guard = mcp.sessionless.lock().unwrap();
+                handle_tools_call(id, req.params, &store, &mut guard, origin)
+            }
+        },
+        _ => McpResponse::error(id, -32601, format!("Method not found: {}", req.method)),
+    };
+    let mut reply = reply(&headers, &response);
+    if let Some(session) = opened.and_then(|s| HeaderValue::from_str(&s).ok()) {
+        reply.headers_mut().insert(SESSION_HEADER, session);
+    }
+    reply
+}
+
+/// `DELETE /mcp`: close the session named in `Mcp-Session-Id`.
+pub async fn mcp_end_session(
+    Extension(mcp): Extension<Arc<McpState>>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(session) = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok()) else {
+        let error = McpResponse::error(serde_json::Value::Null, -32600, "Missing Mcp-Session-Id");
+        return (StatusCode::BAD_REQUEST, Json(error)).into_response();
     };
-    Json(response)
+    match mcp.live_sessions().remove(session) {
+        Some(_) => StatusCode::NO_CONTENT.into_response(),
+        None => unknown_session(serde_json::Value::Null),
+    }
+}
+
+/// 404, which tells the client to initialize a new session.
+fn unknown_session(id: serde_json::Value) -> Response {
+    let error = McpResponse::error(
+        id,
+        -32600,
+        "Unknown or closed MCP session; initialize again",
+    );
+    (StatusCode::NOT_FOUND, Json(error)).into_response()
+}
+
+/// `response` as a JSON body, or as a one-event stream to a client whose
+/// `Accept` lists `text/event-stream`.
+fn reply(headers: &HeaderMap, response: &McpResponse) -> Response {
+    let streams = headers
+        .get(ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/event-stream"));
+    if !streams {
+        return Json(response).into_response();
+    }
+    let data = serde_json::to_string(response).unwrap_or_default();
+    (
+        [
+            (CONTENT_TYPE, "text/event-stream"),
+            (CACHE_CONTROL, "no-cache"),
+        ],
+        Body::from(format!("event: message\ndata: {data}\n\n")),
+    )
+        .into_response()
+}
+
+/// Open a session for the client, agreeing on the version it asked for if
+/// the endpoint speaks it and the newest one otherwise.
+fn handle_initialize(
+    id: serde_json::Value,
+    params: &serde_json::Value,
+    mcp: &McpState,
+) -> (McpResponse, String) {
+    let asked = params["protocolVersion"].as_str();
+    let version = PROTOCOL_VERSIONS
+        .into_iter()
+        .find(|v| Some(*v) == asked)
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+    let client = params["clientInfo"]["name"].as_str().map(str::to_string);
+    let session = mcp.open(client);
+    let response = McpResponse::success(
+        id,
+        serde_json::json!({
+            "protocolVersion": version,
+            "capabilities": {
+                "tools": {}
+            },
+            "serverInfo": {
+                "name": "kuk",
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        }),
+    );
+    (response, session)
 }
 
 fn handle_tools_list(id: serde_json::Value) -> McpResponse {
@@ -82,10 +300,12 @@ fn handle_tools_list(id: serde_json::Value) -> McpResponse {
                     "type": "object",
                     "properties": {
                         "title": {"type": "string", "description": "Card title"},
-                        "column": {"type": "string", "description": "Target column (default: todo)"},
+                        "column": {"type": "string", "description": "Target column (default: the repo's new-card column, else the first column)"},
                         "labels": {"type": "array", "items": {"type": "string"}, "description": "Labels"},
                         "assignee": {"type": "string", "description": "Assignee username"},
-                        "board": {"type": "string", "description": "Board name (default: default)"}
+                        "priority": {"type": "string", "enum": ["low", "medium", "high", "urgent"], "description": "Card priority"},
+                        "board": {"type": "string", "description": "Board name (default: default)"},
+                        "force": {"type": "boolean", "description": "Add even if a similar card exists and the repo rejects duplicates, or the column is at its WIP limit"}
                     },
                     "required": ["title"]
                 }
@@ -106,20 +326,72 @@ fn handle_tools_list(id: serde_json::Value) -> McpResponse {
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "id": {"type": "string", "description": "Card ID or number"},
+                        "id": {"type": "string", "description": "Card ID, short ID (KUK-42) or number"},
                         "to": {"type": "string", "description": "Target column"},
-                        "board": {"type": "string", "description": "Board name (default: default)"}
+                        "board": {"type": "string", "description": "Board name (default: default)"},
+                        "force": {"type": "boolean", "description": "Move even if the target column is at its WIP limit"}
                     },
                     "required": ["id", "to"]
                 }
             },
+            {
+                "name": "kuk_add_cards",
+                "description": "Add several cards in one call, saving the board once. Each card is checked like kuk_add_card; failures are reported per card and do not stop the rest",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "cards": {
+                            "type": "array",
+                            "description": "Cards to add, in order",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "title": {"type": "string", "description": "Card title"},
+                                    "column": {"type": "string", "description": "Target column (default: the repo's new-card column, else the first column)"},
+                                    "labels": {"type": "array", "items": {"type": "string"}, "description": "Labels"},
+                                    "assignee": {"type": "string", "description": "Assignee username"},
+                                    "priority": {"type": "string", "enum": ["low", "medium", "high", "urgent"], "description": "Card priority"},
+                                    "force": {"type": "boolean", "description": "Add even if a similar card exists and the repo rejects duplicates, or the column is at its WIP limit"}
+                                },
+                                "required": ["title"]
+                            }
+                        },
+                        "board": {"type": "string", "description": "Board name (default: default)"}
+                    },
+                    "required": ["cards"]
+                }
+            },
+            {
+                "name": "kuk_move_cards",
+                "description": "Move several cards in one call, saving the board once. Each move is checked like kuk_move_card; failures are reported per move and do not stop the rest",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "moves": {
+                            "type": "array",
+                            "description": "Moves to apply, in order",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "id": {"type": "string", "description": "Card ID, short ID (KUK-42) or number"},
+                                    "to": {"type": "string", "description": "Target column"},
+                                    "force": {"type": "boolean", "description": "Move even if the target column is at its WIP limit"}
+                                },
+                                "required": ["id", "to"]
+                            }
+                        },
+                        "board": {"type": "string", "description": "Board name (default: default)"}
+                    },
+                    "required": ["moves"]
+                }
+            },
             {
                 "name": "kuk_archive_card",
                 "description": "Archive a card",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "id": {"type": "string", "description": "Card ID or number"}
+                        "id": {"type": "string", "description": "Card ID, short ID (KUK-42) or number"}
                     },
                     "required": ["id"]
                 }
@@ -130,7 +402,31 @@ fn handle_tools_list(id: serde_json::Value) -> McpResponse {
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "id": {"type": "string", "description": "Card ID or number"}
+                        "id": {"type": "string", "description": "Card ID, short ID (KUK-42) or number"}
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "kuk_add_comment",
+                "description": "Leave a comment on a card, such as a progress note",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string", "description": "Card ID, short ID (KUK-42) or number"},
+                        "body": {"type": "string", "description": "Comment text"},
+                        "author": {"type": "string", "description": "Who is commenting (default: mcp)"}
+                    },
+                    "required": ["id", "body"]
+                }
+            },
+            {
+                "name": "kuk_list_comments",
+                "description": "List the comments on a card, oldest first",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string", "description": "Card ID, short ID (KUK-42) or number"}
                     },
                     "required": ["id"]
                 }
@@ -144,78 +440,60 @@ fn handle_tools_call(
     id: serde_json::Value,
     params: serde_json::Value,
     store: &SharedStore,
+    guard: &mut Guard,
+    origin: Origin,
 ) -> McpResponse {
     let tool_name = params["name"].as_str().unwrap_or("");
     let args = &params["arguments"];
 
-    match tool_name {
-        "kuk_add_card" => tool_add_card(id, args, store),
+    // The REST API shares the lock; this call's changes are credited to MCP
+    let locked = store.lock().unwrap();
+    let store = &locked.clone().with_origin(origin);
+
+    // Archiving, deleting and commenting act on the active board
+    let board = match tool_name {
+        "kuk_archive_card" | "kuk_delete_card" | "kuk_add_comment" => store
+            .load_config()
+            .map(|c| c.default_board)
+            .unwrap_or_default(),
+        _ => args["board"].as_str().unwrap_or("default").to_string(),
+    };
+    if let Err(rule) = guard.check(tool_name, args, &board) {
+        return McpResponse::error(id, POLICY_VIOLATION, rule);
+    }
+    // Held from each tool's load to its save, so other writers take turns
+    let _lock = match store.lock() {
+        Ok(lock) => lock,
+        Err(e) => return McpResponse::error(id, -32603, e.to_string()),
+    };
+    let response = match tool_name {
+        "kuk_add_card" => respond(id, mcp_tools::add_card(args, store)),
         "kuk_list_cards" => tool_list_cards(id, args, store),
         "kuk_move_card" => tool_move_card(id, args, store),
+        "kuk_add_cards" => respond(id, mcp_tools::add_cards(args, store)),
+        "kuk_move_cards" => respond(id, mcp_tools::move_cards(args, store)),
         "kuk_archive_card" => tool_archive_card(id, args, store),
         "kuk_delete_card" => tool_delete_card(id, args, store),
+        "kuk_add_comment" => tool_add_comment(id, args, store),
+        "kuk_list_comments" => tool_list_comments(id, args, store),
         _ => McpResponse::error(id, -32602, format!("Unknown tool: {tool_name}")),
-    }
-}
-
-fn text_content(text: &str) -> serde_json::Value {
-    serde_json::json!({
-        "content": [{"type": "text", "text": text}]
-    })
-}
-
-fn tool_add_card(
-    id: serde_json::Value,
-    args: &serde_json::Value,
-    store: &SharedStore,
-) -> McpResponse {
-    let title = match args["title"].as_str() {
-        Some(t) => t,
-        None => return McpResponse::error(id, -32602, "title is required"),
     };
-    let column = args["column"].as_str().unwrap_or("todo");
-    let board_name = args["board"].as_str().unwrap_or("default");
-
-    let store = store.lock().unwrap();
-    let mut board = match store.load_board(board_name) {
-        Ok(b) => b,
-        Err(e) => return McpResponse::error(id, -32603, e.to_string()),
-    };
-
-    if !board.has_column(column) {
-        return McpResponse::error(id, -32602, format!("Column not found: {column}"));
-    }
-
-    let mut card = Card::new(title, column);
-    card.order = board.next_order(column);
-
-    if let Some(labels) = args["labels"].as_array() {
-        card.labels = labels
-            .iter()
-            .filter_map(|v| v.as_str().map(String::from))
-            .collect();
-    }
-    if let Some(assignee) = args["assignee"].as_str() {
-        card.assignee = Some(assignee.into());
+    if let Some(result) = &response.result {
+        guard.record(tool_name, result);
     }
+    response
+}
 
-    let result = serde_json::to_string_pretty(&card).unwrap();
-    board.cards.push(card);
-
-    if let Err(e) = store.save_board(&board) {
-        return McpResponse::error(id, -32603, e.to_string());
+/// `result` as the response to request `id`.
+fn respond(id: serde_json::Value, result: ToolResult) -> McpResponse {
+    match result {
+        Ok(content) => McpResponse::success(id, content),
+        Err(e) => McpResponse::error(id, e.code, e.message),
     }
-
-    McpResponse::success(id, text_content(&result))
 }
 
-fn tool_list_cards(
-    id: serde_json::Value,
-    args: &serde_json::Value,
-    store: &SharedStore,
-) -> McpResponse {
+fn tool_list_cards(id: serde_json::Value, args: &serde_json::Value, store: &Store) -> McpResponse {
     let board_name = args["board"].as_str().unwrap_or("default");
-    let store = store.lock().unwrap();
 
     match store.load_board(board_name) {
         Ok(board) => {
@@ -226,11 +504,7 @@ fn tool_list_cards(
     }
 }
 
-fn tool_move_card(
-    id: serde_json::Value,
-    args: &serde_json::Value,
-    store: &SharedStore,
-) -> McpResponse {
+fn tool_move_card(id: serde_json::Value, args: &serde_json::Value, store: &Store) -> McpResponse {
     let card_id_str = match args["id"].as_str() {
         Some(s) => s,
         None => return McpResponse::error(id, -32602, "id is required"),
@@ -241,26 +515,17 @@ fn tool_move_card(
     };
     let board_name = args["board"].as_str().unwrap_or("default");
 
-    let store = store.lock().unwrap();
     let mut board = match store.load_board(board_name) {
         Ok(b) => b,
         Err(e) => return McpResponse::error(id, -32603, e.to_string()),
     };
 
-    if !board.has_column(to) {
-        return McpResponse::error(id, -32602, format!("Column not found: {to}"));
-    }
-
-    let resolved = match board.resolve_card_id(card_id_str) {
-        Some(id) => id,
-        None => return McpResponse::error(id, -32602, format!("Card not found: {card_id_str}")),
+    let force = args["force"].as_bool().unwrap_or(false);
+    let resolved = match move_card_on(&mut board, card_id_str, to, force) {
+        Ok(resolved) => resolved,
+        Err(e) => return McpResponse::error(id, -32602, e),
     };
-
-    let next_order = board.next_order(to);
-    let card = board.find_card_mut(&resolved).unwrap();
-    card.column = to.into();
-    card.order = next_order;
-    card.updated_at = chrono::Utc::now();
+    let card = board.find_card(&resolved).unwrap();
     let result = serde_json::to_string_pretty(card).unwrap();
 
     if let Err(e) = store.save_board(&board) {
@@ -273,14 +538,13 @@ fn tool_move_card(
 fn tool_archive_card(
     id: serde_json::Value,
     args: &serde_json::Value,
-    store: &SharedStore,
+    store: &Store,
 ) -> McpResponse {
     let card_id_str = match args["id"].as_str() {
         Some(s) => s,
         None => return McpResponse::error(id, -32602, "id is required"),
     };
 
-    let store = store.lock().unwrap();
     let config = match store.load_config() {
         Ok(c) => c,
         Err(e) => return McpResponse::error(id, -32603, e.to_string()),
@@ -296,7 +560,7 @@ fn tool_archive_card(
     };
 
     let card = board.find_card_mut(&resolved).unwrap();
-    card.archived = true;
+    card.archive();
     card.updated_at = chrono::Utc::now();
     let result = serde_json::to_string_pretty(card).unwrap();
 
@@ -307,17 +571,12 @@ fn tool_archive_card(
     McpResponse::success(id, text_content(&result))
 }
 
-fn tool_delete_card(
-    id: serde_json::Value,
-    args: &serde_json::Value,
-    store: &SharedStore,
-) -> McpResponse {
+fn tool_delete_card(id: serde_json::Value, args: &serde_json::Value, store: &Store) -> McpResponse {
     let card_id_str = match args["id"].as_str() {
         Some(s) => s,
         None => return McpResponse::error(id, -32602, "id is required"),
     };
 
-    let store = store.lock().unwrap();
     let config = match store.load_config() {
         Ok(c) => c,
         Err(e) => return McpResponse::error(id, -32603, e.to_string()),
@@ -345,3 +604,89 @@ fn tool_delete_card(
     let result = serde_json::json!({"deleted": resolved, "title": title});
     McpResponse::success(id, text_content(&result.to_string()))
 }
+
+fn tool_add_comment(id: serde_json::Value, args: &serde_json::Value, store: &Store) -> McpResponse {
+    let card_id_str = match args["id"].as_str() {
+        Some(s) => s,
+        None => return McpResponse::error(id, -32602, "id is required"),
+    };
+    let body = match args["body"].as_str().map(str::trim) {
+        Some(s) if !s.is_empty() => s,
+        _ => return McpResponse::error(id, -32602, "body is required"),
+    };
+    let author = args["author"].as_str().unwrap_or("mcp");
+
+    let config = match store.load_config() {
+        Ok(c) => c,
+        Err(e) => return McpResponse::error(id, -32603, e.to_string()),
+    };
+    let mut board = match store.load_board(&config.default_board) {
+        Ok(b) => b,
+        Err(e) => return McpResponse::error(id, -32603, e.to_string()),
+    };
+
+    let resolved = match board.resolve_card_id(card_id_str) {
+        Some(id) => id,
+        None => return McpResponse::error(id, -32602, format!("Card not found: {card_id_str}")),
+    };
+
+    let card = board.find_card_mut(&resolved).unwrap();
+    card.add_comment(author, body);
+    let result = serde_json::to_string_pretty(card).unwrap();
+
+    if let Err(e) = store.save_board(&board) {
+        return McpResponse::error(id, -32603, e.to_string());
+    }
+
+    McpResponse::success(id, text_content(&result))
+}
+
+fn tool_list_comments(
+    id: serde_json::Value,
+    args: &serde_json::Value,
+    store: &Store,
+) -> McpResponse {
+    let card_id_str = match args["id"].as_str() {
+        Some(s) => s,
+        None => return McpResponse::error(id, -32602, "id is required"),
+    };
+
+    let config = match store.load_config() {
+        Ok(c) => c,
+        Err(e) => return McpResponse::error(id, -32603, e.to_string()),
+    };
+    let board = match store.load_board(&config.default_board) {
+        Ok(b) => b,
+        Err(e) => return McpResponse::error(id, -32603, e.to_string()),
+    };
+
+    match board
+        .resolve_card_id(card_id_str)
+        .and_then(|resolved| board.find_card(&resolved))
+    {
+        Some(card) => {
+            let json = serde_json::to_string_pretty(&card.comments).unwrap();
+            McpResponse::success(id, text_content(&json))
+        }
+        None => McpResponse::error(id, -32602, format!("Card not found: {card_id_str}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_sessions_are_closed() {
+        let mcp = McpState::new(Guard::new(None));
+        let open = mcp.open(Some("agent".into()));
+        assert!(mcp.live_sessions().contains_key(&open));
+
+        let idle = McpState {
+            idle: Duration::ZERO,
+            ..Arc::into_inner(McpState::new(Guard::new(None))).unwrap()
+        };
+        let session = idle.open(None);
+        assert!(!idle.live_sessions().contains_key(&session));
+    }
+}
@@ -1,4 +1,6 @@
 mod api;
+mod auth;
 mod mcp;
 
-pub use api::serve;
+pub use api::{router, serve};
+pub use auth::{Access, Role, protect};
@@ -0,0 +1,199 @@
+use std::rc::Rc;
+use std::vec;
+
+use crate::error::Result;
+use crate::model::Card;
+
+use super::Store;
+
+/// Which cards `Store::iter_cards` yields. The default is every active card
+/// on every board, without descriptions and metadata.
+#[derive(Debug, Clone, Default)]
+pub struct CardQuery {
+    /// Boards to visit, in this order. Empty means all boards.
+    pub boards: Vec<String>,
+    pub include_archived: bool,
+    pub column: Option<String>,
+    pub label: Option<String>,
+    pub assignee: Option<String>,
+    /// Load full cards rather than board summaries.
+    pub full: bool,
+}
+
+impl CardQuery {
+    fn matches(&self, card: &Card) -> bool {
+        (self.include_archived || !card.archived)
+            && self.column.as_ref().is_none_or(|c| card.column == *c)
+            && self.label.as_ref().is_none_or(|l| card.labels.contains(l))
+            && self
+                .assignee
+                .as_ref()
+                .is_none_or(|a| card.assignee.as_ref() == Some(a))
+    }
+}
+
+/// Lazy iterator over `(board name, card)` pairs, created by
+/// `Store::iter_cards`. Boards are loaded one at a time as the previous one
+/// runs out, and each board's cards come in display order. A board that fails
+/// to load yields its error and iteration moves on to the next one.
+pub struct CardIter<'a> {
+    store: &'a Store,
+    query: CardQuery,
+    boards: vec::IntoIter<String>,
+    current: Option<(Rc<str>, vec::IntoIter<Card>)>,
+}
+
+impl Iterator for CardIter<'_> {
+    type Item = Result<(Rc<str>, Card)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((board, cards)) = &mut self.current
+                && let Some(card) = cards.find(|c| self.query.matches(c))
+            {
+                return Some(Ok((board.clone(), card)));
+            }
+            let name = self.boards.next()?;
+            let loaded = if self.query.full {
+                self.store.load_board(&name)
+            } else {
+                self.store.load_board_summary(&name)
+            };
+            match loaded {
+                Ok(mut board) => {
+                    board.sort_cards();
+                    self.current = Some((name.into(), board.cards.into_iter()));
+                }
+                Err(e) => {
+                    self.current = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl Store {
+    /// Iterate over the cards matching `query` across boards, without holding
+    /// more than one board in memory.
+    pub fn iter_cards(&self, query: CardQuery) -> Result<CardIter<'_>> {
+        let boards = if query.boards.is_empty() {
+            self.list_boards()?
+        } else {
+            query.boards.clone()
+        };
+        Ok(CardIter {
+            store: self,
+            query,
+            boards: boards.into_iter(),
+            current: None,
+        })
+    }
+
+    /// Every active card on every board, for reports.
+    pub fn active_cards(&self) -> Result<Vec<Card>> {
+        self.iter_cards(CardQuery::default())?
+            .map(|item| item.map(|(_, card)| card))
+            .collect()
+    }
+
+    /// Every card on every board, archived ones included, for reports.
+    pub fn all_cards(&self) -> Result<Vec<Card>> {
+        let query = CardQuery {
+            include_archived: true,
+            ..CardQuery::default()
+        };
+        self.iter_cards(query)?
+            .map(|item| item.map(|(_, card)| card))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Store) {
+        let dir = TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+        let mut board = store.load_board("default").unwrap();
+        for (title, column) in [("Ship", "done"), ("Write", "todo"), ("Plan", "todo")] {
+            let mut card = Card::new(title, column);
+            card.order = board.next_order(column);
+            board.cards.push(card);
+        }
+        board.cards[0].labels.push("release".into());
+        board.cards[1].archived = true;
+        store.save_board(&board).unwrap();
+        store.create_board("ops", Vec::new()).unwrap();
+        let mut ops = store.load_board("ops").unwrap();
+        ops.cards.push(Card::new("Rotate keys", "todo"));
+        ops.cards[0].assignee = Some("sam".into());
+        store.save_board(&ops).unwrap();
+        (dir, store)
+    }
+
+    fn titles(store: &Store, query: CardQuery) -> Vec<(String, String)> {
+        store
+            .iter_cards(query)
+            .unwrap()
+            .map(|item| {
+                let (board, card) = item.unwrap();
+                (board.to_string(), card.title)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn yields_cards_board_by_board_in_display_order() {
+        let (_dir, store) = setup();
+        let pairs = |items: &[(&str, &str)]| -> Vec<(String, String)> {
+            items
+                .iter()
+                .map(|(b, t)| (b.to_string(), t.to_string()))
+                .collect()
+        };
+
+        assert_eq!(
+            titles(&store, CardQuery::default()),
+            pairs(&[
+                ("default", "Plan"),
+                ("default", "Ship"),
+                ("ops", "Rotate keys")
+            ])
+        );
+        let archived = CardQuery {
+            boards: vec!["default".into()],
+            include_archived: true,
+            column: Some("todo".into()),
+            ..CardQuery::default()
+        };
+        assert_eq!(
+            titles(&store, archived),
+            pairs(&[("default", "Write"), ("default", "Plan")])
+        );
+        let label = CardQuery {
+            label: Some("release".into()),
+            ..CardQuery::default()
+        };
+        assert_eq!(titles(&store, label), pairs(&[("default", "Ship")]));
+        let assignee = CardQuery {
+            assignee: Some("sam".into()),
+            ..CardQuery::default()
+        };
+        assert_eq!(titles(&store, assignee), pairs(&[("ops", "Rotate keys")]));
+        assert_eq!(store.active_cards().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn broken_board_yields_error_and_continues() {
+        let (_dir, store) = setup();
+        std::fs::write(store.kuk_dir().join("boards/aaa.json"), "{").unwrap();
+
+        let items: Vec<_> = store.iter_cards(CardQuery::default()).unwrap().collect();
+        assert!(items[0].is_err());
+        assert_eq!(items.iter().filter(|i| i.is_ok()).count(), 3);
+    }
+}
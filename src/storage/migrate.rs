@@ -0,0 +1,140 @@
+//! Upgrading board and config files written by older versions of kuk.
+//!
+//! Each file records the format it was written in as `schema_version`;
+//! files from before versioning have none and count as version 0. Loading
+//! an older file runs the steps from its version up to
+//! [`SCHEMA_VERSION`] on its JSON before parsing it, and the next save
+//! writes it back in the current format. A file from a newer kuk is refused
+//! rather than read wrong and saved with its new fields dropped.
+//!
+//! To change the format, bump [`SCHEMA_VERSION`] and add a step to the end
+//! of [`BOARD_STEPS`] and [`CONFIG_STEPS`] that turns the previous format
+//! into the new one.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::error::{KukError, Result};
+use crate::model::SCHEMA_VERSION;
+
+/// Upgrades a file's JSON object by one version.
+type Step = fn(&mut Map<String, Value>);
+
+/// `BOARD_STEPS[n]` upgrades a board from version `n` to `n + 1`. A board
+/// stored in the cards layout is upgraded whole, with its card files
+/// gathered into `cards`.
+const BOARD_STEPS: &[Step] = &[unversioned];
+
+/// `CONFIG_STEPS[n]` upgrades `config.json` from version `n` to `n + 1`.
+const CONFIG_STEPS: &[Step] = &[unversioned];
+
+const _: () = assert!(BOARD_STEPS.len() == SCHEMA_VERSION as usize);
+const _: () = assert!(CONFIG_STEPS.len() == SCHEMA_VERSION as usize);
+
+/// Version 1 only started recording `schema_version`; every field added
+/// before it has a default, so unversioned files read as they are.
+fn unversioned(_: &mut Map<String, Value>) {}
+
+/// The `schema_version` a file's JSON records.
+pub fn version_of(value: &Value) -> u64 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+/// The `schema_version` recorded in a file's JSON text, read without
+/// building the rest of it, so files already current parse only once.
+pub fn version_in(data: &str) -> serde_json::Result<u64> {
+    #[derive(Deserialize)]
+    struct Versioned {
+        #[serde(default)]
+        schema_version: u64,
+    }
+    serde_json::from_str::<Versioned>(data).map(|v| v.schema_version)
+}
+
+/// Fail for a file written by a newer kuk than this one.
+pub fn check_version(version: u64, path: &Path) -> Result<()> {
+    check_against(version, SCHEMA_VERSION.into(), path)
+}
+
+fn check_against(version: u64, current: u64, path: &Path) -> Result<()> {
+    if version > current {
+        return Err(KukError::Other(format!(
+            "{} uses schema version {version}, but this kuk only knows up to {current}. Upgrade kuk to open it.",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Upgrade a board's JSON to the current version.
+pub fn board(value: &mut Value, path: &Path) -> Result<()> {
+    upgrade(value, BOARD_STEPS, path)
+}
+
+/// Upgrade `config.json`'s JSON to the current version.
+pub fn config(value: &mut Value, path: &Path) -> Result<()> {
+    upgrade(value, CONFIG_STEPS, path)
+}
+
+fn upgrade(value: &mut Value, steps: &[Step], path: &Path) -> Result<()> {
+    let version = version_of(value);
+    let current = steps.len() as u64;
+    check_against(version, current, path)?;
+    let Some(fields) = value.as_object_mut() else {
+        return Err(KukError::Other(format!(
+            "{} doesn't hold a JSON object",
+            path.display()
+        )));
+    };
+    for (from, step) in steps.iter().enumerate().skip(version as usize) {
+        tracing::debug!(path = %path.display(), from, "migrating");
+        step(fields);
+    }
+    fields.insert("schema_version".into(), current.into());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn steps_run_from_the_file_version_up() {
+        fn rename(fields: &mut Map<String, Value>) {
+            if let Some(old) = fields.remove("old") {
+                fields.insert("new".into(), old);
+            }
+        }
+        fn add(fields: &mut Map<String, Value>) {
+            fields.insert("added".into(), true.into());
+        }
+        let steps: &[Step] = &[rename, add];
+        let path = Path::new("board.json");
+
+        let mut unversioned = json!({"old": 1});
+        upgrade(&mut unversioned, steps, path).unwrap();
+        assert_eq!(
+            unversioned,
+            json!({"new": 1, "added": true, "schema_version": 2})
+        );
+
+        let mut v1 = json!({"old": 1, "schema_version": 1});
+        upgrade(&mut v1, steps, path).unwrap();
+        assert_eq!(v1["old"], 1);
+        assert_eq!(v1["added"], true);
+        assert!(upgrade(&mut json!({"schema_version": 3}), steps, path).is_err());
+    }
+
+    #[test]
+    fn newer_files_are_refused() {
+        let mut future = json!({"schema_version": SCHEMA_VERSION + 1});
+        let err = board(&mut future, Path::new("board.json")).unwrap_err();
+        assert!(err.to_string().contains("Upgrade kuk"));
+    }
+}
@@ -1,3 +1,9 @@
+mod cards;
+mod migrate;
+mod refs;
+mod sqlite;
 mod store;
+mod summary;
 
-pub use store::Store;
+pub use cards::{CardIter, CardQuery};
+pub use store::{Store, StoreLock};
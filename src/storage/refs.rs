@@ -0,0 +1,186 @@
+//! Board names kept in files kuk-pm owns: the `boards` of each sprint in
+//! `sprints.json` and the `sync_boards` of `pm.json`. kuk doesn't know the
+//! rest of those files, so it edits them as JSON that keeps every key where
+//! kuk-pm wrote it, rather than as `serde_json::Value`, which sorts keys.
+
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+
+/// A JSON value whose objects keep their keys in file order.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Json {
+    Object(Vec<(String, Json)>),
+    Array(Vec<Json>),
+    Scalar(Value),
+}
+
+impl Json {
+    fn get_mut(&mut self, key: &str) -> Option<&mut Json> {
+        match self {
+            Json::Object(fields) => fields.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn is_str(&self, s: &str) -> bool {
+        matches!(self, Json::Scalar(Value::String(v)) if v == s)
+    }
+}
+
+/// Point every sprint's `boards` in `sprints` at `new` instead of `old`, or
+/// drop `old` when `new` is None. Returns whether any sprint listed `old`.
+pub(super) fn rename_in_sprints(sprints: &mut Json, old: &str, new: Option<&str>) -> bool {
+    let Json::Array(sprints) = sprints else {
+        return false;
+    };
+    let mut changed = false;
+    for sprint in sprints {
+        let Some(Json::Array(boards)) = sprint.get_mut("boards") else {
+            continue;
+        };
+        if !boards.iter().any(|b| b.is_str(old)) {
+            continue;
+        }
+        let mut updated: Vec<Json> = Vec::new();
+        for board in boards.drain(..) {
+            let board = match (board.is_str(old), new) {
+                (false, _) => board,
+                (true, Some(new)) => Json::Scalar(new.into()),
+                (true, None) => continue,
+            };
+            if !updated.contains(&board) {
+                updated.push(board);
+            }
+        }
+        *boards = updated;
+        changed = true;
+    }
+    changed
+}
+
+/// Move `old`'s switch in `pm`'s `sync_boards` to `new`, or drop it when
+/// `new` is None. kuk-pm writes the switches sorted by board, so `new`
+/// goes where it sorts. Returns whether `old` had a switch.
+pub(super) fn rename_in_pm(pm: &mut Json, old: &str, new: Option<&str>) -> bool {
+    let Some(Json::Object(sync)) = pm.get_mut("sync_boards") else {
+        return false;
+    };
+    let Some(index) = sync.iter().position(|(board, _)| board == old) else {
+        return false;
+    };
+    let (_, enabled) = sync.remove(index);
+    if let Some(new) = new {
+        sync.retain(|(board, _)| board != new);
+        let at = sync.partition_point(|(board, _)| board.as_str() < new);
+        sync.insert(at, (new.into(), enabled));
+    }
+    true
+}
+
+impl Serialize for Json {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Json::Object(fields) => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (key, value) in fields {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            Json::Array(items) => items.serialize(serializer),
+            Json::Scalar(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Json {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonVisitor)
+    }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+    type Value = Json;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Json, E> {
+        Ok(Json::Scalar(v.into()))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Json, E> {
+        Ok(Json::Scalar(v.into()))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Json, E> {
+        Ok(Json::Scalar(v.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Json, E> {
+        Ok(Json::Scalar(v.into()))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Json, E> {
+        Ok(Json::Scalar(v.into()))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Json, E> {
+        Ok(Json::Scalar(Value::Null))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Json, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Json::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Json, A::Error> {
+        let mut fields = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            fields.push(entry);
+        }
+        Ok(Json::Object(fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Json {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn renames_keep_every_key_where_it_was() {
+        let mut sprints = parse(
+            r#"[{"name":"S1","start":"2026-01-05","boards":["default","web"],"status":"active","milestone":{"number":3,"url":"u"}}]"#,
+        );
+        assert!(rename_in_sprints(&mut sprints, "default", Some("web")));
+        assert_eq!(
+            serde_json::to_string(&sprints).unwrap(),
+            r#"[{"name":"S1","start":"2026-01-05","boards":["web"],"status":"active","milestone":{"number":3,"url":"u"}}]"#
+        );
+        assert!(!rename_in_sprints(&mut sprints, "default", None));
+
+        let mut pm = parse(
+            r#"{"version":"1","auto_branch":true,"sync_boards":{"a":true,"m":false,"z":true},"timezone":"UTC"}"#,
+        );
+        assert!(rename_in_pm(&mut pm, "m", Some("b")));
+        assert_eq!(
+            serde_json::to_string(&pm).unwrap(),
+            r#"{"version":"1","auto_branch":true,"sync_boards":{"a":true,"b":false,"z":true},"timezone":"UTC"}"#
+        );
+        assert!(rename_in_pm(&mut pm, "b", None));
+        assert!(!rename_in_pm(&mut pm, "b", None));
+    }
+}
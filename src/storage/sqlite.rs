@@ -0,0 +1,193 @@
+//! The `sqlite` storage layout: every board in `.kuk/kuk.db`, one row for
+//! each board's columns and labels and one for each card, so a save only
+//! rewrites the cards that changed. Rows hold the same JSON as the files of
+//! the other layouts, and [`read`] hands them back the way
+//! [`Store`](super::Store) reads files, with a made-up path per row for
+//! error messages.
+//!
+//! Built only with the `sqlite` feature; without it boards in this layout
+//! can't be read or written.
+
+/// The database file under `.kuk/`.
+pub const DB_FILE: &str = "kuk.db";
+
+#[cfg(feature = "sqlite")]
+mod imp {
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    use rusqlite::{Connection, OptionalExtension, params};
+
+    use crate::error::{KukError, Result};
+    use crate::model::Board;
+
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS boards (
+            name TEXT PRIMARY KEY,
+            data TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS cards (
+            board TEXT NOT NULL,
+            id TEXT NOT NULL,
+            data TEXT NOT NULL,
+            PRIMARY KEY (board, id)
+        );";
+
+    /// Where a row is reported to come from: `kuk.db#<board>` for the board,
+    /// `kuk.db#<board>/<card-id>` for a card.
+    fn row_path(db: &Path, board: &str, card: Option<&str>) -> PathBuf {
+        let mut path = format!("{}#{board}", db.display());
+        if let Some(card) = card {
+            path.push('/');
+            path.push_str(card);
+        }
+        PathBuf::from(path)
+    }
+
+    fn db_error(db: &Path) -> impl FnOnce(rusqlite::Error) -> KukError + '_ {
+        move |e| KukError::Other(format!("{}: {e}", db.display()))
+    }
+
+    fn open(db: &Path) -> Result<Connection> {
+        let conn = Connection::open(db).map_err(db_error(db))?;
+        conn.execute_batch(SCHEMA).map_err(db_error(db))?;
+        Ok(conn)
+    }
+
+    /// Whether the database holds a board called `name`.
+    pub fn has_board(db: &Path, name: &str) -> Result<bool> {
+        let conn = open(db)?;
+        conn.query_row("SELECT 1 FROM boards WHERE name = ?1", [name], |_| Ok(()))
+            .optional()
+            .map(|row| row.is_some())
+            .map_err(db_error(db))
+    }
+
+    /// The names of the boards in the database, sorted.
+    pub fn boards(db: &Path) -> Result<Vec<String>> {
+        let conn = open(db)?;
+        let mut stmt = conn
+            .prepare("SELECT name FROM boards ORDER BY name")
+            .map_err(db_error(db))?;
+        let names = stmt
+            .query_map([], |row| row.get(0))
+            .and_then(Iterator::collect)
+            .map_err(db_error(db))?;
+        Ok(names)
+    }
+
+    /// A board's rows as (path, JSON) pairs: the board without its
+    /// cards, then each card.
+    pub fn read(db: &Path, name: &str) -> Result<Vec<(PathBuf, String)>> {
+        let conn = open(db)?;
+        let header: Option<String> = conn
+            .query_row("SELECT data FROM boards WHERE name = ?1", [name], |row| {
+                row.get(0)
+            })
+            .optional()
+            .map_err(db_error(db))?;
+        let Some(header) = header else {
+            return Err(KukError::BoardNotFound(name.into()));
+        };
+        let mut rows = vec![(row_path(db, name, None), header)];
+        // By ID, as the cards layout lists its files
+        let mut stmt = conn
+            .prepare("SELECT id, data FROM cards WHERE board = ?1 ORDER BY id")
+            .map_err(db_error(db))?;
+        let cards = stmt
+            .query_map([name], |row| {
+                let id: String = row.get(0)?;
+                Ok((row_path(db, name, Some(&id)), row.get(1)?))
+            })
+            .and_then(Iterator::collect::<rusqlite::Result<Vec<_>>>)
+            .map_err(db_error(db))?;
+        rows.extend(cards);
+        Ok(rows)
+    }
+
+    /// Store `board` in one transaction, with `header` as its row: rewrite
+    /// the rows of cards that changed and drop those of cards no longer on
+    /// it.
+    pub fn write(db: &Path, board: &Board, header: &str) -> Result<()> {
+        let mut conn = open(db)?;
+        let tx = conn.transaction().map_err(db_error(db))?;
+        let mut stored: HashMap<String, String> = {
+            let mut stmt = tx
+                .prepare("SELECT id, data FROM cards WHERE board = ?1")
+                .map_err(db_error(db))?;
+            stmt.query_map([&board.name], |row| Ok((row.get(0)?, row.get(1)?)))
+                .and_then(Iterator::collect)
+                .map_err(db_error(db))?
+        };
+        for card in &board.cards {
+            let json = serde_json::to_string(card)?;
+            if stored.remove(&card.id).as_deref() != Some(json.as_str()) {
+                tx.execute(
+                    "INSERT OR REPLACE INTO cards (board, id, data) VALUES (?1, ?2, ?3)",
+                    params![board.name, card.id, json],
+                )
+                .map_err(db_error(db))?;
+            }
+        }
+        for id in stored.keys() {
+            tx.execute(
+                "DELETE FROM cards WHERE board = ?1 AND id = ?2",
+                params![board.name, id],
+            )
+            .map_err(db_error(db))?;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO boards (name, data) VALUES (?1, ?2)",
+            params![board.name, header],
+        )
+        .map_err(db_error(db))?;
+        tx.commit().map_err(db_error(db))
+    }
+
+    /// Drop a board and its cards.
+    pub fn remove(db: &Path, name: &str) -> Result<()> {
+        let mut conn = open(db)?;
+        let tx = conn.transaction().map_err(db_error(db))?;
+        tx.execute("DELETE FROM cards WHERE board = ?1", [name])
+            .map_err(db_error(db))?;
+        tx.execute("DELETE FROM boards WHERE name = ?1", [name])
+            .map_err(db_error(db))?;
+        tx.commit().map_err(db_error(db))
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+mod imp {
+    use std::path::{Path, PathBuf};
+
+    use crate::error::{KukError, Result};
+    use crate::model::Board;
+
+    fn unsupported() -> KukError {
+        KukError::Other(
+            "This kuk was built without SQLite support; rebuild it with `--features sqlite`".into(),
+        )
+    }
+
+    pub fn has_board(_db: &Path, _name: &str) -> Result<bool> {
+        Err(unsupported())
+    }
+
+    pub fn boards(_db: &Path) -> Result<Vec<String>> {
+        Err(unsupported())
+    }
+
+    pub fn read(_db: &Path, _name: &str) -> Result<Vec<(PathBuf, String)>> {
+        Err(unsupported())
+    }
+
+    pub fn write(_db: &Path, _board: &Board, _header: &str) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn remove(_db: &Path, _name: &str) -> Result<()> {
+        Err(unsupported())
+    }
+}
+
+pub use imp::{boards, has_board, read, remove, write};
@@ -1,12 +1,59 @@
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
 use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
 
 use crate::error::{KukError, Result};
-use crate::model::{Board, GlobalIndex, RepoConfig};
+use crate::model::{
+    BUILTIN_BOARD_TEMPLATES, Board, BoardChange, BoardTemplate, Card, CardTemplate, GlobalIndex,
+    OpLog, Operation, Origin, RepoConfig, SCHEMA_VERSION, StorageLayout, UsageLog,
+};
+
+use super::summary::{parse_board_summary, parse_card_summary};
+use super::{migrate, refs, sqlite};
+
+const TEMPLATE_EXTENSIONS: &[&str] = &["yaml", "yml", "json"];
+
+/// Files under `.kuk/` that belong to this checkout only, listed in the
+/// `.kuk/.gitignore` that `init` writes.
+const LOCAL_FILES: &[&str] = &[".lock", "usage.json"];
+
+thread_local! {
+    /// The `.kuk/` directories whose lock this thread holds, so a save
+    /// inside [`Store::update_board`] doesn't wait on its own lock.
+    static HELD_LOCKS: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The advisory lock on one `.kuk/`, held until dropped; see
+/// [`Store::lock`].
+pub struct StoreLock {
+    _file: fs::File,
+    dir: PathBuf,
+}
+
+impl Drop for StoreLock {
+    fn drop(&mut self) {
+        HELD_LOCKS.with(|held| held.borrow_mut().retain(|dir| *dir != self.dir));
+    }
+}
 
 /// The core storage layer. All file I/O goes through here.
+#[derive(Debug, Clone)]
 pub struct Store {
     repo_root: PathBuf,
+    /// Stamped on changes saved through this store; see [`Store::with_origin`].
+    origin: Option<Origin>,
+    /// The command board saves are logged under; see [`Store::with_oplog`].
+    command: Option<String>,
+    opened_at: DateTime<Utc>,
+    /// The config as last read or saved; see [`Store::with_config_cache`].
+    config: Option<Arc<Mutex<Option<RepoConfig>>>>,
 }
 
 impl Store {
@@ -14,9 +61,51 @@ impl Store {
     pub fn new(repo_root: impl Into<PathBuf>) -> Self {
         Self {
             repo_root: repo_root.into(),
+            origin: None,
+            command: None,
+            opened_at: Utc::now(),
+            config: None,
         }
     }
 
+    /// Attribute changes saved through this store to `origin`: cards
+    /// created, moves and other events recorded since the store was made
+    /// get it on `save_board`, unless they name an origin already.
+    pub fn with_origin(self, origin: Origin) -> Self {
+        Self {
+            origin: Some(origin),
+            ..self
+        }
+    }
+
+    /// Record board saves through this store in `.kuk/oplog.json` as one
+    /// operation of `command`, so `kuk undo` can reverse them together.
+    pub fn with_oplog(self, command: impl Into<String>) -> Self {
+        Self {
+            command: Some(command.into()),
+            ..self
+        }
+    }
+
+    /// Read the config once and keep it, for a command that is done before
+    /// anyone would edit it. Saves through this store keep it current.
+    pub fn with_config_cache(self) -> Self {
+        Self {
+            config: Some(Arc::default()),
+            ..self
+        }
+    }
+
+    /// Where changes saved through this store come from, if set.
+    pub fn origin(&self) -> Option<&Origin> {
+        self.origin.as_ref()
+    }
+
+    /// The repository root this store belongs to.
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
     /// The .kuk directory path.
     pub fn kuk_dir(&self) -> PathBuf {
         self.repo_root.join(".kuk")
@@ -34,6 +123,28 @@ impl Store {
         self.boards_dir().join(format!("{name}.json"))
     }
 
+    /// The directory of a board stored in the cards layout.
+    fn board_dir(&self, name: &str) -> PathBuf {
+        self.boards_dir().join(name)
+    }
+
+    /// The database of boards stored in the sqlite layout.
+    fn db_path(&self) -> PathBuf {
+        self.kuk_dir().join(sqlite::DB_FILE)
+    }
+
+    fn oplog_path(&self) -> PathBuf {
+        self.kuk_dir().join("oplog.json")
+    }
+
+    fn usage_path(&self) -> PathBuf {
+        self.kuk_dir().join("usage.json")
+    }
+
+    fn templates_dir(&self) -> PathBuf {
+        self.kuk_dir().join("card-templates")
+    }
+
     /// Check if .kuk/ exists.
     pub fn is_initialized(&self) -> bool {
         self.kuk_dir().exists()
@@ -41,19 +152,23 @@ impl Store {
 
     /// Initialize .kuk/ with default config and board.
     pub fn init(&self) -> Result<()> {
+        self.init_with(&RepoConfig::default(), &Board::default_board())
+    }
+
+    /// Initialize .kuk/ with the given config and first board.
+    pub fn init_with(&self, config: &RepoConfig, board: &Board) -> Result<()> {
         if self.is_initialized() {
             return Err(KukError::AlreadyInitialized(
                 self.kuk_dir().display().to_string(),
             ));
         }
 
-        fs::create_dir_all(self.boards_dir())?;
-
-        let config = RepoConfig::default();
-        self.write_json(&self.config_path(), &config)?;
-
-        let board = Board::default_board();
-        self.write_json(&self.board_path(&board.name), &board)?;
+        fs::create_dir_all(self.boards_dir()).map_err(KukError::write(self.boards_dir()))?;
+        let _lock = self.lock()?;
+        let ignore = self.kuk_dir().join(".gitignore");
+        fs::write(&ignore, LOCAL_FILES.join("\n") + "\n").map_err(KukError::write(&ignore))?;
+        self.write_config(config)?;
+        self.write_board(board, config.layout)?;
 
         // Register in global index
         if let Some(global) = Self::global_index_path() {
@@ -75,65 +190,654 @@ impl Store {
     }
 
     /// Load per-repo config.
+    #[tracing::instrument(level = "debug", skip(self))]
     pub fn load_config(&self) -> Result<RepoConfig> {
+        if let Some(config) = self.config.as_ref().and_then(|c| c.lock().unwrap().clone()) {
+            return Ok(config);
+        }
+        let config = self.read_config()?;
+        if let Some(cache) = &self.config {
+            *cache.lock().unwrap() = Some(config.clone());
+        }
+        Ok(config)
+    }
+
+    fn read_config(&self) -> Result<RepoConfig> {
         self.ensure_initialized()?;
-        let data = fs::read_to_string(self.config_path())?;
-        Ok(serde_json::from_str(&data)?)
+        let path = self.config_path();
+        let data = read_file(&path)?;
+        let version = migrate::version_in(&data).map_err(KukError::invalid_json(&path))?;
+        if version < SCHEMA_VERSION.into() {
+            let mut value: Value =
+                serde_json::from_str(&data).map_err(KukError::invalid_json(&path))?;
+            migrate::config(&mut value, &path)?;
+            return serde_json::from_value(value).map_err(KukError::invalid_json(path));
+        }
+        migrate::check_version(version, &path)?;
+        serde_json::from_str(&data).map_err(KukError::invalid_json(path))
     }
 
     /// Save per-repo config.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub fn save_config(&self, config: &RepoConfig) -> Result<()> {
         self.ensure_initialized()?;
-        self.write_json(&self.config_path(), config)
+        let _lock = self.lock()?;
+        self.write_config(config)
+    }
+
+    fn write_config(&self, config: &RepoConfig) -> Result<()> {
+        self.write_json(&self.config_path(), config)?;
+        if let Some(cache) = &self.config {
+            *cache.lock().unwrap() = Some(config.clone());
+        }
+        Ok(())
     }
 
     /// Load a board by name.
+    #[tracing::instrument(level = "debug", skip(self))]
     pub fn load_board(&self, name: &str) -> Result<Board> {
+        self.load_board_revision(name).map(|(board, _)| board)
+    }
+
+    /// Load a board along with its revision, a hash of the board's files
+    /// that changes whenever the board is saved with different content.
+    pub fn load_board_revision(&self, name: &str) -> Result<(Board, String)> {
         self.ensure_initialized()?;
-        let path = self.board_path(name);
-        if !path.exists() {
-            return Err(KukError::BoardNotFound(name.into()));
+        let files = self.read_board_files(name)?;
+        let mut board = parse_board(&files)?;
+        board.number_cards();
+        Ok((board, revision_of(&files)))
+    }
+
+    /// The current revision of a board; see [`Store::load_board_revision`].
+    pub fn board_revision(&self, name: &str) -> Result<String> {
+        self.ensure_initialized()?;
+        Ok(revision_of(&self.read_board_files(name)?))
+    }
+
+    /// Load a board for display or reporting without card descriptions and
+    /// metadata, which is much cheaper on boards with large card bodies.
+    /// Column moves are kept, since reports time cards by them. The
+    /// result is marked `partial` and can't be saved. A board in an older
+    /// format is loaded whole so its migrations run.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn load_board_summary(&self, name: &str) -> Result<Board> {
+        self.ensure_initialized()?;
+        let mut files = self.read_board_files(name)?.into_iter();
+        let (path, data) = files.next().expect("a board has at least one file");
+        let mut board = parse_board_summary(&data).map_err(KukError::invalid_json(&path))?;
+        if board.schema_version < SCHEMA_VERSION {
+            let mut board = self.load_board(name)?;
+            board.partial = true;
+            return Ok(board);
+        }
+        migrate::check_version(board.schema_version.into(), &path)?;
+        for (path, data) in files {
+            board
+                .cards
+                .push(parse_card_summary(&data).map_err(KukError::invalid_json(path))?);
         }
-        let data = fs::read_to_string(path)?;
-        Ok(serde_json::from_str(&data)?)
+        board.number_cards();
+        Ok(board)
     }
 
-    /// Save a board.
+    /// How a board is stored, or None if there's no such board. A board
+    /// file wins over a directory of the same name, and both over the
+    /// database.
+    pub fn board_layout(&self, name: &str) -> Option<StorageLayout> {
+        if self.board_path(name).is_file() {
+            Some(StorageLayout::File)
+        } else if self.board_dir(name).join("board.json").is_file() {
+            Some(StorageLayout::Cards)
+        } else if self.db_path().is_file() && sqlite::has_board(&self.db_path(), name).ok()? {
+            Some(StorageLayout::Sqlite)
+        } else {
+            None
+        }
+    }
+
+    /// The files a board is stored in: its board file, the `board.json`
+    /// and then the card files, by name, of the cards layout, or the
+    /// database.
+    pub fn board_files(&self, name: &str) -> Result<Vec<PathBuf>> {
+        match self.board_layout(name) {
+            None => Err(KukError::BoardNotFound(name.into())),
+            Some(StorageLayout::File) => Ok(vec![self.board_path(name)]),
+            Some(StorageLayout::Cards) => {
+                let dir = self.board_dir(name);
+                let mut files = vec![dir.join("board.json")];
+                files.extend(card_files(&dir.join("cards"))?);
+                Ok(files)
+            }
+            Some(StorageLayout::Sqlite) => Ok(vec![self.db_path()]),
+        }
+    }
+
+    /// Where `op` left its changes: the operation log and the files of
+    /// each board it touched that still exists. Boards in the cards layout
+    /// give their whole directory, so card files the operation removed
+    /// are included.
+    pub fn operation_paths(&self, op: &Operation) -> Vec<PathBuf> {
+        let mut paths = vec![self.oplog_path()];
+        for change in &op.boards {
+            match self.board_layout(&change.board) {
+                None => {}
+                Some(StorageLayout::File) => paths.push(self.board_path(&change.board)),
+                Some(StorageLayout::Cards) => paths.push(self.board_dir(&change.board)),
+                Some(StorageLayout::Sqlite) => paths.push(self.db_path()),
+            }
+        }
+        paths.dedup();
+        paths
+    }
+
+    /// A board's stored JSON: the contents of its files, or its rows in the
+    /// database.
+    fn read_board_files(&self, name: &str) -> Result<Vec<(PathBuf, String)>> {
+        if self.board_layout(name) == Some(StorageLayout::Sqlite) {
+            return sqlite::read(&self.db_path(), name);
+        }
+        self.board_files(name)?
+            .into_iter()
+            .map(|path| {
+                let data = read_file(&path)?;
+                Ok((path, data))
+            })
+            .collect()
+    }
+
+    /// Save a board. The file is replaced in one rename under the store's
+    /// lock, so readers see the old board or the new one, never a mix.
+    #[tracing::instrument(level = "debug", skip_all, fields(name = %board.name))]
     pub fn save_board(&self, board: &Board) -> Result<()> {
         self.ensure_initialized()?;
-        self.write_json(&self.board_path(&board.name), board)
+        if board.partial {
+            return Err(KukError::Other(format!(
+                "Refusing to save partially loaded board: {}",
+                board.name
+            )));
+        }
+        let _lock = self.lock()?;
+        let layout = match self.board_layout(&board.name) {
+            Some(layout) => layout,
+            None => self.load_config()?.layout,
+        };
+        if let Some(origin) = &self.origin {
+            let mut board = board.clone();
+            board.stamp_origin(origin, self.opened_at);
+            self.log_save(&board)?;
+            return self.write_board(&board, layout);
+        }
+        self.log_save(board)?;
+        self.write_board(board, layout)
+    }
+
+    /// Take the advisory lock on `.kuk/`, waiting while another process or
+    /// thread holds it, so writes from concurrent `kuk` commands, servers
+    /// and TUIs take turns. Released when the returned guard is dropped.
+    /// Hold it from a load to the save of what was loaded, so no other
+    /// writer's save lands in between and is lost. A thread that already
+    /// holds the lock gets None and keeps it.
+    pub fn lock(&self) -> Result<Option<StoreLock>> {
+        let dir = self.kuk_dir();
+        if HELD_LOCKS.with(|held| held.borrow().contains(&dir)) {
+            return Ok(None);
+        }
+        let path = dir.join(".lock");
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(KukError::write(&path))?;
+        match file.lock() {
+            Ok(()) => {
+                HELD_LOCKS.with(|held| held.borrow_mut().push(dir.clone()));
+                Ok(Some(StoreLock { _file: file, dir }))
+            }
+            // Platforms without file locks, like wasm, write unlocked
+            Err(e) if e.kind() == std::io::ErrorKind::Unsupported => Ok(None),
+            Err(e) => Err(KukError::write(path)(e)),
+        }
+    }
+
+    /// Load board `name`, change it with `change` and save it, holding the
+    /// lock from the load to the save so no other writer's save lands in
+    /// between and is lost. An error from `change` saves nothing.
+    pub fn update_board<T>(
+        &self,
+        name: &str,
+        change: impl FnOnce(&mut Board) -> Result<T>,
+    ) -> Result<T> {
+        self.ensure_initialized()?;
+        let _lock = self.lock()?;
+        let mut board = self.load_board(name)?;
+        let value = change(&mut board)?;
+        self.save_board(&board)?;
+        Ok(value)
+    }
+
+    /// Add what saving `board` changes to the operation log, when this
+    /// store keeps one.
+    fn log_save(&self, board: &Board) -> Result<()> {
+        let Some(command) = &self.command else {
+            return Ok(());
+        };
+        if self.board_layout(&board.name).is_none() {
+            return Ok(());
+        }
+        let old = parse_board(&self.read_board_files(&board.name)?)?;
+        if let Some(change) = BoardChange::between(&old, board) {
+            let mut log = self.load_oplog()?;
+            log.record(self.opened_at, command, change);
+            self.write_json(&self.oplog_path(), &log)?;
+        }
+        Ok(())
+    }
+
+    /// The operation log, empty if nothing has been logged yet.
+    pub fn load_oplog(&self) -> Result<OpLog> {
+        self.ensure_initialized()?;
+        let path = self.oplog_path();
+        if !path.exists() {
+            return Ok(OpLog::default());
+        }
+        let data = read_file(&path)?;
+        serde_json::from_str(&data).map_err(KukError::invalid_json(path))
+    }
+
+    /// The operation this store's command has logged so far, if it has
+    /// changed anything yet.
+    pub fn current_operation(&self) -> Result<Option<Operation>> {
+        let Some(command) = &self.command else {
+            return Ok(None);
+        };
+        let log = self.load_oplog()?;
+        Ok(log
+            .ops
+            .into_iter()
+            .rfind(|op| op.at == self.opened_at && op.command == *command && !op.undone))
+    }
+
+    /// The local usage log, empty if nothing has been recorded yet.
+    pub fn load_usage(&self) -> Result<UsageLog> {
+        self.ensure_initialized()?;
+        let path = self.usage_path();
+        if !path.exists() {
+            return Ok(UsageLog::default());
+        }
+        let data = read_file(&path)?;
+        serde_json::from_str(&data).map_err(KukError::invalid_json(path))
+    }
+
+    /// Count one run of `command` in the usage log.
+    pub fn record_usage(&self, command: &str, took: std::time::Duration, ok: bool) -> Result<()> {
+        self.ensure_initialized()?;
+        let _lock = self.lock()?;
+        let mut log = self.load_usage()?;
+        log.record(command, took, ok, Utc::now());
+        self.write_json(&self.usage_path(), &log)
+    }
+
+    /// Empty the usage log.
+    pub fn clear_usage(&self) -> Result<()> {
+        self.ensure_initialized()?;
+        let _lock = self.lock()?;
+        let path = self.usage_path();
+        if path.exists() {
+            fs::remove_file(&path).map_err(KukError::write(path))?;
+        }
+        Ok(())
+    }
+
+    /// Reverse the newest logged operation still in effect.
+    pub fn undo(&self) -> Result<Operation> {
+        self.replay(true)
+    }
+
+    /// Replay the oldest operation undone since the last one in effect.
+    pub fn redo(&self) -> Result<Operation> {
+        self.replay(false)
+    }
+
+    /// Apply an operation's changes backward (`undo`) or forward, to every
+    /// board it touched or none of them. The boards are saved without
+    /// logging, so undoing doesn't itself become something to undo.
+    fn replay(&self, undo: bool) -> Result<Operation> {
+        self.ensure_initialized()?;
+        let _lock = self.lock()?;
+        let mut log = self.load_oplog()?;
+        let (target, verb) = if undo {
+            (log.undo_target(), "undo")
+        } else {
+            (log.redo_target(), "redo")
+        };
+        let index = target.ok_or_else(|| KukError::Other(format!("Nothing to {verb}.")))?;
+        let op = &log.ops[index];
+
+        let mut boards = Vec::new();
+        for change in &op.boards {
+            let mut board = self.load_board(&change.board)?;
+            change.apply(&mut board, undo).map_err(|what| {
+                KukError::Other(format!(
+                    "Can't {verb} `{}`: {what} has changed since.",
+                    op.command
+                ))
+            })?;
+            boards.push(board);
+        }
+        for board in &boards {
+            let layout = self.board_layout(&board.name).unwrap_or_default();
+            self.write_board(board, layout)?;
+        }
+        log.ops[index].undone = undo;
+        self.write_json(&self.oplog_path(), &log)?;
+        Ok(log.ops.swap_remove(index))
     }
 
     /// List all board names.
     pub fn list_boards(&self) -> Result<Vec<String>> {
         self.ensure_initialized()?;
         let mut boards = Vec::new();
-        for entry in fs::read_dir(self.boards_dir())? {
-            let entry = entry?;
+        let dir = self.boards_dir();
+        for entry in fs::read_dir(&dir).map_err(KukError::read(&dir))? {
+            let entry = entry.map_err(KukError::read(&dir))?;
             let path = entry.path();
             if path.extension().is_some_and(|e| e == "json")
                 && let Some(stem) = path.file_stem()
             {
                 boards.push(stem.to_string_lossy().to_string());
+            } else if path.join("board.json").is_file() {
+                boards.push(entry.file_name().to_string_lossy().to_string());
             }
         }
+        if self.db_path().is_file() {
+            boards.extend(sqlite::boards(&self.db_path())?);
+        }
         boards.sort();
+        boards.dedup();
         Ok(boards)
     }
 
     /// Create a new board.
+    #[tracing::instrument(level = "debug", skip(self, columns))]
     pub fn create_board(&self, name: &str, columns: Vec<crate::model::Column>) -> Result<()> {
-        self.ensure_initialized()?;
-        let path = self.board_path(name);
-        if path.exists() {
-            return Err(KukError::Other(format!("Board already exists: {name}")));
-        }
-        let board = Board {
+        self.add_board(&Board {
+            schema_version: SCHEMA_VERSION,
             name: name.into(),
             columns,
             cards: Vec::new(),
+            labels: Vec::new(),
+            strict_labels: false,
+            last_seq: 0,
+            swimlanes: None,
+            partial: false,
+        })
+    }
+
+    /// Create a new board laid out by `template`.
+    #[tracing::instrument(level = "debug", skip(self, template))]
+    pub fn create_board_from(&self, name: &str, template: &BoardTemplate) -> Result<()> {
+        self.add_board(&template.board(name).map_err(KukError::Other)?)
+    }
+
+    fn add_board(&self, board: &Board) -> Result<()> {
+        self.ensure_initialized()?;
+        if self.board_layout(&board.name).is_some() {
+            return Err(KukError::Other(format!(
+                "Board already exists: {}",
+                board.name
+            )));
+        }
+        let _lock = self.lock()?;
+        self.write_board(board, self.load_config()?.layout)
+    }
+
+    /// Rename a board: move its file and point the repo config, and the
+    /// sprints and board settings kuk-pm keeps, at the new name.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn rename_board(&self, old: &str, new: &str) -> Result<()> {
+        self.ensure_initialized()?;
+        if new.is_empty() || new.contains(['/', '\\']) || new.starts_with('.') {
+            return Err(KukError::Other(format!(
+                "Invalid board name: {new:?}. Board names can't be empty, contain slashes or start with '.'"
+            )));
+        }
+        if self.board_layout(new).is_some() {
+            return Err(KukError::Other(format!("Board already exists: {new}")));
+        }
+        let _lock = self.lock()?;
+        let mut board = self.load_board(old)?;
+        let layout = self.board_layout(old).unwrap_or_default();
+        board.name = new.into();
+        self.write_board(&board, layout)?;
+        self.remove_board_files(old, layout)?;
+
+        let mut config = self.load_config()?;
+        if config.default_board == old {
+            config.default_board = new.into();
+            self.write_config(&config)?;
+        }
+        self.update_oplog_refs(old, Some(new))?;
+        self.update_board_refs(old, Some(new))
+    }
+
+    /// Delete a board's file, with any cards still on it. References to it
+    /// are pointed at `to` when given and dropped otherwise; if it was the
+    /// active board, `to` or else the first other board becomes active.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn delete_board(&self, name: &str, to: Option<&str>) -> Result<()> {
+        self.ensure_initialized()?;
+        let Some(layout) = self.board_layout(name) else {
+            return Err(KukError::BoardNotFound(name.into()));
         };
-        self.write_json(&path, &board)
+        let others: Vec<String> = self
+            .list_boards()?
+            .into_iter()
+            .filter(|b| b != name)
+            .collect();
+        let next = match to.map(String::from).or_else(|| others.first().cloned()) {
+            Some(next) => next,
+            None => {
+                return Err(KukError::Other(format!(
+                    "Cannot delete {name}: it is the only board"
+                )));
+            }
+        };
+        let _lock = self.lock()?;
+        self.remove_board_files(name, layout)?;
+
+        let mut config = self.load_config()?;
+        if config.default_board == name {
+            config.default_board = next;
+            self.write_config(&config)?;
+        }
+        self.update_oplog_refs(name, None)?;
+        self.update_board_refs(name, to)
+    }
+
+    /// Store every board in `layout`, and new boards too from now on.
+    /// Returns the boards that were converted.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn set_layout(&self, layout: StorageLayout) -> Result<Vec<String>> {
+        self.ensure_initialized()?;
+        let _lock = self.lock()?;
+        let mut converted = Vec::new();
+        for name in self.list_boards()? {
+            let Some(current) = self.board_layout(&name).filter(|l| *l != layout) else {
+                continue;
+            };
+            let board = parse_board(&self.read_board_files(&name)?)?;
+            self.write_board(&board, layout)?;
+            self.remove_board_files(&name, current)?;
+            converted.push(name);
+        }
+        let mut config = self.load_config()?;
+        if config.layout != layout {
+            config.layout = layout;
+            self.write_config(&config)?;
+        }
+        Ok(converted)
+    }
+
+    /// Point the boards listed in `sprints.json` and the `sync_boards` of
+    /// `pm.json`, which kuk-pm keeps, from `old` to `new`, or drop `old`
+    /// when `new` is None. Files that don't exist are left alone.
+    fn update_board_refs(&self, old: &str, new: Option<&str>) -> Result<()> {
+        let sprints_path = self.kuk_dir().join("sprints.json");
+        if sprints_path.exists() {
+            let mut sprints: refs::Json = serde_json::from_str(&read_file(&sprints_path)?)
+                .map_err(KukError::invalid_json(&sprints_path))?;
+            if refs::rename_in_sprints(&mut sprints, old, new) {
+                self.write_json(&sprints_path, &sprints)?;
+            }
+        }
+
+        let pm_path = self.kuk_dir().join("pm.json");
+        if pm_path.exists() {
+            let mut pm: refs::Json = serde_json::from_str(&read_file(&pm_path)?)
+                .map_err(KukError::invalid_json(&pm_path))?;
+            if refs::rename_in_pm(&mut pm, old, new) {
+                self.write_json(&pm_path, &pm)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Point logged changes to board `old` at `new` after a rename, or,
+    /// when `old` was deleted, drop the operations that changed it, which
+    /// can no longer be undone or redone. That includes this command's,
+    /// whose moves of the deleted board's cards can't be undone either.
+    fn update_oplog_refs(&self, old: &str, new: Option<&str>) -> Result<()> {
+        let path = self.oplog_path();
+        if !path.exists() {
+            return Ok(());
+        }
+        let mut log = self.load_oplog()?;
+        match new {
+            Some(new) => log.rename_board(old, new),
+            None => {
+                log.forget_board(old);
+                if let Some(command) = &self.command {
+                    log.ops
+                        .retain(|op| op.at != self.opened_at || op.command != *command);
+                }
+            }
+        }
+        self.write_json(&path, &log)
+    }
+
+    // --- Card templates ---
+
+    /// Load a card template by name from `.kuk/card-templates/`, trying
+    /// `.yaml`, `.yml`, then `.json`.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn load_card_template(&self, name: &str) -> Result<CardTemplate> {
+        self.ensure_initialized()?;
+        for ext in TEMPLATE_EXTENSIONS {
+            let path = self.templates_dir().join(format!("{name}.{ext}"));
+            if !path.exists() {
+                continue;
+            }
+            return parse_template(&path, name);
+        }
+        Err(KukError::TemplateNotFound(name.into()))
+    }
+
+    /// List card template names.
+    pub fn list_card_templates(&self) -> Result<Vec<String>> {
+        self.ensure_initialized()?;
+        let dir = self.templates_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut templates = Vec::new();
+        for entry in fs::read_dir(&dir).map_err(KukError::read(&dir))? {
+            let path = entry.map_err(KukError::read(&dir))?.path();
+            if path
+                .extension()
+                .is_some_and(|e| TEMPLATE_EXTENSIONS.iter().any(|t| e == *t))
+                && let Some(stem) = path.file_stem()
+            {
+                templates.push(stem.to_string_lossy().to_string());
+            }
+        }
+        templates.sort();
+        templates.dedup();
+        Ok(templates)
+    }
+
+    // --- Board templates ---
+
+    /// Where `kuk board save-template` keeps templates for every repo:
+    /// `~/.kuk/board-templates/`.
+    pub fn board_templates_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|h| h.join(".kuk").join("board-templates"))
+    }
+
+    /// Resolve a board template: a `.yaml`, `.yml` or `.json` file when
+    /// `name` is a path to one, else a saved template by that name, else a
+    /// built-in.
+    pub fn load_board_template(name: &str) -> Result<BoardTemplate> {
+        let path = Path::new(name);
+        if path
+            .extension()
+            .is_some_and(|e| TEMPLATE_EXTENSIONS.iter().any(|t| e == *t))
+        {
+            return parse_template(path, name);
+        }
+        if let Some(dir) = Self::board_templates_dir() {
+            for ext in TEMPLATE_EXTENSIONS {
+                let path = dir.join(format!("{name}.{ext}"));
+                if path.exists() {
+                    return parse_template(&path, name);
+                }
+            }
+        }
+        BoardTemplate::builtin(name).ok_or_else(|| {
+            let available = Self::list_board_templates().unwrap_or_default().join(", ");
+            KukError::Other(format!(
+                "Board template not found: {name}. Available: {available}"
+            ))
+        })
+    }
+
+    /// Save `template` as `~/.kuk/board-templates/<name>.json`, replacing
+    /// any template saved under that name. Returns the file written.
+    pub fn save_board_template(name: &str, template: &BoardTemplate) -> Result<PathBuf> {
+        if name.is_empty() || name.contains(['/', '\\', '.']) {
+            return Err(KukError::Other(format!("Invalid template name: {name}")));
+        }
+        let dir = Self::board_templates_dir()
+            .ok_or_else(|| KukError::Other("No home directory to save templates in".into()))?;
+        fs::create_dir_all(&dir).map_err(KukError::write(&dir))?;
+        let path = dir.join(format!("{name}.json"));
+        let json = serde_json::to_string_pretty(template)?;
+        fs::write(&path, json).map_err(KukError::write(&path))?;
+        Ok(path)
+    }
+
+    /// Names of the built-in and saved board templates, sorted.
+    pub fn list_board_templates() -> Result<Vec<String>> {
+        let mut templates: Vec<String> = BUILTIN_BOARD_TEMPLATES
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        if let Some(dir) = Self::board_templates_dir().filter(|d| d.exists()) {
+            for entry in fs::read_dir(&dir).map_err(KukError::read(&dir))? {
+                let path = entry.map_err(KukError::read(&dir))?.path();
+                if path
+                    .extension()
+                    .is_some_and(|e| TEMPLATE_EXTENSIONS.iter().any(|t| e == *t))
+                    && let Some(stem) = path.file_stem()
+                {
+                    templates.push(stem.to_string_lossy().to_string());
+                }
+            }
+        }
+        templates.sort();
+        templates.dedup();
+        Ok(templates)
     }
 
     // --- Global index ---
@@ -158,13 +862,165 @@ impl Store {
         }
     }
 
+    /// Write `board` in `layout`. In the cards layout only card files whose
+    /// content changed are rewritten, and files of cards no longer on the
+    /// board are removed.
+    fn write_board(&self, board: &Board, layout: StorageLayout) -> Result<()> {
+        if layout == StorageLayout::File {
+            return self.write_json(&self.board_path(&board.name), board);
+        }
+        let mut header = serde_json::to_value(board)?;
+        if let Some(fields) = header.as_object_mut() {
+            fields.remove("cards");
+        }
+        if layout == StorageLayout::Sqlite {
+            return sqlite::write(&self.db_path(), board, &header.to_string());
+        }
+        let dir = self.board_dir(&board.name);
+        let cards_dir = dir.join("cards");
+        fs::create_dir_all(&cards_dir).map_err(KukError::write(&cards_dir))?;
+
+        let mut stale = card_files(&cards_dir)?;
+        for card in &board.cards {
+            let path = card_path(&cards_dir, card)?;
+            stale.retain(|p| *p != path);
+            let json = serde_json::to_string_pretty(card)?;
+            if fs::read_to_string(&path).ok().as_deref() != Some(json.as_str()) {
+                self.write_file(&path, &json)?;
+            }
+        }
+        self.write_json(&dir.join("board.json"), &header)?;
+        for path in stale {
+            fs::remove_file(&path).map_err(KukError::write(&path))?;
+        }
+        Ok(())
+    }
+
+    fn remove_board_files(&self, name: &str, layout: StorageLayout) -> Result<()> {
+        match layout {
+            StorageLayout::File => {
+                let path = self.board_path(name);
+                fs::remove_file(&path).map_err(KukError::write(&path))
+            }
+            StorageLayout::Cards => {
+                let dir = self.board_dir(name);
+                fs::remove_dir_all(&dir).map_err(KukError::write(&dir))
+            }
+            StorageLayout::Sqlite => sqlite::remove(&self.db_path(), name),
+        }
+    }
+
     fn write_json<T: serde::Serialize>(&self, path: &Path, value: &T) -> Result<()> {
-        let json = serde_json::to_string_pretty(value)?;
-        fs::write(path, json)?;
+        self.write_file(path, &serde_json::to_string_pretty(value)?)
+    }
+
+    /// Write to a temp file beside `path` and rename it over `path`, so a
+    /// crash mid-write leaves the old file rather than half of the new one.
+    fn write_file(&self, path: &Path, json: &str) -> Result<()> {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let tmp = path.with_file_name(format!(".{name}.{}.tmp", std::process::id()));
+        let written = fs::File::create(&tmp)
+            .and_then(|mut file| {
+                file.write_all(json.as_bytes())?;
+                file.sync_all()
+            })
+            .map_err(KukError::write(&tmp))
+            .and_then(|()| fs::rename(&tmp, path).map_err(KukError::write(path)));
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        written?;
+        tracing::debug!(path = %path.display(), bytes = json.len(), "wrote");
         Ok(())
     }
 }
 
+fn read_file(path: &Path) -> Result<String> {
+    let data = fs::read_to_string(path).map_err(KukError::read(path))?;
+    tracing::debug!(path = %path.display(), bytes = data.len(), "read");
+    Ok(data)
+}
+
+/// Parse a card or board template, as YAML unless the file is `.json`.
+fn parse_template<T: serde::de::DeserializeOwned>(path: &Path, name: &str) -> Result<T> {
+    let data = read_file(path)?;
+    let parsed = if path.extension().is_some_and(|e| e == "json") {
+        serde_json::from_str(&data).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(&data).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|e| KukError::Other(format!("Invalid template {name}: {e}")))
+}
+
+/// Build a board from the files [`Store::board_files`] lists.
+fn parse_board(files: &[(PathBuf, String)]) -> Result<Board> {
+    let ((path, data), cards) = files.split_first().expect("a board has at least one file");
+    let version = migrate::version_in(data).map_err(KukError::invalid_json(path))?;
+    if version < SCHEMA_VERSION.into() {
+        return upgrade_board(files);
+    }
+    migrate::check_version(version, path)?;
+    let mut board: Board = serde_json::from_str(data).map_err(KukError::invalid_json(path))?;
+    for (path, data) in cards {
+        let card: Card = serde_json::from_str(data).map_err(KukError::invalid_json(path))?;
+        board.cards.push(card);
+    }
+    Ok(board)
+}
+
+/// Build a board written in an older format, running its JSON, with any
+/// card files gathered into `cards`, through the migrations.
+fn upgrade_board(files: &[(PathBuf, String)]) -> Result<Board> {
+    let parse = |(path, data): &(PathBuf, String)| {
+        serde_json::from_str::<Value>(data).map_err(KukError::invalid_json(path))
+    };
+    let ((path, _), cards) = files.split_first().expect("a board has at least one file");
+    let mut board = parse(&files[0])?;
+    if !cards.is_empty()
+        && let Some(fields) = board.as_object_mut()
+    {
+        let cards = cards.iter().map(parse).collect::<Result<Vec<_>>>()?;
+        fields.insert("cards".into(), Value::Array(cards));
+    }
+    migrate::board(&mut board, path)?;
+    serde_json::from_value(board).map_err(KukError::invalid_json(path))
+}
+
+/// The `.json` card files in a cards-layout `cards/` directory, by name.
+fn card_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(KukError::read(dir))? {
+        let path = entry.map_err(KukError::read(dir))?.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Where a card lives in the cards layout, named by its ID.
+fn card_path(cards_dir: &Path, card: &Card) -> Result<PathBuf> {
+    if card.id.is_empty() || card.id.contains(['/', '\\']) || card.id.starts_with('.') {
+        return Err(KukError::Other(format!(
+            "Card ID {:?} can't be used as a file name",
+            card.id
+        )));
+    }
+    Ok(cards_dir.join(format!("{}.json", card.id)))
+}
+
+fn revision_of(files: &[(PathBuf, String)]) -> String {
+    let mut hasher = DefaultHasher::new();
+    for (_, data) in files {
+        data.hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,6 +1086,24 @@ mod tests {
         assert_eq!(reloaded.default_board, "sprint-1");
     }
 
+    #[test]
+    fn cached_config_is_read_once_and_kept_current() {
+        let (dir, store) = temp_store();
+        store.init().unwrap();
+        let store = store.with_config_cache();
+        let mut config = store.load_config().unwrap();
+        config.inbox_column = "edited-elsewhere".into();
+        Store::new(dir.path()).save_config(&config).unwrap();
+        assert_eq!(store.load_config().unwrap().inbox_column, "inbox");
+
+        store.rename_board("default", "main").unwrap();
+        assert_eq!(store.load_config().unwrap().default_board, "main");
+        config.default_board = "main".into();
+        config.usage_log = true;
+        store.save_config(&config).unwrap();
+        assert!(store.load_config().unwrap().usage_log);
+    }
+
     #[test]
     fn load_default_board() {
         let (_dir, store) = temp_store();
@@ -260,6 +1134,81 @@ mod tests {
         assert_eq!(reloaded.cards[0].title, "Task 1");
     }
 
+    #[test]
+    fn concurrent_saves_take_turns_and_leave_no_temp_files() {
+        let (_dir, store) = temp_store();
+        store.init().unwrap();
+
+        // While one writer holds the lock, nobody else can take it
+        let held = store.lock().unwrap().unwrap();
+        let other = fs::File::open(store.kuk_dir().join(".lock")).unwrap();
+        assert!(other.try_lock().is_err());
+        drop(held);
+        assert!(other.try_lock().is_ok());
+        drop(other);
+
+        let threads: Vec<_> = (0..8)
+            .map(|n| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    let mut board = Board::default_board();
+                    for i in 0..=n {
+                        board.add_card(crate::model::Card::new(format!("Card {i}"), "todo"));
+                    }
+                    store.save_board(&board).unwrap();
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert!(!store.load_board("default").unwrap().cards.is_empty());
+        let leftovers: Vec<_> = fs::read_dir(store.boards_dir())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .filter(|name| name != "default.json")
+            .collect();
+        assert!(leftovers.is_empty(), "{leftovers:?}");
+    }
+
+    #[test]
+    fn update_board_holds_the_lock_from_load_to_save() {
+        let (_dir, store) = temp_store();
+        store.init().unwrap();
+        let lock = store.kuk_dir().join(".lock");
+
+        let threads: Vec<_> = (0..8)
+            .map(|n| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    store
+                        .update_board("default", |board| {
+                            board.add_card(crate::model::Card::new(format!("Card {n}"), "todo"));
+                            Ok(())
+                        })
+                        .unwrap();
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(store.load_board("default").unwrap().cards.len(), 8);
+
+        let held = store
+            .update_board("default", |_| {
+                Ok(fs::File::open(&lock).unwrap().try_lock().is_err())
+            })
+            .unwrap();
+        assert!(held);
+        assert!(fs::File::open(&lock).unwrap().try_lock().is_ok());
+        assert_eq!(
+            fs::read_to_string(store.kuk_dir().join(".gitignore")).unwrap(),
+            ".lock\nusage.json\n"
+        );
+    }
+
     #[test]
     fn list_boards() {
         let (_dir, store) = temp_store();
@@ -276,13 +1225,10 @@ mod tests {
             .create_board(
                 "sprint-1",
                 vec![
+                    crate::model::Column::new("backlog"),
                     crate::model::Column {
-                        name: "backlog".into(),
-                        wip_limit: None,
-                    },
-                    crate::model::Column {
-                        name: "active".into(),
                         wip_limit: Some(3),
+                        ..crate::model::Column::new("active")
                     },
                 ],
             )
@@ -298,16 +1244,232 @@ mod tests {
     fn create_duplicate_board_fails() {
         let (_dir, store) = temp_store();
         store.init().unwrap();
-        let result = store.create_board(
-            "default",
-            vec![crate::model::Column {
-                name: "col".into(),
-                wip_limit: None,
-            }],
-        );
+        let result = store.create_board("default", vec![crate::model::Column::new("col")]);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn board_summary_skips_card_bodies() {
+        let (_dir, store) = temp_store();
+        store.init().unwrap();
+        let mut board = store.load_board("default").unwrap();
+        let mut card = crate::model::Card::new("Big", "doing");
+        card.description = Some("x".repeat(10_000));
+        card.metadata
+            .insert("pm".into(), serde_json::json!({"points": 3}));
+        card.labels = vec!["bug".into()];
+        card.assignee = Some("leslie".into());
+        board.cards.push(card.clone());
+        let id = card.id.clone();
+        board.move_card(&id, "done");
+        let card = board.find_card(&id).unwrap().clone();
+        store.save_board(&board).unwrap();
+
+        let summary = store.load_board_summary("default").unwrap();
+        assert!(summary.partial);
+        let loaded = &summary.cards[0];
+        assert!(loaded.description.is_none());
+        // Only the column moves are kept of the metadata
+        assert_eq!(loaded.metadata.len(), 1);
+        assert_eq!(loaded.entered_column_at(), card.entered_column_at());
+        assert!(loaded.entered_column_at().is_some());
+        assert_eq!(loaded.id, card.id);
+        assert_eq!(loaded.labels, card.labels);
+        assert_eq!(loaded.assignee, card.assignee);
+        assert_eq!(loaded.updated_at, card.updated_at);
+
+        assert!(store.save_board(&summary).is_err());
+        assert!(!store.load_board("default").unwrap().partial);
+        assert!(store.load_board_summary("nope").is_err());
+    }
+
+    #[test]
+    fn cards_layout_keeps_one_file_per_card() {
+        let (_dir, store) = temp_store();
+        store.init().unwrap();
+        let mut board = store.load_board("default").unwrap();
+        for title in ["One", "Two", "Three"] {
+            board.add_card(crate::model::Card::new(title, "todo"));
+        }
+        board.cards[0].description = Some("Details".into());
+        store.save_board(&board).unwrap();
+        let before = store.load_board("default").unwrap();
+
+        assert_eq!(store.set_layout(StorageLayout::Cards).unwrap(), ["default"]);
+        assert!(!store.boards_dir().join("default.json").exists());
+        let cards_dir = store.board_dir("default").join("cards");
+        assert_eq!(fs::read_dir(&cards_dir).unwrap().count(), 3);
+        assert_eq!(store.board_layout("default"), Some(StorageLayout::Cards));
+        assert_eq!(store.list_boards().unwrap(), ["default"]);
+        assert_eq!(store.load_config().unwrap().layout, StorageLayout::Cards);
+
+        let mut board = store.load_board("default").unwrap();
+        board.cards.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut expected = before.clone();
+        expected.cards.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(board, expected);
+        let summary = store.load_board_summary("default").unwrap();
+        assert_eq!(summary.cards.len(), 3);
+        assert!(summary.cards.iter().all(|c| c.description.is_none()));
+
+        // Only the changed card's file is rewritten; removed cards' go away
+        let revision = store.board_revision("default").unwrap();
+        let removed = board.cards.remove(0);
+        board.cards[0].title = "Renamed".into();
+        store.save_board(&board).unwrap();
+        assert_ne!(store.board_revision("default").unwrap(), revision);
+        assert!(!cards_dir.join(format!("{}.json", removed.id)).exists());
+        assert_eq!(fs::read_dir(&cards_dir).unwrap().count(), 2);
+
+        // New boards follow the configured layout, and converting back works
+        store.create_board("next", Vec::new()).unwrap();
+        assert_eq!(store.board_layout("next"), Some(StorageLayout::Cards));
+        store.rename_board("next", "later").unwrap();
+        assert_eq!(store.list_boards().unwrap(), ["default", "later"]);
+        assert_eq!(
+            store.set_layout(StorageLayout::File).unwrap(),
+            ["default", "later"]
+        );
+        assert!(!store.board_dir("default").exists());
+        assert_eq!(store.load_board("default").unwrap().cards.len(), 2);
+        assert!(store.set_layout(StorageLayout::File).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_layout_keeps_boards_in_the_database() {
+        let (_dir, store) = temp_store();
+        store.init().unwrap();
+        let mut board = store.load_board("default").unwrap();
+        for title in ["One", "Two", "Three"] {
+            board.add_card(crate::model::Card::new(title, "todo"));
+        }
+        store.save_board(&board).unwrap();
+        let mut before = store.load_board("default").unwrap();
+        before.cards.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(
+            store.set_layout(StorageLayout::Sqlite).unwrap(),
+            ["default"]
+        );
+        assert!(!store.boards_dir().join("default.json").exists());
+        assert_eq!(store.board_layout("default"), Some(StorageLayout::Sqlite));
+        assert_eq!(store.board_files("default").unwrap(), [store.db_path()]);
+        let mut board = store.load_board("default").unwrap();
+        assert_eq!(board, before);
+        assert_eq!(store.load_board_summary("default").unwrap().cards.len(), 3);
+
+        let revision = store.board_revision("default").unwrap();
+        board.cards.remove(0);
+        board.cards[0].title = "Renamed".into();
+        store.save_board(&board).unwrap();
+        assert_ne!(store.board_revision("default").unwrap(), revision);
+        assert_eq!(store.load_board("default").unwrap(), board);
+
+        store.create_board("next", Vec::new()).unwrap();
+        assert_eq!(store.board_layout("next"), Some(StorageLayout::Sqlite));
+        store.rename_board("next", "later").unwrap();
+        assert_eq!(store.list_boards().unwrap(), ["default", "later"]);
+        store.delete_board("later", None).unwrap();
+        assert_eq!(store.list_boards().unwrap(), ["default"]);
+        assert_eq!(store.set_layout(StorageLayout::File).unwrap(), ["default"]);
+        assert_eq!(store.load_board("default").unwrap().cards.len(), 2);
+    }
+
+    #[test]
+    fn files_from_before_schema_versions_are_upgraded() {
+        let (_dir, store) = temp_store();
+        store.init().unwrap();
+        let mut board = store.load_board("default").unwrap();
+        board.add_card(crate::model::Card::new("Old card", "todo"));
+        store.save_board(&board).unwrap();
+        let strip = |path: &Path| {
+            let mut value: Value = serde_json::from_str(&read_file(path).unwrap()).unwrap();
+            value.as_object_mut().unwrap().remove("schema_version");
+            fs::write(path, value.to_string()).unwrap();
+        };
+        strip(&store.board_path("default"));
+        strip(&store.config_path());
+
+        let config = store.load_config().unwrap();
+        assert_eq!(config.schema_version, SCHEMA_VERSION);
+        store.save_config(&config).unwrap();
+        assert!(
+            read_file(&store.config_path())
+                .unwrap()
+                .contains("schema_version")
+        );
+
+        let summary = store.load_board_summary("default").unwrap();
+        assert!(summary.partial);
+        assert_eq!(summary.schema_version, SCHEMA_VERSION);
+        let board = store.load_board("default").unwrap();
+        assert_eq!(board.cards[0].title, "Old card");
+        store.save_board(&board).unwrap();
+        assert!(
+            read_file(&store.board_path("default"))
+                .unwrap()
+                .contains("schema_version")
+        );
+
+        // The cards layout keeps the version in board.json only
+        store.set_layout(StorageLayout::Cards).unwrap();
+        strip(&store.board_dir("default").join("board.json"));
+        let board = store.load_board("default").unwrap();
+        assert_eq!(board.schema_version, SCHEMA_VERSION);
+        assert_eq!(board.cards[0].title, "Old card");
+    }
+
+    #[test]
+    fn boards_from_a_newer_kuk_are_refused() {
+        let (_dir, store) = temp_store();
+        store.init().unwrap();
+        let path = store.board_path("default");
+        let mut value: Value = serde_json::from_str(&read_file(&path).unwrap()).unwrap();
+        value["schema_version"] = (SCHEMA_VERSION + 1).into();
+        fs::write(&path, value.to_string()).unwrap();
+
+        for err in [
+            store.load_board("default").unwrap_err(),
+            store.load_board_summary("default").unwrap_err(),
+        ] {
+            assert!(err.to_string().contains("Upgrade kuk"), "{err}");
+        }
+    }
+
+    #[test]
+    fn load_card_templates() {
+        let (_dir, store) = temp_store();
+        store.init().unwrap();
+        assert!(store.list_card_templates().unwrap().is_empty());
+
+        let dir = store.kuk_dir().join("card-templates");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("bug-report.yaml"),
+            "title: \"[{component}] {title}\"\nlabels: [bug]\nchecklist:\n  - Reproduce\n",
+        )
+        .unwrap();
+        fs::write(dir.join("spike.json"), r#"{"labels": ["research"]}"#).unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        assert_eq!(
+            store.list_card_templates().unwrap(),
+            vec!["bug-report", "spike"]
+        );
+        let bug = store.load_card_template("bug-report").unwrap();
+        assert_eq!(bug.title.as_deref(), Some("[{component}] {title}"));
+        assert_eq!(bug.checklist, vec!["Reproduce"]);
+        assert_eq!(
+            store.load_card_template("spike").unwrap().labels,
+            vec!["research"]
+        );
+        assert!(matches!(
+            store.load_card_template("nope"),
+            Err(KukError::TemplateNotFound(_))
+        ));
+    }
+
     #[test]
     fn save_board_before_init_fails() {
         let (_dir, store) = temp_store();
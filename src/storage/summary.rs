@@ -0,0 +1,127 @@
+//! Board parsing that skips card bodies, for read paths like `kuk list` and
+//! reports that never look at descriptions or most metadata.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::model::{Board, Card, Column, LabelDef, Priority, Snooze, Swimlanes, TRANSITIONS_KEY};
+
+// Fields not named here (description, comments, metadata other than the
+// column moves, anything newer) are skipped by serde without being
+// allocated.
+
+#[derive(Deserialize)]
+struct BoardSummary {
+    #[serde(default)]
+    schema_version: u32,
+    name: String,
+    columns: Vec<Column>,
+    #[serde(default)]
+    cards: Vec<CardSummary>,
+    #[serde(default)]
+    labels: Vec<LabelDef>,
+    #[serde(default)]
+    strict_labels: bool,
+    #[serde(default)]
+    last_seq: u64,
+    #[serde(default)]
+    swimlanes: Option<Swimlanes>,
+}
+
+#[derive(Deserialize)]
+struct CardSummary {
+    id: String,
+    #[serde(default)]
+    seq: Option<u64>,
+    title: String,
+    column: String,
+    order: u32,
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    due: Option<DateTime<Utc>>,
+    #[serde(default)]
+    priority: Option<Priority>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    snooze: Option<Snooze>,
+    #[serde(default)]
+    epic: bool,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    lane: Option<String>,
+    #[serde(default)]
+    metadata: MetadataSummary,
+}
+
+/// The one metadata key summaries keep: the moves between columns that
+/// reports time cards by.
+#[derive(Default, Deserialize)]
+struct MetadataSummary {
+    #[serde(default)]
+    transitions: Option<serde_json::Value>,
+}
+
+/// Parse a board file into a [`Board`] marked `partial`, with every card's
+/// `description` and `comments` left empty and only the
+/// [`TRANSITIONS_KEY`] of its `metadata` kept. A cards-layout
+/// `board.json` has no cards; they're parsed with [`parse_card_summary`].
+pub(super) fn parse_board_summary(data: &str) -> serde_json::Result<Board> {
+    let summary: BoardSummary = serde_json::from_str(data)?;
+    Ok(Board {
+        schema_version: summary.schema_version,
+        name: summary.name,
+        columns: summary.columns,
+        cards: summary.cards.into_iter().map(Card::from).collect(),
+        labels: summary.labels,
+        strict_labels: summary.strict_labels,
+        last_seq: summary.last_seq,
+        swimlanes: summary.swimlanes,
+        partial: true,
+    })
+}
+
+/// Parse one card file of the cards layout, skipping the same fields as
+/// [`parse_board_summary`].
+pub(super) fn parse_card_summary(data: &str) -> serde_json::Result<Card> {
+    serde_json::from_str::<CardSummary>(data).map(Card::from)
+}
+
+impl From<CardSummary> for Card {
+    fn from(c: CardSummary) -> Self {
+        Card {
+            id: c.id,
+            seq: c.seq,
+            title: c.title,
+            column: c.column,
+            order: c.order,
+            description: None,
+            assignee: c.assignee,
+            labels: c.labels,
+            due: c.due,
+            priority: c.priority,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+            metadata: c
+                .metadata
+                .transitions
+                .map(|moves| HashMap::from([(TRANSITIONS_KEY.to_string(), moves)]))
+                .unwrap_or_default(),
+            archived: c.archived,
+            snooze: c.snooze,
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            epic: c.epic,
+            parent: c.parent,
+            lane: c.lane,
+        }
+    }
+}
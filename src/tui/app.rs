@@ -7,13 +7,17 @@ use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 
 use crate::error::{KukError, Result};
-use crate::model::{Board, Card};
-use crate::storage::Store;
+use crate::model::{Board, Card, CardSort, Origin, Priority, SortKey, Swimlanes, Via, cmp_lanes};
+use crate::storage::{CardQuery, Store};
 
+use super::autosave::{AUTOSAVE_DELAY, Autosave};
+use super::theme::Theme;
 use super::ui;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -24,6 +28,28 @@ pub enum Mode {
     Help,
     Confirm,
     BoardPicker,
+    Finder,
+}
+
+/// A card offered by the fuzzy finder.
+#[derive(Debug, Clone)]
+pub struct FinderHit {
+    pub board: String,
+    pub card_id: String,
+    pub title: String,
+    pub column: String,
+}
+
+/// How `P` orders and narrows the columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PriorityView {
+    /// Board position.
+    #[default]
+    Position,
+    /// Most urgent first, then board position.
+    Sorted,
+    /// Only high and urgent cards, most urgent first.
+    Pressing,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -40,22 +66,42 @@ pub struct App {
     pub input_buf: String,
     pub search_buf: String,
     pub search_active: bool,
+    pub priority_view: PriorityView,
+    /// Card order within columns, cycled with `o` and reversed with `O`.
+    pub sort: CardSort,
+    /// Swimlanes the board is drawn in, starting from the board's own.
+    pub lanes: Option<Swimlanes>,
+    /// Columns show archived cards instead of active ones, toggled with `A`.
+    pub show_archived: bool,
     pub message: Option<String>,
     pub should_quit: bool,
     pub pending_confirm: Option<ConfirmAction>,
     pub pending_g: bool,
     pub board_list: Vec<String>,
     pub board_selected: usize,
+    pub finder_buf: String,
+    pub finder_all_boards: bool,
+    pub finder_candidates: Vec<FinderHit>,
+    pub finder_results: Vec<FinderHit>,
+    pub finder_selected: usize,
+    pub theme: Theme,
+    /// The last add or move (card, column) stopped by a WIP limit. Trying
+    /// the same thing again goes over the limit.
+    wip_warned: Option<(Option<String>, String)>,
+    autosave: Autosave,
 }
 
 impl App {
     pub fn new(repo_root: &Path) -> Result<Self> {
-        let store = Store::new(repo_root);
+        let store = Store::new(repo_root).with_origin(Origin::new(Via::Tui));
         if !store.is_initialized() {
             return Err(KukError::NotInitialized);
         }
         let config = store.load_config()?;
         let board = store.load_board(&config.default_board)?;
+        let lanes = board.swimlanes;
+        let autosave = Autosave::new(store.clone(), AUTOSAVE_DELAY);
+        autosave.loaded(&board);
 
         Ok(Self {
             store,
@@ -66,43 +112,90 @@ impl App {
             input_buf: String::new(),
             search_buf: String::new(),
             search_active: false,
+            priority_view: PriorityView::Position,
+            sort: CardSort::default(),
+            lanes,
+            show_archived: false,
             message: None,
             should_quit: false,
             pending_confirm: None,
             pending_g: false,
             board_list: Vec::new(),
             board_selected: 0,
+            finder_buf: String::new(),
+            finder_all_boards: false,
+            finder_candidates: Vec::new(),
+            finder_results: Vec::new(),
+            finder_selected: 0,
+            theme: Theme::new(config.color_scheme),
+            wip_warned: None,
+            autosave,
         })
     }
 
+    /// Reload the board from disk, writing any unsaved changes first.
     pub fn reload_board(&mut self) -> Result<()> {
+        self.flush()?;
         let config = self.store.load_config()?;
         self.board = self.store.load_board(&config.default_board)?;
+        self.autosave.loaded(&self.board);
         Ok(())
     }
 
-    pub fn save_board(&self) -> Result<()> {
-        self.store.save_board(&self.board)
+    /// Schedule a background save of the board.
+    pub fn mark_dirty(&mut self) {
+        self.autosave.mark_dirty();
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.autosave.is_dirty()
+    }
+
+    /// Hand pending changes to the background writer once the autosave delay
+    /// has passed, and surface any save that failed.
+    pub fn tick(&mut self) {
+        if let Some(e) = self.autosave.tick(&self.board) {
+            self.message = Some(format!("Save failed: {e} (will retry)"));
+        }
+    }
+
+    /// Write pending changes now, waiting for the result.
+    pub fn flush(&mut self) -> Result<()> {
+        self.autosave.flush(&self.board)
     }
 
-    /// Get active (non-archived) cards for a column, sorted by order.
+    /// Get active (non-archived) cards for a column, or its archived ones
+    /// in the archived view, sorted as the priority view or else the chosen
+    /// sort asks, and grouped by swimlane when lanes are on.
     pub fn column_cards(&self, col_idx: usize) -> Vec<&Card> {
         if col_idx >= self.board.columns.len() {
             return Vec::new();
         }
         let col_name = &self.board.columns[col_idx].name;
-        let mut cards: Vec<&Card> = self
-            .board
-            .cards
-            .iter()
-            .filter(|c| &c.column == col_name && !c.archived)
-            .collect();
-        cards.sort_by_key(|c| c.order);
+        let mut cards = if self.show_archived {
+            let mut archived = self.board.archived_cards();
+            archived.retain(|c| c.column == *col_name);
+            archived
+        } else {
+            self.board.column_cards(col_name)
+        };
 
         if self.search_active && !self.search_buf.is_empty() {
             let query = self.search_buf.to_lowercase();
             cards.retain(|c| c.title.to_lowercase().contains(&query));
         }
+        if self.priority_view == PriorityView::Pressing {
+            cards.retain(|c| c.priority >= Some(Priority::High));
+        }
+        if self.priority_view != PriorityView::Position {
+            let by_priority = CardSort::new(SortKey::Priority);
+            cards.sort_by(|a, b| by_priority.cmp(a, b));
+        } else if self.sort != CardSort::default() {
+            cards.sort_by(|a, b| self.sort.cmp(a, b));
+        }
+        if let Some(by) = self.lanes {
+            cards.sort_by(|a, b| cmp_lanes(by.lane_of(a), by.lane_of(b)));
+        }
         cards
     }
 
@@ -138,10 +231,30 @@ impl App {
             Mode::Help => self.handle_help(key),
             Mode::Confirm => self.handle_confirm(key),
             Mode::BoardPicker => self.handle_board_picker(key),
+            Mode::Finder => self.handle_finder(key),
         }
     }
 
     fn handle_normal(&mut self, key: KeyEvent) {
+        // Ctrl+P = fuzzy card finder
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('p') {
+            self.pending_g = false;
+            self.open_finder();
+            return;
+        }
+
+        // Archived cards can only be looked at, restored or deleted
+        if self.show_archived
+            && matches!(
+                key.code,
+                KeyCode::Char('a' | 'L' | '>' | 'H' | '<' | 'K' | 'J' | 'p')
+            )
+        {
+            self.pending_g = false;
+            self.message = Some("Archived cards can't be changed. x restores, A goes back.".into());
+            return;
+        }
+
         match key.code {
             // Quit
             KeyCode::Char('q') => self.should_quit = true,
@@ -239,10 +352,91 @@ impl App {
                 self.demote_card();
             }
 
-            // Archive
+            // Archive, or restore in the archived view
             KeyCode::Char('x') => {
                 self.pending_g = false;
-                self.archive_card();
+                if self.show_archived {
+                    self.unarchive_card();
+                } else {
+                    self.archive_card();
+                }
+            }
+
+            // Toggle the archived view
+            KeyCode::Char('A') => {
+                self.pending_g = false;
+                self.show_archived = !self.show_archived;
+                self.message = Some(
+                    if self.show_archived {
+                        "Showing archived cards. x restores, A goes back."
+                    } else {
+                        "Showing active cards."
+                    }
+                    .into(),
+                );
+                self.selected_row = 0;
+            }
+
+            // Cycle the card's priority
+            KeyCode::Char('p') => {
+                self.pending_g = false;
+                self.cycle_priority();
+            }
+
+            // Cycle priority sort and filter
+            KeyCode::Char('P') => {
+                self.pending_g = false;
+                self.priority_view = match self.priority_view {
+                    PriorityView::Position => PriorityView::Sorted,
+                    PriorityView::Sorted => PriorityView::Pressing,
+                    PriorityView::Pressing => PriorityView::Position,
+                };
+                self.message = Some(
+                    match self.priority_view {
+                        PriorityView::Position => "Board order.",
+                        PriorityView::Sorted => "Sorted by priority.",
+                        PriorityView::Pressing => "Showing high and urgent cards.",
+                    }
+                    .into(),
+                );
+                self.clamp_row();
+            }
+
+            // Cycle the sort key, or reverse it
+            KeyCode::Char('o' | 'O') => {
+                self.pending_g = false;
+                let id = self.current_card_id();
+                self.sort = if key.code == KeyCode::Char('O') {
+                    CardSort {
+                        descending: !self.sort.descending,
+                        ..self.sort
+                    }
+                } else {
+                    let keys = SortKey::ALL;
+                    let next = keys.iter().position(|k| *k == self.sort.key).unwrap_or(0) + 1;
+                    CardSort::new(keys[next % keys.len()])
+                };
+                self.message = Some(if self.sort == CardSort::default() {
+                    "Board order.".into()
+                } else {
+                    format!("Sorted by {}.", self.sort)
+                });
+                self.reselect(id);
+            }
+
+            KeyCode::Char('s') => {
+                self.pending_g = false;
+                self.lanes = match self.lanes {
+                    None => Some(Swimlanes::Assignee),
+                    Some(Swimlanes::Assignee) => Some(Swimlanes::Label),
+                    Some(Swimlanes::Label) => Some(Swimlanes::Lane),
+                    Some(Swimlanes::Lane) => None,
+                };
+                self.message = Some(match self.lanes {
+                    Some(by) => format!("Swimlanes by {by}."),
+                    None => "No swimlanes.".into(),
+                });
+                self.clamp_row();
             }
 
             // Search
@@ -302,15 +496,18 @@ impl App {
             KeyCode::Enter => {
                 if !self.input_buf.is_empty() {
                     let col_name = self.board.columns[self.selected_col].name.clone();
+                    if !self.wip_allows(&col_name, None) {
+                        return;
+                    }
                     let mut card = Card::new(&self.input_buf, &col_name);
                     card.order = self.board.next_order(&col_name);
-                    self.board.cards.push(card);
-                    if let Err(e) = self.save_board() {
-                        self.message = Some(format!("Save failed: {e}"));
-                    } else {
-                        self.message = Some(format!("Added: {}", self.input_buf));
-                        self.selected_row = self.column_cards(self.selected_col).len() - 1;
+                    if let Ok(config) = self.store.load_config() {
+                        config.new_cards.apply(&mut card);
                     }
+                    self.board.add_card(card);
+                    self.mark_dirty();
+                    self.message = Some(format!("Added: {}", self.input_buf));
+                    self.selected_row = self.column_cards(self.selected_col).len() - 1;
                 }
                 self.input_buf.clear();
                 self.mode = Mode::Normal;
@@ -404,15 +601,13 @@ impl App {
                 self.mode = Mode::Normal;
                 self.message = None;
             }
-            KeyCode::Char('j') | KeyCode::Down => {
-                if !self.board_list.is_empty() && self.board_selected < self.board_list.len() - 1 {
-                    self.board_selected += 1;
-                }
+            KeyCode::Char('j') | KeyCode::Down
+                if self.board_selected + 1 < self.board_list.len() =>
+            {
+                self.board_selected += 1;
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                if self.board_selected > 0 {
-                    self.board_selected -= 1;
-                }
+            KeyCode::Char('k') | KeyCode::Up if self.board_selected > 0 => {
+                self.board_selected -= 1;
             }
             KeyCode::Enter => {
                 if let Some(name) = self.board_list.get(self.board_selected).cloned() {
@@ -422,32 +617,8 @@ impl App {
                         self.message = None;
                         return;
                     }
-                    // Switch: save config, reload board
-                    match self.store.load_config() {
-                        Ok(mut config) => {
-                            config.default_board = name.clone();
-                            if let Err(e) = self.store.save_config(&config) {
-                                self.message = Some(format!("Save config failed: {e}"));
-                                self.mode = Mode::Normal;
-                                return;
-                            }
-                            match self.store.load_board(&name) {
-                                Ok(board) => {
-                                    self.board = board;
-                                    self.selected_col = 0;
-                                    self.selected_row = 0;
-                                    self.search_active = false;
-                                    self.search_buf.clear();
-                                    self.message = Some(format!("Switched to board: {name}"));
-                                }
-                                Err(e) => {
-                                    self.message = Some(format!("Load board failed: {e}"));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            self.message = Some(format!("Load config failed: {e}"));
-                        }
+                    if self.switch_board(&name) {
+                        self.message = Some(format!("Switched to board: {name}"));
                     }
                 }
                 self.mode = Mode::Normal;
@@ -460,6 +631,174 @@ impl App {
         }
     }
 
+    /// Make `name` the active board, persisting it as the default. Resets
+    /// selection and search. Returns false (with a message set) on failure.
+    fn switch_board(&mut self, name: &str) -> bool {
+        if let Err(e) = self.flush() {
+            self.message = Some(e.to_string());
+            return false;
+        }
+        let mut config = match self.store.load_config() {
+            Ok(c) => c,
+            Err(e) => {
+                self.message = Some(format!("Load config failed: {e}"));
+                return false;
+            }
+        };
+        config.default_board = name.to_string();
+        if let Err(e) = self.store.save_config(&config) {
+            self.message = Some(format!("Save config failed: {e}"));
+            return false;
+        }
+        match self.store.load_board(name) {
+            Ok(board) => {
+                self.autosave.loaded(&board);
+                self.lanes = board.swimlanes;
+                self.board = board;
+                self.selected_col = 0;
+                self.selected_row = 0;
+                self.search_active = false;
+                self.search_buf.clear();
+                true
+            }
+            Err(e) => {
+                self.message = Some(format!("Load board failed: {e}"));
+                false
+            }
+        }
+    }
+
+    fn open_finder(&mut self) {
+        self.finder_buf.clear();
+        self.finder_selected = 0;
+        self.load_finder_candidates();
+        self.mode = Mode::Finder;
+        self.message = Some("Find card (Tab: all boards, Enter to jump, Esc to cancel):".into());
+    }
+
+    /// Snapshot the cards the finder searches: the current board, or every
+    /// board when `finder_all_boards` is set.
+    fn load_finder_candidates(&mut self) {
+        let mut board = self.board.clone();
+        board.sort_cards();
+        let hit = |board: &str, c: Card| FinderHit {
+            board: board.to_string(),
+            card_id: c.id,
+            title: c.title,
+            column: c.column,
+        };
+        self.finder_candidates = board
+            .cards
+            .into_iter()
+            .filter(|c| !c.archived)
+            .map(|c| hit(&board.name, c))
+            .collect();
+
+        if self.finder_all_boards
+            && let Ok(names) = self.store.list_boards()
+        {
+            let query = CardQuery {
+                boards: names.into_iter().filter(|n| *n != board.name).collect(),
+                ..CardQuery::default()
+            };
+            if !query.boards.is_empty()
+                && let Ok(cards) = self.store.iter_cards(query)
+            {
+                self.finder_candidates
+                    .extend(cards.flatten().map(|(b, c)| hit(&b, c)));
+            }
+        }
+        self.update_finder_results();
+    }
+
+    /// Rank candidates against the query, best match first.
+    fn update_finder_results(&mut self) {
+        if self.finder_buf.is_empty() {
+            self.finder_results = self.finder_candidates.clone();
+        } else {
+            let matcher = SkimMatcherV2::default();
+            let mut scored: Vec<(i64, &FinderHit)> = self
+                .finder_candidates
+                .iter()
+                .filter_map(|hit| {
+                    matcher
+                        .fuzzy_match(&hit.title, &self.finder_buf)
+                        .map(|score| (score, hit))
+                })
+                .collect();
+            // Stable sort keeps board/column order among equal scores
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            self.finder_results = scored.into_iter().map(|(_, hit)| hit.clone()).collect();
+        }
+        if self.finder_selected >= self.finder_results.len() {
+            self.finder_selected = self.finder_results.len().saturating_sub(1);
+        }
+    }
+
+    fn handle_finder(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.message = None;
+            }
+            KeyCode::Down => self.finder_down(),
+            KeyCode::Char('n') if ctrl => self.finder_down(),
+            KeyCode::Up => self.finder_selected = self.finder_selected.saturating_sub(1),
+            KeyCode::Char('p') if ctrl => {
+                self.finder_selected = self.finder_selected.saturating_sub(1)
+            }
+            KeyCode::Tab => {
+                self.finder_all_boards = !self.finder_all_boards;
+                self.load_finder_candidates();
+            }
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                self.message = None;
+                if let Some(hit) = self.finder_results.get(self.finder_selected).cloned() {
+                    self.jump_to(&hit);
+                }
+            }
+            KeyCode::Backspace => {
+                self.finder_buf.pop();
+                self.update_finder_results();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.finder_buf.push(c);
+                self.finder_selected = 0;
+                self.update_finder_results();
+            }
+            _ => {}
+        }
+    }
+
+    fn finder_down(&mut self) {
+        if self.finder_selected + 1 < self.finder_results.len() {
+            self.finder_selected += 1;
+        }
+    }
+
+    /// Move the selection onto a finder hit, switching boards if needed.
+    fn jump_to(&mut self, hit: &FinderHit) {
+        if hit.board != self.board.name && !self.switch_board(&hit.board) {
+            return;
+        }
+        // The search filter could hide the target card
+        self.search_active = false;
+        self.search_buf.clear();
+
+        let Some(col) = self.board.columns.iter().position(|c| c.name == hit.column) else {
+            return;
+        };
+        self.selected_col = col;
+        self.selected_row = self
+            .column_cards(col)
+            .iter()
+            .position(|c| c.id == hit.card_id)
+            .unwrap_or(0);
+        self.message = Some(format!("Jumped to: {}", hit.title));
+    }
+
     fn move_card_right(&mut self) {
         let next_col = self.selected_col + 1;
         if next_col >= self.board.columns.len() {
@@ -467,12 +806,11 @@ impl App {
         }
         if let Some(id) = self.current_card_id() {
             let to = self.board.columns[next_col].name.clone();
-            let order = self.board.next_order(&to);
-            if let Some(card) = self.board.find_card_mut(&id) {
-                card.column = to;
-                card.order = order;
-                card.updated_at = chrono::Utc::now();
-                let _ = self.save_board();
+            if !self.wip_allows(&to, Some(&id)) {
+                return;
+            }
+            if self.board.move_card(&id, &to) {
+                self.mark_dirty();
                 self.message = Some(format!("Moved → {}", self.board.columns[next_col].name));
                 self.clamp_row();
             }
@@ -486,31 +824,44 @@ impl App {
         let prev_col = self.selected_col - 1;
         if let Some(id) = self.current_card_id() {
             let to = self.board.columns[prev_col].name.clone();
-            let order = self.board.next_order(&to);
-            if let Some(card) = self.board.find_card_mut(&id) {
-                card.column = to;
-                card.order = order;
-                card.updated_at = chrono::Utc::now();
-                let _ = self.save_board();
+            if !self.wip_allows(&to, Some(&id)) {
+                return;
+            }
+            if self.board.move_card(&id, &to) {
+                self.mark_dirty();
                 self.message = Some(format!("Moved → {}", self.board.columns[prev_col].name));
                 self.clamp_row();
             }
         }
     }
 
+    /// Whether a card may go into `column`: always when the column has
+    /// room, and over its WIP limit only when the user repeats an attempt
+    /// this just refused.
+    fn wip_allows(&mut self, column: &str, card_id: Option<&str>) -> bool {
+        let Some(limit) = self.board.wip_exceeded(column, card_id) else {
+            return true;
+        };
+        let attempt = (card_id.map(String::from), column.to_string());
+        if self.wip_warned.take() == Some(attempt.clone()) {
+            return true;
+        }
+        self.wip_warned = Some(attempt);
+        let again = match card_id {
+            Some(_) if self.show_archived => "Press x again",
+            Some(_) => "Move it again",
+            None => "Press Enter again",
+        };
+        self.message = Some(format!(
+            "WIP limit reached: {column} is at its limit of {limit} cards. {again} to go over it."
+        ));
+        false
+    }
+
     fn hoist_card(&mut self) {
         if let Some(id) = self.current_card_id() {
-            let column = self.board.find_card(&id).unwrap().column.clone();
-            for c in self.board.cards.iter_mut() {
-                if c.column == column && !c.archived && c.id != id {
-                    c.order += 1;
-                }
-            }
-            if let Some(card) = self.board.find_card_mut(&id) {
-                card.order = 0;
-                card.updated_at = chrono::Utc::now();
-            }
-            let _ = self.save_board();
+            self.board.hoist_card(&id);
+            self.mark_dirty();
             self.selected_row = 0;
             self.message = Some("Hoisted to top.".into());
         }
@@ -518,13 +869,8 @@ impl App {
 
     fn demote_card(&mut self) {
         if let Some(id) = self.current_card_id() {
-            let column = self.board.find_card(&id).unwrap().column.clone();
-            let max_order = self.board.next_order(&column);
-            if let Some(card) = self.board.find_card_mut(&id) {
-                card.order = max_order;
-                card.updated_at = chrono::Utc::now();
-            }
-            let _ = self.save_board();
+            self.board.demote_card(&id);
+            self.mark_dirty();
             let count = self.column_cards(self.selected_col).len();
             if count > 0 {
                 self.selected_row = count - 1;
@@ -533,14 +879,66 @@ impl App {
         }
     }
 
+    /// Step the selected card through no priority, low, medium, high and
+    /// urgent, keeping it selected if the view re-sorts it.
+    fn cycle_priority(&mut self) {
+        let Some(id) = self.current_card_id() else {
+            return;
+        };
+        if let Some(card) = self.board.find_card_mut(&id) {
+            card.priority = match card.priority {
+                None => Some(Priority::Low),
+                Some(Priority::Low) => Some(Priority::Medium),
+                Some(Priority::Medium) => Some(Priority::High),
+                Some(Priority::High) => Some(Priority::Urgent),
+                Some(Priority::Urgent) => None,
+            };
+            card.updated_at = chrono::Utc::now();
+            self.message = Some(match card.priority {
+                Some(p) => format!("Priority: {p}"),
+                None => "Priority cleared.".into(),
+            });
+        }
+        self.mark_dirty();
+        self.reselect(Some(id));
+    }
+
+    /// Keep card `id` selected after its column re-sorted.
+    fn reselect(&mut self, id: Option<String>) {
+        match self
+            .column_cards(self.selected_col)
+            .iter()
+            .position(|c| Some(&c.id) == id.as_ref())
+        {
+            Some(row) => self.selected_row = row,
+            None => self.clamp_row(),
+        }
+    }
+
     fn archive_card(&mut self) {
         if let Some(id) = self.current_card_id() {
             if let Some(card) = self.board.find_card_mut(&id) {
-                card.archived = true;
-                card.updated_at = chrono::Utc::now();
+                card.archive();
                 self.message = Some(format!("Archived: {}", card.title));
             }
-            let _ = self.save_board();
+            self.mark_dirty();
+            self.clamp_row();
+        }
+    }
+
+    /// Restore the selected archived card to its column.
+    fn unarchive_card(&mut self) {
+        let Some(card) = self.current_card() else {
+            return;
+        };
+        let (id, column) = (card.id.clone(), card.column.clone());
+        if !self.wip_allows(&column, Some(&id)) {
+            return;
+        }
+        if self.board.unarchive_card(&id).is_some() {
+            let title = self.board.find_card(&id).map(|c| c.title.clone());
+            self.message = Some(format!("Restored: {}", title.unwrap_or_default()));
+            self.mark_dirty();
             self.clamp_row();
         }
     }
@@ -553,7 +951,7 @@ impl App {
                 .map(|c| c.title.clone())
                 .unwrap_or_default();
             self.board.cards.retain(|c| c.id != id);
-            let _ = self.save_board();
+            self.mark_dirty();
             self.clamp_row();
             self.message = Some(format!("Deleted: {title}"));
         }
@@ -573,13 +971,14 @@ pub fn run_tui(repo_root: &Path) -> Result<()> {
         Terminal::new(backend).map_err(|e| KukError::Other(format!("Terminal error: {e}")))?;
 
     let result = run_loop(&mut terminal, &mut app);
+    let flushed = app.flush();
 
     // Restore terminal
     disable_raw_mode().ok();
     execute!(terminal.backend_mut(), LeaveAlternateScreen).ok();
     terminal.show_cursor().ok();
 
-    result
+    result.and(flushed)
 }
 
 fn run_loop(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
@@ -595,6 +994,7 @@ fn run_loop(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App
         {
             app.handle_key(key);
         }
+        app.tick();
 
         if app.should_quit {
             return Ok(());
@@ -832,6 +1232,75 @@ mod tests {
         assert_eq!(cards[0].title, "Task A");
     }
 
+    #[test]
+    fn priority_cycles_and_sorts() {
+        let (_dir, mut app) = test_app();
+        // Task B: none → low → medium → high, and stays selected once sorted
+        app.handle_key(make_key(KeyCode::Char('j')));
+        app.handle_key(make_key(KeyCode::Char('P')));
+        for _ in 0..3 {
+            app.handle_key(make_key(KeyCode::Char('p')));
+        }
+        assert_eq!(app.current_card().unwrap().title, "Task B");
+        assert_eq!(app.current_card().unwrap().priority, Some(Priority::High));
+        assert_eq!(app.selected_row, 0);
+        assert!(app.has_unsaved_changes());
+
+        // High and urgent only
+        app.handle_key(make_key(KeyCode::Char('P')));
+        let titles: Vec<&str> = app
+            .column_cards(0)
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Task B"]);
+
+        app.handle_key(make_key(KeyCode::Char('P')));
+        assert_eq!(app.priority_view, PriorityView::Position);
+        assert_eq!(app.column_cards(0)[0].title, "Task A");
+    }
+
+    #[test]
+    fn sort_cycles_and_reverses() {
+        let (_dir, mut app) = test_app();
+        app.board.cards[1].priority = Some(Priority::Low);
+
+        app.handle_key(make_key(KeyCode::Char('o')));
+        assert_eq!(app.sort, CardSort::new(SortKey::Priority));
+        assert_eq!(app.column_cards(0)[0].title, "Task B");
+        assert_eq!(app.current_card().unwrap().title, "Task A");
+
+        app.handle_key(make_key(KeyCode::Char('O')));
+        assert_eq!(app.message.as_deref(), Some("Sorted by priority:asc."));
+        for _ in 0..5 {
+            app.handle_key(make_key(KeyCode::Char('o')));
+        }
+        assert_eq!(app.sort, CardSort::default());
+        assert_eq!(app.message.as_deref(), Some("Board order."));
+    }
+
+    #[test]
+    fn swimlanes_group_column_cards() {
+        let (_dir, mut app) = test_app();
+        app.board.cards[1].assignee = Some("al".into());
+
+        app.handle_key(make_key(KeyCode::Char('s')));
+        assert_eq!(app.lanes, Some(Swimlanes::Assignee));
+        let titles: Vec<&str> = app
+            .column_cards(0)
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Task B", "Task A"]);
+        assert_eq!(app.current_card().unwrap().title, "Task B");
+
+        for _ in 0..3 {
+            app.handle_key(make_key(KeyCode::Char('s')));
+        }
+        assert_eq!(app.lanes, None);
+        assert_eq!(app.column_cards(0)[0].title, "Task A");
+    }
+
     #[test]
     fn search_esc_clears() {
         let (_dir, mut app) = test_app();
@@ -869,6 +1338,24 @@ mod tests {
         assert_eq!(app.column_cards(1).len(), 0);
     }
 
+    #[test]
+    fn moves_over_wip_limit_need_a_second_try() {
+        let (_dir, mut app) = test_app();
+        app.board.set_wip_limit("doing", Some(1)).unwrap();
+
+        app.handle_key(make_shift_key(KeyCode::Char('L')));
+        assert_eq!(app.column_cards(1).len(), 1);
+        assert!(
+            app.message
+                .as_deref()
+                .unwrap()
+                .contains("WIP limit reached")
+        );
+
+        app.handle_key(make_shift_key(KeyCode::Char('L')));
+        assert_eq!(app.column_cards(1).len(), 2);
+    }
+
     #[test]
     fn hoist_card_shift_k() {
         let (_dir, mut app) = test_app();
@@ -901,6 +1388,67 @@ mod tests {
         assert_eq!(app.column_cards(0).len(), 1);
     }
 
+    #[test]
+    fn added_cards_get_the_configured_defaults() {
+        let (_dir, mut app) = test_app();
+        let mut config = app.store.load_config().unwrap();
+        config.new_cards.labels = vec!["triage".into()];
+        config.new_cards.assignee = Some("sam".into());
+        app.store.save_config(&config).unwrap();
+
+        app.handle_key(make_key(KeyCode::Char('a')));
+        for c in "New".chars() {
+            app.handle_key(make_key(KeyCode::Char(c)));
+        }
+        app.handle_key(make_key(KeyCode::Enter));
+        let card = app.current_card().unwrap();
+        assert_eq!(card.title, "New");
+        assert_eq!(card.labels, ["triage"]);
+        assert_eq!(card.assignee.as_deref(), Some("sam"));
+    }
+
+    #[test]
+    fn archived_view_shows_and_restores_archived_cards() {
+        let (_dir, mut app) = test_app();
+        app.handle_key(make_key(KeyCode::Char('x')));
+        app.handle_key(make_shift_key(KeyCode::Char('A')));
+        assert!(app.show_archived);
+        assert_eq!(app.current_card().unwrap().title, "Task A");
+        assert_eq!(app.column_cards(1).len(), 0);
+
+        app.handle_key(make_shift_key(KeyCode::Char('L')));
+        assert!(app.current_card().unwrap().archived);
+        assert_eq!(app.current_card().unwrap().column, "todo");
+
+        app.handle_key(make_key(KeyCode::Char('x')));
+        assert_eq!(app.message.as_deref(), Some("Restored: Task A"));
+        assert!(app.column_cards(0).is_empty());
+        app.handle_key(make_shift_key(KeyCode::Char('A')));
+        let titles: Vec<&str> = app
+            .column_cards(0)
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Task B", "Task A"]);
+    }
+
+    #[test]
+    fn edits_are_saved_on_flush_not_per_key() {
+        let (dir, mut app) = test_app();
+        app.handle_key(make_shift_key(KeyCode::Char('L')));
+        app.handle_key(make_key(KeyCode::Char('x')));
+        assert!(app.has_unsaved_changes());
+
+        let on_disk = || Store::new(dir.path()).load_board("default").unwrap();
+        assert_eq!(on_disk().column_cards("doing").len(), 1);
+
+        app.flush().unwrap();
+        assert!(!app.has_unsaved_changes());
+        let board = on_disk();
+        assert_eq!(board.column_cards("todo").len(), 0);
+        assert_eq!(board.column_cards("doing").len(), 2);
+    }
+
     #[test]
     fn delete_card_confirm_y() {
         let (_dir, mut app) = test_app();
@@ -988,29 +1536,14 @@ mod tests {
             .create_board(
                 "sprint-1",
                 vec![
-                    crate::model::Column {
-                        name: "todo".into(),
-                        wip_limit: None,
-                    },
-                    crate::model::Column {
-                        name: "doing".into(),
-                        wip_limit: None,
-                    },
-                    crate::model::Column {
-                        name: "done".into(),
-                        wip_limit: None,
-                    },
+                    crate::model::Column::new("todo"),
+                    crate::model::Column::new("doing"),
+                    crate::model::Column::new("done"),
                 ],
             )
             .unwrap();
         store
-            .create_board(
-                "backlog",
-                vec![crate::model::Column {
-                    name: "ideas".into(),
-                    wip_limit: None,
-                }],
-            )
+            .create_board("backlog", vec![crate::model::Column::new("ideas")])
             .unwrap();
 
         let app = App::new(dir.path()).unwrap();
@@ -1093,6 +1626,25 @@ mod tests {
         assert_eq!(config.default_board, "sprint-1");
     }
 
+    #[test]
+    fn switching_boards_saves_pending_changes() {
+        let (dir, mut app) = test_app_with_boards();
+        for code in [KeyCode::Char('a'), KeyCode::Char('Z'), KeyCode::Enter] {
+            app.handle_key(make_key(code));
+        }
+        assert!(app.has_unsaved_changes());
+        let before = app.board.name.clone();
+
+        app.handle_key(make_key(KeyCode::Char('b')));
+        app.handle_key(make_key(KeyCode::Char('j')));
+        app.handle_key(make_key(KeyCode::Enter));
+        assert_ne!(app.board.name, before);
+        assert!(!app.has_unsaved_changes());
+
+        let saved = Store::new(dir.path()).load_board(&before).unwrap();
+        assert_eq!(saved.cards.len(), 1);
+    }
+
     #[test]
     fn board_picker_select_same_board_noop() {
         let (_dir, mut app) = test_app_with_boards();
@@ -1139,4 +1691,63 @@ mod tests {
         assert!(!app.search_active);
         assert!(app.search_buf.is_empty());
     }
+
+    #[test]
+    fn finder_jumps_to_matching_card() {
+        let (_dir, mut app) = test_app();
+        app.handle_key(make_key_mod(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert_eq!(app.mode, Mode::Finder);
+        assert_eq!(app.finder_results.len(), 3);
+
+        for c in "tsk c".chars() {
+            app.handle_key(make_key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.finder_results[0].title, "Task C");
+
+        app.handle_key(make_key(KeyCode::Enter));
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.selected_col, 1);
+        assert_eq!(app.current_card().unwrap().title, "Task C");
+    }
+
+    #[test]
+    fn finder_navigates_results_and_cancels() {
+        let (_dir, mut app) = test_app();
+        app.handle_key(make_key_mod(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        app.handle_key(make_key(KeyCode::Down));
+        app.handle_key(make_key_mod(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(app.finder_selected, 2);
+        app.handle_key(make_key(KeyCode::Down));
+        assert_eq!(app.finder_selected, 2);
+        app.handle_key(make_key_mod(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert_eq!(app.finder_selected, 1);
+
+        app.handle_key(make_key(KeyCode::Esc));
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.selected_col, 0);
+        assert_eq!(app.selected_row, 0);
+    }
+
+    #[test]
+    fn finder_jumps_across_boards() {
+        let (dir, mut app) = test_app_with_boards();
+        let store = Store::new(dir.path());
+        let mut backlog = store.load_board("backlog").unwrap();
+        backlog.cards.push(Card::new("Dark mode idea", "ideas"));
+        store.save_board(&backlog).unwrap();
+
+        app.handle_key(make_key_mod(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        for c in "dark".chars() {
+            app.handle_key(make_key(KeyCode::Char(c)));
+        }
+        assert!(app.finder_results.is_empty());
+
+        app.handle_key(make_key(KeyCode::Tab));
+        assert!(app.finder_all_boards);
+        assert_eq!(app.finder_results[0].board, "backlog");
+
+        app.handle_key(make_key(KeyCode::Enter));
+        assert_eq!(app.board.name, "backlog");
+        assert_eq!(app.current_card().unwrap().title, "Dark mode idea");
+    }
 }
@@ -0,0 +1,247 @@
+//! Rate-limited background saves for the TUI, so key handling never waits on
+//! serializing and writing a large board.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::error::{KukError, Result};
+use crate::model::Board;
+use crate::storage::Store;
+
+/// How long a change may sit unsaved before it is handed to the writer.
+pub const AUTOSAVE_DELAY: Duration = Duration::from_millis(500);
+
+enum Job {
+    /// The board as just loaded from disk, which later saves merge from.
+    Loaded(Board),
+    Save(Board),
+    /// Reply once every earlier job has been written.
+    Flush(Sender<std::result::Result<(), String>>),
+}
+
+pub struct Autosave {
+    delay: Duration,
+    dirty_since: Option<Instant>,
+    jobs: Option<Sender<Job>>,
+    errors: Receiver<String>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Autosave {
+    pub fn new(store: Store, delay: Duration) -> Self {
+        let (jobs, job_rx) = mpsc::channel();
+        let (error_tx, errors) = mpsc::channel();
+        let worker = thread::spawn(move || write_loop(&store, &job_rx, &error_tx));
+        Self {
+            delay,
+            dirty_since: None,
+            jobs: Some(jobs),
+            errors,
+            worker: Some(worker),
+        }
+    }
+
+    /// Record an unsaved change. The save window starts at the first change,
+    /// so a burst of edits is written at most once per `delay`.
+    pub fn mark_dirty(&mut self) {
+        self.dirty_since.get_or_insert_with(Instant::now);
+    }
+
+    /// Record that `board` was just loaded from disk. Each save merges what
+    /// changed since the last load or save into the board on disk, so changes
+    /// other writers made meanwhile are kept.
+    pub fn loaded(&self, board: &Board) {
+        self.send(Job::Loaded(board.clone()));
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_since.is_some()
+    }
+
+    /// Queue a save if the board has been dirty long enough. Returns the error
+    /// of any background save that failed since the last tick; the board is
+    /// marked dirty again so the next tick retries.
+    pub fn tick(&mut self, board: &Board) -> Option<String> {
+        let failed = self.errors.try_iter().last();
+        if failed.is_some() {
+            self.mark_dirty();
+        }
+        if self
+            .dirty_since
+            .is_some_and(|since| since.elapsed() >= self.delay)
+        {
+            self.dirty_since = None;
+            self.send(Job::Save(board.clone()));
+        }
+        failed
+    }
+
+    /// Write any pending change now and wait for the writer to finish.
+    pub fn flush(&mut self, board: &Board) -> Result<()> {
+        if self.dirty_since.take().is_some() {
+            self.send(Job::Save(board.clone()));
+        }
+        let (ack, done) = mpsc::channel();
+        self.send(Job::Flush(ack));
+        // Errors from earlier, superseded saves no longer matter
+        let result = done
+            .recv()
+            .unwrap_or_else(|_| Err("background writer stopped".into()));
+        self.errors.try_iter().for_each(drop);
+        result.map_err(|e| {
+            self.mark_dirty();
+            KukError::Other(format!("Save failed: {e}"))
+        })
+    }
+
+    fn send(&self, job: Job) {
+        if let Some(jobs) = &self.jobs {
+            // The writer only exits once `jobs` is dropped
+            let _ = jobs.send(job);
+        }
+    }
+}
+
+impl Drop for Autosave {
+    fn drop(&mut self) {
+        self.jobs = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Drain queued jobs in batches, writing only the newest board of each batch.
+/// Flushes are answered with the outcome of the most recent write.
+fn write_loop(store: &Store, jobs: &Receiver<Job>, errors: &Sender<String>) {
+    let mut base = None;
+    let mut last = Ok(());
+    while let Ok(first) = jobs.recv() {
+        let mut latest = None;
+        let mut acks = Vec::new();
+        for job in std::iter::once(first).chain(jobs.try_iter()) {
+            match job {
+                Job::Loaded(board) => {
+                    if let Some(board) = latest.take() {
+                        last = save(store, &mut base, board);
+                    }
+                    base = Some(board);
+                }
+                Job::Save(board) => latest = Some(board),
+                Job::Flush(ack) => acks.push(ack),
+            }
+        }
+
+        if let Some(board) = latest {
+            last = save(store, &mut base, board);
+            if let (Err(e), true) = (&last, acks.is_empty()) {
+                let _ = errors.send(e.clone());
+            }
+        }
+        for ack in acks {
+            let _ = ack.send(last.clone());
+        }
+    }
+}
+
+/// Save `board`, merging what changed since `base` into the board on disk
+/// under the store lock. Where the TUI and another writer changed the same
+/// field, the other writer's value stays. `base` becomes `board` once it
+/// is saved.
+fn save(store: &Store, base: &mut Option<Board>, board: Board) -> std::result::Result<(), String> {
+    let saved = match base.as_ref().filter(|base| base.name == board.name) {
+        Some(base) => store.update_board(&board.name, |disk| {
+            *disk = Board::merge3(Some(base), disk, &board).merged;
+            Ok(())
+        }),
+        None => store.save_board(&board),
+    };
+    saved.map_err(|e| e.to_string())?;
+    *base = Some(board);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Card;
+    use tempfile::TempDir;
+
+    fn store_and_board() -> (TempDir, Store, Board) {
+        let dir = TempDir::new().unwrap();
+        let store = Store::new(dir.path());
+        store.init().unwrap();
+        let board = store.load_board("default").unwrap();
+        (dir, store, board)
+    }
+
+    #[test]
+    fn saves_only_after_delay_or_flush() {
+        let (_dir, store, mut board) = store_and_board();
+        let mut autosave = Autosave::new(store.clone(), Duration::from_secs(3600));
+
+        board.cards.push(Card::new("Pending", "todo"));
+        autosave.mark_dirty();
+        assert_eq!(autosave.tick(&board), None);
+        assert!(autosave.is_dirty());
+        assert!(store.load_board("default").unwrap().cards.is_empty());
+
+        autosave.flush(&board).unwrap();
+        assert!(!autosave.is_dirty());
+        assert_eq!(store.load_board("default").unwrap().cards.len(), 1);
+    }
+
+    #[test]
+    fn tick_hands_off_once_delay_passes() {
+        let (_dir, store, mut board) = store_and_board();
+        let mut autosave = Autosave::new(store.clone(), Duration::ZERO);
+
+        board.cards.push(Card::new("Background", "todo"));
+        autosave.mark_dirty();
+        assert_eq!(autosave.tick(&board), None);
+        assert!(!autosave.is_dirty());
+        // Flushing with nothing dirty waits for the queued write
+        autosave.flush(&board).unwrap();
+        assert_eq!(store.load_board("default").unwrap().cards.len(), 1);
+    }
+
+    #[test]
+    fn saves_keep_changes_other_writers_made_meanwhile() {
+        let (_dir, store, mut board) = store_and_board();
+        let mut autosave = Autosave::new(store.clone(), Duration::from_secs(3600));
+        autosave.loaded(&board);
+
+        let mut other = store.load_board("default").unwrap();
+        other.add_card(Card::new("From the CLI", "todo"));
+        store.save_board(&other).unwrap();
+
+        board.add_card(Card::new("From the TUI", "doing"));
+        autosave.mark_dirty();
+        autosave.flush(&board).unwrap();
+        let saved = store.load_board("default").unwrap();
+        let mut titles: Vec<&str> = saved.cards.iter().map(|c| c.title.as_str()).collect();
+        titles.sort();
+        assert_eq!(titles, ["From the CLI", "From the TUI"]);
+        // Both were numbered 1; the TUI's card gave way
+        let seq = |title| saved.cards.iter().find(|c| c.title == title).unwrap().seq;
+        assert_eq!(seq("From the CLI"), Some(1));
+        assert_eq!(seq("From the TUI"), Some(2));
+    }
+
+    #[test]
+    fn failed_saves_are_reported_and_retried() {
+        let (dir, store, board) = store_and_board();
+        let mut autosave = Autosave::new(store, Duration::ZERO);
+        std::fs::remove_dir_all(dir.path().join(".kuk/boards")).unwrap();
+
+        autosave.mark_dirty();
+        let err = autosave.flush(&board).unwrap_err();
+        assert!(err.to_string().starts_with("Save failed:"));
+        assert!(autosave.is_dirty());
+
+        std::fs::create_dir_all(dir.path().join(".kuk/boards")).unwrap();
+        autosave.flush(&board).unwrap();
+        assert!(!autosave.is_dirty());
+    }
+}
@@ -1,4 +1,12 @@
 mod app;
+mod autosave;
+mod theme;
 mod ui;
 
 pub use app::run_tui;
+pub use theme::Theme;
+
+// Front ends built on kuk's TUI, like `kuk-pm tui`, draw with the same
+// ratatui and crossterm as kuk itself.
+pub use crossterm;
+pub use ratatui;
@@ -0,0 +1,72 @@
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::model::ColorScheme;
+
+/// Styles for the TUI, picked from the repo's `color_scheme`.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    /// Focused borders and overlay titles.
+    pub accent: Style,
+    /// Title bar and highlighted rows.
+    pub selected: Style,
+    pub text: Style,
+    /// Unfocused borders and secondary text.
+    pub muted: Style,
+    pub status: Style,
+    /// Due-date markers on overdue cards and cards due soon, also used for
+    /// urgent and high priorities.
+    pub overdue: Style,
+    pub due_soon: Style,
+    /// Whether label registry colors are shown.
+    pub label_colors: bool,
+}
+
+impl Theme {
+    pub fn new(scheme: ColorScheme) -> Self {
+        let bold = Modifier::BOLD;
+        match scheme {
+            ColorScheme::Default => Self {
+                accent: Style::default().fg(Color::Cyan),
+                selected: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(bold),
+                text: Style::default().fg(Color::White),
+                muted: Style::default().fg(Color::DarkGray),
+                status: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::DarkGray)
+                    .add_modifier(bold),
+                overdue: Style::default().fg(Color::Red).add_modifier(bold),
+                due_soon: Style::default().fg(Color::Yellow),
+                label_colors: true,
+            },
+            ColorScheme::Light => Self {
+                accent: Style::default().fg(Color::Blue),
+                selected: Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Blue)
+                    .add_modifier(bold),
+                text: Style::default().fg(Color::Black),
+                muted: Style::default().fg(Color::Gray),
+                status: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Gray)
+                    .add_modifier(bold),
+                overdue: Style::default().fg(Color::Red).add_modifier(bold),
+                due_soon: Style::default().fg(Color::Magenta),
+                label_colors: true,
+            },
+            ColorScheme::Mono => Self {
+                accent: Style::default().add_modifier(bold),
+                selected: Style::default().add_modifier(bold | Modifier::REVERSED),
+                text: Style::default(),
+                muted: Style::default().add_modifier(Modifier::DIM),
+                status: Style::default().add_modifier(Modifier::REVERSED),
+                overdue: Style::default().add_modifier(bold | Modifier::UNDERLINED),
+                due_soon: Style::default().add_modifier(bold),
+                label_colors: false,
+            },
+        }
+    }
+}
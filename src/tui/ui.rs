@@ -1,9 +1,12 @@
+use chrono::{DateTime, Utc};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap};
 
+use crate::model::{Card, DueStatus, Priority, Swimlanes, split_lanes};
+
 use super::app::{App, Mode};
 
 pub fn draw(f: &mut Frame, app: &App) {
@@ -21,26 +24,39 @@ pub fn draw(f: &mut Frame, app: &App) {
     draw_status_bar(f, chunks[2], app);
 
     if app.mode == Mode::Help {
-        draw_help_overlay(f);
+        draw_help_overlay(f, app);
     }
 
     if app.mode == Mode::BoardPicker {
         draw_board_picker_overlay(f, app);
     }
+
+    if app.mode == Mode::Finder {
+        draw_finder_overlay(f, app);
+    }
 }
 
 fn draw_title_bar(f: &mut Frame, area: Rect, app: &App) {
     let title = format!(
-        " kuk  │  {}  │  {} cards",
+        " kuk  │  {}{}  │  {} {}",
         app.board.name,
-        app.board.cards.iter().filter(|c| !c.archived).count()
-    );
-    let bar = Paragraph::new(title).style(
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::Cyan)
-            .add_modifier(Modifier::BOLD),
+        if app.has_unsaved_changes() {
+            " •"
+        } else {
+            ""
+        },
+        app.board
+            .cards
+            .iter()
+            .filter(|c| c.archived == app.show_archived)
+            .count(),
+        if app.show_archived {
+            "archived"
+        } else {
+            "cards"
+        }
     );
+    let bar = Paragraph::new(title).style(app.theme.selected);
     f.render_widget(bar, area);
 }
 
@@ -49,73 +65,194 @@ fn draw_board(f: &mut Frame, area: Rect, app: &App) {
     if num_cols == 0 {
         return;
     }
+    if let Some(by) = app.lanes {
+        return draw_lanes(f, area, app, by);
+    }
 
-    let constraints: Vec<Constraint> = (0..num_cols)
-        .map(|_| Constraint::Ratio(1, num_cols as u32))
-        .collect();
-
-    let col_areas = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(constraints)
-        .split(area);
+    let col_areas = column_areas(area, num_cols);
+    let selected = app.current_card_id();
 
     for (i, col) in app.board.columns.iter().enumerate() {
         let cards = app.column_cards(i);
-        let is_selected_col = i == app.selected_col;
 
         let wip_info = col
             .wip_limit
+            .filter(|_| !app.show_archived)
             .map(|l| format!(" [{}/{}]", cards.len(), l))
             .unwrap_or_default();
 
-        let header = format!("{} ({}){}", col.name.to_uppercase(), cards.len(), wip_info);
+        let queue = if col.queue { " queue" } else { "" };
+        let header = format!(
+            "{} ({}){}{}",
+            col.name.to_uppercase(),
+            cards.len(),
+            wip_info,
+            queue
+        );
+        draw_cell(f, col_areas[i], app, header, i, &cards, selected.as_deref());
+    }
+}
+
+/// The board as rows of swimlanes, each split into the board's columns.
+fn draw_lanes(f: &mut Frame, area: Rect, app: &App, by: Swimlanes) {
+    let num_cols = app.board.columns.len();
+    let columns: Vec<Vec<&Card>> = (0..num_cols).map(|i| app.column_cards(i)).collect();
+    let lanes = split_lanes(by, &columns);
+    if lanes.is_empty() {
+        let empty = Paragraph::new(format!(" No cards to split by {by}."))
+            .style(app.theme.muted)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Taller lanes for more cards: each row fits its fullest cell plus borders
+    let constraints: Vec<Constraint> = lanes
+        .iter()
+        .map(|lane| {
+            let rows = lane.columns.iter().map(Vec::len).max().unwrap_or(0);
+            Constraint::Fill(rows as u16 + 3)
+        })
+        .collect();
+    let lane_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+    let selected = app.current_card_id();
 
-        let border_style = if is_selected_col {
-            Style::default().fg(Color::Cyan)
+    for (lane, lane_area) in lanes.iter().zip(lane_areas.iter()) {
+        let total: usize = lane.columns.iter().map(Vec::len).sum();
+        let block = Block::default()
+            .title(format!(
+                " {} ({total}) ",
+                lane.name.unwrap_or(by.unset_name())
+            ))
+            .borders(Borders::TOP)
+            .border_style(app.theme.accent);
+        let inner = block.inner(*lane_area);
+        f.render_widget(block, *lane_area);
+
+        let col_areas = column_areas(inner, num_cols);
+        for (i, (col, cards)) in app.board.columns.iter().zip(&lane.columns).enumerate() {
+            let header = format!("{} ({})", col.name.to_uppercase(), cards.len());
+            draw_cell(f, col_areas[i], app, header, i, cards, selected.as_deref());
+        }
+    }
+}
+
+fn column_areas(area: Rect, num_cols: usize) -> std::rc::Rc<[Rect]> {
+    let constraints: Vec<Constraint> = (0..num_cols)
+        .map(|_| Constraint::Ratio(1, num_cols as u32))
+        .collect();
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(area)
+}
+
+/// One column's cards in a bordered box, highlighting the selected card.
+fn draw_cell(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    header: String,
+    col_idx: usize,
+    cards: &[&Card],
+    selected: Option<&str>,
+) {
+    let now = Utc::now();
+    let border_style = if col_idx == app.selected_col {
+        app.theme.accent
+    } else {
+        app.theme.muted
+    };
+
+    let block = Block::default()
+        .title(header)
+        .borders(Borders::ALL)
+        .border_style(border_style);
+
+    let items: Vec<ListItem> = cards
+        .iter()
+        .map(|card| card_item(app, card, selected == Some(card.id.as_str()), now))
+        .collect();
+
+    let list = List::new(items).block(block);
+    f.render_widget(list, area);
+}
+
+fn card_item<'a>(app: &App, card: &Card, is_selected: bool, now: DateTime<Utc>) -> ListItem<'a> {
+    let assignee = card
+        .assignee
+        .as_ref()
+        .map(|a| format!(" @{a}"))
+        .unwrap_or_default();
+
+    let due = app
+        .board
+        .due_status(card, now)
+        .zip(card.due)
+        .map(|(status, at)| {
+            let flag = if status == DueStatus::Overdue {
+                "!"
+            } else {
+                ""
+            };
+            (status, format!(" {flag}{}", at.format("%m-%d")))
+        });
+
+    let priority = card.priority.map(|p| (p, format!(" ({p})")));
+
+    if is_selected {
+        let labels = if card.labels.is_empty() {
+            String::new()
         } else {
-            Style::default().fg(Color::DarkGray)
+            format!(" [{}]", card.labels.join(","))
         };
+        let priority = priority.map(|(_, text)| text).unwrap_or_default();
+        let due = due.map(|(_, text)| text).unwrap_or_default();
+        let text = format!("{}{}{}{}{}", card.title, priority, labels, assignee, due);
+        return ListItem::new(Line::from(Span::styled(text, app.theme.selected)));
+    }
 
-        let block = Block::default()
-            .title(header)
-            .borders(Borders::ALL)
-            .border_style(border_style);
+    let plain = app.theme.text;
+    let mut spans = vec![Span::styled(card.title.clone(), plain)];
+    if let Some((level, text)) = priority {
+        let style = match level {
+            Priority::Urgent => app.theme.overdue,
+            Priority::High => app.theme.due_soon,
+            _ => app.theme.muted,
+        };
+        spans.push(Span::styled(text, style));
+    }
+    if !card.labels.is_empty() {
+        spans.push(Span::styled(" [", plain));
+        for (k, label) in card.labels.iter().enumerate() {
+            if k > 0 {
+                spans.push(Span::styled(",", plain));
+            }
+            spans.push(Span::styled(label.clone(), label_style(app, label)));
+        }
+        spans.push(Span::styled("]", plain));
+    }
+    spans.push(Span::styled(assignee, plain));
+    if let Some((status, text)) = due {
+        let style = match status {
+            DueStatus::Overdue => app.theme.overdue,
+            DueStatus::Soon => app.theme.due_soon,
+            DueStatus::Later => app.theme.muted,
+        };
+        spans.push(Span::styled(text, style));
+    }
+    ListItem::new(Line::from(spans))
+}
 
-        let items: Vec<ListItem> = cards
-            .iter()
-            .enumerate()
-            .map(|(j, card)| {
-                let is_selected = is_selected_col && j == app.selected_row;
-
-                let labels = if card.labels.is_empty() {
-                    String::new()
-                } else {
-                    format!(" [{}]", card.labels.join(","))
-                };
-
-                let assignee = card
-                    .assignee
-                    .as_ref()
-                    .map(|a| format!(" @{a}"))
-                    .unwrap_or_default();
-
-                let text = format!("{}{}{}", card.title, labels, assignee);
-
-                let style = if is_selected {
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(Color::White)
-                };
-
-                ListItem::new(Line::from(Span::styled(text, style)))
-            })
-            .collect();
-
-        let list = List::new(items).block(block);
-        f.render_widget(list, col_areas[i]);
+/// Registry color for a label, or plain text when it has none or the
+/// scheme has no colors.
+fn label_style(app: &App, label: &str) -> Style {
+    match app.board.label_def(label).and_then(|d| d.rgb()) {
+        Some((r, g, b)) if app.theme.label_colors => Style::default().fg(Color::Rgb(r, g, b)),
+        _ => app.theme.text,
     }
 }
 
@@ -127,6 +264,7 @@ fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
         Mode::Help => "HELP",
         Mode::Confirm => "CONFIRM",
         Mode::BoardPicker => "BOARDS",
+        Mode::Finder => "FINDER",
     };
 
     let left = match app.mode {
@@ -141,25 +279,18 @@ fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
         }
     };
 
-    let bar = Paragraph::new(left).style(
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::DarkGray)
-            .add_modifier(Modifier::BOLD),
-    );
+    let bar = Paragraph::new(left).style(app.theme.status);
     f.render_widget(bar, area);
 }
 
-fn draw_help_overlay(f: &mut Frame) {
+fn draw_help_overlay(f: &mut Frame, app: &App) {
     let area = centered_rect(60, 70, f.area());
     f.render_widget(Clear, area);
 
     let help_text = vec![
         Line::from(Span::styled(
             "  kuk — Keyboard Reference",
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Cyan),
+            app.theme.accent.add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from("  Navigation"),
@@ -171,23 +302,29 @@ fn draw_help_overlay(f: &mut Frame) {
         Line::from("  Actions"),
         Line::from("    a              Add card to current column"),
         Line::from("    d              Delete card (with confirm)"),
-        Line::from("    x              Archive card"),
+        Line::from("    x              Archive card (restore it in the archived view)"),
+        Line::from("    A              Show archived cards / active cards"),
         Line::from("    L / >          Move card right"),
         Line::from("    H / <          Move card left"),
+        Line::from("                   (repeat to go over a WIP limit)"),
         Line::from("    K              Hoist (move to top)"),
         Line::from("    J              Demote (move to bottom)"),
+        Line::from("    p              Cycle card priority"),
+        Line::from("    P              Sort by priority / high+ only / board order"),
+        Line::from(
+            "    o / O          Sort by priority / due / updated / created / title; reverse",
+        ),
+        Line::from("    s              Swimlanes by assignee / label / lane / off"),
         Line::from(""),
         Line::from("  Other"),
         Line::from("    b              Switch board"),
+        Line::from("    Ctrl+P         Fuzzy find card (Tab: all boards)"),
         Line::from("    /              Search"),
         Line::from("    r              Refresh board"),
         Line::from("    ?              Toggle help"),
         Line::from("    q / Ctrl+C     Quit"),
         Line::from(""),
-        Line::from(Span::styled(
-            "  Press Esc or ? to close",
-            Style::default().fg(Color::DarkGray),
-        )),
+        Line::from(Span::styled("  Press Esc or ? to close", app.theme.muted)),
     ];
 
     let help = Paragraph::new(help_text)
@@ -195,7 +332,7 @@ fn draw_help_overlay(f: &mut Frame) {
             Block::default()
                 .title(" Help ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(app.theme.accent),
         )
         .wrap(Wrap { trim: false })
         .alignment(Alignment::Left);
@@ -220,16 +357,11 @@ fn draw_board_picker_overlay(f: &mut Frame, app: &App) {
             let text = format!("{prefix}{name}");
 
             let style = if is_selected {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
+                app.theme.selected
             } else if is_active {
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
+                app.theme.accent.add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::White)
+                app.theme.text
             };
 
             ListItem::new(Line::from(Span::styled(text, style)))
@@ -240,12 +372,68 @@ fn draw_board_picker_overlay(f: &mut Frame, app: &App) {
         Block::default()
             .title(" Switch Board ")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(app.theme.accent),
     );
 
     f.render_widget(list, area);
 }
 
+fn draw_finder_overlay(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let scope = if app.finder_all_boards {
+        "all boards"
+    } else {
+        "this board"
+    };
+    let block = Block::default()
+        .title(format!(" Find Card ({scope}) "))
+        .borders(Borders::ALL)
+        .border_style(app.theme.accent);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    let prompt = Paragraph::new(format!("> {}", app.finder_buf))
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    f.render_widget(prompt, chunks[0]);
+
+    // Keep the selected row visible when the list overflows
+    let visible = chunks[1].height as usize;
+    let skip = (app.finder_selected + 1).saturating_sub(visible);
+
+    let items: Vec<ListItem> = app
+        .finder_results
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(visible)
+        .map(|(i, hit)| {
+            let location = if app.finder_all_boards {
+                format!("{}/{}", hit.board, hit.column)
+            } else {
+                hit.column.clone()
+            };
+            let style = if i == app.finder_selected {
+                app.theme.selected
+            } else {
+                app.theme.text
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {}", hit.title), style),
+                Span::styled(format!("  {location}"), app.theme.muted),
+            ]))
+        })
+        .collect();
+
+    f.render_widget(List::new(items), chunks[1]);
+}
+
 fn centered_fixed(width: u16, height: u16, r: Rect) -> Rect {
     let x = r.x + r.width.saturating_sub(width) / 2;
     let y = r.y + r.height.saturating_sub(height) / 2;
@@ -0,0 +1,135 @@
+use kuk::api::{CardBuilder, CardQuery, KukError, Project};
+use tempfile::TempDir;
+
+fn project() -> (TempDir, Project) {
+    let dir = TempDir::new().unwrap();
+    let project = Project::init(dir.path()).unwrap();
+    (dir, project)
+}
+
+// --- Project ---
+
+#[test]
+fn open_requires_initialized_project() {
+    let dir = TempDir::new().unwrap();
+    assert!(matches!(
+        Project::open(dir.path()),
+        Err(KukError::NotInitialized)
+    ));
+    Project::init(dir.path()).unwrap();
+    let project = Project::open(dir.path()).unwrap();
+    assert_eq!(project.root(), dir.path());
+    assert_eq!(project.boards().unwrap(), vec!["default"]);
+}
+
+#[test]
+fn cards_query_spans_boards() {
+    let (_dir, project) = project();
+    let mut default = project.default_board().unwrap();
+    default
+        .add(CardBuilder::new("Fix login").label("bug"))
+        .unwrap();
+    default.add(CardBuilder::new("Write docs")).unwrap();
+    default.save().unwrap();
+    let mut ops = project.create_board("ops", &["queue", "done"]).unwrap();
+    ops.add(CardBuilder::new("Rotate keys").label("bug"))
+        .unwrap();
+    ops.save().unwrap();
+
+    let bugs: Vec<_> = project
+        .cards(CardQuery {
+            label: Some("bug".into()),
+            ..CardQuery::default()
+        })
+        .unwrap()
+        .map(|item| {
+            let (board, card) = item.unwrap();
+            format!("{board}/{}", card.title)
+        })
+        .collect();
+    assert_eq!(bugs, vec!["default/Fix login", "ops/Rotate keys"]);
+}
+
+// --- BoardHandle ---
+
+#[test]
+fn card_lifecycle_persists_on_save() {
+    let (dir, project) = project();
+    let mut board = project.default_board().unwrap();
+    let id = board
+        .add(
+            CardBuilder::new("Ship it")
+                .column("doing")
+                .assignee("sam")
+                .metadata("source", serde_json::json!("embed")),
+        )
+        .unwrap()
+        .id
+        .clone();
+    board.add(CardBuilder::new("Plan sprint")).unwrap();
+    assert_eq!(board.column_cards("todo")[0].title, "Plan sprint");
+
+    board.move_card(&id, "done").unwrap();
+    board.add_label(&id, "release").unwrap();
+    board.assign(&id, None).unwrap();
+    // Not saved yet
+    let reopened = Project::open(dir.path()).unwrap();
+    assert!(reopened.default_board().unwrap().card(&id).is_none());
+
+    board.save().unwrap();
+    let saved = reopened.default_board().unwrap();
+    let card = saved.card(&id).unwrap();
+    assert_eq!(card.column, "done");
+    assert_eq!(card.labels, vec!["release"]);
+    assert!(card.assignee.is_none());
+    assert_eq!(card.metadata["source"], "embed");
+    let titles: Vec<_> = saved.cards().iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(titles, vec!["Plan sprint", "Ship it"]);
+
+    board.archive(&id).unwrap();
+    assert!(board.cards().iter().all(|c| c.id != id));
+    assert_eq!(board.delete("1").unwrap().title, "Plan sprint");
+    board.reload().unwrap();
+    assert_eq!(board.cards().len(), 2);
+}
+
+#[test]
+fn hoist_and_demote_reorder_column() {
+    let (_dir, project) = project();
+    let mut board = project.default_board().unwrap();
+    for title in ["A", "B", "C"] {
+        board.add(CardBuilder::new(title)).unwrap();
+    }
+    let c = board.column_cards("todo")[2].id.clone();
+    board.hoist(&c).unwrap();
+    board.demote("2").unwrap();
+    let titles: Vec<_> = board
+        .column_cards("todo")
+        .iter()
+        .map(|c| c.title.as_str())
+        .collect();
+    assert_eq!(titles, vec!["C", "B", "A"]);
+}
+
+#[test]
+fn invalid_changes_are_rejected() {
+    let (_dir, project) = project();
+    let mut board = project.default_board().unwrap();
+    assert!(matches!(
+        board.add(CardBuilder::new("Lost").column("nowhere")),
+        Err(KukError::ColumnNotFound(_))
+    ));
+    let id = board.add(CardBuilder::new("Real")).unwrap().id.clone();
+    assert!(matches!(
+        board.move_card(&id, "nowhere"),
+        Err(KukError::ColumnNotFound(_))
+    ));
+    assert!(matches!(
+        board.move_card("99", "done"),
+        Err(KukError::CardNotFound(_))
+    ));
+    assert!(matches!(
+        board.remove_label(&id, "missing"),
+        Err(KukError::LabelNotFound(_))
+    ));
+}
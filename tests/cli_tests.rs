@@ -2,6 +2,9 @@ use assert_cmd::Command;
 use predicates::prelude::*;
 use tempfile::TempDir;
 
+// `cargo_bin` is deprecated in newer assert_cmd releases, but the
+// replacement macro isn't available across the whole "2" range.
+#[allow(deprecated)]
 fn kuk() -> Command {
     Command::cargo_bin("kuk").unwrap()
 }
@@ -23,6 +26,40 @@ fn version_prints_version() {
         .stdout(predicate::str::contains("kuk 0.1.0"));
 }
 
+#[test]
+fn version_help_and_intro_read_no_project_files() {
+    // Anything that loaded config or boards would fail on a `.kuk` file
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join(".kuk"), "").unwrap();
+    for args in [&["version"][..], &["--help"], &[]] {
+        kuk_in(&dir)
+            .args(args)
+            .env("HOME", dir.path().join("no-home"))
+            .assert()
+            .success();
+    }
+    kuk_in(&dir).arg("list").assert().failure();
+
+    // In a real project the -vv trace shows every file read
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    for args in [&["-vv", "version"][..], &["-vv"]] {
+        kuk_in(&dir)
+            .args(args)
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("load_config").not())
+            .stderr(predicate::str::contains("read path=").not());
+    }
+    let list = kuk_in(&dir).args(["-vv", "list"]).output().unwrap();
+    let trace = String::from_utf8_lossy(&list.stderr);
+    let config_reads = trace
+        .lines()
+        .filter(|l| l.contains("read path=") && l.contains("config.json"))
+        .count();
+    assert_eq!(config_reads, 1, "{trace}");
+}
+
 // --- No args ---
 
 #[test]
@@ -60,6 +97,28 @@ fn init_creates_kuk_dir() {
     assert!(dir.path().join(".kuk/boards/default.json").exists());
 }
 
+#[test]
+fn init_interactive_applies_answers() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir)
+        .args(["init", "--interactive"])
+        .write_stdin("work\nbacklog, doing, done\n\n2\n\nn\nlight\nn\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("WIP limit for doing [none]: "))
+        .stdout(predicate::str::contains("added .kuk/ to .gitignore"));
+    let config = std::fs::read_to_string(dir.path().join(".kuk/config.json")).unwrap();
+    assert!(config.contains("\"default_board\": \"work\""));
+    assert!(config.contains("\"color_scheme\": \"light\""));
+    let gitignore = std::fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+    assert_eq!(gitignore, ".kuk/\n");
+    kuk_in(&dir)
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"wip_limit\": 2"));
+}
+
 #[test]
 fn init_twice_fails() {
     let dir = TempDir::new().unwrap();
@@ -161,6 +220,169 @@ fn add_card_json_output() {
     assert_eq!(json["column"], "todo");
 }
 
+#[test]
+fn add_stdin_adds_a_card_per_line() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    let notes = "Fix login\n\n- [ ] Write release notes\n\
+                 {\"title\": \"Rotate keys\", \"column\": \"doing\", \"labels\": [\"ops\"], \"priority\": \"high\"}\n";
+    kuk_in(&dir)
+        .args(["add", "--stdin", "--label", "team"])
+        .write_stdin(notes)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added: Fix login → todo (KUK-1)"))
+        .stdout(predicate::str::contains(
+            "Added: Write release notes → todo (KUK-2)",
+        ))
+        .stdout(predicate::str::contains(
+            "Added: Rotate keys → doing (KUK-3)",
+        ));
+
+    let output = kuk_in(&dir)
+        .args(["--json", "show", "KUK-3"])
+        .output()
+        .unwrap();
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["labels"], serde_json::json!(["team", "ops"]));
+    assert_eq!(card["priority"], "high");
+
+    // One bad line and nothing is added
+    kuk_in(&dir)
+        .args(["add", "--stdin"])
+        .write_stdin("Good card\n{\"title\": \"Lost\", \"column\": \"nowhere\"}\n{oops\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "line 2: Column not found: nowhere",
+        ))
+        .stderr(predicate::str::contains("line 3: invalid JSON"));
+    let output = kuk_in(&dir).args(["--json", "list"]).output().unwrap();
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(!text.contains("Good card"), "{text}");
+
+    kuk_in(&dir)
+        .args(["add", "Title", "--stdin"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn import_markdown_turns_tasks_into_cards() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    let file = dir.path().join("plan.md");
+    std::fs::write(
+        &file,
+        "# Launch\n\n- [ ] Write docs #docs @leslie\n  - [x] Outline\n  - [ ] Screenshots\n- [x] Tag release\n",
+    )
+    .unwrap();
+
+    kuk_in(&dir)
+        .args(["import", "markdown"])
+        .arg(&file)
+        .args(["--to", "doing"])
+        .assert()
+        .success()
+        .stdout("Imported: Write docs → doing (KUK-1)\nImported: Tag release → done (KUK-2)\n");
+    let output = kuk_in(&dir)
+        .args(["--json", "show", "KUK-1"])
+        .output()
+        .unwrap();
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["labels"], serde_json::json!(["docs"]));
+    assert_eq!(card["assignee"], "leslie");
+    assert_eq!(card["description"], "- [x] Outline\n- [ ] Screenshots");
+
+    kuk_in(&dir)
+        .args(["label-def", "strict", "on"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["import", "markdown"])
+        .arg(&file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No cards imported"))
+        .stderr(predicate::str::contains("line 3:"));
+}
+
+#[test]
+fn add_uses_the_configured_defaults_unless_overridden() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    let path = dir.path().join(".kuk/config.json");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    config["new_cards"] =
+        serde_json::json!({"column": "doing", "labels": ["triage"], "assignee": "sam"});
+    std::fs::write(&path, config.to_string()).unwrap();
+
+    let added = |args: &[&str]| -> serde_json::Value {
+        let output = kuk_in(&dir)
+            .arg("add")
+            .args(args)
+            .arg("--json")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+    let card = added(&["Crash on save"]);
+    assert_eq!(card["column"], "doing");
+    assert_eq!(card["labels"], serde_json::json!(["triage"]));
+    assert_eq!(card["assignee"], "sam");
+
+    let card = added(&[
+        "Polish",
+        "--to",
+        "todo",
+        "--label",
+        "ui",
+        "--assignee",
+        "ana",
+    ]);
+    assert_eq!(card["column"], "todo");
+    assert_eq!(card["labels"], serde_json::json!(["triage", "ui"]));
+    assert_eq!(card["assignee"], "ana");
+
+    let card = added(&["Plain", "--no-defaults"]);
+    assert_eq!(card["column"], "todo");
+    assert_eq!(card["labels"], serde_json::json!([]));
+    assert!(card["assignee"].is_null());
+}
+
+#[test]
+fn concurrent_writers_keep_each_others_cards() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+
+    std::thread::scope(|s| {
+        for writer in ["a", "b"] {
+            let dir = &dir;
+            s.spawn(move || {
+                for n in 0..8 {
+                    kuk_in(dir)
+                        .args(["add", &format!("{writer}{n}")])
+                        .assert()
+                        .success();
+                }
+            });
+        }
+    });
+
+    let out = kuk_in(&dir).args(["list", "--json"]).output().unwrap();
+    let board: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let mut seqs: Vec<u64> = board["cards"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["seq"].as_u64().unwrap())
+        .collect();
+    seqs.sort();
+    assert_eq!(seqs, (1..=16).collect::<Vec<_>>());
+}
+
 // --- List ---
 
 #[test]
@@ -202,275 +424,2415 @@ fn list_json_output() {
     assert_eq!(json["cards"].as_array().unwrap().len(), 1);
 }
 
-// --- Move ---
-
 #[test]
-fn move_card_by_number() {
+fn list_groups_cards_into_swimlanes() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "Move me"]).assert().success();
+    kuk_in(&dir)
+        .args(["add", "Fix login", "--assignee", "al"])
+        .assert()
+        .success();
+    kuk_in(&dir).args(["add", "Triage"]).assert().success();
     kuk_in(&dir)
         .args(["move", "1", "--to", "doing"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("Moved: Move me → doing"));
-}
+        .success();
 
-#[test]
-fn move_nonexistent_card_fails() {
-    let dir = TempDir::new().unwrap();
-    kuk_in(&dir).arg("init").assert().success();
     kuk_in(&dir)
-        .args(["move", "99", "--to", "doing"])
+        .args(["list", "--lanes"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Card not found"));
-}
-
-#[test]
-fn move_to_invalid_column_fails() {
-    let dir = TempDir::new().unwrap();
-    kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "Card"]).assert().success();
+        .stderr(predicate::str::contains("Board default has no swimlanes"));
     kuk_in(&dir)
-        .args(["move", "1", "--to", "nope"])
+        .args(["list", "--lanes", "assignee"])
         .assert()
-        .failure()
-        .stderr(predicate::str::contains("Column not found"));
-}
-
-// --- Archive ---
+        .success()
+        .stdout(predicate::str::contains(
+            "══ al (1) ══\n── DOING (1)──\n  1. Fix login @al\n",
+        ))
+        .stdout(predicate::str::contains(
+            "══ unassigned (1) ══\n── TODO (1)──\n  1. Triage\n",
+        ));
 
-#[test]
-fn archive_card() {
-    let dir = TempDir::new().unwrap();
-    kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "Archive me"]).assert().success();
     kuk_in(&dir)
-        .args(["archive", "1"])
+        .args(["edit", "KUK-2", "--lane", "ops"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "lanes", "lane"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("Archived"));
+        .stdout(predicate::str::contains("Swimlanes for default: by lane"));
+    let output = kuk_in(&dir)
+        .args(["list", "--lanes", "--json"])
+        .output()
+        .unwrap();
+    let lanes: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(lanes[0]["lane"], "ops");
+    assert_eq!(lanes[0]["columns"][0]["cards"][0]["title"], "Triage");
+    assert_eq!(lanes[1]["lane"], serde_json::Value::Null);
+    assert_eq!(lanes[1]["columns"][1]["name"], "doing");
 
-    // Should not show in list
     kuk_in(&dir)
-        .arg("list")
+        .args(["board", "lanes", "off"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("TODO (0)"));
+        .stdout(predicate::str::contains("No swimlanes for default"));
 }
 
-// --- Delete ---
-
 #[test]
-fn delete_card() {
+fn add_from_card_template() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "Delete me"]).assert().success();
+    let templates = dir.path().join(".kuk/card-templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    std::fs::write(
+        templates.join("bug-report.yaml"),
+        "title: \"[{component}] {title}\"\nlabels: [bug]\nchecklist:\n  - Reproduce\n",
+    )
+    .unwrap();
+
     kuk_in(&dir)
-        .args(["delete", "1"])
+        .args(["add", "Login loops", "--template", "bug-report"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("Deleted"));
-
+        .failure()
+        .stderr(predicate::str::contains("--var component="));
     kuk_in(&dir)
-        .arg("list")
+        .args(["add", "Login loops", "--template", "nope"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("TODO (0)"));
+        .failure()
+        .stderr(predicate::str::contains("Available: bug-report"));
+
+    let output = kuk_in(&dir)
+        .args([
+            "add",
+            "Login loops",
+            "--template",
+            "bug-report",
+            "--var",
+            "component=auth",
+            "--label",
+            "urgent",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["title"], "[auth] Login loops");
+    assert_eq!(card["description"], "- [ ] Reproduce");
+    assert_eq!(card["labels"], serde_json::json!(["bug", "urgent"]));
 }
 
-// --- Hoist / Demote ---
+// --- Search ---
 
 #[test]
-fn hoist_card() {
+fn search_matches_every_term_across_boards() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "First"]).assert().success();
-    kuk_in(&dir).args(["add", "Second"]).assert().success();
     kuk_in(&dir)
-        .args(["hoist", "2"])
+        .args([
+            "add",
+            "Login fails",
+            "--label",
+            "bug",
+            "--assignee",
+            "leslie",
+        ])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("Hoisted"));
-}
-
-#[test]
-fn demote_card() {
-    let dir = TempDir::new().unwrap();
-    kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "First"]).assert().success();
-    kuk_in(&dir).args(["add", "Second"]).assert().success();
+        .success();
     kuk_in(&dir)
-        .args(["demote", "1"])
+        .args(["add", "Login page", "--assignee", "leslie"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("Demoted"));
-}
-
-// --- Label ---
+        .success();
+    kuk_in(&dir)
+        .args(["move", "KUK-1", "--to", "doing"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "create", "ops"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "switch", "ops"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["add", "Login audit", "--label", "bug"])
+        .assert()
+        .success();
 
-#[test]
-fn label_add_and_remove() {
-    let dir = TempDir::new().unwrap();
-    kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "Labelable"]).assert().success();
     kuk_in(&dir)
-        .args(["label", "1", "add", "bug"])
+        .args([
+            "search",
+            "label:bug assignee:leslie column:doing text:login",
+        ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("bug"));
+        .stdout("  default/doing  KUK-1  Login fails [bug] @leslie\n\n1 card(s) found.\n");
+    let output = kuk_in(&dir)
+        .args(["--json", "search", "login label:BUG"])
+        .output()
+        .unwrap();
+    let hits: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let found: Vec<(&str, &str)> = hits
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| (h["board"].as_str().unwrap(), h["title"].as_str().unwrap()))
+        .collect();
+    assert_eq!(found, [("default", "Login fails"), ("ops", "Login audit")]);
 
     kuk_in(&dir)
-        .args(["label", "1", "remove", "bug"])
+        .args(["search", "board:ops assignee:leslie"])
         .assert()
-        .success();
-}
-
-#[test]
-fn label_remove_nonexistent_fails() {
-    let dir = TempDir::new().unwrap();
-    kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "No label"]).assert().success();
+        .success()
+        .stdout("No cards match.\n");
     kuk_in(&dir)
-        .args(["label", "1", "remove", "nope"])
+        .args(["search", "owner:me"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Label not found"));
+        .stderr(predicate::str::contains("Unknown search key 'owner'"));
 }
 
-// --- Assign ---
+// --- Move ---
 
 #[test]
-fn assign_user() {
+fn move_card_by_number() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
-    kuk_in(&dir).args(["add", "Assignable"]).assert().success();
+    kuk_in(&dir).args(["add", "Move me"]).assert().success();
     kuk_in(&dir)
-        .args(["assign", "1", "leslie"])
+        .args(["move", "1", "--to", "doing"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("@leslie"));
+        .stdout(predicate::str::contains("Moved: Move me → doing"));
 }
 
-// --- Board commands ---
-
 #[test]
-fn board_list_shows_active_marker() {
+fn short_ids_survive_moves() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
     kuk_in(&dir)
-        .args(["board", "list"])
+        .args(["add", "First"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("* default"));
-}
+        .stdout(predicate::str::contains("Added: First → todo (KUK-1)"));
+    kuk_in(&dir).args(["add", "Second"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "KUK-1", "--to", "done"])
+        .assert()
+        .success();
 
-#[test]
-fn board_create() {
-    let dir = TempDir::new().unwrap();
-    kuk_in(&dir).arg("init").assert().success();
     kuk_in(&dir)
-        .args(["board", "create", "sprint-1"])
+        .args(["show", "KUK-2"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("Created board: sprint-1"));
-
+        .stdout(predicate::str::starts_with("Second"))
+        .stdout(predicate::str::contains("Short ID:  KUK-2"));
     kuk_in(&dir)
-        .args(["board", "list"])
+        .args(["move", "kuk-2", "--to", "doing"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("sprint-1"));
+        .stdout(predicate::str::contains("Moved: Second → doing"));
+    kuk_in(&dir)
+        .args(["show", "KUK-2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Time in:   todo 0m, doing 0m"));
+    kuk_in(&dir).args(["archive", "KUK-1"]).assert().success();
+    kuk_in(&dir)
+        .args(["add", "Third"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(KUK-3)"));
 }
 
 #[test]
-fn board_create_duplicate_fails() {
+fn move_nonexistent_card_fails() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
     kuk_in(&dir)
-        .args(["board", "create", "default"])
+        .args(["move", "99", "--to", "doing"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("already exists"));
+        .stderr(predicate::str::contains("Card not found"));
 }
 
 #[test]
-fn board_switch_persists() {
+fn move_to_invalid_column_fails() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Card"]).assert().success();
     kuk_in(&dir)
-        .args(["board", "create", "sprint-1"])
+        .args(["move", "1", "--to", "nope"])
         .assert()
-        .success();
+        .failure()
+        .stderr(predicate::str::contains("Column not found"));
+}
 
-    // Switch to sprint-1
+#[test]
+fn clone_copies_a_card_to_a_fresh_one() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
     kuk_in(&dir)
-        .args(["board", "switch", "sprint-1"])
+        .args(["add", "Release", "--label", "ops", "--assignee", "ana"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["edit", "1", "--description=- [x] Tag\n- [ ] Publish"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "done"])
+        .assert()
+        .success();
+
+    kuk_in(&dir)
+        .args(["clone", "1", "--to", "todo"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("Switched to board: sprint-1"));
+        .stdout("Cloned: Release → todo (KUK-2)\n");
+    let output = kuk_in(&dir).args(["--json", "show", "2"]).output().unwrap();
+    let copy: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(copy["labels"], serde_json::json!(["ops"]));
+    assert_eq!(copy["description"], "- [ ] Tag\n- [ ] Publish");
+    assert!(copy["assignee"].is_null());
+    assert!(copy["metadata"]["transitions"].is_null());
 
-    // board list should show * on sprint-1, not default
     kuk_in(&dir)
-        .args(["board", "list"])
+        .args(["board", "create", "ops", "--columns", "inbox,shipped"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["clone", "KUK-1", "--board", "ops"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("* sprint-1"))
-        .stdout(predicate::str::contains("  default"));
+        .stdout("Cloned: Release → inbox on ops (KUK-1)\n");
+    kuk_in(&dir)
+        .args(["clone", "9"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Card not found"));
+}
 
-    // add should go to the active board (sprint-1)
+// --- Archive ---
+
+#[test]
+fn archive_card() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Archive me"]).assert().success();
     kuk_in(&dir)
-        .args(["add", "Task on sprint"])
+        .args(["archive", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Archived"));
+
+    // Should not show in list
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO (0)"));
+}
+
+#[test]
+fn unarchive_restores_an_archived_card() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Old idea"]).assert().success();
+    kuk_in(&dir).args(["add", "Current"]).assert().success();
+    kuk_in(&dir)
+        .args(["archived"])
+        .assert()
+        .success()
+        .stdout("No archived cards on default.\n");
+    kuk_in(&dir).args(["archive", "KUK-1"]).assert().success();
+
+    let output = kuk_in(&dir).arg("archived").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("  KUK-1  todo  Old idea  archived "),
+        "{stdout}"
+    );
+    assert!(stdout.ends_with("\n1 archived card(s). Restore one with `kuk unarchive <id>`.\n"));
+
+    kuk_in(&dir)
+        .args(["unarchive", "KUK-1"])
+        .assert()
+        .success()
+        .stdout("Unarchived: Old idea → todo\n");
+    kuk_in(&dir)
+        .args(["unarchive", "KUK-1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Old idea is not archived."));
+    kuk_in(&dir).args(["list"]).assert().success().stdout(
+        predicate::str::contains("1. Current").and(predicate::str::contains("2. Old idea")),
+    );
+}
+
+// --- Due dates ---
+
+#[test]
+fn due_dates_show_in_list() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["add", "Renew cert", "--due", "2001-01-01"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["add", "Plan offsite"])
         .assert()
         .success();
+    kuk_in(&dir)
+        .args(["due", "2", "2999-06-30"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Due: Plan offsite on 2999-06-30"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Renew cert !due 2001-01-01"))
+        .stdout(predicate::str::contains("Plan offsite due 2999-06-30"));
+
+    kuk_in(&dir)
+        .args(["due", "1", "--clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared due date: Renew cert"));
+    kuk_in(&dir)
+        .args(["due", "1", "tomorrow"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid date"));
+}
+
+// --- Priority ---
+
+#[test]
+fn priority_sorts_and_filters_list() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Tidy docs"]).assert().success();
+    kuk_in(&dir)
+        .args(["add", "Fix outage", "--priority", "urgent"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["priority", "1", "low"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Priority: Tidy docs is low"));
 
-    // list should show it on sprint-1 (the active board)
+    kuk_in(&dir)
+        .args(["list", "--sort", "priority"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1. Fix outage (urgent)\n  2. Tidy docs (low)",
+        ));
+    kuk_in(&dir)
+        .args(["list", "--sort", "priority:asc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1. Tidy docs (low)\n  2. Fix outage (urgent)",
+        ));
+    kuk_in(&dir)
+        .args(["list", "--sort", "size"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid sort: size"));
+    kuk_in(&dir)
+        .args(["list", "--priority", "high"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fix outage"))
+        .stdout(predicate::str::contains("Tidy docs").not());
+
+    kuk_in(&dir)
+        .args(["priority", "1", "--clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared priority: Tidy docs"));
+    kuk_in(&dir)
+        .args(["priority", "1", "someday"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid priority"));
+}
+
+// --- Snooze ---
+
+#[test]
+fn snooze_hides_card_until_woken() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["add", "Waiting on vendor"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["snooze", "1", "--until", "2999-03-01", "--hoist"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("until 2999-03-01"));
     kuk_in(&dir)
         .arg("list")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Task on sprint"));
+        .stdout(predicate::str::contains("TODO (0)"))
+        .stdout(predicate::str::contains("1 snoozed card hidden"));
 
-    // default board should still be empty
     kuk_in(&dir)
-        .args(["list", "--board", "default"])
+        .args(["snooze", "1", "--wake"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("TODO (0)"));
+        .stdout(predicate::str::contains("Woke: Waiting on vendor"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO (1)"));
+
+    kuk_in(&dir)
+        .args(["snooze", "1", "--until", "2001-01-01"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("in the past"));
 }
 
+// --- Stale ---
+
 #[test]
-fn board_switch_nonexistent_fails() {
+fn stale_escalates_by_aging_rules() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "New feature"]).assert().success();
     kuk_in(&dir)
-        .args(["board", "switch", "nope"])
+        .args(["add", "Forgotten bug", "--label", "bug"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["stale", "--escalate"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Board not found"));
+        .stderr(predicate::str::contains("No aging rules"));
+
+    let path = dir.path().join(".kuk/boards/default.json");
+    let mut board: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    board["cards"][1]["updated_at"] = serde_json::json!("2020-01-01T00:00:00Z");
+    std::fs::write(&path, serde_json::to_string(&board).unwrap()).unwrap();
+    kuk_in(&dir)
+        .arg("stale")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Forgotten bug [todo]"))
+        .stdout(predicate::str::contains("New feature").not());
+
+    let path = dir.path().join(".kuk/config.json");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    config["aging"] = serde_json::json!([{"label": "bug", "after_days": 7}]);
+    std::fs::write(&path, config.to_string()).unwrap();
+    kuk_in(&dir)
+        .args(["stale", "--escalate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Escalated: Forgotten bug (bug,"))
+        .stdout(predicate::str::contains(
+            "1 escalation(s) in the last 14 days",
+        ));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1. Forgotten bug"));
 }
 
+// --- Scan ---
+
 #[test]
-fn board_switch_survives_other_commands() {
+fn scan_tracks_code_comments() {
     let dir = TempDir::new().unwrap();
     kuk_in(&dir).arg("init").assert().success();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "// TODO: validate input\nfn f() {} // FIXME: slow\n",
+    )
+    .unwrap();
+
     kuk_in(&dir)
-        .args(["board", "create", "backlog"])
+        .args(["scan", "--paths", "src"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Found 2 comments in 1 files"))
+        .stdout(predicate::str::contains(
+            "+ TODO: validate input (src/lib.rs:1)",
+        ))
+        .stdout(predicate::str::contains("Dry run"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("validate input").not());
+
+    kuk_in(&dir)
+        .args(["scan", "--paths", "src", "--apply"])
         .assert()
         .success();
     kuk_in(&dir)
-        .args(["board", "switch", "backlog"])
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO: validate input"));
+
+    std::fs::write(dir.path().join("src/lib.rs"), "fn f() {} // FIXME: slow\n").unwrap();
+    let output = kuk_in(&dir)
+        .args(["--json", "scan", "--apply"])
+        .output()
+        .unwrap();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["closed"][0]["text"], "validate input");
+    assert_eq!(result["moved"][0]["line"], 1);
+
+    let output = kuk_in(&dir).args(["--json", "list"]).output().unwrap();
+    let board: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let closed = board["cards"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["title"] == "TODO: validate input")
+        .unwrap();
+    assert_eq!(closed["column"], "done");
+}
+
+// --- Delete ---
+
+#[test]
+fn delete_card() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Delete me"]).assert().success();
+    kuk_in(&dir)
+        .args(["delete", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted"));
+
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO (0)"));
+}
+
+// --- Undo ---
+
+#[test]
+fn undo_and_redo_walk_the_operation_log() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["undo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to undo."));
+    kuk_in(&dir).args(["add", "Keep"]).assert().success();
+    kuk_in(&dir).args(["add", "Drop"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "KUK-1", "--to", "doing"])
         .assert()
         .success();
+    kuk_in(&dir).args(["delete", "KUK-2"]).assert().success();
+    assert!(dir.path().join(".kuk/oplog.json").exists());
 
-    // Run several commands, then verify we're still on backlog
-    kuk_in(&dir).arg("version").assert().success();
-    kuk_in(&dir).arg("doctor").assert().success();
     kuk_in(&dir)
-        .args(["board", "list"])
+        .arg("undo")
         .assert()
         .success()
-        .stdout(predicate::str::contains("* backlog"));
+        .stdout(predicate::str::contains("Undid: kuk delete KUK-2"));
+    kuk_in(&dir)
+        .arg("undo")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Undid: kuk move KUK-1 --to doing"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO (2)"));
+
+    kuk_in(&dir)
+        .arg("redo")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Redid: kuk move KUK-1 --to doing"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DOING (1)").and(predicate::str::contains("Drop")));
+
+    // A new change drops what's left to redo
+    kuk_in(&dir).args(["add", "Third"]).assert().success();
+    kuk_in(&dir)
+        .arg("redo")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to redo."));
+}
+
+// --- Hoist / Demote ---
+
+#[test]
+fn hoist_card() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "First"]).assert().success();
+    kuk_in(&dir).args(["add", "Second"]).assert().success();
+    kuk_in(&dir)
+        .args(["hoist", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Hoisted"));
+}
+
+#[test]
+fn demote_card() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "First"]).assert().success();
+    kuk_in(&dir).args(["add", "Second"]).assert().success();
+    kuk_in(&dir)
+        .args(["demote", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Demoted"));
+}
+
+// --- Label ---
+
+#[test]
+fn label_add_and_remove() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Labelable"]).assert().success();
+    kuk_in(&dir)
+        .args(["label", "1", "add", "bug"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("bug"));
+
+    kuk_in(&dir)
+        .args(["label", "1", "remove", "bug"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn label_remove_nonexistent_fails() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "No label"]).assert().success();
+    kuk_in(&dir)
+        .args(["label", "1", "remove", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Label not found"));
+}
+
+// --- Assign ---
+
+#[test]
+fn assign_user() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Assignable"]).assert().success();
+    kuk_in(&dir)
+        .args(["assign", "1", "leslie"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@leslie"));
+}
+
+// --- Show ---
+
+#[test]
+fn history_lists_card_events() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Fix login"]).assert().success();
+    kuk_in(&dir)
+        .args(["label", "1", "add", "bug"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["assign", "1", "leslie"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "doing"])
+        .assert()
+        .success();
+
+    let output = kuk_in(&dir).args(["history", "1"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let steps: Vec<&str> = stdout
+        .lines()
+        .skip(1)
+        .map(|l| l.split("UTC  ").nth(1).unwrap())
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            "created in todo  via cli",
+            "labeled bug  via cli",
+            "assigned to @leslie  via cli",
+            "moved todo → doing  via cli",
+        ]
+    );
+
+    let output = kuk_in(&dir)
+        .args(["--json", "history", "1"])
+        .output()
+        .unwrap();
+    let events: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(events[3]["event"], "moved");
+    assert_eq!(events[3]["to"], "doing");
+    assert_eq!(events[3]["origin"]["via"], "cli");
+}
+
+#[test]
+fn log_filters_activity_by_origin() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Fix login"]).assert().success();
+    let session = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"clientInfo":{"name":"claude-code"}}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"kuk_add_card","arguments":{"title":"Agent card"}}}"#,
+        r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"kuk_move_card","arguments":{"id":"1","to":"doing"}}}"#,
+    ];
+    kuk_in(&dir)
+        .arg("mcp")
+        .write_stdin(session.join("\n"))
+        .assert()
+        .success();
+
+    let output = kuk_in(&dir)
+        .args(["log", "--origin", "mcp"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout
+        .lines()
+        .map(|l| l.split("UTC  ").nth(1).unwrap())
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            "KUK-1  Fix login: moved todo → doing  via mcp (claude-code)",
+            "KUK-2  Agent card: created in todo  via mcp (claude-code)",
+        ]
+    );
+    kuk_in(&dir)
+        .args(["log", "--origin", "cli"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Fix login: created in todo  via cli",
+        ))
+        .stdout(predicate::str::contains("Agent card").not());
+    kuk_in(&dir)
+        .args(["log", "--origin", "rest"])
+        .assert()
+        .success()
+        .stdout("No activity on default came through rest.\n");
+    kuk_in(&dir)
+        .args(["show", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("UTC  via mcp (claude-code)"))
+        .stdout(predicate::str::contains("Metadata:").not());
+
+    let output = kuk_in(&dir)
+        .args(["log", "--last", "1", "--json"])
+        .output()
+        .unwrap();
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(entries.as_array().unwrap().len(), 1);
+    assert_eq!(entries[0]["short_id"], "KUK-1");
+    assert_eq!(entries[0]["origin"]["client"], "claude-code");
+}
+
+#[test]
+fn comment_on_card() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Fix login"]).assert().success();
+    kuk_in(&dir)
+        .args(["comment", "1", "Reproduced on staging", "--author", "ana"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Commented on Fix login"));
+    kuk_in(&dir)
+        .args(["comment", "1", "Fix is up", "--author", "leslie"])
+        .assert()
+        .success();
+
+    kuk_in(&dir)
+        .args(["show", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Comments:\n  ana, "))
+        .stdout(predicate::str::contains(
+            "    Reproduced on staging\n  leslie, ",
+        ));
+
+    let output = kuk_in(&dir).args(["--json", "show", "1"]).output().unwrap();
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["comments"][1]["body"], "Fix is up");
+
+    kuk_in(&dir)
+        .args(["comment", "1", "  "])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Comment must not be empty"));
+}
+
+#[test]
+fn attach_urls_and_repo_files_to_a_card() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Login page"]).assert().success();
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+    std::fs::write(dir.path().join("docs/login.md"), "# Login").unwrap();
+
+    kuk_in(&dir)
+        .args([
+            "attach",
+            "1",
+            "https://example.com/mock",
+            "--title",
+            "Mockup",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Attached https://example.com/mock to Login page",
+        ));
+    kuk_in(&dir)
+        .args(["attach", "1", "./docs/login.md"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["attach", "1", "docs/missing.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("doesn't exist in the repo"));
+
+    std::fs::remove_file(dir.path().join("docs/login.md")).unwrap();
+    kuk_in(&dir)
+        .args(["show", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Attachments:\n  Mockup: https://example.com/mock\n  docs/login.md (missing)\n",
+        ));
+    kuk_in(&dir)
+        .args(["export", "card", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- [Mockup](https://example.com/mock)",
+        ));
+
+    kuk_in(&dir)
+        .args(["attach", "1", "docs/login.md", "--remove"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Detached docs/login.md"));
+    let output = kuk_in(&dir).args(["--json", "show", "1"]).output().unwrap();
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["attachments"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn show_card_detail() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["add", "Fix login", "--label", "bug", "--assignee", "leslie"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["edit", "1", "--description", "Only behind the proxy"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["priority", "1", "high"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "doing"])
+        .assert()
+        .success();
+
+    kuk_in(&dir)
+        .args(["show", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fix login (high)"))
+        .stdout(predicate::str::contains("Column:    doing (#1)"))
+        .stdout(predicate::str::contains("Labels:    bug"))
+        .stdout(predicate::str::contains("Assignee:  @leslie"))
+        .stdout(predicate::str::contains("  Only behind the proxy"))
+        .stdout(predicate::str::contains("Metadata:\n  transitions:"))
+        .stdout(predicate::str::contains("\"to\": \"doing\""));
+
+    let output = kuk_in(&dir).args(["--json", "show", "1"]).output().unwrap();
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["description"], "Only behind the proxy");
+    assert_eq!(card["metadata"]["transitions"][0]["from"], "todo");
+
+    kuk_in(&dir)
+        .args(["show", "9"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Card not found"));
+}
+
+#[test]
+fn export_card_as_a_github_issue() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["add", "Dark mode", "--label", "ui"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args([
+            "edit",
+            "1",
+            "--description=Follow the OS.\n* [x] Tokens\n* [ ] Toggle",
+        ])
+        .assert()
+        .success();
+
+    kuk_in(&dir)
+        .args(["export", "card", "KUK-1", "--format", "gh-issue"])
+        .assert()
+        .success()
+        .stdout("# Dark mode\n\nFollow the OS.\n- [x] Tokens\n- [ ] Toggle\n\n---\nLabels: `ui`\n");
+    let output = kuk_in(&dir)
+        .args(["--json", "export", "card", "KUK-1"])
+        .output()
+        .unwrap();
+    let issue: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(issue["title"], "Dark mode");
+    assert_eq!(issue["labels"], serde_json::json!(["ui"]));
+}
+
+#[test]
+fn export_board_as_csv_and_markdown() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["add", "Dark mode, at last", "--label", "ui"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "create", "ops"])
+        .assert()
+        .success();
+
+    kuk_in(&dir)
+        .args(["export", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("id,short_id,title,column,"))
+        .stdout(predicate::str::contains(
+            ",KUK-1,\"Dark mode, at last\",todo,0,,,ui,",
+        ));
+    kuk_in(&dir)
+        .args(["export", "--format", "md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "## todo (1)\n\n- **KUK-1** Dark mode, at last `ui`\n",
+        ));
+    kuk_in(&dir)
+        .args(["export", "--format", "md", "--board", "ops"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("# ops\n"));
+    kuk_in(&dir)
+        .arg("export")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--format csv or md"));
+}
+
+// --- Usage ---
+
+#[test]
+fn usage_log_counts_commands_only_when_on() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).arg("list").assert().success();
+    assert!(!dir.path().join(".kuk/usage.json").exists());
+    kuk_in(&dir)
+        .arg("usage")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage logging is off"));
+
+    kuk_in(&dir).args(["usage", "on"]).assert().success();
+    kuk_in(&dir)
+        .args(["add", "Secret title"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "nowhere"])
+        .assert()
+        .failure();
+    kuk_in(&dir)
+        .args(["--json", "board", "list"])
+        .assert()
+        .success();
+
+    let log = std::fs::read_to_string(dir.path().join(".kuk/usage.json")).unwrap();
+    assert!(!log.contains("Secret"));
+    let output = kuk_in(&dir).args(["--json", "usage"]).output().unwrap();
+    let usage: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(usage["usage_log"], true);
+    let commands = usage["commands"].as_object().unwrap();
+    let names: Vec<&str> = commands.keys().map(String::as_str).collect();
+    assert_eq!(names, ["add", "board list", "move"]);
+    assert_eq!(commands["move"]["failures"], 1);
+
+    kuk_in(&dir)
+        .arg("usage")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("board list"));
+    kuk_in(&dir).args(["usage", "clear"]).assert().success();
+    kuk_in(&dir)
+        .arg("usage")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No commands recorded yet."));
+}
+
+// --- Edit ---
+
+#[test]
+fn edit_title_and_description() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["add", "Typo in titel"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args([
+            "edit",
+            "1",
+            "--title",
+            "Typo in title",
+            "--description",
+            "Notes",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Edited Typo in title"));
+
+    let output = kuk_in(&dir)
+        .args(["--json", "edit", "1", "--description", ""])
+        .output()
+        .unwrap();
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["title"], "Typo in title");
+    assert!(card.get("description").is_none_or(|d| d.is_null()));
+
+    kuk_in(&dir)
+        .args(["edit", "1", "--title", " "])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Title must not be empty"));
+}
+
+#[test]
+fn edit_in_editor() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Draft"]).assert().success();
+    let output = kuk_in(&dir)
+        .args(["--json", "edit", "1"])
+        .env(
+            "VISUAL",
+            "printf 'Final title\\n\\nWritten in the editor\\n' >",
+        )
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let card: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(card["title"], "Final title");
+    assert_eq!(card["description"], "Written in the editor");
+}
+
+// --- Board commands ---
+
+#[test]
+fn board_list_shows_active_marker() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("* default"));
+}
+
+#[test]
+fn board_create() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "create", "sprint-1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created board: sprint-1"));
+
+    kuk_in(&dir)
+        .args(["board", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sprint-1"));
+}
+
+#[test]
+fn board_rules_apply_on_move() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "rules", "doing", "--assignee", "@qa-team"])
+        .args(["--label", "needs-qa"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("assign @qa-team"));
+    kuk_in(&dir).args(["add", "Check login"]).assert().success();
+    kuk_in(&dir)
+        .args(["move", "1", "--to", "doing"])
+        .assert()
+        .success();
+
+    let out = kuk_in(&dir).args(["list", "--json"]).output().unwrap();
+    let board: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let card = &board["cards"][0];
+    assert_eq!(card["assignee"], "qa-team");
+    assert_eq!(card["labels"], serde_json::json!(["needs-qa"]));
+
+    kuk_in(&dir)
+        .args(["board", "rules", "doing", "--clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No rules for doing"));
+}
+
+#[test]
+fn board_create_duplicate_fails() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "create", "default"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+}
+
+#[test]
+fn board_switch_persists() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "create", "sprint-1"])
+        .assert()
+        .success();
+
+    // Switch to sprint-1
+    kuk_in(&dir)
+        .args(["board", "switch", "sprint-1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Switched to board: sprint-1"));
+
+    // board list should show * on sprint-1, not default
+    kuk_in(&dir)
+        .args(["board", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("* sprint-1"))
+        .stdout(predicate::str::contains("  default"));
+
+    // add should go to the active board (sprint-1)
+    kuk_in(&dir)
+        .args(["add", "Task on sprint"])
+        .assert()
+        .success();
+
+    // list should show it on sprint-1 (the active board)
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task on sprint"));
+
+    // default board should still be empty
+    kuk_in(&dir)
+        .args(["list", "--board", "default"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO (0)"));
+}
+
+#[test]
+fn board_switch_nonexistent_fails() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "switch", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Board not found"));
+}
+
+#[test]
+fn add_duplicate_respects_config_policy() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["add", "Fix login bug"])
+        .assert()
+        .success();
+
+    // Allowed by default
+    kuk_in(&dir)
+        .args(["add", "fix login bug"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    let path = dir.path().join(".kuk/config.json");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    config["duplicates"] = serde_json::json!("reject");
+    std::fs::write(&path, config.to_string()).unwrap();
+
+    kuk_in(&dir)
+        .args(["add", "Fix login bugs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Similar card already exists:"));
+    kuk_in(&dir)
+        .args(["add", "Fix login bugs", "--force"])
+        .assert()
+        .success();
+
+    config["duplicates"] = serde_json::json!("warn");
+    std::fs::write(&path, config.to_string()).unwrap();
+    kuk_in(&dir)
+        .args(["add", "FIX LOGIN BUG"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Warning: similar card already exists",
+        ));
+}
+
+#[test]
+fn doctor_fix_repairs_order_drift() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "First"]).assert().success();
+    kuk_in(&dir).args(["add", "Second"]).assert().success();
+
+    // Simulate drift: both cards share an order value
+    let path = dir.path().join(".kuk/boards/default.json");
+    let mut board: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    for card in board["cards"].as_array_mut().unwrap() {
+        card["order"] = serde_json::json!(7);
+    }
+    std::fs::write(&path, serde_json::to_string(&board).unwrap()).unwrap();
+
+    kuk_in(&dir)
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 card(s) with duplicate"));
+    kuk_in(&dir)
+        .args(["doctor", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("renumbered 2 card(s)"));
+    kuk_in(&dir)
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("All checks passed."));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1. First"));
+}
+
+#[test]
+fn board_switch_survives_other_commands() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "create", "backlog"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "switch", "backlog"])
+        .assert()
+        .success();
+
+    // Run several commands, then verify we're still on backlog
+    kuk_in(&dir).arg("version").assert().success();
+    kuk_in(&dir).arg("doctor").assert().success();
+    kuk_in(&dir)
+        .args(["board", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("* backlog"));
+}
+
+#[test]
+fn board_layout_converts_boards_to_card_files() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "First"]).assert().success();
+    kuk_in(&dir)
+        .args(["board", "layout"])
+        .assert()
+        .success()
+        .stdout("Boards are stored in the file layout\n");
+    kuk_in(&dir)
+        .args(["board", "layout", "cards"])
+        .assert()
+        .success()
+        .stdout("Converted to the cards layout: default\n");
+    assert!(dir.path().join(".kuk/boards/default/board.json").exists());
+    assert!(!dir.path().join(".kuk/boards/default.json").exists());
+
+    kuk_in(&dir).args(["add", "Second"]).assert().success();
+    let cards = std::fs::read_dir(dir.path().join(".kuk/boards/default/cards")).unwrap();
+    assert_eq!(cards.count(), 2);
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("First"))
+        .stdout(predicate::str::contains("Second"));
+    kuk_in(&dir)
+        .args(["board", "layout", "sideways"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid storage layout"));
+}
+
+#[test]
+fn board_rename_updates_config_and_sprints() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "create", "sprint-1"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "switch", "sprint-1"])
+        .assert()
+        .success();
+    kuk_in(&dir).args(["add", "Carry over"]).assert().success();
+    let kuk_dir = dir.path().join(".kuk");
+    std::fs::write(
+        kuk_dir.join("sprints.json"),
+        r#"[{"name": "S1", "start": "2026-01-05", "end": "2026-01-16", "boards": ["default", "sprint-1"], "status": "active"}]"#,
+    )
+    .unwrap();
+    std::fs::write(
+        kuk_dir.join("pm.json"),
+        r#"{"sync_boards": {"sprint-1": false}}"#,
+    )
+    .unwrap();
+
+    kuk_in(&dir)
+        .args(["board", "rename", "sprint-1", "default"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Board already exists: default"));
+    kuk_in(&dir)
+        .args(["board", "rename", "sprint-1", "week-2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Renamed board: sprint-1 → week-2"));
+
+    kuk_in(&dir)
+        .args(["board", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("* week-2"))
+        .stdout(predicate::str::contains("sprint-1").not());
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Carry over"));
+    let sprints: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(kuk_dir.join("sprints.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        sprints[0]["boards"],
+        serde_json::json!(["default", "week-2"])
+    );
+    let pm: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(kuk_dir.join("pm.json")).unwrap()).unwrap();
+    assert_eq!(pm["sync_boards"], serde_json::json!({"week-2": false}));
+}
+
+#[test]
+fn board_rename_and_delete_keep_kuk_pm_files_and_undo_in_order() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "create", "sprint-1"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "switch", "sprint-1"])
+        .assert()
+        .success();
+    kuk_in(&dir).args(["add", "Carry over"]).assert().success();
+    let kuk_dir = dir.path().join(".kuk");
+    std::fs::write(
+        kuk_dir.join("sprints.json"),
+        r#"[{"name": "S1", "start": "2026-01-05", "end": "2026-01-16", "boards": ["sprint-1"], "status": "active"}]"#,
+    )
+    .unwrap();
+    std::fs::write(
+        kuk_dir.join("pm.json"),
+        r#"{"version": "0.1.0", "auto_branch": false, "sync_boards": {"sprint-1": false, "zeta": true}}"#,
+    )
+    .unwrap();
+
+    kuk_in(&dir)
+        .args(["board", "rename", "sprint-1", "week-2"])
+        .assert()
+        .success();
+    let keys = |file: &str, keys: &[&str]| {
+        let text = std::fs::read_to_string(kuk_dir.join(file)).unwrap();
+        let at: Vec<usize> = keys
+            .iter()
+            .map(|k| text.find(&format!("\"{k}\"")).unwrap())
+            .collect();
+        assert!(at.is_sorted(), "{text}");
+    };
+    keys(
+        "sprints.json",
+        &["name", "start", "end", "boards", "week-2", "status"],
+    );
+    keys(
+        "pm.json",
+        &["version", "auto_branch", "sync_boards", "week-2", "zeta"],
+    );
+
+    // The add logged before the rename is undone on the renamed board
+    kuk_in(&dir)
+        .arg("undo")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Undid: kuk add \"Carry over\""));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Carry over").not());
+
+    // Changes to a deleted board, and its cards' move, can't be undone
+    kuk_in(&dir)
+        .args(["board", "create", "spike"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "switch", "spike"])
+        .assert()
+        .success();
+    kuk_in(&dir).args(["add", "Prototype"]).assert().success();
+    kuk_in(&dir)
+        .args(["board", "delete", "spike", "--to", "week-2", "--yes"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .arg("undo")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to undo."));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Prototype"));
+}
+
+#[test]
+fn board_delete_asks_and_can_move_cards() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["board", "create", "spike"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["board", "switch", "spike"])
+        .assert()
+        .success();
+    kuk_in(&dir).args(["add", "Prototype"]).assert().success();
+
+    kuk_in(&dir)
+        .args(["board", "delete", "spike", "--to", "default"])
+        .write_stdin("n\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Delete board spike and move its 1 cards to default? [y/N]",
+        ))
+        .stderr(predicate::str::contains("Board not deleted: spike"));
+    kuk_in(&dir)
+        .args(["board", "delete", "spike", "--to", "default"])
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Deleted board: spike (1 cards moved to default)",
+        ))
+        .stdout(predicate::str::contains("Switched to board: default"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1. Prototype"));
+
+    kuk_in(&dir)
+        .args(["board", "delete", "default", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Cannot delete default: it is the only board",
+        ));
+}
+
+#[test]
+fn boards_start_from_templates_and_column_lists() {
+    let dir = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    let kuk_home = || {
+        let mut cmd = kuk_in(&dir);
+        cmd.env("HOME", home.path());
+        cmd
+    };
+    kuk_home()
+        .args(["init", "--template", "scrum", "--board-name", "team"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "columns: backlog → todo → doing → review → done",
+        ));
+    kuk_home()
+        .args(["add", "Plan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("backlog"));
+    kuk_home()
+        .args(["board", "create", "ops", "--columns", "new, fixing,shipped"])
+        .assert()
+        .success();
+    kuk_home()
+        .args(["list", "--board", "ops"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FIXING"))
+        .stdout(predicate::str::contains("SHIPPED"));
+
+    kuk_home()
+        .args(["column", "set-wip", "review", "2"])
+        .assert()
+        .success();
+    kuk_home()
+        .args(["board", "save-template", "ours"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Saved board template: ours"));
+    assert!(home.path().join(".kuk/board-templates/ours.json").exists());
+    kuk_home()
+        .args(["board", "create", "next", "--template", "ours"])
+        .assert()
+        .success();
+    let out = kuk_home()
+        .args(["list", "--board", "next", "--json"])
+        .output()
+        .unwrap();
+    let next: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(next["columns"][3]["wip_limit"], 2);
+    assert_eq!(next["cards"].as_array().unwrap().len(), 0);
+
+    kuk_home()
+        .args(["board", "templates"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("bugtracker"))
+        .stdout(predicate::str::contains("ours"));
+    kuk_home()
+        .args(["board", "create", "x", "--template", "waterfall"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Board template not found: waterfall. Available: bugtracker, kanban, ours, scrum",
+        ));
+}
+
+// --- Label registry ---
+
+#[test]
+fn label_def_add_list_and_strict() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["label-def", "add", "bug", "--color", "D73A4A"])
+        .args(["--description", "Something is broken"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["label-def", "add", "bug", "--color", "red"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid color"));
+    kuk_in(&dir)
+        .args(["add", "Crash", "--label", "bug"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["label-def", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "bug  #d73a4a  Something is broken  (1 cards)",
+        ));
+
+    // Piped output stays free of color codes
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Crash [bug]"));
+
+    kuk_in(&dir)
+        .args(["label-def", "strict", "on"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["add", "Typo", "--label", "docs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown label: docs"));
+    kuk_in(&dir)
+        .args(["label", "1", "add", "docs"])
+        .assert()
+        .failure();
+    kuk_in(&dir)
+        .args(["label", "1", "add", "bug"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn label_def_rename_rewrites_cards() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["label-def", "add", "bug"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["add", "Crash", "--label", "bug"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["label-def", "rename", "bug", "defect"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 cards updated"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Crash [defect]"));
+    kuk_in(&dir)
+        .args(["label-def", "delete", "defect", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""deleted":"defect""#));
+    kuk_in(&dir)
+        .args(["label-def", "delete", "defect"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn label_rename_and_merge_rewrite_cards() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    for (title, label) in [("Crash", "bug"), ("Typo", "defect"), ("Both", "bug")] {
+        kuk_in(&dir)
+            .args(["add", title, "--label", label])
+            .assert()
+            .success();
+    }
+    kuk_in(&dir)
+        .args(["label", "3", "add", "defect"])
+        .assert()
+        .success();
+
+    kuk_in(&dir)
+        .args(["label", "rename", "bug", "defect"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("kuk label merge bug defect"));
+    kuk_in(&dir)
+        .args(["label", "merge", "bug", "defect", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""cards":2"#));
+    kuk_in(&dir)
+        .args(["label", "rename", "defect", "Bug"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 cards updated"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Both [Bug]"))
+        .stdout(predicate::str::contains("defect").not());
+    kuk_in(&dir)
+        .args(["label", "merge", "nope", "Bug"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Label not found: nope"));
+}
+
+// --- Epics ---
+
+#[test]
+fn epics_roll_up_card_progress() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["epic", "create", "Auth overhaul"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Created epic: Auth overhaul → todo (KUK-1)",
+        ));
+    for title in ["Login page", "Logout", "SSO"] {
+        kuk_in(&dir).args(["add", title]).assert().success();
+    }
+    kuk_in(&dir)
+        .args(["epic", "add", "KUK-1", "KUK-2", "KUK-3", "KUK-4"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Added 3 card(s) to Auth overhaul (0/3 done)",
+        ));
+    kuk_in(&dir)
+        .args(["epic", "add", "KUK-2", "KUK-3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not an epic: Login page"));
+    kuk_in(&dir)
+        .args(["move", "KUK-2", "--to", "done"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["epic", "remove", "KUK-4"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed 1 card(s)"));
+
+    kuk_in(&dir)
+        .args(["list", "--epics"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "KUK-1 Auth overhaul [todo]  1/2 done (50%)",
+        ))
+        .stdout(predicate::str::contains("    [x] KUK-2 Login page [done]"))
+        .stdout(predicate::str::contains("    [ ] KUK-3 Logout [todo]"))
+        .stdout(predicate::str::contains("SSO").not());
+    kuk_in(&dir)
+        .args(["show", "KUK-3"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Parent:    KUK-1 Auth overhaul"));
+
+    let output = kuk_in(&dir)
+        .args(["list", "--epics", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["progress"]["done"], 1);
+    assert_eq!(json[0]["cards"][0]["short_id"], "KUK-3");
+}
+
+// --- Columns ---
+
+#[test]
+fn column_commands_keep_cards_consistent() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["column", "add", "review", "--at", "3", "--wip", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Added column: review (position 3 of 4)",
+        ));
+    kuk_in(&dir)
+        .args(["column", "add", "review"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Column already exists: review"));
+    kuk_in(&dir)
+        .args(["add", "Audit login", "--to", "review"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["column", "rename", "review", "qa"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Renamed column: review → qa (1 cards updated)",
+        ));
+    kuk_in(&dir)
+        .args(["column", "move", "qa", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Moved column: qa to position 1 (qa → todo → doing → done)",
+        ));
+    kuk_in(&dir)
+        .args(["column", "set-wip", "qa", "0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed WIP limit from qa"));
+    kuk_in(&dir)
+        .args(["column", "queue", "qa", "on"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("qa is now a queue"));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("── QA (1) queue──"));
+    kuk_in(&dir)
+        .args(["column", "queue", "qa", "off"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("qa is no longer a queue"));
+    kuk_in(&dir)
+        .args(["column", "delete", "qa"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("qa holds 1 card(s)"));
+    kuk_in(&dir)
+        .args(["column", "delete", "qa", "--to", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("nope"));
+    kuk_in(&dir)
+        .args(["column", "delete", "qa", "--to", "doing"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Deleted column: qa (1 cards moved to doing)",
+        ));
+
+    let path = dir.path().join(".kuk/boards/default.json");
+    let board: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let columns: Vec<&str> = board["columns"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(columns, ["todo", "doing", "done"]);
+    assert_eq!(board["cards"][0]["column"], "doing");
+}
+
+#[test]
+fn wip_limit_blocks_add_and_move_without_force() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["column", "set-wip", "doing", "1"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["add", "Login", "--to", "doing"])
+        .assert()
+        .success();
+    kuk_in(&dir).args(["add", "Logout"]).assert().success();
+
+    kuk_in(&dir)
+        .args(["add", "Signup", "--to", "doing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "WIP limit reached: doing is at its limit of 1 cards. Use --force to add anyway.",
+        ));
+    kuk_in(&dir)
+        .args(["move", "KUK-2", "--to", "doing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Use --force to move anyway."));
+    // Moving within the full column is not adding to it
+    kuk_in(&dir)
+        .args(["move", "KUK-1", "--to", "doing"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["move", "KUK-2", "--to", "doing", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Moved: Logout → doing"));
+}
+
+// --- Inbox ---
+
+#[test]
+fn capture_without_daemon_files_into_this_repo() {
+    let dir = TempDir::new().unwrap();
+    let socket = dir.path().join("none.sock");
+    kuk_in(&dir)
+        .args(["capture", "--socket"])
+        .arg(&socket)
+        .arg("Stray thought")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No capture daemon"));
+
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["capture", "--socket"])
+        .arg(&socket)
+        .write_stdin("fix the flaky test\n\nbump deps\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Captured: fix the flaky test → default/inbox (KUK-1)",
+        ))
+        .stdout(predicate::str::contains("Captured: bump deps"));
+    kuk_in(&dir)
+        .arg("inbox")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Inbox (2)"))
+        .stdout(predicate::str::contains("[capture, "));
+}
+
+/// Run git in `dir`, with this build's kuk first on the PATH for the merge
+/// driver.
+fn git(dir: &TempDir, args: &[&str]) -> std::process::Output {
+    let bin = std::path::Path::new(env!("CARGO_BIN_EXE_kuk"))
+        .parent()
+        .unwrap();
+    let path = std::env::join_paths(std::iter::once(bin.to_path_buf()).chain(
+        std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+    ))
+    .unwrap();
+    std::process::Command::new("git")
+        .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+        .args(args)
+        .current_dir(dir.path())
+        .env("PATH", path)
+        .output()
+        .unwrap()
+}
+
+/// Rename card 1 on main and label it on `side`, each adding a card too:
+/// both touch the card's `updated_at` line, which git can't merge.
+fn diverge(dir: &TempDir, side: &str) {
+    git(dir, &["checkout", "-qb", side]);
+    kuk_in(dir)
+        .args(["label", "1", "add", side])
+        .assert()
+        .success();
+    kuk_in(dir)
+        .args(["add", &format!("From {side}")])
+        .assert()
+        .success();
+    git(dir, &["commit", "-qam", side]);
+    git(dir, &["checkout", "-q", "main"]);
+    kuk_in(dir)
+        .args(["edit", "1", "--title", &format!("Renamed before {side}")])
+        .assert()
+        .success();
+    git(dir, &["commit", "-qam", "main"]);
+}
+
+#[test]
+fn auto_commit_commits_each_change_to_kuk() {
+    let dir = TempDir::new().unwrap();
+    git(&dir, &["init", "-q", "-b", "main"]);
+    kuk_in(&dir).arg("init").assert().success();
+    let config = dir.path().join(".kuk/config.json");
+    let mut json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&config).unwrap()).unwrap();
+    json["auto_commit"] = true.into();
+    std::fs::write(&config, json.to_string()).unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "not kuk's").unwrap();
+
+    let kuk_git = |args: &[&str]| {
+        kuk_in(&dir)
+            .args(args)
+            .env("GIT_AUTHOR_NAME", "t")
+            .env("GIT_AUTHOR_EMAIL", "t@t")
+            .env("GIT_COMMITTER_NAME", "t")
+            .env("GIT_COMMITTER_EMAIL", "t@t")
+            .assert()
+            .success();
+    };
+    kuk_git(&["add", "Ship it"]);
+    kuk_git(&["move", "1", "--to", "done"]);
+
+    let log = git(&dir, &["log", "--format=%s|%b"]);
+    let log = String::from_utf8_lossy(&log.stdout);
+    let subjects: Vec<&str> = log
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.split('|').next().unwrap())
+        .collect();
+    assert_eq!(subjects, ["kuk: move KUK-1 to done", "kuk: add KUK-1"]);
+    assert!(log.contains("|kuk move 1 --to done"));
+    let status = git(&dir, &["status", "--porcelain"]);
+    assert_eq!(
+        String::from_utf8_lossy(&status.stdout),
+        "?? .kuk/.gitignore\n?? .kuk/config.json\n?? notes.txt\n"
+    );
+
+    // Reads commit nothing, even with other changes under .kuk/ waiting
+    std::fs::write(dir.path().join(".kuk/pm.json"), "{}").unwrap();
+    kuk_git(&["list"]);
+    kuk_git(&["show", "1"]);
+    let count = git(&dir, &["rev-list", "--count", "HEAD"]);
+    assert_eq!(String::from_utf8_lossy(&count.stdout).trim(), "2");
+
+    // A change commits only the files it touched
+    kuk_git(&["add", "Next"]);
+    let files = git(&dir, &["show", "--name-only", "--format=", "HEAD"]);
+    assert_eq!(
+        String::from_utf8_lossy(&files.stdout),
+        ".kuk/boards/default.json\n.kuk/oplog.json\n"
+    );
+}
+
+#[test]
+fn resolve_merges_conflicted_boards_and_installs_the_driver() {
+    let dir = TempDir::new().unwrap();
+    git(&dir, &["init", "-q", "-b", "main"]);
+    std::fs::write(dir.path().join(".gitignore"), ".kuk/oplog.json\n").unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir).args(["add", "Shared"]).assert().success();
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-qm", "base"]);
+
+    diverge(&dir, "one");
+    assert!(!git(&dir, &["merge", "-q", "one"]).status.success());
+    kuk_in(&dir)
+        .arg("resolve")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Resolved .kuk/boards/default.json",
+        ));
+    assert!(git(&dir, &["commit", "-qm", "merge"]).status.success());
+    kuk_in(&dir)
+        .args(["show", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Renamed before one"))
+        .stdout(predicate::str::contains("one"));
+    kuk_in(&dir)
+        .arg("resolve")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No conflicted board files"));
+
+    kuk_in(&dir)
+        .args(["resolve", "--install"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added to .gitattributes"));
+    kuk_in(&dir)
+        .args(["resolve", "--install"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".gitattributes").not());
+    let attributes = std::fs::read_to_string(dir.path().join(".gitattributes")).unwrap();
+    assert_eq!(attributes, ".kuk/boards/**/*.json merge=kuk\n");
+    git(&dir, &["add", ".gitattributes"]);
+    git(&dir, &["commit", "-qm", "driver"]);
+
+    // With the driver installed, git merges the board itself
+    diverge(&dir, "two");
+    assert!(git(&dir, &["merge", "-q", "two"]).status.success());
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Renamed before two"))
+        .stdout(predicate::str::contains("From one"))
+        .stdout(predicate::str::contains("From two"));
+}
+
+#[test]
+fn inbox_add_promote_and_discard() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["inbox"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Inbox is empty."));
+    kuk_in(&dir)
+        .args(["inbox", "add", "Export is broken", "--source", "email"])
+        .args(["--from", "ana@example.com"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Received: Export is broken → inbox",
+        ));
+    kuk_in(&dir)
+        .args(["inbox", "add", "Spam"])
+        .assert()
+        .success();
+    kuk_in(&dir)
+        .args(["inbox", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Inbox (2)"))
+        .stdout(predicate::str::contains(
+            "1. Export is broken  [email from ana@example.com",
+        ));
+
+    kuk_in(&dir)
+        .args(["inbox", "promote", "1", "--to", "inbox"])
+        .assert()
+        .failure();
+    kuk_in(&dir)
+        .args(["inbox", "promote", "1", "--label", "bug"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Promoted: Export is broken → todo",
+        ));
+    kuk_in(&dir)
+        .args(["inbox", "discard", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Discarded: Spam"));
+    kuk_in(&dir)
+        .args(["inbox", "discard", "1"])
+        .assert()
+        .failure();
+    kuk_in(&dir)
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Export is broken [bug]"));
+}
+
+#[test]
+fn inbox_promote_and_discard_take_short_ids() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    for title in ["Export is broken", "Spam"] {
+        kuk_in(&dir)
+            .args(["inbox", "add", title])
+            .assert()
+            .success();
+    }
+    kuk_in(&dir)
+        .args(["inbox", "discard", "KUK-2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Discarded: Spam"));
+    kuk_in(&dir)
+        .args(["inbox", "promote", "kuk-1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Promoted: Export is broken → todo",
+        ));
+    kuk_in(&dir)
+        .args(["inbox", "discard", "KUK-1"])
+        .assert()
+        .failure();
+}
+
+// --- Logging ---
+
+#[test]
+fn verbose_logs_storage_to_stderr() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args(["list", "--json", "-vv"])
+        .env_remove("KUK_LOG")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("{"))
+        .stderr(predicate::str::contains("load_board{name=\"default\"}"))
+        .stderr(predicate::str::contains("time.busy"));
+    kuk_in(&dir)
+        .arg("list")
+        .env_remove("KUK_LOG")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+    kuk_in(&dir)
+        .arg("list")
+        .env("KUK_LOG", "kuk::storage=debug")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("load_board_summary"));
+}
+
+// --- Errors ---
+
+#[test]
+fn corrupt_board_reports_cause_and_hint() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    std::fs::write(
+        dir.path().join(".kuk/boards/default.json"),
+        "{\n  \"name\": \"default\",\n  oops\n}",
+    )
+    .unwrap();
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "default.json is invalid JSON at line 3",
+        ))
+        .stderr(predicate::str::contains("caused by:"))
+        .stderr(predicate::str::contains("hint:"));
+}
+
+// --- Demo ---
+
+#[test]
+fn demo_writes_sample_project() {
+    let dir = TempDir::new().unwrap();
+    let demo = dir.path().join("demo");
+    kuk_in(&dir)
+        .args(["demo", "--no-tui", "--dir"])
+        .arg(&demo)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Demo project written to"));
+    kuk()
+        .arg("--repo")
+        .arg(&demo)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Login redirect loop on Safari"))
+        .stdout(predicate::str::contains("REVIEW"));
+    assert!(demo.join(".kuk/sprints.json").exists());
+    kuk_in(&dir)
+        .args(["demo", "--no-tui", "--dir"])
+        .arg(&demo)
+        .assert()
+        .failure();
+}
+
+// --- RPC ---
+
+#[test]
+fn rpc_answers_requests_on_stdio() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .arg("rpc")
+        .write_stdin(concat!(
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "add", "params": {"title": "From the editor"}}"#,
+            "\n",
+            r#"[{"id": 2, "method": "cards"}, {"id": 3, "method": "boards"}]"#,
+            "\n",
+        ))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""id":1,"#))
+        .stdout(predicate::str::contains(
+            r#"{"jsonrpc":"2.0","method":"changed","params":{"board":"default","source":"rpc"}}"#,
+        ))
+        .stdout(predicate::str::contains(r#""result":["default"]"#));
+    kuk_in(&dir)
+        .arg("list")
+        .assert()
+        .stdout(predicate::str::contains("From the editor"));
+}
+
+// --- Dev ---
+
+#[test]
+fn dev_generate_writes_boards() {
+    let dir = TempDir::new().unwrap();
+    kuk_in(&dir).arg("init").assert().success();
+    kuk_in(&dir)
+        .args([
+            "dev", "generate", "--cards", "50", "--boards", "2", "--json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""boards":["gen-1","gen-2"]"#));
+    kuk_in(&dir)
+        .args(["list", "--board", "gen-2", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#25"));
+}
+
+#[test]
+fn dev_is_hidden_from_help() {
+    kuk()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Developer utilities").not());
 }
 
 // --- Projects ---